@@ -3,7 +3,7 @@ use anyhow::Result;
 use braintrust::{BraintrustClient, TraceBuilder};
 use litellm::{
     AgentMessage, ChatCompletionRequest, DeltaToolCall, FunctionCall, LiteLLMClient,
-    MessageProgress, Metadata, Tool, ToolCall, ToolChoice,
+    MessageProgress, Metadata, StreamOptions, Tool, ToolCall, ToolChoice,
 };
 use once_cell::sync::Lazy;
 use serde_json::Value;
@@ -21,6 +21,10 @@ use crate::models::AgentThread;
 use crate::agents::modes::ModeConfiguration;
 
 // Global BraintrustClient instance
+/// Id of the assistant message sent when processing is interrupted by
+/// [`Agent::shutdown`]
+pub const SHUTDOWN_MESSAGE_ID: &str = "shutdown_message";
+
 static BRAINTRUST_CLIENT: Lazy<Option<Arc<BraintrustClient>>> = Lazy::new(|| {
     match (
         std::env::var("BRAINTRUST_API_KEY"),
@@ -70,6 +74,13 @@ impl MessageBuffer {
         }
     }
 
+    /// Drops the partial response before a stream is restarted
+    fn reset(&mut self) {
+        self.content.clear();
+        self.tool_calls.clear();
+        self.message_id = None;
+    }
+
     fn should_flush(&self) -> bool {
         self.last_flush.elapsed() >= Duration::from_millis(50)
     }
@@ -220,6 +231,12 @@ impl Agent {
         }
     }
 
+    /// Lets LLM calls fail over to the configured fallback models found in `approved`
+    pub fn with_approved_fallbacks(mut self, approved: &[String]) -> Self {
+        self.llm_client = self.llm_client.with_approved_fallbacks(approved);
+        self
+    }
+
     /// Create a new Agent that shares state and stream with an existing agent
     pub fn from_existing(
         existing_agent: &Agent,
@@ -229,7 +246,9 @@ impl Agent {
         let llm_api_key = env::var("LLM_API_KEY").ok(); // Use ok() instead of expect
         let llm_base_url = env::var("LLM_BASE_URL").ok(); // Use ok() instead of expect
 
-        let llm_client = LiteLLMClient::new(llm_api_key, llm_base_url);
+        // Keep the existing agent's fallbacks, which are limited to its organization
+        let llm_client = LiteLLMClient::new(llm_api_key, llm_base_url)
+            .with_fallback_models(existing_agent.llm_client.fallback_models().to_vec());
 
         Self {
             llm_client,
@@ -503,7 +522,7 @@ impl Agent {
                     // Use the clone created before select!
                     let agent_clone_shutdown = agent_clone_for_post_process.clone(); // Can clone the clone
                     let shutdown_msg = AgentMessage::assistant(
-                        Some("shutdown_message".to_string()),
+                        Some(SHUTDOWN_MESSAGE_ID.to_string()),
                         Some("Processing interrupted due to shutdown signal".to_string()),
                         None,
                         MessageProgress::Complete,
@@ -670,6 +689,16 @@ impl Agent {
             .filter(|msg| matches!(msg, AgentMessage::User { .. }))
             .cloned();
 
+        // The chat message being answered, if the caller set one, for usage attribution
+        let message_id = agent
+            .get_state_value("message_id")
+            .await
+            .and_then(|value| value.as_str().map(str::to_string));
+        let organization_id = agent
+            .get_state_value("organization_id")
+            .await
+            .and_then(|value| value.as_str().map(str::to_string));
+
         // Create the tool-enabled request
         let request = ChatCompletionRequest {
             model: mode_config.model, // Use the model from mode config
@@ -677,123 +706,159 @@ impl Agent {
             tools: if tools.is_empty() { None } else { Some(tools) },
             tool_choice: Some(ToolChoice::Required), // Or adjust based on mode?
             stream: Some(true), // Enable streaming
+            stream_options: Some(StreamOptions {
+                include_usage: true,
+            }),
             metadata: Some(Metadata {
                 generation_name: "agent".to_string(),
                 user_id: thread_ref.user_id.to_string(),
                 session_id: thread_ref.id.to_string(),
                 trace_id: Uuid::new_v4().to_string(),
+                message_id,
+                organization_id,
             }),
             reasoning_effort: Some("medium".to_string()),
             ..Default::default()
         };
 
-        // Get the streaming response from the LLM
-        let mut stream_rx = match agent
-            .llm_client
-            .stream_chat_completion(request.clone())
-            .await
-        {
-            Ok(rx) => rx,
-            Err(e) => {
-                // --- Added Error Handling ---
-                let error_message = format!("Error starting LLM stream: {:?}", e);
-                tracing::error!(agent_name = %agent.name, chat_id = %agent.session_id, user_id = %agent.user_id, "{}", error_message);
-                // Log error in span
-                if let Some(parent_span) = parent_span.clone() {
-                    if let Some(client) = &*BRAINTRUST_CLIENT {
-                        let error_span = parent_span.with_output(serde_json::json!({
-                            "error": format!("Error starting stream: {:?}", e)
-                        }));
-
-                        // Log span non-blockingly (client handles the background processing)
-                        if let Err(log_err) = client.log_span(error_span).await {
-                            error!("Failed to log error span: {}", log_err);
-                        }
-                    }
-                }
-                // --- End Added Error Handling ---
-                return Err(anyhow::anyhow!(error_message)); // Return immediately
-            }
-        };
-
         // We store the parent span to use for creating individual tool spans
         // This avoids creating a general assistant span that would never be completed
         let parent_for_tool_spans = parent_span.clone();
 
         // Process the streaming chunks
         let mut buffer = MessageBuffer::new();
-        let mut _is_complete = false;
+        let retry_policy = agent.llm_client.retry_policy().clone();
+        let mut stream_attempt = 0;
+
+        // A stream that fails before anything reached the client is requested
+        // again from the start. Once part of the response was sent, a restart
+        // would replace tool calls the client has already seen, so the error is
+        // returned instead.
+        loop {
+            // Get the streaming response from the LLM
+            let mut stream_rx = match agent
+                .llm_client
+                .stream_chat_completion(request.clone())
+                .await
+            {
+                Ok(rx) => rx,
+                Err(e) => {
+                    // --- Added Error Handling ---
+                    let error_message = format!("Error starting LLM stream: {:?}", e);
+                    tracing::error!(agent_name = %agent.name, chat_id = %agent.session_id, user_id = %agent.user_id, "{}", error_message);
+                    // Log error in span
+                    if let Some(parent_span) = parent_span.clone() {
+                        if let Some(client) = &*BRAINTRUST_CLIENT {
+                            let error_span = parent_span.with_output(serde_json::json!({
+                                "error": format!("Error starting stream: {:?}", e)
+                            }));
 
-        while let Some(chunk_result) = stream_rx.recv().await {
-            match chunk_result {
-                Ok(chunk) => {
-                    if chunk.choices.is_empty() {
-                        continue;
+                            // Log span non-blockingly (client handles the background processing)
+                            if let Err(log_err) = client.log_span(error_span).await {
+                                error!("Failed to log error span: {}", log_err);
+                            }
+                        }
                     }
+                    // --- End Added Error Handling ---
+                    return Err(anyhow::anyhow!(error_message)); // Return immediately
+                }
+            };
 
-                    buffer.message_id = Some(chunk.id.clone());
-                    let delta = &chunk.choices[0].delta;
+            let mut stream_error = None;
 
-                    // Accumulate content if present
-                    if let Some(content) = &delta.content {
-                        buffer.content.push_str(content);
-                    }
+            while let Some(chunk_result) = stream_rx.recv().await {
+                match chunk_result {
+                    Ok(chunk) => {
+                        if chunk.choices.is_empty() {
+                            continue;
+                        }
+
+                        buffer.message_id = Some(chunk.id.clone());
+                        let delta = &chunk.choices[0].delta;
 
-                    // Process tool calls if present
-                    if let Some(tool_calls) = &delta.tool_calls {
-                        for tool_call in tool_calls {
-                            let id = tool_call.id.clone().unwrap_or_else(|| {
-                                buffer
-                                    .tool_calls
-                                    .keys()
-                                    .next()
-                                    .cloned()
-                                    .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
-                            });
-
-                            // Get or create the pending tool call
-                            let pending_call = buffer.tool_calls.entry(id.clone()).or_default();
-
-                            // Update the pending call with the delta
-                            pending_call.update_from_delta(tool_call);
+                        // Accumulate content if present
+                        if let Some(content) = &delta.content {
+                            buffer.content.push_str(content);
                         }
-                    }
 
-                    // Check if we should flush the buffer
-                    if buffer.should_flush() {
-                        buffer.flush(&agent).await?;
-                    }
+                        // Process tool calls if present
+                        if let Some(tool_calls) = &delta.tool_calls {
+                            for tool_call in tool_calls {
+                                let id = tool_call.id.clone().unwrap_or_else(|| {
+                                    buffer
+                                        .tool_calls
+                                        .keys()
+                                        .next()
+                                        .cloned()
+                                        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+                                });
+
+                                // Get or create the pending tool call
+                                let pending_call = buffer.tool_calls.entry(id.clone()).or_default();
+
+                                // Update the pending call with the delta
+                                pending_call.update_from_delta(tool_call);
+                            }
+                        }
 
-                    // Check if this is the final chunk
-                    if chunk.choices[0].finish_reason.is_some() {
-                        _is_complete = true;
+                        // Check if we should flush the buffer
+                        if buffer.should_flush() {
+                            buffer.flush(&agent).await?;
+                        }
+                    }
+                    Err(e) => {
+                        stream_error = Some(e);
+                        break;
                     }
                 }
-                Err(e) => {
-                    // --- Added Error Handling ---
-                    let error_message = format!("Error receiving chunk from LLM stream: {:?}", e);
-                    tracing::error!(agent_name = %agent.name, chat_id = %agent.session_id, user_id = %agent.user_id, "{}", error_message);
-                    // Log error in parent span
-                    if let Some(parent) = &parent_for_tool_spans {
-                        if let Some(client) = &*BRAINTRUST_CLIENT {
-                            // Create error info
-                            let error_info = serde_json::json!({
-                                "error": format!("Error in stream: {:?}", e)
-                            });
+            }
 
-                            // Log error as output to parent span
-                            let error_span = parent.clone().with_output(error_info);
+            let Some(e) = stream_error else {
+                break;
+            };
 
-                            // Log span non-blockingly (client handles the background processing)
-                            if let Err(log_err) = client.log_span(error_span).await {
-                                error!("Failed to log stream error span: {}", log_err);
-                            }
-                        }
+            if !buffer.first_message_sent
+                && litellm::is_retryable(&e)
+                && stream_attempt < retry_policy.max_retries
+            {
+                stream_attempt += 1;
+                let delay = retry_policy.delay_for(stream_attempt, &e);
+                tracing::warn!(
+                    agent_name = %agent.name,
+                    chat_id = %agent.session_id,
+                    "LLM stream interrupted (attempt {} of {}), resuming in {:?}: {:?}",
+                    stream_attempt,
+                    retry_policy.max_retries + 1,
+                    delay,
+                    e
+                );
+                buffer.reset();
+                tokio::time::sleep(delay).await;
+                continue;
+            }
+
+            // --- Added Error Handling ---
+            let error_message = format!("Error receiving chunk from LLM stream: {:?}", e);
+            tracing::error!(agent_name = %agent.name, chat_id = %agent.session_id, user_id = %agent.user_id, "{}", error_message);
+            // Log error in parent span
+            if let Some(parent) = &parent_for_tool_spans {
+                if let Some(client) = &*BRAINTRUST_CLIENT {
+                    // Create error info
+                    let error_info = serde_json::json!({
+                        "error": format!("Error in stream: {:?}", e)
+                    });
+
+                    // Log error as output to parent span
+                    let error_span = parent.clone().with_output(error_info);
+
+                    // Log span non-blockingly (client handles the background processing)
+                    if let Err(log_err) = client.log_span(error_span).await {
+                        error!("Failed to log stream error span: {}", log_err);
                     }
-                    // --- End Added Error Handling ---
-                    return Err(anyhow::anyhow!(error_message)); // Return immediately
                 }
             }
+            // --- End Added Error Handling ---
+            return Err(anyhow::anyhow!(error_message)); // Return immediately
         }
 
         // Flush any remaining buffered content or tool calls before creating final message
@@ -0,0 +1,195 @@
+use anyhow::Result;
+use database::enums::ModelPurpose;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::agent::ModeProvider;
+use crate::agents::modes::ModeConfiguration;
+use crate::tools::cli_tools::{
+    BashTool, CliSandbox, EditTool, GlobTool, GrepTool, LSTool, ReplaceTool, ViewTool,
+};
+use crate::tools::{IntoToolCallExecutor, ToolExecutor};
+use crate::{Agent, AgentError, AgentExt, AgentThread};
+
+use litellm::AgentMessage;
+
+// Provides the CLI prompt and tools; the CLI agent has a single mode
+struct CliModeProvider {
+    sandbox: Arc<CliSandbox>,
+}
+
+#[async_trait::async_trait]
+impl ModeProvider for CliModeProvider {
+    async fn get_configuration_for_state(
+        &self,
+        state: &HashMap<String, Value>,
+    ) -> Result<ModeConfiguration> {
+        let cwd = state
+            .get("cwd")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| self.sandbox.root().display().to_string());
+
+        // Every tool shares the sandbox, so they all stay inside the working
+        // directory and destructive commands go through the same confirmation
+        let sandbox = Arc::clone(&self.sandbox);
+        let tool_loader: Box<
+            dyn Fn(&Arc<Agent>) -> Pin<Box<dyn Future<Output = Result<()>> + Send>> + Send + Sync,
+        > = Box::new(move |agent_arc: &Arc<Agent>| {
+            let agent_clone = Arc::clone(agent_arc);
+            let sandbox = Arc::clone(&sandbox);
+            Box::pin(async move {
+                agent_clone.clear_tools().await;
+
+                let bash_tool = BashTool::new(agent_clone.clone(), sandbox.clone());
+                let glob_tool = GlobTool::new(agent_clone.clone(), sandbox.clone());
+                let grep_tool = GrepTool::new(agent_clone.clone(), sandbox.clone());
+                let ls_tool = LSTool::new(agent_clone.clone(), sandbox.clone());
+                let view_tool = ViewTool::new(agent_clone.clone(), sandbox.clone());
+                let edit_tool = EditTool::new(agent_clone.clone(), sandbox.clone());
+                let replace_tool = ReplaceTool::new(agent_clone.clone(), sandbox);
+
+                let condition = Some(|_state: &HashMap<String, Value>| -> bool { true });
+
+                agent_clone
+                    .add_tool(
+                        bash_tool.get_name(),
+                        bash_tool.into_tool_call_executor(),
+                        condition.clone(),
+                    )
+                    .await;
+                agent_clone
+                    .add_tool(
+                        glob_tool.get_name(),
+                        glob_tool.into_tool_call_executor(),
+                        condition.clone(),
+                    )
+                    .await;
+                agent_clone
+                    .add_tool(
+                        grep_tool.get_name(),
+                        grep_tool.into_tool_call_executor(),
+                        condition.clone(),
+                    )
+                    .await;
+                agent_clone
+                    .add_tool(
+                        ls_tool.get_name(),
+                        ls_tool.into_tool_call_executor(),
+                        condition.clone(),
+                    )
+                    .await;
+                agent_clone
+                    .add_tool(
+                        view_tool.get_name(),
+                        view_tool.into_tool_call_executor(),
+                        condition.clone(),
+                    )
+                    .await;
+                agent_clone
+                    .add_tool(
+                        edit_tool.get_name(),
+                        edit_tool.into_tool_call_executor(),
+                        condition.clone(),
+                    )
+                    .await;
+                agent_clone
+                    .add_tool(
+                        replace_tool.get_name(),
+                        replace_tool.into_tool_call_executor(),
+                        condition,
+                    )
+                    .await;
+
+                Ok(())
+            })
+        });
+
+        Ok(ModeConfiguration {
+            prompt: CLI_PROMPT.replace("{CWD}", &cwd),
+            model: ModelPurpose::Analysis.default_model().to_string(),
+            tool_loader,
+            terminating_tools: vec![],
+        })
+    }
+}
+
+/// The agent behind `buster chat`, working on the files of a local project
+///
+/// All of its tools are confined by one [`CliSandbox`].
+pub struct BusterCliAgent {
+    agent: Arc<Agent>,
+}
+
+impl AgentExt for BusterCliAgent {
+    fn get_agent_arc(&self) -> &Arc<Agent> {
+        &self.agent
+    }
+}
+
+impl BusterCliAgent {
+    pub async fn new(
+        user_id: Uuid,
+        session_id: Uuid,
+        api_key: Option<String>,
+        base_url: Option<String>,
+        cwd: Option<String>,
+        sandbox: Arc<CliSandbox>,
+    ) -> Result<Self> {
+        let mode_provider = Arc::new(CliModeProvider { sandbox });
+
+        let agent = Arc::new(Agent::new(
+            ModelPurpose::Analysis.default_model().to_string(),
+            user_id,
+            session_id,
+            "buster_cli_agent".to_string(),
+            api_key,
+            base_url,
+            mode_provider,
+        ));
+
+        if let Some(cwd) = cwd {
+            agent
+                .set_state_value("cwd".to_string(), Value::String(cwd))
+                .await;
+        }
+
+        Ok(Self { agent })
+    }
+
+    /// Processes the thread, streaming the agent's messages
+    ///
+    /// `cwd` replaces the working directory shown to the model; the tools
+    /// stay confined to the sandbox either way.
+    pub async fn run(
+        &self,
+        thread: &mut AgentThread,
+        cwd: Option<String>,
+    ) -> Result<broadcast::Receiver<Result<AgentMessage, AgentError>>> {
+        if let Some(cwd) = cwd {
+            self.agent
+                .set_state_value("cwd".to_string(), Value::String(cwd))
+                .await;
+        }
+
+        self.stream_process_thread(thread).await
+    }
+
+    /// Shutdown the agent and all its tools
+    pub async fn shutdown(&self) -> Result<()> {
+        self.agent.shutdown().await
+    }
+}
+
+const CLI_PROMPT: &str = r##"
+You are Buster, an expert analytics and data engineer working in the user's local project at {CWD}.
+
+Use the tools to explore and change the project: list, search and read files before editing them, and prefer the file tools over shell commands for reading and editing. Every path must stay inside the project directory. Commands that delete or overwrite files, or that rewrite or publish git history, ask the user for confirmation first; if one is declined, don't retry it, explain what you wanted to do instead.
+
+Keep answers short and reference files by their path relative to the project.
+"##;
@@ -1,5 +1,12 @@
 use anyhow::Result;
 use chrono::Local;
+use database::{
+    enums::{ModelPurpose, TermKind},
+    helpers::{
+        custom_tools::get_user_custom_tools, model_configs::get_user_models,
+        terms::get_user_terms,
+    },
+};
 use dataset_security::get_permissioned_datasets;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -65,7 +72,7 @@ impl ModeProvider for BusterModeProvider {
         let current_mode = determine_agent_state(state);
 
         // Call the appropriate get_configuration function based on the mode
-        let mode_config = match current_mode {
+        let mut mode_config = match current_mode {
             AgentState::Initializing => modes::initialization::get_configuration(&self.agent_data),
             AgentState::DataCatalogSearch => {
                 modes::data_catalog_search::get_configuration(&self.agent_data)
@@ -75,6 +82,17 @@ impl ModeProvider for BusterModeProvider {
             AgentState::Review => modes::review::get_configuration(&self.agent_data),
         };
 
+        // Planning and analysis follow the organization's knowledge base
+        if matches!(
+            current_mode,
+            AgentState::Planning | AgentState::AnalysisExecution
+        ) {
+            mode_config.prompt.push_str(&modes::knowledge_base::prompt_section(
+                &self.agent_data.instructions,
+                state,
+            ));
+        }
+
         Ok(mode_config)
     }
 }
@@ -105,9 +123,30 @@ impl BusterMultiAgent {
             .collect();
         let dataset_names = Arc::new(dataset_names);
 
+        // Resolve the models configured for the user's organization
+        let models = Arc::new(get_user_models(&user_id).await?);
+        let initial_model = models.model_for(ModelPurpose::Initialization);
+        let approved_models = models.approved_models();
+        let organization_id = models.organization_id();
+
+        // Organization-registered tools, already filtered to the user's role
+        let custom_tools = Arc::new(get_user_custom_tools(&user_id).await?);
+
+        // Knowledge base instructions; glossary terms are retrieved per search
+        let instructions: Vec<_> = get_user_terms(&user_id)
+            .await?
+            .into_iter()
+            .map(|entry| entry.term)
+            .filter(|term| term.kind == TermKind::Instruction)
+            .collect();
+        let instructions = Arc::new(instructions);
+
         let agent_data = ModeAgentData {
             dataset_names,
             todays_date,
+            models,
+            custom_tools,
+            instructions,
         };
 
         // Create the mode provider
@@ -115,20 +154,31 @@ impl BusterMultiAgent {
 
         // Create agent, passing the provider
         let agent = Arc::new(Agent::new(
-            "o4-mini".to_string(), // Initial model (can be overridden by first mode)
+            initial_model, // Initial model (can be overridden by first mode)
             user_id,
             session_id,
             "buster_multi_agent".to_string(),
             None,          // api_key
             None,          // base_url
             mode_provider, // Pass the provider
-        ));
+        )
+        .with_approved_fallbacks(&approved_models));
 
         // Set the initial is_follow_up flag in state
         agent
             .set_state_value("is_follow_up".to_string(), Value::Bool(is_follow_up))
             .await;
 
+        // The organization LLM usage is billed to
+        if let Some(organization_id) = organization_id {
+            agent
+                .set_state_value(
+                    "organization_id".to_string(),
+                    Value::String(organization_id.to_string()),
+                )
+                .await;
+        }
+
         let buster_agent = Self { agent };
 
         Ok(buster_agent)
@@ -8,12 +8,15 @@ use std::sync::Arc;
 use crate::tools::ToolExecutor;
 use crate::Agent; // For get_name()
 
+use database::enums::ModelPurpose;
+
 // Import necessary types from the parent module (modes/mod.rs)
 use super::{ModeAgentData, ModeConfiguration};
 
 // Import necessary tools for this mode
 use crate::tools::{
     categories::{
+        custom_tools::ExternalTool,
         file_tools::{
             CreateDashboardFilesTool, CreateMetricFilesTool, ModifyDashboardFilesTool,
             ModifyMetricFilesTool, SearchDataCatalogTool,
@@ -29,14 +32,16 @@ pub fn get_configuration(agent_data: &ModeAgentData) -> ModeConfiguration {
     let prompt = PROMPT.replace("{TODAYS_DATE}", &agent_data.todays_date);
     // Note: This prompt doesn't use {DATASETS}
 
-    // 2. Define the model for this mode (Using default based on original MODEL = None)
-    let model = "o4-mini".to_string();
+    // 2. Define the model for this mode (organization override or default)
+    let model = agent_data.models.model_for(ModelPurpose::Analysis);
 
     // 3. Define the tool loader closure
+    let custom_tools = Arc::clone(&agent_data.custom_tools);
     let tool_loader: Box<
         dyn Fn(&Arc<Agent>) -> Pin<Box<dyn Future<Output = Result<()>> + Send>> + Send + Sync,
-    > = Box::new(|agent_arc: &Arc<Agent>| {
+    > = Box::new(move |agent_arc: &Arc<Agent>| {
         let agent_clone = Arc::clone(agent_arc); // Clone Arc for the async block
+        let custom_tools = Arc::clone(&custom_tools);
         Box::pin(async move {
             // Clear existing tools before loading mode-specific ones
             agent_clone.clear_tools().await;
@@ -135,6 +140,18 @@ pub fn get_configuration(agent_data: &ModeAgentData) -> ModeConfiguration {
                 )
                 .await;
 
+            // Organization-registered tools are available throughout analysis
+            for custom_tool in custom_tools.iter() {
+                let external_tool = ExternalTool::new(custom_tool.clone());
+                agent_clone
+                    .add_tool(
+                        external_tool.get_name(),
+                        external_tool.into_tool_call_executor(),
+                        always_available,
+                    )
+                    .await;
+            }
+
             Ok(())
         })
     });
@@ -8,6 +8,8 @@ use std::future::Future;
 use crate::tools::ToolExecutor;
 use crate::Agent; // For get_name()
 
+use database::enums::ModelPurpose;
+
 // Import necessary types from the parent module (modes/mod.rs)
 use super::{ModeAgentData, ModeConfiguration};
 
@@ -31,8 +33,8 @@ pub fn get_configuration(agent_data: &ModeAgentData) -> ModeConfiguration {
         .replace("{DATASET_DESCRIPTIONS}", "<Dataset descriptions currently unavailable>"); // Temporary placeholder
         // Note: This prompt doesn't use {TODAYS_DATE}
 
-    // 2. Define the model for this mode
-    let model = "o4-mini".to_string(); // Use o4-mini as requested
+    // 2. Define the model for this mode (organization override or default)
+    let model = agent_data.models.model_for(ModelPurpose::DataCatalogSearch);
 
     // 3. Define the tool loader closure
     let tool_loader: Box<dyn Fn(&Arc<Agent>) -> Pin<Box<dyn Future<Output = Result<()>> + Send>> + Send + Sync> = 
@@ -5,6 +5,8 @@ use std::sync::Arc;
 use std::pin::Pin;
 use std::future::Future;
 
+use database::enums::ModelPurpose;
+
 // Import necessary types from the parent module (modes/mod.rs)
 use super::{ModeAgentData, ModeConfiguration};
 use crate::{Agent, ToolExecutor};
@@ -41,8 +43,8 @@ pub fn get_configuration(agent_data: &ModeAgentData) -> ModeConfiguration {
         .replace("{DATASETS}", &agent_data.dataset_names.join(", "))
         .replace("{TODAYS_DATE}", &agent_data.todays_date);
 
-    // 2. Define the model for this mode (Using a default, adjust if needed)
-    let model = "o4-mini".to_string(); // Assuming default based on original MODEL = None
+    // 2. Define the model for this mode (organization override or default)
+    let model = agent_data.models.model_for(ModelPurpose::Initialization);
 
     // 3. Define the tool loader closure
     let tool_loader: Box<dyn Fn(&Arc<Agent>) -> Pin<Box<dyn Future<Output = Result<()>> + Send>> + Send + Sync> = 
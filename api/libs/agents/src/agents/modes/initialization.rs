@@ -5,6 +5,8 @@ use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
 
+use database::enums::ModelPurpose;
+
 // Import necessary types from the parent module (modes/mod.rs)
 use super::{ModeAgentData, ModeConfiguration};
 use crate::{Agent, ToolExecutor};
@@ -24,10 +26,8 @@ pub fn get_configuration(agent_data: &ModeAgentData) -> ModeConfiguration {
         .replace("{DATASETS}", &agent_data.dataset_names.join(", "))
         .replace("{TODAYS_DATE}", &agent_data.todays_date);
 
-    // 2. Define the model for this mode (Using a default, adjust if needed)
-    //    Since the original MODEL was None, we might use the agent's default
-    //    or specify a standard one like "o4-mini". Let's use "o4-mini".
-    let model = "o4-mini".to_string();
+    // 2. Define the model for this mode (organization override or default)
+    let model = agent_data.models.model_for(ModelPurpose::Initialization);
 
     // 3. Define the tool loader closure
     let tool_loader: Box<
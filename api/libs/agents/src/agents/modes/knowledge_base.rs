@@ -0,0 +1,324 @@
+use std::collections::{HashMap, HashSet};
+
+use database::{
+    enums::TermKind,
+    helpers::terms::TermWithDatasets,
+    models::{Term, VerifiedExample},
+};
+use dataset_retrieval::{Bm25Retriever, RetrievalDocument};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// Agent state key holding the glossary terms found by the last catalog search
+pub const KNOWLEDGE_TERMS_STATE_KEY: &str = "knowledge_terms";
+
+/// Most glossary terms handed to the planning and analysis prompts
+pub const MAX_KNOWLEDGE_TERMS: usize = 10;
+
+/// Agent state key holding the verified examples found by the last catalog search
+pub const VERIFIED_EXAMPLES_STATE_KEY: &str = "verified_examples";
+
+/// Most verified examples handed to the planning and analysis prompts
+pub const MAX_VERIFIED_EXAMPLES: usize = 3;
+
+/// A glossary term retrieved for the current request
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KnowledgeTerm {
+    pub id: Uuid,
+    pub name: String,
+    pub definition: Option<String>,
+    pub sql_snippet: Option<String>,
+}
+
+impl From<&Term> for KnowledgeTerm {
+    fn from(term: &Term) -> Self {
+        Self {
+            id: term.id,
+            name: term.name.clone(),
+            definition: term.definition.clone(),
+            sql_snippet: term.sql_snippet.clone(),
+        }
+    }
+}
+
+/// A verified question and SQL pair retrieved as a few-shot example
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KnowledgeExample {
+    pub id: Uuid,
+    pub question: String,
+    pub sql: String,
+}
+
+/// Picks the glossary terms relevant to a catalog search
+///
+/// Terms matching the query come first, most relevant first, followed by
+/// terms linked to one of the datasets the search found. Instructions are
+/// left out; they are part of every prompt.
+pub fn rank_terms(
+    query: &str,
+    terms: &[TermWithDatasets],
+    dataset_ids: &HashSet<Uuid>,
+    limit: usize,
+) -> Vec<KnowledgeTerm> {
+    let glossary: HashMap<Uuid, &Term> = terms
+        .iter()
+        .filter(|entry| entry.term.kind == TermKind::Term)
+        .map(|entry| (entry.term.id, &entry.term))
+        .collect();
+
+    let documents: Vec<RetrievalDocument> = terms
+        .iter()
+        .filter(|entry| glossary.contains_key(&entry.term.id))
+        .map(|entry| RetrievalDocument {
+            id: entry.term.id,
+            name: entry.term.name.clone(),
+            content: entry.term.definition.clone().unwrap_or_default(),
+        })
+        .collect();
+
+    let matched = Bm25Retriever::default()
+        .rank(query, &documents)
+        .into_iter()
+        .map(|scored| scored.id);
+    let linked = terms
+        .iter()
+        .filter(|entry| glossary.contains_key(&entry.term.id))
+        .filter(|entry| entry.dataset_ids.iter().any(|id| dataset_ids.contains(id)))
+        .map(|entry| entry.term.id);
+
+    let mut seen = HashSet::new();
+    matched
+        .chain(linked)
+        .filter(|id| seen.insert(*id))
+        .take(limit)
+        .map(|id| KnowledgeTerm::from(glossary[&id]))
+        .collect()
+}
+
+/// Picks the verified examples whose questions are closest to the query
+pub fn rank_examples(
+    query: &str,
+    examples: &[VerifiedExample],
+    limit: usize,
+) -> Vec<KnowledgeExample> {
+    let documents: Vec<RetrievalDocument> = examples
+        .iter()
+        .map(|example| RetrievalDocument {
+            id: example.id,
+            name: String::new(),
+            content: example.question.clone(),
+        })
+        .collect();
+    let by_id: HashMap<Uuid, &VerifiedExample> = examples
+        .iter()
+        .map(|example| (example.id, example))
+        .collect();
+
+    Bm25Retriever::default()
+        .rank(query, &documents)
+        .into_iter()
+        .take(limit)
+        .map(|scored| {
+            let example = by_id[&scored.id];
+            KnowledgeExample {
+                id: example.id,
+                question: example.question.clone(),
+                sql: example.sql.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Renders the organization's instructions, the retrieved glossary terms and
+/// verified examples for a system prompt; empty when there are none
+pub fn prompt_section(instructions: &[Term], state: &HashMap<String, Value>) -> String {
+    let terms: Vec<KnowledgeTerm> = state
+        .get(KNOWLEDGE_TERMS_STATE_KEY)
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default();
+    let examples: Vec<KnowledgeExample> = state
+        .get(VERIFIED_EXAMPLES_STATE_KEY)
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default();
+
+    let mut section = String::new();
+
+    let instructions: Vec<&str> = instructions
+        .iter()
+        .filter_map(|instruction| instruction.definition.as_deref())
+        .collect();
+    if !instructions.is_empty() {
+        section.push_str("\n\n## Organization instructions\nFollow these instructions from the organization's admins:\n");
+        for instruction in instructions {
+            section.push_str(&format!("- {}\n", instruction));
+        }
+    }
+
+    if !terms.is_empty() {
+        section.push_str("\n\n## Business glossary\nUse these definitions when the request mentions the terms below. Prefer a term's SQL over your own interpretation:\n");
+        for term in terms {
+            section.push_str(&format!("- **{}**", term.name));
+            if let Some(definition) = &term.definition {
+                section.push_str(&format!(": {}", definition));
+            }
+            if let Some(sql_snippet) = &term.sql_snippet {
+                section.push_str(&format!(" (SQL: `{}`)", sql_snippet));
+            }
+            section.push('\n');
+        }
+    }
+
+    if !examples.is_empty() {
+        section.push_str("\n\n## Verified examples\nAdmins confirmed these queries answer similar questions correctly. Reuse their logic where it applies:\n");
+        for example in examples {
+            section.push_str(&format!(
+                "\nQuestion: {}\n```sql\n{}\n```\n",
+                example.question, example.sql
+            ));
+        }
+    }
+
+    section
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn entry(
+        name: &str,
+        definition: &str,
+        kind: TermKind,
+        dataset_ids: Vec<Uuid>,
+    ) -> TermWithDatasets {
+        TermWithDatasets {
+            term: Term {
+                id: Uuid::new_v4(),
+                name: name.to_string(),
+                definition: Some(definition.to_string()),
+                sql_snippet: None,
+                organization_id: Uuid::new_v4(),
+                created_by: Uuid::new_v4(),
+                updated_by: Uuid::new_v4(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                deleted_at: None,
+                kind,
+            },
+            dataset_ids,
+        }
+    }
+
+    #[test]
+    fn test_rank_terms_matches_query_then_linked_datasets() {
+        let dataset_id = Uuid::new_v4();
+        let terms = vec![
+            entry(
+                "Churn",
+                "Customers who cancelled their subscription",
+                TermKind::Term,
+                vec![],
+            ),
+            entry(
+                "ARR",
+                "Annual recurring revenue",
+                TermKind::Term,
+                vec![dataset_id],
+            ),
+            entry(
+                "Fiscal year",
+                "Churn is always reported by fiscal year",
+                TermKind::Instruction,
+                vec![],
+            ),
+            entry(
+                "Warehouse",
+                "Physical stock location",
+                TermKind::Term,
+                vec![],
+            ),
+        ];
+
+        let ranked = rank_terms(
+            "monthly churn by plan",
+            &terms,
+            &HashSet::from([dataset_id]),
+            10,
+        );
+        let names: Vec<&str> = ranked.iter().map(|term| term.name.as_str()).collect();
+        assert_eq!(names, vec!["Churn", "ARR"]);
+
+        let limited = rank_terms(
+            "monthly churn by plan",
+            &terms,
+            &HashSet::from([dataset_id]),
+            1,
+        );
+        assert_eq!(limited.len(), 1);
+    }
+
+    #[test]
+    fn test_rank_examples() {
+        let example = |question: &str| VerifiedExample {
+            id: Uuid::new_v4(),
+            organization_id: Uuid::new_v4(),
+            question: question.to_string(),
+            sql: "select 1".to_string(),
+            feedback_id: None,
+            created_by: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        };
+        let examples = vec![
+            example("How many orders shipped late last month?"),
+            example("What is our monthly recurring revenue by plan?"),
+        ];
+
+        let ranked = rank_examples("recurring revenue for the enterprise plan", &examples, 3);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].id, examples[1].id);
+    }
+
+    #[test]
+    fn test_prompt_section() {
+        let instruction = entry(
+            "Fiscal year",
+            "Fiscal years start in February",
+            TermKind::Instruction,
+            vec![],
+        )
+        .term;
+        assert!(prompt_section(&[], &HashMap::new()).is_empty());
+
+        let term = KnowledgeTerm {
+            id: Uuid::new_v4(),
+            name: "Active customer".to_string(),
+            definition: Some("Ordered in the last 90 days".to_string()),
+            sql_snippet: Some("last_order_at > now() - interval '90 days'".to_string()),
+        };
+        let example = KnowledgeExample {
+            id: Uuid::new_v4(),
+            question: "How many active customers do we have?".to_string(),
+            sql: "select count(*) from customers".to_string(),
+        };
+        let state = HashMap::from([
+            (
+                KNOWLEDGE_TERMS_STATE_KEY.to_string(),
+                serde_json::to_value(vec![term]).unwrap(),
+            ),
+            (
+                VERIFIED_EXAMPLES_STATE_KEY.to_string(),
+                serde_json::to_value(vec![example]).unwrap(),
+            ),
+        ]);
+
+        let section = prompt_section(&[instruction], &state);
+        assert!(section.contains("- Fiscal years start in February"));
+        assert!(section.contains("**Active customer**: Ordered in the last 90 days"));
+        assert!(section.contains("interval '90 days'"));
+        assert!(section.contains("Question: How many active customers do we have?"));
+    }
+}
@@ -5,11 +5,14 @@ use std::sync::Arc;
 use std::pin::Pin;
 use std::future::Future;
 use crate::Agent; // Assuming Agent is accessible at this path
+use database::helpers::model_configs::OrganizationModels;
+use database::models::{CustomTool, Term};
 
 pub mod analysis;
 pub mod data_catalog_search;
 pub mod follow_up_initialization;
 pub mod initialization;
+pub mod knowledge_base;
 pub mod planning;
 pub mod review;
 
@@ -24,6 +27,13 @@ pub mod review;
 pub struct ModeAgentData {
    pub dataset_names: Arc<Vec<String>>,
    pub todays_date: Arc<String>,
+   /// The organization's model choices, consulted by each mode's configuration
+   pub models: Arc<OrganizationModels>,
+   /// Custom tools the user's role may use, offered during analysis
+   pub custom_tools: Arc<Vec<CustomTool>>,
+   /// The organization's knowledge base instructions, part of every planning
+   /// and analysis prompt
+   pub instructions: Arc<Vec<Term>>,
    // Add other shared data if needed by modes, e.g., user_id, session_id if not in Agent state
 }
 
@@ -8,6 +8,8 @@ use std::sync::Arc;
 use crate::tools::ToolExecutor;
 use crate::Agent; // For get_name()
 
+use database::enums::ModelPurpose;
+
 // Import necessary types from the parent module (modes/mod.rs)
 use super::{ModeAgentData, ModeConfiguration};
 
@@ -27,8 +29,8 @@ pub fn get_configuration(agent_data: &ModeAgentData) -> ModeConfiguration {
         .replace("{TODAYS_DATE}", &agent_data.todays_date)
         .replace("{DATASETS}", &agent_data.dataset_names.join(", "));
 
-    // 2. Define the model for this mode (Using default based on original MODEL = None)
-    let model = "o4-mini".to_string();
+    // 2. Define the model for this mode (organization override or default)
+    let model = agent_data.models.model_for(ModelPurpose::Planning);
 
     // 3. Define the tool loader closure
     let tool_loader: Box<
@@ -8,6 +8,8 @@ use std::sync::Arc;
 use crate::tools::ToolExecutor;
 use crate::Agent; // For get_name()
 
+use database::enums::ModelPurpose;
+
 // Import necessary types from the parent module (modes/mod.rs)
 use super::{ModeAgentData, ModeConfiguration};
 
@@ -19,12 +21,12 @@ use crate::tools::{
 };
 
 // Function to get the configuration for the Review mode
-pub fn get_configuration(_agent_data: &ModeAgentData) -> ModeConfiguration {
+pub fn get_configuration(agent_data: &ModeAgentData) -> ModeConfiguration {
     // 1. Get the prompt (doesn't need formatting for this mode)
     let prompt = REVIEW_PROMPT.to_string();
 
-    // 2. Define the model for this mode (From original MODEL const)
-    let model = "gemini-2.0-flash-001".to_string();
+    // 2. Define the model for this mode (organization override or default)
+    let model = agent_data.models.model_for(ModelPurpose::Review);
 
     // 3. Define the tool loader closure
     let tool_loader: Box<
@@ -8,7 +8,7 @@ mod models;
 pub mod tools;
 
 // Re-export public API
-pub use agent::{Agent, AgentError, AgentExt};
+pub use agent::{Agent, AgentError, AgentExt, SHUTDOWN_MESSAGE_ID};
 pub use agents::*;
 pub use models::*;
 
@@ -2,10 +2,12 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::process::Command;
+use std::time::Duration;
 use crate::{agent::Agent, tools::ToolExecutor};
 use anyhow::Result;
 
+use super::sandbox::CliSandbox;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct BashParams {
     command: String,
@@ -15,11 +17,15 @@ pub struct BashParams {
 
 pub struct BashTool {
     _agent: Arc<Agent>,
+    sandbox: Arc<CliSandbox>,
 }
 
 impl BashTool {
-    pub fn new(agent: Arc<Agent>) -> Self {
-        Self { _agent: agent }
+    pub fn new(agent: Arc<Agent>, sandbox: Arc<CliSandbox>) -> Self {
+        Self {
+            _agent: agent,
+            sandbox,
+        }
     }
 }
 
@@ -33,38 +39,16 @@ impl ToolExecutor for BashTool {
     }
 
     async fn execute(&self, params: Self::Params, _tool_call_id: String) -> Result<Self::Output> {
-        let mut command = Command::new("sh");
-        command.arg("-c").arg(&params.command);
-
-        // We'd implement timeout here with actual timeout logic
-        let _timeout_ms = params.timeout.unwrap_or(1800000); // Default 30 min
-        
-        match command.output() {
-            Ok(output) => {
-                let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-                let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-                
-                let mut result = stdout; // stdout is already owned String
-                if !stderr.is_empty() {
-                    if !result.is_empty() && !result.ends_with('\n') {
-                        result.push('\n'); // Append a newline character if needed
-                    }
-                    result.push_str(&stderr); // Append stderr
-                }
-                Ok(result)
-            },
-            Err(e) => Err(anyhow::anyhow!(
-                "Failed to execute command '{}': {}",
-                params.command,
-                e
-            )),
-        }
+        // Runs from the working directory under the command policy; destructive
+        // commands wait for the user's approval
+        let timeout = params.timeout.map(Duration::from_millis);
+        self.sandbox.run_command(&params.command, timeout).await
     }
 
     async fn get_schema(&self) -> Value {
         serde_json::json!({
             "name": self.get_name(),
-            "description": "Executes a given bash command in a persistent shell session with optional timeout, ensuring proper handling and security measures.",
+            "description": "Executes a given bash command from the working directory with an optional timeout (default 120000 ms). Commands that delete or overwrite files ask the user for confirmation first.",
             "parameters": {
                 "type": "object",
                 "properties": {
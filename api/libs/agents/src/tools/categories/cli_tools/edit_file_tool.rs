@@ -3,12 +3,13 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fs;
-use std::path::Path;
 use crate::{
     agent::Agent,
     tools::ToolExecutor
 };
 
+use super::sandbox::CliSandbox;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct EditParams {
     file_path: String,
@@ -19,11 +20,12 @@ pub struct EditParams {
 
 pub struct EditTool {
     agent: Arc<Agent>,
+    sandbox: Arc<CliSandbox>,
 }
 
 impl EditTool {
-    pub fn new(agent: Arc<Agent>) -> Self {
-        Self { agent }
+    pub fn new(agent: Arc<Agent>, sandbox: Arc<CliSandbox>) -> Self {
+        Self { agent, sandbox }
     }
 }
 
@@ -37,7 +39,7 @@ impl ToolExecutor for EditTool {
     }
 
     async fn execute(&self, params: Self::Params, _tool_call_id: String) -> Result<Self::Output, anyhow::Error> {
-        let file_path = Path::new(&params.file_path);
+        let file_path = self.sandbox.resolve_path(&params.file_path)?;
         if !file_path.exists() && !params.old_string.is_empty() {
             return Err(anyhow::anyhow!("File does not exist: {}", params.file_path));
         }
@@ -48,11 +50,11 @@ impl ToolExecutor for EditTool {
             if let Some(parent) = file_path.parent() {
                 std::fs::create_dir_all(parent)?;
             }
-            std::fs::write(file_path, &params.new_string)?;
+            std::fs::write(&file_path, &params.new_string)?;
             return Ok(format!("Created new file: {}", params.file_path));
         }
         
-        let content = std::fs::read_to_string(file_path)?;
+        let content = std::fs::read_to_string(&file_path)?;
         let expected = params.expected_replacements.unwrap_or(1);
         let matches = content.matches(&params.old_string).count();
         
@@ -74,7 +76,7 @@ impl ToolExecutor for EditTool {
         }
         
         let new_content = content.replace(&params.old_string, &params.new_string);
-        std::fs::write(file_path, new_content)?;
+        std::fs::write(&file_path, new_content)?;
         
         Ok(format!("Successfully edited file: {}", params.file_path))
     }
@@ -3,12 +3,13 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use glob::glob;
-use std::path::PathBuf;
 use crate::{
     agent::Agent,
     tools::ToolExecutor
 };
 
+use super::sandbox::CliSandbox;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct GlobToolParams {
     pattern: String,
@@ -17,11 +18,12 @@ pub struct GlobToolParams {
 
 pub struct GlobTool {
     agent: Arc<Agent>,
+    sandbox: Arc<CliSandbox>,
 }
 
 impl GlobTool {
-    pub fn new(agent: Arc<Agent>) -> Self {
-        Self { agent }
+    pub fn new(agent: Arc<Agent>, sandbox: Arc<CliSandbox>) -> Self {
+        Self { agent, sandbox }
     }
 }
 
@@ -36,8 +38,8 @@ impl ToolExecutor for GlobTool {
 
     async fn execute(&self, params: Self::Params, _tool_call_id: String) -> Result<Self::Output, anyhow::Error> {
         let base_path = match params.path {
-            Some(dir) => PathBuf::from(dir),
-            None => std::env::current_dir().map_err(|e| anyhow::anyhow!("Failed to get current directory: {}", e))?,
+            Some(dir) => self.sandbox.resolve_path(&dir)?,
+            None => self.sandbox.root().to_path_buf(),
         };
 
         let full_pattern = base_path.join(&params.pattern);
@@ -48,11 +50,13 @@ impl ToolExecutor for GlobTool {
             Ok(paths) => {
                 for entry in paths {
                     match entry {
-                        Ok(path) => {
+                        // Patterns like `../*` could reach outside the working directory
+                        Ok(path) if self.sandbox.contains(&path) => {
                             if let Some(s) = path.to_str() {
                                 matched_files.push(s.to_string());
                             }
                         }
+                        Ok(_) => {}
                         Err(e) => eprintln!("Error processing glob entry: {}", e), // Log error but continue
                     }
                 }
@@ -96,7 +100,7 @@ impl ToolExecutor for GlobTool {
                     },
                     "path": {
                         "type": "string",
-                        "description": "The directory to search in. If not specified, the current working directory will be used."
+                        "description": "The directory to search in, inside the working directory. If not specified, the working directory will be used."
                     }
                 },
                 "required": ["pattern"]
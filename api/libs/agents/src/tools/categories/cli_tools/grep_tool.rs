@@ -4,13 +4,14 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use regex::Regex;
 use std::fs;
-use std::path::PathBuf;
 use glob::glob;
 use crate::{
     agent::Agent,
     tools::ToolExecutor
 };
 
+use super::sandbox::CliSandbox;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct GrepToolParams {
     pattern: String,
@@ -20,11 +21,12 @@ pub struct GrepToolParams {
 
 pub struct GrepTool {
     agent: Arc<Agent>,
+    sandbox: Arc<CliSandbox>,
 }
 
 impl GrepTool {
-    pub fn new(agent: Arc<Agent>) -> Self {
-        Self { agent }
+    pub fn new(agent: Arc<Agent>, sandbox: Arc<CliSandbox>) -> Self {
+        Self { agent, sandbox }
     }
 }
 
@@ -39,8 +41,8 @@ impl ToolExecutor for GrepTool {
 
     async fn execute(&self, params: Self::Params, _tool_call_id: String) -> Result<Self::Output, anyhow::Error> {
         let base_path = match params.path {
-            Some(dir) => PathBuf::from(dir),
-            None => std::env::current_dir().map_err(|e| anyhow::anyhow!("Failed to get current directory: {}", e))?,
+            Some(dir) => self.sandbox.resolve_path(&dir)?,
+            None => self.sandbox.root().to_path_buf(),
         };
         
         // Find all files that match the include pattern
@@ -51,7 +53,7 @@ impl ToolExecutor for GrepTool {
         let mut file_paths = Vec::new();
         for entry in glob(pattern_str)? {
             if let Ok(path) = entry {
-                if path.is_file() {
+                if path.is_file() && self.sandbox.contains(&path) {
                     file_paths.push(path);
                 }
             }
@@ -118,7 +120,7 @@ impl ToolExecutor for GrepTool {
                     },
                     "path": {
                         "type": "string",
-                        "description": "The directory to search in. Defaults to the current working directory."
+                        "description": "The directory to search in, inside the working directory. Defaults to the working directory."
                     },
                     "include": {
                         "type": "string",
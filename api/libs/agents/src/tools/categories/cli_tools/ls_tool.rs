@@ -3,13 +3,14 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fs;
-use std::path::{Path, PathBuf};
 use crate::{
     agent::Agent,
     tools::ToolExecutor
 };
 use glob::Pattern;
 
+use super::sandbox::CliSandbox;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct LSParams {
     path: String,
@@ -18,11 +19,12 @@ pub struct LSParams {
 
 pub struct LSTool {
     agent: Arc<Agent>,
+    sandbox: Arc<CliSandbox>,
 }
 
 impl LSTool {
-    pub fn new(agent: Arc<Agent>) -> Self {
-        Self { agent }
+    pub fn new(agent: Arc<Agent>, sandbox: Arc<CliSandbox>) -> Self {
+        Self { agent, sandbox }
     }
 }
 
@@ -36,7 +38,7 @@ impl ToolExecutor for LSTool {
     }
 
     async fn execute(&self, params: Self::Params, _tool_call_id: String) -> Result<Self::Output, anyhow::Error> {
-        let path = PathBuf::from(&params.path);
+        let path = self.sandbox.resolve_path(&params.path)?;
         if !path.exists() {
             return Err(anyhow::anyhow!("Path does not exist: {}", params.path));
         }
@@ -2,6 +2,7 @@ pub mod bash_tool;
 pub mod glob_tool;
 pub mod grep_tool;
 pub mod ls_tool;
+pub mod sandbox;
 pub mod read_file_tool; // Will be renamed to view_tool
 pub mod edit_file_tool; // Will be renamed to edit_tool
 pub mod write_file_tool; // Will be renamed to replace_tool
@@ -10,6 +11,7 @@ pub use bash_tool::BashTool;
 pub use glob_tool::GlobTool;
 pub use grep_tool::GrepTool;
 pub use ls_tool::LSTool;
+pub use sandbox::{ChannelConfirmer, CliSandbox, CommandConfirmer, CommandPolicy, ConfirmationRequest};
 pub use read_file_tool::ViewTool;
 pub use edit_file_tool::EditTool;
 pub use write_file_tool::ReplaceTool; 
\ No newline at end of file
@@ -3,12 +3,13 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fs;
-use std::path::Path;
 use crate::{
     agent::Agent,
     tools::ToolExecutor
 };
 
+use super::sandbox::CliSandbox;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ViewSingleParams {
     file_path: String,
@@ -26,15 +27,16 @@ pub struct ViewParams {
 
 pub struct ViewTool {
     agent: Arc<Agent>,
+    sandbox: Arc<CliSandbox>,
 }
 
 impl ViewTool {
-    pub fn new(agent: Arc<Agent>) -> Self {
-        Self { agent }
+    pub fn new(agent: Arc<Agent>, sandbox: Arc<CliSandbox>) -> Self {
+        Self { agent, sandbox }
     }
 
     async fn read_single_file(&self, file_path: &str, offset: Option<usize>, limit: Option<usize>) -> Result<String, anyhow::Error> {
-        let path = Path::new(file_path);
+        let path = self.sandbox.resolve_path(file_path)?;
         if !path.exists() {
             return Err(anyhow::anyhow!("File does not exist: {}", file_path));
         }
@@ -42,7 +44,7 @@ impl ViewTool {
             return Err(anyhow::anyhow!("Path is not a file: {}", file_path));
         }
 
-        let content = fs::read_to_string(path)
+        let content = fs::read_to_string(&path)
             .map_err(|e| anyhow::anyhow!("Failed to read file '{}': {}", file_path, e))?;
 
         let lines: Vec<&str> = content.lines().collect();
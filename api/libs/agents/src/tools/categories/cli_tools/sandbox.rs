@@ -0,0 +1,860 @@
+//! Limits on what the CLI tools may touch and run.
+//!
+//! Every CLI tool shares a [`CliSandbox`]: file tools may only resolve paths
+//! inside its working directory, and shell commands are checked against an
+//! allow/deny list, may only name paths inside that directory, run from it
+//! and are killed when they exceed their timeout. Destructive commands need approval from a
+//! [`CommandConfirmer`], which `buster chat` surfaces as a prompt.
+
+use std::path::{Component, Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use tokio::process::Command;
+use tokio::sync::{mpsc, oneshot};
+
+/// Timeout for commands that don't ask for one
+pub const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Longest timeout a command may ask for
+pub const MAX_COMMAND_TIMEOUT: Duration = Duration::from_secs(600);
+
+// Programs refused unless explicitly allowed
+const DEFAULT_DENIED_COMMANDS: &[&str] = &[
+    "sudo", "su", "doas", "shutdown", "reboot", "halt", "poweroff", "mkfs", "dd", "fdisk", "mount",
+    "umount", "passwd", "crontab",
+];
+
+// Programs that change or delete files, or publish changes, outside the
+// agent's reviewable edits
+const DESTRUCTIVE_COMMANDS: &[&str] = &[
+    "rm", "rmdir", "mv", "chmod", "chown", "truncate", "shred", "unlink",
+];
+
+// Git subcommands that rewrite history, discard work or leave the machine
+const DESTRUCTIVE_GIT_SUBCOMMANDS: &[&str] =
+    &["push", "reset", "clean", "rebase", "checkout", "restore"];
+
+// Programs refused whatever the policy, since the commands they run can't be
+// checked: as another user, or parsed by a shell of their own with `-c`
+const PRIVILEGED_COMMANDS: &[&str] = &["sudo", "su", "doas"];
+const SHELLS: &[&str] = &["sh", "bash", "zsh", "dash", "ksh"];
+
+// `find` actions that run a command
+const FIND_EXEC_ACTIONS: &[&str] = &["-exec", "-execdir", "-ok", "-okdir"];
+
+// Shell words that come before a command rather than being one
+const RESERVED_WORDS: &[&str] = &[
+    "!", "{", "}", "if", "then", "else", "elif", "fi", "do", "done", "while", "until",
+];
+
+/// Which programs shell commands may run
+#[derive(Debug, Clone)]
+pub struct CommandPolicy {
+    /// When non-empty, only these programs may run
+    pub allow: Vec<String>,
+    /// Programs that may never run; takes precedence over `allow`
+    pub deny: Vec<String>,
+}
+
+impl Default for CommandPolicy {
+    fn default() -> Self {
+        Self {
+            allow: vec![],
+            deny: DEFAULT_DENIED_COMMANDS
+                .iter()
+                .map(|c| c.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl CommandPolicy {
+    /// Errors if any program in the command line isn't permitted
+    ///
+    /// Programs run through `env`, `xargs`, `find -exec` and similar wrappers
+    /// are checked too. Running commands as another user, or through a shell
+    /// or `eval` that would parse them out of reach of these checks, is
+    /// refused whatever the policy says.
+    pub fn check(&self, command: &str) -> Result<()> {
+        for command in parse_command_line(command) {
+            for argv in invocations(command.argv()) {
+                let program = program_name(&argv[0]);
+
+                if PRIVILEGED_COMMANDS.contains(&program.as_str()) {
+                    return Err(anyhow!(
+                        "Command '{}' is not allowed: commands can't run as another user",
+                        program
+                    ));
+                }
+                if program == "eval"
+                    || (SHELLS.contains(&program.as_str()) && argv[1..].iter().any(|a| is_c_flag(a)))
+                {
+                    return Err(anyhow!(
+                        "Command '{}' is not allowed: run the commands directly so they can be checked",
+                        program
+                    ));
+                }
+
+                if self.deny.iter().any(|denied| *denied == program) {
+                    return Err(anyhow!("Command '{}' is not allowed", program));
+                }
+                if !self.allow.is_empty() && !self.allow.iter().any(|allowed| *allowed == program) {
+                    return Err(anyhow!(
+                        "Command '{}' is not in the list of allowed commands",
+                        program
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One command of a command line, e.g. `FOO=1 rm -f a.txt 2>/dev/null`
+#[derive(Debug, Default, Clone, PartialEq)]
+struct SimpleCommand {
+    /// Words with quotes and escapes removed
+    words: Vec<String>,
+    /// Redirection operators such as `>`, `2>&` or `<`, with their targets
+    redirects: Vec<(String, String)>,
+}
+
+impl SimpleCommand {
+    /// The program and its arguments, without leading `VAR=value`
+    /// assignments and reserved words like `if` or `{`
+    fn argv(&self) -> &[String] {
+        let start = self
+            .words
+            .iter()
+            .position(|word| !is_env_assignment(word) && !RESERVED_WORDS.contains(&word.as_str()))
+            .unwrap_or(self.words.len());
+        &self.words[start..]
+    }
+}
+
+/// Builds up the commands of a command line as [`parse_command_line`] reads it
+#[derive(Default)]
+struct CommandLineParser {
+    commands: Vec<SimpleCommand>,
+    current: SimpleCommand,
+    word: Option<String>,
+    redirect: Option<String>,
+}
+
+impl CommandLineParser {
+    fn push(&mut self, c: char) {
+        self.word.get_or_insert_with(String::new).push(c);
+    }
+
+    fn end_word(&mut self) {
+        if let Some(word) = self.word.take() {
+            match self.redirect.take() {
+                Some(op) => self.current.redirects.push((op, word)),
+                None => self.current.words.push(word),
+            }
+        }
+    }
+
+    fn end_command(&mut self) {
+        self.end_word();
+        if let Some(op) = self.redirect.take() {
+            self.current.redirects.push((op, String::new()));
+        }
+        let command = std::mem::take(&mut self.current);
+        if !command.words.is_empty() || !command.redirects.is_empty() {
+            self.commands.push(command);
+        }
+    }
+}
+
+/// Splits a shell command line into its commands, e.g. `cd x && rm -rf y |
+/// tee z 2>&1` into `cd x`, `rm -rf y` and `tee z` with its redirection
+///
+/// Commands are separated by `;`, `&&`, `||`, `|`, `&` and newlines outside
+/// quotes; `&` in redirections like `2>&1` or `&>` doesn't separate them.
+/// Commands inside `$(...)` and backticks are returned as well, after the
+/// command they appear in.
+fn parse_command_line(command: &str) -> Vec<SimpleCommand> {
+    let chars: Vec<char> = command.chars().collect();
+    let mut parser = CommandLineParser::default();
+    let mut nested = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if let Some((inner, end)) = substitution(&chars, i) {
+            nested.extend(parse_command_line(&inner));
+            parser.push('$');
+            i = end;
+            continue;
+        }
+
+        match c {
+            '\\' => {
+                if let Some(next) = next {
+                    parser.push(next);
+                }
+                i += 1;
+            }
+            '\'' => {
+                parser.word.get_or_insert_with(String::new);
+                i += 1;
+                while i < chars.len() && chars[i] != '\'' {
+                    parser.push(chars[i]);
+                    i += 1;
+                }
+            }
+            '"' => {
+                parser.word.get_or_insert_with(String::new);
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    if let Some((inner, end)) = substitution(&chars, i) {
+                        nested.extend(parse_command_line(&inner));
+                        parser.push('$');
+                        i = end;
+                        continue;
+                    }
+                    if chars[i] == '\\' && i + 1 < chars.len() {
+                        i += 1;
+                    }
+                    parser.push(chars[i]);
+                    i += 1;
+                }
+            }
+            ';' | '\n' => parser.end_command(),
+            '|' => {
+                parser.end_command();
+                if next == Some('|') {
+                    i += 1;
+                }
+            }
+            '&' if next == Some('>') => {
+                parser.end_word();
+                let mut op = "&>".to_string();
+                i += 1;
+                if chars.get(i + 1) == Some(&'>') {
+                    op.push('>');
+                    i += 1;
+                }
+                parser.redirect = Some(op);
+            }
+            '&' => {
+                parser.end_command();
+                if next == Some('&') {
+                    i += 1;
+                }
+            }
+            '>' | '<' => {
+                // A number right before the operator is the descriptor it redirects
+                let mut op = match parser.word.take() {
+                    Some(word) if word.chars().all(|c| c.is_ascii_digit()) && !word.is_empty() => {
+                        word
+                    }
+                    word => {
+                        parser.word = word;
+                        parser.end_word();
+                        String::new()
+                    }
+                };
+                op.push(c);
+                let continuations: &[char] = if c == '>' { &['>', '|'] } else { &['<'] };
+                while let Some(&next) = chars.get(i + 1) {
+                    if continuations.contains(&next) {
+                        op.push(next);
+                        i += 1;
+                    } else {
+                        break;
+                    }
+                }
+                if chars.get(i + 1) == Some(&'&') {
+                    op.push('&');
+                    i += 1;
+                }
+                parser.redirect = Some(op);
+            }
+            '(' | ')' => parser.end_word(),
+            c if c.is_whitespace() => parser.end_word(),
+            c => parser.push(c),
+        }
+        i += 1;
+    }
+
+    parser.end_command();
+    parser.commands.extend(nested);
+    parser.commands
+}
+
+/// The command inside a `$(...)` or backtick substitution starting at `start`,
+/// and the index just past it
+fn substitution(chars: &[char], start: usize) -> Option<(String, usize)> {
+    match (chars.get(start), chars.get(start + 1)) {
+        (Some('$'), Some('(')) => {
+            let mut depth = 0;
+            for (i, c) in chars.iter().enumerate().skip(start + 1) {
+                match c {
+                    '(' => depth += 1,
+                    ')' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some((chars[start + 2..i].iter().collect(), i + 1));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Some((chars[start + 2..].iter().collect(), chars.len()))
+        }
+        (Some('`'), _) => {
+            let end = chars[start + 1..]
+                .iter()
+                .position(|c| *c == '`')
+                .map(|offset| start + 1 + offset)
+                .unwrap_or(chars.len());
+            Some((chars[start + 1..end].iter().collect(), (end + 1).min(chars.len())))
+        }
+        _ => None,
+    }
+}
+
+/// The program invocations in `argv`: the command itself, then any command
+/// it runs, like `rm` in `xargs rm`, `find . -exec rm {} ;` or `env rm`
+fn invocations(argv: &[String]) -> Vec<&[String]> {
+    let Some(first) = argv.first() else {
+        return vec![];
+    };
+    let mut found = vec![argv];
+    let args = &argv[1..];
+
+    match program_name(first).as_str() {
+        "find" => {
+            let mut rest = args;
+            while let Some(start) = rest.iter().position(|a| FIND_EXEC_ACTIONS.contains(&a.as_str())) {
+                let command = &rest[start + 1..];
+                let end = command
+                    .iter()
+                    .position(|a| a == ";" || a == "+")
+                    .unwrap_or(command.len());
+                found.extend(invocations(&command[..end]));
+                rest = &command[end..];
+            }
+        }
+        program => {
+            if let Some((value_options, positional)) = command_wrapper(program) {
+                let mut start = 0;
+                while let Some(arg) = args.get(start) {
+                    if value_options.contains(&arg.as_str()) {
+                        start += 2;
+                    } else if arg.starts_with('-') || (program == "env" && is_env_assignment(arg)) {
+                        start += 1;
+                    } else {
+                        break;
+                    }
+                }
+                let start = (start + positional).min(args.len());
+                found.extend(invocations(&args[start..]));
+            }
+        }
+    }
+
+    found
+}
+
+/// For programs that run the command given in their arguments: the options
+/// that take a separate value, and how many positional arguments come before
+/// the command
+fn command_wrapper(program: &str) -> Option<(&'static [&'static str], usize)> {
+    match program {
+        "env" => Some((&["-u", "-C", "-S"], 0)),
+        "xargs" => Some((&["-I", "-n", "-P", "-L", "-d", "-E", "-s", "-a"], 0)),
+        "nice" => Some((&["-n"], 0)),
+        "timeout" => Some((&["-s", "-k"], 1)),
+        "nohup" | "time" | "command" | "exec" | "stdbuf" => Some((&[], 0)),
+        _ => None,
+    }
+}
+
+fn is_c_flag(arg: &str) -> bool {
+    arg.starts_with('-') && !arg.starts_with("--") && arg[1..].contains('c')
+}
+
+fn is_env_assignment(word: &str) -> bool {
+    match word.split_once('=') {
+        Some((name, _)) => {
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        None => false,
+    }
+}
+
+fn program_name(word: &str) -> String {
+    word.rsplit('/').next().unwrap_or(word).to_string()
+}
+
+/// Whether a redirection points one descriptor at another, as in `2>&1`
+fn duplicates_descriptor(op: &str, target: &str) -> bool {
+    op.ends_with('&')
+        && (target == "-" || (!target.is_empty() && target.chars().all(|c| c.is_ascii_digit())))
+}
+
+/// Whether a redirection truncates a file; appending, reading, duplicating a
+/// descriptor and writing to `/dev/null` don't
+fn overwrites_file(op: &str, target: &str) -> bool {
+    op.contains('>') && !op.ends_with(">>") && !duplicates_descriptor(op, target) && target != "/dev/null"
+}
+
+/// Whether a word is meant as a path, as far as a command line shows
+fn looks_like_path(word: &str) -> bool {
+    !word.contains("://") && (word.contains('/') || word == ".." || word.starts_with('~'))
+}
+
+/// Why a command needs confirmation, or `None` if it can run right away
+pub fn destructive_reason(command: &str) -> Option<String> {
+    for command in parse_command_line(command) {
+        for argv in invocations(command.argv()) {
+            let program = program_name(&argv[0]);
+            let args = &argv[1..];
+
+            if DESTRUCTIVE_COMMANDS.contains(&program.as_str()) {
+                return Some(format!("'{}' can delete or overwrite files", program));
+            }
+            if program == "git" {
+                if let Some(subcommand) = args.iter().find(|w| !w.starts_with('-')) {
+                    if DESTRUCTIVE_GIT_SUBCOMMANDS.contains(&subcommand.as_str()) {
+                        return Some(format!("'git {}' can discard or publish work", subcommand));
+                    }
+                }
+            }
+            if program == "find" && args.iter().any(|a| a == "-delete") {
+                return Some("'find -delete' deletes files".to_string());
+            }
+        }
+
+        if command
+            .redirects
+            .iter()
+            .any(|(op, target)| overwrites_file(op, target))
+        {
+            return Some("output redirection can overwrite files".to_string());
+        }
+    }
+
+    None
+}
+
+/// Asks the user whether a destructive command may run
+#[async_trait]
+pub trait CommandConfirmer: Send + Sync {
+    /// Returns `true` if `command` may run
+    async fn confirm(&self, command: &str, reason: &str) -> bool;
+}
+
+/// A pending request for the user to approve a command
+#[derive(Debug)]
+pub struct ConfirmationRequest {
+    pub command: String,
+    pub reason: String,
+    respond: oneshot::Sender<bool>,
+}
+
+impl ConfirmationRequest {
+    /// Answers the request; dropping it unanswered denies the command
+    pub fn respond(self, approved: bool) {
+        let _ = self.respond.send(approved);
+    }
+}
+
+/// Forwards confirmations to a UI over a channel
+///
+/// The UI receives [`ConfirmationRequest`]s and answers each with
+/// [`ConfirmationRequest::respond`]. If the UI goes away, commands are denied.
+pub struct ChannelConfirmer {
+    requests: mpsc::Sender<ConfirmationRequest>,
+}
+
+impl ChannelConfirmer {
+    pub fn new() -> (Self, mpsc::Receiver<ConfirmationRequest>) {
+        let (requests, receiver) = mpsc::channel(8);
+        (Self { requests }, receiver)
+    }
+}
+
+#[async_trait]
+impl CommandConfirmer for ChannelConfirmer {
+    async fn confirm(&self, command: &str, reason: &str) -> bool {
+        let (respond, response) = oneshot::channel();
+        let request = ConfirmationRequest {
+            command: command.to_string(),
+            reason: reason.to_string(),
+            respond,
+        };
+
+        if self.requests.send(request).await.is_err() {
+            return false;
+        }
+        response.await.unwrap_or(false)
+    }
+}
+
+/// The working directory jail, command policy and confirmation hook shared
+/// by the CLI tools
+pub struct CliSandbox {
+    root: PathBuf,
+    policy: CommandPolicy,
+    confirmer: Option<Arc<dyn CommandConfirmer>>,
+}
+
+impl CliSandbox {
+    /// A sandbox rooted at `root` with the default command policy
+    ///
+    /// Without a confirmer, destructive commands are refused.
+    pub fn new(root: impl AsRef<Path>) -> Result<Self> {
+        let root = root.as_ref().canonicalize().map_err(|e| {
+            anyhow!(
+                "Invalid working directory '{}': {}",
+                root.as_ref().display(),
+                e
+            )
+        })?;
+
+        Ok(Self {
+            root,
+            policy: CommandPolicy::default(),
+            confirmer: None,
+        })
+    }
+
+    pub fn with_policy(mut self, policy: CommandPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn with_confirmer(mut self, confirmer: Arc<dyn CommandConfirmer>) -> Self {
+        self.confirmer = Some(confirmer);
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Resolves `path` against the working directory, refusing anything that
+    /// ends up outside it, including through `..` or symlinks
+    ///
+    /// The path doesn't need to exist, so it can be used for new files.
+    pub fn resolve_path(&self, path: &str) -> Result<PathBuf> {
+        let joined = self.root.join(path);
+
+        let mut normalized = PathBuf::new();
+        for component in joined.components() {
+            match component {
+                Component::ParentDir => {
+                    normalized.pop();
+                }
+                Component::CurDir => {}
+                other => normalized.push(other),
+            }
+        }
+
+        // Resolve symlinks through the deepest part of the path that exists
+        let mut existing = normalized.as_path();
+        let mut missing = Vec::new();
+        while !existing.exists() {
+            match (existing.parent(), existing.file_name()) {
+                (Some(parent), Some(name)) => {
+                    missing.push(name.to_owned());
+                    existing = parent;
+                }
+                _ => break,
+            }
+        }
+        let mut resolved = existing
+            .canonicalize()
+            .unwrap_or_else(|_| existing.to_path_buf());
+        for name in missing.into_iter().rev() {
+            resolved.push(name);
+        }
+
+        if !resolved.starts_with(&self.root) {
+            return Err(anyhow!(
+                "Path '{}' is outside the working directory {}",
+                path,
+                self.root.display()
+            ));
+        }
+
+        Ok(resolved)
+    }
+
+    /// Whether an existing path is inside the working directory once
+    /// symlinks are resolved
+    pub fn contains(&self, path: &Path) -> bool {
+        path.canonicalize()
+            .map(|path| path.starts_with(&self.root))
+            .unwrap_or(false)
+    }
+
+    /// Errors if a command line names a path outside the working directory,
+    /// as an argument, an `--option=value` or a redirection target
+    pub fn check_command_paths(&self, command: &str) -> Result<()> {
+        for command in parse_command_line(command) {
+            let argv = command.argv();
+            let is_cd = argv.first().is_some_and(|program| program_name(program) == "cd");
+            if is_cd && argv.len() == 1 {
+                return Err(anyhow!("'cd' without a directory leaves the working directory"));
+            }
+
+            let arguments = argv.iter().skip(1).filter_map(|arg| match arg.strip_prefix('-') {
+                Some(option) => option.split_once('=').map(|(_, value)| value),
+                None => Some(arg.as_str()),
+            });
+            let targets = command
+                .redirects
+                .iter()
+                .filter(|(op, target)| !duplicates_descriptor(op, target))
+                .map(|(_, target)| target.as_str());
+
+            for path in arguments.chain(targets) {
+                if path == "/dev/null" || !(is_cd || looks_like_path(path)) {
+                    continue;
+                }
+                if path.starts_with('~') || path.starts_with('$') {
+                    return Err(anyhow!(
+                        "Path '{}' can't be checked against the working directory",
+                        path
+                    ));
+                }
+                self.resolve_path(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks a command against the policy and the working directory and, if
+    /// it's destructive, asks for confirmation
+    pub async fn authorize_command(&self, command: &str) -> Result<()> {
+        self.policy.check(command)?;
+        self.check_command_paths(command)?;
+
+        if let Some(reason) = destructive_reason(command) {
+            let approved = match &self.confirmer {
+                Some(confirmer) => confirmer.confirm(command, &reason).await,
+                None => false,
+            };
+            if !approved {
+                return Err(anyhow!("Command was not approved: {}", reason));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs a shell command in the working directory, killing it after
+    /// `timeout` (capped at [`MAX_COMMAND_TIMEOUT`])
+    ///
+    /// Returns stdout followed by stderr.
+    pub async fn run_command(&self, command: &str, timeout: Option<Duration>) -> Result<String> {
+        self.authorize_command(command).await?;
+
+        let timeout = timeout
+            .unwrap_or(DEFAULT_COMMAND_TIMEOUT)
+            .min(MAX_COMMAND_TIMEOUT);
+
+        let mut process = Command::new("sh");
+        process
+            .arg("-c")
+            .arg(command)
+            .current_dir(&self.root)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        // Own process group so a timeout kills everything the command started
+        #[cfg(unix)]
+        process.process_group(0);
+
+        let child = process
+            .spawn()
+            .map_err(|e| anyhow!("Failed to execute command '{}': {}", command, e))?;
+        let pid = child.id();
+
+        match tokio::time::timeout(timeout, child.wait_with_output()).await {
+            Ok(output) => {
+                let output = output
+                    .map_err(|e| anyhow!("Failed to execute command '{}': {}", command, e))?;
+
+                let mut result = String::from_utf8_lossy(&output.stdout).to_string();
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                if !stderr.is_empty() {
+                    if !result.is_empty() && !result.ends_with('\n') {
+                        result.push('\n');
+                    }
+                    result.push_str(&stderr);
+                }
+                Ok(result)
+            }
+            Err(_) => {
+                // The shell itself is killed when its future is dropped
+                #[cfg(unix)]
+                if let Some(pid) = pid {
+                    let _ = std::process::Command::new("kill")
+                        .arg("-KILL")
+                        .arg(format!("-{}", pid))
+                        .status();
+                }
+                Err(anyhow!(
+                    "Command timed out after {} ms and was killed: {}",
+                    timeout.as_millis(),
+                    command
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Approve(bool);
+
+    #[async_trait]
+    impl CommandConfirmer for Approve {
+        async fn confirm(&self, _command: &str, _reason: &str) -> bool {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_resolve_path_stays_in_working_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("models")).unwrap();
+        let sandbox = CliSandbox::new(dir.path()).unwrap();
+
+        assert_eq!(
+            sandbox.resolve_path("models/orders.yml").unwrap(),
+            sandbox.root().join("models/orders.yml")
+        );
+        assert_eq!(
+            sandbox
+                .resolve_path(sandbox.root().join("new/file.sql").to_str().unwrap())
+                .unwrap(),
+            sandbox.root().join("new/file.sql")
+        );
+        assert!(sandbox.resolve_path("../outside.txt").is_err());
+        assert!(sandbox.resolve_path("models/../../outside.txt").is_err());
+        assert!(sandbox.resolve_path("/etc/passwd").is_err());
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink("/etc", dir.path().join("escape")).unwrap();
+            assert!(sandbox.resolve_path("escape/passwd").is_err());
+        }
+    }
+
+    #[test]
+    fn test_command_policy_and_destructive_commands() {
+        let policy = CommandPolicy::default();
+        assert!(policy.check("ls -la && cat README.md").is_ok());
+        assert!(policy.check("echo hi; sudo rm -rf /").is_err());
+        assert!(policy.check("FOO=1 /usr/bin/sudo ls").is_err());
+
+        let allow_only = CommandPolicy {
+            allow: vec!["dbt".to_string(), "ls".to_string()],
+            deny: vec![],
+        };
+        assert!(allow_only.check("dbt run | ls").is_ok());
+        assert!(allow_only.check("dbt run && curl example.com").is_err());
+
+        assert!(destructive_reason("ls -la").is_none());
+        assert!(destructive_reason("cat a 2>&1 | grep b").is_none());
+        assert!(destructive_reason("cd models && rm orders.yml").is_some());
+        assert!(destructive_reason("git push origin main").is_some());
+        assert!(destructive_reason("git status").is_none());
+        assert!(destructive_reason("echo x > schema.yml").is_some());
+        assert!(destructive_reason("echo x > schema.yml 2>&1").is_some());
+        assert!(destructive_reason("echo x &> schema.yml").is_some());
+        assert!(destructive_reason("ls 2>/dev/null").is_none());
+        assert!(destructive_reason("echo x >> notes.md").is_none());
+        assert!(destructive_reason("echo 'a > b' | grep a").is_none());
+        assert!(destructive_reason("find . -name '*.tmp' -exec rm {} \\;").is_some());
+        assert!(destructive_reason("find . -name '*.tmp' -delete").is_some());
+        assert!(destructive_reason("ls | xargs -n 1 rm").is_some());
+        assert!(destructive_reason("dbt run & rm target.log").is_some());
+        assert!(destructive_reason("echo $(rm a.txt)").is_some());
+    }
+
+    #[test]
+    fn test_command_policy_checks_wrapped_commands() {
+        let policy = CommandPolicy {
+            allow: vec![],
+            deny: vec!["curl".to_string()],
+        };
+        assert!(policy.check("cat a 2>&1 | grep b").is_ok());
+        assert!(policy.check("sudo ls").is_err());
+        assert!(policy.check("bash -c 'ls'").is_err());
+        assert!(policy.check("sh -ec ls").is_err());
+        assert!(policy.check("eval ls").is_err());
+        assert!(policy.check("bash scripts/build.sh").is_ok());
+        assert!(policy.check("echo example.com | xargs -I {} curl {}").is_err());
+        assert!(policy.check("find . -name '*.yml' -exec curl -d @{} x \\;").is_err());
+        assert!(policy.check("env FOO=1 curl example.com").is_err());
+        assert!(policy.check("timeout 5 curl example.com").is_err());
+        assert!(policy.check("echo `curl example.com`").is_err());
+    }
+
+    #[test]
+    fn test_command_paths_stay_in_working_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let sandbox = CliSandbox::new(dir.path()).unwrap();
+
+        assert!(sandbox.check_command_paths("cat models/orders.yml 2>/dev/null").is_ok());
+        assert!(sandbox.check_command_paths("cd models && ls").is_ok());
+        assert!(sandbox.check_command_paths("curl https://example.com/api").is_ok());
+        assert!(sandbox.check_command_paths("cat /etc/passwd").is_err());
+        assert!(sandbox.check_command_paths("ls ../").is_err());
+        assert!(sandbox.check_command_paths("cd .. && ls").is_err());
+        assert!(sandbox.check_command_paths("cd && ls").is_err());
+        assert!(sandbox.check_command_paths("cat ~/.ssh/id_rsa").is_err());
+        assert!(sandbox.check_command_paths("echo x > /tmp/out").is_err());
+        assert!(sandbox.check_command_paths("grep -r --include=/etc/x a").is_err());
+        assert!(sandbox.check_command_paths("ls | xargs cat /etc/hosts").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_command_confirmation_and_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "hello").unwrap();
+
+        let sandbox = CliSandbox::new(dir.path()).unwrap();
+        assert_eq!(
+            sandbox.run_command("cat a.txt", None).await.unwrap(),
+            "hello"
+        );
+        assert!(sandbox.run_command("rm a.txt", None).await.is_err());
+        assert!(dir.path().join("a.txt").exists());
+
+        let denied = CliSandbox::new(dir.path())
+            .unwrap()
+            .with_confirmer(Arc::new(Approve(false)));
+        assert!(denied.run_command("rm a.txt", None).await.is_err());
+
+        let approved = CliSandbox::new(dir.path())
+            .unwrap()
+            .with_confirmer(Arc::new(Approve(true)));
+        approved.run_command("rm a.txt", None).await.unwrap();
+        assert!(!dir.path().join("a.txt").exists());
+
+        let started = std::time::Instant::now();
+        let result = sandbox
+            .run_command("sleep 5", Some(Duration::from_millis(200)))
+            .await;
+        assert!(result.unwrap_err().to_string().contains("timed out"));
+        assert!(started.elapsed() < Duration::from_secs(3));
+    }
+}
@@ -3,9 +3,10 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fs;
-use std::path::Path;
 use std::sync::Arc;
 
+use super::sandbox::CliSandbox;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ReplaceParams {
     file_path: String,
@@ -14,11 +15,12 @@ pub struct ReplaceParams {
 
 pub struct ReplaceTool {
     agent: Arc<Agent>,
+    sandbox: Arc<CliSandbox>,
 }
 
 impl ReplaceTool {
-    pub fn new(agent: Arc<Agent>) -> Self {
-        Self { agent }
+    pub fn new(agent: Arc<Agent>, sandbox: Arc<CliSandbox>) -> Self {
+        Self { agent, sandbox }
     }
 }
 
@@ -36,7 +38,7 @@ impl ToolExecutor for ReplaceTool {
         params: Self::Params,
         _tool_call_id: String,
     ) -> Result<Self::Output, anyhow::Error> {
-        let file_path = Path::new(&params.file_path);
+        let file_path = self.sandbox.resolve_path(&params.file_path)?;
 
         // Ensure parent directory exists
         if let Some(parent_dir) = file_path.parent() {
@@ -46,7 +48,7 @@ impl ToolExecutor for ReplaceTool {
         }
 
         // Write the file (always overwrite)
-        fs::write(file_path, &params.content)?;
+        fs::write(&file_path, &params.content)?;
 
         Ok(format!(
             "Successfully wrote content to file: {}",
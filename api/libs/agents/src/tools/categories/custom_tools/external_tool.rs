@@ -0,0 +1,253 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use database::{enums::CustomToolKind, models::CustomTool};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Client,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{custom_tool_name, mcp::McpSession, MAX_CUSTOM_TOOL_TIMEOUT};
+use crate::tools::ToolExecutor;
+
+/// Results longer than this are truncated before they reach the LLM
+const MAX_RESULT_CHARS: usize = 20_000;
+
+/// What a custom tool call returns to the agent
+///
+/// Failures are reported here rather than as errors so one misbehaving
+/// endpoint doesn't end the agent's run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomToolOutput {
+    /// The tool's display name, shown in reasoning messages
+    pub tool: String,
+    pub success: bool,
+    pub result: Option<Value>,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// An organization's custom tool, called over HTTP or MCP
+pub struct ExternalTool {
+    tool: CustomTool,
+    client: Client,
+}
+
+impl ExternalTool {
+    pub fn new(tool: CustomTool) -> Self {
+        Self {
+            tool,
+            client: Client::new(),
+        }
+    }
+
+    /// The configured timeout, capped at [`MAX_CUSTOM_TOOL_TIMEOUT`]
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.tool.timeout_ms.max(1) as u64).min(MAX_CUSTOM_TOOL_TIMEOUT)
+    }
+
+    async fn call(&self, arguments: Value) -> Result<Value> {
+        let headers = header_map(&self.tool.headers)?;
+
+        match self.tool.kind {
+            CustomToolKind::Http => {
+                let response = self
+                    .client
+                    .post(&self.tool.endpoint)
+                    .headers(headers)
+                    .json(&arguments)
+                    .send()
+                    .await
+                    .context("Failed to reach the tool endpoint")?;
+
+                let status = response.status();
+                let text = response.text().await?;
+                if !status.is_success() {
+                    bail!("Tool endpoint returned {}: {}", status, truncate(&text));
+                }
+
+                Ok(serde_json::from_str(&text).unwrap_or(Value::String(text)))
+            }
+            CustomToolKind::Mcp => {
+                let remote_name = self.tool.remote_name.as_deref().unwrap_or(&self.tool.name);
+                let session =
+                    McpSession::connect(self.client.clone(), &self.tool.endpoint, headers).await?;
+                session.call_tool(remote_name, arguments).await
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ToolExecutor for ExternalTool {
+    type Output = CustomToolOutput;
+    type Params = Value;
+
+    async fn execute(&self, params: Self::Params, _tool_call_id: String) -> Result<Self::Output> {
+        let start = Instant::now();
+        let timeout = self.timeout();
+
+        let outcome = match tokio::time::timeout(timeout, self.call(params)).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow!("Timed out after {} ms", timeout.as_millis())),
+        };
+
+        let duration_ms = start.elapsed().as_millis() as u64;
+        let output = match outcome {
+            Ok(result) => CustomToolOutput {
+                tool: self.tool.display_name.clone(),
+                success: true,
+                result: Some(limit_result(result)),
+                error: None,
+                duration_ms,
+            },
+            Err(e) => {
+                tracing::warn!("Custom tool {} failed: {:?}", self.tool.name, e);
+                CustomToolOutput {
+                    tool: self.tool.display_name.clone(),
+                    success: false,
+                    result: None,
+                    error: Some(e.to_string()),
+                    duration_ms,
+                }
+            }
+        };
+
+        Ok(output)
+    }
+
+    async fn get_schema(&self) -> Value {
+        serde_json::json!({
+            "name": self.get_name(),
+            "description": self.tool.description,
+            "parameters": self.tool.input_schema,
+        })
+    }
+
+    fn get_name(&self) -> String {
+        custom_tool_name(&self.tool.name)
+    }
+}
+
+/// Builds request headers from a tool's `headers` object of string values
+pub fn header_map(headers: &Value) -> Result<HeaderMap> {
+    let mut map = HeaderMap::new();
+    let Some(headers) = headers.as_object() else {
+        return Ok(map);
+    };
+
+    for (name, value) in headers {
+        let value = value
+            .as_str()
+            .ok_or_else(|| anyhow!("Header '{}' must be a string", name))?;
+        map.insert(
+            HeaderName::from_bytes(name.as_bytes())
+                .with_context(|| format!("Invalid header name '{}'", name))?,
+            HeaderValue::from_str(value)
+                .with_context(|| format!("Invalid value for header '{}'", name))?,
+        );
+    }
+
+    Ok(map)
+}
+
+fn truncate(text: &str) -> String {
+    match text.char_indices().nth(MAX_RESULT_CHARS) {
+        Some((end, _)) => format!("{}… [truncated]", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+fn limit_result(result: Value) -> Value {
+    let serialized = result.to_string();
+    if serialized.len() <= MAX_RESULT_CHARS {
+        result
+    } else {
+        Value::String(truncate(&serialized))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn http_tool(endpoint: String, timeout_ms: i32) -> CustomTool {
+        CustomTool {
+            id: Uuid::new_v4(),
+            organization_id: Uuid::new_v4(),
+            name: "fx_rates".to_string(),
+            display_name: "FX rates".to_string(),
+            description: "Looks up exchange rates".to_string(),
+            kind: CustomToolKind::Http,
+            endpoint,
+            remote_name: None,
+            input_schema: json!({ "type": "object", "properties": { "currency": { "type": "string" } } }),
+            headers: json!({ "Authorization": "Bearer secret" }),
+            allowed_roles: vec![],
+            timeout_ms,
+            enabled: true,
+            created_by: Uuid::new_v4(),
+            updated_by: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_tool_posts_arguments() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/rates")
+            .match_header("authorization", "Bearer secret")
+            .match_body(mockito::Matcher::Json(json!({ "currency": "EUR" })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"rate":1.08}"#)
+            .create_async()
+            .await;
+
+        let tool = ExternalTool::new(http_tool(format!("{}/rates", server.url()), 5_000));
+        assert_eq!(tool.get_name(), "custom_fx_rates");
+
+        let output = tool
+            .execute(json!({ "currency": "EUR" }), "call_1".to_string())
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert!(output.success);
+        assert_eq!(output.result, Some(json!({ "rate": 1.08 })));
+    }
+
+    #[tokio::test]
+    async fn test_http_tool_reports_failures() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/rates")
+            .with_status(502)
+            .with_body("upstream down")
+            .create_async()
+            .await;
+
+        let tool = ExternalTool::new(http_tool(format!("{}/rates", server.url()), 5_000));
+        let output = tool.execute(json!({}), "call_1".to_string()).await.unwrap();
+
+        assert!(!output.success);
+        assert_eq!(
+            output.error.as_deref(),
+            Some("Tool endpoint returned 502 Bad Gateway: upstream down")
+        );
+    }
+
+    #[test]
+    fn test_timeout_is_capped() {
+        let tool = ExternalTool::new(http_tool(String::new(), 10_000_000));
+        assert_eq!(tool.timeout(), MAX_CUSTOM_TOOL_TIMEOUT);
+    }
+}
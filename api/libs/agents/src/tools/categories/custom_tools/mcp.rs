@@ -0,0 +1,256 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::{anyhow, bail, Context, Result};
+use reqwest::{
+    header::{HeaderMap, HeaderValue, ACCEPT, CONTENT_TYPE},
+    Client,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+const PROTOCOL_VERSION: &str = "2025-03-26";
+const SESSION_HEADER: &str = "mcp-session-id";
+
+/// A tool advertised by an MCP server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpToolInfo {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default = "empty_object_schema")]
+    pub input_schema: Value,
+}
+
+fn empty_object_schema() -> Value {
+    json!({ "type": "object", "properties": {} })
+}
+
+/// An initialized session with an MCP server over the streamable HTTP
+/// transport
+///
+/// Servers may answer a request with plain JSON or with an event stream, so
+/// both are accepted.
+pub struct McpSession {
+    client: Client,
+    endpoint: String,
+    headers: HeaderMap,
+    session_id: Option<String>,
+    next_id: AtomicU64,
+}
+
+impl McpSession {
+    /// Performs the initialize handshake
+    pub async fn connect(client: Client, endpoint: &str, headers: HeaderMap) -> Result<Self> {
+        let mut session = Self {
+            client,
+            endpoint: endpoint.to_string(),
+            headers,
+            session_id: None,
+            next_id: AtomicU64::new(1),
+        };
+
+        let (result, session_id) = session
+            .send_request(
+                "initialize",
+                json!({
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": { "name": "buster", "version": env!("CARGO_PKG_VERSION") }
+                }),
+            )
+            .await
+            .context("MCP initialize failed")?;
+        session.session_id = session_id;
+        let server_name = result
+            .pointer("/serverInfo/name")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        tracing::debug!("Connected to MCP server {} ({})", endpoint, server_name);
+
+        session
+            .send_notification("notifications/initialized")
+            .await?;
+
+        Ok(session)
+    }
+
+    /// Lists the tools the server exposes
+    pub async fn list_tools(&self) -> Result<Vec<McpToolInfo>> {
+        let (result, _) = self.send_request("tools/list", json!({})).await?;
+        let tools = result.get("tools").cloned().unwrap_or(Value::Array(vec![]));
+
+        serde_json::from_value(tools).context("Invalid tools/list result from MCP server")
+    }
+
+    /// Calls a tool and returns its structured content, or its text content
+    /// when the server doesn't provide structured output
+    pub async fn call_tool(&self, name: &str, arguments: Value) -> Result<Value> {
+        let (result, _) = self
+            .send_request(
+                "tools/call",
+                json!({ "name": name, "arguments": arguments }),
+            )
+            .await?;
+
+        tool_result_value(result)
+    }
+
+    async fn send_request(&self, method: &str, params: Value) -> Result<(Value, Option<String>)> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let body = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+
+        let response = self
+            .post(&body)
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach MCP server for {}", method))?;
+
+        let status = response.status();
+        let session_id = response
+            .headers()
+            .get(SESSION_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let is_event_stream = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/event-stream"));
+        let text = response.text().await?;
+
+        if !status.is_success() {
+            bail!("MCP server returned {} for {}: {}", status, method, text);
+        }
+
+        let message = if is_event_stream {
+            find_event_stream_response(&text, id)
+                .ok_or_else(|| anyhow!("MCP server sent no response to {}", method))?
+        } else {
+            serde_json::from_str(&text)
+                .with_context(|| format!("Invalid JSON-RPC response to {}", method))?
+        };
+
+        if let Some(error) = message.get("error") {
+            bail!(
+                "MCP {} failed: {}",
+                method,
+                error
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown error")
+            );
+        }
+
+        let result = message
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow!("MCP response to {} has no result", method))?;
+
+        Ok((result, session_id))
+    }
+
+    async fn send_notification(&self, method: &str) -> Result<()> {
+        let body = json!({ "jsonrpc": "2.0", "method": method });
+        let response = self.post(&body).send().await?;
+        if !response.status().is_success() {
+            bail!("MCP server returned {} for {}", response.status(), method);
+        }
+        Ok(())
+    }
+
+    fn post(&self, body: &Value) -> reqwest::RequestBuilder {
+        let mut request = self
+            .client
+            .post(&self.endpoint)
+            .headers(self.headers.clone())
+            .header(
+                ACCEPT,
+                HeaderValue::from_static("application/json, text/event-stream"),
+            )
+            .json(body);
+        if let Some(session_id) = &self.session_id {
+            request = request.header(SESSION_HEADER, session_id);
+        }
+        request
+    }
+}
+
+/// Finds the JSON-RPC response with `id` among the events of an SSE body
+fn find_event_stream_response(body: &str, id: u64) -> Option<Value> {
+    body.lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .filter_map(|data| serde_json::from_str::<Value>(data.trim()).ok())
+        .find(|message| message.get("id").and_then(Value::as_u64) == Some(id))
+}
+
+fn tool_result_value(result: Value) -> Result<Value> {
+    let text = result
+        .get("content")
+        .and_then(Value::as_array)
+        .map(|content| {
+            content
+                .iter()
+                .filter_map(|item| item.get("text").and_then(Value::as_str))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default();
+
+    if result
+        .get("isError")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+    {
+        bail!(
+            "{}",
+            if text.is_empty() {
+                "Tool reported an error"
+            } else {
+                &text
+            }
+        );
+    }
+
+    if let Some(structured) = result.get("structuredContent") {
+        return Ok(structured.clone());
+    }
+
+    Ok(serde_json::from_str(&text).unwrap_or(Value::String(text)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_event_stream_response() {
+        let body = "event: message\ndata: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/progress\"}\n\nevent: message\ndata: {\"jsonrpc\":\"2.0\",\"id\":2,\"result\":{\"ok\":true}}\n\n";
+
+        let message = find_event_stream_response(body, 2).unwrap();
+        assert_eq!(message["result"]["ok"], true);
+        assert!(find_event_stream_response(body, 3).is_none());
+    }
+
+    #[test]
+    fn test_tool_result_value() {
+        let structured = json!({
+            "content": [{ "type": "text", "text": "{\"rate\":1.1}" }],
+            "structuredContent": { "rate": 1.1 }
+        });
+        assert_eq!(
+            tool_result_value(structured).unwrap(),
+            json!({ "rate": 1.1 })
+        );
+
+        let text = json!({ "content": [{ "type": "text", "text": "EUR is up" }] });
+        assert_eq!(tool_result_value(text).unwrap(), json!("EUR is up"));
+
+        let error =
+            json!({ "content": [{ "type": "text", "text": "unknown currency" }], "isError": true });
+        assert_eq!(
+            tool_result_value(error).unwrap_err().to_string(),
+            "unknown currency"
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! Tools an organization registers at runtime instead of compiling in
+//!
+//! Each enabled `custom_tools` row the user's role allows becomes an
+//! [`ExternalTool`], reached either with a plain HTTP POST or through a Model
+//! Context Protocol server. Registered names carry [`CUSTOM_TOOL_PREFIX`] so
+//! they can't shadow built-in tools and can be recognized when streaming.
+
+mod external_tool;
+pub mod mcp;
+
+use std::time::Duration;
+
+pub use external_tool::{header_map, CustomToolOutput, ExternalTool};
+pub use mcp::{McpSession, McpToolInfo};
+
+/// Prefix of every custom tool's name as seen by the LLM
+pub const CUSTOM_TOOL_PREFIX: &str = "custom_";
+
+/// Upper bound on a custom tool's configured timeout
+pub const MAX_CUSTOM_TOOL_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Longest name an organization may give a tool, leaving room for the prefix
+/// within the 64 characters LLM providers accept
+pub const MAX_CUSTOM_TOOL_NAME_LEN: usize = 48;
+
+/// The name a custom tool is registered and called under
+pub fn custom_tool_name(name: &str) -> String {
+    format!("{}{}", CUSTOM_TOOL_PREFIX, name)
+}
+
+/// Whether a tool call targets a custom tool
+pub fn is_custom_tool(tool_name: &str) -> bool {
+    tool_name.starts_with(CUSTOM_TOOL_PREFIX)
+}
+
+/// Tool names are lowercase snake case so they are valid function names for
+/// every provider
+pub fn is_valid_custom_tool_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_CUSTOM_TOOL_NAME_LEN
+        && name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_tool_names() {
+        assert_eq!(custom_tool_name("fx_rates"), "custom_fx_rates");
+        assert!(is_custom_tool("custom_fx_rates"));
+        assert!(!is_custom_tool("create_metrics"));
+
+        assert!(is_valid_custom_tool_name("fx_rates_v2"));
+        assert!(!is_valid_custom_tool_name(""));
+        assert!(!is_valid_custom_tool_name("2fx"));
+        assert!(!is_valid_custom_tool_name("fx-rates"));
+        assert!(!is_valid_custom_tool_name("FxRates"));
+        assert!(!is_valid_custom_tool_name(
+            &"a".repeat(MAX_CUSTOM_TOOL_NAME_LEN + 1)
+        ));
+    }
+}
@@ -5,24 +5,36 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use braintrust::{get_prompt_system_message, BraintrustClient};
 use chrono::{DateTime, Utc};
-use cohere_rust::{
-    api::rerank::{ReRankModel, ReRankRequest},
-    Cohere,
+use database::{
+    enums::ModelPurpose,
+    helpers::{
+        model_configs::get_user_models, terms::get_user_terms,
+        verified_examples::get_user_examples,
+    },
+    pool::get_pg_pool,
+    schema::datasets,
 };
-use database::{pool::get_pg_pool, schema::datasets};
 use diesel::prelude::*;
 use diesel_async::RunQueryDsl;
 use futures::stream::{self, StreamExt};
-use litellm::{AgentMessage, ChatCompletionRequest, EmbeddingRequest, LiteLLMClient, Metadata, ResponseFormat};
+use litellm::{AgentMessage, ChatCompletionRequest, embedding_dimensions, EmbeddingRequest, LiteLLMClient, Metadata, ResponseFormat};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
+use dataset_retrieval::{HybridRetriever, RetrievalDocument, Retriever};
 use dataset_security::{get_permissioned_datasets, PermissionedDataset};
 use sqlx::PgPool;
 use stored_values;
 
-use crate::{agent::Agent, tools::ToolExecutor};
+use crate::{
+    agent::Agent,
+    agents::modes::knowledge_base::{
+        rank_examples, rank_terms, KnowledgeExample, KnowledgeTerm, KNOWLEDGE_TERMS_STATE_KEY,
+        MAX_KNOWLEDGE_TERMS, MAX_VERIFIED_EXAMPLES, VERIFIED_EXAMPLES_STATE_KEY,
+    },
+    tools::ToolExecutor,
+};
 
 // NEW: Structure to represent found values with their source information
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
@@ -48,6 +60,12 @@ pub struct SearchDataCatalogOutput {
     pub value_search_terms: Option<Vec<String>>,
     pub duration: i64,
     pub results: Vec<DatasetSearchResult>,
+    /// Glossary terms from the organization's knowledge base relevant to the search
+    #[serde(default)]
+    pub terms: Vec<KnowledgeTerm>,
+    /// Verified question and SQL pairs similar to the request
+    #[serde(default)]
+    pub examples: Vec<KnowledgeExample>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
@@ -78,13 +96,13 @@ struct SearchableDimension {
 }
 
 // NEW: Helper function to generate embeddings for search terms
-async fn generate_embedding_for_text(text: &str) -> Result<Vec<f32>> {
+async fn generate_embedding_for_text(text: &str, model: &str) -> Result<Vec<f32>> {
     let litellm_client = LiteLLMClient::new(None, None);
     
     let embedding_request = EmbeddingRequest {
-        model: "text-embedding-3-small".to_string(),
+        model: model.to_string(),
         input: vec![text.to_string()], // Single input as a vector
-        dimensions: Some(1536),
+        dimensions: Some(embedding_dimensions(model)),
         encoding_format: Some("float".to_string()),
         user: None,
     };
@@ -320,6 +338,12 @@ impl ToolExecutor for SearchDataCatalogTool {
         let user_id = self.agent.get_user_id();
         let session_id = self.agent.get_session_id();
 
+        // Models configured for the user's organization
+        let models = get_user_models(&user_id).await?;
+        let filter_model = models.model_for(ModelPurpose::CatalogFiltering);
+        let embedding_model = models.model_for(ModelPurpose::Embeddings);
+        let organization_id = models.organization_id();
+
         let specific_queries = params.specific_queries.clone().unwrap_or_default();
         let exploratory_topics = params.exploratory_topics.clone().unwrap_or_default();
         
@@ -345,8 +369,9 @@ impl ToolExecutor for SearchDataCatalogTool {
         let user_prompt_clone = user_prompt_str.clone();
         let user_id_clone = user_id.clone();
         let session_id_clone = session_id.clone();
+        let extraction_model = filter_model.clone();
         let value_search_terms_future = tokio::spawn(async move {
-            extract_value_search_terms(user_prompt_clone, user_id_clone, session_id_clone).await
+            extract_value_search_terms(user_prompt_clone, user_id_clone, session_id_clone, organization_id, extraction_model).await
         });
         
         // 2. Begin fetching datasets concurrently
@@ -367,6 +392,8 @@ impl ToolExecutor for SearchDataCatalogTool {
                     value_search_terms: Some(vec![]),
                     duration: start_time.elapsed().as_millis() as i64,
                     results: vec![],
+                    terms: vec![],
+                    examples: vec![],
                 });
             }
         };
@@ -380,6 +407,8 @@ impl ToolExecutor for SearchDataCatalogTool {
                 value_search_terms: Some(vec![]),
                 duration: start_time.elapsed().as_millis() as i64,
                 results: vec![],
+                terms: vec![],
+                examples: vec![],
             });
         }
 
@@ -395,6 +424,8 @@ impl ToolExecutor for SearchDataCatalogTool {
                     value_search_terms: Some(vec![]),
                     duration: start_time.elapsed().as_millis() as i64,
                     results: vec![],
+                    terms: vec![],
+                    examples: vec![],
                 });
             }
         };
@@ -402,9 +433,15 @@ impl ToolExecutor for SearchDataCatalogTool {
         debug!(data_source_id = %target_data_source_id, "Identified target data source ID for value search");
 
         // Prepare documents from datasets
-        let documents: Vec<String> = all_datasets
+        let documents: Vec<RetrievalDocument> = all_datasets
             .iter()
-            .filter_map(|dataset| dataset.yml_content.clone())
+            .filter_map(|dataset| {
+                dataset.yml_content.as_ref().map(|yml| RetrievalDocument {
+                    id: dataset.id,
+                    name: dataset.name.clone(),
+                    content: yml.clone(),
+                })
+            })
             .collect();
 
         if documents.is_empty() {
@@ -416,12 +453,15 @@ impl ToolExecutor for SearchDataCatalogTool {
                 value_search_terms: Some(vec![]),
                 duration: start_time.elapsed().as_millis() as i64,
                 results: vec![],
+                terms: vec![],
+                examples: vec![],
             });
         }
 
-        // 3. Start reranking tasks concurrently (for specific queries and exploratory topics)
+        // 3. Start retrieval tasks concurrently (for specific queries and exploratory topics)
         // We'll use the user prompt for the LLM filtering
         let user_prompt_for_task = user_prompt_str.clone();
+        let retriever = Arc::new(HybridRetriever::from_env(&embedding_model));
         
         // 3a. Start specific query reranking
         let specific_rerank_futures = stream::iter(specific_queries.clone())
@@ -429,12 +469,13 @@ impl ToolExecutor for SearchDataCatalogTool {
                 let current_query = query.clone();
                 let datasets_clone = all_datasets.clone();
                 let documents_clone = documents.clone();
+                let retriever = retriever.clone();
 
                 async move {
-                    let ranked = match rerank_datasets(&current_query, &datasets_clone, &documents_clone).await {
+                    let ranked = match rerank_datasets(retriever.as_ref(), &current_query, &datasets_clone, &documents_clone).await {
                         Ok(r) => r,
                         Err(e) => {
-                            error!(error = %e, query = current_query, "Reranking failed for specific query");
+                            error!(error = %e, query = current_query, "Retrieval failed for specific query");
                             Vec::new()
                         }
                     };
@@ -450,12 +491,13 @@ impl ToolExecutor for SearchDataCatalogTool {
                 let current_topic = topic.clone();
                 let datasets_clone = all_datasets.clone();
                 let documents_clone = documents.clone();
+                let retriever = retriever.clone();
 
                 async move {
-                    let ranked = match rerank_datasets(&current_topic, &datasets_clone, &documents_clone).await {
+                    let ranked = match rerank_datasets(retriever.as_ref(), &current_topic, &datasets_clone, &documents_clone).await {
                         Ok(r) => r,
                         Err(e) => {
-                            error!(error = %e, topic = current_topic, "Reranking failed for exploratory topic");
+                            error!(error = %e, topic = current_topic, "Retrieval failed for exploratory topic");
                             Vec::new()
                         }
                     };
@@ -494,14 +536,17 @@ impl ToolExecutor for SearchDataCatalogTool {
                 value_search_terms: Some(valid_value_search_terms.clone()), // Return the filtered list
                 duration: start_time.elapsed().as_millis() as i64,
                 results: vec![],
+                terms: vec![],
+                examples: vec![],
             });
         }
 
         let embedding_terms = valid_value_search_terms.clone();
+        let batch_embedding_model = embedding_model.clone();
 
         // 4. Generate embeddings for all valid terms concurrently using batching
         let embedding_batch_future = tokio::spawn(async move {
-            generate_embeddings_batch(embedding_terms).await
+            generate_embeddings_batch(embedding_terms, &batch_embedding_model).await
         });
 
         // Await the batch embedding generation
@@ -580,13 +625,14 @@ impl ToolExecutor for SearchDataCatalogTool {
                 let session_id_clone = session_id.clone();
                 let prompt_clone = user_prompt_for_task.clone();
                 let values_clone = all_found_values.clone();
+                let model_clone = filter_model.clone();
 
                 async move {
                     if ranked.is_empty() {
                         return Ok(vec![]);
                     }
                     
-                    match filter_specific_datasets_with_llm(&query, &prompt_clone, ranked, &user_id_clone, &session_id_clone, &values_clone).await {
+                    match filter_specific_datasets_with_llm(&query, &prompt_clone, ranked, &user_id_clone, &session_id_clone, organization_id, &values_clone, &model_clone).await {
                         Ok(filtered) => Ok(filtered),
                         Err(e) => {
                             error!(error = %e, query = query, "LLM filtering failed for specific query");
@@ -603,13 +649,14 @@ impl ToolExecutor for SearchDataCatalogTool {
                 let session_id_clone = session_id.clone();
                 let prompt_clone = user_prompt_for_task.clone();
                 let values_clone = all_found_values.clone();
+                let model_clone = filter_model.clone();
 
                 async move {
                     if ranked.is_empty() {
                         return Ok(vec![]);
                     }
                     
-                    match filter_exploratory_datasets_with_llm(&topic, &prompt_clone, ranked, &user_id_clone, &session_id_clone, &values_clone).await {
+                    match filter_exploratory_datasets_with_llm(&topic, &prompt_clone, ranked, &user_id_clone, &session_id_clone, organization_id, &values_clone, &model_clone).await {
                         Ok(filtered) => Ok(filtered),
                         Err(e) => {
                             error!(error = %e, topic = topic, "LLM filtering failed for exploratory topic");
@@ -681,7 +728,8 @@ impl ToolExecutor for SearchDataCatalogTool {
                     target_data_source_id,
                     &user_prompt_str,
                     &user_id,
-                    &session_id
+                    &session_id,
+                    &embedding_model,
                 ).await {
                     Ok(updated_yml) => {
                         debug!(
@@ -721,6 +769,42 @@ impl ToolExecutor for SearchDataCatalogTool {
             .set_state_value(String::from("searched_data_catalog"), Value::Bool(true))
             .await;
 
+        // Glossary terms matching the request or linked to the datasets found
+        let knowledge_query = std::iter::once(user_prompt_str.as_str())
+            .chain(specific_queries.iter().map(String::as_str))
+            .chain(exploratory_topics.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let found_dataset_ids: HashSet<Uuid> = updated_results.iter().map(|result| result.id).collect();
+        let terms = match get_user_terms(&user_id).await {
+            Ok(terms) => rank_terms(&knowledge_query, &terms, &found_dataset_ids, MAX_KNOWLEDGE_TERMS),
+            Err(e) => {
+                warn!(error = %e, "Failed to load the knowledge base; continuing without glossary terms");
+                vec![]
+            }
+        };
+        self.agent
+            .set_state_value(
+                String::from(KNOWLEDGE_TERMS_STATE_KEY),
+                serde_json::to_value(&terms).unwrap_or(Value::Null),
+            )
+            .await;
+
+        // Verified examples for similar questions, used as few-shot examples
+        let examples = match get_user_examples(&user_id).await {
+            Ok(examples) => rank_examples(&user_prompt_str, &examples, MAX_VERIFIED_EXAMPLES),
+            Err(e) => {
+                warn!(error = %e, "Failed to load verified examples; continuing without them");
+                vec![]
+            }
+        };
+        self.agent
+            .set_state_value(
+                String::from(VERIFIED_EXAMPLES_STATE_KEY),
+                serde_json::to_value(&examples).unwrap_or(Value::Null),
+            )
+            .await;
+
         let duration = start_time.elapsed().as_millis();
 
         Ok(SearchDataCatalogOutput {
@@ -730,6 +814,8 @@ impl ToolExecutor for SearchDataCatalogTool {
             value_search_terms: Some(valid_value_search_terms),
             duration: duration as i64,
             results: updated_results,  // Use updated results instead of final_search_results
+            terms,
+            examples,
         })
     }
 
@@ -785,51 +871,38 @@ async fn get_search_data_catalog_description() -> String {
     }
 }
 
+/// Ranks the datasets most relevant to `query` with the configured retriever
 async fn rerank_datasets(
+    retriever: &dyn Retriever,
     query: &str,
     all_datasets: &[PermissionedDataset],
-    documents: &[String],
+    documents: &[RetrievalDocument],
 ) -> Result<Vec<RankedDataset>, anyhow::Error> {
     if documents.is_empty() || all_datasets.is_empty() {
         return Ok(vec![]);
     }
-    let co = Cohere::default();
-
-    let request = ReRankRequest {
-        query,
-        documents,
-        model: ReRankModel::EnglishV3,
-        top_n: Some(35),
-        ..Default::default()
-    };
 
-    let rerank_results = match co.rerank(&request).await {
-        Ok(results) => results,
-        Err(e) => {
-            error!(error = %e, query = query, "Cohere rerank API call failed");
-            return Err(anyhow::anyhow!("Cohere rerank failed: {}", e));
-        }
-    };
+    let retrieved = retriever
+        .retrieve(query, documents, 35)
+        .await
+        .map_err(|e| {
+            error!(error = %e, query = query, "Dataset retrieval failed");
+            e
+        })?;
 
-    let mut ranked_datasets = Vec::new();
-    for result in rerank_results {
-        if let Some(dataset) = all_datasets.get(result.index as usize) {
-            ranked_datasets.push(RankedDataset {
-                dataset: dataset.clone(),
-            });
-        } else {
-            error!(
-                "Invalid dataset index {} from Cohere for query '{}'. Max index: {}",
-                result.index,
-                query,
-                all_datasets.len() - 1
-            );
-        }
-    }
+    let datasets_by_id: HashMap<Uuid, &PermissionedDataset> =
+        all_datasets.iter().map(|dataset| (dataset.id, dataset)).collect();
 
-    let relevant_datasets = ranked_datasets.into_iter().collect::<Vec<_>>();
+    let ranked_datasets = retrieved
+        .into_iter()
+        .filter_map(|scored| {
+            datasets_by_id.get(&scored.id).map(|dataset| RankedDataset {
+                dataset: (*dataset).clone(),
+            })
+        })
+        .collect();
 
-    Ok(relevant_datasets)
+    Ok(ranked_datasets)
 }
 
 async fn llm_filter_helper(
@@ -839,8 +912,10 @@ async fn llm_filter_helper(
     ranked_datasets: Vec<RankedDataset>,
     user_id: &Uuid,
     session_id: &Uuid,
+    organization_id: Option<Uuid>,
     generation_name_suffix: &str,
     all_found_values: &[FoundValueInfo],
+    model: &str,
 ) -> Result<Vec<DatasetResult>, anyhow::Error> {
     if ranked_datasets.is_empty() {
         return Ok(vec![]);
@@ -888,7 +963,7 @@ async fn llm_filter_helper(
     let llm_client = LiteLLMClient::new(None, None);
 
     let request = ChatCompletionRequest {
-        model: "gemini-2.0-flash-001".to_string(),
+        model: model.to_string(),
         messages: vec![AgentMessage::User {
             id: None,
             content: prompt,
@@ -904,6 +979,8 @@ async fn llm_filter_helper(
             user_id: user_id.to_string(),
             session_id: session_id.to_string(),
             trace_id: Uuid::new_v4().to_string(),
+            message_id: None,
+            organization_id: organization_id.map(|id| id.to_string()),
         }),
         max_completion_tokens: Some(8096),
         temperature: Some(0.0),
@@ -974,13 +1051,16 @@ async fn llm_filter_helper(
     Ok(filtered_datasets)
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn filter_specific_datasets_with_llm(
     query: &str,
     user_prompt: &str,
     ranked_datasets: Vec<RankedDataset>,
     user_id: &Uuid,
     session_id: &Uuid,
+    organization_id: Option<Uuid>,
     all_found_values: &[FoundValueInfo],
+    model: &str,
 ) -> Result<Vec<DatasetResult>, anyhow::Error> {
     debug!(
         "Filtering {} datasets with SPECIFIC LLM for query: {}",
@@ -994,18 +1074,23 @@ async fn filter_specific_datasets_with_llm(
         ranked_datasets,
         user_id,
         session_id,
+        organization_id,
         "specific",
-        all_found_values
+        all_found_values,
+        model,
     ).await
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn filter_exploratory_datasets_with_llm(
     topic: &str,
     user_prompt: &str,
     ranked_datasets: Vec<RankedDataset>,
     user_id: &Uuid,
     session_id: &Uuid,
+    organization_id: Option<Uuid>,
     all_found_values: &[FoundValueInfo],
+    model: &str,
 ) -> Result<Vec<DatasetResult>, anyhow::Error> {
     debug!(
         "Filtering {} datasets with EXPLORATORY LLM for topic: {}",
@@ -1019,13 +1104,21 @@ async fn filter_exploratory_datasets_with_llm(
         ranked_datasets,
         user_id,
         session_id,
+        organization_id,
         "exploratory",
-        all_found_values
+        all_found_values,
+        model,
     ).await
 }
 
 // NEW: Extract potential column values from user query using Gemini
-async fn extract_value_search_terms(user_request: String, user_id: Uuid, session_id: Uuid) -> Result<Vec<String>> {
+async fn extract_value_search_terms(
+    user_request: String,
+    user_id: Uuid,
+    session_id: Uuid,
+    organization_id: Option<Uuid>,
+    model: String,
+) -> Result<Vec<String>> {
     debug!("Extracting potential value search terms from user request");
     
     let prompt = r#"
@@ -1067,7 +1160,7 @@ Example response formats:
     let llm_client = LiteLLMClient::new(None, None);
 
     let request = ChatCompletionRequest {
-        model: "gemini-2.0-flash-001".to_string(),
+        model: model.to_string(),
         messages: vec![AgentMessage::User {
             id: None,
             content: prompt,
@@ -1088,6 +1181,8 @@ Example response formats:
             user_id: user_id.to_string(),
             session_id: session_id.to_string(),
             trace_id: Uuid::new_v4().to_string(),
+            message_id: None,
+            organization_id: organization_id.map(|id| id.to_string()),
         }),
         max_completion_tokens: Some(2048),
         temperature: Some(0.0),
@@ -1145,7 +1240,7 @@ Example response formats:
 }
 
 // NEW: Helper function to generate embeddings for multiple texts in a batch
-async fn generate_embeddings_batch(texts: Vec<String>) -> Result<Vec<(String, Vec<f32>)>> {
+async fn generate_embeddings_batch(texts: Vec<String>, model: &str) -> Result<Vec<(String, Vec<f32>)>> {
     if texts.is_empty() {
         return Ok(vec![]);
     }
@@ -1153,9 +1248,9 @@ async fn generate_embeddings_batch(texts: Vec<String>) -> Result<Vec<(String, Ve
     let litellm_client = LiteLLMClient::new(None, None);
     
     let embedding_request = EmbeddingRequest {
-        model: "text-embedding-3-small".to_string(),
+        model: model.to_string(),
         input: texts.clone(), // Pass all texts to the API
-        dimensions: Some(1536),
+        dimensions: Some(embedding_dimensions(model)),
         encoding_format: Some("float".to_string()),
         user: None,
     };
@@ -1309,6 +1404,7 @@ async fn search_and_update_yml(
     user_query: &str,
     user_id: &Uuid,
     session_id: &Uuid,
+    embedding_model: &str,
 ) -> Result<String> {
     // Extract searchable dimensions
     let searchable_dimensions = extract_searchable_dimensions(yml_content)?;
@@ -1319,7 +1415,7 @@ async fn search_and_update_yml(
     }
     
     // Generate embedding for user query
-    let embedding = generate_embedding_for_text(user_query).await?;
+    let embedding = generate_embedding_for_text(user_query, embedding_model).await?;
     
     // Extract database structure from YAML - this is now model-based
     let database_info = extract_database_info_from_yaml(yml_content)?;
@@ -2,6 +2,7 @@
 //!
 //! This module organizes tools into logical categories:
 //! - agents_as_tools: Tools that expose agent capabilities as tools for other agents
+//! - custom_tools: Organization-registered tools called over HTTP or MCP
 //! - data_tools: Tools for data manipulation, transformation, and analysis
 //! - file_tools: Tools for file system operations and file manipulation
 //! - interaction_tools: Tools for user interaction and UI manipulation
@@ -12,4 +13,5 @@ pub mod planning_tools;
 
 pub mod response_tools;
 pub mod cli_tools;
+pub mod custom_tools;
 pub mod utility_tools; 
\ No newline at end of file
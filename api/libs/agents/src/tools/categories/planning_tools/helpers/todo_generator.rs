@@ -1,4 +1,5 @@
 use anyhow::Result;
+use database::{enums::ModelPurpose, helpers::model_configs::get_user_models};
 use litellm::{AgentMessage, ChatCompletionRequest, LiteLLMClient, Metadata, ResponseFormat};
 use serde_json::Value;
 use tracing::{error, warn};
@@ -61,8 +62,13 @@ Example Output for the above plan: `["Create line chart visualization 'Daily Tra
         plan
     );
 
+    // Todo extraction is part of planning
+    let models = get_user_models(&user_id).await?;
+    let model = models.model_for(ModelPurpose::Planning);
+    let llm_client = llm_client.with_approved_fallbacks(&models.approved_models());
+
     let request = ChatCompletionRequest {
-        model: "gemini-2.0-flash-001".to_string(),
+        model,
         messages: vec![AgentMessage::User { id: None, content: prompt, name: None }],
         stream: Some(false),
         response_format: Some(ResponseFormat { type_: "json_object".to_string(), json_schema: None }),
@@ -71,6 +77,8 @@ Example Output for the above plan: `["Create line chart visualization 'Daily Tra
             user_id: user_id.to_string(),
             session_id: session_id.to_string(),
             trace_id: Uuid::new_v4().to_string(),
+            message_id: None,
+            organization_id: models.organization_id().map(|id| id.to_string()),
         }),
         max_completion_tokens: Some(1024),
         temperature: Some(0.0),
@@ -12,4 +12,5 @@ pub use executor::{ToolExecutor, ToolCallExecutor, IntoToolCallExecutor};
 pub use categories::file_tools;
 pub use categories::planning_tools;
 pub use categories::cli_tools;
+pub use categories::custom_tools;
 
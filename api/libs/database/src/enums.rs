@@ -533,6 +533,499 @@ impl DataSourceType {
     }
 }
 
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    diesel::AsExpression,
+    diesel::FromSqlRow,
+)]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelPurpose {
+    Initialization,
+    DataCatalogSearch,
+    Planning,
+    Analysis,
+    Review,
+    CatalogFiltering,
+    TitleGeneration,
+    Embeddings,
+    ContextCompaction,
+}
+
+impl ModelPurpose {
+    pub const ALL: [ModelPurpose; 9] = [
+        ModelPurpose::Initialization,
+        ModelPurpose::DataCatalogSearch,
+        ModelPurpose::Planning,
+        ModelPurpose::Analysis,
+        ModelPurpose::Review,
+        ModelPurpose::CatalogFiltering,
+        ModelPurpose::TitleGeneration,
+        ModelPurpose::Embeddings,
+        ModelPurpose::ContextCompaction,
+    ];
+
+    pub fn try_from_str(s: &str) -> Option<Self> {
+        match s {
+            "initialization" => Some(ModelPurpose::Initialization),
+            "data_catalog_search" => Some(ModelPurpose::DataCatalogSearch),
+            "planning" => Some(ModelPurpose::Planning),
+            "analysis" => Some(ModelPurpose::Analysis),
+            "review" => Some(ModelPurpose::Review),
+            "catalog_filtering" => Some(ModelPurpose::CatalogFiltering),
+            "title_generation" => Some(ModelPurpose::TitleGeneration),
+            "embeddings" => Some(ModelPurpose::Embeddings),
+            "context_compaction" => Some(ModelPurpose::ContextCompaction),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match *self {
+            ModelPurpose::Initialization => "initialization",
+            ModelPurpose::DataCatalogSearch => "data_catalog_search",
+            ModelPurpose::Planning => "planning",
+            ModelPurpose::Analysis => "analysis",
+            ModelPurpose::Review => "review",
+            ModelPurpose::CatalogFiltering => "catalog_filtering",
+            ModelPurpose::TitleGeneration => "title_generation",
+            ModelPurpose::Embeddings => "embeddings",
+            ModelPurpose::ContextCompaction => "context_compaction",
+        }
+    }
+
+    /// The model used when an organization hasn't configured one
+    pub fn default_model(&self) -> &'static str {
+        match *self {
+            ModelPurpose::Initialization
+            | ModelPurpose::DataCatalogSearch
+            | ModelPurpose::Planning
+            | ModelPurpose::Analysis => "o4-mini",
+            ModelPurpose::Review
+            | ModelPurpose::CatalogFiltering
+            | ModelPurpose::TitleGeneration
+            | ModelPurpose::ContextCompaction => "gemini-2.0-flash-001",
+            ModelPurpose::Embeddings => "text-embedding-3-small",
+        }
+    }
+}
+
+impl FromStr for ModelPurpose {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_str(s).ok_or_else(|| format!("Invalid ModelPurpose: {}", s))
+    }
+}
+
+impl ToSql<Text, Pg> for ModelPurpose {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.to_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for ModelPurpose {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let s = std::str::from_utf8(bytes.as_bytes())?;
+        Self::try_from_str(s).ok_or_else(|| "Unrecognized ModelPurpose".into())
+    }
+}
+
+/// How the agent reaches an organization's custom tool
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    diesel::AsExpression,
+    diesel::FromSqlRow,
+)]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomToolKind {
+    /// The arguments are POSTed as JSON to the endpoint
+    Http,
+    /// A tool exposed by a Model Context Protocol server
+    Mcp,
+}
+
+impl CustomToolKind {
+    pub fn to_str(&self) -> &'static str {
+        match *self {
+            CustomToolKind::Http => "http",
+            CustomToolKind::Mcp => "mcp",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for CustomToolKind {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.to_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for CustomToolKind {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match bytes.as_bytes() {
+            b"http" => Ok(CustomToolKind::Http),
+            b"mcp" => Ok(CustomToolKind::Mcp),
+            _ => Err("Unrecognized CustomToolKind".into()),
+        }
+    }
+}
+
+/// What a knowledge base entry tells the agent
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    diesel::AsExpression,
+    diesel::FromSqlRow,
+)]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "snake_case")]
+pub enum TermKind {
+    /// A glossary definition, given to the agent when relevant to the question
+    Term,
+    /// A free-form rule the agent always follows
+    Instruction,
+}
+
+impl TermKind {
+    pub fn to_str(&self) -> &'static str {
+        match *self {
+            TermKind::Term => "term",
+            TermKind::Instruction => "instruction",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for TermKind {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.to_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for TermKind {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match bytes.as_bytes() {
+            b"term" => Ok(TermKind::Term),
+            b"instruction" => Ok(TermKind::Instruction),
+            _ => Err("Unrecognized TermKind".into()),
+        }
+    }
+}
+
+/// A user's verdict on an answer
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    diesel::AsExpression,
+    diesel::FromSqlRow,
+)]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackRating {
+    /// The answer was right
+    Positive,
+    /// The answer was wrong or unhelpful
+    Negative,
+}
+
+impl FeedbackRating {
+    pub fn to_str(&self) -> &'static str {
+        match *self {
+            FeedbackRating::Positive => "positive",
+            FeedbackRating::Negative => "negative",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for FeedbackRating {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.to_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for FeedbackRating {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match bytes.as_bytes() {
+            b"positive" => Ok(FeedbackRating::Positive),
+            b"negative" => Ok(FeedbackRating::Negative),
+            _ => Err("Unrecognized FeedbackRating".into()),
+        }
+    }
+}
+
+/// What was wrong with an answer
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    diesel::AsExpression,
+    diesel::FromSqlRow,
+)]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackCategory {
+    /// The numbers or the query were wrong
+    WrongData,
+    /// The data was right but shown the wrong way
+    WrongVisualization,
+    /// The agent answered a different question
+    MisunderstoodQuestion,
+    /// Part of the question was left unanswered
+    Incomplete,
+    /// Anything else
+    Other,
+}
+
+impl FeedbackCategory {
+    pub fn to_str(&self) -> &'static str {
+        match *self {
+            FeedbackCategory::WrongData => "wrong_data",
+            FeedbackCategory::WrongVisualization => "wrong_visualization",
+            FeedbackCategory::MisunderstoodQuestion => "misunderstood_question",
+            FeedbackCategory::Incomplete => "incomplete",
+            FeedbackCategory::Other => "other",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for FeedbackCategory {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.to_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for FeedbackCategory {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match bytes.as_bytes() {
+            b"wrong_data" => Ok(FeedbackCategory::WrongData),
+            b"wrong_visualization" => Ok(FeedbackCategory::WrongVisualization),
+            b"misunderstood_question" => Ok(FeedbackCategory::MisunderstoodQuestion),
+            b"incomplete" => Ok(FeedbackCategory::Incomplete),
+            b"other" => Ok(FeedbackCategory::Other),
+            _ => Err("Unrecognized FeedbackCategory".into()),
+        }
+    }
+}
+
+/// Where a piece of feedback is in the review queue
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    diesel::AsExpression,
+    diesel::FromSqlRow,
+)]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackStatus {
+    /// Waiting in the review queue
+    Pending,
+    /// Turned into a verified example
+    Promoted,
+    /// Reviewed and not used
+    Dismissed,
+}
+
+impl FeedbackStatus {
+    pub fn to_str(&self) -> &'static str {
+        match *self {
+            FeedbackStatus::Pending => "pending",
+            FeedbackStatus::Promoted => "promoted",
+            FeedbackStatus::Dismissed => "dismissed",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for FeedbackStatus {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.to_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for FeedbackStatus {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match bytes.as_bytes() {
+            b"pending" => Ok(FeedbackStatus::Pending),
+            b"promoted" => Ok(FeedbackStatus::Promoted),
+            b"dismissed" => Ok(FeedbackStatus::Dismissed),
+            _ => Err("Unrecognized FeedbackStatus".into()),
+        }
+    }
+}
+
+/// An asset lifecycle event organizations can subscribe webhooks to
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    diesel::AsExpression,
+    diesel::FromSqlRow,
+)]
+#[diesel(sql_type = Text)]
+pub enum WebhookEventType {
+    #[serde(rename = "metric.created")]
+    MetricCreated,
+    #[serde(rename = "metric.updated")]
+    MetricUpdated,
+    #[serde(rename = "metric.verified")]
+    MetricVerified,
+    #[serde(rename = "dashboard.created")]
+    DashboardCreated,
+    #[serde(rename = "dashboard.shared")]
+    DashboardShared,
+    #[serde(rename = "dataset.deployed")]
+    DatasetDeployed,
+    #[serde(rename = "chat.completed")]
+    ChatCompleted,
+    #[serde(rename = "data_source.created")]
+    DataSourceCreated,
+    #[serde(rename = "data_source.updated")]
+    DataSourceUpdated,
+    #[serde(rename = "data_source.deleted")]
+    DataSourceDeleted,
+}
+
+impl WebhookEventType {
+    pub fn to_str(&self) -> &'static str {
+        match *self {
+            WebhookEventType::MetricCreated => "metric.created",
+            WebhookEventType::MetricUpdated => "metric.updated",
+            WebhookEventType::MetricVerified => "metric.verified",
+            WebhookEventType::DashboardCreated => "dashboard.created",
+            WebhookEventType::DashboardShared => "dashboard.shared",
+            WebhookEventType::DatasetDeployed => "dataset.deployed",
+            WebhookEventType::ChatCompleted => "chat.completed",
+            WebhookEventType::DataSourceCreated => "data_source.created",
+            WebhookEventType::DataSourceUpdated => "data_source.updated",
+            WebhookEventType::DataSourceDeleted => "data_source.deleted",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for WebhookEventType {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.to_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for WebhookEventType {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match bytes.as_bytes() {
+            b"metric.created" => Ok(WebhookEventType::MetricCreated),
+            b"metric.updated" => Ok(WebhookEventType::MetricUpdated),
+            b"metric.verified" => Ok(WebhookEventType::MetricVerified),
+            b"dashboard.created" => Ok(WebhookEventType::DashboardCreated),
+            b"dashboard.shared" => Ok(WebhookEventType::DashboardShared),
+            b"dataset.deployed" => Ok(WebhookEventType::DatasetDeployed),
+            b"chat.completed" => Ok(WebhookEventType::ChatCompleted),
+            b"data_source.created" => Ok(WebhookEventType::DataSourceCreated),
+            b"data_source.updated" => Ok(WebhookEventType::DataSourceUpdated),
+            b"data_source.deleted" => Ok(WebhookEventType::DataSourceDeleted),
+            _ => Err("Unrecognized WebhookEventType".into()),
+        }
+    }
+}
+
+/// Where a webhook delivery is in its retry schedule
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    diesel::AsExpression,
+    diesel::FromSqlRow,
+)]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    /// Not yet accepted by the endpoint; retried at `next_attempt_at`
+    Pending,
+    /// The endpoint answered with a 2xx status
+    Delivered,
+    /// Every attempt failed
+    Failed,
+}
+
+impl WebhookDeliveryStatus {
+    pub fn to_str(&self) -> &'static str {
+        match *self {
+            WebhookDeliveryStatus::Pending => "pending",
+            WebhookDeliveryStatus::Delivered => "delivered",
+            WebhookDeliveryStatus::Failed => "failed",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for WebhookDeliveryStatus {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.to_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for WebhookDeliveryStatus {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match bytes.as_bytes() {
+            b"pending" => Ok(WebhookDeliveryStatus::Pending),
+            b"delivered" => Ok(WebhookDeliveryStatus::Delivered),
+            b"failed" => Ok(WebhookDeliveryStatus::Failed),
+            _ => Err("Unrecognized WebhookDeliveryStatus".into()),
+        }
+    }
+}
+
 impl FromStr for DataSourceType {
     type Err = String;
 
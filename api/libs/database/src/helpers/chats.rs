@@ -1,3 +1,5 @@
+use std::collections::{HashMap, HashSet};
+
 use crate::enums::{AssetPermissionRole, AssetType};
 use anyhow::Result;
 use diesel::JoinOnDsl;
@@ -8,7 +10,7 @@ use uuid::Uuid;
 
 use crate::models::{AssetPermission, Chat};
 use crate::pool::get_pg_pool;
-use crate::schema::{asset_permissions, chats, collections_to_assets};
+use crate::schema::{asset_permissions, chats, collections_to_assets, messages};
 
 /// Fetches a single chat by ID that hasn't been deleted
 ///
@@ -234,3 +236,116 @@ pub async fn fetch_chats_with_permissions(
 
     Ok(result)
 }
+
+/// A message's id and the id of the message it follows
+pub type MessageNode = (Uuid, Option<Uuid>);
+
+/// Fetches the ids and parents of a chat's messages, oldest first
+///
+/// Includes every branch of the chat.
+pub async fn fetch_chat_message_tree(chat_id: &Uuid) -> Result<Vec<MessageNode>> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let tree = messages::table
+        .filter(messages::chat_id.eq(chat_id))
+        .filter(messages::deleted_at.is_null())
+        .order_by(messages::created_at.asc())
+        .select((messages::id, messages::parent_message_id))
+        .load::<MessageNode>(&mut conn)
+        .await?;
+
+    Ok(tree)
+}
+
+/// Returns the last message of the chat's active branch
+///
+/// `active_message_id` is the chat's stored selection; chats without one, or
+/// whose selection no longer exists, fall back to their most recent message.
+pub fn active_leaf(tree: &[MessageNode], active_message_id: Option<Uuid>) -> Option<Uuid> {
+    active_message_id
+        .filter(|active| tree.iter().any(|(id, _)| id == active))
+        .or_else(|| tree.last().map(|(id, _)| *id))
+}
+
+/// Returns the messages from the start of the chat to `leaf`, oldest first
+pub fn branch_path(tree: &[MessageNode], leaf: Uuid) -> Vec<Uuid> {
+    let parents: HashMap<Uuid, Option<Uuid>> = tree.iter().cloned().collect();
+
+    let mut path = Vec::new();
+    let mut current = Some(leaf);
+    while let Some(id) = current {
+        // Guards against cycles from bad data
+        if !parents.contains_key(&id) || path.contains(&id) {
+            break;
+        }
+        path.push(id);
+        current = parents[&id];
+    }
+
+    path.reverse();
+    path
+}
+
+/// Follows the most recent reply from `message_id` down to the end of its
+/// branch
+pub fn latest_descendant(tree: &[MessageNode], message_id: Uuid) -> Uuid {
+    let mut current = message_id;
+    let mut seen = HashSet::from([current]);
+
+    // The tree is oldest first, so the last child found is the newest
+    while let Some((child, _)) = tree
+        .iter()
+        .rev()
+        .find(|(_, parent)| *parent == Some(current))
+    {
+        if !seen.insert(*child) {
+            break;
+        }
+        current = *child;
+    }
+
+    current
+}
+
+/// Returns the messages sharing `parent_message_id`, oldest first
+pub fn sibling_ids(tree: &[MessageNode], parent_message_id: Option<Uuid>) -> Vec<Uuid> {
+    tree.iter()
+        .filter(|(_, parent)| *parent == parent_message_id)
+        .map(|(id, _)| *id)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_branch_navigation() {
+        let first = Uuid::new_v4();
+        let reply = Uuid::new_v4();
+        let edited_reply = Uuid::new_v4();
+        let follow_up = Uuid::new_v4();
+
+        // `edited_reply` is a newer alternative to `reply`
+        let tree = vec![
+            (first, None),
+            (reply, Some(first)),
+            (follow_up, Some(reply)),
+            (edited_reply, Some(first)),
+        ];
+
+        assert_eq!(active_leaf(&tree, None), Some(edited_reply));
+        assert_eq!(active_leaf(&tree, Some(follow_up)), Some(follow_up));
+        assert_eq!(active_leaf(&tree, Some(Uuid::new_v4())), Some(edited_reply));
+        assert_eq!(active_leaf(&[], None), None);
+
+        assert_eq!(branch_path(&tree, follow_up), vec![first, reply, follow_up]);
+        assert_eq!(branch_path(&tree, edited_reply), vec![first, edited_reply]);
+
+        assert_eq!(latest_descendant(&tree, first), edited_reply);
+        assert_eq!(latest_descendant(&tree, reply), follow_up);
+
+        assert_eq!(sibling_ids(&tree, Some(first)), vec![reply, edited_reply]);
+        assert_eq!(sibling_ids(&tree, None), vec![first]);
+    }
+}
@@ -0,0 +1,65 @@
+use anyhow::Result;
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::enums::UserOrganizationRole;
+use crate::models::CustomTool;
+use crate::pool::get_pg_pool;
+use crate::schema::{custom_tools, users_to_organizations};
+
+/// Loads an organization's custom tools, including disabled ones
+///
+/// # Arguments
+/// * `organization_id` - The organization to load tools for
+pub async fn fetch_organization_custom_tools(organization_id: &Uuid) -> Result<Vec<CustomTool>> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let tools = custom_tools::table
+        .filter(custom_tools::organization_id.eq(organization_id))
+        .filter(custom_tools::deleted_at.is_null())
+        .order(custom_tools::name.asc())
+        .load::<CustomTool>(&mut conn)
+        .await?;
+
+    Ok(tools)
+}
+
+/// Whether a user with `role` may use `tool`
+///
+/// Tools without allowed roles are open to every member of the organization.
+pub fn is_custom_tool_allowed(tool: &CustomTool, role: UserOrganizationRole) -> bool {
+    tool.enabled && (tool.allowed_roles.is_empty() || tool.allowed_roles.contains(&role))
+}
+
+/// Gets the enabled custom tools a user's role allows them to use
+///
+/// Users without an organization get none.
+pub async fn get_user_custom_tools(user_id: &Uuid) -> Result<Vec<CustomTool>> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let membership = match users_to_organizations::table
+        .filter(users_to_organizations::user_id.eq(user_id))
+        .filter(users_to_organizations::deleted_at.is_null())
+        .select((
+            users_to_organizations::organization_id,
+            users_to_organizations::role,
+        ))
+        .first::<(Uuid, UserOrganizationRole)>(&mut conn)
+        .await
+    {
+        Ok(membership) => membership,
+        Err(diesel::NotFound) => return Ok(vec![]),
+        Err(e) => return Err(e.into()),
+    };
+    drop(conn);
+
+    let (organization_id, role) = membership;
+    let tools = fetch_organization_custom_tools(&organization_id)
+        .await?
+        .into_iter()
+        .filter(|tool| is_custom_tool_allowed(tool, role))
+        .collect();
+
+    Ok(tools)
+}
@@ -0,0 +1,153 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use diesel::{
+    deserialize::QueryableByName,
+    dsl::sql,
+    sql_types::{BigInt, Double, Nullable, Text, Timestamptz, Uuid as SqlUuid},
+    ExpressionMethods, OptionalExtension, QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::{LlmUsageEvent, OrganizationLlmBudget};
+use crate::pool::get_pg_pool;
+use crate::schema::{llm_usage_events, organization_llm_budgets};
+
+/// Records a single LLM call
+pub async fn insert_llm_usage_event(event: &LlmUsageEvent) -> Result<()> {
+    let mut conn = get_pg_pool().get().await?;
+
+    diesel::insert_into(llm_usage_events::table)
+        .values(event)
+        .execute(&mut conn)
+        .await?;
+
+    Ok(())
+}
+
+/// Gets an organization's estimated LLM spend since `since`
+pub async fn get_organization_llm_spend(
+    organization_id: &Uuid,
+    since: DateTime<Utc>,
+) -> Result<f64> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let spend = llm_usage_events::table
+        .filter(llm_usage_events::organization_id.eq(organization_id))
+        .filter(llm_usage_events::created_at.ge(since))
+        .select(sql::<Nullable<Double>>("SUM(estimated_cost)"))
+        .first::<Option<f64>>(&mut conn)
+        .await?;
+
+    Ok(spend.unwrap_or(0.0))
+}
+
+/// Gets an organization's monthly LLM budget, if it has one
+pub async fn get_organization_llm_budget(
+    organization_id: &Uuid,
+) -> Result<Option<OrganizationLlmBudget>> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let budget = organization_llm_budgets::table
+        .filter(organization_llm_budgets::organization_id.eq(organization_id))
+        .first::<OrganizationLlmBudget>(&mut conn)
+        .await
+        .optional()?;
+
+    Ok(budget)
+}
+
+/// Which usage events a rollup covers
+#[derive(Debug, Clone, Copy)]
+pub enum LlmUsageScope {
+    Organization(Uuid),
+    Chat(Uuid),
+}
+
+/// How usage events are grouped in a rollup
+#[derive(Debug, Clone, Copy)]
+pub enum LlmUsageGrouping {
+    /// By UTC calendar day, keyed as `YYYY-MM-DD`
+    Day,
+    User,
+    Model,
+    Message,
+}
+
+impl LlmUsageGrouping {
+    fn key_sql(&self) -> &'static str {
+        match self {
+            LlmUsageGrouping::Day => "to_char(created_at AT TIME ZONE 'UTC', 'YYYY-MM-DD')",
+            LlmUsageGrouping::User => "user_id::text",
+            LlmUsageGrouping::Model => "model",
+            LlmUsageGrouping::Message => "message_id::text",
+        }
+    }
+}
+
+/// Usage summed over a group of LLM calls
+#[derive(QueryableByName, Debug, Clone, Serialize)]
+pub struct LlmUsageRollup {
+    /// The group, e.g. a day, user id, model or message id; null for events
+    /// without one (such as embeddings made outside a chat)
+    #[diesel(sql_type = Nullable<Text>)]
+    pub key: Option<String>,
+    #[diesel(sql_type = BigInt)]
+    pub calls: i64,
+    #[diesel(sql_type = BigInt)]
+    pub prompt_tokens: i64,
+    #[diesel(sql_type = BigInt)]
+    pub completion_tokens: i64,
+    #[diesel(sql_type = BigInt)]
+    pub total_tokens: i64,
+    #[diesel(sql_type = Double)]
+    pub estimated_cost: f64,
+}
+
+/// Sums LLM usage within a scope and optional time window, per group
+///
+/// # Arguments
+/// * `scope` - The organization or chat to report on
+/// * `grouping` - How to group the events
+/// * `start` - Inclusive lower bound on `created_at`, if any
+/// * `end` - Exclusive upper bound on `created_at`, if any
+pub async fn get_llm_usage_rollup(
+    scope: LlmUsageScope,
+    grouping: LlmUsageGrouping,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+) -> Result<Vec<LlmUsageRollup>> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let (scope_column, scope_id) = match scope {
+        LlmUsageScope::Organization(id) => ("organization_id", id),
+        LlmUsageScope::Chat(id) => ("chat_id", id),
+    };
+
+    let query = format!(
+        "SELECT {key} AS key,
+                COUNT(*) AS calls,
+                COALESCE(SUM(prompt_tokens), 0)::BIGINT AS prompt_tokens,
+                COALESCE(SUM(completion_tokens), 0)::BIGINT AS completion_tokens,
+                COALESCE(SUM(total_tokens), 0)::BIGINT AS total_tokens,
+                COALESCE(SUM(estimated_cost), 0)::DOUBLE PRECISION AS estimated_cost
+         FROM llm_usage_events
+         WHERE {scope_column} = $1
+           AND ($2::timestamptz IS NULL OR created_at >= $2)
+           AND ($3::timestamptz IS NULL OR created_at < $3)
+         GROUP BY 1
+         ORDER BY 1",
+        key = grouping.key_sql(),
+        scope_column = scope_column,
+    );
+
+    let rollup = diesel::sql_query(query)
+        .bind::<SqlUuid, _>(scope_id)
+        .bind::<Nullable<Timestamptz>, _>(start)
+        .bind::<Nullable<Timestamptz>, _>(end)
+        .load::<LlmUsageRollup>(&mut conn)
+        .await?;
+
+    Ok(rollup)
+}
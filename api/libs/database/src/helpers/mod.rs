@@ -1,7 +1,13 @@
 pub mod collections;
+pub mod custom_tools;
 pub mod dashboard_files;
 pub mod metric_files;
 pub mod chats;
+pub mod llm_usage;
+pub mod model_configs;
 pub mod organization;
+pub mod terms;
 pub mod test_utils;
-pub mod datasets;
\ No newline at end of file
+pub mod verified_examples;
+pub mod datasets;
+pub mod version_restores;
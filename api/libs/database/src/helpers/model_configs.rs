@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::enums::ModelPurpose;
+use crate::helpers::organization::get_user_organization_id;
+use crate::models::OrganizationModelConfig;
+use crate::pool::get_pg_pool;
+use crate::schema::{data_sources, organization_model_configs};
+
+/// The LLM models an organization has chosen, keyed by purpose
+///
+/// Purposes without an override fall back to `ModelPurpose::default_model`.
+#[derive(Debug, Clone, Default)]
+pub struct OrganizationModels {
+    organization_id: Option<Uuid>,
+    overrides: HashMap<ModelPurpose, String>,
+}
+
+impl OrganizationModels {
+    pub fn new(overrides: HashMap<ModelPurpose, String>) -> Self {
+        Self {
+            organization_id: None,
+            overrides,
+        }
+    }
+
+    /// The organization the models belong to, `None` for the defaults given to
+    /// users without one
+    pub fn organization_id(&self) -> Option<Uuid> {
+        self.organization_id
+    }
+
+    /// The model to use for `purpose`, falling back to the built-in default
+    pub fn model_for(&self, purpose: ModelPurpose) -> String {
+        self.configured(purpose)
+            .unwrap_or(purpose.default_model())
+            .to_string()
+    }
+
+    /// The chat models the organization uses for any purpose, which are the
+    /// only ones its requests may fail over to
+    pub fn approved_models(&self) -> Vec<String> {
+        let mut models: Vec<String> = Vec::new();
+        for purpose in ModelPurpose::ALL {
+            let model = self.model_for(purpose);
+            if purpose != ModelPurpose::Embeddings && !models.contains(&model) {
+                models.push(model);
+            }
+        }
+        models
+    }
+
+    /// The organization's override for `purpose`, if it has one
+    pub fn configured(&self, purpose: ModelPurpose) -> Option<&str> {
+        self.overrides.get(&purpose).map(String::as_str)
+    }
+}
+
+/// Loads the model overrides configured for an organization
+///
+/// # Arguments
+/// * `organization_id` - The organization to load overrides for
+pub async fn fetch_organization_model_configs(
+    organization_id: &Uuid,
+) -> Result<Vec<OrganizationModelConfig>> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let configs = organization_model_configs::table
+        .filter(organization_model_configs::organization_id.eq(organization_id))
+        .load::<OrganizationModelConfig>(&mut conn)
+        .await?;
+
+    Ok(configs)
+}
+
+/// Gets the models an organization uses for each purpose
+///
+/// # Arguments
+/// * `organization_id` - The organization to resolve models for
+pub async fn get_organization_models(organization_id: &Uuid) -> Result<OrganizationModels> {
+    let overrides = fetch_organization_model_configs(organization_id)
+        .await?
+        .into_iter()
+        .map(|config| (config.purpose, config.model))
+        .collect();
+
+    Ok(OrganizationModels {
+        organization_id: Some(*organization_id),
+        overrides,
+    })
+}
+
+/// Gets the models to use on behalf of a user, based on their organization
+///
+/// Users without an organization get the defaults.
+pub async fn get_user_models(user_id: &Uuid) -> Result<OrganizationModels> {
+    match get_user_organization_id(user_id).await? {
+        Some(organization_id) => get_organization_models(&organization_id).await,
+        None => Ok(OrganizationModels::default()),
+    }
+}
+
+/// Gets the models of the organization that owns a data source
+pub async fn get_data_source_models(data_source_id: &Uuid) -> Result<OrganizationModels> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let organization_id = data_sources::table
+        .filter(data_sources::id.eq(data_source_id))
+        .select(data_sources::organization_id)
+        .first::<Uuid>(&mut conn)
+        .await?;
+
+    get_organization_models(&organization_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_for_falls_back_to_default() {
+        let models = OrganizationModels::new(HashMap::from([(
+            ModelPurpose::Planning,
+            "claude-3-7-sonnet".to_string(),
+        )]));
+
+        assert_eq!(models.model_for(ModelPurpose::Planning), "claude-3-7-sonnet");
+        assert_eq!(models.model_for(ModelPurpose::Analysis), "o4-mini");
+        assert_eq!(models.configured(ModelPurpose::Review), None);
+        assert_eq!(
+            OrganizationModels::default().model_for(ModelPurpose::Embeddings),
+            "text-embedding-3-small"
+        );
+    }
+
+    #[test]
+    fn test_approved_models() {
+        let models = OrganizationModels::new(HashMap::from([(
+            ModelPurpose::Planning,
+            "claude-3-7-sonnet".to_string(),
+        )]));
+
+        let approved = models.approved_models();
+        assert!(approved.contains(&"claude-3-7-sonnet".to_string()));
+        assert!(approved.contains(&"o4-mini".to_string()));
+        assert!(approved.contains(&"gemini-2.0-flash-001".to_string()));
+        assert!(!approved.contains(&"text-embedding-3-small".to_string()));
+        assert_eq!(approved.len(), 3);
+    }
+}
@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::helpers::organization::get_user_organization_id;
+use crate::models::Term;
+use crate::pool::get_pg_pool;
+use crate::schema::{terms, terms_to_datasets};
+
+/// A knowledge base entry with the datasets it is linked to
+#[derive(Debug, Clone)]
+pub struct TermWithDatasets {
+    pub term: Term,
+    pub dataset_ids: Vec<Uuid>,
+}
+
+/// Loads the dataset links of the given terms, keyed by term
+pub async fn fetch_term_dataset_ids(term_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<Uuid>>> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let links = terms_to_datasets::table
+        .filter(terms_to_datasets::term_id.eq_any(term_ids))
+        .filter(terms_to_datasets::deleted_at.is_null())
+        .select((terms_to_datasets::term_id, terms_to_datasets::dataset_id))
+        .load::<(Uuid, Uuid)>(&mut conn)
+        .await?;
+
+    let mut dataset_ids: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for (term_id, dataset_id) in links {
+        dataset_ids.entry(term_id).or_default().push(dataset_id);
+    }
+
+    Ok(dataset_ids)
+}
+
+/// Loads an organization's knowledge base: its glossary terms and
+/// instructions, with the datasets each is linked to
+///
+/// # Arguments
+/// * `organization_id` - The organization to load the knowledge base of
+pub async fn fetch_organization_terms(organization_id: &Uuid) -> Result<Vec<TermWithDatasets>> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let terms = terms::table
+        .filter(terms::organization_id.eq(organization_id))
+        .filter(terms::deleted_at.is_null())
+        .order(terms::name.asc())
+        .load::<Term>(&mut conn)
+        .await?;
+    drop(conn);
+
+    let term_ids: Vec<Uuid> = terms.iter().map(|term| term.id).collect();
+    let mut dataset_ids = fetch_term_dataset_ids(&term_ids).await?;
+
+    Ok(terms
+        .into_iter()
+        .map(|term| TermWithDatasets {
+            dataset_ids: dataset_ids.remove(&term.id).unwrap_or_default(),
+            term,
+        })
+        .collect())
+}
+
+/// Gets the knowledge base of a user's organization
+///
+/// Users without an organization get none.
+pub async fn get_user_terms(user_id: &Uuid) -> Result<Vec<TermWithDatasets>> {
+    match get_user_organization_id(user_id).await? {
+        Some(organization_id) => fetch_organization_terms(&organization_id).await,
+        None => Ok(vec![]),
+    }
+}
@@ -0,0 +1,36 @@
+use anyhow::Result;
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::helpers::organization::get_user_organization_id;
+use crate::models::VerifiedExample;
+use crate::pool::get_pg_pool;
+use crate::schema::verified_examples;
+
+/// Loads an organization's verified question and SQL pairs, newest first
+///
+/// # Arguments
+/// * `organization_id` - The organization to load examples for
+pub async fn fetch_organization_examples(organization_id: &Uuid) -> Result<Vec<VerifiedExample>> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let examples = verified_examples::table
+        .filter(verified_examples::organization_id.eq(organization_id))
+        .filter(verified_examples::deleted_at.is_null())
+        .order(verified_examples::created_at.desc())
+        .load::<VerifiedExample>(&mut conn)
+        .await?;
+
+    Ok(examples)
+}
+
+/// Gets the verified examples of a user's organization
+///
+/// Users without an organization get none.
+pub async fn get_user_examples(user_id: &Uuid) -> Result<Vec<VerifiedExample>> {
+    match get_user_organization_id(user_id).await? {
+        Some(organization_id) => fetch_organization_examples(&organization_id).await,
+        None => Ok(vec![]),
+    }
+}
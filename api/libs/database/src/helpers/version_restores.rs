@@ -0,0 +1,65 @@
+use anyhow::Result;
+use chrono::Utc;
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::enums::AssetType;
+use crate::models::AssetVersionRestore;
+use crate::pool::get_pg_pool;
+use crate::schema::asset_version_restores;
+
+/// Records that `restored_version_number` of an asset was restored as `new_version_number`
+///
+/// # Arguments
+/// * `asset_id` - The metric or dashboard file that was restored
+/// * `asset_type` - The type of the asset
+/// * `organization_id` - The organization that owns the asset
+/// * `restored_version_number` - The historical version that was restored
+/// * `new_version_number` - The version created by the restore
+/// * `restored_by` - The user that performed the restore
+pub async fn record_version_restore(
+    asset_id: &Uuid,
+    asset_type: AssetType,
+    organization_id: &Uuid,
+    restored_version_number: i32,
+    new_version_number: i32,
+    restored_by: &Uuid,
+) -> Result<AssetVersionRestore> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let restore = AssetVersionRestore {
+        id: Uuid::new_v4(),
+        asset_id: *asset_id,
+        asset_type,
+        organization_id: *organization_id,
+        restored_version_number,
+        new_version_number,
+        restored_by: *restored_by,
+        created_at: Utc::now(),
+    };
+
+    diesel::insert_into(asset_version_restores::table)
+        .values(&restore)
+        .execute(&mut conn)
+        .await?;
+
+    Ok(restore)
+}
+
+/// Lists the restores performed on an asset, most recent first
+pub async fn fetch_version_restores(
+    asset_id: &Uuid,
+    asset_type: AssetType,
+) -> Result<Vec<AssetVersionRestore>> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let restores = asset_version_restores::table
+        .filter(asset_version_restores::asset_id.eq(asset_id))
+        .filter(asset_version_restores::asset_type.eq(asset_type))
+        .order(asset_version_restores::created_at.desc())
+        .load::<AssetVersionRestore>(&mut conn)
+        .await?;
+
+    Ok(restores)
+}
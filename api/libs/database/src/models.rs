@@ -57,6 +57,7 @@ pub struct Message {
     pub deleted_at: Option<DateTime<Utc>>,
     pub created_by: Uuid,
     pub feedback: Option<String>,
+    pub parent_message_id: Option<Uuid>,
 }
 
 #[derive(Queryable, Insertable, Debug, Clone)]
@@ -115,6 +116,7 @@ pub struct Chat {
     pub most_recent_file_id: Option<Uuid>,
     pub most_recent_file_type: Option<String>,
     pub most_recent_version_number: Option<i32>,
+    pub active_message_id: Option<Uuid>,
 }
 
 #[derive(Queryable, Insertable, Associations, Debug)]
@@ -476,6 +478,7 @@ pub struct Term {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
+    pub kind: TermKind,
 }
 
 #[derive(
@@ -551,6 +554,172 @@ pub struct AssetPermission {
     pub updated_by: Uuid,
 }
 
+#[derive(Queryable, Insertable, Identifiable, Debug, Clone, Serialize)]
+#[diesel(table_name = asset_comments)]
+pub struct AssetComment {
+    pub id: Uuid,
+    pub asset_id: Uuid,
+    pub asset_type: AssetType,
+    pub organization_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub content: String,
+    pub mentions: Vec<Uuid>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Queryable, Insertable, Identifiable, Debug, Clone, Serialize)]
+#[diesel(table_name = metric_annotations)]
+pub struct MetricAnnotation {
+    pub id: Uuid,
+    pub metric_file_id: Uuid,
+    pub organization_id: Uuid,
+    pub x_value: String,
+    pub text: String,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Queryable, Insertable, Debug, Clone, Serialize)]
+#[diesel(table_name = dataset_reviewers)]
+pub struct DatasetReviewer {
+    pub dataset_id: Uuid,
+    pub user_id: Uuid,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Queryable, Insertable, Identifiable, Debug, Clone, Serialize)]
+#[diesel(table_name = metric_review_events)]
+pub struct MetricReviewEvent {
+    pub id: Uuid,
+    pub metric_file_id: Uuid,
+    pub organization_id: Uuid,
+    pub actor_id: Uuid,
+    pub action: String,
+    pub from_status: Verification,
+    pub to_status: Verification,
+    pub comment: Option<String>,
+    pub reviewer_ids: Vec<Uuid>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Queryable, Insertable, Debug, Clone, Serialize)]
+#[diesel(table_name = organization_model_configs)]
+pub struct OrganizationModelConfig {
+    pub organization_id: Uuid,
+    pub purpose: ModelPurpose,
+    pub model: String,
+    pub updated_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Queryable, Insertable, Identifiable, Debug, Clone, Serialize)]
+#[diesel(table_name = llm_usage_events)]
+pub struct LlmUsageEvent {
+    pub id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub chat_id: Option<Uuid>,
+    pub message_id: Option<Uuid>,
+    pub model: String,
+    pub generation_name: Option<String>,
+    pub prompt_tokens: i32,
+    pub completion_tokens: i32,
+    pub total_tokens: i32,
+    pub estimated_cost: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Queryable, Insertable, Debug, Clone, Serialize)]
+#[diesel(table_name = organization_llm_budgets)]
+pub struct OrganizationLlmBudget {
+    pub organization_id: Uuid,
+    pub monthly_budget: f64,
+    pub updated_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Queryable, Insertable, Identifiable, Debug, Clone, Serialize)]
+#[diesel(table_name = custom_tools)]
+pub struct CustomTool {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub display_name: String,
+    pub description: String,
+    pub kind: CustomToolKind,
+    pub endpoint: String,
+    pub remote_name: Option<String>,
+    pub input_schema: Value,
+    /// May hold credentials, so it is never serialized
+    #[serde(skip_serializing)]
+    pub headers: Value,
+    pub allowed_roles: Vec<UserOrganizationRole>,
+    pub timeout_ms: i32,
+    pub enabled: bool,
+    pub created_by: Uuid,
+    pub updated_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Queryable, Insertable, Identifiable, Debug, Clone, Serialize)]
+#[diesel(table_name = asset_version_restores)]
+pub struct AssetVersionRestore {
+    pub id: Uuid,
+    pub asset_id: Uuid,
+    pub asset_type: AssetType,
+    pub organization_id: Uuid,
+    pub restored_version_number: i32,
+    pub new_version_number: i32,
+    pub restored_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Queryable, Insertable, Identifiable, Debug, Clone, Serialize)]
+#[diesel(table_name = asset_feedback)]
+pub struct AssetFeedback {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub message_id: Option<Uuid>,
+    pub metric_file_id: Option<Uuid>,
+    pub rating: FeedbackRating,
+    pub category: Option<FeedbackCategory>,
+    pub comment: Option<String>,
+    pub correction_sql: Option<String>,
+    pub question: Option<String>,
+    pub status: FeedbackStatus,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Queryable, Insertable, Identifiable, Debug, Clone, Serialize)]
+#[diesel(table_name = verified_examples)]
+pub struct VerifiedExample {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub question: String,
+    pub sql: String,
+    pub feedback_id: Option<Uuid>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
 #[derive(Queryable, Insertable, Associations, Debug)]
 #[diesel(belongs_to(Collection, foreign_key = collection_id))]
 #[diesel(table_name = collections_to_assets)]
@@ -599,6 +768,77 @@ pub struct UserFavorite {
     pub deleted_at: Option<DateTime<Utc>>,
 }
 
+#[derive(Queryable, Insertable, Identifiable, Debug, Clone)]
+#[diesel(table_name = slack_integrations)]
+pub struct SlackIntegration {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub team_id: String,
+    pub team_name: String,
+    pub bot_user_id: String,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = slack_user_links)]
+pub struct SlackUserLink {
+    pub slack_integration_id: Uuid,
+    pub slack_user_id: String,
+    pub user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = slack_threads)]
+pub struct SlackThread {
+    pub slack_integration_id: Uuid,
+    pub channel_id: String,
+    pub thread_ts: String,
+    pub user_id: Uuid,
+    pub chat_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Queryable, Insertable, Identifiable, Debug, Clone)]
+#[diesel(table_name = webhooks)]
+pub struct Webhook {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub url: String,
+    pub description: Option<String>,
+    pub events: Vec<WebhookEventType>,
+    pub enabled: bool,
+    pub created_by: Uuid,
+    pub updated_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Queryable, Insertable, Identifiable, Debug, Clone)]
+#[diesel(table_name = webhook_deliveries)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub webhook_id: Uuid,
+    pub event_id: Uuid,
+    pub event_type: WebhookEventType,
+    pub payload: Value,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
 #[derive(Queryable, Insertable, Associations, Debug)]
 #[diesel(belongs_to(Term, foreign_key = term_id))]
 #[diesel(belongs_to(Dataset, foreign_key = dataset_id))]
@@ -82,6 +82,62 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::AssetTypeEnum;
+
+    asset_comments (id) {
+        id -> Uuid,
+        asset_id -> Uuid,
+        asset_type -> AssetTypeEnum,
+        organization_id -> Uuid,
+        parent_id -> Nullable<Uuid>,
+        content -> Text,
+        mentions -> Array<Uuid>,
+        created_by -> Uuid,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        deleted_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    asset_feedback (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        message_id -> Nullable<Uuid>,
+        metric_file_id -> Nullable<Uuid>,
+        rating -> Text,
+        category -> Nullable<Text>,
+        comment -> Nullable<Text>,
+        correction_sql -> Nullable<Text>,
+        question -> Nullable<Text>,
+        status -> Text,
+        reviewed_by -> Nullable<Uuid>,
+        reviewed_at -> Nullable<Timestamptz>,
+        created_by -> Uuid,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        deleted_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::AssetTypeEnum;
+
+    asset_version_restores (id) {
+        id -> Uuid,
+        asset_id -> Uuid,
+        asset_type -> AssetTypeEnum,
+        organization_id -> Uuid,
+        restored_version_number -> Int4,
+        new_version_number -> Int4,
+        restored_by -> Uuid,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     chats (id) {
         id -> Uuid,
@@ -99,6 +155,7 @@ diesel::table! {
         #[max_length = 255]
         most_recent_file_type -> Nullable<Varchar>,
         most_recent_version_number -> Nullable<Int4>,
+        active_message_id -> Nullable<Uuid>,
     }
 }
 
@@ -132,6 +189,32 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::UserOrganizationRoleEnum;
+
+    custom_tools (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        name -> Text,
+        display_name -> Text,
+        description -> Text,
+        kind -> Text,
+        endpoint -> Text,
+        remote_name -> Nullable<Text>,
+        input_schema -> Jsonb,
+        headers -> Jsonb,
+        allowed_roles -> Array<UserOrganizationRoleEnum>,
+        timeout_ms -> Int4,
+        enabled -> Bool,
+        created_by -> Uuid,
+        updated_by -> Uuid,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        deleted_at -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     dashboard_files (id) {
         id -> Uuid,
@@ -267,6 +350,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    dataset_reviewers (dataset_id, user_id) {
+        dataset_id -> Uuid,
+        user_id -> Uuid,
+        created_by -> Uuid,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::DatasetTypeEnum;
@@ -325,6 +417,23 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    llm_usage_events (id) {
+        id -> Uuid,
+        organization_id -> Nullable<Uuid>,
+        user_id -> Nullable<Uuid>,
+        chat_id -> Nullable<Uuid>,
+        message_id -> Nullable<Uuid>,
+        model -> Text,
+        generation_name -> Nullable<Text>,
+        prompt_tokens -> Int4,
+        completion_tokens -> Int4,
+        total_tokens -> Int4,
+        estimated_cost -> Float8,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     messages (id) {
         id -> Uuid,
@@ -340,6 +449,7 @@ diesel::table! {
         deleted_at -> Nullable<Timestamptz>,
         created_by -> Uuid,
         feedback -> Nullable<Text>,
+        parent_message_id -> Nullable<Uuid>,
     }
 }
 
@@ -425,6 +535,60 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    metric_annotations (id) {
+        id -> Uuid,
+        metric_file_id -> Uuid,
+        organization_id -> Uuid,
+        x_value -> Text,
+        text -> Text,
+        created_by -> Uuid,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        deleted_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::VerificationEnum;
+
+    metric_review_events (id) {
+        id -> Uuid,
+        metric_file_id -> Uuid,
+        organization_id -> Uuid,
+        actor_id -> Uuid,
+        action -> Text,
+        from_status -> VerificationEnum,
+        to_status -> VerificationEnum,
+        comment -> Nullable<Text>,
+        reviewer_ids -> Array<Uuid>,
+        due_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    organization_llm_budgets (organization_id) {
+        organization_id -> Uuid,
+        monthly_budget -> Float8,
+        updated_by -> Uuid,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    organization_model_configs (organization_id, purpose) {
+        organization_id -> Uuid,
+        purpose -> Text,
+        model -> Text,
+        updated_by -> Uuid,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     organizations (id) {
         id -> Uuid,
@@ -474,6 +638,42 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    slack_integrations (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        team_id -> Text,
+        team_name -> Text,
+        bot_user_id -> Text,
+        created_by -> Uuid,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        deleted_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    slack_threads (slack_integration_id, channel_id, thread_ts, user_id) {
+        slack_integration_id -> Uuid,
+        channel_id -> Text,
+        thread_ts -> Text,
+        user_id -> Uuid,
+        chat_id -> Uuid,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    slack_user_links (slack_integration_id, slack_user_id) {
+        slack_integration_id -> Uuid,
+        slack_user_id -> Text,
+        user_id -> Uuid,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     sql_evaluations (id) {
         id -> Uuid,
@@ -547,6 +747,7 @@ diesel::table! {
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
         deleted_at -> Nullable<Timestamptz>,
+        kind -> Text,
     }
 }
 
@@ -641,10 +842,67 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    verified_examples (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        question -> Text,
+        sql -> Text,
+        feedback_id -> Nullable<Uuid>,
+        created_by -> Uuid,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        deleted_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    webhook_deliveries (id) {
+        id -> Uuid,
+        webhook_id -> Uuid,
+        event_id -> Uuid,
+        event_type -> Text,
+        payload -> Jsonb,
+        status -> Text,
+        attempts -> Int4,
+        response_status -> Nullable<Int4>,
+        response_body -> Nullable<Text>,
+        error -> Nullable<Text>,
+        next_attempt_at -> Nullable<Timestamptz>,
+        delivered_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    webhooks (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        url -> Text,
+        description -> Nullable<Text>,
+        events -> Array<Text>,
+        enabled -> Bool,
+        created_by -> Uuid,
+        updated_by -> Uuid,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        deleted_at -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::joinable!(api_keys -> organizations (organization_id));
 diesel::joinable!(api_keys -> users (owner_id));
+diesel::joinable!(asset_comments -> organizations (organization_id));
+diesel::joinable!(asset_comments -> users (created_by));
+diesel::joinable!(asset_feedback -> messages (message_id));
+diesel::joinable!(asset_feedback -> metric_files (metric_file_id));
+diesel::joinable!(asset_feedback -> organizations (organization_id));
+diesel::joinable!(asset_version_restores -> organizations (organization_id));
+diesel::joinable!(asset_version_restores -> users (restored_by));
 diesel::joinable!(chats -> organizations (organization_id));
 diesel::joinable!(collections -> organizations (organization_id));
+diesel::joinable!(custom_tools -> organizations (organization_id));
 diesel::joinable!(dashboard_versions -> dashboards (dashboard_id));
 diesel::joinable!(dashboards -> organizations (organization_id));
 diesel::joinable!(data_sources -> organizations (organization_id));
@@ -653,6 +911,7 @@ diesel::joinable!(dataset_groups_permissions -> dataset_groups (dataset_group_id
 diesel::joinable!(dataset_groups_permissions -> organizations (organization_id));
 diesel::joinable!(dataset_permissions -> datasets (dataset_id));
 diesel::joinable!(dataset_permissions -> organizations (organization_id));
+diesel::joinable!(dataset_reviewers -> datasets (dataset_id));
 diesel::joinable!(datasets -> data_sources (data_source_id));
 diesel::joinable!(datasets -> organizations (organization_id));
 diesel::joinable!(datasets_to_dataset_groups -> dataset_groups (dataset_group_id));
@@ -666,9 +925,26 @@ diesel::joinable!(messages_to_files -> messages (message_id));
 diesel::joinable!(metric_files_to_dashboard_files -> dashboard_files (dashboard_file_id));
 diesel::joinable!(metric_files_to_dashboard_files -> metric_files (metric_file_id));
 diesel::joinable!(metric_files_to_dashboard_files -> users (created_by));
+diesel::joinable!(metric_annotations -> metric_files (metric_file_id));
+diesel::joinable!(metric_annotations -> users (created_by));
+diesel::joinable!(metric_review_events -> metric_files (metric_file_id));
+diesel::joinable!(metric_review_events -> organizations (organization_id));
+diesel::joinable!(llm_usage_events -> organizations (organization_id));
+diesel::joinable!(llm_usage_events -> users (user_id));
+diesel::joinable!(organization_llm_budgets -> organizations (organization_id));
+diesel::joinable!(organization_llm_budgets -> users (updated_by));
+diesel::joinable!(organization_model_configs -> organizations (organization_id));
+diesel::joinable!(organization_model_configs -> users (updated_by));
 diesel::joinable!(permission_groups -> organizations (organization_id));
 diesel::joinable!(permission_groups_to_users -> permission_groups (permission_group_id));
 diesel::joinable!(permission_groups_to_users -> users (user_id));
+diesel::joinable!(slack_integrations -> organizations (organization_id));
+diesel::joinable!(slack_integrations -> users (created_by));
+diesel::joinable!(slack_threads -> chats (chat_id));
+diesel::joinable!(slack_threads -> slack_integrations (slack_integration_id));
+diesel::joinable!(slack_threads -> users (user_id));
+diesel::joinable!(slack_user_links -> slack_integrations (slack_integration_id));
+diesel::joinable!(slack_user_links -> users (user_id));
 diesel::joinable!(stored_values_sync_jobs -> data_sources (data_source_id));
 diesel::joinable!(teams -> organizations (organization_id));
 diesel::joinable!(teams -> users (created_by));
@@ -683,13 +959,21 @@ diesel::joinable!(threads_to_dashboards -> threads_deprecated (thread_id));
 diesel::joinable!(threads_to_dashboards -> users (added_by));
 diesel::joinable!(user_favorites -> users (user_id));
 diesel::joinable!(users_to_organizations -> organizations (organization_id));
+diesel::joinable!(verified_examples -> asset_feedback (feedback_id));
+diesel::joinable!(verified_examples -> organizations (organization_id));
+diesel::joinable!(webhook_deliveries -> webhooks (webhook_id));
+diesel::joinable!(webhooks -> organizations (organization_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     api_keys,
+    asset_comments,
+    asset_feedback,
     asset_permissions,
+    asset_version_restores,
     chats,
     collections,
     collections_to_assets,
+    custom_tools,
     dashboard_files,
     dashboard_versions,
     dashboards,
@@ -698,19 +982,28 @@ diesel::allow_tables_to_appear_in_same_query!(
     dataset_groups,
     dataset_groups_permissions,
     dataset_permissions,
+    dataset_reviewers,
     datasets,
     datasets_to_dataset_groups,
     datasets_to_permission_groups,
     entity_relationship,
+    llm_usage_events,
     messages,
     messages_deprecated,
     messages_to_files,
     metric_files,
+    metric_annotations,
     metric_files_to_dashboard_files,
+    metric_review_events,
+    organization_llm_budgets,
+    organization_model_configs,
     organizations,
     permission_groups,
     permission_groups_to_identities,
     permission_groups_to_users,
+    slack_integrations,
+    slack_threads,
+    slack_user_links,
     sql_evaluations,
     stored_values_sync_jobs,
     teams,
@@ -722,4 +1015,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     user_favorites,
     users,
     users_to_organizations,
+    verified_examples,
+    webhook_deliveries,
+    webhooks,
 );
@@ -130,6 +130,78 @@ pub struct MetricYml {
     pub chart_config: ChartConfig,
     #[serde(alias = "dataset_ids")]
     pub dataset_ids: Vec<Uuid>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty", alias = "drill_targets")]
+    pub drill_targets: Vec<DrillTarget>,
+}
+
+// --- Drill-down Configuration ---
+
+// Where a click on a data point leads. Clicked column values are bound into
+// the target's SQL as literals by the metric data handler.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type")]
+#[serde(rename_all = "camelCase")]
+pub enum DrillTarget {
+    #[serde(rename = "metric")]
+    Metric(MetricDrillTarget),
+    #[serde(rename = "query")]
+    Query(QueryDrillTarget),
+    #[serde(rename = "hierarchy")]
+    Hierarchy(HierarchyDrillTarget),
+}
+
+impl DrillTarget {
+    pub fn name(&self) -> &str {
+        match self {
+            DrillTarget::Metric(target) => &target.name,
+            DrillTarget::Query(target) => &target.name,
+            DrillTarget::Hierarchy(target) => &target.name,
+        }
+    }
+}
+
+// Opens another metric filtered on the clicked values
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricDrillTarget {
+    pub name: String,
+    #[serde(alias = "metric_id")]
+    pub metric_id: Uuid,
+    // Clicked columns to filter the target on; empty means all of them
+    #[serde(default)]
+    pub params: Vec<String>,
+}
+
+// Runs a detail query with `{{column}}` placeholders for the clicked values
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryDrillTarget {
+    pub name: String,
+    pub sql: String,
+}
+
+// Walks a dimension hierarchy such as year -> quarter -> month. Each level's
+// SQL may reference the columns of the levels above it as placeholders.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HierarchyDrillTarget {
+    pub name: String,
+    pub levels: Vec<DrillLevel>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DrillLevel {
+    pub column: String,
+    pub sql: String,
+}
+
+lazy_static! {
+    // Placeholder syntax for drill SQL templates, e.g. `{{region}}`
+    pub static ref DRILL_PLACEHOLDER_RE: Regex =
+        Regex::new(r#"\{\{\s*([A-Za-z0-9_]*)\s*\}\}"#).unwrap();
+    // Column names that may be used unquoted in drill filters
+    pub static ref DRILL_IDENTIFIER_RE: Regex = Regex::new(r#"^[A-Za-z_][A-Za-z0-9_]*$"#).unwrap();
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -820,6 +892,61 @@ impl MetricYml {
     }
 
     pub fn validate(&self) -> Result<()> {
+        let mut names = std::collections::HashSet::new();
+        for target in &self.drill_targets {
+            if target.name().trim().is_empty() {
+                return Err(anyhow::anyhow!("Drill targets must have a name"));
+            }
+            if !names.insert(target.name().to_lowercase()) {
+                return Err(anyhow::anyhow!(
+                    "Duplicate drill target name: {}",
+                    target.name()
+                ));
+            }
+
+            let mut columns: Vec<&str> = Vec::new();
+            let mut templates: Vec<&str> = Vec::new();
+            match target {
+                DrillTarget::Metric(target) => {
+                    columns.extend(target.params.iter().map(String::as_str));
+                }
+                DrillTarget::Query(target) => templates.push(&target.sql),
+                DrillTarget::Hierarchy(target) => {
+                    if target.levels.is_empty() {
+                        return Err(anyhow::anyhow!(
+                            "Hierarchy drill target {} has no levels",
+                            target.name
+                        ));
+                    }
+                    for level in &target.levels {
+                        columns.push(&level.column);
+                        templates.push(&level.sql);
+                    }
+                }
+            }
+
+            for column in columns {
+                if !DRILL_IDENTIFIER_RE.is_match(column) {
+                    return Err(anyhow::anyhow!(
+                        "Invalid column in drill target {}: {}",
+                        target.name(),
+                        column
+                    ));
+                }
+            }
+            for template in templates {
+                for caps in DRILL_PLACEHOLDER_RE.captures_iter(template) {
+                    if !DRILL_IDENTIFIER_RE.is_match(&caps[1]) {
+                        return Err(anyhow::anyhow!(
+                            "Invalid placeholder in drill target {}: {}",
+                            target.name(),
+                            &caps[0]
+                        ));
+                    }
+                }
+            }
+        }
+
         Ok(())
     }
 }
@@ -1119,6 +1246,69 @@ chartConfig:
 
 
     // ... existing tests ...
+    #[test]
+    fn test_drill_targets_parse_and_validate() -> Result<()> {
+        let yml_content = r#"
+name: Revenue by Year
+timeFrame: all_time
+sql: SELECT year, SUM(amount) AS revenue FROM sales GROUP BY year
+datasetIds: ["00000000-0000-0000-0000-000000000001"]
+chartConfig:
+  selectedChartType: bar
+  columnLabelFormats:
+    year: { columnType: number, style: number }
+    revenue: { columnType: number, style: currency }
+  barAndLineAxis:
+    x: [year]
+    y: [revenue]
+drillTargets:
+  - type: hierarchy
+    name: By period
+    levels:
+      - column: quarter
+        sql: SELECT quarter, SUM(amount) AS revenue FROM sales WHERE year = {{year}} GROUP BY quarter
+      - column: month
+        sql: SELECT month, SUM(amount) AS revenue FROM sales WHERE year = {{year}} AND quarter = {{ quarter }} GROUP BY month
+  - type: metric
+    name: Orders
+    metricId: 00000000-0000-0000-0000-000000000002
+    params: [year]
+"#;
+        let metric = MetricYml::new(yml_content.to_string())?;
+
+        assert_eq!(metric.drill_targets.len(), 2);
+        match &metric.drill_targets[0] {
+            DrillTarget::Hierarchy(target) => {
+                assert_eq!(target.levels.len(), 2);
+                assert_eq!(target.levels[1].column, "month");
+            }
+            _ => panic!("Expected hierarchy drill target"),
+        }
+        assert_eq!(metric.drill_targets[1].name(), "Orders");
+
+        // Drill targets are omitted when absent
+        let serialized = serde_yaml::to_string(&MetricYml {
+            drill_targets: Vec::new(),
+            ..metric.clone()
+        })?;
+        assert!(!serialized.contains("drillTargets"));
+
+        // Duplicate names and injected identifiers are rejected
+        let mut duplicate = metric.clone();
+        duplicate.drill_targets.push(duplicate.drill_targets[1].clone());
+        assert!(duplicate.validate().is_err());
+
+        let mut bad_param = metric;
+        bad_param.drill_targets = vec![DrillTarget::Metric(MetricDrillTarget {
+            name: "Orders".to_string(),
+            metric_id: Uuid::nil(),
+            params: vec!["year; DROP TABLE sales".to_string()],
+        })];
+        assert!(bad_param.validate().is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_column_label_format_constructors() {
         let number_format = ColumnLabelFormat::new_number();
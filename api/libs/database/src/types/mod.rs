@@ -2,8 +2,10 @@ pub mod version_history;
 pub mod metric_yml;
 pub mod dashboard_yml;
 pub mod data_metadata;
+pub mod version_diff;
 
 pub use version_history::*;
 pub use metric_yml::*;
 pub use dashboard_yml::*;
 pub use data_metadata::*;
+pub use version_diff::*;
@@ -0,0 +1,376 @@
+use std::collections::{BTreeSet, HashMap};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{ChartConfig, DashboardYml, MetricYml, Row, VersionContent, VersionHistory};
+
+/// Above this many line comparisons we skip the LCS and report a full replacement.
+const MAX_LINE_DIFF_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct VersionDiff {
+    pub from_version: i32,
+    pub to_version: i32,
+    /// Line-level diff of the YAML representation of both versions.
+    pub yaml_diff: Vec<DiffLine>,
+    /// Higher level changes (SQL changed, chart type changed, rows added...).
+    pub changes: Vec<SemanticChange>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffOp {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DiffLine {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SemanticChange {
+    NameChanged { from: String, to: String },
+    DescriptionChanged { from: Option<String>, to: Option<String> },
+    TimeFrameChanged { from: String, to: String },
+    SqlChanged { from: String, to: String },
+    ChartTypeChanged { from: String, to: String },
+    /// Same chart type, but axes/formatting/etc. differ.
+    ChartConfigChanged,
+    DatasetsChanged { added: Vec<Uuid>, removed: Vec<Uuid> },
+    RowAdded { row_id: u32, metric_ids: Vec<Uuid> },
+    RowRemoved { row_id: u32, metric_ids: Vec<Uuid> },
+    /// Items, column sizes or height of an existing row changed.
+    RowChanged { row_id: u32 },
+    MetricAdded { metric_id: Uuid },
+    MetricRemoved { metric_id: Uuid },
+}
+
+impl VersionHistory {
+    /// Diffs two versions of the same asset. Errors if either version is missing
+    /// or the versions hold different content types.
+    pub fn diff(&self, from_version: i32, to_version: i32) -> Result<VersionDiff> {
+        let from = self
+            .get_version(from_version)
+            .ok_or_else(|| anyhow!("Version {} not found", from_version))?;
+        let to = self
+            .get_version(to_version)
+            .ok_or_else(|| anyhow!("Version {} not found", to_version))?;
+
+        let (from_yaml, to_yaml, changes) = match (&from.content, &to.content) {
+            (VersionContent::MetricYml(a), VersionContent::MetricYml(b)) => (
+                serde_yaml::to_string(a)?,
+                serde_yaml::to_string(b)?,
+                diff_metric_yml(a, b)?,
+            ),
+            (VersionContent::DashboardYml(a), VersionContent::DashboardYml(b)) => (
+                serde_yaml::to_string(a)?,
+                serde_yaml::to_string(b)?,
+                diff_dashboard_yml(a, b),
+            ),
+            _ => return Err(anyhow!("Cannot diff versions with different content types")),
+        };
+
+        Ok(VersionDiff {
+            from_version,
+            to_version,
+            yaml_diff: diff_lines(&from_yaml, &to_yaml),
+            changes,
+        })
+    }
+}
+
+pub fn chart_type_name(chart_config: &ChartConfig) -> &'static str {
+    match chart_config {
+        ChartConfig::Bar(_) => "bar",
+        ChartConfig::Line(_) => "line",
+        ChartConfig::Scatter(_) => "scatter",
+        ChartConfig::Pie(_) => "pie",
+        ChartConfig::Combo(_) => "combo",
+        ChartConfig::Metric(_) => "metric",
+        ChartConfig::Table(_) => "table",
+    }
+}
+
+pub fn diff_metric_yml(from: &MetricYml, to: &MetricYml) -> Result<Vec<SemanticChange>> {
+    let mut changes = Vec::new();
+
+    if from.name != to.name {
+        changes.push(SemanticChange::NameChanged {
+            from: from.name.clone(),
+            to: to.name.clone(),
+        });
+    }
+    if from.description != to.description {
+        changes.push(SemanticChange::DescriptionChanged {
+            from: from.description.clone(),
+            to: to.description.clone(),
+        });
+    }
+    if from.time_frame != to.time_frame {
+        changes.push(SemanticChange::TimeFrameChanged {
+            from: from.time_frame.clone(),
+            to: to.time_frame.clone(),
+        });
+    }
+    if normalize_sql(&from.sql) != normalize_sql(&to.sql) {
+        changes.push(SemanticChange::SqlChanged {
+            from: from.sql.clone(),
+            to: to.sql.clone(),
+        });
+    }
+
+    let (from_chart, to_chart) = (
+        chart_type_name(&from.chart_config),
+        chart_type_name(&to.chart_config),
+    );
+    if from_chart != to_chart {
+        changes.push(SemanticChange::ChartTypeChanged {
+            from: from_chart.to_string(),
+            to: to_chart.to_string(),
+        });
+    } else if serde_json::to_value(&from.chart_config)? != serde_json::to_value(&to.chart_config)? {
+        changes.push(SemanticChange::ChartConfigChanged);
+    }
+
+    let from_datasets: BTreeSet<Uuid> = from.dataset_ids.iter().copied().collect();
+    let to_datasets: BTreeSet<Uuid> = to.dataset_ids.iter().copied().collect();
+    if from_datasets != to_datasets {
+        changes.push(SemanticChange::DatasetsChanged {
+            added: to_datasets.difference(&from_datasets).copied().collect(),
+            removed: from_datasets.difference(&to_datasets).copied().collect(),
+        });
+    }
+
+    Ok(changes)
+}
+
+pub fn diff_dashboard_yml(from: &DashboardYml, to: &DashboardYml) -> Vec<SemanticChange> {
+    let mut changes = Vec::new();
+
+    if from.name != to.name {
+        changes.push(SemanticChange::NameChanged {
+            from: from.name.clone(),
+            to: to.name.clone(),
+        });
+    }
+    if from.description != to.description {
+        changes.push(SemanticChange::DescriptionChanged {
+            from: from.description.clone(),
+            to: to.description.clone(),
+        });
+    }
+
+    let row_metric_ids = |row: &Row| row.items.iter().map(|item| item.id).collect::<Vec<_>>();
+    let from_rows: HashMap<u32, &Row> = from.rows.iter().map(|row| (row.id, row)).collect();
+    let to_rows: HashMap<u32, &Row> = to.rows.iter().map(|row| (row.id, row)).collect();
+
+    for row in &from.rows {
+        match to_rows.get(&row.id) {
+            None => changes.push(SemanticChange::RowRemoved {
+                row_id: row.id,
+                metric_ids: row_metric_ids(row),
+            }),
+            Some(other) => {
+                if row_metric_ids(row) != row_metric_ids(other)
+                    || row.column_sizes != other.column_sizes
+                    || row.row_height != other.row_height
+                {
+                    changes.push(SemanticChange::RowChanged { row_id: row.id });
+                }
+            }
+        }
+    }
+    for row in &to.rows {
+        if !from_rows.contains_key(&row.id) {
+            changes.push(SemanticChange::RowAdded {
+                row_id: row.id,
+                metric_ids: row_metric_ids(row),
+            });
+        }
+    }
+
+    let from_metrics: BTreeSet<Uuid> = from.rows.iter().flat_map(row_metric_ids).collect();
+    let to_metrics: BTreeSet<Uuid> = to.rows.iter().flat_map(row_metric_ids).collect();
+    changes.extend(
+        to_metrics
+            .difference(&from_metrics)
+            .map(|id| SemanticChange::MetricAdded { metric_id: *id }),
+    );
+    changes.extend(
+        from_metrics
+            .difference(&to_metrics)
+            .map(|id| SemanticChange::MetricRemoved { metric_id: *id }),
+    );
+
+    changes
+}
+
+/// Whitespace-insensitive comparison key for SQL, so reformatting alone isn't a change.
+pub fn normalize_sql(sql: &str) -> String {
+    sql.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// LCS based line diff.
+pub fn diff_lines(from: &str, to: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = from.lines().collect();
+    let b: Vec<&str> = to.lines().collect();
+
+    let line = |op: DiffOp, text: &str| DiffLine {
+        op,
+        text: text.to_string(),
+    };
+
+    if a.len().saturating_mul(b.len()) > MAX_LINE_DIFF_CELLS {
+        return a
+            .iter()
+            .map(|text| line(DiffOp::Delete, text))
+            .chain(b.iter().map(|text| line(DiffOp::Insert, text)))
+            .collect();
+    }
+
+    // lcs[i][j] = length of the LCS of a[i..] and b[j..]
+    let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut result = Vec::with_capacity(a.len().max(b.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            result.push(line(DiffOp::Equal, a[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            result.push(line(DiffOp::Delete, a[i]));
+            i += 1;
+        } else {
+            result.push(line(DiffOp::Insert, b[j]));
+            j += 1;
+        }
+    }
+    result.extend(a[i..].iter().map(|text| line(DiffOp::Delete, text)));
+    result.extend(b[j..].iter().map(|text| line(DiffOp::Insert, text)));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::RowItem;
+
+    fn metric_yml(sql: &str, chart_type: &str) -> MetricYml {
+        let yml = format!(
+            r#"
+name: Revenue
+time_frame: last 30 days
+sql: "{}"
+dataset_ids: ["00000000-0000-0000-0000-000000000001"]
+chart_config:
+  selectedChartType: {}
+  columnLabelFormats: {{}}
+  barAndLineAxis:
+    x: [month]
+    y: [revenue]
+"#,
+            sql, chart_type
+        );
+        serde_yaml::from_str(&yml).unwrap()
+    }
+
+    fn row(id: u32, metric_ids: &[Uuid]) -> Row {
+        Row {
+            id,
+            items: metric_ids.iter().map(|id| RowItem { id: *id }).collect(),
+            row_height: None,
+            column_sizes: vec![12 / metric_ids.len() as u32; metric_ids.len()],
+        }
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let diff = diff_lines("a\nb\nc", "a\nc\nd");
+        let ops: Vec<(DiffOp, &str)> = diff.iter().map(|l| (l.op, l.text.as_str())).collect();
+        assert_eq!(
+            ops,
+            vec![
+                (DiffOp::Equal, "a"),
+                (DiffOp::Delete, "b"),
+                (DiffOp::Equal, "c"),
+                (DiffOp::Insert, "d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_metric_sql_and_chart_type_changes() {
+        let from = metric_yml("SELECT month, revenue FROM sales", "bar");
+        let to = metric_yml("SELECT month, sum(revenue) AS revenue FROM sales GROUP BY 1", "line");
+        let changes = diff_metric_yml(&from, &to).unwrap();
+
+        assert!(changes
+            .iter()
+            .any(|c| matches!(c, SemanticChange::SqlChanged { .. })));
+        assert!(changes.contains(&SemanticChange::ChartTypeChanged {
+            from: "bar".to_string(),
+            to: "line".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_metric_sql_whitespace_is_not_a_change() {
+        let from = metric_yml("SELECT month, revenue FROM sales", "bar");
+        let to = metric_yml("SELECT month,   revenue\\nFROM sales", "bar");
+        assert!(diff_metric_yml(&from, &to).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_dashboard_row_changes() {
+        let (m1, m2, m3) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let from = DashboardYml {
+            name: "Board".to_string(),
+            description: None,
+            rows: vec![row(1, &[m1]), row(2, &[m2])],
+        };
+        let to = DashboardYml {
+            name: "Board".to_string(),
+            description: None,
+            rows: vec![row(1, &[m1, m3]), row(3, &[m2])],
+        };
+
+        let changes = diff_dashboard_yml(&from, &to);
+        assert!(changes.contains(&SemanticChange::RowChanged { row_id: 1 }));
+        assert!(changes.contains(&SemanticChange::RowRemoved {
+            row_id: 2,
+            metric_ids: vec![m2]
+        }));
+        assert!(changes.contains(&SemanticChange::RowAdded {
+            row_id: 3,
+            metric_ids: vec![m2]
+        }));
+        assert!(changes.contains(&SemanticChange::MetricAdded { metric_id: m3 }));
+        assert!(!changes
+            .iter()
+            .any(|c| matches!(c, SemanticChange::MetricRemoved { .. })));
+    }
+
+    #[test]
+    fn test_version_history_diff_missing_version() {
+        let history = VersionHistory::new(1, metric_yml("SELECT 1", "bar"));
+        assert!(history.diff(1, 2).is_err());
+        assert!(history.diff(1, 1).unwrap().changes.is_empty());
+    }
+}
@@ -33,6 +33,7 @@ impl AssetTestHelpers {
             time_frame: "last 30 days".to_string(),
             chart_config: create_default_chart_config(),
             dataset_ids: Vec::new(),
+            drill_targets: Vec::new(),
         };
         
         let metric_file = MetricFile {
@@ -0,0 +1,190 @@
+use std::collections::{BTreeSet, HashMap};
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::tokenize::tokenize;
+use crate::{RetrievalDocument, Retriever, ScoredDocument};
+
+/// Okapi BM25 over the text of each dataset
+///
+/// Runs entirely in memory over the candidate documents, so it needs no
+/// external service and always ranks the same input the same way.
+#[derive(Debug, Clone, Copy)]
+pub struct Bm25Retriever {
+    /// Term frequency saturation
+    pub k1: f64,
+    /// How strongly longer documents are penalised
+    pub b: f64,
+}
+
+impl Default for Bm25Retriever {
+    fn default() -> Self {
+        Self { k1: 1.2, b: 0.75 }
+    }
+}
+
+impl Bm25Retriever {
+    /// Scores every document against `query`, most relevant first
+    ///
+    /// Documents sharing no term with the query are left out. Ties keep the
+    /// order of `documents`.
+    pub fn rank(&self, query: &str, documents: &[RetrievalDocument]) -> Vec<ScoredDocument> {
+        // Ordered so scores are summed in the same order on every call
+        let query_terms: BTreeSet<String> = tokenize(query).into_iter().collect();
+        if query_terms.is_empty() || documents.is_empty() {
+            return vec![];
+        }
+
+        // The name is part of the document so datasets matching by name alone
+        // are still found
+        let term_counts: Vec<HashMap<String, usize>> = documents
+            .iter()
+            .map(|document| {
+                let mut counts = HashMap::new();
+                for term in tokenize(&document.name)
+                    .into_iter()
+                    .chain(tokenize(&document.content))
+                {
+                    *counts.entry(term).or_insert(0) += 1;
+                }
+                counts
+            })
+            .collect();
+
+        let lengths: Vec<f64> = term_counts
+            .iter()
+            .map(|counts| counts.values().sum::<usize>() as f64)
+            .collect();
+        let average_length = (lengths.iter().sum::<f64>() / documents.len() as f64).max(1.0);
+        let document_count = documents.len() as f64;
+
+        let idf: HashMap<&str, f64> = query_terms
+            .iter()
+            .map(|term| {
+                let frequency = term_counts
+                    .iter()
+                    .filter(|counts| counts.contains_key(term))
+                    .count() as f64;
+                let idf = (1.0 + (document_count - frequency + 0.5) / (frequency + 0.5)).ln();
+                (term.as_str(), idf)
+            })
+            .collect();
+
+        let mut scored: Vec<(usize, f64)> = term_counts
+            .iter()
+            .enumerate()
+            .filter_map(|(index, counts)| {
+                let length_norm = 1.0 - self.b + self.b * lengths[index] / average_length;
+                let score: f64 = query_terms
+                    .iter()
+                    .filter_map(|term| {
+                        let tf = *counts.get(term)? as f64;
+                        Some(
+                            idf[term.as_str()] * tf * (self.k1 + 1.0)
+                                / (tf + self.k1 * length_norm),
+                        )
+                    })
+                    .sum();
+                (score > 0.0).then_some((index, score))
+            })
+            .collect();
+
+        // Stable sort keeps ties in input order
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        scored
+            .into_iter()
+            .map(|(index, score)| ScoredDocument {
+                id: documents[index].id,
+                score,
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Retriever for Bm25Retriever {
+    fn name(&self) -> &'static str {
+        "bm25"
+    }
+
+    async fn retrieve(
+        &self,
+        query: &str,
+        documents: &[RetrievalDocument],
+        limit: usize,
+    ) -> Result<Vec<ScoredDocument>> {
+        let mut ranked = self.rank(query, documents);
+        ranked.truncate(limit);
+        Ok(ranked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn document(name: &str, content: &str) -> RetrievalDocument {
+        RetrievalDocument {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_rank_prefers_matching_datasets() {
+        let documents = vec![
+            document(
+                "products",
+                "name: products\ndimensions:\n  - name: category\n  - name: price",
+            ),
+            document(
+                "orders",
+                "name: orders\ndescription: Customer orders\nmeasures:\n  - name: order_total\ndimensions:\n  - name: customer_id",
+            ),
+            document(
+                "customers",
+                "name: customers\ndescription: One row per customer\ndimensions:\n  - name: region",
+            ),
+        ];
+
+        let ranked = Bm25Retriever::default().rank("total orders per customer", &documents);
+
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].id, documents[1].id);
+        assert_eq!(ranked[1].id, documents[2].id);
+        assert!(ranked[0].score > ranked[1].score);
+
+        // Same input, same ranking
+        assert_eq!(
+            Bm25Retriever::default().rank("total orders per customer", &documents),
+            ranked
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retrieve_applies_limit_and_ignores_empty_queries() {
+        let documents = vec![
+            document("sales", "revenue by month"),
+            document("sales_daily", "revenue by day"),
+        ];
+        let retriever = Bm25Retriever::default();
+
+        assert_eq!(
+            retriever
+                .retrieve("revenue", &documents, 1)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(retriever
+            .retrieve("the of", &documents, 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::env;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use cohere_rust::{
+    api::rerank::{ReRankModel, ReRankRequest},
+    Cohere,
+};
+use tracing::warn;
+
+use crate::{Reranker, RetrievalDocument, ScoredDocument};
+
+/// Reranks candidates with Cohere's hosted rerank model
+pub struct CohereReranker {
+    client: Cohere,
+}
+
+impl CohereReranker {
+    /// A reranker using `COHERE_API_KEY`, or `None` when it isn't set
+    pub fn from_env() -> Option<Self> {
+        env::var("COHERE_API_KEY")
+            .ok()
+            .filter(|key| !key.is_empty())
+            .map(|_| Self {
+                client: Cohere::default(),
+            })
+    }
+}
+
+#[async_trait]
+impl Reranker for CohereReranker {
+    fn name(&self) -> &'static str {
+        "cohere"
+    }
+
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: &[RetrievalDocument],
+        candidates: Vec<ScoredDocument>,
+        limit: usize,
+    ) -> Result<Vec<ScoredDocument>> {
+        let contents: HashMap<_, _> = documents
+            .iter()
+            .map(|document| (document.id, document.content.as_str()))
+            .collect();
+        let candidates: Vec<ScoredDocument> = candidates
+            .into_iter()
+            .filter(|candidate| contents.contains_key(&candidate.id))
+            .collect();
+        if candidates.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let texts: Vec<String> = candidates
+            .iter()
+            .map(|candidate| contents[&candidate.id].to_string())
+            .collect();
+
+        let request = ReRankRequest {
+            query,
+            documents: &texts,
+            model: ReRankModel::EnglishV3,
+            top_n: Some(limit as u64),
+            ..Default::default()
+        };
+
+        let results = self
+            .client
+            .rerank(&request)
+            .await
+            .map_err(|e| anyhow!("Cohere rerank failed: {}", e))?;
+
+        Ok(results
+            .into_iter()
+            .filter_map(|result| match candidates.get(result.index as usize) {
+                Some(candidate) => Some(ScoredDocument {
+                    id: candidate.id,
+                    score: result.relevance_score,
+                }),
+                None => {
+                    warn!(
+                        "Invalid document index {} from Cohere for query '{}'",
+                        result.index, query
+                    );
+                    None
+                }
+            })
+            .collect())
+    }
+}
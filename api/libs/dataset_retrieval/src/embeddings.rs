@@ -0,0 +1,386 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context, Result};
+use database::pool::get_sqlx_pool;
+use litellm::{embedding_dimensions, EmbeddingRequest, LiteLLMClient};
+use sha2::{Digest, Sha256};
+use tracing::debug;
+use uuid::Uuid;
+
+// Texts per embeddings request
+const EMBEDDING_BATCH_SIZE: usize = 100;
+
+// Keeps long dataset YAML within embedding model input limits
+const MAX_EMBEDDING_TEXT_CHARS: usize = 16_000;
+
+/// A column to embed alongside its dataset
+#[derive(Debug, Clone)]
+pub struct ColumnEmbeddingInput {
+    pub name: String,
+    /// Built with [`column_embedding_text`]
+    pub text: String,
+}
+
+/// A dataset to embed for vector retrieval
+#[derive(Debug, Clone)]
+pub struct DatasetEmbeddingInput {
+    pub dataset_id: Uuid,
+    pub organization_id: Uuid,
+    /// Built with [`dataset_embedding_text`]
+    pub text: String,
+    /// The dataset's current columns; embeddings of other columns are removed
+    pub columns: Vec<ColumnEmbeddingInput>,
+}
+
+/// What [`sync_dataset_embeddings`] did
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EmbeddingSyncStats {
+    /// Dataset and column texts that were new or changed and got embedded
+    pub embedded: usize,
+    /// Texts whose stored embedding was already current
+    pub unchanged: usize,
+    /// Column embeddings removed because the column no longer exists
+    pub removed: usize,
+}
+
+fn truncate_text(text: String) -> String {
+    match text.char_indices().nth(MAX_EMBEDDING_TEXT_CHARS) {
+        Some((end, _)) => text[..end].to_string(),
+        None => text,
+    }
+}
+
+/// The text embedded for a dataset: its name, description and YAML
+pub fn dataset_embedding_text(name: &str, description: Option<&str>, yml: Option<&str>) -> String {
+    let mut text = format!("Dataset: {}", name);
+    if let Some(description) = description.filter(|d| !d.trim().is_empty()) {
+        text.push_str("\nDescription: ");
+        text.push_str(description.trim());
+    }
+    if let Some(yml) = yml.filter(|y| !y.trim().is_empty()) {
+        text.push('\n');
+        text.push_str(yml);
+    }
+
+    truncate_text(text)
+}
+
+/// The text embedded for a column: its name, type and description, with
+/// the dataset it belongs to
+pub fn column_embedding_text(
+    dataset_name: &str,
+    column_name: &str,
+    description: Option<&str>,
+    type_: Option<&str>,
+) -> String {
+    let mut text = format!("Column {} of dataset {}", column_name, dataset_name);
+    if let Some(type_) = type_.filter(|t| !t.trim().is_empty()) {
+        text.push_str(&format!(" ({})", type_.trim()));
+    }
+    if let Some(description) = description.filter(|d| !d.trim().is_empty()) {
+        text.push_str(": ");
+        text.push_str(description.trim());
+    }
+
+    truncate_text(text)
+}
+
+/// Fingerprint of an embedded text, used to skip re-embedding unchanged content
+pub fn content_hash(text: &str) -> String {
+    format!("{:x}", Sha256::digest(text.as_bytes()))
+}
+
+/// Embeds `texts` with `model`, in order, at the model's configured dimensions
+pub async fn embed_texts(texts: &[String], model: &str) -> Result<Vec<Vec<f32>>> {
+    let client = LiteLLMClient::new(None, None);
+    let mut embeddings = Vec::with_capacity(texts.len());
+
+    for batch in texts.chunks(EMBEDDING_BATCH_SIZE) {
+        let response = client
+            .generate_embeddings(EmbeddingRequest {
+                model: model.to_string(),
+                input: batch.to_vec(),
+                dimensions: Some(embedding_dimensions(model)),
+                encoding_format: Some("float".to_string()),
+                user: None,
+            })
+            .await
+            .context("Failed to generate dataset embeddings")?;
+
+        if response.data.len() != batch.len() {
+            return Err(anyhow!(
+                "Expected {} embeddings but received {}",
+                batch.len(),
+                response.data.len()
+            ));
+        }
+
+        embeddings.extend(response.data.into_iter().map(|data| data.embedding));
+    }
+
+    Ok(embeddings)
+}
+
+/// Formats an embedding as a pgvector literal, e.g. `[0.1,0.2]`
+pub(crate) fn vector_literal(embedding: &[f32]) -> String {
+    let values: Vec<String> = embedding.iter().map(|value| value.to_string()).collect();
+    format!("[{}]", values.join(","))
+}
+
+/// A dataset or column text that needs (re-)embedding
+#[derive(Debug, Clone, PartialEq)]
+struct PendingEmbedding {
+    dataset_id: Uuid,
+    organization_id: Uuid,
+    /// `None` for the dataset itself
+    column_name: Option<String>,
+    text: String,
+    hash: String,
+}
+
+/// Picks the texts whose stored embedding is missing or out of date
+///
+/// `existing_datasets` maps dataset ids, and `existing_columns` dataset id and
+/// column name pairs, to the content hash of their stored embedding.
+fn pending_embeddings(
+    inputs: &[DatasetEmbeddingInput],
+    existing_datasets: &HashMap<Uuid, String>,
+    existing_columns: &HashMap<(Uuid, String), String>,
+) -> (Vec<PendingEmbedding>, usize) {
+    let mut pending = Vec::new();
+    let mut unchanged = 0;
+
+    for input in inputs {
+        let hash = content_hash(&input.text);
+        if existing_datasets.get(&input.dataset_id) == Some(&hash) {
+            unchanged += 1;
+        } else {
+            pending.push(PendingEmbedding {
+                dataset_id: input.dataset_id,
+                organization_id: input.organization_id,
+                column_name: None,
+                text: input.text.clone(),
+                hash,
+            });
+        }
+
+        for column in &input.columns {
+            let hash = content_hash(&column.text);
+            if existing_columns.get(&(input.dataset_id, column.name.clone())) == Some(&hash) {
+                unchanged += 1;
+            } else {
+                pending.push(PendingEmbedding {
+                    dataset_id: input.dataset_id,
+                    organization_id: input.organization_id,
+                    column_name: Some(column.name.clone()),
+                    text: column.text.clone(),
+                    hash,
+                });
+            }
+        }
+    }
+
+    (pending, unchanged)
+}
+
+/// Brings the stored embeddings of datasets and their columns up to date
+///
+/// Only texts that are new or changed since they were last embedded with
+/// `model` are sent to the embedding model, so redeploying unchanged datasets
+/// is cheap. Embeddings of columns that were removed are deleted.
+pub async fn sync_dataset_embeddings(
+    inputs: &[DatasetEmbeddingInput],
+    model: &str,
+) -> Result<EmbeddingSyncStats> {
+    if inputs.is_empty() {
+        return Ok(EmbeddingSyncStats::default());
+    }
+
+    let dataset_ids: Vec<Uuid> = inputs.iter().map(|input| input.dataset_id).collect();
+    let mut conn = get_sqlx_pool().acquire().await?;
+
+    let existing_datasets: HashMap<Uuid, String> = sqlx::query_as::<_, (Uuid, String)>(
+        "SELECT dataset_id, content_hash FROM dataset_embeddings WHERE dataset_id = ANY($1) AND model = $2",
+    )
+    .bind(&dataset_ids)
+    .bind(model)
+    .fetch_all(&mut *conn)
+    .await
+    .context("Failed to load dataset embedding hashes")?
+    .into_iter()
+    .collect();
+
+    let existing_columns: HashMap<(Uuid, String), String> =
+        sqlx::query_as::<_, (Uuid, String, String)>(
+            "SELECT dataset_id, column_name, content_hash FROM dataset_column_embeddings WHERE dataset_id = ANY($1) AND model = $2",
+        )
+        .bind(&dataset_ids)
+        .bind(model)
+        .fetch_all(&mut *conn)
+        .await
+        .context("Failed to load column embedding hashes")?
+        .into_iter()
+        .map(|(dataset_id, column_name, hash)| ((dataset_id, column_name), hash))
+        .collect();
+
+    let mut stats = EmbeddingSyncStats::default();
+
+    for input in inputs {
+        let current: Vec<String> = input.columns.iter().map(|c| c.name.clone()).collect();
+
+        let removed = sqlx::query(
+            "DELETE FROM dataset_column_embeddings WHERE dataset_id = $1 AND NOT (column_name = ANY($2))",
+        )
+        .bind(input.dataset_id)
+        .bind(&current)
+        .execute(&mut *conn)
+        .await
+        .with_context(|| {
+            format!("Failed to remove column embeddings for dataset {}", input.dataset_id)
+        })?;
+        stats.removed += removed.rows_affected() as usize;
+    }
+
+    let (pending, unchanged) = pending_embeddings(inputs, &existing_datasets, &existing_columns);
+    stats.unchanged = unchanged;
+    if pending.is_empty() {
+        return Ok(stats);
+    }
+
+    let texts: Vec<String> = pending.iter().map(|p| p.text.clone()).collect();
+    let embeddings = embed_texts(&texts, model).await?;
+
+    for (pending, embedding) in pending.iter().zip(embeddings) {
+        let query = match &pending.column_name {
+            None => sqlx::query(
+                r#"
+                INSERT INTO dataset_embeddings (dataset_id, organization_id, model, content_hash, embedding, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, $5::halfvec, now(), now())
+                ON CONFLICT (dataset_id, model) DO UPDATE SET
+                    organization_id = EXCLUDED.organization_id,
+                    content_hash = EXCLUDED.content_hash,
+                    embedding = EXCLUDED.embedding,
+                    updated_at = now()
+                "#,
+            )
+            .bind(pending.dataset_id)
+            .bind(pending.organization_id)
+            .bind(model)
+            .bind(&pending.hash)
+            .bind(vector_literal(&embedding)),
+            Some(column_name) => sqlx::query(
+                r#"
+                INSERT INTO dataset_column_embeddings (dataset_id, column_name, organization_id, model, content_hash, embedding, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, $5, $6::halfvec, now(), now())
+                ON CONFLICT (dataset_id, column_name, model) DO UPDATE SET
+                    organization_id = EXCLUDED.organization_id,
+                    content_hash = EXCLUDED.content_hash,
+                    embedding = EXCLUDED.embedding,
+                    updated_at = now()
+                "#,
+            )
+            .bind(pending.dataset_id)
+            .bind(column_name)
+            .bind(pending.organization_id)
+            .bind(model)
+            .bind(&pending.hash)
+            .bind(vector_literal(&embedding)),
+        };
+
+        query.execute(&mut *conn).await.with_context(|| {
+            format!(
+                "Failed to store embedding for dataset {}",
+                pending.dataset_id
+            )
+        })?;
+        stats.embedded += 1;
+    }
+
+    debug!(
+        embedded = stats.embedded,
+        unchanged = stats.unchanged,
+        removed = stats.removed,
+        %model,
+        "Synced dataset embeddings"
+    );
+
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedding_texts() {
+        assert_eq!(
+            dataset_embedding_text("orders", Some(" Customer orders "), Some("name: orders")),
+            "Dataset: orders\nDescription: Customer orders\nname: orders"
+        );
+        assert_eq!(
+            dataset_embedding_text("orders", Some(""), None),
+            "Dataset: orders"
+        );
+        assert_eq!(
+            dataset_embedding_text("x", None, Some(&"é".repeat(MAX_EMBEDDING_TEXT_CHARS)))
+                .chars()
+                .count(),
+            MAX_EMBEDDING_TEXT_CHARS
+        );
+        assert_eq!(
+            column_embedding_text(
+                "orders",
+                "order_total",
+                Some("Order value in USD"),
+                Some("number")
+            ),
+            "Column order_total of dataset orders (number): Order value in USD"
+        );
+        assert_eq!(vector_literal(&[0.5, -1.0]), "[0.5,-1]");
+    }
+
+    #[test]
+    fn test_pending_embeddings_skips_unchanged_content() {
+        let dataset_id = Uuid::new_v4();
+        let input = DatasetEmbeddingInput {
+            dataset_id,
+            organization_id: Uuid::new_v4(),
+            text: "Dataset: orders".to_string(),
+            columns: vec![
+                ColumnEmbeddingInput {
+                    name: "id".to_string(),
+                    text: "Column id of dataset orders".to_string(),
+                },
+                ColumnEmbeddingInput {
+                    name: "total".to_string(),
+                    text: "Column total of dataset orders: Order value".to_string(),
+                },
+            ],
+        };
+
+        let existing_datasets = HashMap::from([(dataset_id, content_hash("Dataset: orders"))]);
+        let existing_columns = HashMap::from([
+            (
+                (dataset_id, "id".to_string()),
+                content_hash("Column id of dataset orders"),
+            ),
+            (
+                (dataset_id, "total".to_string()),
+                content_hash("Column total of dataset orders"),
+            ),
+        ]);
+
+        let (pending, unchanged) = pending_embeddings(
+            std::slice::from_ref(&input),
+            &existing_datasets,
+            &existing_columns,
+        );
+        assert_eq!(unchanged, 2);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].column_name.as_deref(), Some("total"));
+
+        let (pending, unchanged) = pending_embeddings(&[input], &HashMap::new(), &HashMap::new());
+        assert_eq!(unchanged, 0);
+        assert_eq!(pending.len(), 3);
+    }
+}
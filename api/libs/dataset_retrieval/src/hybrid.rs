@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures::future::join_all;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::{
+    Bm25Retriever, CohereReranker, Reranker, RetrievalDocument, Retriever, ScoredDocument,
+    VectorRetriever,
+};
+
+// Dampens the weight of top ranks in reciprocal rank fusion
+const RRF_K: f64 = 60.0;
+
+// How many fused candidates each retriever contributes and the reranker sees
+const DEFAULT_CANDIDATE_POOL: usize = 50;
+
+/// Merges several rankings, scoring each document by `sum(1 / (k + rank))`
+///
+/// Documents ranked by more retrievers, and ranked higher, come first. Ties
+/// keep the order in which documents were first seen.
+pub fn reciprocal_rank_fusion(rankings: &[Vec<ScoredDocument>]) -> Vec<ScoredDocument> {
+    let mut scores: HashMap<Uuid, f64> = HashMap::new();
+    let mut order: Vec<Uuid> = Vec::new();
+
+    for ranking in rankings {
+        for (rank, document) in ranking.iter().enumerate() {
+            let score = scores.entry(document.id).or_insert_with(|| {
+                order.push(document.id);
+                0.0
+            });
+            *score += 1.0 / (RRF_K + rank as f64 + 1.0);
+        }
+    }
+
+    let mut fused: Vec<ScoredDocument> = order
+        .into_iter()
+        .map(|id| ScoredDocument {
+            id,
+            score: scores[&id],
+        })
+        .collect();
+    fused.sort_by(|a, b| b.score.total_cmp(&a.score));
+    fused
+}
+
+/// Fuses several retrievers and optionally reranks the result
+///
+/// A failing retriever or reranker is logged and skipped, so search degrades
+/// to whatever still works (at worst BM25) instead of failing.
+pub struct HybridRetriever {
+    retrievers: Vec<Arc<dyn Retriever>>,
+    reranker: Option<Arc<dyn Reranker>>,
+    candidate_pool: usize,
+}
+
+impl HybridRetriever {
+    pub fn new(retrievers: Vec<Arc<dyn Retriever>>, reranker: Option<Arc<dyn Reranker>>) -> Self {
+        Self {
+            retrievers,
+            reranker,
+            candidate_pool: DEFAULT_CANDIDATE_POOL,
+        }
+    }
+
+    pub fn with_candidate_pool(mut self, candidate_pool: usize) -> Self {
+        self.candidate_pool = candidate_pool.max(1);
+        self
+    }
+
+    /// Builds the retriever configured for this deployment
+    ///
+    /// `DATASET_RETRIEVERS` lists the retrievers to fuse (`bm25`, `vector`),
+    /// defaulting to both. `DATASET_RERANKER` is `cohere` or `none`; when
+    /// unset, Cohere is used if `COHERE_API_KEY` is set.
+    pub fn from_env(embedding_model: &str) -> Self {
+        let names = env::var("DATASET_RETRIEVERS").unwrap_or_else(|_| "bm25,vector".to_string());
+
+        let mut retrievers: Vec<Arc<dyn Retriever>> = Vec::new();
+        for name in names.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            match name {
+                "bm25" => retrievers.push(Arc::new(Bm25Retriever::default())),
+                "vector" => retrievers.push(Arc::new(VectorRetriever::new(embedding_model))),
+                other => warn!("Ignoring unknown dataset retriever '{}'", other),
+            }
+        }
+        if retrievers.is_empty() {
+            retrievers.push(Arc::new(Bm25Retriever::default()));
+        }
+
+        let reranker: Option<Arc<dyn Reranker>> = match env::var("DATASET_RERANKER").ok().as_deref()
+        {
+            Some("none") => None,
+            Some("cohere") | None => CohereReranker::from_env().map(|r| Arc::new(r) as _),
+            Some(other) => {
+                warn!("Ignoring unknown dataset reranker '{}'", other);
+                None
+            }
+        };
+
+        Self::new(retrievers, reranker)
+    }
+}
+
+#[async_trait]
+impl Retriever for HybridRetriever {
+    fn name(&self) -> &'static str {
+        "hybrid"
+    }
+
+    async fn retrieve(
+        &self,
+        query: &str,
+        documents: &[RetrievalDocument],
+        limit: usize,
+    ) -> Result<Vec<ScoredDocument>> {
+        if documents.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let pool = self.candidate_pool.max(limit);
+        let results = join_all(
+            self.retrievers
+                .iter()
+                .map(|retriever| retriever.retrieve(query, documents, pool)),
+        )
+        .await;
+
+        let mut rankings = Vec::new();
+        let mut last_error = None;
+        for (retriever, result) in self.retrievers.iter().zip(results) {
+            match result {
+                Ok(ranking) => {
+                    debug!(
+                        retriever = retriever.name(),
+                        count = ranking.len(),
+                        "Retrieved datasets"
+                    );
+                    rankings.push(ranking);
+                }
+                Err(e) => {
+                    warn!(retriever = retriever.name(), error = %e, "Dataset retriever failed");
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        if rankings.is_empty() {
+            return Err(last_error.unwrap_or_else(|| anyhow!("No dataset retrievers configured")));
+        }
+
+        let mut fused = reciprocal_rank_fusion(&rankings);
+        fused.truncate(pool);
+
+        if let Some(reranker) = &self.reranker {
+            match reranker
+                .rerank(query, documents, fused.clone(), limit)
+                .await
+            {
+                Ok(reranked) => return Ok(reranked),
+                Err(e) => {
+                    warn!(reranker = reranker.name(), error = %e, "Dataset reranker failed, keeping fused ranking");
+                }
+            }
+        }
+
+        fused.truncate(limit);
+        Ok(fused)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Failing;
+
+    #[async_trait]
+    impl Retriever for Failing {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        async fn retrieve(
+            &self,
+            _query: &str,
+            _documents: &[RetrievalDocument],
+            _limit: usize,
+        ) -> Result<Vec<ScoredDocument>> {
+            Err(anyhow!("unavailable"))
+        }
+    }
+
+    fn scored(ids: &[Uuid]) -> Vec<ScoredDocument> {
+        ids.iter()
+            .map(|id| ScoredDocument {
+                id: *id,
+                score: 1.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_reciprocal_rank_fusion() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        let fused = reciprocal_rank_fusion(&[scored(&[a, b]), scored(&[b, c])]);
+        let ids: Vec<Uuid> = fused.iter().map(|d| d.id).collect();
+
+        assert_eq!(ids, vec![b, a, c]);
+    }
+
+    #[tokio::test]
+    async fn test_retrieve_skips_failing_retrievers() {
+        let documents = vec![
+            RetrievalDocument {
+                id: Uuid::new_v4(),
+                name: "orders".to_string(),
+                content: "order totals".to_string(),
+            },
+            RetrievalDocument {
+                id: Uuid::new_v4(),
+                name: "products".to_string(),
+                content: "product catalog".to_string(),
+            },
+        ];
+
+        let retriever = HybridRetriever::new(
+            vec![Arc::new(Failing), Arc::new(Bm25Retriever::default())],
+            None,
+        );
+        let results = retriever.retrieve("orders", &documents, 10).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, documents[0].id);
+
+        let failing = HybridRetriever::new(vec![Arc::new(Failing)], None);
+        assert!(failing.retrieve("orders", &documents, 10).await.is_err());
+    }
+}
@@ -0,0 +1,69 @@
+//! Retrieval of datasets relevant to a natural-language query.
+//!
+//! Catalog search ranks the datasets a user can see with one or more
+//! [`Retriever`]s, fuses their rankings and optionally reorders the result
+//! with a [`Reranker`]. The local retrievers ([`Bm25Retriever`] and
+//! [`VectorRetriever`]) need no hosted service beyond the embedding model, so
+//! search keeps working when Cohere isn't configured.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+pub mod bm25;
+pub mod cohere;
+pub mod embeddings;
+pub mod hybrid;
+mod tokenize;
+pub mod vector;
+
+pub use bm25::Bm25Retriever;
+pub use cohere::CohereReranker;
+pub use hybrid::{reciprocal_rank_fusion, HybridRetriever};
+pub use vector::VectorRetriever;
+
+/// A dataset as seen by the retrievers
+#[derive(Debug, Clone)]
+pub struct RetrievalDocument {
+    pub id: Uuid,
+    pub name: String,
+    /// The text to match against, usually the dataset's YAML
+    pub content: String,
+}
+
+/// A dataset matched by a retriever; higher scores are more relevant
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoredDocument {
+    pub id: Uuid,
+    pub score: f64,
+}
+
+/// Ranks candidate datasets against a query
+#[async_trait]
+pub trait Retriever: Send + Sync {
+    /// Short name used in logs
+    fn name(&self) -> &'static str;
+
+    /// Returns at most `limit` of `documents`, most relevant first
+    async fn retrieve(
+        &self,
+        query: &str,
+        documents: &[RetrievalDocument],
+        limit: usize,
+    ) -> Result<Vec<ScoredDocument>>;
+}
+
+/// Reorders retrieved datasets, typically with a cross-encoder
+#[async_trait]
+pub trait Reranker: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Reorders `candidates` (drawn from `documents`) and keeps at most `limit`
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: &[RetrievalDocument],
+        candidates: Vec<ScoredDocument>,
+        limit: usize,
+    ) -> Result<Vec<ScoredDocument>>;
+}
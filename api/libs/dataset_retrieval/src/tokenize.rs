@@ -0,0 +1,41 @@
+// Words too common in dataset YAML and questions to say anything about relevance
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "how", "in", "is", "it", "me",
+    "of", "on", "or", "show", "that", "the", "this", "to", "was", "what", "which", "with",
+];
+
+/// Splits text into lowercase terms for lexical matching
+///
+/// Identifiers are split on any non-alphanumeric character, so `order_total`
+/// yields `order` and `total`. A trailing plural `s` is dropped so "customers"
+/// matches a `customer` column.
+pub(crate) fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(|token| token.to_lowercase())
+        .filter(|token| token.len() > 1 && !STOPWORDS.contains(&token.as_str()))
+        .map(|token| stem(&token))
+        .collect()
+}
+
+fn stem(token: &str) -> String {
+    if token.len() > 3 && token.ends_with('s') && !token.ends_with("ss") {
+        token[..token.len() - 1].to_string()
+    } else {
+        token.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_splits_identifiers_and_drops_plurals() {
+        assert_eq!(
+            tokenize("Show me the Customers by order_total, in 2024"),
+            vec!["customer", "order", "total", "2024"]
+        );
+        assert_eq!(tokenize("business"), vec!["business"]);
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use database::pool::get_sqlx_pool;
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::embeddings::{embed_texts, vector_literal};
+use crate::{RetrievalDocument, Retriever, ScoredDocument};
+
+/// Cosine similarity between the query and the stored embeddings of each
+/// dataset and its columns
+///
+/// Embeddings are computed when datasets are deployed. Only the candidate
+/// datasets, which are the ones the user has access to, are searched.
+/// Datasets without an embedding for `embedding_model` aren't returned, and
+/// when none of the candidates have one the query isn't embedded at all.
+pub struct VectorRetriever {
+    embedding_model: String,
+}
+
+impl VectorRetriever {
+    pub fn new(embedding_model: impl Into<String>) -> Self {
+        Self {
+            embedding_model: embedding_model.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Retriever for VectorRetriever {
+    fn name(&self) -> &'static str {
+        "vector"
+    }
+
+    async fn retrieve(
+        &self,
+        query: &str,
+        documents: &[RetrievalDocument],
+        limit: usize,
+    ) -> Result<Vec<ScoredDocument>> {
+        if documents.is_empty() || query.trim().is_empty() {
+            return Ok(vec![]);
+        }
+
+        let dataset_ids: Vec<Uuid> = documents.iter().map(|document| document.id).collect();
+        let mut conn = get_sqlx_pool().acquire().await?;
+
+        let embedded: i64 = sqlx::query_scalar(
+            r#"
+            SELECT (SELECT COUNT(*) FROM dataset_embeddings WHERE dataset_id = ANY($1) AND model = $2)
+                 + (SELECT COUNT(*) FROM dataset_column_embeddings WHERE dataset_id = ANY($1) AND model = $2)
+            "#,
+        )
+        .bind(&dataset_ids)
+        .bind(&self.embedding_model)
+        .fetch_one(&mut *conn)
+        .await
+        .context("Failed to count dataset embeddings")?;
+
+        if embedded == 0 {
+            debug!(model = %self.embedding_model, "No dataset embeddings for candidates");
+            return Ok(vec![]);
+        }
+
+        let query_embedding = embed_texts(&[query.to_string()], &self.embedding_model)
+            .await?
+            .pop()
+            .unwrap_or_default();
+
+        // A dataset scores as its best match, either as a whole or through one
+        // of its columns, so a question about a single field still finds it
+        let rows: Vec<(Uuid, f64)> = sqlx::query_as(
+            r#"
+            WITH query AS (SELECT $1::halfvec AS embedding),
+            matches AS (
+                SELECT dataset_id, 1 - (e.embedding <=> query.embedding) AS similarity
+                FROM dataset_embeddings e, query
+                WHERE dataset_id = ANY($2) AND model = $3
+                UNION ALL
+                SELECT dataset_id, 1 - (c.embedding <=> query.embedding) AS similarity
+                FROM dataset_column_embeddings c, query
+                WHERE dataset_id = ANY($2) AND model = $3
+            )
+            SELECT dataset_id, MAX(similarity) AS similarity
+            FROM matches
+            GROUP BY dataset_id
+            ORDER BY similarity DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(vector_literal(&query_embedding))
+        .bind(&dataset_ids)
+        .bind(&self.embedding_model)
+        .bind(limit as i64)
+        .fetch_all(&mut *conn)
+        .await
+        .context("Failed to search dataset embeddings")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(id, score)| ScoredDocument { id, score })
+            .collect())
+    }
+}
@@ -10,7 +10,7 @@ pub mod resend;
 // // mod errors;
 
 // Re-exports public API from the resend module
-pub use resend::{send_email, EmailType, CollectionInvite, DashboardInvite, ThreadInvite, InviteToBuster};
+pub use resend::{send_email, EmailType, CollectionInvite, DashboardInvite, ThreadInvite, InviteToBuster, MetricReviewNotification, CommentMention};
 
 // // Example placeholder for where the resend logic might go
 // pub async fn resend_email(/* parameters */) -> Result<()> {
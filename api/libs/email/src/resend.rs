@@ -42,12 +42,34 @@ pub struct InviteToBuster {
     pub organization_name: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct MetricReviewNotification {
+    pub metric_name: String,
+    pub metric_id: Uuid,
+    pub actor_name: String,
+    /// Past-tense description of the transition, e.g. "approved" or "requested a review of"
+    pub action: String,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CommentMention {
+    pub asset_name: String,
+    pub asset_id: Uuid,
+    /// "metric" or "dashboard", used for wording and the link
+    pub asset_kind: &'static str,
+    pub author_name: String,
+    pub comment: String,
+}
+
 #[derive(Debug, Clone)] // Added derives
 pub enum EmailType {
     CollectionInvite(CollectionInvite),
     DashboardInvite(DashboardInvite),
     ThreadInvite(ThreadInvite),
     InviteToBuster(InviteToBuster),
+    MetricReview(MetricReviewNotification),
+    CommentMention(CommentMention),
 }
 
 struct EmailParams {
@@ -72,6 +94,10 @@ pub async fn send_email(to_addresses: HashSet<String>, email_type: EmailType) ->
         EmailType::InviteToBuster(invite_to_buster) => {
             create_invite_to_buster_params(invite_to_buster)
         }
+        EmailType::MetricReview(metric_review) => create_metric_review_params(metric_review),
+        EmailType::CommentMention(comment_mention) => {
+            create_comment_mention_params(comment_mention)
+        }
     };
 
     let email_html = EMAIL_TEMPLATE
@@ -246,4 +272,60 @@ fn create_invite_to_buster_params(invite_to_buster: InviteToBuster) -> EmailPara
     }
 }
 
+fn create_metric_review_params(metric_review: MetricReviewNotification) -> EmailParams {
+    let mut message = format!(
+        "{actor_name} {action} the metric: '{metric_name}'.",
+        actor_name = metric_review.actor_name,
+        action = metric_review.action,
+        metric_name = metric_review.metric_name
+    );
+    if let Some(comment) = metric_review.comment.filter(|c| !c.trim().is_empty()) {
+        message.push_str(&format!(" Comment: \"{}\"", comment.trim()));
+    }
+
+    EmailParams {
+        subject: format!(
+            "{actor_name} {action} the metric: '{metric_name}'",
+            actor_name = metric_review.actor_name,
+            action = metric_review.action,
+            metric_name = metric_review.metric_name
+        ),
+        message,
+        button_link: format!(
+            "{}/app/metrics/{metric_id}",
+            *BUSTER_URL,
+            metric_id = metric_review.metric_id
+        ),
+        button_text: "View Metric",
+    }
+}
+
+fn create_comment_mention_params(comment_mention: CommentMention) -> EmailParams {
+    EmailParams {
+        subject: format!(
+            "{author_name} mentioned you on the {asset_kind}: '{asset_name}'",
+            author_name = comment_mention.author_name,
+            asset_kind = comment_mention.asset_kind,
+            asset_name = comment_mention.asset_name
+        ),
+        message: format!(
+            "{author_name} mentioned you on the {asset_kind}: '{asset_name}'. \"{comment}\"",
+            author_name = comment_mention.author_name,
+            asset_kind = comment_mention.asset_kind,
+            asset_name = comment_mention.asset_name,
+            comment = comment_mention.comment.trim()
+        ),
+        button_link: format!(
+            "{}/app/{asset_kind}s/{asset_id}",
+            *BUSTER_URL,
+            asset_kind = comment_mention.asset_kind,
+            asset_id = comment_mention.asset_id
+        ),
+        button_text: match comment_mention.asset_kind {
+            "dashboard" => "View Dashboard",
+            _ => "View Metric",
+        },
+    }
+}
+
 // Tests are moved to libs/email/tests/resend_tests.rs 
\ No newline at end of file
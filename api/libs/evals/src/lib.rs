@@ -0,0 +1,20 @@
+//! Golden question evals for the Buster agent
+//!
+//! A suite is a YAML file of questions with the datasets, results and tool
+//! calls expected for each. The runner asks `BusterMultiAgent` every
+//! question, runs the SQL it produced on a fixture warehouse, and scores
+//! result equivalence, dataset recall, tool trajectory and assertions.
+//! Reports are written as JSON and Markdown, optionally compared against a
+//! previous run, and can be sent to `sql_evaluations` or Braintrust.
+
+pub mod report;
+pub mod runner;
+pub mod scoring;
+pub mod sinks;
+pub mod suite;
+pub mod warehouse;
+
+pub use report::{CaseResult, CaseScores, EvalReport};
+pub use runner::{EvalRunner, Transcript};
+pub use suite::{Assertion, EvalCase, EvalSuite};
+pub use warehouse::FixtureWarehouse;
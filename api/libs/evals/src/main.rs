@@ -0,0 +1,107 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+use database::pool::init_pools;
+use dotenv::dotenv;
+use evals::{sinks, EvalReport, EvalRunner, EvalSuite};
+use tracing_subscriber::EnvFilter;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Sink {
+    SqlEvaluations,
+    Braintrust,
+}
+
+/// Runs a golden question suite against the agent and writes a report
+#[derive(Parser, Debug)]
+#[command(name = "buster-eval")]
+struct Args {
+    /// Path to the suite YAML
+    suite: PathBuf,
+
+    /// Directory the JSON and Markdown reports are written to
+    #[arg(long, default_value = "eval-reports")]
+    out: PathBuf,
+
+    /// Previous JSON report to compare against
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// Label for this run, e.g. the prompt or model being tried
+    #[arg(long)]
+    label: Option<String>,
+
+    /// Only run these case ids
+    #[arg(long = "case")]
+    cases: Vec<String>,
+
+    /// Also send results here
+    #[arg(long = "sink", value_enum)]
+    sinks: Vec<Sink>,
+
+    /// Braintrust project for the braintrust sink
+    #[arg(long, env = "BRAINTRUST_EVAL_PROJECT_ID")]
+    braintrust_project: Option<String>,
+
+    /// Exit with an error if the pass rate falls below this fraction
+    #[arg(long)]
+    min_pass_rate: Option<f64>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+    let args = Args::parse();
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+        )
+        .init();
+
+    rustls::crypto::ring::default_provider()
+        .install_default()
+        .expect("Failed to install default crypto provider");
+
+    let suite = EvalSuite::load(&args.suite)?;
+    let baseline = args.baseline.as_ref().map(EvalReport::load).transpose()?;
+
+    init_pools().await?;
+
+    let runner = EvalRunner::new(suite).await?;
+    let report = runner.run(args.label.clone(), &args.cases).await?;
+
+    let (json_path, markdown_path) = report.write(&args.out, baseline.as_ref())?;
+    println!("{}", report.to_markdown(baseline.as_ref()));
+    println!(
+        "Wrote {} and {}",
+        json_path.display(),
+        markdown_path.display()
+    );
+
+    for sink in &args.sinks {
+        match sink {
+            Sink::SqlEvaluations => {
+                let rows = sinks::write_sql_evaluations(&report).await?;
+                println!("Wrote {} rows to sql_evaluations", rows);
+            }
+            Sink::Braintrust => match &args.braintrust_project {
+                Some(project_id) => {
+                    sinks::log_to_braintrust(&report, project_id).await?;
+                    println!("Logged run to Braintrust project {}", project_id);
+                }
+                None => anyhow::bail!("--braintrust-project or BRAINTRUST_EVAL_PROJECT_ID is required for the braintrust sink"),
+            },
+        }
+    }
+
+    if let Some(min_pass_rate) = args.min_pass_rate {
+        let pass_rate = report.summary.passed as f64 / report.summary.cases as f64;
+        if pass_rate < min_pass_rate {
+            anyhow::bail!("Pass rate {:.2} is below {:.2}", pass_rate, min_pass_rate);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::scoring::AssertionResult;
+
+/// Per-dimension scores for a case, each between 0 and 1
+///
+/// A dimension is `None` when the case sets no expectation for it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CaseScores {
+    pub datasets: Option<f64>,
+    pub result: Option<f64>,
+    pub trajectory: Option<f64>,
+    pub assertions: Option<f64>,
+}
+
+impl CaseScores {
+    /// Mean of the scored dimensions
+    pub fn overall(&self) -> f64 {
+        let scores: Vec<f64> = [self.datasets, self.result, self.trajectory, self.assertions]
+            .into_iter()
+            .flatten()
+            .collect();
+        if scores.is_empty() {
+            return 1.0;
+        }
+        scores.iter().sum::<f64>() / scores.len() as f64
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaseResult {
+    pub id: String,
+    pub question: String,
+    pub score: f64,
+    pub passed: bool,
+    pub scores: CaseScores,
+    /// Tools the agent called, in order
+    pub tools: Vec<String>,
+    /// Datasets the agent found in the catalog
+    pub datasets: Vec<String>,
+    /// SQL of the agent's final metric
+    pub sql: Option<String>,
+    pub answer: Option<String>,
+    pub assertions: Vec<AssertionResult>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportSummary {
+    pub cases: usize,
+    pub passed: usize,
+    pub errored: usize,
+    pub mean_score: f64,
+}
+
+/// The outcome of running a suite once
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvalReport {
+    pub run_id: Uuid,
+    pub suite: String,
+    /// Free-form label for what changed in this run, e.g. a prompt version
+    pub label: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub pass_threshold: f64,
+    pub summary: ReportSummary,
+    pub cases: Vec<CaseResult>,
+}
+
+impl EvalReport {
+    pub fn new(
+        suite: &str,
+        label: Option<String>,
+        pass_threshold: f64,
+        started_at: DateTime<Utc>,
+        cases: Vec<CaseResult>,
+    ) -> Self {
+        let summary = ReportSummary {
+            cases: cases.len(),
+            passed: cases.iter().filter(|c| c.passed).count(),
+            errored: cases.iter().filter(|c| c.error.is_some()).count(),
+            mean_score: if cases.is_empty() {
+                0.0
+            } else {
+                cases.iter().map(|c| c.score).sum::<f64>() / cases.len() as f64
+            },
+        };
+
+        Self {
+            run_id: Uuid::new_v4(),
+            suite: suite.to_string(),
+            label,
+            started_at,
+            finished_at: Utc::now(),
+            pass_threshold,
+            summary,
+            cases,
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read eval report {}", path.display()))?;
+        serde_json::from_str(&json).context("Failed to parse eval report")
+    }
+
+    /// Writes `<run_id>.json` and `<run_id>.md` to `dir`, comparing against
+    /// `baseline` in the Markdown when given
+    pub fn write(
+        &self,
+        dir: impl AsRef<Path>,
+        baseline: Option<&EvalReport>,
+    ) -> Result<(PathBuf, PathBuf)> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create report directory {}", dir.display()))?;
+
+        let json_path = dir.join(format!("{}.json", self.run_id));
+        let markdown_path = dir.join(format!("{}.md", self.run_id));
+        std::fs::write(&json_path, serde_json::to_string_pretty(self)?)?;
+        std::fs::write(&markdown_path, self.to_markdown(baseline))?;
+
+        Ok((json_path, markdown_path))
+    }
+
+    pub fn to_markdown(&self, baseline: Option<&EvalReport>) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# Eval report: {}", self.suite);
+        let _ = writeln!(out);
+        let _ = writeln!(out, "- Run: `{}`", self.run_id);
+        if let Some(label) = &self.label {
+            let _ = writeln!(out, "- Label: {}", label);
+        }
+        let _ = writeln!(
+            out,
+            "- Passed: {}/{} (threshold {:.2})",
+            self.summary.passed, self.summary.cases, self.pass_threshold
+        );
+        let _ = writeln!(out, "- Errored: {}", self.summary.errored);
+        let _ = write!(out, "- Mean score: {:.3}", self.summary.mean_score);
+        if let Some(baseline) = baseline {
+            let _ = write!(
+                out,
+                " ({:+.3} vs `{}`)",
+                self.summary.mean_score - baseline.summary.mean_score,
+                baseline
+                    .label
+                    .as_deref()
+                    .unwrap_or(&baseline.run_id.to_string())
+            );
+        }
+        let _ = writeln!(out);
+        let _ = writeln!(out);
+
+        let baseline_scores: HashMap<&str, &CaseResult> = baseline
+            .map(|b| b.cases.iter().map(|c| (c.id.as_str(), c)).collect())
+            .unwrap_or_default();
+
+        let _ = writeln!(
+            out,
+            "| Case | Score | Δ | Datasets | Result | Trajectory | Assertions | Status |"
+        );
+        let _ = writeln!(out, "|---|---|---|---|---|---|---|---|");
+        for case in &self.cases {
+            let delta = match baseline_scores.get(case.id.as_str()) {
+                Some(previous) => format!("{:+.2}", case.score - previous.score),
+                None if baseline.is_some() => "new".to_string(),
+                None => String::new(),
+            };
+            let status = match (&case.error, case.passed) {
+                (Some(_), _) => "error",
+                (None, true) => "pass",
+                (None, false) => "fail",
+            };
+            let _ = writeln!(
+                out,
+                "| {} | {:.2} | {} | {} | {} | {} | {} | {} |",
+                case.id,
+                case.score,
+                delta,
+                format_score(case.scores.datasets),
+                format_score(case.scores.result),
+                format_score(case.scores.trajectory),
+                format_score(case.scores.assertions),
+                status
+            );
+        }
+
+        let regressions: Vec<&CaseResult> = self
+            .cases
+            .iter()
+            .filter(|case| {
+                baseline_scores
+                    .get(case.id.as_str())
+                    .is_some_and(|previous| previous.passed && !case.passed)
+            })
+            .collect();
+        if !regressions.is_empty() {
+            let _ = writeln!(out);
+            let _ = writeln!(out, "## Regressions");
+            let _ = writeln!(out);
+            for case in regressions {
+                let _ = writeln!(out, "- `{}`: {}", case.id, case.question);
+            }
+        }
+
+        let failures: Vec<&CaseResult> = self.cases.iter().filter(|c| !c.passed).collect();
+        if !failures.is_empty() {
+            let _ = writeln!(out);
+            let _ = writeln!(out, "## Failures");
+            for case in failures {
+                let _ = writeln!(out);
+                let _ = writeln!(out, "### {}", case.id);
+                let _ = writeln!(out);
+                let _ = writeln!(out, "{}", case.question);
+                let _ = writeln!(out);
+                if let Some(error) = &case.error {
+                    let _ = writeln!(out, "- Error: {}", error);
+                }
+                let _ = writeln!(out, "- Tools: {}", case.tools.join(" → "));
+                let _ = writeln!(out, "- Datasets: {}", case.datasets.join(", "));
+                for assertion in case.assertions.iter().filter(|a| !a.passed) {
+                    let _ = writeln!(out, "- Failed assertion: {}", assertion.assertion);
+                }
+                if let Some(sql) = &case.sql {
+                    let _ = writeln!(out);
+                    let _ = writeln!(out, "```sql\n{}\n```", sql.trim());
+                }
+            }
+        }
+
+        out
+    }
+}
+
+fn format_score(score: Option<f64>) -> String {
+    score
+        .map(|s| format!("{:.2}", s))
+        .unwrap_or_else(|| "–".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn case(id: &str, score: f64, passed: bool) -> CaseResult {
+        CaseResult {
+            id: id.to_string(),
+            question: format!("question {}", id),
+            score,
+            passed,
+            scores: CaseScores {
+                result: Some(score),
+                ..Default::default()
+            },
+            tools: vec!["search_data_catalog".to_string()],
+            datasets: vec![],
+            sql: None,
+            answer: None,
+            assertions: vec![],
+            error: None,
+            duration_ms: 10,
+        }
+    }
+
+    #[test]
+    fn test_markdown_compares_against_baseline() {
+        let baseline = EvalReport::new(
+            "orders",
+            Some("v1".to_string()),
+            0.8,
+            Utc::now(),
+            vec![case("revenue", 1.0, true), case("refunds", 0.5, false)],
+        );
+        let current = EvalReport::new(
+            "orders",
+            Some("v2".to_string()),
+            0.8,
+            Utc::now(),
+            vec![
+                case("revenue", 0.5, false),
+                case("refunds", 1.0, true),
+                case("churn", 1.0, true),
+            ],
+        );
+
+        assert_eq!(current.summary.passed, 2);
+
+        let markdown = current.to_markdown(Some(&baseline));
+        assert!(markdown.contains("| revenue | 0.50 | -0.50 |"));
+        assert!(markdown.contains("| churn | 1.00 | new |"));
+        assert!(markdown.contains("## Regressions\n\n- `revenue`"));
+        assert!(markdown.contains("vs `v1`"));
+    }
+}
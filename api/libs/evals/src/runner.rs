@@ -0,0 +1,396 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use agents::{AgentThread, BusterMultiAgent};
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use litellm::{AgentMessage, MessageProgress};
+use serde_json::Value;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::report::{CaseResult, CaseScores, EvalReport};
+use crate::scoring::{
+    check_assertion, dataset_recall, result_sets_match, trajectory_score, AssertionResult,
+    ResultSet,
+};
+use crate::suite::{EvalCase, EvalSuite};
+use crate::warehouse::FixtureWarehouse;
+
+/// What the agent did while answering a question
+#[derive(Debug, Default)]
+pub struct Transcript {
+    pub tools: Vec<String>,
+    pub datasets: Vec<String>,
+    pub sql: Option<String>,
+    pub answer: Option<String>,
+}
+
+impl Transcript {
+    /// Reads the completed tool calls and results out of an agent's messages
+    pub fn from_messages(messages: &[AgentMessage]) -> Self {
+        let mut transcript = Self::default();
+
+        for message in messages {
+            match message {
+                AgentMessage::Assistant {
+                    tool_calls: Some(tool_calls),
+                    progress: MessageProgress::Complete,
+                    ..
+                } => {
+                    for call in tool_calls {
+                        transcript.tools.push(call.function.name.clone());
+                        let arguments: Value =
+                            serde_json::from_str(&call.function.arguments).unwrap_or_default();
+
+                        match call.function.name.as_str() {
+                            "create_metrics" | "update_metrics" => {
+                                if let Some(sql) = last_metric_sql(&arguments) {
+                                    transcript.sql = Some(sql);
+                                }
+                            }
+                            "done" => {
+                                if let Some(response) =
+                                    arguments.get("final_response").and_then(Value::as_str)
+                                {
+                                    transcript.answer = Some(response.to_string());
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                AgentMessage::Assistant {
+                    content: Some(content),
+                    tool_calls: None,
+                    progress: MessageProgress::Complete,
+                    ..
+                } if transcript.answer.is_none() => {
+                    transcript.answer = Some(content.clone());
+                }
+                AgentMessage::Tool {
+                    content,
+                    name: Some(name),
+                    progress: MessageProgress::Complete,
+                    ..
+                } if name == "search_data_catalog" => {
+                    let output: Value = serde_json::from_str(content).unwrap_or_default();
+                    let names = output
+                        .get("results")
+                        .and_then(Value::as_array)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|result| result.get("name").and_then(Value::as_str));
+                    for name in names {
+                        if !transcript.datasets.iter().any(|d| d == name) {
+                            transcript.datasets.push(name.to_string());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        transcript
+    }
+}
+
+// The SQL of the last metric file in a create/update metrics call
+fn last_metric_sql(arguments: &Value) -> Option<String> {
+    arguments
+        .get("files")?
+        .as_array()?
+        .iter()
+        .rev()
+        .filter_map(|file| file.get("yml_content").and_then(Value::as_str))
+        .filter_map(|yml| serde_yaml::from_str::<serde_yaml::Value>(yml).ok())
+        .find_map(|yml| {
+            yml.get("sql")
+                .and_then(|sql| sql.as_str())
+                .map(str::to_string)
+        })
+}
+
+/// Runs a suite's cases through `BusterMultiAgent` and scores them
+pub struct EvalRunner {
+    suite: EvalSuite,
+    warehouse: Option<FixtureWarehouse>,
+}
+
+impl EvalRunner {
+    pub async fn new(suite: EvalSuite) -> Result<Self> {
+        let warehouse = match &suite.warehouse_url {
+            Some(url) => Some(FixtureWarehouse::connect(url).await?),
+            None => None,
+        };
+
+        Ok(Self { suite, warehouse })
+    }
+
+    /// Runs every case, or only those in `case_ids` when it isn't empty
+    pub async fn run(&self, label: Option<String>, case_ids: &[String]) -> Result<EvalReport> {
+        let started_at = Utc::now();
+        let cases: Vec<&EvalCase> = self
+            .suite
+            .cases
+            .iter()
+            .filter(|case| case_ids.is_empty() || case_ids.contains(&case.id))
+            .collect();
+        if cases.is_empty() {
+            return Err(anyhow!("No eval cases match {}", case_ids.join(", ")));
+        }
+
+        let mut results = Vec::with_capacity(cases.len());
+        for case in cases {
+            info!(case = %case.id, "Running eval case");
+            let result = self.run_case(case).await;
+            info!(case = %case.id, score = result.score, passed = result.passed, "Finished eval case");
+            results.push(result);
+        }
+
+        Ok(EvalReport::new(
+            &self.suite.name,
+            label,
+            self.suite.pass_threshold,
+            started_at,
+            results,
+        ))
+    }
+
+    async fn run_case(&self, case: &EvalCase) -> CaseResult {
+        let started = Instant::now();
+        let timeout = Duration::from_secs(self.suite.timeout_secs);
+
+        let outcome = match tokio::time::timeout(timeout, self.run_agent(&case.question)).await {
+            Ok(Ok(messages)) => {
+                let transcript = Transcript::from_messages(&messages);
+                self.score(case, &transcript)
+                    .await
+                    .map(|(scores, assertions)| (transcript, scores, assertions))
+            }
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(anyhow!(
+                "Timed out after {} seconds",
+                self.suite.timeout_secs
+            )),
+        };
+
+        let duration_ms = started.elapsed().as_millis() as i64;
+        match outcome {
+            Ok((transcript, scores, assertions)) => {
+                let score = scores.overall();
+                CaseResult {
+                    id: case.id.clone(),
+                    question: case.question.clone(),
+                    score,
+                    passed: score >= self.suite.pass_threshold,
+                    scores,
+                    tools: transcript.tools,
+                    datasets: transcript.datasets,
+                    sql: transcript.sql,
+                    answer: transcript.answer,
+                    assertions,
+                    error: None,
+                    duration_ms,
+                }
+            }
+            Err(e) => {
+                warn!(case = %case.id, error = %e, "Eval case failed");
+                CaseResult {
+                    id: case.id.clone(),
+                    question: case.question.clone(),
+                    score: 0.0,
+                    passed: false,
+                    scores: CaseScores::default(),
+                    tools: vec![],
+                    datasets: vec![],
+                    sql: None,
+                    answer: None,
+                    assertions: vec![],
+                    error: Some(e.to_string()),
+                    duration_ms,
+                }
+            }
+        }
+    }
+
+    /// Asks the agent a question in a fresh chat and collects its messages
+    async fn run_agent(&self, question: &str) -> Result<Vec<AgentMessage>> {
+        let user_id = self.suite.user_id;
+        let session_id = Uuid::new_v4();
+
+        let agent = Arc::new(BusterMultiAgent::new(user_id, session_id, false).await?);
+        let mut thread = AgentThread::new(
+            Some(session_id),
+            user_id,
+            vec![AgentMessage::user(question.to_string())],
+        );
+
+        let mut rx = agent.run(&mut thread).await?;
+        let mut messages = Vec::new();
+        let result = loop {
+            match rx.recv().await {
+                Ok(Ok(AgentMessage::Done)) | Err(RecvError::Closed) => break Ok(()),
+                Ok(Ok(message)) => messages.push(message),
+                Ok(Err(e)) => break Err(anyhow!("Agent failed: {}", e)),
+                Err(RecvError::Lagged(n)) => {
+                    warn!("Eval runner lagged behind the agent by {} messages", n);
+                }
+            }
+        };
+
+        if let Err(e) = agent.shutdown().await {
+            warn!("Failed to shut down eval agent: {}", e);
+        }
+        result.map(|_| messages)
+    }
+
+    async fn score(
+        &self,
+        case: &EvalCase,
+        transcript: &Transcript,
+    ) -> Result<(CaseScores, Vec<AssertionResult>)> {
+        let mut scores = CaseScores::default();
+
+        if !case.expected_datasets.is_empty() {
+            scores.datasets = Some(dataset_recall(
+                &case.expected_datasets,
+                &transcript.datasets,
+            ));
+        }
+        if !case.expected_tools.is_empty() || !case.forbidden_tools.is_empty() {
+            scores.trajectory = Some(trajectory_score(
+                &case.expected_tools,
+                &case.forbidden_tools,
+                &transcript.tools,
+            ));
+        }
+
+        // The agent's metric is run on the fixture warehouse; SQL that fails
+        // there just doesn't match
+        let actual = match (&self.warehouse, &transcript.sql) {
+            (Some(warehouse), Some(sql)) => match warehouse.query(sql).await {
+                Ok(result) => Some(result),
+                Err(e) => {
+                    warn!(case = %case.id, error = %e, "Agent SQL failed on the fixture warehouse");
+                    None
+                }
+            },
+            _ => None,
+        };
+
+        if case.expects_result() {
+            let expected = match (&case.expected_rows, &case.expected_sql, &self.warehouse) {
+                (Some(rows), _, _) => rows.clone(),
+                (None, Some(sql), Some(warehouse)) => warehouse.query(sql).await?.rows,
+                _ => return Err(anyhow!("Case '{}' needs a fixture warehouse", case.id)),
+            };
+            let matched = actual.as_ref().is_some_and(|actual: &ResultSet| {
+                result_sets_match(&expected, &actual.rows, case.ordered)
+            });
+            scores.result = Some(if matched { 1.0 } else { 0.0 });
+        }
+
+        let assertions: Vec<AssertionResult> = case
+            .assertions
+            .iter()
+            .map(|assertion| {
+                check_assertion(
+                    assertion,
+                    actual.as_ref(),
+                    transcript.sql.as_deref(),
+                    transcript.answer.as_deref(),
+                )
+            })
+            .collect();
+        if !assertions.is_empty() {
+            let passed = assertions.iter().filter(|a| a.passed).count();
+            scores.assertions = Some(passed as f64 / assertions.len() as f64);
+        }
+
+        Ok((scores, assertions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use litellm::{FunctionCall, ToolCall};
+
+    fn tool_call(name: &str, arguments: Value) -> ToolCall {
+        ToolCall {
+            id: format!("call_{}", name),
+            call_type: "function".to_string(),
+            function: FunctionCall {
+                name: name.to_string(),
+                arguments: arguments.to_string(),
+            },
+            code_interpreter: None,
+            retrieval: None,
+        }
+    }
+
+    #[test]
+    fn test_transcript_from_messages() {
+        let messages = vec![
+            AgentMessage::assistant(
+                None,
+                None,
+                Some(vec![tool_call(
+                    "search_data_catalog",
+                    serde_json::json!({}),
+                )]),
+                MessageProgress::Complete,
+                None,
+                None,
+            ),
+            AgentMessage::tool(
+                None,
+                serde_json::json!({"results": [{"id": Uuid::new_v4(), "name": "orders"}]})
+                    .to_string(),
+                "call_search_data_catalog".to_string(),
+                Some("search_data_catalog".to_string()),
+                MessageProgress::Complete,
+            ),
+            // Partial calls streamed before completion are ignored
+            AgentMessage::assistant(
+                None,
+                None,
+                Some(vec![tool_call("create_metrics", serde_json::json!({}))]),
+                MessageProgress::InProgress,
+                None,
+                None,
+            ),
+            AgentMessage::assistant(
+                None,
+                None,
+                Some(vec![
+                    tool_call(
+                        "create_metrics",
+                        serde_json::json!({"files": [{"name": "Revenue", "yml_content": "name: Revenue\nsql: select sum(amount) from orders\n"}]}),
+                    ),
+                    tool_call(
+                        "done",
+                        serde_json::json!({"final_response": "Revenue was $10"}),
+                    ),
+                ]),
+                MessageProgress::Complete,
+                None,
+                None,
+            ),
+        ];
+
+        let transcript = Transcript::from_messages(&messages);
+        assert_eq!(
+            transcript.tools,
+            vec!["search_data_catalog", "create_metrics", "done"]
+        );
+        assert_eq!(transcript.datasets, vec!["orders"]);
+        assert_eq!(
+            transcript.sql.as_deref(),
+            Some("select sum(amount) from orders")
+        );
+        assert_eq!(transcript.answer.as_deref(), Some("Revenue was $10"));
+    }
+}
@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::suite::Assertion;
+
+/// Rows returned by a query, with values in column order
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResultSet {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+/// Outcome of a single assertion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssertionResult {
+    pub assertion: String,
+    pub passed: bool,
+}
+
+// Floats are compared at this many decimal places so that `avg()` and
+// `sum()` over numerics match regardless of the type the warehouse returns
+const FLOAT_PRECISION: i32 = 4;
+
+fn normalize_value(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => normalize_number(n.as_f64().unwrap_or_default()),
+        Value::String(s) => match s.trim().parse::<f64>() {
+            // Numerics often come back as strings
+            Ok(n) => normalize_number(n),
+            Err(_) => s.trim().to_string(),
+        },
+        other => other.to_string(),
+    }
+}
+
+fn normalize_number(n: f64) -> String {
+    let factor = 10f64.powi(FLOAT_PRECISION);
+    let rounded = (n * factor).round() / factor;
+    // Avoid "-0"
+    if rounded == 0.0 {
+        "0".to_string()
+    } else {
+        rounded.to_string()
+    }
+}
+
+fn normalize_rows(rows: &[Vec<Value>], sort_columns: bool) -> Vec<Vec<String>> {
+    rows.iter()
+        .map(|row| {
+            let mut values: Vec<String> = row.iter().map(normalize_value).collect();
+            if sort_columns {
+                values.sort();
+            }
+            values
+        })
+        .collect()
+}
+
+/// Whether two result sets hold the same data
+///
+/// Column names are ignored since the agent picks its own aliases. Rows are
+/// compared as a multiset unless `ordered`, and a result with the same
+/// columns in a different order still matches.
+pub fn result_sets_match(expected: &[Vec<Value>], actual: &[Vec<Value>], ordered: bool) -> bool {
+    if expected.len() != actual.len() {
+        return false;
+    }
+
+    [false, true].into_iter().any(|sort_columns| {
+        let mut expected = normalize_rows(expected, sort_columns);
+        let mut actual = normalize_rows(actual, sort_columns);
+        if !ordered {
+            expected.sort();
+            actual.sort();
+        }
+        expected == actual
+    })
+}
+
+/// How much of the expected tool sequence the agent followed, in order
+///
+/// Scored as the longest common subsequence over the expected length, so
+/// extra calls in between don't count against the agent. Any forbidden tool
+/// scores zero.
+pub fn trajectory_score(expected: &[String], forbidden: &[String], actual: &[String]) -> f64 {
+    if actual.iter().any(|tool| forbidden.contains(tool)) {
+        return 0.0;
+    }
+    if expected.is_empty() {
+        return 1.0;
+    }
+
+    let mut lengths = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for (i, expected_tool) in expected.iter().enumerate() {
+        for (j, actual_tool) in actual.iter().enumerate() {
+            lengths[i + 1][j + 1] = if expected_tool == actual_tool {
+                lengths[i][j] + 1
+            } else {
+                lengths[i][j + 1].max(lengths[i + 1][j])
+            };
+        }
+    }
+
+    lengths[expected.len()][actual.len()] as f64 / expected.len() as f64
+}
+
+/// Share of the expected datasets the agent found, ignoring case
+pub fn dataset_recall(expected: &[String], found: &[String]) -> f64 {
+    if expected.is_empty() {
+        return 1.0;
+    }
+
+    let hits = expected
+        .iter()
+        .filter(|name| found.iter().any(|f| f.eq_ignore_ascii_case(name)))
+        .count();
+    hits as f64 / expected.len() as f64
+}
+
+pub fn check_assertion(
+    assertion: &Assertion,
+    result: Option<&ResultSet>,
+    sql: Option<&str>,
+    answer: Option<&str>,
+) -> AssertionResult {
+    let contains_all = |haystack: Option<&str>, needles: &[String]| {
+        haystack
+            .map(|h| h.to_lowercase())
+            .is_some_and(|h| needles.iter().all(|n| h.contains(&n.to_lowercase())))
+    };
+
+    let (description, passed) = match assertion {
+        Assertion::RowCount(count) => (
+            format!("row_count = {}", count),
+            result.is_some_and(|r| r.rows.len() == *count),
+        ),
+        Assertion::Columns(columns) => (
+            format!("columns include {}", columns.join(", ")),
+            result.is_some_and(|r| {
+                columns
+                    .iter()
+                    .all(|c| r.columns.iter().any(|rc| rc.eq_ignore_ascii_case(c)))
+            }),
+        ),
+        Assertion::SqlContains(needles) => (
+            format!("sql contains {}", needles.join(", ")),
+            contains_all(sql, needles),
+        ),
+        Assertion::AnswerContains(needles) => (
+            format!("answer contains {}", needles.join(", ")),
+            contains_all(answer, needles),
+        ),
+    };
+
+    AssertionResult {
+        assertion: description,
+        passed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rows(value: Value) -> Vec<Vec<Value>> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_result_sets_match() {
+        let expected = rows(json!([["widget", 10], ["gadget", 7.5]]));
+
+        assert!(result_sets_match(
+            &expected,
+            &rows(json!([["gadget", "7.50000"], ["widget", 10.0]])),
+            false
+        ));
+        assert!(!result_sets_match(
+            &expected,
+            &rows(json!([["gadget", 7.5], ["widget", 10]])),
+            true
+        ));
+        assert!(result_sets_match(
+            &expected,
+            &rows(json!([[10, "widget"], [7.5, "gadget"]])),
+            false
+        ));
+        assert!(!result_sets_match(
+            &expected,
+            &rows(json!([["widget", 10]])),
+            false
+        ));
+        assert!(!result_sets_match(
+            &expected,
+            &rows(json!([["widget", 11], ["gadget", 7.5]])),
+            false
+        ));
+    }
+
+    #[test]
+    fn test_trajectory_and_datasets() {
+        let tools = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+        let expected = tools(&["search_data_catalog", "create_metrics", "done"]);
+        let actual = tools(&[
+            "search_data_catalog",
+            "create_plan_straightforward",
+            "create_metrics",
+            "done",
+        ]);
+        assert_eq!(trajectory_score(&expected, &[], &actual), 1.0);
+        assert!(
+            (trajectory_score(&expected, &[], &tools(&["create_metrics", "done"])) - 2.0 / 3.0)
+                .abs()
+                < 1e-9
+        );
+        assert_eq!(
+            trajectory_score(&expected, &tools(&["create_plan_straightforward"]), &actual),
+            0.0
+        );
+
+        assert_eq!(
+            dataset_recall(&tools(&["Orders", "products"]), &tools(&["orders"])),
+            0.5
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use anyhow::{Context, Result};
+use braintrust::{BraintrustClient, Span};
+use chrono::Utc;
+use database::{models::SqlEvaluation, pool::get_pg_pool, schema::sql_evaluations};
+use diesel::insert_into;
+use diesel_async::RunQueryDsl;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::report::EvalReport;
+
+/// Stores one `sql_evaluations` row per case
+pub async fn write_sql_evaluations(report: &EvalReport) -> Result<usize> {
+    let now = Utc::now();
+    let rows: Vec<SqlEvaluation> = report
+        .cases
+        .iter()
+        .map(|case| SqlEvaluation {
+            id: Uuid::new_v4(),
+            evaluation_obj: json!({
+                "run_id": report.run_id,
+                "suite": report.suite,
+                "label": report.label,
+                "case": case,
+            }),
+            evaluation_summary: format!(
+                "[{}/{}] {}: {}",
+                report.suite,
+                case.id,
+                if case.passed { "pass" } else { "fail" },
+                case.question
+            ),
+            score: format!("{:.3}", case.score),
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        })
+        .collect();
+
+    let mut conn = get_pg_pool().get().await?;
+    insert_into(sql_evaluations::table)
+        .values(&rows)
+        .execute(&mut conn)
+        .await
+        .context("Failed to write sql_evaluations")
+}
+
+/// Logs the run to a Braintrust project as one trace with a span per case
+pub async fn log_to_braintrust(report: &EvalReport, project_id: &str) -> Result<()> {
+    let client = BraintrustClient::new(None, project_id)?;
+
+    let mut root = Span::new(&format!("eval:{}", report.suite), "eval", "", None)
+        .with_input(json!({ "suite": report.suite, "label": report.label }))
+        .with_output(serde_json::to_value(&report.summary)?)
+        .with_metadata("run_id", report.run_id);
+    root.root_span_id = root.span_id.clone();
+
+    for case in &report.cases {
+        let span = Span::new(&case.id, "eval_case", &root.span_id, Some(&root.span_id))
+            .with_input(json!({ "question": case.question }))
+            .with_output(json!({
+                "answer": case.answer,
+                "sql": case.sql,
+                "tools": case.tools,
+                "datasets": case.datasets,
+                "error": case.error,
+            }))
+            .with_json_metadata("scores", serde_json::to_value(&case.scores)?)
+            .with_metadata("score", case.score)
+            .with_metadata("passed", case.passed);
+        client.log_span_sync(span).await?;
+    }
+
+    client.log_span_sync(root).await
+}
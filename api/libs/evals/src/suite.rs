@@ -0,0 +1,218 @@
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+fn default_pass_threshold() -> f64 {
+    0.8
+}
+
+fn default_timeout_secs() -> u64 {
+    300
+}
+
+/// A set of golden questions run against one fixture warehouse
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvalSuite {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// User the agent runs as. Their organization's datasets must point at
+    /// the fixture warehouse.
+    pub user_id: Uuid,
+    /// Connection string of the fixture warehouse, used to run the expected
+    /// and generated SQL
+    #[serde(default)]
+    pub warehouse_url: Option<String>,
+    /// Minimum case score that counts as a pass
+    #[serde(default = "default_pass_threshold")]
+    pub pass_threshold: f64,
+    /// How long a single case may run before it's failed
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    pub cases: Vec<EvalCase>,
+}
+
+/// A question and what a correct answer looks like
+///
+/// Every expectation is optional; a case is scored on the ones it sets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvalCase {
+    pub id: String,
+    pub question: String,
+    /// Dataset names the agent should find through the data catalog
+    #[serde(default)]
+    pub expected_datasets: Vec<String>,
+    /// SQL whose result the agent's final metric must reproduce
+    #[serde(default)]
+    pub expected_sql: Option<String>,
+    /// Literal rows the agent's final metric must return, as an alternative
+    /// to `expected_sql`
+    #[serde(default)]
+    pub expected_rows: Option<Vec<Vec<Value>>>,
+    /// Whether row order matters when comparing results
+    #[serde(default)]
+    pub ordered: bool,
+    /// Tools the agent should call, in this order; other calls may be
+    /// interleaved
+    #[serde(default)]
+    pub expected_tools: Vec<String>,
+    /// Tools the agent must not call
+    #[serde(default)]
+    pub forbidden_tools: Vec<String>,
+    #[serde(default, with = "serde_yaml::with::singleton_map_recursive")]
+    pub assertions: Vec<Assertion>,
+}
+
+impl EvalCase {
+    pub fn expects_result(&self) -> bool {
+        self.expected_sql.is_some() || self.expected_rows.is_some()
+    }
+}
+
+/// A check on the agent's output, written as a single-key map in YAML, e.g.
+/// `- row_count: 12`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Assertion {
+    /// The final metric returns exactly this many rows
+    RowCount(usize),
+    /// The final metric returns these columns, in any order
+    Columns(Vec<String>),
+    /// The final metric's SQL contains each of these strings, ignoring case
+    SqlContains(Vec<String>),
+    /// The agent's final response contains each of these strings, ignoring case
+    AnswerContains(Vec<String>),
+}
+
+impl EvalSuite {
+    /// Parses a suite, replacing `${VAR}` anywhere in it with the value of
+    /// the environment variable `VAR`
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        let yaml = expand_env(yaml)?;
+        let suite: EvalSuite = serde_yaml::from_str(&yaml).context("Failed to parse eval suite")?;
+        suite.validate()?;
+        Ok(suite)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let yaml = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read eval suite {}", path.display()))?;
+        Self::from_yaml(&yaml)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.cases.is_empty() {
+            return Err(anyhow!("Eval suite '{}' has no cases", self.name));
+        }
+
+        let mut ids = std::collections::HashSet::new();
+        for case in &self.cases {
+            if !ids.insert(case.id.as_str()) {
+                return Err(anyhow!("Duplicate eval case id '{}'", case.id));
+            }
+            if case.expected_sql.is_some() && case.expected_rows.is_some() {
+                return Err(anyhow!(
+                    "Eval case '{}' sets both expected_sql and expected_rows",
+                    case.id
+                ));
+            }
+            if case.expects_result() && self.warehouse_url.is_none() {
+                return Err(anyhow!(
+                    "Eval case '{}' compares results but the suite has no warehouse_url",
+                    case.id
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn expand_env(value: &str) -> Result<String> {
+    let mut expanded = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find("${") {
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| anyhow!("Unterminated variable in '{}'", value))?;
+        let name = &rest[start + 2..start + end];
+        let var =
+            std::env::var(name).map_err(|_| anyhow!("Environment variable {} is not set", name))?;
+
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&var);
+        rest = &rest[start + end + 1..];
+    }
+    expanded.push_str(rest);
+
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_suite() {
+        std::env::set_var("EVALS_TEST_WAREHOUSE", "postgres://localhost/fixtures");
+        let suite = EvalSuite::from_yaml(
+            r#"
+name: orders
+user_id: 8e4f1a52-7d2c-4c55-9b0a-1f9b3f0c2d11
+warehouse_url: ${EVALS_TEST_WAREHOUSE}
+cases:
+  - id: revenue
+    question: What was total revenue?
+    expected_datasets: [orders]
+    expected_sql: select sum(amount) from orders
+    expected_tools: [search_data_catalog, create_metrics]
+    assertions:
+      - row_count: 1
+      - sql_contains: [sum(]
+  - id: top-products
+    question: Top 3 products
+    expected_rows: [["widget", 10], ["gadget", 7]]
+    ordered: true
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            suite.warehouse_url.as_deref(),
+            Some("postgres://localhost/fixtures")
+        );
+        assert_eq!(suite.pass_threshold, 0.8);
+        assert_eq!(suite.cases.len(), 2);
+        assert!(matches!(
+            suite.cases[0].assertions[0],
+            Assertion::RowCount(1)
+        ));
+        assert!(suite.cases[1].expects_result());
+
+        let duplicate = EvalSuite::from_yaml(
+            r#"
+name: dup
+user_id: 8e4f1a52-7d2c-4c55-9b0a-1f9b3f0c2d11
+cases:
+  - { id: a, question: one }
+  - { id: a, question: two }
+"#,
+        );
+        assert!(duplicate.is_err());
+    }
+
+    #[test]
+    fn test_example_suite_parses() {
+        std::env::set_var("EVAL_USER_ID", "8e4f1a52-7d2c-4c55-9b0a-1f9b3f0c2d11");
+        std::env::set_var("EVAL_WAREHOUSE_URL", "postgres://localhost/fixtures");
+
+        let suite =
+            EvalSuite::load(concat!(env!("CARGO_MANIFEST_DIR"), "/suites/orders.yml")).unwrap();
+        assert_eq!(suite.cases.len(), 3);
+    }
+}
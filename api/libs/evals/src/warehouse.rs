@@ -0,0 +1,54 @@
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+use sqlx::postgres::{PgPool, PgPoolOptions};
+
+use crate::scoring::ResultSet;
+
+/// The Postgres database holding the fixture data the suite's datasets
+/// point at
+///
+/// Expected and generated SQL both run here, so results are compared on
+/// the same data the agent queried.
+pub struct FixtureWarehouse {
+    pool: PgPool,
+}
+
+impl FixtureWarehouse {
+    pub async fn connect(url: &str) -> Result<Self> {
+        let pool = PgPoolOptions::new()
+            .max_connections(4)
+            .connect(url)
+            .await
+            .context("Failed to connect to the fixture warehouse")?;
+
+        Ok(Self { pool })
+    }
+
+    /// Runs a query, keeping its column order
+    pub async fn query(&self, sql: &str) -> Result<ResultSet> {
+        let sql = sql.trim().trim_end_matches(';');
+        // Serializing each row to json sidesteps decoding every column type
+        let wrapped = format!(
+            "SELECT row_to_json(eval_result)::text FROM ({}) AS eval_result",
+            sql
+        );
+
+        let rows: Vec<String> = sqlx::query_scalar(&wrapped)
+            .fetch_all(&self.pool)
+            .await
+            .with_context(|| format!("Failed to run query on the fixture warehouse: {}", sql))?;
+
+        let mut result = ResultSet::default();
+        for row in rows {
+            let row: Map<String, Value> = serde_json::from_str(&row)?;
+            if result.columns.is_empty() {
+                result.columns = row.keys().cloned().collect();
+            }
+            result
+                .rows
+                .push(row.into_iter().map(|(_, value)| value).collect());
+        }
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,33 @@
+-- Fixture warehouse for suites/orders.yml. Load into the Postgres database
+-- that EVAL_WAREHOUSE_URL points at, and deploy datasets for these tables in
+-- the eval user's organization.
+create schema if not exists eval;
+
+drop table if exists eval.orders;
+drop table if exists eval.products;
+
+create table eval.products (
+    id integer primary key,
+    name text not null,
+    category text not null
+);
+
+create table eval.orders (
+    id integer primary key,
+    product_id integer not null references eval.products (id),
+    quantity integer not null,
+    amount numeric(10, 2) not null,
+    ordered_at date not null
+);
+
+insert into eval.products (id, name, category) values
+    (1, 'Widget', 'Hardware'),
+    (2, 'Gadget', 'Hardware'),
+    (3, 'Manual', 'Books');
+
+insert into eval.orders (id, product_id, quantity, amount, ordered_at) values
+    (1, 1, 2, 40.00, '2025-01-05'),
+    (2, 2, 1, 25.50, '2025-01-17'),
+    (3, 1, 1, 20.00, '2025-02-02'),
+    (4, 3, 3, 30.00, '2025-02-14'),
+    (5, 2, 4, 102.00, '2025-03-09');
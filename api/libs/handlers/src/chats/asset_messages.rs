@@ -237,6 +237,7 @@ pub async fn generate_asset_messages(
         title: asset_details.name.clone(),
         raw_llm_messages, // Add the agent context messages
         feedback: None,
+        parent_message_id: None, // Will be set by caller
     };
     
     Ok(vec![message])
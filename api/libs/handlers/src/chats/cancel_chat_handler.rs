@@ -0,0 +1,230 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, LazyLock,
+};
+use std::time::Duration;
+
+use agents::BusterMultiAgent;
+use anyhow::{anyhow, Result};
+use dashmap::DashMap;
+use database::{
+    enums::AssetPermissionRole, helpers::chats::fetch_chat_with_permission, pool::get_pg_pool,
+    pool::get_redis_pool, schema::messages,
+};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use sharing::check_permission_access;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// How often a running generation checks Redis for a stop requested on
+/// another server
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long a stop request waits in Redis for the server running the
+/// generation to pick it up
+const CANCEL_REQUEST_TTL_SECS: u64 = 300;
+
+/// Generations running on this server, keyed by message id
+static RUNNING_GENERATIONS: LazyLock<DashMap<Uuid, RunningGeneration>> =
+    LazyLock::new(DashMap::new);
+
+struct RunningGeneration {
+    chat_id: Uuid,
+    agent: Arc<BusterMultiAgent>,
+    cancelled: Arc<AtomicBool>,
+}
+
+fn cancel_request_key(message_id: &Uuid) -> String {
+    format!("chat_cancel:{}", message_id)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CancelChatStatus {
+    /// The generation was running here and has been signalled
+    Stopping,
+    /// The generation isn't running on this server; the server running it
+    /// will stop it within a second
+    Requested,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CancelChatResponse {
+    pub chat_id: Uuid,
+    pub message_id: Uuid,
+    pub status: CancelChatStatus,
+}
+
+/// Tracks a generation while `post_chat_handler` streams it
+///
+/// While the handle is alive the generation can be stopped with
+/// [`cancel_chat_handler`], from this server or another one. Dropping it
+/// unregisters the generation.
+pub struct GenerationHandle {
+    message_id: Uuid,
+    cancelled: Arc<AtomicBool>,
+    watcher: JoinHandle<()>,
+}
+
+impl GenerationHandle {
+    pub fn register(chat_id: Uuid, message_id: Uuid, agent: Arc<BusterMultiAgent>) -> Self {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        RUNNING_GENERATIONS.insert(
+            message_id,
+            RunningGeneration {
+                chat_id,
+                agent,
+                cancelled: cancelled.clone(),
+            },
+        );
+
+        let watcher = tokio::spawn(async move {
+            let key = cancel_request_key(&message_id);
+            loop {
+                tokio::time::sleep(CANCEL_POLL_INTERVAL).await;
+
+                let mut conn = match get_redis_pool().get().await {
+                    Ok(conn) => conn,
+                    Err(e) => {
+                        tracing::debug!("Error getting redis connection for cancel watcher: {}", e);
+                        continue;
+                    }
+                };
+                match conn.del::<_, i64>(&key).await {
+                    Ok(removed) if removed > 0 => {
+                        tracing::info!(
+                            "Stop requested for message {} from another server",
+                            message_id
+                        );
+                        stop_local_generation(&message_id).await;
+                        return;
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::debug!("Error checking stop request: {}", e),
+                }
+            }
+        });
+
+        Self {
+            message_id,
+            cancelled,
+            watcher,
+        }
+    }
+
+    /// Whether the generation was stopped before the agent finished
+    pub fn is_cancelled(&self) -> bool {
+        AtomicBool::load(&self.cancelled, Ordering::SeqCst)
+    }
+}
+
+impl Drop for GenerationHandle {
+    fn drop(&mut self) {
+        self.watcher.abort();
+        RUNNING_GENERATIONS.remove(&self.message_id);
+    }
+}
+
+/// Signals the agent of a generation running on this server
+///
+/// Returns false if the message isn't generating here.
+async fn stop_local_generation(message_id: &Uuid) -> bool {
+    let agent = match RUNNING_GENERATIONS.get(message_id) {
+        Some(generation) => {
+            generation.cancelled.store(true, Ordering::SeqCst);
+            generation.agent.clone()
+        }
+        None => return false,
+    };
+
+    // Errors only when the agent has already finished and nothing is listening
+    if let Err(e) = agent.shutdown().await {
+        tracing::debug!(
+            "Agent for message {} had already stopped: {}",
+            message_id,
+            e
+        );
+    }
+
+    true
+}
+
+/// Stops an in-flight chat generation
+///
+/// The agent's pending LLM stream and tool calls, including warehouse
+/// queries, are dropped. `post_chat_handler` then persists the partial
+/// reasoning and response with a "stopped" status and closes its stream.
+///
+/// Requires edit access to the chat.
+pub async fn cancel_chat_handler(
+    chat_id: &Uuid,
+    message_id: &Uuid,
+    user: &AuthenticatedUser,
+) -> Result<CancelChatResponse> {
+    let chat_with_permission = fetch_chat_with_permission(chat_id, &user.id)
+        .await?
+        .ok_or_else(|| anyhow!("Chat not found"))?;
+
+    let is_creator = chat_with_permission.chat.created_by == user.id;
+    let has_permission = check_permission_access(
+        chat_with_permission.permission,
+        &[
+            AssetPermissionRole::CanEdit,
+            AssetPermissionRole::FullAccess,
+            AssetPermissionRole::Owner,
+        ],
+        chat_with_permission.chat.organization_id,
+        &user.organizations,
+    );
+    if !is_creator && !has_permission {
+        return Err(anyhow!("You don't have permission to stop this chat"));
+    }
+
+    let running_chat_id = RUNNING_GENERATIONS
+        .get(message_id)
+        .map(|generation| generation.chat_id);
+    if let Some(running_chat_id) = running_chat_id {
+        if running_chat_id != *chat_id {
+            return Err(anyhow!("Message not found in this chat"));
+        }
+        if stop_local_generation(message_id).await {
+            return Ok(CancelChatResponse {
+                chat_id: *chat_id,
+                message_id: *message_id,
+                status: CancelChatStatus::Stopping,
+            });
+        }
+    }
+
+    // Messages are only stored once their generation ends
+    let mut conn = get_pg_pool().get().await?;
+    let finished = messages::table
+        .filter(messages::id.eq(message_id))
+        .filter(messages::chat_id.eq(chat_id))
+        .count()
+        .get_result::<i64>(&mut conn)
+        .await?
+        > 0;
+    if finished {
+        return Err(anyhow!("Message has already finished generating"));
+    }
+
+    let mut redis_conn = get_redis_pool()
+        .get()
+        .await
+        .map_err(|e| anyhow!("Error getting redis connection: {}", e))?;
+    redis_conn
+        .set_ex::<_, _, ()>(cancel_request_key(message_id), 1, CANCEL_REQUEST_TTL_SECS)
+        .await
+        .map_err(|e| anyhow!("Error requesting stop: {}", e))?;
+
+    Ok(CancelChatResponse {
+        chat_id: *chat_id,
+        message_id: *message_id,
+        status: CancelChatStatus::Requested,
+    })
+}
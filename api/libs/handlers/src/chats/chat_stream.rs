@@ -0,0 +1,324 @@
+use anyhow::{anyhow, Result};
+use database::pool::get_redis_pool;
+use middleware::AuthenticatedUser;
+use redis::{
+    streams::{StreamMaxlen, StreamRangeReply},
+    AsyncCommands,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::chats::{
+    get_chat_handler,
+    post_chat_handler::{BusterContainer, ThreadEvent},
+    ChatWithMessages,
+};
+
+/// How long a message's stream events are kept after its last event
+const CHAT_STREAM_TTL_SECS: i64 = 900;
+
+/// Upper bound on the events kept per message
+const CHAT_STREAM_MAXLEN: usize = 10_000;
+
+fn chat_stream_key(message_id: &Uuid) -> String {
+    format!("chat_stream:{}", message_id)
+}
+
+/// An event recorded to a message's stream
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChatStreamRecord {
+    Event {
+        event: ThreadEvent,
+        container: Value,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+impl ChatStreamRecord {
+    /// Whether no more events follow this one
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            ChatStreamRecord::Event {
+                event: ThreadEvent::Completed,
+                ..
+            } | ChatStreamRecord::Failed { .. }
+        )
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChatStreamEntry {
+    /// Redis stream entry id; increases with every event of the message
+    pub sequence_id: String,
+    #[serde(flatten)]
+    pub record: ChatStreamRecord,
+}
+
+/// Events a reconnecting client missed
+#[derive(Debug, Serialize, Clone)]
+pub struct ChatStreamResume {
+    pub chat_id: Uuid,
+    pub message_id: Uuid,
+    /// Events after the last acknowledged sequence id, oldest first
+    pub entries: Vec<ChatStreamEntry>,
+    /// Whether the generation has ended
+    pub completed: bool,
+    /// The stored chat once the generation has ended
+    pub chat: Option<ChatWithMessages>,
+}
+
+/// Records the events of a chat message's generation as they are streamed
+///
+/// The message is taken from the first event, which is always the
+/// `InitializeChat` event carrying the chat with its new message. Recording
+/// failures are logged rather than returned so they never interrupt the
+/// live stream.
+#[derive(Default)]
+pub struct ChatStreamRecorder {
+    message_id: Option<Uuid>,
+}
+
+impl ChatStreamRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records an event and returns its sequence id
+    pub async fn record(
+        &mut self,
+        container: &BusterContainer,
+        event: ThreadEvent,
+    ) -> Option<String> {
+        if self.message_id.is_none() {
+            self.message_id = container_message_id(container);
+        }
+        let message_id = self.message_id?;
+
+        let container = match serde_json::to_value(container) {
+            Ok(container) => container,
+            Err(e) => {
+                tracing::error!("Failed to serialize chat stream event: {}", e);
+                return None;
+            }
+        };
+
+        self.append(&message_id, ChatStreamRecord::Event { event, container })
+            .await
+    }
+
+    /// Records that the generation failed
+    pub async fn record_failure(&self, error: &str) -> Option<String> {
+        let message_id = self.message_id?;
+        self.append(
+            &message_id,
+            ChatStreamRecord::Failed {
+                error: error.to_string(),
+            },
+        )
+        .await
+    }
+
+    async fn append(&self, message_id: &Uuid, record: ChatStreamRecord) -> Option<String> {
+        match append_chat_stream_record(message_id, &record).await {
+            Ok(sequence_id) => Some(sequence_id),
+            Err(e) => {
+                tracing::error!(
+                    "Failed to record chat stream event for message {}: {}",
+                    message_id,
+                    e
+                );
+                None
+            }
+        }
+    }
+}
+
+fn container_message_id(container: &BusterContainer) -> Option<Uuid> {
+    match container {
+        BusterContainer::Chat(chat) => chat
+            .message_ids
+            .last()
+            .and_then(|id| Uuid::parse_str(id).ok()),
+        BusterContainer::ChatMessage(message) => Some(message.message_id),
+        BusterContainer::ReasoningMessage(message) => Some(message.message_id),
+        BusterContainer::GeneratingTitle(title) => Some(title.message_id),
+    }
+}
+
+async fn append_chat_stream_record(message_id: &Uuid, record: &ChatStreamRecord) -> Result<String> {
+    let key = chat_stream_key(message_id);
+    let data = serde_json::to_string(record)?;
+
+    let mut conn = get_redis_pool()
+        .get()
+        .await
+        .map_err(|e| anyhow!("Error getting redis connection: {}", e))?;
+
+    let sequence_id: String = conn
+        .xadd_maxlen(
+            &key,
+            StreamMaxlen::Approx(CHAT_STREAM_MAXLEN),
+            "*",
+            &[("data", data)],
+        )
+        .await?;
+    conn.expire::<_, ()>(&key, CHAT_STREAM_TTL_SECS).await?;
+
+    Ok(sequence_id)
+}
+
+/// Reads a message's recorded events, starting at `from` when given
+///
+/// Returns None when nothing is recorded for the message, either because it
+/// never streamed or because its retention window passed.
+async fn read_chat_stream(
+    message_id: &Uuid,
+    from: Option<&str>,
+) -> Result<Option<Vec<ChatStreamEntry>>> {
+    let key = chat_stream_key(message_id);
+
+    let mut conn = get_redis_pool()
+        .get()
+        .await
+        .map_err(|e| anyhow!("Error getting redis connection: {}", e))?;
+
+    if !conn.exists::<_, bool>(&key).await? {
+        return Ok(None);
+    }
+
+    let reply: StreamRangeReply =
+        conn.xrange(&key, from.unwrap_or("-"), "+")
+            .await
+            .map_err(|e| match from {
+                Some(from) if e.kind() == redis::ErrorKind::ResponseError => {
+                    anyhow!("Invalid sequence id: {}", from)
+                }
+                _ => anyhow!("Error reading chat stream: {}", e),
+            })?;
+
+    let entries = reply
+        .ids
+        .into_iter()
+        .filter_map(|stream_id| {
+            let data = stream_id.get::<String>("data")?;
+            match serde_json::from_str::<ChatStreamRecord>(&data) {
+                Ok(record) => Some(ChatStreamEntry {
+                    sequence_id: stream_id.id,
+                    record,
+                }),
+                Err(e) => {
+                    tracing::warn!("Skipping unreadable chat stream entry: {}", e);
+                    None
+                }
+            }
+        })
+        .collect();
+
+    Ok(Some(entries))
+}
+
+/// Returns the events recorded after `after`, or all of them without it
+///
+/// Used to follow a generation that is still running once
+/// [`resume_chat_stream_handler`] has checked access. Returns None once the
+/// stream has expired.
+pub async fn read_chat_stream_after(
+    message_id: &Uuid,
+    after: Option<&str>,
+) -> Result<Option<Vec<ChatStreamEntry>>> {
+    Ok(read_chat_stream(message_id, after).await?.map(|entries| {
+        entries
+            .into_iter()
+            .filter(|entry| Some(entry.sequence_id.as_str()) != after)
+            .collect()
+    }))
+}
+
+/// Returns the events of a chat message's generation that a client missed
+///
+/// `last_sequence_id` is the last event the client received; without it the
+/// whole stream is returned. Events are kept for a window after the last one
+/// is recorded. Once the generation has ended the stored chat is included
+/// so the client can replace whatever partial state it holds.
+///
+/// Requires view access to the chat.
+pub async fn resume_chat_stream_handler(
+    chat_id: &Uuid,
+    message_id: &Uuid,
+    last_sequence_id: Option<&str>,
+    user: &AuthenticatedUser,
+) -> Result<ChatStreamResume> {
+    let chat = get_chat_handler(chat_id, user, false).await?;
+    let is_stored = chat.messages.contains_key(&message_id.to_string());
+
+    let (entries, completed) = match read_chat_stream(message_id, last_sequence_id).await? {
+        // Read from the acknowledged event itself so a stream that ended with
+        // it still counts as completed
+        Some(entries) => {
+            let completed = entries.iter().any(|entry| entry.record.is_terminal());
+            let entries = entries
+                .into_iter()
+                .filter(|entry| Some(entry.sequence_id.as_str()) != last_sequence_id)
+                .collect();
+            (entries, completed)
+        }
+        // The retention window passed, so only the stored chat is left
+        None if is_stored => (vec![], true),
+        None => return Err(anyhow!("Message not found in this chat")),
+    };
+
+    Ok(ChatStreamResume {
+        chat_id: *chat_id,
+        message_id: *message_id,
+        entries,
+        completed,
+        chat: completed.then_some(chat),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_chat_stream_entry_round_trip() {
+        let entry = ChatStreamEntry {
+            sequence_id: "1714000000000-0".to_string(),
+            record: ChatStreamRecord::Event {
+                event: ThreadEvent::GeneratingResponseMessage,
+                container: json!({ "chat_id": Uuid::nil() }),
+            },
+        };
+
+        let value = serde_json::to_value(&entry).unwrap();
+        assert_eq!(value["kind"], "event");
+        assert_eq!(value["event"], "generating_response_message");
+
+        let parsed: ChatStreamEntry = serde_json::from_value(value).unwrap();
+        assert!(!parsed.record.is_terminal());
+    }
+
+    #[test]
+    fn test_terminal_records() {
+        assert!(ChatStreamRecord::Event {
+            event: ThreadEvent::Completed,
+            container: Value::Null,
+        }
+        .is_terminal());
+        assert!(ChatStreamRecord::Failed {
+            error: "boom".to_string(),
+        }
+        .is_terminal());
+        assert!(!ChatStreamRecord::Event {
+            event: ThreadEvent::InitializeChat,
+            container: Value::Null,
+        }
+        .is_terminal());
+    }
+}
@@ -0,0 +1,439 @@
+use std::collections::HashMap;
+use std::env;
+
+use agents::AgentMessage;
+use anyhow::{anyhow, Result};
+use database::{enums::ModelPurpose, helpers::model_configs::get_user_models};
+use litellm::{ChatCompletionRequest, LiteLLMClient, Metadata};
+use serde_json::Value;
+use tiktoken_rs::{cl100k_base_singleton, o200k_base_singleton, tokenizer::Tokenizer};
+use uuid::Uuid;
+
+/// Marks the message that stands in for the compacted turns
+pub const SUMMARY_HEADER: &str = "[Summary of the earlier conversation]";
+
+/// Context window assumed for models missing from the table
+const DEFAULT_CONTEXT_WINDOW: usize = 128_000;
+
+// Input context windows in tokens, overridable through `LLM_CONTEXT_WINDOWS`
+const DEFAULT_CONTEXT_WINDOWS: &[(&str, usize)] = &[
+    ("o4-mini", 200_000),
+    ("o3-mini", 200_000),
+    ("gpt-4o", 128_000),
+    ("gpt-4o-mini", 128_000),
+    ("gpt-4.1", 1_047_576),
+    ("gpt-4.1-mini", 1_047_576),
+    ("gemini-2.0-flash-001", 1_048_576),
+    ("claude-3-7-sonnet-latest", 200_000),
+    ("claude-3-5-haiku-latest", 200_000),
+];
+
+/// Share of the context window the reloaded history may use by default; the
+/// rest is left for the system prompt, tool definitions and the new turn
+const DEFAULT_BUDGET_RATIO: f64 = 0.5;
+
+/// Share of the budget kept verbatim as the most recent turns when the
+/// history is compacted
+const RECENT_TURNS_RATIO: f64 = 0.5;
+
+/// Tokens added per message for its role and framing
+const TOKENS_PER_MESSAGE: usize = 4;
+
+/// Longest excerpt of a message given to the LLM to summarize
+const SUMMARY_INPUT_CHARS: usize = 4_000;
+
+/// Longest excerpt of a message kept when the summary has to be built
+/// without the LLM
+const FALLBACK_EXCERPT_CHARS: usize = 500;
+
+const FILE_TOOLS: &[&str] = &[
+    "create_metrics",
+    "update_metrics",
+    "create_dashboards",
+    "update_dashboards",
+    "import_assets",
+];
+
+const COMPACTION_PROMPT: &str = r#"You are compacting the earlier part of a conversation between a user and a data analyst agent so the agent can continue it with less context.
+
+Write a concise summary of the conversation below for the agent. Keep:
+- what the user asked for and any preferences or corrections they gave
+- key findings, numbers and conclusions from the analysis
+- the datasets, tables, columns and filters that were used
+- decisions made and anything left unfinished
+
+Do not invent details. Write in plain prose and short bullet points, without a preamble.
+
+Conversation:
+{conversation_messages}"#;
+
+/// The input context window of `model`, ignoring any provider prefix
+///
+/// Windows can be set per model with `LLM_CONTEXT_WINDOWS`, a JSON object
+/// such as `{"llama3.1": 131072}`.
+pub fn context_window(model: &str) -> usize {
+    let mut windows: HashMap<String, usize> = DEFAULT_CONTEXT_WINDOWS
+        .iter()
+        .map(|(model, window)| (model.to_string(), *window))
+        .collect();
+
+    if let Ok(json) = env::var("LLM_CONTEXT_WINDOWS") {
+        match serde_json::from_str::<HashMap<String, usize>>(&json) {
+            Ok(overrides) => windows.extend(overrides),
+            Err(e) => tracing::warn!("Ignoring invalid LLM_CONTEXT_WINDOWS: {}", e),
+        }
+    }
+
+    windows
+        .get(model)
+        .or_else(|| {
+            model
+                .rsplit_once('/')
+                .and_then(|(_, name)| windows.get(name))
+        })
+        .copied()
+        .unwrap_or(DEFAULT_CONTEXT_WINDOW)
+}
+
+/// How many tokens of reloaded history a follow-up may use with `model`
+///
+/// `CHAT_CONTEXT_TOKEN_BUDGET` sets a fixed budget for every model; otherwise
+/// it is half of the model's context window.
+pub fn context_token_budget(model: &str) -> usize {
+    env::var("CHAT_CONTEXT_TOKEN_BUDGET")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|budget| *budget > 0)
+        .unwrap_or_else(|| (context_window(model) as f64 * DEFAULT_BUDGET_RATIO) as usize)
+}
+
+/// Counts the tokens `messages` take up in a request to `model`
+///
+/// Models without a known tokenizer, such as Claude and Gemini, are counted
+/// with `cl100k_base`, which is close enough for budgeting.
+pub fn count_tokens(model: &str, messages: &[AgentMessage]) -> usize {
+    let name = model.rsplit_once('/').map_or(model, |(_, name)| name);
+    let uses_o200k = name.starts_with("o1")
+        || name.starts_with("o3")
+        || name.starts_with("o4")
+        || name.starts_with("gpt-4.1")
+        || tiktoken_rs::tokenizer::get_tokenizer(name) == Some(Tokenizer::O200kBase);
+    let bpe = if uses_o200k {
+        o200k_base_singleton()
+    } else {
+        cl100k_base_singleton()
+    };
+    let bpe = bpe.lock();
+
+    messages
+        .iter()
+        .map(|message| {
+            let mut text = message.get_content().unwrap_or_default();
+            if let AgentMessage::Assistant {
+                tool_calls: Some(tool_calls),
+                ..
+            } = message
+            {
+                for tool_call in tool_calls {
+                    text.push_str(&tool_call.function.name);
+                    text.push_str(&tool_call.function.arguments);
+                }
+            }
+            TOKENS_PER_MESSAGE + bpe.encode_with_special_tokens(&text).len()
+        })
+        .sum()
+}
+
+/// Finds where the recent turns of a history that is over `budget` start
+///
+/// Turns start at user messages, so every tool call stays with its response.
+/// The messages before the returned index are summarized and the rest are
+/// kept as they are. Returns None when the history fits or is a single turn.
+pub fn compaction_split(model: &str, messages: &[AgentMessage], budget: usize) -> Option<usize> {
+    if count_tokens(model, messages) <= budget {
+        return None;
+    }
+
+    let recent_budget = (budget as f64 * RECENT_TURNS_RATIO) as usize;
+    let mut split = messages.len();
+    let mut recent_tokens = 0;
+
+    for (index, message) in messages.iter().enumerate().rev() {
+        if !matches!(message, AgentMessage::User { .. }) {
+            continue;
+        }
+        let tokens = count_tokens(model, &messages[index..split]);
+        // The latest turn is kept whatever its size
+        if split < messages.len() && recent_tokens + tokens > recent_budget {
+            break;
+        }
+        recent_tokens += tokens;
+        split = index;
+    }
+
+    (split > 0 && split < messages.len()).then_some(split)
+}
+
+/// Lists the metrics and dashboards the agent created, updated or imported,
+/// one line per file at its latest version
+fn asset_references(messages: &[AgentMessage]) -> Vec<String> {
+    // id -> (name, file type, version)
+    let mut files: Vec<(String, (String, String, i64))> = Vec::new();
+
+    for message in messages {
+        let AgentMessage::Tool {
+            name: Some(tool_name),
+            content,
+            ..
+        } = message
+        else {
+            continue;
+        };
+        if !FILE_TOOLS.contains(&tool_name.as_str()) {
+            continue;
+        }
+        let Ok(response) = serde_json::from_str::<Value>(content) else {
+            continue;
+        };
+
+        for file in response
+            .get("files")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let Some(id) = file.get("id").and_then(Value::as_str) else {
+                continue;
+            };
+            let field = |key: &str| {
+                file.get(key)
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            let details = (
+                field("name"),
+                file.get("file_type")
+                    .or_else(|| file.get("asset_type"))
+                    .and_then(Value::as_str)
+                    .unwrap_or("file")
+                    .to_string(),
+                file.get("version_number")
+                    .and_then(Value::as_i64)
+                    .unwrap_or(1),
+            );
+
+            match files.iter_mut().find(|(file_id, _)| file_id == id) {
+                Some((_, existing)) => *existing = details,
+                None => files.push((id.to_string(), details)),
+            }
+        }
+    }
+
+    files
+        .into_iter()
+        .map(|(id, (name, file_type, version))| {
+            format!(
+                "- {} \"{}\" (id: {}, version {})",
+                file_type, name, id, version
+            )
+        })
+        .collect()
+}
+
+fn format_conversation(messages: &[AgentMessage], excerpt_chars: usize) -> String {
+    messages
+        .iter()
+        .filter_map(|message| {
+            let content = message.get_content()?;
+            if content.trim().is_empty() {
+                return None;
+            }
+            let content = if content.chars().count() > excerpt_chars {
+                format!(
+                    "{}...",
+                    content.chars().take(excerpt_chars).collect::<String>()
+                )
+            } else {
+                content
+            };
+            Some(format!("{}: {}", message.get_role(), content))
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+async fn summarize_turns(
+    messages: &[AgentMessage],
+    user_id: &Uuid,
+    chat_id: &Uuid,
+) -> Result<String> {
+    let prompt = COMPACTION_PROMPT.replace(
+        "{conversation_messages}",
+        &format_conversation(messages, SUMMARY_INPUT_CHARS),
+    );
+
+    let models = get_user_models(user_id).await?;
+    let llm_client = LiteLLMClient::new(None, None).with_approved_fallbacks(&models.approved_models());
+
+    let request = ChatCompletionRequest {
+        model: models.model_for(ModelPurpose::ContextCompaction),
+        messages: vec![AgentMessage::user(prompt)],
+        metadata: Some(Metadata {
+            generation_name: "context_compaction".to_string(),
+            user_id: user_id.to_string(),
+            session_id: chat_id.to_string(),
+            trace_id: chat_id.to_string(),
+            message_id: None,
+            organization_id: models.organization_id().map(|id| id.to_string()),
+        }),
+        ..Default::default()
+    };
+
+    let response = llm_client
+        .chat_completion(request)
+        .await
+        .map_err(|e| anyhow!("Failed to summarize conversation: {}", e))?;
+
+    match response.choices.first().map(|choice| &choice.message) {
+        Some(AgentMessage::Assistant {
+            content: Some(content),
+            ..
+        }) if !content.trim().is_empty() => Ok(content.trim().to_string()),
+        _ => Err(anyhow!("LLM response missing content")),
+    }
+}
+
+/// Builds the message that replaces the compacted turns
+fn summary_message(summary: &str, asset_references: &[String]) -> AgentMessage {
+    let mut content = format!("{}\n\n{}", SUMMARY_HEADER, summary);
+    if !asset_references.is_empty() {
+        content.push_str("\n\nFiles created earlier in this chat:\n");
+        content.push_str(&asset_references.join("\n"));
+    }
+    AgentMessage::user(content)
+}
+
+/// Compacts a chat's reloaded history so it fits the analysis model's budget
+///
+/// Older turns are replaced by a single summary message that keeps the key
+/// findings and lists every metric and dashboard created so far; the most
+/// recent turns are kept as they are. The compacted history is saved with the
+/// next message, so each turn is only summarized once. If the summary can't
+/// be generated, excerpts of the older turns are used instead so the
+/// follow-up still fits.
+pub async fn compact_chat_history(
+    messages: Vec<AgentMessage>,
+    user_id: &Uuid,
+    chat_id: &Uuid,
+) -> Vec<AgentMessage> {
+    let model = match get_user_models(user_id).await {
+        Ok(models) => models.model_for(ModelPurpose::Analysis),
+        Err(e) => {
+            tracing::warn!("Failed to load models for context compaction: {}", e);
+            ModelPurpose::Analysis.default_model().to_string()
+        }
+    };
+    let budget = context_token_budget(&model);
+
+    let Some(split) = compaction_split(&model, &messages, budget) else {
+        return messages;
+    };
+    let mut older = messages;
+    let recent = older.split_off(split);
+
+    tracing::info!(
+        "Compacting {} earlier messages of chat {} to fit a {} token budget",
+        older.len(),
+        chat_id,
+        budget
+    );
+
+    let summary = match summarize_turns(&older, user_id, chat_id).await {
+        Ok(summary) => summary,
+        Err(e) => {
+            tracing::warn!("Falling back to excerpts for chat {}: {}", chat_id, e);
+            format_conversation(&older, FALLBACK_EXCERPT_CHARS)
+        }
+    };
+
+    let mut compacted = vec![summary_message(&summary, &asset_references(&older))];
+    compacted.extend(recent);
+    compacted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use litellm::MessageProgress;
+    use serde_json::json;
+
+    fn turn(request: &str, response: &str) -> Vec<AgentMessage> {
+        vec![
+            AgentMessage::user(request),
+            AgentMessage::assistant(
+                None,
+                Some(response.to_string()),
+                None,
+                MessageProgress::Complete,
+                None,
+                None,
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_compaction_split_keeps_recent_turns() {
+        let long = "revenue by region ".repeat(200);
+        let messages: Vec<AgentMessage> = (0..6)
+            .flat_map(|i| turn(&format!("question {}", i), &long))
+            .collect();
+        let total = count_tokens("o4-mini", &messages);
+
+        assert_eq!(compaction_split("o4-mini", &messages, total), None);
+
+        let split = compaction_split("o4-mini", &messages, total / 2).unwrap();
+        assert!(split > 0);
+        assert!(matches!(messages[split], AgentMessage::User { .. }));
+        assert!(count_tokens("o4-mini", &messages[split..]) <= total / 4);
+        assert_eq!(
+            messages[messages.len() - 2].get_content().as_deref(),
+            Some("question 5")
+        );
+    }
+
+    #[test]
+    fn test_compaction_split_always_keeps_latest_turn() {
+        let messages = turn("only question", &"a very long answer ".repeat(500));
+        assert_eq!(compaction_split("o4-mini", &messages, 10), None);
+    }
+
+    #[test]
+    fn test_asset_references_keep_latest_version() {
+        let id = Uuid::new_v4().to_string();
+        let tool = |version: i64| {
+            AgentMessage::Tool {
+            id: None,
+            content: json!({
+                "files": [{ "id": id, "name": "Revenue", "file_type": "metric", "version_number": version }]
+            })
+            .to_string(),
+            tool_call_id: "call_1".to_string(),
+            name: Some("create_metrics".to_string()),
+            progress: MessageProgress::Complete,
+        }
+        };
+
+        let references = asset_references(&[tool(1), tool(2)]);
+        assert_eq!(
+            references,
+            vec![format!("- metric \"Revenue\" (id: {}, version 2)", id)]
+        );
+    }
+
+    #[test]
+    fn test_context_window_ignores_provider_prefix() {
+        assert_eq!(context_window("openai/o4-mini"), 200_000);
+        assert_eq!(context_window("unknown-model"), DEFAULT_CONTEXT_WINDOW);
+    }
+}
@@ -18,6 +18,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use uuid::Uuid;
 
+use super::chat_compaction::compact_chat_history;
 use super::ContextLoader;
 
 // --- Structs for Simulated Tool Call (handling multiple files) ---
@@ -41,11 +42,17 @@ struct UserManuallyModifiedFileOutput {
 
 pub struct ChatContextLoader {
     pub chat_id: Uuid,
+    /// The message the new one follows on its branch; None when the new
+    /// message starts the chat over
+    pub parent_message_id: Option<Uuid>,
 }
 
 impl ChatContextLoader {
-    pub fn new(chat_id: Uuid) -> Self {
-        Self { chat_id }
+    pub fn new(chat_id: Uuid, parent_message_id: Option<Uuid>) -> Self {
+        Self {
+            chat_id,
+            parent_message_id,
+        }
     }
 
     // Helper function to check for tool usage and set appropriate context
@@ -403,11 +410,15 @@ impl ContextLoader for ChatContextLoader {
             .first::<database::models::Chat>(&mut conn)
             .await?;
 
-        // Get only the most recent message for the chat
+        // Each message stores the history of its branch up to itself, so only
+        // the message being followed is needed
+        let Some(parent_message_id) = self.parent_message_id else {
+            return Ok(vec![]);
+        };
         let message = match messages::table
+            .filter(messages::id.eq(parent_message_id))
             .filter(messages::chat_id.eq(chat.id))
             .filter(messages::deleted_at.is_null())
-            .order_by(messages::created_at.desc())
             .first::<database::models::Message>(&mut conn)
             .await
         {
@@ -447,6 +458,9 @@ impl ContextLoader for ChatContextLoader {
             }
         }
 
+        // Long chats are compacted so follow-ups stay within the model's context
+        let mut agent_messages = compact_chat_history(agent_messages, &user.id, &chat.id).await;
+
         // Check for external updates and get simulated messages
         let simulated_update_messages =
             match Self::check_external_asset_updates(agent, &raw_messages).await {
@@ -7,6 +7,7 @@ use agents::Agent;
 use database::enums::AssetType;
 use uuid::Uuid;
 
+pub mod chat_compaction;
 pub mod chat_context;
 pub mod metric_context;
 pub mod dashboard_context;
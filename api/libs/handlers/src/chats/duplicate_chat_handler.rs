@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use anyhow::{anyhow, Result};
 use chrono::Utc;
 use diesel::{ExpressionMethods, QueryDsl};
@@ -8,7 +10,9 @@ use uuid::Uuid;
 use crate::chats::get_chat_handler::get_chat_handler;
 use crate::chats::types::ChatWithMessages;
 use database::enums::{AssetPermissionRole, AssetType, IdentityType};
-use database::helpers::chats::fetch_chat_with_permission;
+use database::helpers::chats::{
+    active_leaf, branch_path, fetch_chat_message_tree, fetch_chat_with_permission,
+};
 use database::models::{AssetPermission, Chat, Message, MessageToFile};
 use database::pool::get_pg_pool;
 use database::schema::{asset_permissions, chats, messages, messages_to_files};
@@ -18,7 +22,7 @@ use sharing::check_permission_access;
 ///
 /// # Arguments
 /// * `chat_id` - UUID of the source chat to duplicate
-/// * `message_id` - Optional UUID of a message in the chat; if provided, only messages created at or after this message will be duplicated
+/// * `message_id` - Optional UUID of a message in the chat; if provided, only the branch of messages leading up to and including it is duplicated
 /// * `user` - The authenticated user making the request
 ///
 /// # Returns
@@ -60,8 +64,44 @@ pub async fn duplicate_chat_handler(
 
     let mut conn = get_pg_pool().get().await?;
 
-    // 1. Create a new chat record
     let source_chat = chat_with_permission.chat;
+
+    // 1. Determine which messages to duplicate based on message_id parameter
+    let tree = fetch_chat_message_tree(chat_id).await?;
+    let messages_to_duplicate = match message_id {
+        Some(msg_id) => {
+            // Only the branch leading to the message is copied
+            if !tree.iter().any(|(id, _)| id == msg_id) {
+                return Err(anyhow!("Message not found in this chat"));
+            }
+            let branch = branch_path(&tree, *msg_id);
+
+            messages::table
+                .filter(messages::id.eq_any(&branch))
+                .order_by(messages::created_at.asc())
+                .load::<Message>(&mut conn)
+                .await?
+        }
+        None => {
+            // Get all non-deleted messages from the chat, across every branch
+            messages::table
+                .filter(messages::chat_id.eq(chat_id))
+                .filter(messages::deleted_at.is_null())
+                .order_by(messages::created_at.asc())
+                .load::<Message>(&mut conn)
+                .await?
+        }
+    };
+
+    // Copies keep the same branch structure under new ids
+    let new_message_ids: HashMap<Uuid, Uuid> = messages_to_duplicate
+        .iter()
+        .map(|message| (message.id, Uuid::new_v4()))
+        .collect();
+    let source_active_message_id =
+        message_id.copied().or_else(|| active_leaf(&tree, source_chat.active_message_id));
+
+    // 2. Create a new chat record
     let new_chat_id = Uuid::new_v4();
     let now = Utc::now();
 
@@ -83,6 +123,8 @@ pub async fn duplicate_chat_handler(
         most_recent_file_id: source_chat.most_recent_file_id,
         most_recent_file_type: source_chat.most_recent_file_type.clone(),
         most_recent_version_number: source_chat.most_recent_version_number,
+        active_message_id: source_active_message_id
+            .and_then(|id| new_message_ids.get(&id).copied()),
     };
 
     // Insert the new chat record
@@ -91,7 +133,7 @@ pub async fn duplicate_chat_handler(
         .execute(&mut conn)
         .await?;
 
-    // 2. Set permissions for the new chat (owner for the current user)
+    // 3. Set permissions for the new chat (owner for the current user)
     let new_permission = AssetPermission {
         identity_id: user.id,
         identity_type: IdentityType::User,
@@ -110,40 +152,9 @@ pub async fn duplicate_chat_handler(
         .execute(&mut conn)
         .await?;
 
-    // 3. Determine which messages to duplicate based on message_id parameter
-    let messages_to_duplicate = match message_id {
-        Some(msg_id) => {
-            // Get the specific message and check if it belongs to the chat
-            let message = messages::table
-                .filter(messages::id.eq(msg_id))
-                .filter(messages::chat_id.eq(chat_id))
-                .filter(messages::deleted_at.is_null())
-                .first::<Message>(&mut conn)
-                .await?;
-
-            // Get this message and all older messages (messages created at or before this message)
-            messages::table
-                .filter(messages::chat_id.eq(chat_id))
-                .filter(messages::deleted_at.is_null())
-                .filter(messages::created_at.le(message.created_at))
-                .order_by(messages::created_at.asc())
-                .load::<Message>(&mut conn)
-                .await?
-        }
-        None => {
-            // Get all non-deleted messages from the chat
-            messages::table
-                .filter(messages::chat_id.eq(chat_id))
-                .filter(messages::deleted_at.is_null())
-                .order_by(messages::created_at.asc())
-                .load::<Message>(&mut conn)
-                .await?
-        }
-    };
-
     // 4. Duplicate each message
     for source_message in messages_to_duplicate {
-        let new_message_id = Uuid::new_v4();
+        let new_message_id = new_message_ids[&source_message.id];
 
         // Create a new message record
         let new_message = Message {
@@ -160,6 +171,9 @@ pub async fn duplicate_chat_handler(
             deleted_at: None,
             created_by: user.id,
             feedback: source_message.feedback.clone(),
+            parent_message_id: source_message
+                .parent_message_id
+                .and_then(|id| new_message_ids.get(&id).copied()),
         };
 
         // Insert the new message record
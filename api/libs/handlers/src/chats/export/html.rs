@@ -0,0 +1,274 @@
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+use super::{cell_text, ChatDocument, ChatResponse, ExportedMetric};
+
+const STYLE: &str = "body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',Helvetica,Arial,sans-serif;color:#1f2328;max-width:960px;margin:40px auto;padding:0 24px;line-height:1.5}\
+header{border-bottom:1px solid #d0d7de;margin-bottom:24px}\
+.meta{color:#656d76;font-size:14px}\
+.turn{border-top:1px solid #d0d7de;padding-top:16px;margin-top:24px}\
+.prompt{background:#f6f8fa;border-left:4px solid #7c3aed;padding:8px 16px;white-space:pre-wrap}\
+.reasoning{color:#656d76;font-size:14px}\
+.metric,.dashboard{border:1px solid #d0d7de;border-radius:6px;padding:12px 16px;margin:16px 0}\
+pre{background:#f6f8fa;padding:12px;overflow-x:auto;border-radius:6px}\
+table{border-collapse:collapse;font-size:14px;display:block;overflow-x:auto}\
+th,td{border:1px solid #d0d7de;padding:4px 8px;text-align:left}\
+th{background:#f6f8fa}\
+.note{color:#656d76;font-style:italic}";
+
+/// Renders a chat as a standalone HTML page with inline styles
+///
+/// The agent's Markdown is converted to HTML; raw HTML inside it is escaped.
+pub fn render_chat_html(document: &ChatDocument) -> String {
+    let mut out =
+        String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str(&format!("<title>{}</title>\n", escape(&document.title)));
+    out.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", STYLE));
+
+    out.push_str(&format!(
+        "<header>\n<h1>{}</h1>\n<p class=\"meta\">Chat by {} · exported {} · <a href=\"{}\">open in Buster</a></p>\n</header>\n",
+        escape(&document.title),
+        escape(&document.created_by_name),
+        document.generated_at.format("%Y-%m-%d %H:%M UTC"),
+        escape(&document.link)
+    ));
+
+    for turn in &document.turns {
+        out.push_str("<section class=\"turn\">\n");
+        if let Some(prompt) = &turn.prompt {
+            out.push_str(&format!(
+                "<h2>{} asked</h2>\n<div class=\"prompt\">{}</div>\n",
+                escape(&turn.sender_name),
+                escape(prompt)
+            ));
+        }
+
+        if turn.reasoning_summary.is_some() || !turn.reasoning_steps.is_empty() {
+            out.push_str(&format!(
+                "<details class=\"reasoning\">\n<summary>{}</summary>\n<ul>\n",
+                escape(turn.reasoning_summary.as_deref().unwrap_or("Reasoning"))
+            ));
+            for step in &turn.reasoning_steps {
+                out.push_str(&format!("<li>{}</li>\n", escape(step)));
+            }
+            out.push_str("</ul>\n</details>\n");
+        }
+
+        for response in &turn.responses {
+            match response {
+                ChatResponse::Text(text) => out.push_str(&markdown_to_html(text)),
+                ChatResponse::Metric(metric) => render_metric(&mut out, metric),
+                ChatResponse::Dashboard { name, link } => out.push_str(&format!(
+                    "<div class=\"dashboard\"><strong>Dashboard:</strong> <a href=\"{}\">{}</a></div>\n",
+                    escape(link),
+                    escape(name)
+                )),
+            }
+        }
+        out.push_str("</section>\n");
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn render_metric(out: &mut String, metric: &ExportedMetric) {
+    out.push_str("<div class=\"metric\">\n");
+    out.push_str(&format!(
+        "<h3><a href=\"{}\">{}</a></h3>\n",
+        escape(&metric.link),
+        escape(&metric.name)
+    ));
+    if let Some(description) = &metric.description {
+        out.push_str(&format!("<p>{}</p>\n", escape(description)));
+    }
+    if let Some(sql) = &metric.sql {
+        out.push_str(&format!(
+            "<pre><code class=\"language-sql\">{}</code></pre>\n",
+            escape(sql.trim())
+        ));
+    }
+
+    if let Some(error) = &metric.error {
+        out.push_str(&format!(
+            "<p class=\"note\">{}</p>\n</div>\n",
+            escape(error)
+        ));
+        return;
+    }
+    if metric.columns.is_empty() {
+        out.push_str("<p class=\"note\">No rows returned</p>\n</div>\n");
+        return;
+    }
+
+    out.push_str("<table>\n<thead><tr>");
+    for column in &metric.columns {
+        out.push_str(&format!("<th>{}</th>", escape(column)));
+    }
+    out.push_str("</tr></thead>\n<tbody>\n");
+    for row in &metric.rows {
+        out.push_str("<tr>");
+        for value in row {
+            out.push_str(&format!("<td>{}</td>", escape(&cell_text(value))));
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</tbody>\n</table>\n");
+    if metric.truncated {
+        out.push_str(&format!(
+            "<p class=\"note\">Showing the first {} rows</p>\n",
+            metric.rows.len()
+        ));
+    }
+    out.push_str("</div>\n");
+}
+
+/// Converts the agent's Markdown to HTML
+fn markdown_to_html(markdown: &str) -> String {
+    let mut out = String::new();
+    let mut in_table_head = false;
+
+    for event in Parser::new_ext(
+        markdown,
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH,
+    ) {
+        match event {
+            Event::Start(tag) => match tag {
+                Tag::Paragraph => out.push_str("<p>"),
+                Tag::Heading { level, .. } => out.push_str(&format!("<{}>", heading(level))),
+                Tag::BlockQuote(_) => out.push_str("<blockquote>\n"),
+                Tag::CodeBlock(_) => out.push_str("<pre><code>"),
+                Tag::List(Some(1)) => out.push_str("<ol>\n"),
+                Tag::List(Some(start)) => out.push_str(&format!("<ol start=\"{}\">\n", start)),
+                Tag::List(None) => out.push_str("<ul>\n"),
+                Tag::Item => out.push_str("<li>"),
+                Tag::Table(_) => out.push_str("<table>\n"),
+                Tag::TableHead => {
+                    in_table_head = true;
+                    out.push_str("<thead><tr>");
+                }
+                Tag::TableRow => out.push_str("<tr>"),
+                Tag::TableCell => out.push_str(if in_table_head { "<th>" } else { "<td>" }),
+                Tag::Emphasis => out.push_str("<em>"),
+                Tag::Strong => out.push_str("<strong>"),
+                Tag::Strikethrough => out.push_str("<del>"),
+                Tag::Link { dest_url, .. } => {
+                    out.push_str(&format!("<a href=\"{}\">", escape(&safe_url(&dest_url))))
+                }
+                Tag::Image { dest_url, .. } => {
+                    out.push_str(&format!("<a href=\"{}\">", escape(&safe_url(&dest_url))))
+                }
+                _ => {}
+            },
+            Event::End(tag) => match tag {
+                TagEnd::Paragraph => out.push_str("</p>\n"),
+                TagEnd::Heading(level) => out.push_str(&format!("</{}>\n", heading(level))),
+                TagEnd::BlockQuote(_) => out.push_str("</blockquote>\n"),
+                TagEnd::CodeBlock => out.push_str("</code></pre>\n"),
+                TagEnd::List(true) => out.push_str("</ol>\n"),
+                TagEnd::List(false) => out.push_str("</ul>\n"),
+                TagEnd::Item => out.push_str("</li>\n"),
+                TagEnd::Table => out.push_str("</tbody></table>\n"),
+                TagEnd::TableHead => {
+                    in_table_head = false;
+                    out.push_str("</tr></thead>\n<tbody>\n");
+                }
+                TagEnd::TableRow => out.push_str("</tr>\n"),
+                TagEnd::TableCell => out.push_str(if in_table_head { "</th>" } else { "</td>" }),
+                TagEnd::Emphasis => out.push_str("</em>"),
+                TagEnd::Strong => out.push_str("</strong>"),
+                TagEnd::Strikethrough => out.push_str("</del>"),
+                TagEnd::Link | TagEnd::Image => out.push_str("</a>"),
+                _ => {}
+            },
+            Event::Text(text) | Event::Html(text) | Event::InlineHtml(text) => {
+                out.push_str(&escape(&text))
+            }
+            Event::Code(code) => out.push_str(&format!("<code>{}</code>", escape(&code))),
+            Event::SoftBreak => out.push('\n'),
+            Event::HardBreak => out.push_str("<br>\n"),
+            Event::Rule => out.push_str("<hr>\n"),
+            _ => {}
+        }
+    }
+
+    out
+}
+
+fn heading(level: HeadingLevel) -> &'static str {
+    match level {
+        HeadingLevel::H1 => "h1",
+        HeadingLevel::H2 => "h2",
+        HeadingLevel::H3 => "h3",
+        HeadingLevel::H4 => "h4",
+        HeadingLevel::H5 => "h5",
+        HeadingLevel::H6 => "h6",
+    }
+}
+
+/// Drops script URLs from links in the agent's Markdown
+fn safe_url(url: &str) -> String {
+    let scheme = url.trim_start().to_ascii_lowercase();
+    if scheme.starts_with("javascript:")
+        || scheme.starts_with("data:")
+        || scheme.starts_with("vbscript:")
+    {
+        "#".to_string()
+    } else {
+        url.to_string()
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chats::export::tests::sample_document;
+
+    #[test]
+    fn test_markdown_to_html() {
+        assert_eq!(
+            markdown_to_html("**EMEA** grew `fast`"),
+            "<p><strong>EMEA</strong> grew <code>fast</code></p>\n"
+        );
+        assert_eq!(
+            markdown_to_html("| a | b |\n|---|---|\n| 1 | 2 |"),
+            "<table>\n<thead><tr><th>a</th><th>b</th></tr></thead>\n<tbody>\n<tr><td>1</td><td>2</td></tr>\n</tbody></table>\n"
+        );
+        assert_eq!(
+            markdown_to_html("<script>alert(1)</script>"),
+            "&lt;script&gt;alert(1)&lt;/script&gt;"
+        );
+        assert_eq!(
+            markdown_to_html("[x](javascript:alert(1))"),
+            "<p><a href=\"#\">x</a></p>\n"
+        );
+    }
+
+    #[test]
+    fn test_render_chat_html() {
+        let html = render_chat_html(&sample_document());
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Revenue &lt;Q3&gt;</title>"));
+        assert!(html.contains("<summary>Reasoned for 12 seconds</summary>"));
+        assert!(html.contains("<p><strong>EMEA</strong> grew fastest.</p>"));
+        assert!(html.contains("<td>EMEA | UK</td><td>1200.5</td>"));
+        assert!(
+            html.contains("<a href=\"https://buster.example/app/dashboards/3\">Sales overview</a>")
+        );
+    }
+}
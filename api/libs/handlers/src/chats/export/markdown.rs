@@ -0,0 +1,117 @@
+use super::{cell_text, ChatDocument, ChatResponse, ExportedMetric};
+
+/// Renders a chat as a Markdown document
+pub fn render_chat_markdown(document: &ChatDocument) -> String {
+    let mut out = format!("# {}\n\n", document.title);
+    out.push_str(&format!(
+        "_Chat by {} · exported {} · [open in Buster]({})_\n",
+        document.created_by_name,
+        document.generated_at.format("%Y-%m-%d %H:%M UTC"),
+        document.link
+    ));
+
+    for turn in &document.turns {
+        out.push_str("\n---\n\n");
+        if let Some(prompt) = &turn.prompt {
+            out.push_str(&format!(
+                "## {} asked\n\n{}\n",
+                turn.sender_name,
+                quote(prompt)
+            ));
+        }
+
+        if turn.reasoning_summary.is_some() || !turn.reasoning_steps.is_empty() {
+            out.push_str(&format!(
+                "\n**{}**\n\n",
+                turn.reasoning_summary.as_deref().unwrap_or("Reasoning")
+            ));
+            for step in &turn.reasoning_steps {
+                out.push_str(&format!("- {}\n", step));
+            }
+        }
+
+        for response in &turn.responses {
+            out.push('\n');
+            match response {
+                ChatResponse::Text(text) => {
+                    out.push_str(text.trim_end());
+                    out.push('\n');
+                }
+                ChatResponse::Metric(metric) => render_metric(&mut out, metric),
+                ChatResponse::Dashboard { name, link } => {
+                    out.push_str(&format!("**Dashboard:** [{}]({})\n", name, link));
+                }
+            }
+        }
+    }
+
+    out
+}
+
+fn render_metric(out: &mut String, metric: &ExportedMetric) {
+    out.push_str(&format!("### [{}]({})\n\n", metric.name, metric.link));
+    if let Some(description) = &metric.description {
+        out.push_str(&format!("{}\n\n", description));
+    }
+    if let Some(sql) = &metric.sql {
+        out.push_str(&format!("```sql\n{}\n```\n\n", sql.trim()));
+    }
+
+    if let Some(error) = &metric.error {
+        out.push_str(&format!("_{}_\n", error));
+        return;
+    }
+    if metric.columns.is_empty() {
+        out.push_str("_No rows returned_\n");
+        return;
+    }
+
+    out.push_str(&table_row(metric.columns.iter().map(String::as_str)));
+    out.push_str(&table_row(metric.columns.iter().map(|_| "---")));
+    for row in &metric.rows {
+        let cells: Vec<String> = row.iter().map(cell_text).collect();
+        out.push_str(&table_row(cells.iter().map(String::as_str)));
+    }
+    if metric.truncated {
+        out.push_str(&format!(
+            "\n_Showing the first {} rows_\n",
+            metric.rows.len()
+        ));
+    }
+}
+
+fn table_row<'a>(cells: impl Iterator<Item = &'a str>) -> String {
+    let cells: Vec<String> = cells
+        .map(|cell| cell.replace('|', "\\|").replace(['\r', '\n'], " "))
+        .collect();
+    format!("| {} |\n", cells.join(" | "))
+}
+
+fn quote(text: &str) -> String {
+    text.lines()
+        .map(|line| format!("> {}", line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chats::export::tests::sample_document;
+
+    #[test]
+    fn test_render_chat_markdown() {
+        let markdown = render_chat_markdown(&sample_document());
+
+        assert!(markdown.starts_with("# Revenue <Q3>\n"));
+        assert!(markdown.contains("## Ada asked\n\n> What was revenue by region?"));
+        assert!(markdown.contains("**Reasoned for 12 seconds**\n\n- Searched your data catalog"));
+        assert!(markdown.contains("```sql\nselect region"));
+        assert!(markdown.contains(
+            "| region | revenue |\n| --- | --- |\n| EMEA \\| UK | 1200.5 |\n| AMER |  |"
+        ));
+        assert!(markdown.contains("_Showing the first 2 rows_"));
+        assert!(markdown
+            .contains("**Dashboard:** [Sales overview](https://buster.example/app/dashboards/3)"));
+    }
+}
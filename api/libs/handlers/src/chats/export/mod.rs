@@ -0,0 +1,116 @@
+//! Self-contained Markdown and HTML documents of a chat, for sharing an
+//! investigation with people outside Buster.
+
+mod html;
+mod markdown;
+
+pub use html::render_chat_html;
+pub use markdown::render_chat_markdown;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+/// A chat's active branch, ready to render
+#[derive(Debug, Clone)]
+pub struct ChatDocument {
+    pub title: String,
+    pub link: String,
+    pub created_by_name: String,
+    pub generated_at: DateTime<Utc>,
+    pub turns: Vec<ChatTurn>,
+}
+
+/// One prompt and everything the agent answered with
+#[derive(Debug, Clone)]
+pub struct ChatTurn {
+    pub prompt: Option<String>,
+    pub sender_name: String,
+    pub created_at: DateTime<Utc>,
+    /// e.g. "Reasoned for 42 seconds"
+    pub reasoning_summary: Option<String>,
+    /// Titles of the reasoning steps, in order
+    pub reasoning_steps: Vec<String>,
+    pub responses: Vec<ChatResponse>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ChatResponse {
+    /// Markdown written by the agent
+    Text(String),
+    Metric(ExportedMetric),
+    Dashboard { name: String, link: String },
+}
+
+/// A metric with the rows it returned when the chat was exported
+#[derive(Debug, Clone)]
+pub struct ExportedMetric {
+    pub name: String,
+    pub description: Option<String>,
+    pub sql: Option<String>,
+    pub link: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+    /// More rows exist than were exported
+    pub truncated: bool,
+    pub error: Option<String>,
+}
+
+/// Plain text of a result cell
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// A chat exercising every kind of response
+    pub fn sample_document() -> ChatDocument {
+        let now = Utc::now();
+        ChatDocument {
+            title: "Revenue <Q3>".to_string(),
+            link: "https://buster.example/app/chats/1".to_string(),
+            created_by_name: "Ada".to_string(),
+            generated_at: now,
+            turns: vec![ChatTurn {
+                prompt: Some("What was revenue by region?".to_string()),
+                sender_name: "Ada".to_string(),
+                created_at: now,
+                reasoning_summary: Some("Reasoned for 12 seconds".to_string()),
+                reasoning_steps: vec!["Searched your data catalog".to_string()],
+                responses: vec![
+                    ChatResponse::Text("**EMEA** grew fastest.".to_string()),
+                    ChatResponse::Metric(ExportedMetric {
+                        name: "Revenue by region".to_string(),
+                        description: None,
+                        sql: Some("select region, sum(amount) from orders group by 1".to_string()),
+                        link: "https://buster.example/app/metrics/2".to_string(),
+                        columns: vec!["region".to_string(), "revenue".to_string()],
+                        rows: vec![
+                            vec![json!("EMEA | UK"), json!(1200.5)],
+                            vec![json!("AMER"), Value::Null],
+                        ],
+                        truncated: true,
+                        error: None,
+                    }),
+                    ChatResponse::Dashboard {
+                        name: "Sales overview".to_string(),
+                        link: "https://buster.example/app/dashboards/3".to_string(),
+                    },
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_cell_text() {
+        assert_eq!(cell_text(&json!("EMEA")), "EMEA");
+        assert_eq!(cell_text(&json!(3.5)), "3.5");
+        assert_eq!(cell_text(&Value::Null), "");
+    }
+}
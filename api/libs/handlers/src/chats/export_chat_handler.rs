@@ -0,0 +1,324 @@
+use std::env;
+
+use anyhow::Result;
+use chrono::Utc;
+use futures::future::join_all;
+use middleware::AuthenticatedUser;
+use serde::Deserialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::chats::export::{
+    render_chat_html, render_chat_markdown, ChatDocument, ChatResponse, ChatTurn, ExportedMetric,
+};
+use crate::chats::get_chat_handler;
+use crate::chats::post_chat_handler::BusterChatMessage;
+use crate::messages::types::ChatMessage;
+use crate::metrics::{get_metric_data_handler, get_metric_handler, GetMetricDataRequest};
+
+/// Default cap on rows exported per metric; enough to read, small enough to share
+const DEFAULT_ROW_LIMIT: i64 = 100;
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatExportFormat {
+    #[default]
+    Markdown,
+    Html,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct ExportChatRequest {
+    #[serde(default)]
+    pub format: ChatExportFormat,
+    pub row_limit: Option<i64>,
+}
+
+#[derive(Debug)]
+pub struct ChatExport {
+    pub file_name: String,
+    pub content_type: &'static str,
+    pub content: String,
+}
+
+/// Exports the active branch of a chat as a self-contained Markdown or HTML
+/// document: prompts, responses, a reasoning summary, each metric's SQL and
+/// results, and links to dashboards.
+///
+/// Metrics are exported with the data the user can see today, not the data
+/// shown when the chat happened.
+pub async fn export_chat_handler(
+    chat_id: &Uuid,
+    user: &AuthenticatedUser,
+    request: ExportChatRequest,
+) -> Result<ChatExport> {
+    // Permission checks are handled here
+    let chat = get_chat_handler(chat_id, user, false).await?;
+    let row_limit = request.row_limit.unwrap_or(DEFAULT_ROW_LIMIT).max(1);
+    let base_url = env::var("BUSTER_URL").unwrap_or_default();
+
+    let turns = join_all(
+        chat.message_ids
+            .iter()
+            .filter_map(|id| chat.messages.get(id))
+            .map(|message| build_turn(message, user, row_limit, &base_url)),
+    )
+    .await;
+
+    let document = ChatDocument {
+        title: chat.title.clone(),
+        link: format!("{}/app/chats/{}", base_url, chat.id),
+        created_by_name: chat.created_by_name.clone(),
+        generated_at: Utc::now(),
+        turns,
+    };
+
+    Ok(match request.format {
+        ChatExportFormat::Markdown => ChatExport {
+            file_name: export_file_name(&chat.title, "md"),
+            content_type: "text/markdown; charset=utf-8",
+            content: render_chat_markdown(&document),
+        },
+        ChatExportFormat::Html => ChatExport {
+            file_name: export_file_name(&chat.title, "html"),
+            content_type: "text/html; charset=utf-8",
+            content: render_chat_html(&document),
+        },
+    })
+}
+
+/// Builds a message's turn, loading each metric with up to `row_limit` rows
+pub(crate) async fn build_turn(
+    message: &ChatMessage,
+    user: &AuthenticatedUser,
+    row_limit: i64,
+    base_url: &str,
+) -> ChatTurn {
+    let responses = join_all(
+        message
+            .response_message_ids
+            .iter()
+            .filter_map(|id| message.response_messages.get(id))
+            .filter_map(|value| serde_json::from_value::<BusterChatMessage>(value.clone()).ok())
+            .map(|response| build_response(response, user, row_limit, base_url)),
+    )
+    .await
+    .into_iter()
+    .flatten()
+    .collect();
+
+    ChatTurn {
+        prompt: message
+            .request_message
+            .as_ref()
+            .and_then(|request| request.request.clone()),
+        sender_name: message
+            .request_message
+            .as_ref()
+            .map(|request| request.sender_name.clone())
+            .unwrap_or_default(),
+        created_at: message.created_at,
+        reasoning_summary: message.final_reasoning_message.clone(),
+        reasoning_steps: reasoning_steps(message),
+        responses,
+    }
+}
+
+async fn build_response(
+    response: BusterChatMessage,
+    user: &AuthenticatedUser,
+    row_limit: i64,
+    base_url: &str,
+) -> Option<ChatResponse> {
+    match response {
+        BusterChatMessage::Text { message, .. } => message
+            .filter(|text| !text.trim().is_empty())
+            .map(ChatResponse::Text),
+        BusterChatMessage::File {
+            id,
+            file_type,
+            file_name,
+            version_number,
+            ..
+        } => {
+            let file_id = Uuid::parse_str(&id).ok()?;
+            match file_type.as_str() {
+                "metric" => Some(ChatResponse::Metric(
+                    build_metric(
+                        file_id,
+                        file_name,
+                        version_number,
+                        user,
+                        row_limit,
+                        base_url,
+                    )
+                    .await,
+                )),
+                "dashboard" => Some(ChatResponse::Dashboard {
+                    name: file_name,
+                    link: format!("{}/app/dashboards/{}", base_url, file_id),
+                }),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Loads a metric and up to `row_limit` of its rows with the user's permissions,
+/// recording failures on the result rather than erroring
+pub(crate) async fn build_metric(
+    metric_id: Uuid,
+    file_name: String,
+    version_number: i32,
+    user: &AuthenticatedUser,
+    row_limit: i64,
+    base_url: &str,
+) -> ExportedMetric {
+    let mut exported = ExportedMetric {
+        name: file_name,
+        description: None,
+        sql: None,
+        link: format!("{}/app/metrics/{}", base_url, metric_id),
+        columns: Vec::new(),
+        rows: Vec::new(),
+        truncated: false,
+        error: None,
+    };
+
+    let metric = match get_metric_handler(&metric_id, user, Some(version_number), None).await {
+        Ok(metric) => metric,
+        Err(e) => {
+            tracing::warn!(metric_id = %metric_id, "Failed to load metric for chat export: {}", e);
+            exported.error = Some("This metric could not be loaded".to_string());
+            return exported;
+        }
+    };
+    exported.name = metric.name;
+    exported.description = metric.description;
+    exported.sql = Some(metric.sql);
+
+    // One extra row tells whether the results were cut off
+    let data = get_metric_data_handler(
+        GetMetricDataRequest {
+            metric_id,
+            version_number: Some(version_number),
+            limit: Some(row_limit + 1),
+            password: None,
+            drill: None,
+        },
+        user.clone(),
+    )
+    .await;
+
+    match data {
+        Ok(response) => {
+            let mut rows = response.data;
+            exported.truncated = rows.len() as i64 > row_limit;
+            rows.truncate(row_limit as usize);
+            exported.columns = rows
+                .first()
+                .map(|row| row.keys().cloned().collect())
+                .unwrap_or_default();
+            exported.rows = rows
+                .into_iter()
+                .map(|row| {
+                    row.into_values()
+                        .map(|value| serde_json::to_value(value).unwrap_or(Value::Null))
+                        .collect()
+                })
+                .collect();
+        }
+        Err(e) => {
+            tracing::warn!(metric_id = %metric_id, "Failed to fetch metric data for chat export: {}", e);
+            exported.error = Some("Failed to load data for this metric".to_string());
+        }
+    }
+
+    exported
+}
+
+/// Titles of a message's reasoning steps, in order, skipping the closing
+/// "finished reasoning" marker
+fn reasoning_steps(message: &ChatMessage) -> Vec<String> {
+    message
+        .reasoning_message_ids
+        .iter()
+        .filter_map(|id| message.reasoning_messages.get(id))
+        .filter(|step| {
+            !step
+                .get("finished_reasoning")
+                .and_then(Value::as_bool)
+                .unwrap_or(false)
+        })
+        .filter_map(|step| step.get("title").and_then(Value::as_str))
+        .map(str::trim)
+        .filter(|title| !title.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn export_file_name(title: &str, extension: &str) -> String {
+    let slug = title
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>()
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    if slug.is_empty() {
+        format!("chat.{}", extension)
+    } else {
+        format!("{}.{}", slug, extension)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_export_file_name() {
+        assert_eq!(
+            export_file_name("Revenue by region?", "md"),
+            "revenue-by-region.md"
+        );
+        assert_eq!(export_file_name("", "html"), "chat.html");
+    }
+
+    #[test]
+    fn test_reasoning_steps() {
+        let mut message = ChatMessage::new(
+            Some("question".to_string()),
+            Uuid::new_v4(),
+            "Ada".to_string(),
+            None,
+        );
+        message.reasoning_message_ids = vec!["a".into(), "b".into(), "c".into()];
+        message.reasoning_messages = HashMap::from([
+            (
+                "a".to_string(),
+                json!({ "title": "Searched your data catalog" }),
+            ),
+            ("b".to_string(), json!({ "title": "  " })),
+            (
+                "c".to_string(),
+                json!({ "title": "Finished reasoning", "finished_reasoning": true }),
+            ),
+        ]);
+
+        assert_eq!(
+            reasoning_steps(&message),
+            vec!["Searched your data catalog"]
+        );
+    }
+}
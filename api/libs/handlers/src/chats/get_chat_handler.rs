@@ -14,7 +14,7 @@ use crate::messages::types::{ChatMessage, ChatUserMessage};
 use database::schema::{asset_permissions, chats, messages, users};
 use database::{
     enums::{AssetPermissionRole, AssetType, IdentityType},
-    helpers::chats::fetch_chat_with_permission,
+    helpers::chats::{active_leaf, fetch_chat_with_permission, sibling_ids, MessageNode},
     pool::get_pg_pool,
 };
 use sharing::check_permission_access;
@@ -43,6 +43,7 @@ pub struct MessageWithUser {
     pub user_name: Option<String>,
     pub user_attributes: Value,
     pub feedback: Option<String>,
+    pub parent_message_id: Option<Uuid>,
 }
 
 #[derive(Queryable)]
@@ -125,6 +126,7 @@ pub async fn get_chat_handler(
                     users::name.nullable(),
                     users::attributes,
                     messages::feedback.nullable(),
+                    messages::parent_message_id,
                 ))
                 .load::<MessageWithUser>(&mut conn)
                 .await
@@ -237,6 +239,13 @@ pub async fn get_chat_handler(
         user_attributes: creator_attributes,
     };
 
+    // Messages of every branch, oldest first
+    let tree: Vec<MessageNode> = messages
+        .iter()
+        .map(|msg| (msg.id, msg.parent_message_id))
+        .collect();
+    let active_message_id = active_leaf(&tree, chat_with_permission.chat.active_message_id);
+
     // Transform messages into ThreadMessage format
     let thread_messages: Vec<ChatMessage> = messages
         .into_iter()
@@ -280,6 +289,13 @@ pub async fn get_chat_handler(
                 msg.created_at,
                 msg.feedback,
             )
+            .with_branch(
+                msg.parent_message_id,
+                sibling_ids(&tree, msg.parent_message_id)
+                    .iter()
+                    .map(Uuid::to_string)
+                    .collect(),
+            )
         })
         .collect();
 
@@ -337,7 +353,7 @@ pub async fn get_chat_handler(
     };
 
     // Construct and return the ChatWithMessages with permissions
-    let chat = ChatWithMessages::new_with_messages(
+    let mut chat = ChatWithMessages::new_with_messages(
         thread.id,
         thread.title,
         thread_messages,
@@ -346,6 +362,7 @@ pub async fn get_chat_handler(
         thread.user_name.unwrap_or_else(|| "Unknown".to_string()),
         created_by_avatar,
     );
+    chat.set_active_branch(active_message_id);
 
     // Get the user's permission
     let user_permission = chat_with_permission.permission;
@@ -11,6 +11,11 @@ pub mod sharing;
 pub mod asset_messages;
 pub mod restore_chat_handler;
 pub mod duplicate_chat_handler;
+pub mod cancel_chat_handler;
+pub mod chat_stream;
+pub mod switch_chat_branch_handler;
+pub mod export;
+pub mod export_chat_handler;
 
 pub use get_chat_handler::get_chat_handler;
 pub use get_raw_llm_messages_handler::get_raw_llm_messages_handler;
@@ -20,6 +25,10 @@ pub use delete_chats_handler::delete_chats_handler;
 pub use list_chats_handler::list_chats_handler;
 pub use restore_chat_handler::restore_chat_handler;
 pub use duplicate_chat_handler::duplicate_chat_handler;
+pub use cancel_chat_handler::cancel_chat_handler;
+pub use chat_stream::resume_chat_stream_handler;
+pub use switch_chat_branch_handler::switch_chat_branch_handler;
+pub use export_chat_handler::{export_chat_handler, ChatExport, ChatExportFormat, ExportChatRequest};
 pub use sharing::delete_chat_sharing_handler;
 pub use sharing::create_chat_sharing_handler;
 pub use sharing::update_chat_sharing_handler;
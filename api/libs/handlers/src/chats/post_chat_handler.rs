@@ -7,6 +7,7 @@ use std::sync::Arc;
 
 use agents::{
     tools::{
+        custom_tools::{is_custom_tool, CustomToolOutput, CUSTOM_TOOL_PREFIX},
         file_tools::{
              create_dashboards::CreateDashboardFilesOutput,
             create_metrics::{CreateMetricFilesOutput}, // Alias to avoid name clash
@@ -15,13 +16,14 @@ use agents::{
         // Remove the old import
         // planning_tools::CreatePlanOutput,
     },
-    AgentExt, AgentMessage, AgentThread, BusterMultiAgent,
+    AgentExt, AgentMessage, AgentThread, BusterMultiAgent, SHUTDOWN_MESSAGE_ID,
 };
 
 use anyhow::{anyhow, Result};
 use chrono::Utc;
 use database::{
-    enums::{AssetPermissionRole, AssetType, IdentityType},
+    enums::{AssetPermissionRole, AssetType, IdentityType, ModelPurpose, WebhookEventType},
+    helpers::model_configs::get_user_models,
     models::{AssetPermission, Chat, Message, MessageToFile},
     pool::get_pg_pool,
     schema::{
@@ -35,11 +37,12 @@ use litellm::{
     Metadata, ToolCall,
 };
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
 use uuid::Uuid;
 
 use crate::chats::{
     asset_messages::{create_message_file_association, generate_asset_messages},
+    cancel_chat_handler::GenerationHandle,
     context_loaders::{
         chat_context::ChatContextLoader, create_asset_context_loader,
         dashboard_context::DashboardContextLoader, fetch_asset_details,
@@ -48,7 +51,9 @@ use crate::chats::{
     get_chat_handler,
     streaming_parser::StreamingParser,
 };
+use crate::llm_usage::ensure_within_llm_budget;
 use crate::messages::types::{ChatMessage, ChatUserMessage};
+use crate::webhooks::emit_webhook_event;
 
 use super::types::ChatWithMessages;
 use tokio::sync::mpsc;
@@ -73,7 +78,8 @@ struct CompletedFileInfo {
 }
 
 // Define ThreadEvent
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ThreadEvent {
     GeneratingResponseMessage,
     GeneratingReasoningMessage,
@@ -203,6 +209,12 @@ pub async fn post_chat_handler(
             return Err(anyhow!("User has no organization ID"));
         }
     };
+    // Organizations over their monthly LLM budget can keep existing chats going
+    // but can't start new ones
+    if request.chat_id.is_none() && request.prompt.is_some() {
+        ensure_within_llm_budget(&user_org_id).await?;
+    }
+
     let (chat_id, message_id, mut chat_with_messages) =
         initialize_chat(&request, &user, user_org_id).await?;
 
@@ -235,8 +247,11 @@ pub async fn post_chat_handler(
 
         // Add messages to chat and associate with chat_id
         let mut updated_messages = Vec::new();
+        let mut parent_message_id = None;
         for mut message in messages {
             message.chat_id = chat_id;
+            message.parent_message_id = parent_message_id;
+            parent_message_id = Some(message.id);
 
             // Insert message into database first
             let mut conn = get_pg_pool().get().await?;
@@ -302,7 +317,8 @@ pub async fn post_chat_handler(
                 None,
                 message.created_at,
                 None
-            );
+            )
+            .with_branch(message.parent_message_id, vec![message.id.to_string()]);
 
             chat_with_messages.add_message(chat_message);
 
@@ -311,6 +327,13 @@ pub async fn post_chat_handler(
             // when the agent is initialized and loads the context
         }
 
+        // The auto-generated messages replace the initial one as the active branch
+        let mut conn = get_pg_pool().get().await?;
+        diesel::update(chats::table.find(chat_id))
+            .set(chats::active_message_id.eq(parent_message_id))
+            .execute(&mut conn)
+            .await?;
+
         // Explicitly update the chat in the database with most_recent_file information
         // to ensure it behaves like files generated in a chat
         let asset_type_string = match asset_type_value {
@@ -348,10 +371,26 @@ pub async fn post_chat_handler(
     let is_follow_up = request.chat_id.is_some();
     // Create the agent and wrap it in Arc
     let agent = Arc::new(BusterMultiAgent::new(user.id, chat_id, is_follow_up).await?);
+    // Lets the agent attribute its LLM usage to this message
+    agent
+        .get_agent_arc()
+        .set_state_value(
+            "message_id".to_string(),
+            Value::String(message_id.to_string()),
+        )
+        .await;
+
+    // Lets clients stop the generation until it finishes
+    let generation = GenerationHandle::register(chat_id, message_id, agent.clone());
 
     // Load context if provided (combines both legacy and new asset references)
     if let Some(existing_chat_id) = request.chat_id {
-        let context_loader = ChatContextLoader::new(existing_chat_id);
+        // Only the branch the new message continues is loaded
+        let parent_message_id = chat_with_messages
+            .messages
+            .get(&message_id.to_string())
+            .and_then(|message| message.parent_message_id);
+        let context_loader = ChatContextLoader::new(existing_chat_id, parent_message_id);
         let context_messages = context_loader
             .load_context(&user, agent.get_agent_arc()) // Use get_agent_arc()
             .await?;
@@ -465,6 +504,12 @@ pub async fn post_chat_handler(
                 // --- End Update timestamp ---
                 break;
             }
+            // The agent's own interruption notice isn't shown when a user stopped it
+            Ok(AgentMessage::Assistant { id: Some(ref id), .. })
+                if id == SHUTDOWN_MESSAGE_ID && generation.is_cancelled() =>
+            {
+                continue;
+            }
             Ok(msg) => {
                 // Store the original message for file processing
                 all_messages.push(msg.clone());
@@ -776,32 +821,43 @@ pub async fn post_chat_handler(
         }
     }
 
+    let stopped = generation.is_cancelled();
+    drop(generation);
+    if stopped {
+        tracing::info!("Generation stopped for chat_id: {}, message_id: {}", chat_id, message_id);
+    }
+
     let title = title_handle.await??;
     // Calculate final duration based on when reasoning completed
-    let final_duration = reasoning_complete_time
-        .map(|t| t.duration_since(start_time))
-        .unwrap_or_else(|| {
-            // Fallback if reasoning_complete_time was never set (shouldn't happen in normal flow)
-            tracing::warn!("Reasoning complete time not captured, using total elapsed time.");
-            start_time.elapsed()
-        });
+    let final_duration = if stopped {
+        start_time.elapsed()
+    } else {
+        reasoning_complete_time
+            .map(|t| t.duration_since(start_time))
+            .unwrap_or_else(|| {
+                // Fallback if reasoning_complete_time was never set (shouldn't happen in normal flow)
+                tracing::warn!("Reasoning complete time not captured, using total elapsed time.");
+                start_time.elapsed()
+            })
+    };
 
     // Format the final reasoning duration
+    let verb = if stopped { "Stopped after" } else { "Reasoned for" };
     let formatted_final_reasoning_duration = if final_duration.as_secs() < 60 {
-        format!("Reasoned for {} seconds", final_duration.as_secs())
+        format!("{} {} seconds", verb, final_duration.as_secs())
     } else {
         let minutes = final_duration.as_secs() / 60;
         if minutes == 1 {
-            "Reasoned for 1 minute".to_string() // Singular minute
+            format!("{} 1 minute", verb) // Singular minute
         } else {
-            format!("Reasoned for {} min", minutes) // Plural minutes (abbreviated)
+            format!("{} {} min", verb, minutes) // Plural minutes (abbreviated)
         }
     };
 
     // Transform all messages for final storage
     // Get initial response messages (text, etc.) and reasoning messages separately
     let (mut text_and_other_response_messages, reasoning_messages) =
-        prepare_final_message_state(&all_transformed_containers)?;
+        prepare_final_message_state(&all_transformed_containers, stopped)?;
 
     // Create the final response message list: Start with filtered files, then add text/other messages
     // Use the file messages that were generated and sent early
@@ -838,6 +894,18 @@ pub async fn post_chat_handler(
 
     final_response_messages.append(&mut text_and_other_response_messages);
 
+    // Keep the branch the message was started on
+    let (parent_message_id, sibling_message_ids) = chat_with_messages
+        .messages
+        .get(&message_id.to_string())
+        .map(|message| {
+            (
+                message.parent_message_id,
+                message.sibling_message_ids.clone(),
+            )
+        })
+        .unwrap_or_default();
+
     // Update chat_with_messages with final state (now including filtered files first)
     let final_message = ChatMessage::new_with_messages(
         message_id,
@@ -852,7 +920,8 @@ pub async fn post_chat_handler(
         Some(formatted_final_reasoning_duration.clone()), // Use formatted reasoning duration
         Utc::now(),
         None,
-    );
+    )
+    .with_branch(parent_message_id, sibling_message_ids);
 
     chat_with_messages.update_message(final_message);
 
@@ -871,6 +940,7 @@ pub async fn post_chat_handler(
         title: title.title.clone().unwrap_or_default(),
         raw_llm_messages: serde_json::to_value(&raw_llm_messages)?,
         feedback: None,
+        parent_message_id,
     };
 
     let mut conn = get_pg_pool().get().await?;
@@ -881,6 +951,13 @@ pub async fn post_chat_handler(
         .execute(&mut conn)
         .await?;
 
+    // The new message ends the chat's active branch
+    diesel::update(chats::table)
+        .filter(chats::id.eq(chat_id))
+        .set(chats::active_message_id.eq(Some(message_id)))
+        .execute(&mut conn)
+        .await?;
+
     // First process completed files (database updates only)
     // Use a separate connection scope to ensure prompt release
     {
@@ -926,6 +1003,17 @@ pub async fn post_chat_handler(
         chat_with_messages.title = title.clone();
     }
 
+    emit_webhook_event(
+        user_org_id,
+        WebhookEventType::ChatCompleted,
+        json!({
+            "id": chat_id,
+            "message_id": message_id,
+            "title": chat_with_messages.title,
+            "actor_id": user.id,
+        }),
+    );
+
     // Send final completed state
     if let Some(tx) = &tx {
         tx.send(Ok((
@@ -940,8 +1028,16 @@ pub async fn post_chat_handler(
 }
 
 /// Prepares the final message state from transformed containers
-fn prepare_final_message_state(containers: &[BusterContainer]) -> Result<(Vec<Value>, Vec<Value>)> {
+///
+/// When the generation was `stopped`, text still streaming is kept as it was
+/// when it stopped and unfinished reasoning is marked "stopped".
+fn prepare_final_message_state(
+    containers: &[BusterContainer],
+    stopped: bool,
+) -> Result<(Vec<Value>, Vec<Value>)> {
     let mut response_messages = Vec::new();
+    // Chunks of text messages that never completed, in order of first chunk
+    let mut partial_texts: Vec<(String, String, Option<String>)> = Vec::new();
     // Use a Vec to maintain order, with a HashMap to track latest version of each message
     let mut reasoning_map: std::collections::HashMap<String, (usize, Value)> =
         std::collections::HashMap::new();
@@ -953,14 +1049,29 @@ fn prepare_final_message_state(containers: &[BusterContainer]) -> Result<(Vec<Va
                 // For text messages, only include complete ones (message present, chunk absent)
                 match &chat.response_message {
                     BusterChatMessage::Text {
+                        id,
                         message,
                         message_chunk,
+                        originating_tool_name,
                         ..
                     } => {
                         if message.is_some() && message_chunk.is_none() {
+                            partial_texts.retain(|(partial_id, _, _)| partial_id != id);
                             if let Ok(value) = serde_json::to_value(&chat.response_message) {
                                 response_messages.push(value);
                             }
+                        } else if let (true, Some(chunk)) = (stopped, message_chunk) {
+                            match partial_texts
+                                .iter_mut()
+                                .find(|(partial_id, _, _)| partial_id == id)
+                            {
+                                Some((_, text, _)) => text.push_str(chunk),
+                                None => partial_texts.push((
+                                    id.clone(),
+                                    chunk.clone(),
+                                    originating_tool_name.clone(),
+                                )),
+                            }
                         }
                     }
                     // For non-text messages (like files), keep existing behavior
@@ -972,8 +1083,9 @@ fn prepare_final_message_state(containers: &[BusterContainer]) -> Result<(Vec<Va
                 }
             }
             BusterContainer::ReasoningMessage(reasoning) => {
-                // Only include reasoning messages that are explicitly marked as completed
-                let should_include = match &reasoning.reasoning {
+                // Only include reasoning messages that are explicitly marked as completed,
+                // or everything the agent got to when it was stopped
+                let completed = match &reasoning.reasoning {
                     BusterReasoningMessage::Pill(thought) => thought.status == "completed",
                     BusterReasoningMessage::File(file) => file.status == "completed",
                     BusterReasoningMessage::Text(text) => {
@@ -981,8 +1093,11 @@ fn prepare_final_message_state(containers: &[BusterContainer]) -> Result<(Vec<Va
                     }
                 };
 
-                if should_include {
-                    if let Ok(value) = serde_json::to_value(&reasoning.reasoning) {
+                if completed || stopped {
+                    if let Ok(mut value) = serde_json::to_value(&reasoning.reasoning) {
+                        if !completed {
+                            value["status"] = Value::String("stopped".to_string());
+                        }
                         // Get the ID from the reasoning message
                         let id = match &reasoning.reasoning {
                             BusterReasoningMessage::Pill(thought) => thought.id.clone(),
@@ -1015,6 +1130,19 @@ fn prepare_final_message_state(containers: &[BusterContainer]) -> Result<(Vec<Va
     // Remove any null values (shouldn't happen, but just in case)
     reasoning_messages.retain(|v| !v.is_null());
 
+    for (id, text, originating_tool_name) in partial_texts {
+        let partial = BusterChatMessage::Text {
+            id,
+            message: Some(text),
+            message_chunk: None,
+            is_final_message: Some(true),
+            originating_tool_name,
+        };
+        if let Ok(value) = serde_json::to_value(&partial) {
+            response_messages.push(value);
+        }
+    }
+
     Ok((response_messages, reasoning_messages))
 }
 
@@ -1023,8 +1151,8 @@ async fn process_completed_files(
     conn: &mut diesel_async::AsyncPgConnection,
     message: &Message,
     messages: &[AgentMessage], // Use original AgentMessages for context if needed
-    _organization_id: &Uuid,
-    _user_id: &Uuid,
+    organization_id: &Uuid,
+    user_id: &Uuid,
     chunk_tracker: &ChunkTracker, // Pass tracker if needed for transforming messages again
     start_time: &Instant, // Add start_time parameter
     last_reasoning_completion_time: &mut Instant, // Add last_reasoning_completion_time parameter
@@ -1105,6 +1233,30 @@ async fn process_completed_files(
                                 continue; // Skip chat update if DB link fails
                             }
 
+                            // Files the agent wrote are new at version 1
+                            let event_type = match (
+                                file_content.file_type.as_str(),
+                                file_content.version_number,
+                            ) {
+                                ("metric", 1) => Some(WebhookEventType::MetricCreated),
+                                ("metric", _) => Some(WebhookEventType::MetricUpdated),
+                                ("dashboard", 1) => Some(WebhookEventType::DashboardCreated),
+                                _ => None,
+                            };
+                            if let Some(event_type) = event_type {
+                                emit_webhook_event(
+                                    *organization_id,
+                                    event_type,
+                                    json!({
+                                        "id": file_uuid,
+                                        "name": file_content.file_name,
+                                        "version_number": file_content.version_number,
+                                        "chat_id": message.chat_id,
+                                        "actor_id": user_id,
+                                    }),
+                                );
+                            }
+
                             // Determine file type for chat update
                             let file_type_for_chat = match file_content.file_type.as_str() {
                                 "dashboard" => Some("dashboard".to_string()),
@@ -1548,6 +1700,8 @@ fn transform_tool_message(
         "update_dashboards" => tool_modify_dashboards(id.clone(), content, delta_duration)?,
         // Handle both new plan tools here - pass duration
         "create_plan_straightforward" | "create_plan_investigative" => vec![],
+        // Organization-registered tools share one generic rendering
+        name if is_custom_tool(name) => tool_custom(id.clone(), content, delta_duration)?,
         _ => vec![],
     };
 
@@ -2001,6 +2155,47 @@ fn tool_modify_dashboards(id: String, content: String, delta_duration: Duration)
 }
 
 // Restore the original tool_data_catalog_search function
+/// Longest custom tool result shown in a reasoning message
+const MAX_CUSTOM_TOOL_REASONING_CHARS: usize = 2_000;
+
+fn tool_custom(id: String, content: String, delta_duration: Duration) -> Result<Vec<BusterReasoningMessage>> {
+    let output = serde_json::from_str::<CustomToolOutput>(&content)
+        .map_err(|e| anyhow!("Failed to parse CustomToolOutput: {}", e))?;
+
+    let (title, message, status) = if output.success {
+        let result = match output.result {
+            Some(Value::String(text)) => text,
+            Some(value) => format!(
+                "```json\n{}\n```",
+                serde_json::to_string_pretty(&value).unwrap_or_default()
+            ),
+            None => String::new(),
+        };
+        let result = match result.char_indices().nth(MAX_CUSTOM_TOOL_REASONING_CHARS) {
+            Some((end, _)) => format!("{}…", &result[..end]),
+            None => result,
+        };
+        (format!("Used {}", output.tool), result, "completed")
+    } else {
+        (
+            format!("{} failed", output.tool),
+            output.error.unwrap_or_default(),
+            "failed",
+        )
+    };
+
+    Ok(vec![BusterReasoningMessage::Text(BusterReasoningText {
+        id,
+        reasoning_type: "text".to_string(),
+        title,
+        secondary_title: format!("{} seconds", delta_duration.as_secs()),
+        message: if message.is_empty() { None } else { Some(message) },
+        message_chunk: None,
+        status: Some(status.to_string()),
+        finished_reasoning: false,
+    })])
+}
+
 fn tool_data_catalog_search(id: String, content: String, delta_duration: Duration) -> Result<Vec<BusterReasoningMessage>> {
     let data_catalog_result = match serde_json::from_str::<SearchDataCatalogOutput>(&content) {
         Ok(result) => result,
@@ -2059,31 +2254,66 @@ fn tool_data_catalog_search(id: String, content: String, delta_duration: Duratio
 fn proccess_data_catalog_search_results(
     results: SearchDataCatalogOutput,
 ) -> Result<Vec<BusterThoughtPillContainer>> {
-    if results.results.is_empty() {
+    if results.results.is_empty() && results.terms.is_empty() && results.examples.is_empty() {
         return Ok(vec![BusterThoughtPillContainer {
             title: "No results found".to_string(),
             pills: vec![],
         }]);
     }
 
-    let mut dataset_pills = Vec::new();
+    let mut containers = Vec::new();
+
+    if !results.results.is_empty() {
+        let mut dataset_pills = Vec::new();
 
-    // Create a pill for each result
-    for result in results.results {
-        dataset_pills.push(BusterThoughtPill {
-            id: result.id.to_string(),
-            text: result.name.clone().unwrap_or_default(),
-            thought_file_type: "dataset".to_string(), // Set type to "dataset" for all pills
+        // Create a pill for each result
+        for result in results.results {
+            dataset_pills.push(BusterThoughtPill {
+                id: result.id.to_string(),
+                text: result.name.clone().unwrap_or_default(),
+                thought_file_type: "dataset".to_string(), // Set type to "dataset" for all pills
+            });
+        }
+
+        // Create a single container with all dataset pills
+        containers.push(BusterThoughtPillContainer {
+            title: String::from("Datasets"),
+            pills: dataset_pills,
         });
     }
 
-    // Create a single container with all dataset pills
-    let container = BusterThoughtPillContainer {
-        title: String::from("Datasets"),
-        pills: dataset_pills,
-    };
+    // Glossary terms the agent was given, so users can see which definitions it used
+    if !results.terms.is_empty() {
+        containers.push(BusterThoughtPillContainer {
+            title: String::from("Knowledge base"),
+            pills: results
+                .terms
+                .into_iter()
+                .map(|term| BusterThoughtPill {
+                    id: term.id.to_string(),
+                    text: term.name,
+                    thought_file_type: "term".to_string(),
+                })
+                .collect(),
+        });
+    }
+
+    if !results.examples.is_empty() {
+        containers.push(BusterThoughtPillContainer {
+            title: String::from("Verified examples"),
+            pills: results
+                .examples
+                .into_iter()
+                .map(|example| BusterThoughtPill {
+                    id: example.id.to_string(),
+                    text: example.question,
+                    thought_file_type: "verified_example".to_string(),
+                })
+                .collect(),
+        });
+    }
 
-    Ok(vec![container])
+    Ok(containers)
 }
 
 fn transform_assistant_tool_message(
@@ -2143,7 +2373,7 @@ fn transform_assistant_tool_message(
                         }));
                     }
                 }
-                if progress == MessageProgress::Complete {
+                if matches!(progress, MessageProgress::Complete) {
                     // --- MODIFICATION START ---
                     // Attempt to parse final arguments directly from the complete chunk
                     #[derive(Deserialize)]
@@ -2596,6 +2826,27 @@ fn transform_assistant_tool_message(
                 }
                  tracker.clear_chunk(tool_id.clone());
             }
+            name if is_custom_tool(name) => {
+                // Show the call as in progress once its arguments are complete;
+                // the tool result replaces it under the same id
+                if progress == MessageProgress::Complete {
+                    all_results.push(ToolTransformResult::Reasoning(BusterReasoningMessage::Text(
+                        BusterReasoningText {
+                            id: tool_id.clone(),
+                            reasoning_type: "text".to_string(),
+                            title: format!(
+                                "Calling {}...",
+                                name.trim_start_matches(CUSTOM_TOOL_PREFIX)
+                            ),
+                            secondary_title: "".to_string(),
+                            message: None,
+                            message_chunk: None,
+                            status: Some("loading".to_string()),
+                            finished_reasoning: false,
+                        },
+                    )));
+                }
+            }
             _ => {
                 // Log unhandled tools
                 tracing::warn!(
@@ -2710,11 +2961,12 @@ pub async fn generate_conversation_title(
     let prompt = TITLE_GENERATION_PROMPT.replace("{conversation_messages}", &formatted_messages);
 
     // Set up LiteLLM client
-    let llm_client = LiteLLMClient::new(None, None);
+    let models = get_user_models(user_id).await?;
+    let llm_client = LiteLLMClient::new(None, None).with_approved_fallbacks(&models.approved_models());
 
     // Create the request
     let request = ChatCompletionRequest {
-        model: "gemini-2.0-flash-001".to_string(),
+        model: models.model_for(ModelPurpose::TitleGeneration),
         messages: vec![LiteLLMAgentMessage::User {
             id: None,
             content: prompt,
@@ -2725,6 +2977,8 @@ pub async fn generate_conversation_title(
             user_id: user_id.to_string(),
             session_id: session_id.to_string(),
             trace_id: session_id.to_string(),
+            message_id: Some(message_id.to_string()),
+            organization_id: models.organization_id().map(|id| id.to_string()),
         }),
         ..Default::default()
     };
@@ -2813,63 +3067,32 @@ async fn initialize_chat(
     let prompt_text = request.prompt.clone().unwrap_or_default();
 
     if let Some(existing_chat_id) = request.chat_id {
-        // --- START: Added logic for message_id presence ---
-        if let Some(target_message_id) = request.message_id {
-            // Use target_message_id (from the request) for deletion logic
-            let mut conn = get_pg_pool().get().await?;
-
-            // Fetch the created_at timestamp of the target message
-            let target_message_created_at = messages::table
-                .filter(messages::id.eq(target_message_id))
-                .select(messages::created_at)
-                .first::<chrono::NaiveDateTime>(&mut conn)
-                .await
-                .optional()?; // Use optional in case the message doesn't exist
-
-            if let Some(created_at_ts) = target_message_created_at {
-                // Mark subsequent messages as deleted
-                let update_result = diesel::update(messages::table)
-                    .filter(messages::chat_id.eq(existing_chat_id))
-                    .filter(messages::created_at.ge(created_at_ts))
-                    .set(messages::deleted_at.eq(Some(Utc::now().naive_utc()))) // Use naive_utc() for NaiveDateTime
-                    .execute(&mut conn)
-                    .await;
-
-                match update_result {
-                    Ok(num_updated) => {
-                        tracing::info!(
-                            "Marked {} messages as deleted for chat {} starting from message {}",
-                            num_updated,
-                            existing_chat_id,
-                            target_message_id
-                        );
-                    }
-                    Err(e) => {
-                        tracing::error!(
-                            "Failed to mark messages as deleted for chat {}: {}",
-                            existing_chat_id,
-                            e
-                        );
-                        // Propagate the error or handle appropriately
-                        return Err(anyhow!("Failed to update messages: {}", e));
-                    }
-                }
-            } else {
-                // Handle case where the target_message_id doesn't exist
-                tracing::warn!(
-                    "Target message_id {} not found for chat {}, proceeding without deleting messages.",
-                    target_message_id,
-                    existing_chat_id
-                );
-                // Potentially return an error or proceed based on desired behavior
-            }
-        }
-        // --- END: Added logic for message_id presence ---
+        let mut existing_chat = get_chat_handler(&existing_chat_id, &user, true).await?;
 
+        // Editing an earlier prompt starts a sibling branch from the message
+        // before it, leaving the original branch in place
+        let parent_message_id = match request.message_id {
+            Some(target_message_id) => existing_chat
+                .messages
+                .get(&target_message_id.to_string())
+                .ok_or_else(|| anyhow!("Message not found in this chat"))?
+                .parent_message_id,
+            None => existing_chat
+                .message_ids
+                .last()
+                .and_then(|id| Uuid::parse_str(id).ok()),
+        };
+        existing_chat.set_active_branch(parent_message_id);
 
-        // Get existing chat - no need to create new chat in DB
-        // This now fetches the chat *after* potential deletions
-        let mut existing_chat = get_chat_handler(&existing_chat_id, &user, true).await?;
+        let mut siblings: Vec<&ChatMessage> = existing_chat
+            .messages
+            .values()
+            .filter(|message| message.parent_message_id == parent_message_id)
+            .collect();
+        siblings.sort_by_key(|message| message.created_at);
+        let mut sibling_message_ids: Vec<String> =
+            siblings.iter().map(|message| message.id.to_string()).collect();
+        sibling_message_ids.push(new_message_id.to_string());
 
         // Create new message using the *new* message ID
         let message = ChatMessage::new_with_messages(
@@ -2885,7 +3108,8 @@ async fn initialize_chat(
             None,
             Utc::now(),
             None,
-        );
+        )
+        .with_branch(parent_message_id, sibling_message_ids);
 
         // Add message to existing chat
         existing_chat.add_message(message);
@@ -2909,6 +3133,7 @@ async fn initialize_chat(
             most_recent_file_id: None,
             most_recent_file_type: None,
             most_recent_version_number: None,
+            active_message_id: Some(new_message_id),
         };
 
         // Create initial message using the *new* message ID
@@ -2,6 +2,7 @@ use anyhow::{anyhow, Result};
 use chrono::Utc;
 use database::{
     enums::AssetType,
+    helpers::chats::{active_leaf, fetch_chat_message_tree},
     models::{Message, MessageToFile},
     pool::get_pg_pool,
     schema::{chats, dashboard_files, messages, messages_to_files, metric_files},
@@ -44,13 +45,13 @@ pub struct ChatRestoreRequest {
 /// # Process
 /// 1. Concurrently:
 ///    a. Restores the specified asset version using the appropriate handler
-///    b. Fetches the most recent message from the chat (to copy raw_llm_messages)
+///    b. Fetches the last message of the chat's active branch (to copy raw_llm_messages)
 /// 2. Waits for restoration and fetch to complete.
 /// 3. Constructs new message details (text, file link, raw_llm_messages).
 /// 4. Concurrently:
 ///    a. Inserts the new message documenting the restoration
 ///    b. Inserts the message-to-file association
-///    c. Updates the chat record with the latest file info
+///    c. Updates the chat record with the latest file info and active message
 /// 5. Waits for insertions and update to complete.
 /// 6. Returns the updated chat with all messages
 pub async fn restore_chat_handler(
@@ -110,17 +111,23 @@ pub async fn restore_chat_handler(
         Ok::<_, anyhow::Error>((file_type, file_name, file_id, version_number))
     });
 
-    // Task 2: Get the most recent message to copy raw_llm_messages
+    // Task 2: Get the last message of the active branch to copy raw_llm_messages
     let last_message_task = tokio::spawn(async move {
         let mut conn = get_pg_pool().get().await?;
-        let last_message = messages::table
-            .filter(messages::chat_id.eq(&chat_id_clone1))
-            .filter(messages::deleted_at.is_null())
-            .limit(1)
-            .order_by(messages::created_at.desc())
-            .first::<Message>(&mut conn) // Assuming Message derives Clone
-            .await
-            .ok();
+        let active_message_id = chats::table
+            .filter(chats::id.eq(&chat_id_clone1))
+            .select(chats::active_message_id)
+            .first::<Option<Uuid>>(&mut conn)
+            .await?;
+        let tree = fetch_chat_message_tree(&chat_id_clone1).await?;
+        let last_message = match active_leaf(&tree, active_message_id) {
+            Some(leaf) => messages::table
+                .filter(messages::id.eq(leaf))
+                .first::<Message>(&mut conn) // Assuming Message derives Clone
+                .await
+                .ok(),
+            None => None,
+        };
         // Explicitly type the Ok variant
         Ok::<_, anyhow::Error>(last_message)
     });
@@ -247,6 +254,7 @@ pub async fn restore_chat_handler(
         deleted_at: None,
         created_by: user.id,
         feedback: None,
+        parent_message_id: last_message.as_ref().map(|message| message.id),
     };
 
     // Create MessageToFile object - requires MessageToFile to be Clone if used in multiple tasks
@@ -297,6 +305,7 @@ pub async fn restore_chat_handler(
                 chats::most_recent_version_number.eq(Some(version_number)), // version_number is Copy
                 chats::most_recent_file_type.eq(Some(request_asset_type_clone.to_string())),
                 chats::updated_at.eq(now), // now is Copy
+                chats::active_message_id.eq(Some(message_id)),
             ))
             .execute(&mut conn)
             .await?;
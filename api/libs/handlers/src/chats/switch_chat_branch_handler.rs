@@ -0,0 +1,73 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use database::{
+    enums::AssetPermissionRole,
+    helpers::chats::{fetch_chat_message_tree, fetch_chat_with_permission, latest_descendant},
+    pool::get_pg_pool,
+    schema::chats,
+};
+use diesel::ExpressionMethods;
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use serde::{Deserialize, Serialize};
+use sharing::check_permission_access;
+use uuid::Uuid;
+
+use crate::chats::{get_chat_handler, ChatWithMessages};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SwitchChatBranchRequest {
+    /// Any message of the branch to show, usually one of a message's
+    /// `sibling_message_ids`
+    pub message_id: Uuid,
+}
+
+/// Makes the branch through a message the chat's active branch
+///
+/// The branch is followed from the message down to its most recent reply, so
+/// switching to an edited prompt shows the conversation that continued from
+/// it. Follow-up messages are added to the end of the active branch.
+///
+/// Requires edit access to the chat.
+pub async fn switch_chat_branch_handler(
+    chat_id: &Uuid,
+    request: SwitchChatBranchRequest,
+    user: &AuthenticatedUser,
+) -> Result<ChatWithMessages> {
+    let chat_with_permission = fetch_chat_with_permission(chat_id, &user.id)
+        .await?
+        .ok_or_else(|| anyhow!("Chat not found"))?;
+
+    let is_creator = chat_with_permission.chat.created_by == user.id;
+    let has_permission = check_permission_access(
+        chat_with_permission.permission,
+        &[
+            AssetPermissionRole::CanEdit,
+            AssetPermissionRole::FullAccess,
+            AssetPermissionRole::Owner,
+        ],
+        chat_with_permission.chat.organization_id,
+        &user.organizations,
+    );
+    if !is_creator && !has_permission {
+        return Err(anyhow!("You don't have permission to edit this chat"));
+    }
+
+    let tree = fetch_chat_message_tree(chat_id).await?;
+    if !tree.iter().any(|(id, _)| *id == request.message_id) {
+        return Err(anyhow!("Message not found in this chat"));
+    }
+    let leaf = latest_descendant(&tree, request.message_id);
+
+    let mut conn = get_pg_pool().get().await?;
+    diesel::update(chats::table)
+        .filter(chats::id.eq(chat_id))
+        .set((
+            chats::active_message_id.eq(Some(leaf)),
+            chats::updated_at.eq(Utc::now()),
+        ))
+        .execute(&mut conn)
+        .await?;
+
+    get_chat_handler(chat_id, user, false).await
+}
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use database::enums::AssetPermissionRole;
+use database::helpers::chats::{branch_path, MessageNode};
 use chrono::{DateTime, Utc};
 
 use crate::messages::types::ChatMessage;
@@ -19,7 +20,9 @@ pub struct ChatWithMessages {
     pub id: Uuid,
     pub title: String,
     pub is_favorited: bool,
+    /// The messages of the active branch, oldest first
     pub message_ids: Vec<String>,
+    /// Every message of the chat, including those on other branches
     pub messages: HashMap<String, ChatMessage>,
     pub created_at: String,
     pub updated_at: String,
@@ -135,6 +138,25 @@ impl ChatWithMessages {
         self.updated_at = chrono::Utc::now().to_rfc3339();
     }
 
+    /// Makes the branch ending at `leaf` the active one
+    ///
+    /// With no leaf the active branch is empty, as when the first message of
+    /// the chat is edited.
+    pub fn set_active_branch(&mut self, leaf: Option<Uuid>) {
+        let tree: Vec<MessageNode> = self
+            .messages
+            .values()
+            .map(|message| (message.id, message.parent_message_id))
+            .collect();
+
+        self.message_ids = leaf
+            .map(|leaf| branch_path(&tree, leaf))
+            .unwrap_or_default()
+            .iter()
+            .map(Uuid::to_string)
+            .collect();
+    }
+
     pub fn update_message(&mut self, message: ChatMessage) {
         let message_id = message.id.to_string();
         if !self.message_ids.contains(&message_id) {
@@ -0,0 +1,80 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use database::{
+    enums::AssetType, models::AssetComment, pool::get_pg_pool, schema::asset_comments,
+};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use super::helpers::{
+    comment_to_response, fetch_commentable_asset, fetch_comment_users, notify_mentions,
+    resolve_mentions,
+};
+use super::types::{AssetCommentResponse, CreateCommentRequest};
+
+/// Handler to comment on a metric or dashboard, optionally as a reply
+///
+/// Anyone who can view the asset can comment. `@user@example.com` mentions of
+/// organization members are stored with the comment and emailed.
+pub async fn create_comment_handler(
+    asset_id: &Uuid,
+    asset_type: AssetType,
+    user: &AuthenticatedUser,
+    request: CreateCommentRequest,
+) -> Result<AssetCommentResponse> {
+    let content = request.content.trim().to_string();
+    if content.is_empty() {
+        return Err(anyhow!("Comment cannot be empty"));
+    }
+
+    let asset = fetch_commentable_asset(asset_id, asset_type, user).await?;
+
+    let mut conn = get_pg_pool().get().await?;
+
+    if let Some(parent_id) = request.parent_id {
+        let parent_exists = asset_comments::table
+            .filter(asset_comments::id.eq(parent_id))
+            .filter(asset_comments::asset_id.eq(asset_id))
+            .filter(asset_comments::asset_type.eq(asset_type))
+            .filter(asset_comments::deleted_at.is_null())
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await?;
+        if parent_exists == 0 {
+            return Err(anyhow!("Parent comment not found"));
+        }
+    }
+
+    let mentions = resolve_mentions(&content, &asset.organization_id).await?;
+    let now = Utc::now();
+
+    let comment = AssetComment {
+        id: Uuid::new_v4(),
+        asset_id: *asset_id,
+        asset_type,
+        organization_id: asset.organization_id,
+        parent_id: request.parent_id,
+        content,
+        mentions,
+        created_by: user.id,
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+    };
+
+    diesel::insert_into(asset_comments::table)
+        .values(&comment)
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to create comment: {}", e))?;
+
+    notify_mentions(&asset, user, &comment.content, &comment.mentions).await;
+
+    let mut user_ids = comment.mentions.clone();
+    user_ids.push(user.id);
+    let users = fetch_comment_users(&user_ids).await?;
+
+    Ok(comment_to_response(comment, &users))
+}
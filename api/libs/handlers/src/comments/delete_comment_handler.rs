@@ -0,0 +1,60 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use database::{
+    enums::{AssetType, UserOrganizationRole},
+    models::AssetComment,
+    pool::get_pg_pool,
+    schema::asset_comments,
+};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use super::helpers::fetch_commentable_asset;
+
+/// Handler to delete a comment. The author and workspace/data admins can
+/// delete; replies stay visible under a placeholder.
+pub async fn delete_comment_handler(
+    asset_id: &Uuid,
+    asset_type: AssetType,
+    comment_id: &Uuid,
+    user: &AuthenticatedUser,
+) -> Result<()> {
+    let asset = fetch_commentable_asset(asset_id, asset_type, user).await?;
+
+    let mut conn = get_pg_pool().get().await?;
+
+    let existing = asset_comments::table
+        .filter(asset_comments::id.eq(comment_id))
+        .filter(asset_comments::asset_id.eq(asset_id))
+        .filter(asset_comments::asset_type.eq(asset_type))
+        .filter(asset_comments::deleted_at.is_null())
+        .first::<AssetComment>(&mut conn)
+        .await
+        .map_err(|e| match e {
+            diesel::NotFound => anyhow!("Comment not found"),
+            _ => anyhow!("Failed to load comment: {}", e),
+        })?;
+
+    let is_admin = user.organizations.iter().any(|org| {
+        org.id == asset.organization_id
+            && matches!(
+                org.role,
+                UserOrganizationRole::WorkspaceAdmin | UserOrganizationRole::DataAdmin
+            )
+    });
+
+    if existing.created_by != user.id && !is_admin {
+        return Err(anyhow!("You don't have permission to delete this comment"));
+    }
+
+    diesel::update(asset_comments::table)
+        .filter(asset_comments::id.eq(comment_id))
+        .set(asset_comments::deleted_at.eq(Some(Utc::now())))
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to delete comment: {}", e))?;
+
+    Ok(())
+}
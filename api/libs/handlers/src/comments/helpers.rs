@@ -0,0 +1,331 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use anyhow::{anyhow, Result};
+use database::{
+    enums::AssetType,
+    helpers::{dashboard_files::fetch_dashboard_file, metric_files::fetch_metric_file},
+    models::AssetComment,
+    pool::get_pg_pool,
+    schema::{users, users_to_organizations},
+};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use email::{send_email, CommentMention, EmailType};
+use middleware::AuthenticatedUser;
+use regex::Regex;
+use sharing::find_user_by_email;
+use uuid::Uuid;
+
+use super::types::{AssetCommentResponse, CommentUser};
+use crate::dashboards::get_dashboard_handler;
+use crate::metrics::get_metric_handler;
+
+static MENTION_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|[^\w@])@([A-Za-z0-9._%+\-]+@[A-Za-z0-9.\-]+\.[A-Za-z]{2,})").unwrap()
+});
+
+/// The commentable asset a user has view access to
+pub struct CommentableAsset {
+    pub id: Uuid,
+    pub asset_type: AssetType,
+    pub name: String,
+    pub organization_id: Uuid,
+}
+
+impl CommentableAsset {
+    pub fn kind(&self) -> &'static str {
+        match self.asset_type {
+            AssetType::DashboardFile => "dashboard",
+            _ => "metric",
+        }
+    }
+}
+
+/// Loads a metric or dashboard, enforcing the same access rules as viewing it
+pub async fn fetch_commentable_asset(
+    asset_id: &Uuid,
+    asset_type: AssetType,
+    user: &AuthenticatedUser,
+) -> Result<CommentableAsset> {
+    match asset_type {
+        AssetType::MetricFile => {
+            get_metric_handler(asset_id, user, None, None).await?;
+            let metric_file = fetch_metric_file(asset_id)
+                .await?
+                .ok_or_else(|| anyhow!("Metric file not found"))?;
+            Ok(CommentableAsset {
+                id: metric_file.id,
+                asset_type,
+                name: metric_file.name,
+                organization_id: metric_file.organization_id,
+            })
+        }
+        AssetType::DashboardFile => {
+            get_dashboard_handler(asset_id, user, None, None).await?;
+            let dashboard_file = fetch_dashboard_file(asset_id)
+                .await?
+                .ok_or_else(|| anyhow!("Dashboard not found"))?;
+            Ok(CommentableAsset {
+                id: dashboard_file.id,
+                asset_type,
+                name: dashboard_file.name,
+                organization_id: dashboard_file.organization_id,
+            })
+        }
+        _ => Err(anyhow!(
+            "Comments are only supported on metrics and dashboards"
+        )),
+    }
+}
+
+/// Extracts the emails of `@user@example.com` style mentions, in order of
+/// first appearance and without duplicates
+pub fn extract_mention_emails(content: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    MENTION_REGEX
+        .captures_iter(content)
+        .filter_map(|captures| captures.get(1))
+        .map(|m| m.as_str().trim_end_matches('.').to_lowercase())
+        .filter(|email| seen.insert(email.clone()))
+        .collect()
+}
+
+/// Resolves mentioned emails to users of the asset's organization. Unknown
+/// emails and users outside the organization are ignored.
+pub async fn resolve_mentions(content: &str, organization_id: &Uuid) -> Result<Vec<Uuid>> {
+    let mut candidates = Vec::new();
+    for email in extract_mention_emails(content) {
+        if let Some(user) = find_user_by_email(&email).await? {
+            candidates.push(user.id);
+        }
+    }
+
+    if candidates.is_empty() {
+        return Ok(candidates);
+    }
+
+    let mut conn = get_pg_pool().get().await?;
+    let members: HashSet<Uuid> = users_to_organizations::table
+        .filter(users_to_organizations::organization_id.eq(organization_id))
+        .filter(users_to_organizations::user_id.eq_any(&candidates))
+        .filter(users_to_organizations::deleted_at.is_null())
+        .select(users_to_organizations::user_id)
+        .load::<Uuid>(&mut conn)
+        .await?
+        .into_iter()
+        .collect();
+
+    Ok(candidates
+        .into_iter()
+        .filter(|id| members.contains(id))
+        .collect())
+}
+
+pub async fn fetch_comment_users(user_ids: &[Uuid]) -> Result<HashMap<Uuid, CommentUser>> {
+    if user_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut conn = get_pg_pool().get().await?;
+
+    let users = users::table
+        .filter(users::id.eq_any(user_ids))
+        .select((users::id, users::name, users::email, users::avatar_url))
+        .load::<(Uuid, Option<String>, String, Option<String>)>(&mut conn)
+        .await?
+        .into_iter()
+        .map(|(id, name, email, avatar_url)| {
+            (
+                id,
+                CommentUser {
+                    id,
+                    name,
+                    email,
+                    avatar_url,
+                },
+            )
+        })
+        .collect();
+
+    Ok(users)
+}
+
+/// Emails newly mentioned users. Failures are logged rather than returned so
+/// the comment itself is never lost to a mail outage.
+pub async fn notify_mentions(
+    asset: &CommentableAsset,
+    author: &AuthenticatedUser,
+    content: &str,
+    mentioned_user_ids: &[Uuid],
+) {
+    let recipients: Vec<Uuid> = mentioned_user_ids
+        .iter()
+        .copied()
+        .filter(|id| *id != author.id)
+        .collect();
+
+    let emails: HashSet<String> = match fetch_comment_users(&recipients).await {
+        Ok(users) => users.into_values().map(|user| user.email).collect(),
+        Err(e) => {
+            tracing::error!(error = %e, asset_id = %asset.id, "Failed to resolve mentioned users");
+            return;
+        }
+    };
+
+    if emails.is_empty() {
+        return;
+    }
+
+    let mention = CommentMention {
+        asset_name: asset.name.clone(),
+        asset_id: asset.id,
+        asset_kind: asset.kind(),
+        author_name: author.name.clone().unwrap_or_else(|| author.email.clone()),
+        comment: content.to_string(),
+    };
+
+    if let Err(e) = send_email(emails, EmailType::CommentMention(mention)).await {
+        tracing::error!(error = %e, asset_id = %asset.id, "Failed to send mention emails");
+    }
+}
+
+pub fn comment_to_response(
+    comment: AssetComment,
+    users: &HashMap<Uuid, CommentUser>,
+) -> AssetCommentResponse {
+    let deleted = comment.deleted_at.is_some();
+    AssetCommentResponse {
+        id: comment.id,
+        asset_id: comment.asset_id,
+        asset_type: comment.asset_type,
+        parent_id: comment.parent_id,
+        content: if deleted { String::new() } else { comment.content },
+        author: if deleted {
+            None
+        } else {
+            users.get(&comment.created_by).cloned()
+        },
+        mentions: if deleted {
+            Vec::new()
+        } else {
+            comment
+                .mentions
+                .iter()
+                .filter_map(|id| users.get(id).cloned())
+                .collect()
+        },
+        created_at: comment.created_at,
+        updated_at: comment.updated_at,
+        edited: comment.updated_at > comment.created_at,
+        deleted,
+        replies: Vec::new(),
+    }
+}
+
+/// Nests comments under their parents, oldest first. Deleted comments are only
+/// kept (as empty placeholders) when they still have visible replies.
+pub fn build_comment_tree(
+    comments: Vec<AssetComment>,
+    users: &HashMap<Uuid, CommentUser>,
+) -> Vec<AssetCommentResponse> {
+    let mut children: HashMap<Option<Uuid>, Vec<AssetComment>> = HashMap::new();
+    for comment in comments {
+        children.entry(comment.parent_id).or_default().push(comment);
+    }
+    for siblings in children.values_mut() {
+        siblings.sort_by_key(|comment| comment.created_at);
+    }
+
+    fn attach(
+        parent_id: Option<Uuid>,
+        children: &mut HashMap<Option<Uuid>, Vec<AssetComment>>,
+        users: &HashMap<Uuid, CommentUser>,
+    ) -> Vec<AssetCommentResponse> {
+        let siblings = children.remove(&parent_id).unwrap_or_default();
+        siblings
+            .into_iter()
+            .filter_map(|comment| {
+                let id = comment.id;
+                let mut response = comment_to_response(comment, users);
+                response.replies = attach(Some(id), children, users);
+                if response.deleted && response.replies.is_empty() {
+                    None
+                } else {
+                    Some(response)
+                }
+            })
+            .collect()
+    }
+
+    attach(None, &mut children, users)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn comment(id: Uuid, parent_id: Option<Uuid>, minutes: i64, deleted: bool) -> AssetComment {
+        let created_at = Utc::now() + Duration::minutes(minutes);
+        AssetComment {
+            id,
+            asset_id: Uuid::nil(),
+            asset_type: AssetType::MetricFile,
+            organization_id: Uuid::nil(),
+            parent_id,
+            content: format!("comment {}", minutes),
+            mentions: vec![],
+            created_by: Uuid::nil(),
+            created_at,
+            updated_at: created_at,
+            deleted_at: if deleted { Some(created_at) } else { None },
+        }
+    }
+
+    #[test]
+    fn test_extract_mention_emails() {
+        let emails = extract_mention_emails(
+            "@Jane@Example.com can you check this with @bob@example.co.uk? cc @jane@example.com.",
+        );
+        assert_eq!(emails, vec!["jane@example.com", "bob@example.co.uk"]);
+
+        // Plain email addresses are not mentions
+        assert!(extract_mention_emails("mail jane@example.com").is_empty());
+    }
+
+    #[test]
+    fn test_build_comment_tree() {
+        let (root, reply, nested, deleted_root, deleted_leaf) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        let reply_to_deleted = Uuid::new_v4();
+
+        let tree = build_comment_tree(
+            vec![
+                comment(nested, Some(reply), 3, false),
+                comment(reply, Some(root), 2, false),
+                comment(root, None, 1, false),
+                comment(deleted_root, None, 4, true),
+                comment(reply_to_deleted, Some(deleted_root), 5, false),
+                comment(deleted_leaf, Some(root), 6, true),
+            ],
+            &HashMap::new(),
+        );
+
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].id, root);
+        assert_eq!(tree[0].replies.len(), 1);
+        assert_eq!(tree[0].replies[0].id, reply);
+        assert_eq!(tree[0].replies[0].replies[0].id, nested);
+
+        assert_eq!(tree[1].id, deleted_root);
+        assert!(tree[1].deleted);
+        assert!(tree[1].content.is_empty());
+        assert_eq!(tree[1].replies[0].id, reply_to_deleted);
+    }
+}
@@ -0,0 +1,44 @@
+use anyhow::{anyhow, Result};
+use database::{
+    enums::AssetType, models::AssetComment, pool::get_pg_pool, schema::asset_comments,
+};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use super::helpers::{build_comment_tree, fetch_commentable_asset, fetch_comment_users};
+use super::types::AssetCommentResponse;
+
+/// Handler to list the comment threads on a metric or dashboard
+///
+/// Returns top-level comments oldest first, each with its replies nested.
+pub async fn list_comments_handler(
+    asset_id: &Uuid,
+    asset_type: AssetType,
+    user: &AuthenticatedUser,
+) -> Result<Vec<AssetCommentResponse>> {
+    fetch_commentable_asset(asset_id, asset_type, user).await?;
+
+    let mut conn = get_pg_pool().get().await?;
+
+    // Deleted comments are loaded so that their replies keep their place in the thread
+    let comments = asset_comments::table
+        .filter(asset_comments::asset_id.eq(asset_id))
+        .filter(asset_comments::asset_type.eq(asset_type))
+        .load::<AssetComment>(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to load comments: {}", e))?;
+
+    let mut user_ids: Vec<Uuid> = comments
+        .iter()
+        .flat_map(|comment| {
+            std::iter::once(comment.created_by).chain(comment.mentions.iter().copied())
+        })
+        .collect();
+    user_ids.sort();
+    user_ids.dedup();
+    let users = fetch_comment_users(&user_ids).await?;
+
+    Ok(build_comment_tree(comments, &users))
+}
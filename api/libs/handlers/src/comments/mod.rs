@@ -0,0 +1,12 @@
+mod create_comment_handler;
+mod delete_comment_handler;
+mod helpers;
+mod list_comments_handler;
+mod types;
+mod update_comment_handler;
+
+pub use create_comment_handler::*;
+pub use delete_comment_handler::*;
+pub use list_comments_handler::*;
+pub use types::*;
+pub use update_comment_handler::*;
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use database::enums::AssetType;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreateCommentRequest {
+    pub content: String,
+    /// Comment being replied to, if any
+    pub parent_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UpdateCommentRequest {
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CommentUser {
+    pub id: Uuid,
+    pub name: Option<String>,
+    pub email: String,
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AssetCommentResponse {
+    pub id: Uuid,
+    pub asset_id: Uuid,
+    pub asset_type: AssetType,
+    pub parent_id: Option<Uuid>,
+    /// Empty for deleted comments that are kept to preserve their replies
+    pub content: String,
+    pub author: Option<CommentUser>,
+    pub mentions: Vec<CommentUser>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub edited: bool,
+    pub deleted: bool,
+    pub replies: Vec<AssetCommentResponse>,
+}
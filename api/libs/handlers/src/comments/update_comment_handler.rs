@@ -0,0 +1,76 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use database::{
+    enums::AssetType, models::AssetComment, pool::get_pg_pool, schema::asset_comments,
+};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use super::helpers::{
+    comment_to_response, fetch_commentable_asset, fetch_comment_users, notify_mentions,
+    resolve_mentions,
+};
+use super::types::{AssetCommentResponse, UpdateCommentRequest};
+
+/// Handler to edit a comment. Only the author can edit; users mentioned for
+/// the first time by the edit are notified.
+pub async fn update_comment_handler(
+    asset_id: &Uuid,
+    asset_type: AssetType,
+    comment_id: &Uuid,
+    user: &AuthenticatedUser,
+    request: UpdateCommentRequest,
+) -> Result<AssetCommentResponse> {
+    let content = request.content.trim().to_string();
+    if content.is_empty() {
+        return Err(anyhow!("Comment cannot be empty"));
+    }
+
+    let asset = fetch_commentable_asset(asset_id, asset_type, user).await?;
+
+    let mut conn = get_pg_pool().get().await?;
+
+    let existing = asset_comments::table
+        .filter(asset_comments::id.eq(comment_id))
+        .filter(asset_comments::asset_id.eq(asset_id))
+        .filter(asset_comments::asset_type.eq(asset_type))
+        .filter(asset_comments::deleted_at.is_null())
+        .first::<AssetComment>(&mut conn)
+        .await
+        .map_err(|e| match e {
+            diesel::NotFound => anyhow!("Comment not found"),
+            _ => anyhow!("Failed to load comment: {}", e),
+        })?;
+
+    if existing.created_by != user.id {
+        return Err(anyhow!("You don't have permission to edit this comment"));
+    }
+
+    let mentions = resolve_mentions(&content, &asset.organization_id).await?;
+    let new_mentions: Vec<Uuid> = mentions
+        .iter()
+        .copied()
+        .filter(|id| !existing.mentions.contains(id))
+        .collect();
+
+    let comment = diesel::update(asset_comments::table)
+        .filter(asset_comments::id.eq(comment_id))
+        .set((
+            asset_comments::content.eq(&content),
+            asset_comments::mentions.eq(&mentions),
+            asset_comments::updated_at.eq(Utc::now()),
+        ))
+        .get_result::<AssetComment>(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to update comment: {}", e))?;
+
+    notify_mentions(&asset, user, &content, &new_mentions).await;
+
+    let mut user_ids = mentions;
+    user_ids.push(user.id);
+    let users = fetch_comment_users(&user_ids).await?;
+
+    Ok(comment_to_response(comment, &users))
+}
@@ -7,13 +7,14 @@ use database::types::VersionHistory;
 use diesel::{insert_into, ExpressionMethods};
 use diesel_async::RunQueryDsl;
 use middleware::AuthenticatedUser;
-use serde_json::Value;
+use serde_json::{json, Value};
 use uuid::Uuid;
 
 use crate::metrics::Version;
+use crate::webhooks::emit_webhook_event;
 use super::{BusterDashboard, BusterDashboardResponse, DashboardConfig};
 use database::organization::get_user_organization_id;
-use database::enums::{AssetPermissionRole, AssetType, IdentityType, Verification};
+use database::enums::{AssetPermissionRole, AssetType, IdentityType, Verification, WebhookEventType};
 use database::schema::asset_permissions;
 use std::collections::HashMap;
 
@@ -96,6 +97,17 @@ pub async fn create_dashboard_handler(user: &AuthenticatedUser) -> Result<Buster
         .execute(&mut conn)
         .await?;
 
+    emit_webhook_event(
+        organization_id,
+        WebhookEventType::DashboardCreated,
+        json!({
+            "id": dashboard_id,
+            "name": dashboard_file.1,
+            "version_number": 1,
+            "actor_id": user.id,
+        }),
+    );
+
     // Construct the dashboard
     let dashboard = BusterDashboard {
         config: DashboardConfig { rows: vec![] },
@@ -0,0 +1,235 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use indexmap::IndexMap;
+use middleware::AuthenticatedUser;
+use serde::Deserialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::dashboards::get_dashboard_handler;
+use crate::dashboards::pdf::{render_dashboard_pdf, DashboardPdfContent, DataRow, PdfRow, PdfTile};
+use crate::metrics::{
+    get_metric_data_handler, get_metric_for_dashboard_handler, BusterMetric, DrillContext,
+    GetMetricDataRequest, Version,
+};
+
+/// Default cap on rows fetched per tile; a printed tile can't show more anyway.
+const DEFAULT_ROW_LIMIT: i64 = 5000;
+
+#[derive(Debug, Deserialize, Default)]
+pub struct ExportDashboardPdfRequest {
+    pub version_number: Option<i32>,
+    /// Optional password for accessing public password-protected dashboards
+    pub password: Option<String>,
+    /// Variable values applied by the viewer, printed in the document header.
+    /// Each one filters the tiles that have a column of the same name.
+    #[serde(default)]
+    pub variables: IndexMap<String, String>,
+    pub row_limit: Option<i64>,
+}
+
+#[derive(Debug)]
+pub struct DashboardPdfExport {
+    pub file_name: String,
+    pub content: Vec<u8>,
+}
+
+/// Renders a dashboard version, including every tile's data, into a paginated PDF.
+pub async fn export_dashboard_pdf_handler(
+    dashboard_id: &Uuid,
+    user: &AuthenticatedUser,
+    request: ExportDashboardPdfRequest,
+) -> Result<DashboardPdfExport> {
+    // Permission checks (including public/password access) are handled here.
+    let dashboard_response = get_dashboard_handler(
+        dashboard_id,
+        user,
+        request.version_number,
+        request.password.clone(),
+    )
+    .await?;
+    let dashboard = dashboard_response.dashboard;
+    let metrics = dashboard_response.metrics;
+    let row_limit = request.row_limit.unwrap_or(DEFAULT_ROW_LIMIT);
+    // An older dashboard version shows its metrics as they were at the time
+    let pinned_at = request.version_number.and(dashboard.updated_at);
+    let filters = (!request.variables.is_empty()).then(|| DrillContext {
+        target: None,
+        values: request
+            .variables
+            .iter()
+            .map(|(name, value)| (name.clone(), Value::String(value.clone())))
+            .collect(),
+    });
+
+    let tile_futures = dashboard.config.rows.iter().map(|row| {
+        let row_tiles = row.items.iter().map(|item| {
+            let metric = Uuid::parse_str(&item.id)
+                .ok()
+                .and_then(|id| metrics.get(&id))
+                .cloned();
+            build_tile(
+                metric,
+                user,
+                row_limit,
+                pinned_at,
+                filters.clone(),
+                request.password.clone(),
+            )
+        });
+        join_all(row_tiles)
+    });
+    let rows_tiles = join_all(tile_futures).await;
+
+    let rows = dashboard
+        .config
+        .rows
+        .iter()
+        .zip(rows_tiles)
+        .map(|(row, tiles)| PdfRow {
+            row_height: row.row_height,
+            column_sizes: row.column_sizes.clone().unwrap_or_default(),
+            tiles,
+        })
+        .collect();
+
+    let content = DashboardPdfContent {
+        name: dashboard.name.clone(),
+        description: dashboard.description.clone(),
+        version_number: dashboard.version_number,
+        variables: request.variables,
+        generated_at: Utc::now(),
+        rows,
+    };
+
+    let content = tokio::task::spawn_blocking(move || render_dashboard_pdf(&content))
+        .await
+        .map_err(|e| anyhow!("Failed to render dashboard PDF: {}", e))?;
+
+    Ok(DashboardPdfExport {
+        file_name: pdf_file_name(&dashboard.name),
+        content,
+    })
+}
+
+async fn build_tile(
+    metric: Option<BusterMetric>,
+    user: &AuthenticatedUser,
+    row_limit: i64,
+    pinned_at: Option<DateTime<Utc>>,
+    filters: Option<DrillContext>,
+    password: Option<String>,
+) -> PdfTile {
+    let Some(mut metric) = metric else {
+        return PdfTile {
+            title: "Unavailable metric".to_string(),
+            description: None,
+            chart_config: None,
+            data: Vec::new(),
+            error: Some("This metric could not be loaded".to_string()),
+        };
+    };
+
+    let version_number = pinned_at.and_then(|at| pinned_version(&metric.versions, at));
+    if let Some(version) = version_number.filter(|v| *v != metric.version_number) {
+        match get_metric_for_dashboard_handler(&metric.id, Some(version)).await {
+            Ok(pinned) => metric = pinned,
+            Err(e) => tracing::error!(
+                metric_id = %metric.id,
+                version,
+                "Failed to load pinned metric version for PDF export: {}",
+                e
+            ),
+        }
+    }
+
+    let data_result = get_metric_data_handler(
+        GetMetricDataRequest {
+            metric_id: metric.id,
+            version_number,
+            limit: Some(row_limit),
+            password,
+            drill: filters,
+        },
+        user.clone(),
+    )
+    .await;
+
+    let (data, error) = match data_result {
+        Ok(response) => match response
+            .data
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|(column, value)| serde_json::to_value(value).map(|v| (column, v)))
+                    .collect::<Result<DataRow, _>>()
+            })
+            .collect::<Result<Vec<DataRow>, _>>()
+        {
+            Ok(data) => (data, None),
+            Err(e) => (Vec::new(), Some(format!("Failed to read results: {}", e))),
+        },
+        Err(e) => {
+            tracing::error!(metric_id = %metric.id, "Failed to fetch metric data for PDF export: {}", e);
+            (Vec::new(), Some("Failed to load data for this metric".to_string()))
+        }
+    };
+
+    PdfTile {
+        title: metric.name,
+        description: metric.description,
+        chart_config: metric.chart_config,
+        data,
+        error: error.or(metric.error),
+    }
+}
+
+/// The metric version that was current at `at`
+fn pinned_version(versions: &[Version], at: DateTime<Utc>) -> Option<i32> {
+    versions
+        .iter()
+        .filter(|version| version.updated_at <= at)
+        .map(|version| version.version_number)
+        .max()
+}
+
+fn pdf_file_name(dashboard_name: &str) -> String {
+    let slug = dashboard_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    if slug.is_empty() {
+        "dashboard.pdf".to_string()
+    } else {
+        format!("{}.pdf", slug)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pdf_file_name() {
+        assert_eq!(pdf_file_name("Q3 Board Pack: Revenue"), "q3-board-pack-revenue.pdf");
+        assert_eq!(pdf_file_name("日本"), "dashboard.pdf");
+    }
+
+    #[test]
+    fn test_pinned_version() {
+        let at = Utc::now();
+        let version = |version_number, days: i64| Version {
+            version_number,
+            updated_at: at + chrono::Duration::days(days),
+        };
+        let versions = vec![version(1, -2), version(2, -1), version(3, 1)];
+        assert_eq!(pinned_version(&versions, at), Some(2));
+        assert_eq!(pinned_version(&versions, at - chrono::Duration::days(3)), None);
+    }
+}
@@ -0,0 +1,37 @@
+use anyhow::{anyhow, Result};
+use database::pool::get_pg_pool;
+use database::schema::dashboard_files;
+use database::types::{VersionDiff, VersionHistory};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use super::get_dashboard_handler;
+
+/// Handler to compare two versions of a dashboard
+///
+/// Returns a line diff of the two YAML versions together with a list of
+/// semantic changes (rows and metrics added/removed, ...). Requires the same
+/// access as viewing the dashboard.
+pub async fn get_dashboard_version_diff_handler(
+    dashboard_id: &Uuid,
+    user: &AuthenticatedUser,
+    from_version: i32,
+    to_version: i32,
+) -> Result<VersionDiff> {
+    // Reuses the get handler's permission checks
+    get_dashboard_handler(dashboard_id, user, None, None).await?;
+
+    let mut conn = get_pg_pool().get().await?;
+
+    let version_history = dashboard_files::table
+        .filter(dashboard_files::id.eq(dashboard_id))
+        .filter(dashboard_files::deleted_at.is_null())
+        .select(dashboard_files::version_history)
+        .first::<VersionHistory>(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to get version history: {}", e))?;
+
+    version_history.diff(from_version, to_version)
+}
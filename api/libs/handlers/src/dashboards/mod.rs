@@ -1,14 +1,21 @@
 mod create_dashboard_handler;
 mod delete_dashboard_handler;
+mod export_dashboard_pdf_handler;
 mod get_dashboard_handler;
+mod get_dashboard_version_diff_handler;
 mod list_dashboard_handler;
+mod restore_dashboard_version_handler;
 mod update_dashboard_handler;
 mod types;
+mod pdf;
 pub mod sharing;
 
 pub use create_dashboard_handler::*;
 pub use delete_dashboard_handler::*;
+pub use export_dashboard_pdf_handler::*;
 pub use get_dashboard_handler::*;
+pub use get_dashboard_version_diff_handler::*;
 pub use list_dashboard_handler::*;
+pub use restore_dashboard_version_handler::*;
 pub use update_dashboard_handler::*;
 pub use types::*;
\ No newline at end of file
@@ -0,0 +1,755 @@
+//! Static renderings of metric tiles for the PDF export.
+//!
+//! These mirror the web charts closely enough for print: bar/line/combo,
+//! scatter, pie, big-number metrics and tables. Interactive features
+//! (tooltips, zoom, trendlines) are intentionally not rendered.
+
+use database::types::{BaseChartConfig, ChartConfig, ColumnLabelFormat};
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use super::document::{text_width, truncate_to_width, wrap_text, Color, Font, Page};
+
+pub type DataRow = IndexMap<String, Value>;
+
+/// Matches the default palette of the web app's charts.
+const DEFAULT_PALETTE: [Color; 8] = [
+    Color::rgb(183, 155, 255),
+    Color::rgb(140, 225, 181),
+    Color::rgb(255, 160, 149),
+    Color::rgb(167, 198, 255),
+    Color::rgb(255, 209, 120),
+    Color::rgb(122, 204, 218),
+    Color::rgb(244, 167, 215),
+    Color::rgb(186, 186, 186),
+];
+
+const MAX_BAR_CATEGORIES: usize = 40;
+const MAX_SERIES: usize = 4;
+const AXIS_FONT_SIZE: f32 = 6.5;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Rect {
+    pub fn inset(&self, dx: f32, dy: f32) -> Rect {
+        Rect {
+            x: self.x + dx,
+            y: self.y + dy,
+            width: (self.width - 2.0 * dx).max(0.0),
+            height: (self.height - 2.0 * dy).max(0.0),
+        }
+    }
+
+    fn right(&self) -> f32 {
+        self.x + self.width
+    }
+
+    fn bottom(&self) -> f32 {
+        self.y + self.height
+    }
+}
+
+/// Everything needed to draw one dashboard tile.
+#[derive(Debug, Clone)]
+pub struct PdfTile {
+    pub title: String,
+    pub description: Option<String>,
+    pub chart_config: Option<ChartConfig>,
+    pub data: Vec<DataRow>,
+    pub error: Option<String>,
+}
+
+pub fn draw_tile(page: &mut Page, area: Rect, tile: &PdfTile) {
+    page.rect(area.x, area.y, area.width, area.height, None, Some(Color::LIGHT_GRAY));
+    let inner = area.inset(8.0, 8.0);
+
+    let title = truncate_to_width(&tile.title, 10.0, Font::Bold, inner.width);
+    page.text(inner.x, inner.y + 10.0, 10.0, Font::Bold, Color::BLACK, &title);
+    let mut body_top = inner.y + 16.0;
+    if let Some(description) = tile.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        let description = truncate_to_width(description, 7.0, Font::Regular, inner.width);
+        page.text(inner.x, body_top + 6.0, 7.0, Font::Regular, Color::GRAY, &description);
+        body_top += 10.0;
+    }
+
+    let body = Rect {
+        x: inner.x,
+        y: body_top + 4.0,
+        width: inner.width,
+        height: (inner.bottom() - body_top - 4.0).max(0.0),
+    };
+
+    if let Some(error) = &tile.error {
+        draw_message(page, body, error);
+        return;
+    }
+
+    match &tile.chart_config {
+        Some(ChartConfig::Bar(config)) => {
+            let axis = &config.bar_and_line_axis;
+            draw_cartesian(page, body, &config.base, &tile.data, &axis.x, &axis.y, SeriesKind::Bar);
+        }
+        Some(ChartConfig::Line(config)) => {
+            let axis = &config.bar_and_line_axis;
+            draw_cartesian(page, body, &config.base, &tile.data, &axis.x, &axis.y, SeriesKind::Line);
+        }
+        Some(ChartConfig::Combo(config)) => {
+            let axis = &config.combo_chart_axis;
+            draw_cartesian(page, body, &config.base, &tile.data, &axis.x, &axis.y, SeriesKind::Combo);
+        }
+        Some(ChartConfig::Scatter(config)) => {
+            let axis = &config.scatter_axis;
+            draw_scatter(page, body, &config.base, &tile.data, &axis.x, &axis.y);
+        }
+        Some(ChartConfig::Pie(config)) => {
+            let axis = &config.pie_chart_axis;
+            draw_pie(page, body, &config.base, &tile.data, &axis.x, &axis.y);
+        }
+        Some(ChartConfig::Metric(config)) => {
+            draw_metric(
+                page,
+                body,
+                &config.base,
+                &tile.data,
+                &config.metric_column_id,
+                config.metric_value_aggregate.as_deref(),
+            );
+        }
+        Some(ChartConfig::Table(config)) => {
+            draw_table(
+                page,
+                body,
+                &config.base,
+                &tile.data,
+                config.table_column_order.as_deref(),
+            );
+        }
+        None => draw_table(page, body, &default_base(), &tile.data, None),
+    }
+}
+
+fn default_base() -> BaseChartConfig {
+    BaseChartConfig {
+        column_label_formats: IndexMap::new(),
+        column_settings: None,
+        colors: None,
+        show_legend: None,
+        grid_lines: None,
+        show_legend_headline: None,
+        goal_lines: None,
+        trendlines: None,
+        disable_tooltip: None,
+        y_axis_config: None,
+        x_axis_config: None,
+        category_axis_style_config: None,
+        y2_axis_config: None,
+    }
+}
+
+fn draw_message(page: &mut Page, area: Rect, message: &str) {
+    let lines = wrap_text(message, 8.0, Font::Regular, area.width, 3);
+    let start = area.y + area.height / 2.0 - (lines.len() as f32 - 1.0) * 5.0;
+    for (i, line) in lines.iter().enumerate() {
+        page.text_centered(
+            area.x + area.width / 2.0,
+            start + i as f32 * 10.0,
+            8.0,
+            Font::Regular,
+            Color::GRAY,
+            line,
+        );
+    }
+}
+
+fn palette(base: &BaseChartConfig) -> Vec<Color> {
+    let custom: Vec<Color> = base
+        .colors
+        .iter()
+        .flatten()
+        .filter_map(|c| Color::from_hex(c))
+        .collect();
+    if custom.is_empty() {
+        DEFAULT_PALETTE.to_vec()
+    } else {
+        custom
+    }
+}
+
+/// Looks up a column, tolerating the lowercased keys used in chart configs.
+fn column_value<'a>(row: &'a DataRow, column: &str) -> Option<&'a Value> {
+    row.get(column).or_else(|| {
+        row.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(column))
+            .map(|(_, value)| value)
+    })
+}
+
+fn column_format<'a>(base: &'a BaseChartConfig, column: &str) -> Option<&'a ColumnLabelFormat> {
+    base.column_label_formats.get(column).or_else(|| {
+        base.column_label_formats
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(column))
+            .map(|(_, format)| format)
+    })
+}
+
+fn column_label(base: &BaseChartConfig, column: &str) -> String {
+    column_format(base, column)
+        .and_then(|f| f.display_name.clone())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| humanize_column(column))
+}
+
+fn humanize_column(column: &str) -> String {
+    column
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+pub fn value_as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse::<f64>().ok(),
+        Value::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+        _ => None,
+    }
+}
+
+pub fn value_as_label(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n
+            .as_f64()
+            .map(format_number)
+            .unwrap_or_else(|| n.to_string()),
+        other => other.to_string(),
+    }
+}
+
+/// Formats a number with thousands separators and at most two decimals.
+pub fn format_number(value: f64) -> String {
+    if !value.is_finite() {
+        return value.to_string();
+    }
+    let rounded = (value * 100.0).round() / 100.0;
+    let negative = rounded < 0.0;
+    let abs = rounded.abs();
+    let integer = abs.trunc() as u64;
+    let fraction = ((abs - abs.trunc()) * 100.0).round() as u64;
+
+    let digits = integer.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+
+    let mut out = String::new();
+    if negative {
+        out.push('-');
+    }
+    out.push_str(&grouped);
+    if fraction > 0 {
+        let fraction = format!("{:02}", fraction);
+        out.push('.');
+        out.push_str(fraction.trim_end_matches('0'));
+    }
+    out
+}
+
+/// Formats a cell according to the column's label format (prefix, suffix,
+/// multiplier, percent style).
+pub fn format_cell(base: &BaseChartConfig, column: &str, value: &Value) -> String {
+    let format = column_format(base, column);
+    let missing = format.and_then(|f| f.replace_missing_data_with.as_ref());
+    let value = match (value, missing) {
+        (Value::Null, Some(replacement)) => replacement,
+        _ => value,
+    };
+
+    let Some(format) = format else {
+        return value_as_label(value);
+    };
+    if format.column_type != "number" {
+        return value_as_label(value);
+    }
+    let Some(number) = value_as_f64(value) else {
+        return value_as_label(value);
+    };
+
+    let number = number * format.multiplier.unwrap_or(1.0);
+    let mut formatted = format!(
+        "{}{}",
+        format.prefix.clone().unwrap_or_default(),
+        format_number(number)
+    );
+    if format.style == "percent" {
+        formatted.push('%');
+    } else if format.style == "currency" && format.prefix.is_none() {
+        formatted.insert(0, '$');
+    }
+    formatted.push_str(format.suffix.as_deref().unwrap_or_default());
+    formatted
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SeriesKind {
+    Bar,
+    Line,
+    /// First series as bars, the rest as lines.
+    Combo,
+}
+
+fn draw_legend(page: &mut Page, area: Rect, labels: &[String], colors: &[Color]) -> f32 {
+    if labels.len() < 2 {
+        return 0.0;
+    }
+    let mut x = area.x;
+    for (i, label) in labels.iter().enumerate() {
+        let label = truncate_to_width(label, AXIS_FONT_SIZE, Font::Regular, 90.0);
+        let width = 10.0 + text_width(&label, AXIS_FONT_SIZE, Font::Regular) + 10.0;
+        if x + width > area.right() {
+            break;
+        }
+        page.rect(x, area.y + 1.0, 6.0, 6.0, Some(colors[i % colors.len()]), None);
+        page.text(x + 9.0, area.y + 7.0, AXIS_FONT_SIZE, Font::Regular, Color::DARK_GRAY, &label);
+        x += width;
+    }
+    12.0
+}
+
+/// Returns a "nice" axis range and tick step covering `[min, max]`.
+fn nice_scale(min: f64, max: f64, ticks: usize) -> (f64, f64, f64) {
+    let (min, max) = if (max - min).abs() < f64::EPSILON {
+        (min - 1.0, max + 1.0)
+    } else {
+        (min, max)
+    };
+    let raw_step = (max - min) / ticks.max(1) as f64;
+    let magnitude = 10f64.powf(raw_step.log10().floor());
+    let step = [1.0, 2.0, 2.5, 5.0, 10.0]
+        .iter()
+        .map(|m| m * magnitude)
+        .find(|s| *s >= raw_step)
+        .unwrap_or(10.0 * magnitude);
+    ((min / step).floor() * step, (max / step).ceil() * step, step)
+}
+
+fn draw_cartesian(
+    page: &mut Page,
+    area: Rect,
+    base: &BaseChartConfig,
+    data: &[DataRow],
+    x_columns: &[String],
+    y_columns: &[String],
+    kind: SeriesKind,
+) {
+    let Some(x_column) = x_columns.first() else {
+        draw_message(page, area, "Chart has no x axis configured");
+        return;
+    };
+    if data.is_empty() || y_columns.is_empty() {
+        draw_message(page, area, "No results");
+        return;
+    }
+
+    let max_points = if kind == SeriesKind::Line { 500 } else { MAX_BAR_CATEGORIES };
+    let rows = &data[..data.len().min(max_points)];
+    let series: Vec<&String> = y_columns.iter().take(MAX_SERIES).collect();
+    let colors = palette(base);
+
+    let labels: Vec<String> = series.iter().map(|c| column_label(base, c)).collect();
+    let legend_height = draw_legend(page, area, &labels, &colors);
+
+    let values: Vec<Vec<Option<f64>>> = series
+        .iter()
+        .map(|column| {
+            rows.iter()
+                .map(|row| column_value(row, column).and_then(value_as_f64))
+                .collect()
+        })
+        .collect();
+    let all_values = values.iter().flatten().flatten().copied();
+    let (data_min, data_max) = all_values.fold((f64::MAX, f64::MIN), |(lo, hi), v| {
+        (lo.min(v), hi.max(v))
+    });
+    if data_min > data_max {
+        draw_message(page, area, "No numeric values to plot");
+        return;
+    }
+    let (axis_min, axis_max, step) = nice_scale(data_min.min(0.0), data_max.max(0.0), 4);
+
+    let label_width = [axis_min, axis_max]
+        .iter()
+        .map(|v| text_width(&format_number(*v), AXIS_FONT_SIZE, Font::Regular))
+        .fold(0.0f32, f32::max);
+    let plot = Rect {
+        x: area.x + label_width + 4.0,
+        y: area.y + legend_height + 2.0,
+        width: (area.width - label_width - 4.0).max(1.0),
+        height: (area.height - legend_height - 14.0).max(1.0),
+    };
+    let to_y = |v: f64| {
+        plot.bottom() - ((v - axis_min) / (axis_max - axis_min)) as f32 * plot.height
+    };
+
+    // Grid lines and y-axis labels.
+    let mut tick = axis_min;
+    while tick <= axis_max + step / 2.0 {
+        let y = to_y(tick);
+        if base.grid_lines.unwrap_or(true) {
+            page.line(plot.x, y, plot.right(), y, Color::LIGHT_GRAY, 0.4);
+        }
+        page.text_right(
+            plot.x - 3.0,
+            y + 2.0,
+            AXIS_FONT_SIZE,
+            Font::Regular,
+            Color::GRAY,
+            &format_number(tick),
+        );
+        tick += step;
+    }
+
+    let band = plot.width / rows.len() as f32;
+    let center = |i: usize| plot.x + band * (i as f32 + 0.5);
+
+    // X-axis labels, thinned out so they don't overlap.
+    let label_every = ((rows.len() as f32 * 40.0) / plot.width).ceil().max(1.0) as usize;
+    for (i, row) in rows.iter().enumerate().step_by(label_every) {
+        let label = column_value(row, x_column)
+            .map(|v| format_cell(base, x_column, v))
+            .unwrap_or_default();
+        let label = truncate_to_width(&label, AXIS_FONT_SIZE, Font::Regular, band * label_every as f32 - 2.0);
+        page.text_centered(center(i), plot.bottom() + 9.0, AXIS_FONT_SIZE, Font::Regular, Color::GRAY, &label);
+    }
+
+    let bar_series: Vec<usize> = match kind {
+        SeriesKind::Bar => (0..series.len()).collect(),
+        SeriesKind::Combo => vec![0],
+        SeriesKind::Line => Vec::new(),
+    };
+    if !bar_series.is_empty() {
+        let group_width = band * 0.8;
+        let bar_width = group_width / bar_series.len() as f32;
+        let zero = to_y(0.0);
+        for (slot, &s) in bar_series.iter().enumerate() {
+            for (i, value) in values[s].iter().enumerate() {
+                let Some(value) = value else { continue };
+                let x = center(i) - group_width / 2.0 + bar_width * slot as f32;
+                let y = to_y(*value);
+                let (top, height) = if y < zero { (y, zero - y) } else { (zero, y - zero) };
+                page.rect(x, top, bar_width.max(0.5), height, Some(colors[s % colors.len()]), None);
+            }
+        }
+    }
+
+    for (s, series_values) in values.iter().enumerate() {
+        if bar_series.contains(&s) {
+            continue;
+        }
+        // Break the line on missing values rather than interpolating.
+        let mut segment: Vec<(f32, f32)> = Vec::new();
+        for (i, value) in series_values.iter().enumerate() {
+            match value {
+                Some(v) => segment.push((center(i), to_y(*v))),
+                None => {
+                    page.polyline(&segment, colors[s % colors.len()], 1.5);
+                    segment.clear();
+                }
+            }
+        }
+        if segment.len() == 1 {
+            page.circle(segment[0].0, segment[0].1, 1.5, colors[s % colors.len()]);
+        }
+        page.polyline(&segment, colors[s % colors.len()], 1.5);
+    }
+
+    page.line(plot.x, to_y(axis_min), plot.right(), to_y(axis_min), Color::GRAY, 0.6);
+}
+
+fn draw_scatter(
+    page: &mut Page,
+    area: Rect,
+    base: &BaseChartConfig,
+    data: &[DataRow],
+    x_columns: &[String],
+    y_columns: &[String],
+) {
+    let (Some(x_column), Some(y_column)) = (x_columns.first(), y_columns.first()) else {
+        draw_message(page, area, "Chart axes are not configured");
+        return;
+    };
+    let points: Vec<(f64, f64)> = data
+        .iter()
+        .filter_map(|row| {
+            Some((
+                value_as_f64(column_value(row, x_column)?)?,
+                value_as_f64(column_value(row, y_column)?)?,
+            ))
+        })
+        .collect();
+    if points.is_empty() {
+        draw_message(page, area, "No numeric values to plot");
+        return;
+    }
+
+    let (x_lo, x_hi, _) = nice_scale(
+        points.iter().map(|p| p.0).fold(f64::MAX, f64::min),
+        points.iter().map(|p| p.0).fold(f64::MIN, f64::max),
+        4,
+    );
+    let (y_lo, y_hi, y_step) = nice_scale(
+        points.iter().map(|p| p.1).fold(f64::MAX, f64::min),
+        points.iter().map(|p| p.1).fold(f64::MIN, f64::max),
+        4,
+    );
+
+    let plot = area.inset(12.0, 4.0);
+    let to_x = |v: f64| plot.x + ((v - x_lo) / (x_hi - x_lo)) as f32 * plot.width;
+    let to_y = |v: f64| plot.bottom() - ((v - y_lo) / (y_hi - y_lo)) as f32 * plot.height;
+
+    let mut tick = y_lo;
+    while tick <= y_hi + y_step / 2.0 {
+        page.line(plot.x, to_y(tick), plot.right(), to_y(tick), Color::LIGHT_GRAY, 0.4);
+        page.text_right(plot.x - 2.0, to_y(tick) + 2.0, AXIS_FONT_SIZE, Font::Regular, Color::GRAY, &format_number(tick));
+        tick += y_step;
+    }
+    page.text(plot.x, plot.bottom() + 9.0, AXIS_FONT_SIZE, Font::Regular, Color::GRAY, &format_number(x_lo));
+    page.text_right(plot.right(), plot.bottom() + 9.0, AXIS_FONT_SIZE, Font::Regular, Color::GRAY, &format_number(x_hi));
+
+    let color = palette(base)[0];
+    for (x, y) in points.iter().take(2_000) {
+        page.circle(to_x(*x), to_y(*y), 1.8, color);
+    }
+}
+
+fn draw_pie(
+    page: &mut Page,
+    area: Rect,
+    base: &BaseChartConfig,
+    data: &[DataRow],
+    x_columns: &[String],
+    y_columns: &[String],
+) {
+    let (Some(x_column), Some(y_column)) = (x_columns.first(), y_columns.first()) else {
+        draw_message(page, area, "Chart axes are not configured");
+        return;
+    };
+    let slices: Vec<(String, f64)> = data
+        .iter()
+        .filter_map(|row| {
+            let value = value_as_f64(column_value(row, y_column)?)?;
+            let label = column_value(row, x_column)
+                .map(|v| format_cell(base, x_column, v))
+                .unwrap_or_default();
+            (value > 0.0).then_some((label, value))
+        })
+        .collect();
+    let total: f64 = slices.iter().map(|(_, v)| v).sum();
+    if slices.is_empty() || total <= 0.0 {
+        draw_message(page, area, "No values to plot");
+        return;
+    }
+
+    let colors = palette(base);
+    let radius = (area.height / 2.0).min(area.width / 4.0) - 4.0;
+    let cx = area.x + radius + 4.0;
+    let cy = area.y + area.height / 2.0;
+
+    let mut angle = 0.0f32;
+    for (i, (_, value)) in slices.iter().enumerate() {
+        let sweep = (*value / total) as f32 * std::f32::consts::TAU;
+        page.wedge(cx, cy, radius, angle, angle + sweep, colors[i % colors.len()]);
+        angle += sweep;
+    }
+
+    let legend_x = cx + radius + 14.0;
+    let legend_width = area.right() - legend_x;
+    let max_entries = ((area.height / 11.0) as usize).max(1);
+    for (i, (label, value)) in slices.iter().enumerate().take(max_entries) {
+        let y = area.y + 4.0 + i as f32 * 11.0;
+        page.rect(legend_x, y, 6.0, 6.0, Some(colors[i % colors.len()]), None);
+        let text = format!("{} ({:.1}%)", label, value / total * 100.0);
+        let text = truncate_to_width(&text, AXIS_FONT_SIZE + 0.5, Font::Regular, legend_width - 10.0);
+        page.text(legend_x + 9.0, y + 6.0, AXIS_FONT_SIZE + 0.5, Font::Regular, Color::DARK_GRAY, &text);
+    }
+}
+
+/// Reduces a column to the single value shown by a metric ("big number") tile.
+pub fn aggregate_metric_value(data: &[DataRow], column: &str, aggregate: Option<&str>) -> Option<Value> {
+    let first = data.first().and_then(|row| column_value(row, column)).cloned();
+    let numbers = || data.iter().filter_map(|row| column_value(row, column).and_then(value_as_f64));
+    let number = |v: f64| serde_json::Number::from_f64(v).map(Value::Number);
+
+    match aggregate.unwrap_or("first") {
+        "sum" => number(numbers().sum()),
+        "average" => {
+            let values: Vec<f64> = numbers().collect();
+            if values.is_empty() {
+                None
+            } else {
+                number(values.iter().sum::<f64>() / values.len() as f64)
+            }
+        }
+        "median" => {
+            let mut values: Vec<f64> = numbers().collect();
+            if values.is_empty() {
+                return None;
+            }
+            values.sort_by(|a, b| a.total_cmp(b));
+            let mid = values.len() / 2;
+            if values.len().is_multiple_of(2) {
+                number((values[mid - 1] + values[mid]) / 2.0)
+            } else {
+                number(values[mid])
+            }
+        }
+        "count" => Some(Value::from(data.len() as u64)),
+        "max" => numbers().reduce(f64::max).and_then(number),
+        "min" => numbers().reduce(f64::min).and_then(number),
+        _ => first,
+    }
+}
+
+fn draw_metric(
+    page: &mut Page,
+    area: Rect,
+    base: &BaseChartConfig,
+    data: &[DataRow],
+    column: &str,
+    aggregate: Option<&str>,
+) {
+    let Some(value) = aggregate_metric_value(data, column, aggregate) else {
+        draw_message(page, area, "No results");
+        return;
+    };
+    let text = format_cell(base, column, &value);
+    let size = (area.height * 0.35).clamp(12.0, 36.0);
+    let text = truncate_to_width(&text, size, Font::Bold, area.width);
+    let cy = area.y + area.height / 2.0;
+    page.text_centered(area.x + area.width / 2.0, cy + size / 3.0, size, Font::Bold, Color::BLACK, &text);
+    page.text_centered(
+        area.x + area.width / 2.0,
+        cy + size / 3.0 + 14.0,
+        8.0,
+        Font::Regular,
+        Color::GRAY,
+        &column_label(base, column),
+    );
+}
+
+fn draw_table(
+    page: &mut Page,
+    area: Rect,
+    base: &BaseChartConfig,
+    data: &[DataRow],
+    column_order: Option<&[String]>,
+) {
+    let columns: Vec<String> = match column_order {
+        Some(order) if !order.is_empty() => order.to_vec(),
+        _ => data
+            .first()
+            .map(|row| row.keys().cloned().collect())
+            .unwrap_or_default(),
+    };
+    if columns.is_empty() {
+        draw_message(page, area, "No results");
+        return;
+    }
+
+    const ROW_HEIGHT: f32 = 11.0;
+    const FONT_SIZE: f32 = 7.0;
+    let column_width = area.width / columns.len() as f32;
+    let visible_rows = ((area.height / ROW_HEIGHT) as usize).saturating_sub(2);
+
+    page.rect(area.x, area.y, area.width, ROW_HEIGHT, Some(Color::TABLE_HEADER), None);
+    for (c, column) in columns.iter().enumerate() {
+        let label = truncate_to_width(&column_label(base, column), FONT_SIZE, Font::Bold, column_width - 4.0);
+        page.text(area.x + c as f32 * column_width + 2.0, area.y + 8.0, FONT_SIZE, Font::Bold, Color::DARK_GRAY, &label);
+    }
+
+    for (r, row) in data.iter().take(visible_rows).enumerate() {
+        let y = area.y + ROW_HEIGHT * (r as f32 + 1.0);
+        page.line(area.x, y, area.right(), y, Color::LIGHT_GRAY, 0.3);
+        for (c, column) in columns.iter().enumerate() {
+            let cell = column_value(row, column)
+                .map(|v| format_cell(base, column, v))
+                .unwrap_or_default();
+            let cell = truncate_to_width(&cell, FONT_SIZE, Font::Regular, column_width - 4.0);
+            page.text(area.x + c as f32 * column_width + 2.0, y + 8.0, FONT_SIZE, Font::Regular, Color::BLACK, &cell);
+        }
+    }
+
+    if data.len() > visible_rows {
+        let note = format!("Showing {} of {} rows", visible_rows, data.len());
+        page.text_right(area.right(), area.bottom(), 6.0, Font::Regular, Color::GRAY, &note);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rows(values: &[f64]) -> Vec<DataRow> {
+        values
+            .iter()
+            .map(|v| {
+                let mut row = DataRow::new();
+                row.insert("revenue".to_string(), json!(v));
+                row
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(1234567.0), "1,234,567");
+        assert_eq!(format_number(-1234.5), "-1,234.5");
+        assert_eq!(format_number(0.125), "0.13");
+        assert_eq!(format_number(12.0), "12");
+    }
+
+    #[test]
+    fn test_nice_scale_covers_range() {
+        let (lo, hi, step) = nice_scale(0.0, 93.0, 4);
+        assert_eq!(lo, 0.0);
+        assert!(hi >= 93.0);
+        assert_eq!(step, 25.0);
+    }
+
+    #[test]
+    fn test_aggregate_metric_value() {
+        let data = rows(&[1.0, 3.0, 8.0]);
+        assert_eq!(aggregate_metric_value(&data, "revenue", Some("sum")), Some(json!(12.0)));
+        assert_eq!(aggregate_metric_value(&data, "revenue", Some("median")), Some(json!(3.0)));
+        assert_eq!(aggregate_metric_value(&data, "revenue", Some("count")), Some(json!(3)));
+        assert_eq!(aggregate_metric_value(&data, "REVENUE", None), Some(json!(1.0)));
+        assert_eq!(aggregate_metric_value(&[], "revenue", Some("average")), None);
+    }
+
+    #[test]
+    fn test_humanize_column() {
+        assert_eq!(humanize_column("total_revenue"), "Total Revenue");
+        assert_eq!(humanize_column("mrr"), "Mrr");
+    }
+}
@@ -0,0 +1,483 @@
+//! Minimal PDF 1.4 writer used by the dashboard export.
+//!
+//! Only what the export needs is supported: the standard Helvetica faces,
+//! filled/stroked paths and single-line text. All coordinates passed to
+//! [`Page`] are measured from the top-left corner of the page in points.
+
+use std::fmt::Write as _;
+
+/// US Letter in landscape orientation.
+pub const PAGE_WIDTH: f32 = 792.0;
+pub const PAGE_HEIGHT: f32 = 612.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+}
+
+impl Color {
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self {
+            r: r as f32 / 255.0,
+            g: g as f32 / 255.0,
+            b: b as f32 / 255.0,
+        }
+    }
+
+    /// Parses `#rrggbb` / `#rgb` colors as used in `colors` of chart configs.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.trim().trim_start_matches('#');
+        let expanded = match hex.len() {
+            3 => hex.chars().flat_map(|c| [c, c]).collect::<String>(),
+            6 => hex.to_string(),
+            _ => return None,
+        };
+        let channel = |i: usize| u8::from_str_radix(&expanded[i..i + 2], 16).ok();
+        Some(Self::rgb(channel(0)?, channel(2)?, channel(4)?))
+    }
+
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const DARK_GRAY: Color = Color::rgb(64, 64, 64);
+    pub const GRAY: Color = Color::rgb(128, 128, 128);
+    pub const LIGHT_GRAY: Color = Color::rgb(224, 224, 224);
+    pub const TABLE_HEADER: Color = Color::rgb(245, 245, 245);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource_name(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+}
+
+/// A single page's content stream.
+#[derive(Debug, Default)]
+pub struct Page {
+    content: String,
+}
+
+impl Page {
+    fn flip(y: f32) -> f32 {
+        PAGE_HEIGHT - y
+    }
+
+    fn set_fill(&mut self, color: Color) {
+        let _ = writeln!(self.content, "{:.3} {:.3} {:.3} rg", color.r, color.g, color.b);
+    }
+
+    fn set_stroke(&mut self, color: Color, width: f32) {
+        let _ = writeln!(
+            self.content,
+            "{:.3} {:.3} {:.3} RG {:.2} w",
+            color.r, color.g, color.b, width
+        );
+    }
+
+    /// Draws `text` with its baseline at `y`.
+    pub fn text(&mut self, x: f32, y: f32, size: f32, font: Font, color: Color, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        self.set_fill(color);
+        let _ = writeln!(
+            self.content,
+            "BT /{} {:.1} Tf {:.2} {:.2} Td ({}) Tj ET",
+            font.resource_name(),
+            size,
+            x,
+            Self::flip(y),
+            escape_text(text)
+        );
+    }
+
+    /// Draws `text` right-aligned so that it ends at `x`.
+    pub fn text_right(&mut self, x: f32, y: f32, size: f32, font: Font, color: Color, text: &str) {
+        let width = text_width(text, size, font);
+        self.text(x - width, y, size, font, color, text);
+    }
+
+    /// Draws `text` horizontally centered on `x`.
+    pub fn text_centered(
+        &mut self,
+        x: f32,
+        y: f32,
+        size: f32,
+        font: Font,
+        color: Color,
+        text: &str,
+    ) {
+        let width = text_width(text, size, font);
+        self.text(x - width / 2.0, y, size, font, color, text);
+    }
+
+    /// Draws a rectangle whose top-left corner is at (`x`, `y`).
+    pub fn rect(
+        &mut self,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        fill: Option<Color>,
+        stroke: Option<Color>,
+    ) {
+        let op = match (fill, stroke) {
+            (Some(fill), Some(stroke)) => {
+                self.set_fill(fill);
+                self.set_stroke(stroke, 0.5);
+                "B"
+            }
+            (Some(fill), None) => {
+                self.set_fill(fill);
+                "f"
+            }
+            (None, Some(stroke)) => {
+                self.set_stroke(stroke, 0.5);
+                "S"
+            }
+            (None, None) => return,
+        };
+        let _ = writeln!(
+            self.content,
+            "{:.2} {:.2} {:.2} {:.2} re {}",
+            x,
+            Self::flip(y + height),
+            width,
+            height,
+            op
+        );
+    }
+
+    pub fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, color: Color, width: f32) {
+        self.polyline(&[(x1, y1), (x2, y2)], color, width);
+    }
+
+    pub fn polyline(&mut self, points: &[(f32, f32)], color: Color, width: f32) {
+        if points.len() < 2 {
+            return;
+        }
+        self.set_stroke(color, width);
+        for (i, (x, y)) in points.iter().enumerate() {
+            let op = if i == 0 { "m" } else { "l" };
+            let _ = writeln!(self.content, "{:.2} {:.2} {}", x, Self::flip(*y), op);
+        }
+        self.content.push_str("S\n");
+    }
+
+    pub fn circle(&mut self, cx: f32, cy: f32, radius: f32, fill: Color) {
+        self.wedge(cx, cy, radius, 0.0, std::f32::consts::TAU, fill);
+    }
+
+    /// Fills a pie slice between two angles (radians, clockwise from 12 o'clock).
+    pub fn wedge(&mut self, cx: f32, cy: f32, radius: f32, start: f32, end: f32, fill: Color) {
+        if end <= start || radius <= 0.0 {
+            return;
+        }
+        self.set_fill(fill);
+
+        // Work in PDF space where y grows upwards; convert the clockwise-from-top
+        // angle convention into standard counter-clockwise-from-x-axis angles.
+        let cy = Self::flip(cy);
+        let to_std = |a: f32| std::f32::consts::FRAC_PI_2 - a;
+        let point = |a: f32| (cx + radius * a.cos(), cy + radius * a.sin());
+
+        let full_circle = end - start >= std::f32::consts::TAU - f32::EPSILON;
+        let (sx, sy) = point(to_std(start));
+        if full_circle {
+            let _ = writeln!(self.content, "{:.2} {:.2} m", sx, sy);
+        } else {
+            let _ = writeln!(self.content, "{:.2} {:.2} m {:.2} {:.2} l", cx, cy, sx, sy);
+        }
+
+        // Approximate the arc with cubic Béziers of at most a quarter turn each.
+        let segments = ((end - start) / std::f32::consts::FRAC_PI_2).ceil().max(1.0) as usize;
+        let step = (end - start) / segments as f32;
+        for i in 0..segments {
+            let a0 = to_std(start + step * i as f32);
+            let a1 = to_std(start + step * (i + 1) as f32);
+            let k = 4.0 / 3.0 * ((a1 - a0) / 4.0).tan();
+            let (x0, y0) = point(a0);
+            let (x3, y3) = point(a1);
+            let (x1, y1) = (x0 - k * radius * a0.sin(), y0 + k * radius * a0.cos());
+            let (x2, y2) = (x3 + k * radius * a1.sin(), y3 - k * radius * a1.cos());
+            let _ = writeln!(
+                self.content,
+                "{:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c",
+                x1, y1, x2, y2, x3, y3
+            );
+        }
+        self.content.push_str("h f\n");
+    }
+}
+
+/// An in-memory PDF document made of [`Page`]s.
+#[derive(Debug)]
+pub struct PdfDocument {
+    title: String,
+    pages: Vec<Page>,
+}
+
+impl PdfDocument {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            pages: Vec::new(),
+        }
+    }
+
+    pub fn add_page(&mut self) -> &mut Page {
+        self.pages.push(Page::default());
+        self.pages.last_mut().expect("page was just pushed")
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    pub fn pages_mut(&mut self) -> impl Iterator<Item = &mut Page> {
+        self.pages.iter_mut()
+    }
+
+    /// Serializes the document into PDF bytes.
+    pub fn finish(mut self) -> Vec<u8> {
+        if self.pages.is_empty() {
+            self.add_page();
+        }
+
+        // Object layout: 1 catalog, 2 page tree, 3/4 fonts, 5 info,
+        // then a (page, content stream) pair per page.
+        let first_page_obj = 6;
+        let page_ids: Vec<usize> = (0..self.pages.len())
+            .map(|i| first_page_obj + i * 2)
+            .collect();
+
+        let mut objects: Vec<String> = Vec::with_capacity(5 + self.pages.len() * 2);
+        objects.push("<< /Type /Catalog /Pages 2 0 R >>".to_string());
+        objects.push(format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids
+                .iter()
+                .map(|id| format!("{} 0 R", id))
+                .collect::<Vec<_>>()
+                .join(" "),
+            page_ids.len()
+        ));
+        objects.push(
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                .to_string(),
+        );
+        objects.push(
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+                .to_string(),
+        );
+        objects.push(format!(
+            "<< /Title ({}) /Producer (Buster) >>",
+            escape_text(&self.title)
+        ));
+
+        for (page, page_id) in self.pages.iter().zip(&page_ids) {
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                PAGE_WIDTH,
+                PAGE_HEIGHT,
+                page_id + 1
+            ));
+            objects.push(format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                page.content.len(),
+                page.content
+            ));
+        }
+
+        let mut out: Vec<u8> = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).as_bytes());
+        }
+
+        let xref_offset = out.len();
+        let mut trailer = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(trailer, "{:010} 00000 n ", offset);
+        }
+        let _ = write!(
+            trailer,
+            "trailer\n<< /Size {} /Root 1 0 R /Info 5 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_offset
+        );
+        out.extend_from_slice(trailer.as_bytes());
+        out
+    }
+}
+
+/// Escapes text for a PDF literal string using WinAnsi (Latin-1) encoding.
+/// Characters outside Latin-1 are replaced with `?`.
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' | '\r' | '\t' => escaped.push(' '),
+            ' '..='~' => escaped.push(c),
+            '\u{a0}'..='\u{ff}' => {
+                let _ = write!(escaped, "\\{:03o}", c as u32);
+            }
+            _ => escaped.push('?'),
+        }
+    }
+    escaped
+}
+
+/// Helvetica advance widths (per 1000 em) for ASCII 32..=126.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, // ' '..'/'
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, // '0'..'9'
+    278, 278, 584, 584, 584, 556, 1015, // ':'..'@'
+    667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, // 'A'..'M'
+    722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, // 'N'..'Z'
+    278, 278, 278, 469, 556, 333, // '['..'`'
+    556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, // 'a'..'m'
+    556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, // 'n'..'z'
+    334, 260, 334, 584, // '{'..'~'
+];
+
+/// Approximate rendered width of `text` in points.
+pub fn text_width(text: &str, size: f32, font: Font) -> f32 {
+    let units: u32 = text
+        .chars()
+        .map(|c| match c {
+            ' '..='~' => HELVETICA_WIDTHS[c as usize - 32] as u32,
+            _ => 556,
+        })
+        .sum();
+    let bold_factor = match font {
+        Font::Regular => 1.0,
+        Font::Bold => 1.06,
+    };
+    units as f32 / 1000.0 * size * bold_factor
+}
+
+/// Shortens `text` with a trailing ellipsis so it fits into `max_width`.
+pub fn truncate_to_width(text: &str, size: f32, font: Font, max_width: f32) -> String {
+    if text_width(text, size, font) <= max_width {
+        return text.to_string();
+    }
+    let mut truncated: String = text.to_string();
+    while !truncated.is_empty() {
+        truncated.pop();
+        let candidate = format!("{}...", truncated.trim_end());
+        if text_width(&candidate, size, font) <= max_width {
+            return candidate;
+        }
+    }
+    String::new()
+}
+
+/// Greedy word wrap into at most `max_lines` lines; the last line is truncated.
+pub fn wrap_text(text: &str, size: f32, font: Font, max_width: f32, max_lines: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{} {}", current, word)
+        };
+        if text_width(&candidate, size, font) <= max_width || current.is_empty() {
+            current = candidate;
+        } else {
+            lines.push(std::mem::replace(&mut current, word.to_string()));
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    if lines.len() > max_lines {
+        let rest = lines.split_off(max_lines.saturating_sub(1)).join(" ");
+        lines.push(format!("{}...", rest));
+    }
+    lines
+        .into_iter()
+        .map(|line| truncate_to_width(&line, size, font, max_width))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("a(b)c\\"), "a\\(b\\)c\\\\");
+        assert_eq!(escape_text("café"), "caf\\351");
+        assert_eq!(escape_text("日本"), "??");
+    }
+
+    #[test]
+    fn test_color_from_hex() {
+        assert_eq!(Color::from_hex("#ffffff"), Some(Color::rgb(255, 255, 255)));
+        assert_eq!(Color::from_hex("#f00"), Some(Color::rgb(255, 0, 0)));
+        assert_eq!(Color::from_hex("blue"), None);
+    }
+
+    #[test]
+    fn test_truncate_to_width() {
+        let text = "Monthly recurring revenue by region";
+        let truncated = truncate_to_width(text, 10.0, Font::Regular, 80.0);
+        assert!(truncated.ends_with("..."));
+        assert!(text_width(&truncated, 10.0, Font::Regular) <= 80.0);
+        assert_eq!(truncate_to_width("MRR", 10.0, Font::Regular, 80.0), "MRR");
+    }
+
+    #[test]
+    fn test_wrap_text_limits_lines() {
+        let lines = wrap_text(
+            "one two three four five six seven eight nine ten",
+            10.0,
+            Font::Regular,
+            50.0,
+            2,
+        );
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].ends_with("..."));
+    }
+
+    #[test]
+    fn test_finish_produces_valid_structure() {
+        let mut doc = PdfDocument::new("Test");
+        doc.add_page().text(10.0, 20.0, 12.0, Font::Bold, Color::BLACK, "Hello");
+        doc.add_page();
+        let bytes = doc.finish();
+        let text = String::from_utf8_lossy(&bytes);
+
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.contains("/Count 2"));
+        assert!(text.contains("(Hello) Tj"));
+        assert!(text.trim_end().ends_with("%%EOF"));
+
+        // startxref must point at the xref table.
+        let startxref: usize = text
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|s| s.lines().next())
+            .and_then(|s| s.parse().ok())
+            .unwrap();
+        assert!(bytes[startxref..].starts_with(b"xref"));
+    }
+}
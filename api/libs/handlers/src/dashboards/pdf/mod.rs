@@ -0,0 +1,195 @@
+//! Paginated PDF rendering of a dashboard.
+//!
+//! Rows keep the dashboard layout: tile widths come from `column_sizes`
+//! (a 12-column grid) and heights from `row_height`. A row is never split
+//! across pages; if it doesn't fit it starts a new page.
+
+mod charts;
+mod document;
+
+use chrono::{DateTime, Utc};
+use indexmap::IndexMap;
+
+pub use charts::{DataRow, PdfTile};
+use charts::{draw_tile, Rect};
+use document::{text_width, truncate_to_width, wrap_text, Color, Font, PdfDocument, PAGE_HEIGHT, PAGE_WIDTH};
+
+const MARGIN: f32 = 36.0;
+const FOOTER_HEIGHT: f32 = 16.0;
+const TILE_GAP: f32 = 8.0;
+const GRID_COLUMNS: u32 = 12;
+/// Dashboard row heights are in screen pixels (320-550); scale them to print.
+const ROW_HEIGHT_SCALE: f32 = 0.5;
+const DEFAULT_ROW_HEIGHT: u32 = 320;
+
+#[derive(Debug, Clone)]
+pub struct PdfRow {
+    pub row_height: Option<u32>,
+    pub column_sizes: Vec<u32>,
+    pub tiles: Vec<PdfTile>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DashboardPdfContent {
+    pub name: String,
+    pub description: Option<String>,
+    pub version_number: i32,
+    pub variables: IndexMap<String, String>,
+    pub generated_at: DateTime<Utc>,
+    pub rows: Vec<PdfRow>,
+}
+
+pub fn render_dashboard_pdf(content: &DashboardPdfContent) -> Vec<u8> {
+    let mut doc = PdfDocument::new(content.name.clone());
+    let content_width = PAGE_WIDTH - 2.0 * MARGIN;
+    let content_bottom = PAGE_HEIGHT - MARGIN - FOOTER_HEIGHT;
+
+    let page = doc.add_page();
+    let mut cursor = MARGIN + 18.0;
+    let title = truncate_to_width(&content.name, 18.0, Font::Bold, content_width);
+    page.text(MARGIN, cursor, 18.0, Font::Bold, Color::BLACK, &title);
+    cursor += 6.0;
+
+    if let Some(description) = content.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        for line in wrap_text(description, 9.0, Font::Regular, content_width, 3) {
+            cursor += 12.0;
+            page.text(MARGIN, cursor, 9.0, Font::Regular, Color::DARK_GRAY, &line);
+        }
+    }
+
+    cursor += 14.0;
+    let meta = format!(
+        "Version {}  |  Generated {}",
+        content.version_number,
+        content.generated_at.format("%Y-%m-%d %H:%M UTC")
+    );
+    page.text(MARGIN, cursor, 8.0, Font::Regular, Color::GRAY, &meta);
+
+    if !content.variables.is_empty() {
+        let applied = content
+            .variables
+            .iter()
+            .map(|(name, value)| format!("{} = {}", name, value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let label = "Applied variables: ";
+        let label_width = text_width(label, 8.0, Font::Bold);
+        for (i, line) in wrap_text(&applied, 8.0, Font::Regular, content_width - label_width, 3)
+            .iter()
+            .enumerate()
+        {
+            cursor += 11.0;
+            if i == 0 {
+                page.text(MARGIN, cursor, 8.0, Font::Bold, Color::DARK_GRAY, label);
+            }
+            page.text(MARGIN + label_width, cursor, 8.0, Font::Regular, Color::DARK_GRAY, line);
+        }
+    }
+    cursor += 12.0;
+
+    let mut page = page;
+    for row in &content.rows {
+        let height = row.row_height.unwrap_or(DEFAULT_ROW_HEIGHT) as f32 * ROW_HEIGHT_SCALE;
+        if cursor + height > content_bottom {
+            page = doc.add_page();
+            cursor = MARGIN;
+        }
+
+        let sizes = normalized_column_sizes(&row.column_sizes, row.tiles.len());
+        let usable_width = content_width - TILE_GAP * (row.tiles.len().saturating_sub(1)) as f32;
+        let mut x = MARGIN;
+        for (tile, size) in row.tiles.iter().zip(sizes) {
+            let width = usable_width * size as f32 / GRID_COLUMNS as f32;
+            draw_tile(
+                page,
+                Rect {
+                    x,
+                    y: cursor,
+                    width,
+                    height,
+                },
+                tile,
+            );
+            x += width + TILE_GAP;
+        }
+        cursor += height + TILE_GAP;
+    }
+
+    let total_pages = doc.page_count();
+    let footer_y = PAGE_HEIGHT - MARGIN + 4.0;
+    let footer_name = truncate_to_width(&content.name, 7.0, Font::Regular, content_width / 2.0);
+    for (i, page) in doc.pages_mut().enumerate() {
+        page.text(MARGIN, footer_y, 7.0, Font::Regular, Color::GRAY, &footer_name);
+        page.text_right(
+            PAGE_WIDTH - MARGIN,
+            footer_y,
+            7.0,
+            Font::Regular,
+            Color::GRAY,
+            &format!("Page {} of {}", i + 1, total_pages),
+        );
+    }
+
+    doc.finish()
+}
+
+/// Returns one 12-grid column size per tile, falling back to an even split
+/// when the stored sizes don't line up with the tiles.
+fn normalized_column_sizes(column_sizes: &[u32], tile_count: usize) -> Vec<u32> {
+    if tile_count == 0 {
+        return Vec::new();
+    }
+    if column_sizes.len() == tile_count && column_sizes.iter().sum::<u32>() == GRID_COLUMNS {
+        return column_sizes.to_vec();
+    }
+    let even = GRID_COLUMNS / tile_count as u32;
+    let mut sizes = vec![even; tile_count];
+    sizes[tile_count - 1] += GRID_COLUMNS - even * tile_count as u32;
+    sizes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tile(title: &str) -> PdfTile {
+        PdfTile {
+            title: title.to_string(),
+            description: None,
+            chart_config: None,
+            data: Vec::new(),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_normalized_column_sizes() {
+        assert_eq!(normalized_column_sizes(&[4, 8], 2), vec![4, 8]);
+        assert_eq!(normalized_column_sizes(&[], 3), vec![4, 4, 4]);
+        assert_eq!(normalized_column_sizes(&[6, 6], 1), vec![12]);
+        assert_eq!(normalized_column_sizes(&[3, 3, 3], 0), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_rows_paginate() {
+        let content = DashboardPdfContent {
+            name: "Board Pack".to_string(),
+            description: Some("Quarterly numbers".to_string()),
+            version_number: 3,
+            variables: IndexMap::from([("region".to_string(), "EMEA".to_string())]),
+            generated_at: Utc::now(),
+            rows: (0..4)
+                .map(|i| PdfRow {
+                    row_height: Some(550),
+                    column_sizes: vec![12],
+                    tiles: vec![tile(&format!("Tile {}", i))],
+                })
+                .collect(),
+        };
+
+        let pdf = String::from_utf8_lossy(&render_dashboard_pdf(&content)).into_owned();
+        assert!(pdf.contains("/Count 4"));
+        assert!(pdf.contains("(Page 4 of 4) Tj"));
+        assert!(pdf.contains("(region = EMEA) Tj"));
+    }
+}
@@ -0,0 +1,42 @@
+use anyhow::Result;
+use database::enums::AssetType;
+use database::helpers::version_restores::fetch_version_restores;
+use database::models::AssetVersionRestore;
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use super::{
+    get_dashboard_handler, update_dashboard_handler, BusterDashboardResponse,
+    DashboardUpdateRequest,
+};
+
+/// Handler to restore a dashboard to a previous version
+///
+/// The restored content is saved as a new version; the restore itself is
+/// recorded in the audit trail by `update_dashboard_handler`.
+pub async fn restore_dashboard_version_handler(
+    dashboard_id: &Uuid,
+    user: &AuthenticatedUser,
+    version_number: i32,
+) -> Result<BusterDashboardResponse> {
+    update_dashboard_handler(
+        *dashboard_id,
+        DashboardUpdateRequest {
+            restore_to_version: Some(version_number),
+            ..Default::default()
+        },
+        user,
+    )
+    .await
+}
+
+/// Handler to list the restores performed on a dashboard, most recent first
+pub async fn list_dashboard_version_restores_handler(
+    dashboard_id: &Uuid,
+    user: &AuthenticatedUser,
+) -> Result<Vec<AssetVersionRestore>> {
+    // Reuses the get handler's permission checks
+    get_dashboard_handler(dashboard_id, user, None, None).await?;
+
+    fetch_version_restores(dashboard_id, AssetType::DashboardFile).await
+}
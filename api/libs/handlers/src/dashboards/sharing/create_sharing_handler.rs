@@ -1,9 +1,10 @@
 use anyhow::{anyhow, Result};
 use database::{
-    enums::{AssetPermissionRole, AssetType},
+    enums::{AssetPermissionRole, AssetType, WebhookEventType},
     helpers::dashboard_files::fetch_dashboard_file_with_permission,
 };
 use middleware::AuthenticatedUser;
+use serde_json::json;
 use sharing::{
     check_permission_access,
     create_asset_permission::create_share_by_email,
@@ -11,6 +12,8 @@ use sharing::{
 use tracing::{error, info};
 use uuid::Uuid;
 
+use crate::webhooks::emit_webhook_event;
+
 /// Creates sharing permissions for a dashboard with specified users
 ///
 /// # Arguments
@@ -61,6 +64,10 @@ pub async fn create_dashboard_sharing_handler(
 
     // Process each email and create sharing permissions
     let recipient_count = emails_and_roles.len();
+    let shared_with: Vec<_> = emails_and_roles
+        .iter()
+        .map(|(email, role)| json!({ "email": email, "role": role }))
+        .collect();
     for (email, role) in emails_and_roles {
         if !email.contains('@') {
             error!("Invalid email format: {}", email);
@@ -94,6 +101,18 @@ pub async fn create_dashboard_sharing_handler(
         "Successfully created dashboard sharing permissions"
     );
 
+    let dashboard_file = dashboard_with_permission.dashboard_file;
+    emit_webhook_event(
+        dashboard_file.organization_id,
+        WebhookEventType::DashboardShared,
+        json!({
+            "id": dashboard_file.id,
+            "name": dashboard_file.name,
+            "shared_with": shared_with,
+            "actor_id": user.id,
+        }),
+    );
+
     Ok(())
 }
 
@@ -1,7 +1,8 @@
 use anyhow::{anyhow, Result};
 use chrono::Utc;
-use database::enums::{AssetPermissionRole, Verification};
+use database::enums::{AssetPermissionRole, AssetType, Verification};
 use database::helpers::dashboard_files::fetch_dashboard_file_with_permission;
+use database::helpers::version_restores::record_version_restore;
 use database::models::MetricFileToDashboardFile;
 use database::pool::get_pg_pool;
 use database::schema::{dashboard_files, metric_files_to_dashboard_files};
@@ -301,6 +302,19 @@ pub async fn update_dashboard_handler(
             .await?;
     }
 
+    // Keep an audit trail of restores
+    if let Some(restored_version) = request.restore_to_version {
+        record_version_restore(
+            &dashboard_id,
+            AssetType::DashboardFile,
+            &dashboard_with_permission.dashboard_file.organization_id,
+            restored_version,
+            current_version_history.get_version_number(),
+            &user.id,
+        )
+        .await?;
+    }
+
     // Extract metric IDs from the updated dashboard content
     let metric_ids = extract_metric_ids_from_dashboard(&dashboard_yml);
 
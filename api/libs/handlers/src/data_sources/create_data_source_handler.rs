@@ -4,10 +4,11 @@ use diesel::{ExpressionMethods, QueryDsl};
 use diesel_async::RunQueryDsl;
 use middleware::types::AuthenticatedUser;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use uuid::Uuid;
 
 use database::{
-    enums::{DataSourceOnboardingStatus, UserOrganizationRole},
+    enums::{DataSourceOnboardingStatus, UserOrganizationRole, WebhookEventType},
     models::DataSource,
     pool::get_pg_pool,
     schema::data_sources,
@@ -16,6 +17,8 @@ use database::{
 use query_engine::credentials::Credential;
 use stored_values::schema::create_search_schema;
 
+use crate::webhooks::emit_webhook_event;
+
 #[derive(Deserialize)]
 pub struct CreateDataSourceRequest {
     pub name: String,
@@ -121,6 +124,17 @@ pub async fn create_data_source_handler(
         .await
         .map_err(|e| anyhow!("Error storing credentials in vault: {}", e))?;
 
+    emit_webhook_event(
+        data_source.organization_id,
+        WebhookEventType::DataSourceCreated,
+        json!({
+            "id": data_source.id,
+            "name": data_source.name,
+            "type": data_source.type_.to_string(),
+            "actor_id": user.id,
+        }),
+    );
+
     // Build response using AuthenticatedUser info
     let response = CreateDataSourceResponse {
         id: data_source.id.to_string(),
@@ -4,7 +4,8 @@ use diesel::{ExpressionMethods, QueryDsl};
 use diesel_async::RunQueryDsl;
 use middleware::types::AuthenticatedUser;
 use uuid::Uuid;
-use database::enums::UserOrganizationRole;
+use database::enums::{UserOrganizationRole, WebhookEventType};
+use serde_json::json;
 
 use database::{
     models::DataSource,
@@ -13,6 +14,8 @@ use database::{
     vault::delete_secret,
 };
 
+use crate::webhooks::emit_webhook_event;
+
 pub async fn delete_data_source_handler(
     user: &AuthenticatedUser,
     data_source_id: &Uuid,
@@ -33,7 +36,7 @@ pub async fn delete_data_source_handler(
     let mut conn = get_pg_pool().get().await?;
 
     // Get the data source to verify it exists and belongs to the user's organization
-    let _data_source = data_sources::table
+    let data_source = data_sources::table
         .filter(data_sources::id.eq(data_source_id))
         .filter(data_sources::organization_id.eq(user_org.id))
         .filter(data_sources::deleted_at.is_null())
@@ -54,5 +57,16 @@ pub async fn delete_data_source_handler(
         .await
         .map_err(|e| anyhow!("Error deleting credentials from vault: {}", e))?;
 
+    emit_webhook_event(
+        data_source.organization_id,
+        WebhookEventType::DataSourceDeleted,
+        json!({
+            "id": data_source.id,
+            "name": data_source.name,
+            "type": data_source.type_.to_string(),
+            "actor_id": user.id,
+        }),
+    );
+
     Ok(())
 }
\ No newline at end of file
@@ -6,10 +6,11 @@ use diesel::{AsChangeset, ExpressionMethods, QueryDsl};
 use diesel_async::RunQueryDsl;
 use middleware::types::AuthenticatedUser;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use uuid::Uuid;
 
 use database::{
-    enums::{DataSourceType, UserOrganizationRole},
+    enums::{DataSourceType, UserOrganizationRole, WebhookEventType},
     models::{DataSource, User},
     pool::get_pg_pool,
     schema::{data_sources, users},
@@ -17,6 +18,8 @@ use database::{
 };
 use query_engine::credentials::Credential;
 
+use crate::webhooks::emit_webhook_event;
+
 /// Request for updating a data source
 #[derive(Debug, Deserialize)]
 pub struct UpdateDataSourceRequest {
@@ -415,6 +418,18 @@ pub async fn update_data_source_handler(
             .map_err(|e| anyhow!("Error updating credentials in vault: {}", e))?;
     }
 
+    emit_webhook_event(
+        data_source.organization_id,
+        WebhookEventType::DataSourceUpdated,
+        json!({
+            "id": data_source.id,
+            "name": data_source.name,
+            "type": data_source.type_.to_string(),
+            "credentials_changed": request.credential.is_some(),
+            "actor_id": user.id,
+        }),
+    );
+
     // Get the creator's information
     let creator = users::table
         .filter(users::id.eq(data_source.created_by))
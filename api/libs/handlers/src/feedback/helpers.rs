@@ -0,0 +1,196 @@
+use anyhow::{anyhow, Result};
+use database::{
+    enums::{FeedbackRating, UserOrganizationRole},
+    helpers::metric_files::fetch_metric_file,
+    models::AssetFeedback,
+    pool::get_pg_pool,
+    schema::{asset_feedback, messages, messages_to_files, metric_files},
+};
+use diesel::{ExpressionMethods, JoinOnDsl, QueryDsl};
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use super::types::ReviewFeedbackRequest;
+use crate::organizations::organization_models_handler::organization_role;
+
+/// Errors unless the user may review feedback and manage verified examples
+pub fn ensure_feedback_reviewer(user: &AuthenticatedUser, organization_id: &Uuid) -> Result<()> {
+    match organization_role(user, organization_id)? {
+        UserOrganizationRole::WorkspaceAdmin | UserOrganizationRole::DataAdmin => Ok(()),
+        _ => Err(anyhow!("User is not a workspace or data admin")),
+    }
+}
+
+/// Trims a value, treating blank strings as missing
+pub fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Loads a feedback entry of the organization
+pub async fn fetch_feedback(organization_id: &Uuid, feedback_id: &Uuid) -> Result<AssetFeedback> {
+    let mut conn = get_pg_pool().get().await?;
+
+    asset_feedback::table
+        .filter(asset_feedback::id.eq(feedback_id))
+        .filter(asset_feedback::organization_id.eq(organization_id))
+        .filter(asset_feedback::deleted_at.is_null())
+        .first::<AssetFeedback>(&mut conn)
+        .await
+        .map_err(|e| match e {
+            diesel::NotFound => anyhow!("Feedback not found"),
+            e => anyhow!("Failed to load feedback: {}", e),
+        })
+}
+
+/// The question of the most recent message that produced the metric
+pub async fn fetch_metric_question(metric_id: &Uuid) -> Result<Option<String>> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let question = messages_to_files::table
+        .inner_join(messages::table)
+        .filter(messages_to_files::file_id.eq(metric_id))
+        .filter(messages_to_files::deleted_at.is_null())
+        .filter(messages::deleted_at.is_null())
+        .order(messages::created_at.desc())
+        .select(messages::request_message)
+        .first::<Option<String>>(&mut conn)
+        .await
+        .map(non_empty);
+
+    match question {
+        Ok(question) => Ok(question),
+        Err(diesel::NotFound) => Ok(None),
+        Err(e) => Err(anyhow!("Failed to load the metric's question: {}", e)),
+    }
+}
+
+/// The SQL behind the answer the feedback is about: the metric's, or for a
+/// message the first metric it produced
+pub async fn fetch_answer_sql(feedback: &AssetFeedback) -> Result<Option<String>> {
+    let metric_id = match (feedback.metric_file_id, feedback.message_id) {
+        (Some(metric_id), _) => Some(metric_id),
+        (None, Some(message_id)) => {
+            let mut conn = get_pg_pool().get().await?;
+            messages_to_files::table
+                .inner_join(metric_files::table.on(metric_files::id.eq(messages_to_files::file_id)))
+                .filter(messages_to_files::message_id.eq(message_id))
+                .filter(messages_to_files::deleted_at.is_null())
+                .filter(metric_files::deleted_at.is_null())
+                .order(messages_to_files::created_at.asc())
+                .select(metric_files::id)
+                .first::<Uuid>(&mut conn)
+                .await
+                .ok()
+        }
+        (None, None) => None,
+    };
+
+    match metric_id {
+        Some(metric_id) => Ok(fetch_metric_file(&metric_id)
+            .await?
+            .map(|metric_file| metric_file.content.sql)),
+        None => Ok(None),
+    }
+}
+
+/// Picks the question and SQL a promoted example is made of
+///
+/// The reviewer's values win over what was captured with the feedback. Without
+/// a correction, only positively rated answers may be promoted as they are;
+/// `answer_sql` is the SQL of that answer.
+pub fn resolve_example(
+    request: &ReviewFeedbackRequest,
+    feedback: &AssetFeedback,
+    answer_sql: Option<String>,
+) -> Result<(String, String)> {
+    let question = non_empty(request.question.clone())
+        .or_else(|| non_empty(feedback.question.clone()))
+        .ok_or_else(|| anyhow!("Invalid review: a question is required to promote feedback"))?;
+
+    let sql = non_empty(request.sql.clone())
+        .or_else(|| non_empty(feedback.correction_sql.clone()))
+        .or_else(|| match feedback.rating {
+            FeedbackRating::Positive => non_empty(answer_sql),
+            FeedbackRating::Negative => None,
+        })
+        .ok_or_else(|| anyhow!("Invalid review: no corrected SQL to promote"))?;
+
+    Ok((question, sql))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use database::enums::FeedbackStatus;
+
+    use crate::feedback::types::FeedbackReviewAction;
+
+    fn feedback(rating: FeedbackRating, correction_sql: Option<&str>) -> AssetFeedback {
+        AssetFeedback {
+            id: Uuid::new_v4(),
+            organization_id: Uuid::new_v4(),
+            message_id: Some(Uuid::new_v4()),
+            metric_file_id: None,
+            rating,
+            category: None,
+            comment: None,
+            correction_sql: correction_sql.map(str::to_string),
+            question: Some("Revenue by month".to_string()),
+            status: FeedbackStatus::Pending,
+            reviewed_by: None,
+            reviewed_at: None,
+            created_by: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn promote(question: Option<&str>, sql: Option<&str>) -> ReviewFeedbackRequest {
+        ReviewFeedbackRequest {
+            action: FeedbackReviewAction::Promote,
+            question: question.map(str::to_string),
+            sql: sql.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_resolve_example() {
+        let answer_sql = Some("select 1".to_string());
+
+        // A correction beats the answer's SQL
+        let negative = feedback(FeedbackRating::Negative, Some("select 2"));
+        assert_eq!(
+            resolve_example(&promote(None, None), &negative, answer_sql.clone()).unwrap(),
+            ("Revenue by month".to_string(), "select 2".to_string())
+        );
+
+        // The reviewer's edits beat both
+        assert_eq!(
+            resolve_example(
+                &promote(Some("Monthly revenue"), Some("select 3")),
+                &negative,
+                None
+            )
+            .unwrap(),
+            ("Monthly revenue".to_string(), "select 3".to_string())
+        );
+
+        // Wrong answers without a correction have nothing to promote
+        let uncorrected = feedback(FeedbackRating::Negative, None);
+        assert!(resolve_example(&promote(None, None), &uncorrected, answer_sql.clone()).is_err());
+
+        // Right answers are promoted as they are
+        let positive = feedback(FeedbackRating::Positive, Some("  "));
+        assert_eq!(
+            resolve_example(&promote(None, None), &positive, answer_sql)
+                .unwrap()
+                .1,
+            "select 1"
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use anyhow::{anyhow, Result};
+use database::{
+    enums::FeedbackStatus, models::AssetFeedback, pool::get_pg_pool, schema::asset_feedback,
+};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use super::helpers::ensure_feedback_reviewer;
+use super::types::{FeedbackResponse, ListFeedbackQuery};
+
+/// Handler to list the organization's feedback for review, oldest first
+///
+/// Restricted to workspace and data admins. Shows pending feedback unless
+/// another status is asked for.
+pub async fn list_feedback_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+    query: ListFeedbackQuery,
+) -> Result<Vec<FeedbackResponse>> {
+    ensure_feedback_reviewer(user, &organization_id)?;
+
+    let mut conn = get_pg_pool().get().await?;
+
+    let mut feedback_query = asset_feedback::table
+        .filter(asset_feedback::organization_id.eq(organization_id))
+        .filter(asset_feedback::status.eq(query.status.unwrap_or(FeedbackStatus::Pending)))
+        .filter(asset_feedback::deleted_at.is_null())
+        .into_boxed();
+    if let Some(rating) = query.rating {
+        feedback_query = feedback_query.filter(asset_feedback::rating.eq(rating));
+    }
+
+    let feedback = feedback_query
+        .order(asset_feedback::created_at.asc())
+        .load::<AssetFeedback>(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to load feedback: {}", e))?;
+
+    Ok(feedback.into_iter().map(FeedbackResponse::from).collect())
+}
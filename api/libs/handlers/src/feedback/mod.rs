@@ -0,0 +1,12 @@
+mod helpers;
+mod list_feedback_handler;
+mod review_feedback_handler;
+mod submit_feedback_handler;
+mod types;
+mod verified_examples_handler;
+
+pub use list_feedback_handler::*;
+pub use review_feedback_handler::*;
+pub use submit_feedback_handler::*;
+pub use types::*;
+pub use verified_examples_handler::*;
@@ -0,0 +1,89 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use database::{
+    enums::FeedbackStatus,
+    models::VerifiedExample,
+    pool::get_pg_pool,
+    schema::{asset_feedback, verified_examples},
+};
+use diesel::ExpressionMethods;
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use super::helpers::{ensure_feedback_reviewer, fetch_answer_sql, fetch_feedback, resolve_example};
+use super::types::{FeedbackReviewAction, ReviewFeedbackRequest, ReviewFeedbackResponse};
+
+/// Handler to promote or dismiss a piece of feedback
+///
+/// Restricted to workspace and data admins. Promoting saves the question and
+/// its corrected SQL (or, for a positive rating, the answer's SQL) as a
+/// verified example the agent retrieves for similar questions.
+pub async fn review_feedback_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+    feedback_id: Uuid,
+    request: ReviewFeedbackRequest,
+) -> Result<ReviewFeedbackResponse> {
+    ensure_feedback_reviewer(user, &organization_id)?;
+
+    let mut feedback = fetch_feedback(&organization_id, &feedback_id).await?;
+    if feedback.status == FeedbackStatus::Promoted {
+        return Err(anyhow!("Invalid review: feedback was already promoted"));
+    }
+
+    let now = Utc::now();
+    let example = match request.action {
+        FeedbackReviewAction::Promote => {
+            let answer_sql = fetch_answer_sql(&feedback).await?;
+            let (question, sql) = resolve_example(&request, &feedback, answer_sql)?;
+            Some(VerifiedExample {
+                id: Uuid::new_v4(),
+                organization_id,
+                question,
+                sql,
+                feedback_id: Some(feedback.id),
+                created_by: user.id,
+                created_at: now,
+                updated_at: now,
+                deleted_at: None,
+            })
+        }
+        FeedbackReviewAction::Dismiss => None,
+    };
+
+    feedback.status = match request.action {
+        FeedbackReviewAction::Promote => FeedbackStatus::Promoted,
+        FeedbackReviewAction::Dismiss => FeedbackStatus::Dismissed,
+    };
+    feedback.reviewed_by = Some(user.id);
+    feedback.reviewed_at = Some(now);
+    feedback.updated_at = now;
+
+    let mut conn = get_pg_pool().get().await?;
+
+    if let Some(example) = &example {
+        diesel::insert_into(verified_examples::table)
+            .values(example)
+            .execute(&mut conn)
+            .await
+            .map_err(|e| anyhow!("Failed to save verified example: {}", e))?;
+    }
+
+    diesel::update(asset_feedback::table)
+        .filter(asset_feedback::id.eq(feedback.id))
+        .set((
+            asset_feedback::status.eq(feedback.status),
+            asset_feedback::reviewed_by.eq(feedback.reviewed_by),
+            asset_feedback::reviewed_at.eq(feedback.reviewed_at),
+            asset_feedback::updated_at.eq(feedback.updated_at),
+        ))
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to update feedback: {}", e))?;
+
+    Ok(ReviewFeedbackResponse {
+        feedback: feedback.into(),
+        example: example.map(Into::into),
+    })
+}
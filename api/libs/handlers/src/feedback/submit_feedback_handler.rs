@@ -0,0 +1,212 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use database::{
+    enums::{AssetPermissionRole, FeedbackStatus},
+    helpers::{chats::fetch_chat_with_permission, metric_files::fetch_metric_file},
+    models::AssetFeedback,
+    pool::get_pg_pool,
+    schema::{asset_feedback, messages},
+};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use sharing::check_permission_access;
+use uuid::Uuid;
+
+use super::helpers::{fetch_metric_question, non_empty};
+use super::types::{FeedbackResponse, SubmitFeedbackRequest};
+use crate::metrics::get_metric_handler;
+
+/// What a piece of feedback is about
+#[derive(Clone, Copy)]
+enum FeedbackTarget {
+    Message(Uuid),
+    Metric(Uuid),
+}
+
+/// Handler to rate a chat message
+///
+/// Anyone who can view the chat can give feedback. The question is captured
+/// with the feedback so it can later be promoted to a verified example.
+pub async fn submit_message_feedback_handler(
+    message_id: &Uuid,
+    user: &AuthenticatedUser,
+    request: SubmitFeedbackRequest,
+) -> Result<FeedbackResponse> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let (chat_id, question, created_by) = messages::table
+        .filter(messages::id.eq(message_id))
+        .filter(messages::deleted_at.is_null())
+        .select((
+            messages::chat_id,
+            messages::request_message,
+            messages::created_by,
+        ))
+        .first::<(Uuid, Option<String>, Uuid)>(&mut conn)
+        .await
+        .map_err(|e| match e {
+            diesel::NotFound => anyhow!("Message not found"),
+            e => anyhow!("Failed to load message: {}", e),
+        })?;
+    drop(conn);
+
+    let chat_with_permission = fetch_chat_with_permission(&chat_id, &user.id)
+        .await?
+        .ok_or_else(|| anyhow!("Chat not found"))?;
+    let has_permission = check_permission_access(
+        chat_with_permission.permission,
+        &[
+            AssetPermissionRole::CanView,
+            AssetPermissionRole::CanEdit,
+            AssetPermissionRole::FullAccess,
+            AssetPermissionRole::Owner,
+        ],
+        chat_with_permission.chat.organization_id,
+        &user.organizations,
+    );
+    if !has_permission && chat_with_permission.chat.created_by != user.id {
+        return Err(anyhow!("You don't have permission to view this chat"));
+    }
+
+    let feedback = save_feedback(
+        FeedbackTarget::Message(*message_id),
+        chat_with_permission.chat.organization_id,
+        non_empty(question),
+        user,
+        request,
+    )
+    .await?;
+
+    // The message's own feedback column mirrors its author's rating
+    if created_by == user.id {
+        let mut conn = get_pg_pool().get().await?;
+        diesel::update(messages::table)
+            .filter(messages::id.eq(message_id))
+            .set((
+                messages::feedback.eq(feedback.rating.to_str()),
+                messages::updated_at.eq(Utc::now()),
+            ))
+            .execute(&mut conn)
+            .await?;
+    }
+
+    Ok(feedback.into())
+}
+
+/// Handler to rate a metric
+///
+/// Anyone who can view the metric can give feedback. The question is taken
+/// from the chat message that produced the metric, falling back to its name.
+pub async fn submit_metric_feedback_handler(
+    metric_id: &Uuid,
+    user: &AuthenticatedUser,
+    request: SubmitFeedbackRequest,
+) -> Result<FeedbackResponse> {
+    // Reuses the get handler's permission checks
+    get_metric_handler(metric_id, user, None, None).await?;
+
+    let metric_file = fetch_metric_file(metric_id)
+        .await?
+        .ok_or_else(|| anyhow!("Metric file not found"))?;
+
+    let question = fetch_metric_question(metric_id)
+        .await?
+        .unwrap_or_else(|| metric_file.name.clone());
+
+    let feedback = save_feedback(
+        FeedbackTarget::Metric(*metric_id),
+        metric_file.organization_id,
+        Some(question),
+        user,
+        request,
+    )
+    .await?;
+
+    Ok(feedback.into())
+}
+
+/// Stores the user's feedback, replacing their earlier feedback on the same
+/// answer and sending it back to the review queue
+async fn save_feedback(
+    target: FeedbackTarget,
+    organization_id: Uuid,
+    question: Option<String>,
+    user: &AuthenticatedUser,
+    request: SubmitFeedbackRequest,
+) -> Result<AssetFeedback> {
+    let (message_id, metric_file_id) = match target {
+        FeedbackTarget::Message(id) => (Some(id), None),
+        FeedbackTarget::Metric(id) => (None, Some(id)),
+    };
+
+    let mut conn = get_pg_pool().get().await?;
+
+    let mut query = asset_feedback::table
+        .filter(asset_feedback::created_by.eq(user.id))
+        .filter(asset_feedback::deleted_at.is_null())
+        .into_boxed();
+    query = match target {
+        FeedbackTarget::Message(id) => query.filter(asset_feedback::message_id.eq(id)),
+        FeedbackTarget::Metric(id) => query.filter(asset_feedback::metric_file_id.eq(id)),
+    };
+    let existing = query
+        .first::<AssetFeedback>(&mut conn)
+        .await
+        .map(Some)
+        .or_else(|e| match e {
+            diesel::NotFound => Ok(None),
+            e => Err(anyhow!("Failed to load feedback: {}", e)),
+        })?;
+
+    let now = Utc::now();
+    let feedback = AssetFeedback {
+        id: existing
+            .as_ref()
+            .map_or_else(Uuid::new_v4, |feedback| feedback.id),
+        organization_id,
+        message_id,
+        metric_file_id,
+        rating: request.rating,
+        category: request.category,
+        comment: non_empty(request.comment),
+        correction_sql: non_empty(request.correction_sql),
+        question,
+        status: FeedbackStatus::Pending,
+        reviewed_by: None,
+        reviewed_at: None,
+        created_by: user.id,
+        created_at: existing
+            .as_ref()
+            .map_or(now, |feedback| feedback.created_at),
+        updated_at: now,
+        deleted_at: None,
+    };
+
+    if existing.is_some() {
+        diesel::update(asset_feedback::table)
+            .filter(asset_feedback::id.eq(feedback.id))
+            .set((
+                asset_feedback::rating.eq(feedback.rating),
+                asset_feedback::category.eq(feedback.category),
+                asset_feedback::comment.eq(&feedback.comment),
+                asset_feedback::correction_sql.eq(&feedback.correction_sql),
+                asset_feedback::question.eq(&feedback.question),
+                asset_feedback::status.eq(feedback.status),
+                asset_feedback::reviewed_by.eq(feedback.reviewed_by),
+                asset_feedback::reviewed_at.eq(feedback.reviewed_at),
+                asset_feedback::updated_at.eq(feedback.updated_at),
+            ))
+            .execute(&mut conn)
+            .await
+            .map_err(|e| anyhow!("Failed to save feedback: {}", e))?;
+    } else {
+        diesel::insert_into(asset_feedback::table)
+            .values(&feedback)
+            .execute(&mut conn)
+            .await
+            .map_err(|e| anyhow!("Failed to save feedback: {}", e))?;
+    }
+
+    Ok(feedback)
+}
@@ -0,0 +1,118 @@
+use chrono::{DateTime, Utc};
+use database::enums::{FeedbackCategory, FeedbackRating, FeedbackStatus};
+use database::models::{AssetFeedback, VerifiedExample};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Feedback on a message or metric; submitting again replaces the user's
+/// earlier feedback on the same answer
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubmitFeedbackRequest {
+    pub rating: FeedbackRating,
+    pub category: Option<FeedbackCategory>,
+    pub comment: Option<String>,
+    /// The SQL that would have answered the question correctly
+    pub correction_sql: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FeedbackResponse {
+    pub id: Uuid,
+    pub message_id: Option<Uuid>,
+    pub metric_id: Option<Uuid>,
+    pub rating: FeedbackRating,
+    pub category: Option<FeedbackCategory>,
+    pub comment: Option<String>,
+    pub correction_sql: Option<String>,
+    /// The question that produced the answer
+    pub question: Option<String>,
+    pub status: FeedbackStatus,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+}
+
+impl From<AssetFeedback> for FeedbackResponse {
+    fn from(feedback: AssetFeedback) -> Self {
+        Self {
+            id: feedback.id,
+            message_id: feedback.message_id,
+            metric_id: feedback.metric_file_id,
+            rating: feedback.rating,
+            category: feedback.category,
+            comment: feedback.comment,
+            correction_sql: feedback.correction_sql,
+            question: feedback.question,
+            status: feedback.status,
+            created_by: feedback.created_by,
+            created_at: feedback.created_at,
+            reviewed_by: feedback.reviewed_by,
+            reviewed_at: feedback.reviewed_at,
+        }
+    }
+}
+
+/// Filters the review queue; defaults to pending feedback
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ListFeedbackQuery {
+    pub status: Option<FeedbackStatus>,
+    pub rating: Option<FeedbackRating>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackReviewAction {
+    /// Save the question and SQL as a verified example
+    Promote,
+    Dismiss,
+}
+
+/// An admin's decision on a piece of feedback
+///
+/// When promoting, `question` and `sql` override what was captured with the
+/// feedback.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReviewFeedbackRequest {
+    pub action: FeedbackReviewAction,
+    pub question: Option<String>,
+    pub sql: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReviewFeedbackResponse {
+    pub feedback: FeedbackResponse,
+    /// The example created when the feedback was promoted
+    pub example: Option<VerifiedExampleResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VerifiedExampleResponse {
+    pub id: Uuid,
+    pub question: String,
+    pub sql: String,
+    /// The feedback the example was promoted from, if any
+    pub feedback_id: Option<Uuid>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<VerifiedExample> for VerifiedExampleResponse {
+    fn from(example: VerifiedExample) -> Self {
+        Self {
+            id: example.id,
+            question: example.question,
+            sql: example.sql,
+            feedback_id: example.feedback_id,
+            created_by: example.created_by,
+            created_at: example.created_at,
+        }
+    }
+}
+
+/// Adds a verified example directly, without going through feedback
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreateVerifiedExampleRequest {
+    pub question: String,
+    pub sql: String,
+}
@@ -0,0 +1,93 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use database::{
+    helpers::verified_examples::fetch_organization_examples, models::VerifiedExample,
+    pool::get_pg_pool, schema::verified_examples,
+};
+use diesel::ExpressionMethods;
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use super::helpers::{ensure_feedback_reviewer, non_empty};
+use super::types::{CreateVerifiedExampleRequest, VerifiedExampleResponse};
+use crate::organizations::organization_models_handler::organization_role;
+
+/// Handler to list the organization's verified examples, newest first
+pub async fn list_verified_examples_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+) -> Result<Vec<VerifiedExampleResponse>> {
+    organization_role(user, &organization_id)?;
+
+    Ok(fetch_organization_examples(&organization_id)
+        .await?
+        .into_iter()
+        .map(VerifiedExampleResponse::from)
+        .collect())
+}
+
+/// Handler to add a verified example without going through feedback
+///
+/// Restricted to workspace and data admins.
+pub async fn create_verified_example_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+    request: CreateVerifiedExampleRequest,
+) -> Result<VerifiedExampleResponse> {
+    ensure_feedback_reviewer(user, &organization_id)?;
+
+    let question = non_empty(Some(request.question))
+        .ok_or_else(|| anyhow!("Invalid question: must not be empty"))?;
+    let sql =
+        non_empty(Some(request.sql)).ok_or_else(|| anyhow!("Invalid sql: must not be empty"))?;
+
+    let now = Utc::now();
+    let example = VerifiedExample {
+        id: Uuid::new_v4(),
+        organization_id,
+        question,
+        sql,
+        feedback_id: None,
+        created_by: user.id,
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+    };
+
+    let mut conn = get_pg_pool().get().await?;
+    diesel::insert_into(verified_examples::table)
+        .values(&example)
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to save verified example: {}", e))?;
+
+    Ok(example.into())
+}
+
+/// Handler to remove a verified example so the agent stops using it
+///
+/// Restricted to workspace and data admins.
+pub async fn delete_verified_example_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+    example_id: Uuid,
+) -> Result<()> {
+    ensure_feedback_reviewer(user, &organization_id)?;
+
+    let mut conn = get_pg_pool().get().await?;
+    let deleted = diesel::update(verified_examples::table)
+        .filter(verified_examples::id.eq(example_id))
+        .filter(verified_examples::organization_id.eq(organization_id))
+        .filter(verified_examples::deleted_at.is_null())
+        .set(verified_examples::deleted_at.eq(Some(Utc::now())))
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to delete verified example: {}", e))?;
+
+    if deleted == 0 {
+        return Err(anyhow!("Verified example not found"));
+    }
+
+    Ok(())
+}
@@ -1,15 +1,20 @@
 pub mod chats;
 pub mod collections;
+pub mod comments;
 pub mod dashboards;
 pub mod data_sources;
 pub mod favorites;
+pub mod feedback;
+pub mod llm_usage;
 pub mod logs;
 pub mod messages;
 pub mod metrics;
 pub mod organizations;
 pub mod search;
+pub mod slack;
 pub mod users;
 pub mod utils;
+pub mod webhooks;
 
 // Re-export commonly used types and functions
 pub use chats::types as thread_types;
\ No newline at end of file
@@ -0,0 +1,50 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use uuid::Uuid;
+
+use database::helpers::llm_usage::{get_organization_llm_budget, get_organization_llm_spend};
+
+/// Midnight UTC on the first day of `now`'s month, when budgets reset
+pub fn current_budget_period_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now)
+}
+
+/// Errors if the organization has spent its monthly LLM budget
+///
+/// Organizations without a budget are never blocked.
+pub async fn ensure_within_llm_budget(organization_id: &Uuid) -> Result<()> {
+    let Some(budget) = get_organization_llm_budget(organization_id).await? else {
+        return Ok(());
+    };
+
+    let spend =
+        get_organization_llm_spend(organization_id, current_budget_period_start(Utc::now()))
+            .await?;
+
+    if spend >= budget.monthly_budget {
+        return Err(anyhow!(
+            "Monthly LLM budget exceeded: {:.2} of {:.2} USD spent",
+            spend,
+            budget.monthly_budget
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_budget_period_start() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 31, 23, 59, 59).unwrap();
+
+        assert_eq!(
+            current_budget_period_start(now),
+            Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap()
+        );
+    }
+}
@@ -0,0 +1,7 @@
+pub mod budget;
+pub mod pricing;
+pub mod recorder;
+
+pub use budget::*;
+pub use pricing::*;
+pub use recorder::*;
@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::env;
+use std::sync::LazyLock;
+
+use serde::Deserialize;
+
+/// Price of a model in USD per million tokens
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct ModelPrice {
+    pub input: f64,
+    #[serde(default)]
+    pub output: f64,
+}
+
+// Built-in list prices, overridable through `LLM_PRICE_TABLE`
+const DEFAULT_PRICES: &[(&str, f64, f64)] = &[
+    ("o4-mini", 1.10, 4.40),
+    ("o3-mini", 1.10, 4.40),
+    ("gpt-4o", 2.50, 10.00),
+    ("gpt-4o-mini", 0.15, 0.60),
+    ("gpt-4.1", 2.00, 8.00),
+    ("gpt-4.1-mini", 0.40, 1.60),
+    ("gemini-2.0-flash-001", 0.10, 0.40),
+    ("claude-3-7-sonnet-latest", 3.00, 15.00),
+    ("claude-3-5-haiku-latest", 0.80, 4.00),
+    ("text-embedding-3-small", 0.02, 0.0),
+    ("text-embedding-3-large", 0.13, 0.0),
+];
+
+/// Per-model token prices used to estimate LLM cost
+#[derive(Debug, Clone)]
+pub struct PriceTable {
+    prices: HashMap<String, ModelPrice>,
+}
+
+impl PriceTable {
+    /// The built-in prices, with overrides from `overrides_json`
+    ///
+    /// Overrides are a JSON object keyed by model, e.g.
+    /// `{"o4-mini": {"input": 1.1, "output": 4.4}}`. Unparseable overrides are
+    /// ignored with a warning.
+    pub fn with_overrides(overrides_json: Option<&str>) -> Self {
+        let mut prices: HashMap<String, ModelPrice> = DEFAULT_PRICES
+            .iter()
+            .map(|(model, input, output)| {
+                (
+                    model.to_string(),
+                    ModelPrice {
+                        input: *input,
+                        output: *output,
+                    },
+                )
+            })
+            .collect();
+
+        if let Some(json) = overrides_json {
+            match serde_json::from_str::<HashMap<String, ModelPrice>>(json) {
+                Ok(overrides) => prices.extend(overrides),
+                Err(e) => tracing::warn!("Ignoring invalid LLM_PRICE_TABLE: {}", e),
+            }
+        }
+
+        Self { prices }
+    }
+
+    /// The price of `model`, ignoring any provider prefix (`anthropic/...`)
+    /// when the full name isn't priced
+    pub fn price_for(&self, model: &str) -> Option<ModelPrice> {
+        self.prices.get(model).copied().or_else(|| {
+            model
+                .rsplit_once('/')
+                .and_then(|(_, name)| self.prices.get(name).copied())
+        })
+    }
+
+    /// Estimated cost in USD; unpriced models cost nothing
+    pub fn estimate_cost(&self, model: &str, prompt_tokens: i32, completion_tokens: i32) -> f64 {
+        match self.price_for(model) {
+            Some(price) => {
+                (prompt_tokens.max(0) as f64 * price.input
+                    + completion_tokens.max(0) as f64 * price.output)
+                    / 1_000_000.0
+            }
+            None => {
+                tracing::debug!("No price configured for model {}", model);
+                0.0
+            }
+        }
+    }
+}
+
+static PRICE_TABLE: LazyLock<PriceTable> =
+    LazyLock::new(|| PriceTable::with_overrides(env::var("LLM_PRICE_TABLE").ok().as_deref()));
+
+/// The price table in effect for this process
+pub fn price_table() -> &'static PriceTable {
+    &PRICE_TABLE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_cost_with_overrides() {
+        let table = PriceTable::with_overrides(Some(
+            r#"{"o4-mini": {"input": 2.0, "output": 8.0}, "llama3.1": {"input": 0}}"#,
+        ));
+
+        assert_eq!(table.estimate_cost("o4-mini", 1_000_000, 500_000), 6.0);
+        assert_eq!(table.estimate_cost("ollama/llama3.1", 1_000_000, 1_000_000), 0.0);
+        assert_eq!(
+            table.price_for("anthropic/claude-3-7-sonnet-latest"),
+            Some(ModelPrice {
+                input: 3.0,
+                output: 15.0
+            })
+        );
+        assert_eq!(table.estimate_cost("unknown-model", 1000, 1000), 0.0);
+
+        let defaults = PriceTable::with_overrides(Some("not json"));
+        assert_eq!(defaults.price_for("o4-mini").unwrap().input, 1.10);
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::Utc;
+use litellm::usage::{set_usage_recorder, UsageEvent, UsageRecorder};
+use uuid::Uuid;
+
+use database::{
+    helpers::{llm_usage::insert_llm_usage_event, organization::get_user_organization_id},
+    models::LlmUsageEvent,
+};
+
+use super::price_table;
+
+/// Stores the usage of every LLM call in `llm_usage_events`
+pub struct DatabaseUsageRecorder;
+
+impl UsageRecorder for DatabaseUsageRecorder {
+    fn record(&self, event: UsageEvent) {
+        // Usage is reported from async contexts; without a runtime there is
+        // nowhere to write it
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+
+        runtime.spawn(async move {
+            if let Err(e) = store_usage_event(event).await {
+                tracing::warn!("Failed to record LLM usage: {}", e);
+            }
+        });
+    }
+}
+
+/// Installs [`DatabaseUsageRecorder`] as the process-wide usage recorder
+pub fn install_llm_usage_recorder() {
+    if !set_usage_recorder(Arc::new(DatabaseUsageRecorder)) {
+        tracing::warn!("An LLM usage recorder was already installed");
+    }
+}
+
+fn parse_id(id: Option<&str>) -> Option<Uuid> {
+    id.and_then(|id| Uuid::parse_str(id).ok())
+}
+
+async fn store_usage_event(event: UsageEvent) -> Result<()> {
+    let user_id = parse_id(event.user_id.as_deref());
+    // Only calls made without an organization (e.g. embeddings) need a lookup
+    let organization_id = match (parse_id(event.organization_id.as_deref()), user_id) {
+        (Some(organization_id), _) => Some(organization_id),
+        (None, Some(user_id)) => get_user_organization_id(&user_id).await?,
+        (None, None) => None,
+    };
+
+    let estimated_cost =
+        price_table().estimate_cost(&event.model, event.prompt_tokens, event.completion_tokens);
+
+    insert_llm_usage_event(&LlmUsageEvent {
+        id: Uuid::new_v4(),
+        organization_id,
+        user_id,
+        chat_id: parse_id(event.session_id.as_deref()),
+        message_id: parse_id(event.message_id.as_deref()),
+        model: event.model,
+        generation_name: event.generation_name,
+        prompt_tokens: event.prompt_tokens,
+        completion_tokens: event.completion_tokens,
+        total_tokens: event.total_tokens,
+        estimated_cost,
+        created_at: Utc::now(),
+    })
+    .await
+}
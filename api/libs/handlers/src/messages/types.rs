@@ -20,6 +20,13 @@ pub struct ChatMessage {
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub final_reasoning_message: Option<String>,
     pub feedback: Option<String>,
+    /// The message this one replies to; None for the first message
+    #[serde(default)]
+    pub parent_message_id: Option<Uuid>,
+    /// Alternatives to this message sharing its parent, including itself,
+    /// oldest first
+    #[serde(default)]
+    pub sibling_message_ids: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -52,6 +59,8 @@ impl ChatMessage {
             created_at: Utc::now(),
             final_reasoning_message: None,
             feedback: None,
+            parent_message_id: None,
+            sibling_message_ids: Vec::new(),
         }
     }
 
@@ -100,6 +109,19 @@ impl ChatMessage {
             created_at,
             final_reasoning_message,
             feedback,
+            parent_message_id: None,
+            sibling_message_ids: Vec::new(),
         }
     }
+
+    /// Places the message in its chat's tree of branches
+    pub fn with_branch(
+        mut self,
+        parent_message_id: Option<Uuid>,
+        sibling_message_ids: Vec<String>,
+    ) -> Self {
+        self.parent_message_id = parent_message_id;
+        self.sibling_message_ids = sibling_message_ids;
+        self
+    }
 }
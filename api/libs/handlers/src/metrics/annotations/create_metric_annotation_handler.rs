@@ -0,0 +1,56 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use database::{models::MetricAnnotation, pool::get_pg_pool, schema::metric_annotations};
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::helpers::{check_annotation_edit_access, normalize_x_value, to_buster_annotation};
+use crate::metrics::types::BusterMetricAnnotation;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreateMetricAnnotationRequest {
+    /// X-axis value to pin the annotation to, e.g. "2024-03-01"
+    pub x_value: Value,
+    pub text: String,
+}
+
+/// Handler to add a time-series annotation (e.g. "pricing change") to a metric
+pub async fn create_metric_annotation_handler(
+    metric_id: &Uuid,
+    user: &AuthenticatedUser,
+    request: CreateMetricAnnotationRequest,
+) -> Result<BusterMetricAnnotation> {
+    let text = request.text.trim().to_string();
+    if text.is_empty() {
+        return Err(anyhow!("Annotation text cannot be empty"));
+    }
+    let x_value = normalize_x_value(&request.x_value)?;
+
+    let organization_id = check_annotation_edit_access(metric_id, user).await?;
+
+    let mut conn = get_pg_pool().get().await?;
+    let now = Utc::now();
+
+    let annotation = MetricAnnotation {
+        id: Uuid::new_v4(),
+        metric_file_id: *metric_id,
+        organization_id,
+        x_value,
+        text,
+        created_by: user.id,
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+    };
+
+    diesel::insert_into(metric_annotations::table)
+        .values(&annotation)
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to create annotation: {}", e))?;
+
+    Ok(to_buster_annotation(annotation, user.name.clone()))
+}
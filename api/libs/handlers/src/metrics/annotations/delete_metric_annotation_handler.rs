@@ -0,0 +1,35 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use database::{pool::get_pg_pool, schema::metric_annotations};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use super::helpers::check_annotation_edit_access;
+
+/// Handler to remove an annotation from a metric
+pub async fn delete_metric_annotation_handler(
+    metric_id: &Uuid,
+    annotation_id: &Uuid,
+    user: &AuthenticatedUser,
+) -> Result<()> {
+    check_annotation_edit_access(metric_id, user).await?;
+
+    let mut conn = get_pg_pool().get().await?;
+
+    let deleted = diesel::update(metric_annotations::table)
+        .filter(metric_annotations::id.eq(annotation_id))
+        .filter(metric_annotations::metric_file_id.eq(metric_id))
+        .filter(metric_annotations::deleted_at.is_null())
+        .set(metric_annotations::deleted_at.eq(Some(Utc::now())))
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to delete annotation: {}", e))?;
+
+    if deleted == 0 {
+        return Err(anyhow!("Annotation not found"));
+    }
+
+    Ok(())
+}
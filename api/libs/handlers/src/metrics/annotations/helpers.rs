@@ -0,0 +1,138 @@
+use std::cmp::Ordering;
+
+use anyhow::{anyhow, Result};
+use database::{
+    enums::AssetPermissionRole, helpers::metric_files::fetch_metric_file_with_permissions,
+    models::MetricAnnotation, pool::get_pg_pool, schema::{metric_annotations, users},
+};
+use diesel::{ExpressionMethods, JoinOnDsl, NullableExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use serde_json::Value;
+use sharing::check_permission_access;
+use uuid::Uuid;
+
+use crate::metrics::types::BusterMetricAnnotation;
+
+/// Loads the live annotations of a metric, ordered by x-axis value
+///
+/// `x_value` is stored as text, so numbers are compared after parsing rather
+/// than by the database.
+pub async fn fetch_metric_annotations(metric_id: &Uuid) -> Result<Vec<BusterMetricAnnotation>> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let annotations = metric_annotations::table
+        .left_join(users::table.on(users::id.eq(metric_annotations::created_by)))
+        .filter(metric_annotations::metric_file_id.eq(metric_id))
+        .filter(metric_annotations::deleted_at.is_null())
+        .order(metric_annotations::created_at.asc())
+        .select((
+            metric_annotations::all_columns,
+            users::name.nullable(),
+        ))
+        .load::<(MetricAnnotation, Option<String>)>(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to load metric annotations: {}", e))?;
+
+    let mut annotations: Vec<BusterMetricAnnotation> = annotations
+        .into_iter()
+        .map(|(annotation, created_by_name)| to_buster_annotation(annotation, created_by_name))
+        .collect();
+    // Stable, so annotations on the same value stay in creation order
+    annotations.sort_by(|a, b| compare_x_values(&a.x_value, &b.x_value));
+
+    Ok(annotations)
+}
+
+/// Orders numeric x-values numerically and everything else (dates,
+/// categories) as text; numbers sort before text
+fn compare_x_values(a: &str, b: &str) -> Ordering {
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+pub fn to_buster_annotation(
+    annotation: MetricAnnotation,
+    created_by_name: Option<String>,
+) -> BusterMetricAnnotation {
+    BusterMetricAnnotation {
+        id: annotation.id,
+        x_value: annotation.x_value,
+        text: annotation.text,
+        created_by: annotation.created_by,
+        created_by_name,
+        created_at: annotation.created_at,
+        updated_at: annotation.updated_at,
+    }
+}
+
+/// Annotations can be pinned to a date, number or category; they're stored as text
+pub fn normalize_x_value(x_value: &Value) -> Result<String> {
+    let normalized = match x_value {
+        Value::String(s) => s.trim().to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        _ => return Err(anyhow!("x_value must be a string, number or boolean")),
+    };
+    if normalized.is_empty() {
+        return Err(anyhow!("x_value cannot be empty"));
+    }
+    Ok(normalized)
+}
+
+/// Ensures the user can edit the metric and returns its organization id
+pub async fn check_annotation_edit_access(
+    metric_id: &Uuid,
+    user: &AuthenticatedUser,
+) -> Result<Uuid> {
+    let metric_file_with_permission = fetch_metric_file_with_permissions(metric_id, &user.id)
+        .await
+        .map_err(|e| anyhow!("Failed to fetch metric file with permissions: {}", e))?
+        .ok_or_else(|| anyhow!("Metric file not found"))?;
+
+    let organization_id = metric_file_with_permission.metric_file.organization_id;
+
+    if !check_permission_access(
+        metric_file_with_permission.permission,
+        &[
+            AssetPermissionRole::CanEdit,
+            AssetPermissionRole::FullAccess,
+            AssetPermissionRole::Owner,
+        ],
+        organization_id,
+        &user.organizations,
+    ) {
+        return Err(anyhow!(
+            "You don't have permission to annotate this metric. Editor or higher role required."
+        ));
+    }
+
+    Ok(organization_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_normalize_x_value() {
+        assert_eq!(normalize_x_value(&json!(" 2024-03-01 ")).unwrap(), "2024-03-01");
+        assert_eq!(normalize_x_value(&json!(42)).unwrap(), "42");
+        assert_eq!(normalize_x_value(&json!(1.5)).unwrap(), "1.5");
+        assert!(normalize_x_value(&json!("")).is_err());
+        assert!(normalize_x_value(&json!(null)).is_err());
+        assert!(normalize_x_value(&json!({"x": 1})).is_err());
+    }
+
+    #[test]
+    fn test_compare_x_values() {
+        let mut values = vec!["10", "9", "2024-03-01", "-1.5", "2023-12-31", "100"];
+        values.sort_by(|a, b| compare_x_values(a, b));
+        assert_eq!(values, vec!["-1.5", "9", "10", "100", "2023-12-31", "2024-03-01"]);
+    }
+}
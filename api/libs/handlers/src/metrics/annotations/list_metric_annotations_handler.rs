@@ -0,0 +1,19 @@
+use anyhow::Result;
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+pub use super::helpers::fetch_metric_annotations;
+use crate::metrics::get_metric_handler::get_metric_handler;
+use crate::metrics::types::BusterMetricAnnotation;
+
+/// Handler to list a metric's annotations. `BusterMetric` already carries
+/// them; this is for refreshing annotations without reloading the metric.
+pub async fn list_metric_annotations_handler(
+    metric_id: &Uuid,
+    user: &AuthenticatedUser,
+) -> Result<Vec<BusterMetricAnnotation>> {
+    // Reuses the get handler's permission checks
+    get_metric_handler(metric_id, user, None, None).await?;
+
+    fetch_metric_annotations(metric_id).await
+}
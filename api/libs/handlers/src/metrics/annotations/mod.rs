@@ -0,0 +1,10 @@
+pub mod create_metric_annotation_handler;
+pub mod delete_metric_annotation_handler;
+mod helpers;
+pub mod list_metric_annotations_handler;
+pub mod update_metric_annotation_handler;
+
+pub use create_metric_annotation_handler::*;
+pub use delete_metric_annotation_handler::*;
+pub use list_metric_annotations_handler::*;
+pub use update_metric_annotation_handler::*;
@@ -0,0 +1,71 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use database::{models::MetricAnnotation, pool::get_pg_pool, schema::metric_annotations};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::helpers::{check_annotation_edit_access, normalize_x_value, to_buster_annotation};
+use super::list_metric_annotations_handler::fetch_metric_annotations;
+use crate::metrics::types::BusterMetricAnnotation;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct UpdateMetricAnnotationRequest {
+    pub x_value: Option<Value>,
+    pub text: Option<String>,
+}
+
+/// Handler to edit a metric annotation's position or text
+pub async fn update_metric_annotation_handler(
+    metric_id: &Uuid,
+    annotation_id: &Uuid,
+    user: &AuthenticatedUser,
+    request: UpdateMetricAnnotationRequest,
+) -> Result<BusterMetricAnnotation> {
+    check_annotation_edit_access(metric_id, user).await?;
+
+    let x_value = request.x_value.as_ref().map(normalize_x_value).transpose()?;
+    let text = match request.text.map(|text| text.trim().to_string()) {
+        Some(text) if text.is_empty() => {
+            return Err(anyhow!("Annotation text cannot be empty"))
+        }
+        text => text,
+    };
+
+    let mut conn = get_pg_pool().get().await?;
+
+    #[derive(diesel::AsChangeset)]
+    #[diesel(table_name = metric_annotations)]
+    struct MetricAnnotationChangeset {
+        x_value: Option<String>,
+        text: Option<String>,
+        updated_at: chrono::DateTime<Utc>,
+    }
+
+    let annotation = diesel::update(metric_annotations::table)
+        .filter(metric_annotations::id.eq(annotation_id))
+        .filter(metric_annotations::metric_file_id.eq(metric_id))
+        .filter(metric_annotations::deleted_at.is_null())
+        .set(MetricAnnotationChangeset {
+            x_value,
+            text,
+            updated_at: Utc::now(),
+        })
+        .get_result::<MetricAnnotation>(&mut conn)
+        .await
+        .map_err(|e| match e {
+            diesel::NotFound => anyhow!("Annotation not found"),
+            _ => anyhow!("Failed to update annotation: {}", e),
+        })?;
+
+    // Re-read to pick up the author's name
+    let annotation_id = annotation.id;
+    Ok(fetch_metric_annotations(metric_id)
+        .await?
+        .into_iter()
+        .find(|a| a.id == annotation_id)
+        .unwrap_or_else(|| to_buster_annotation(annotation, None)))
+}
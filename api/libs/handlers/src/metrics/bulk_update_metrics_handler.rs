@@ -3,12 +3,14 @@ use database::helpers::metric_files::fetch_metric_files_with_permissions;
 use futures::future::join_all;
 use middleware::AuthenticatedUser;
 use sharing::check_permission_access;
-use database::enums::AssetPermissionRole;
+use database::enums::{AssetPermissionRole, Verification};
 use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::metrics::types::{BulkUpdateMetricsResponse, FailedMetricUpdate, MetricStatusUpdate, BusterMetric};
-use crate::metrics::update_metric_handler::{update_metric_handler, UpdateMetricRequest};
+use crate::metrics::get_metric_handler::get_metric_handler;
+use crate::metrics::review::transition_metric_review_handler::transition_metric_review_handler;
+use crate::metrics::review::types::{MetricReviewTransition, MetricReviewTransitionRequest};
 
 /// Map error to a client-friendly error code
 /// 
@@ -44,27 +46,35 @@ fn map_error_to_code(error: &anyhow::Error) -> String {
 
 /// Process a single metric status update
 /// 
-/// This function handles permission checking and applies the update to a single metric
+/// The status change goes through the review workflow, so only the metric's
+/// reviewers can certify it and every change is recorded
 /// 
 /// # Arguments
 /// * `update` - The metric update to process
+/// * `current` - The metric's current verification status
 /// * `user` - The authenticated user
 /// 
 /// # Returns
 /// * `Result<BusterMetric>` - The updated metric or an error
 async fn process_single_update(
     update: &MetricStatusUpdate,
+    current: Verification,
     user: &AuthenticatedUser,
 ) -> Result<BusterMetric> {
-    // Create an update request with just the verification status
-    let request = UpdateMetricRequest {
-        verification: Some(update.verification),
-        update_version: Some(false),
-        ..UpdateMetricRequest::default()
-    };
-    
-    // Update the metric using existing handler
-    update_metric_handler(&update.id, user, request).await
+    if update.verification != current {
+        let action = MetricReviewTransition::to_reach(current, update.verification)?;
+        transition_metric_review_handler(
+            &update.id,
+            user,
+            MetricReviewTransitionRequest {
+                action,
+                comment: None,
+            },
+        )
+        .await?;
+    }
+
+    get_metric_handler(&update.id, user, None, None).await
 }
 
 /// Handler for bulk updating multiple metric statuses in a single operation
@@ -117,6 +127,7 @@ pub async fn bulk_update_metrics_handler(
             (
                 metric_with_permission.permission,
                 metric_with_permission.metric_file.organization_id,
+                metric_with_permission.metric_file.verification,
             ),
         );
     }
@@ -132,7 +143,7 @@ pub async fn bulk_update_metrics_handler(
         for update in chunk {
             // Check if we have permission info for this metric
             match permission_map.get(&update.id) {
-                Some((Some(permission), organization_id)) => {
+                Some((Some(permission), organization_id, verification)) => {
                     // Check if user has sufficient permission
                     if check_permission_access(
                         Some(*permission),
@@ -145,7 +156,7 @@ pub async fn bulk_update_metrics_handler(
                         &user.organizations,
                     ) {
                         // User has permission, process the update
-                        futures.push(process_single_update(update, user));
+                        futures.push(process_single_update(update, *verification, user));
                     } else {
                         // User doesn't have sufficient permission
                         failed_updates.push(FailedMetricUpdate {
@@ -155,7 +166,7 @@ pub async fn bulk_update_metrics_handler(
                         });
                     }
                 }
-                Some((None, _)) => {
+                Some((None, _, _)) => {
                     // Metric exists but user has no permission
                     failed_updates.push(FailedMetricUpdate {
                         metric_id: update.id,
@@ -0,0 +1,413 @@
+use anyhow::{anyhow, Result};
+use database::types::{DrillTarget, MetricYml, DRILL_IDENTIFIER_RE, DRILL_PLACEHOLDER_RE};
+use indexmap::IndexMap;
+use middleware::AuthenticatedUser;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::metrics::get_metric_handler;
+
+/// The data point a user clicked, sent along with a metric data request
+///
+/// With a `target`, the named drill target of the metric is followed. Without
+/// one, the values cross-filter the metric itself, so a click on one dashboard
+/// tile can be broadcast to every other tile.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DrillContext {
+    pub target: Option<String>,
+    #[serde(default)]
+    pub values: IndexMap<String, Value>,
+}
+
+/// Describes the drill that produced a metric data response
+#[derive(Debug, Clone, Serialize)]
+pub struct AppliedDrill {
+    pub target: Option<String>,
+    /// Set when the drill opened another metric
+    pub metric_id: Option<Uuid>,
+    /// Column of the hierarchy level that was returned
+    pub level: Option<String>,
+    pub has_next_level: bool,
+    /// Columns the clicked values were applied to
+    pub filters: Vec<String>,
+}
+
+/// SQL and datasets to query for a drill
+#[derive(Debug)]
+pub struct ResolvedDrill {
+    pub sql: String,
+    pub dataset_ids: Vec<Uuid>,
+    pub applied: AppliedDrill,
+}
+
+/// What a drill context resolves to before any other metric is loaded
+#[derive(Debug, PartialEq)]
+pub enum DrillPlan {
+    Sql {
+        sql: String,
+        level: Option<String>,
+        has_next_level: bool,
+        filters: Vec<String>,
+    },
+    Metric {
+        metric_id: Uuid,
+        filters: Vec<(String, Value)>,
+    },
+}
+
+/// Resolves a drill context against a metric into the SQL to run
+///
+/// `metric_columns` are the metric's output columns, used to pick which
+/// cross-filter values apply to it.
+pub async fn resolve_drill(
+    metric_yml: &MetricYml,
+    metric_columns: &[String],
+    context: &DrillContext,
+    user: &AuthenticatedUser,
+) -> Result<ResolvedDrill> {
+    match plan_drill(metric_yml, metric_columns, context)? {
+        DrillPlan::Sql {
+            sql,
+            level,
+            has_next_level,
+            filters,
+        } => Ok(ResolvedDrill {
+            sql,
+            dataset_ids: metric_yml.dataset_ids.clone(),
+            applied: AppliedDrill {
+                target: context.target.clone(),
+                metric_id: None,
+                level,
+                has_next_level,
+                filters,
+            },
+        }),
+        DrillPlan::Metric { metric_id, filters } => {
+            // Drilling into another metric requires access to it in its own right
+            let target_metric = get_metric_handler(&metric_id, user, None, None).await?;
+            let target_yml: MetricYml = serde_yaml::from_str(&target_metric.file)
+                .map_err(|e| anyhow!("Failed to parse drill target metric: {}", e))?;
+
+            Ok(ResolvedDrill {
+                sql: apply_drill_filters(&target_yml.sql, &filters)?,
+                dataset_ids: target_yml.dataset_ids,
+                applied: AppliedDrill {
+                    target: context.target.clone(),
+                    metric_id: Some(metric_id),
+                    level: None,
+                    has_next_level: false,
+                    filters: filters.into_iter().map(|(column, _)| column).collect(),
+                },
+            })
+        }
+    }
+}
+
+/// Works out which SQL a drill context leads to without touching the database
+pub fn plan_drill(
+    metric_yml: &MetricYml,
+    metric_columns: &[String],
+    context: &DrillContext,
+) -> Result<DrillPlan> {
+    // Metric columns are stored lowercased, so clicked columns are matched the same way
+    let values: IndexMap<String, Value> = context
+        .values
+        .iter()
+        .map(|(column, value)| (column.to_lowercase(), value.clone()))
+        .collect();
+
+    let Some(target_name) = &context.target else {
+        let filters: Vec<(String, Value)> = values
+            .into_iter()
+            .filter(|(column, _)| metric_columns.iter().any(|c| c.eq_ignore_ascii_case(column)))
+            .collect();
+        return Ok(DrillPlan::Sql {
+            sql: apply_drill_filters(&metric_yml.sql, &filters)?,
+            level: None,
+            has_next_level: false,
+            filters: filters.into_iter().map(|(column, _)| column).collect(),
+        });
+    };
+
+    let target = metric_yml
+        .drill_targets
+        .iter()
+        .find(|target| target.name().eq_ignore_ascii_case(target_name))
+        .ok_or_else(|| anyhow!("Invalid drill: target '{}' is not defined", target_name))?;
+
+    match target {
+        DrillTarget::Metric(target) => {
+            let filters = if target.params.is_empty() {
+                values.into_iter().collect()
+            } else {
+                target
+                    .params
+                    .iter()
+                    .map(|param| {
+                        let column = param.to_lowercase();
+                        values
+                            .get(&column)
+                            .cloned()
+                            .map(|value| (column, value))
+                            .ok_or_else(|| anyhow!("Invalid drill: missing value for '{}'", param))
+                    })
+                    .collect::<Result<Vec<_>>>()?
+            };
+            Ok(DrillPlan::Metric {
+                metric_id: target.metric_id,
+                filters,
+            })
+        }
+        DrillTarget::Query(target) => Ok(DrillPlan::Sql {
+            sql: bind_drill_values(&target.sql, &values)?,
+            level: None,
+            has_next_level: false,
+            filters: values.keys().cloned().collect(),
+        }),
+        DrillTarget::Hierarchy(target) => {
+            // Every level already clicked through contributes a value, so the
+            // next level is the first one without one
+            let depth = target
+                .levels
+                .iter()
+                .take_while(|level| values.contains_key(&level.column.to_lowercase()))
+                .count();
+            let level = target.levels.get(depth).ok_or_else(|| {
+                anyhow!(
+                    "Invalid drill: already at the deepest level of '{}'",
+                    target.name
+                )
+            })?;
+            Ok(DrillPlan::Sql {
+                sql: bind_drill_values(&level.sql, &values)?,
+                level: Some(level.column.clone()),
+                has_next_level: depth + 1 < target.levels.len(),
+                filters: target.levels[..depth]
+                    .iter()
+                    .map(|level| level.column.clone())
+                    .collect(),
+            })
+        }
+    }
+}
+
+/// Replaces `{{column}}` placeholders in a drill SQL template with literals
+pub fn bind_drill_values(template: &str, values: &IndexMap<String, Value>) -> Result<String> {
+    let mut sql = String::with_capacity(template.len());
+    let mut last = 0;
+
+    for caps in DRILL_PLACEHOLDER_RE.captures_iter(template) {
+        let placeholder = caps.get(0).unwrap();
+        let column = caps[1].to_lowercase();
+        let value = values
+            .get(&column)
+            .ok_or_else(|| anyhow!("Invalid drill: missing value for '{}'", &caps[1]))?;
+
+        sql.push_str(&template[last..placeholder.start()]);
+        sql.push_str(&sql_literal(value)?);
+        last = placeholder.end();
+    }
+    sql.push_str(&template[last..]);
+
+    Ok(sql)
+}
+
+/// Wraps a metric's SQL so that only rows matching the clicked values remain
+pub fn apply_drill_filters(sql: &str, filters: &[(String, Value)]) -> Result<String> {
+    if filters.is_empty() {
+        return Ok(sql.to_string());
+    }
+
+    let conditions = filters
+        .iter()
+        .map(|(column, value)| {
+            if !DRILL_IDENTIFIER_RE.is_match(column) {
+                return Err(anyhow!("Invalid drill: bad column name '{}'", column));
+            }
+            Ok(match value {
+                Value::Null => format!("{} IS NULL", column),
+                value => format!("{} = {}", column, sql_literal(value)?),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // The newline keeps a trailing line comment in the source SQL from
+    // swallowing the closing parenthesis
+    let inner = sql.trim().trim_end_matches(';').trim_end();
+    Ok(format!(
+        "SELECT * FROM (\n{}\n) AS drill_source WHERE {}",
+        inner,
+        conditions.join(" AND ")
+    ))
+}
+
+/// Renders a clicked value as a SQL literal
+///
+/// Strings are single-quoted with embedded quotes doubled. Backslashes and
+/// control characters are rejected outright, since several warehouses treat
+/// backslash as an escape inside string literals.
+pub fn sql_literal(value: &Value) -> Result<String> {
+    match value {
+        Value::Null => Ok("NULL".to_string()),
+        Value::Bool(b) => Ok(if *b { "TRUE" } else { "FALSE" }.to_string()),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::String(s) => {
+            if s.chars().any(|c| c == '\\' || c.is_control()) {
+                return Err(anyhow!(
+                    "Invalid drill: values cannot contain backslashes or control characters"
+                ));
+            }
+            Ok(format!("'{}'", s.replace('\'', "''")))
+        }
+        Value::Array(_) | Value::Object(_) => Err(anyhow!(
+            "Invalid drill: values must be strings, numbers, booleans or null"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn metric_yml() -> MetricYml {
+        MetricYml::new(
+            r#"
+name: Revenue by Year
+timeFrame: all_time
+sql: SELECT year, region, SUM(amount) AS revenue FROM sales GROUP BY year, region;
+datasetIds: ["00000000-0000-0000-0000-000000000001"]
+chartConfig:
+  selectedChartType: bar
+  columnLabelFormats: {}
+  barAndLineAxis:
+    x: [year]
+    y: [revenue]
+drillTargets:
+  - type: query
+    name: Orders
+    sql: SELECT * FROM orders WHERE region = {{region}} AND year = {{ year }}
+  - type: hierarchy
+    name: Period
+    levels:
+      - column: quarter
+        sql: SELECT quarter, SUM(amount) AS revenue FROM sales WHERE year = {{year}} GROUP BY quarter
+      - column: month
+        sql: SELECT month, SUM(amount) AS revenue FROM sales WHERE year = {{year}} AND quarter = {{quarter}} GROUP BY month
+"#
+            .to_string(),
+        )
+        .unwrap()
+    }
+
+    fn context(target: Option<&str>, values: Value) -> DrillContext {
+        serde_json::from_value(json!({ "target": target, "values": values })).unwrap()
+    }
+
+    #[test]
+    fn test_sql_literal_escaping() {
+        assert_eq!(sql_literal(&json!("O'Brien")).unwrap(), "'O''Brien'");
+        assert_eq!(sql_literal(&json!("x' OR 1=1 --")).unwrap(), "'x'' OR 1=1 --'");
+        assert_eq!(sql_literal(&json!(2024)).unwrap(), "2024");
+        assert_eq!(sql_literal(&json!(1.5)).unwrap(), "1.5");
+        assert_eq!(sql_literal(&json!(true)).unwrap(), "TRUE");
+        assert_eq!(sql_literal(&Value::Null).unwrap(), "NULL");
+        assert!(sql_literal(&json!("\\' OR 1=1 --")).is_err());
+        assert!(sql_literal(&json!("a\nb")).is_err());
+        assert!(sql_literal(&json!(["a"])).is_err());
+    }
+
+    #[test]
+    fn test_query_target_binds_placeholders() {
+        let plan = plan_drill(
+            &metric_yml(),
+            &[],
+            &context(Some("orders"), json!({ "REGION": "West's", "year": 2024 })),
+        )
+        .unwrap();
+
+        match plan {
+            DrillPlan::Sql { sql, .. } => assert_eq!(
+                sql,
+                "SELECT * FROM orders WHERE region = 'West''s' AND year = 2024"
+            ),
+            _ => panic!("Expected SQL plan"),
+        }
+
+        let missing = plan_drill(&metric_yml(), &[], &context(Some("orders"), json!({ "year": 2024 })));
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn test_hierarchy_walks_levels() {
+        let yml = metric_yml();
+
+        let first = plan_drill(&yml, &[], &context(Some("Period"), json!({ "year": 2024 }))).unwrap();
+        match first {
+            DrillPlan::Sql {
+                level,
+                has_next_level,
+                sql,
+                ..
+            } => {
+                assert_eq!(level.as_deref(), Some("quarter"));
+                assert!(has_next_level);
+                assert!(sql.contains("WHERE year = 2024 GROUP BY quarter"));
+            }
+            _ => panic!("Expected SQL plan"),
+        }
+
+        let second = plan_drill(
+            &yml,
+            &[],
+            &context(Some("Period"), json!({ "year": 2024, "quarter": "Q1" })),
+        )
+        .unwrap();
+        match second {
+            DrillPlan::Sql {
+                level,
+                has_next_level,
+                filters,
+                ..
+            } => {
+                assert_eq!(level.as_deref(), Some("month"));
+                assert!(!has_next_level);
+                assert_eq!(filters, vec!["quarter".to_string()]);
+            }
+            _ => panic!("Expected SQL plan"),
+        }
+
+        let past_end = plan_drill(
+            &yml,
+            &[],
+            &context(
+                Some("Period"),
+                json!({ "year": 2024, "quarter": "Q1", "month": 1 }),
+            ),
+        );
+        assert!(past_end.is_err());
+    }
+
+    #[test]
+    fn test_cross_filter_only_applies_known_columns() {
+        let plan = plan_drill(
+            &metric_yml(),
+            &["year".to_string(), "region".to_string(), "revenue".to_string()],
+            &context(None, json!({ "region": "West", "channel": "web", "year": null })),
+        )
+        .unwrap();
+
+        assert_eq!(
+            plan,
+            DrillPlan::Sql {
+                sql: "SELECT * FROM (\nSELECT year, region, SUM(amount) AS revenue FROM sales GROUP BY year, region\n) AS drill_source WHERE region = 'West' AND year IS NULL".to_string(),
+                level: None,
+                has_next_level: false,
+                filters: vec!["region".to_string(), "year".to_string()],
+            }
+        );
+
+        assert!(apply_drill_filters("SELECT 1", &[("a; DROP".to_string(), json!(1))]).is_err());
+    }
+}
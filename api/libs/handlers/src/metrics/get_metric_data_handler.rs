@@ -16,6 +16,7 @@ use uuid::Uuid;
 use query_engine::data_source_helpers;
 use query_engine::data_types::DataType;
 
+use crate::metrics::drill::{resolve_drill, AppliedDrill, DrillContext};
 use crate::metrics::{get_metric_for_dashboard_handler, get_metric_handler, BusterMetric};
 
 /// Request structure for the get_metric_data handler
@@ -25,6 +26,7 @@ pub struct GetMetricDataRequest {
     pub version_number: Option<i32>,
     pub limit: Option<i64>,
     pub password: Option<String>,
+    pub drill: Option<DrillContext>,
 }
 
 /// Structure for the metric data response
@@ -33,6 +35,8 @@ pub struct MetricDataResponse {
     pub metric_id: Uuid,
     pub data: Vec<IndexMap<String, DataType>>,
     pub data_metadata: DataMetadata,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drill: Option<AppliedDrill>,
 }
 
 /// Handler to retrieve both the metric definition and its associated data
@@ -145,8 +149,27 @@ pub async fn get_metric_data_handler(
             return Err(anyhow!("Failed to parse metric definition: {}", parse_err));
         }
     };
-    let sql = metric_yml.sql;
-    let dataset_ids = metric_yml.dataset_ids;
+
+    // Resolve the clicked data point, if any, into the SQL to run instead
+    let (sql, dataset_ids, applied_drill) = match &request.drill {
+        Some(drill) => {
+            let metric_columns: Vec<String> = metric
+                .data_metadata
+                .as_ref()
+                .map(|metadata| {
+                    metadata
+                        .column_metadata
+                        .iter()
+                        .map(|column| column.name.clone())
+                        .collect()
+                })
+                .unwrap_or_default();
+            let resolved = resolve_drill(&metric_yml, &metric_columns, drill, &user).await?;
+            tracing::debug!("Applied drill: {:?}", resolved.applied);
+            (resolved.sql, resolved.dataset_ids, Some(resolved.applied))
+        }
+        None => (metric_yml.sql, metric_yml.dataset_ids, None),
+    };
 
     if dataset_ids.is_empty() {
         tracing::error!(
@@ -230,7 +253,9 @@ pub async fn get_metric_data_handler(
         }
     };
 
-    // Determine which metadata to use
+    // Determine which metadata to use. Cached metadata describes the metric's
+    // own columns, so it doesn't apply to drilled results.
+    let cached_metadata = cached_metadata.filter(|_| applied_drill.is_none());
     let final_metadata = if let Some(metadata) = cached_metadata {
         tracing::debug!(
             "Using cached metadata. Cached rows: {}, Query rows: {}",
@@ -263,5 +288,6 @@ pub async fn get_metric_data_handler(
         metric_id: request.metric_id,
         data: query_result.data,
         data_metadata: final_metadata,
+        drill: applied_drill,
     })
 }
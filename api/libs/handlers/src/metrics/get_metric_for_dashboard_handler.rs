@@ -8,6 +8,7 @@ use futures::future::join;
 use serde_yaml;
 use uuid::Uuid;
 
+use crate::metrics::annotations::fetch_metric_annotations;
 use crate::metrics::types::{AssociatedCollection, AssociatedDashboard, BusterMetric, Dataset};
 use database::enums::AssetPermissionRole; // Keep for hardcoding permission
 use database::pool::get_pg_pool;
@@ -256,6 +257,14 @@ pub async fn get_metric_for_dashboard_handler(
         }
     };
 
+    let annotations = match fetch_metric_annotations(metric_id).await {
+        Ok(annotations) => annotations,
+        Err(e) => {
+            tracing::error!("Failed to fetch annotations for metric {}: {}", metric_id, e);
+            vec![]
+        }
+    };
+
     // Construct BusterMetric using resolved values
     Ok(BusterMetric {
         id: metric_file.id,
@@ -291,5 +300,6 @@ pub async fn get_metric_for_dashboard_handler(
         public_expiry_date: None,   // Default value
         public_enabled_by: None,    // Default value
         public_password: None,      // Default value
+        annotations, // Not versioned
     })
-} 
\ No newline at end of file
+} 
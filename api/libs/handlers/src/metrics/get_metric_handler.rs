@@ -6,6 +6,7 @@ use middleware::AuthenticatedUser;
 use serde_yaml;
 use uuid::Uuid;
 
+use crate::metrics::annotations::fetch_metric_annotations;
 use crate::metrics::types::{AssociatedCollection, AssociatedDashboard, BusterMetric, Dataset};
 use database::enums::{AssetPermissionRole, AssetType, IdentityType};
 use database::helpers::metric_files::fetch_metric_file_with_permissions;
@@ -391,6 +392,14 @@ pub async fn get_metric_handler(
         Err(_) => None,
     };
 
+    let annotations = match fetch_metric_annotations(metric_id).await {
+        Ok(annotations) => annotations,
+        Err(e) => {
+            tracing::error!("Failed to fetch annotations for metric {}: {}", metric_id, e);
+            vec![]
+        }
+    };
+
     // Construct BusterMetric using resolved values
     Ok(BusterMetric {
         id: metric_file.id,
@@ -425,5 +434,6 @@ pub async fn get_metric_handler(
         public_expiry_date: metric_file.public_expiry_date, // Not versioned
         public_enabled_by: public_enabled_by_user, // Not versioned
         public_password: metric_file.public_password, // Not versioned
+        annotations, // Not versioned
     })
 }
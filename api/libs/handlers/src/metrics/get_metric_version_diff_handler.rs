@@ -0,0 +1,39 @@
+use anyhow::{anyhow, Result};
+use database::{
+    pool::get_pg_pool,
+    schema::metric_files,
+    types::{VersionDiff, VersionHistory},
+};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use crate::metrics::get_metric_handler::get_metric_handler;
+
+/// Handler to compare two versions of a metric
+///
+/// Returns a line diff of the two YAML versions together with a list of
+/// semantic changes (SQL, chart type, datasets, ...). Requires the same
+/// access as viewing the metric.
+pub async fn get_metric_version_diff_handler(
+    metric_id: &Uuid,
+    user: &AuthenticatedUser,
+    from_version: i32,
+    to_version: i32,
+) -> Result<VersionDiff> {
+    // Reuses the get handler's permission checks
+    get_metric_handler(metric_id, user, None, None).await?;
+
+    let mut conn = get_pg_pool().get().await?;
+
+    let version_history = metric_files::table
+        .filter(metric_files::id.eq(metric_id))
+        .filter(metric_files::deleted_at.is_null())
+        .select(metric_files::version_history)
+        .first::<VersionHistory>(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to get version history: {}", e))?;
+
+    version_history.diff(from_version, to_version)
+}
@@ -1,18 +1,27 @@
+pub mod annotations;
 pub mod bulk_update_metrics_handler;
 pub mod delete_metric_handler;
+pub mod drill;
 pub mod get_metric_data_handler;
 pub mod get_metric_handler;
+pub mod get_metric_version_diff_handler;
 pub mod list_metrics_handler;
+pub mod restore_metric_version_handler;
+pub mod review;
 pub mod sharing;
 pub mod types;
 pub mod update_metric_handler;
 pub mod get_metric_for_dashboard_handler;
 
 // Re-export specific items from handlers
+pub use annotations::*;
 pub use bulk_update_metrics_handler::*;
 pub use delete_metric_handler::*;
+pub use drill::*;
 pub use get_metric_handler::*;
+pub use get_metric_version_diff_handler::*;
 pub use list_metrics_handler::*;
+pub use restore_metric_version_handler::*;
 pub use update_metric_handler::*;
 pub use get_metric_for_dashboard_handler::get_metric_for_dashboard_handler;
 
@@ -22,6 +31,7 @@ pub use get_metric_data_handler::{
     get_metric_data_handler, GetMetricDataRequest, MetricDataResponse,
 };
 
-// Re-export types and sharing
+// Re-export types, review and sharing
+pub use review::*;
 pub use sharing::*;
 pub use types::*;
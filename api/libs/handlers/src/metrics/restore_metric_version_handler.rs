@@ -0,0 +1,42 @@
+use anyhow::Result;
+use database::{
+    enums::AssetType, helpers::version_restores::fetch_version_restores,
+    models::AssetVersionRestore,
+};
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use crate::metrics::get_metric_handler::get_metric_handler;
+use crate::metrics::types::BusterMetric;
+use crate::metrics::update_metric_handler::{update_metric_handler, UpdateMetricRequest};
+
+/// Handler to restore a metric to a previous version
+///
+/// The restored content is saved as a new version; the restore itself is
+/// recorded in the audit trail by `update_metric_handler`.
+pub async fn restore_metric_version_handler(
+    metric_id: &Uuid,
+    user: &AuthenticatedUser,
+    version_number: i32,
+) -> Result<BusterMetric> {
+    update_metric_handler(
+        metric_id,
+        user,
+        UpdateMetricRequest {
+            restore_to_version: Some(version_number),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+/// Handler to list the restores performed on a metric, most recent first
+pub async fn list_metric_version_restores_handler(
+    metric_id: &Uuid,
+    user: &AuthenticatedUser,
+) -> Result<Vec<AssetVersionRestore>> {
+    // Reuses the get handler's permission checks
+    get_metric_handler(metric_id, user, None, None).await?;
+
+    fetch_version_restores(metric_id, AssetType::MetricFile).await
+}
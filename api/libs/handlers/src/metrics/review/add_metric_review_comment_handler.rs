@@ -0,0 +1,60 @@
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+use database::helpers::metric_files::fetch_metric_file;
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use super::get_metric_review_handler::get_metric_review_handler;
+use super::helpers::{
+    fetch_latest_review_request, fetch_metric_reviewer_ids, notify_review_participants,
+    record_review_event,
+};
+use super::types::{MetricReviewCommentRequest, MetricReviewResponse, REVIEW_ACTION_COMMENTED};
+use crate::metrics::get_metric_handler::get_metric_handler;
+
+/// Handler to add a review comment to a metric without changing its status
+///
+/// Anyone who can view the metric can comment; reviewers, the last requester
+/// and the author are notified.
+pub async fn add_metric_review_comment_handler(
+    metric_id: &Uuid,
+    user: &AuthenticatedUser,
+    request: MetricReviewCommentRequest,
+) -> Result<MetricReviewResponse> {
+    let comment = request.comment.trim().to_string();
+    if comment.is_empty() {
+        return Err(anyhow!("Comment cannot be empty"));
+    }
+
+    // Reuses the get handler's permission checks
+    get_metric_handler(metric_id, user, None, None).await?;
+
+    let metric_file = fetch_metric_file(metric_id)
+        .await?
+        .ok_or_else(|| anyhow!("Metric file not found"))?;
+
+    let reviewer_ids = fetch_metric_reviewer_ids(&metric_file).await?;
+
+    record_review_event(
+        &metric_file,
+        &user.id,
+        REVIEW_ACTION_COMMENTED,
+        metric_file.verification,
+        metric_file.verification,
+        Some(comment.clone()),
+        reviewer_ids.clone(),
+        None,
+    )
+    .await?;
+
+    let mut recipients: HashSet<Uuid> = reviewer_ids.into_iter().collect();
+    recipients.insert(metric_file.created_by);
+    if let Some(request) = fetch_latest_review_request(metric_id).await? {
+        recipients.insert(request.actor_id);
+    }
+    notify_review_participants(&metric_file, user, "commented on", Some(comment), recipients)
+        .await;
+
+    get_metric_review_handler(metric_id, user).await
+}
@@ -0,0 +1,138 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use database::{
+    enums::UserOrganizationRole,
+    models::DatasetReviewer,
+    pool::get_pg_pool,
+    schema::{dataset_reviewers, datasets, users, users_to_organizations},
+};
+use diesel::{ExpressionMethods, JoinOnDsl, QueryDsl};
+use diesel_async::{scoped_futures::ScopedFutureExt, AsyncConnection, RunQueryDsl};
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use super::types::{MetricReviewer, SetDatasetReviewersRequest};
+
+/// Returns the dataset's organization if the user belongs to it
+async fn dataset_organization_for_user(
+    dataset_id: &Uuid,
+    user: &AuthenticatedUser,
+) -> Result<(Uuid, UserOrganizationRole)> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let organization_id = datasets::table
+        .filter(datasets::id.eq(dataset_id))
+        .filter(datasets::deleted_at.is_null())
+        .select(datasets::organization_id)
+        .first::<Uuid>(&mut conn)
+        .await
+        .map_err(|e| match e {
+            diesel::NotFound => anyhow!("Dataset not found"),
+            _ => anyhow!("Error getting dataset: {}", e),
+        })?;
+
+    let membership = user
+        .organizations
+        .iter()
+        .find(|org| org.id == organization_id)
+        .ok_or_else(|| anyhow!("Dataset not found"))?;
+
+    Ok((organization_id, membership.role))
+}
+
+/// Handler to list the designated reviewers of a dataset
+pub async fn list_dataset_reviewers_handler(
+    dataset_id: &Uuid,
+    user: &AuthenticatedUser,
+) -> Result<Vec<MetricReviewer>> {
+    dataset_organization_for_user(dataset_id, user).await?;
+
+    let mut conn = get_pg_pool().get().await?;
+
+    let reviewers = dataset_reviewers::table
+        .inner_join(users::table.on(users::id.eq(dataset_reviewers::user_id)))
+        .filter(dataset_reviewers::dataset_id.eq(dataset_id))
+        .order(users::email.asc())
+        .select((users::id, users::name, users::email))
+        .load::<(Uuid, Option<String>, String)>(&mut conn)
+        .await?
+        .into_iter()
+        .map(|(id, name, email)| MetricReviewer { id, name, email })
+        .collect();
+
+    Ok(reviewers)
+}
+
+/// Handler to replace the designated reviewers of a dataset
+///
+/// Restricted to workspace and data admins. Reviewers must belong to the
+/// dataset's organization.
+pub async fn set_dataset_reviewers_handler(
+    dataset_id: &Uuid,
+    user: &AuthenticatedUser,
+    request: SetDatasetReviewersRequest,
+) -> Result<Vec<MetricReviewer>> {
+    let (organization_id, role) = dataset_organization_for_user(dataset_id, user).await?;
+
+    if role != UserOrganizationRole::WorkspaceAdmin && role != UserOrganizationRole::DataAdmin {
+        return Err(anyhow!(
+            "You don't have permission to manage reviewers. Workspace or data admin role required."
+        ));
+    }
+
+    let mut user_ids = request.user_ids;
+    user_ids.sort();
+    user_ids.dedup();
+
+    let mut conn = get_pg_pool().get().await?;
+
+    let members = users_to_organizations::table
+        .filter(users_to_organizations::organization_id.eq(organization_id))
+        .filter(users_to_organizations::user_id.eq_any(&user_ids))
+        .filter(users_to_organizations::deleted_at.is_null())
+        .count()
+        .get_result::<i64>(&mut conn)
+        .await?;
+
+    if members != user_ids.len() as i64 {
+        return Err(anyhow!("All reviewers must be members of the dataset's organization"));
+    }
+
+    let now = Utc::now();
+    let rows: Vec<DatasetReviewer> = user_ids
+        .iter()
+        .map(|user_id| DatasetReviewer {
+            dataset_id: *dataset_id,
+            user_id: *user_id,
+            created_by: user.id,
+            created_at: now,
+        })
+        .collect();
+
+    // Replace the reviewers atomically so a failed insert doesn't leave the
+    // dataset without any
+    conn.transaction::<_, anyhow::Error, _>(|conn| {
+        async move {
+            diesel::delete(dataset_reviewers::table)
+                .filter(dataset_reviewers::dataset_id.eq(dataset_id))
+                .execute(conn)
+                .await
+                .map_err(|e| anyhow!("Failed to clear dataset reviewers: {}", e))?;
+
+            if !rows.is_empty() {
+                diesel::insert_into(dataset_reviewers::table)
+                    .values(&rows)
+                    .execute(conn)
+                    .await
+                    .map_err(|e| anyhow!("Failed to add dataset reviewers: {}", e))?;
+            }
+
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await?;
+    drop(conn);
+
+    list_dataset_reviewers_handler(dataset_id, user).await
+}
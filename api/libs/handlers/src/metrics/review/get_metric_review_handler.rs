@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use database::{
+    enums::Verification, helpers::metric_files::fetch_metric_file, models::MetricReviewEvent,
+    pool::get_pg_pool, schema::metric_review_events,
+};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use super::helpers::{fetch_metric_reviewer_ids, fetch_reviewers};
+use super::types::{
+    MetricReviewEventResponse, MetricReviewResponse, MetricReviewer, REVIEW_ACTION_REQUESTED,
+};
+use crate::metrics::get_metric_handler::get_metric_handler;
+
+/// Handler to get a metric's certification status, reviewers, SLA and review history
+pub async fn get_metric_review_handler(
+    metric_id: &Uuid,
+    user: &AuthenticatedUser,
+) -> Result<MetricReviewResponse> {
+    // Reuses the get handler's permission checks
+    get_metric_handler(metric_id, user, None, None).await?;
+
+    let metric_file = fetch_metric_file(metric_id)
+        .await?
+        .ok_or_else(|| anyhow!("Metric file not found"))?;
+
+    let mut conn = get_pg_pool().get().await?;
+
+    let events = metric_review_events::table
+        .filter(metric_review_events::metric_file_id.eq(metric_id))
+        .order(metric_review_events::created_at.asc())
+        .load::<MetricReviewEvent>(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to load review events: {}", e))?;
+
+    let reviewer_ids = fetch_metric_reviewer_ids(&metric_file).await?;
+
+    let mut user_ids: Vec<Uuid> = events.iter().map(|event| event.actor_id).collect();
+    user_ids.extend(reviewer_ids.iter().copied());
+    user_ids.sort();
+    user_ids.dedup();
+    let users: HashMap<Uuid, MetricReviewer> = fetch_reviewers(&user_ids)
+        .await?
+        .into_iter()
+        .map(|user| (user.id, user))
+        .collect();
+
+    let review_open = matches!(
+        metric_file.verification,
+        Verification::Requested | Verification::InReview
+    );
+    let due_at = if review_open {
+        events
+            .iter()
+            .rev()
+            .find(|event| event.action == REVIEW_ACTION_REQUESTED)
+            .and_then(|event| event.due_at)
+    } else {
+        None
+    };
+
+    let unknown_user = |id: Uuid| MetricReviewer {
+        id,
+        name: None,
+        email: String::new(),
+    };
+
+    Ok(MetricReviewResponse {
+        metric_id: *metric_id,
+        status: metric_file.verification,
+        reviewers: reviewer_ids
+            .iter()
+            .filter_map(|id| users.get(id).cloned())
+            .collect(),
+        due_at,
+        overdue: due_at.is_some_and(|due_at| due_at < Utc::now()),
+        events: events
+            .into_iter()
+            .map(|event| MetricReviewEventResponse {
+                id: event.id,
+                actor: users
+                    .get(&event.actor_id)
+                    .cloned()
+                    .unwrap_or_else(|| unknown_user(event.actor_id)),
+                action: event.action,
+                from_status: event.from_status,
+                to_status: event.to_status,
+                comment: event.comment,
+                due_at: event.due_at,
+                created_at: event.created_at,
+            })
+            .collect(),
+    })
+}
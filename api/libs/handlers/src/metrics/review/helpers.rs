@@ -0,0 +1,206 @@
+use std::collections::HashSet;
+use std::env;
+
+use anyhow::{anyhow, Result};
+use chrono::{Duration, Utc};
+use database::{
+    enums::{UserOrganizationRole, Verification},
+    models::{MetricFile, MetricReviewEvent},
+    pool::get_pg_pool,
+    schema::{dataset_reviewers, metric_review_events, users, users_to_organizations},
+};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use email::{send_email, EmailType, MetricReviewNotification};
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use super::types::{MetricReviewer, REVIEW_ACTION_DEMOTED, REVIEW_ACTION_REQUESTED};
+
+const DEFAULT_REVIEW_SLA_HOURS: i64 = 72;
+
+/// How long reviewers have to act on a review request (`METRIC_REVIEW_SLA_HOURS`, default 72)
+pub fn review_sla() -> Duration {
+    let hours = env::var("METRIC_REVIEW_SLA_HOURS")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|hours| *hours > 0)
+        .unwrap_or(DEFAULT_REVIEW_SLA_HOURS);
+    Duration::hours(hours)
+}
+
+/// Resolves who may review a metric: the designated reviewers of any of its
+/// datasets, falling back to the organization's workspace and data admins
+/// when none are configured.
+pub async fn fetch_metric_reviewer_ids(metric_file: &MetricFile) -> Result<Vec<Uuid>> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let designated = dataset_reviewers::table
+        .filter(dataset_reviewers::dataset_id.eq_any(&metric_file.content.dataset_ids))
+        .select(dataset_reviewers::user_id)
+        .distinct()
+        .load::<Uuid>(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to load dataset reviewers: {}", e))?;
+
+    if !designated.is_empty() {
+        return Ok(designated);
+    }
+
+    users_to_organizations::table
+        .filter(users_to_organizations::organization_id.eq(metric_file.organization_id))
+        .filter(users_to_organizations::role.eq_any(vec![
+            UserOrganizationRole::WorkspaceAdmin,
+            UserOrganizationRole::DataAdmin,
+        ]))
+        .filter(users_to_organizations::deleted_at.is_null())
+        .select(users_to_organizations::user_id)
+        .load::<Uuid>(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to load organization admins: {}", e))
+}
+
+pub async fn fetch_reviewers(user_ids: &[Uuid]) -> Result<Vec<MetricReviewer>> {
+    if user_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut conn = get_pg_pool().get().await?;
+
+    let reviewers = users::table
+        .filter(users::id.eq_any(user_ids))
+        .select((users::id, users::name, users::email))
+        .load::<(Uuid, Option<String>, String)>(&mut conn)
+        .await?
+        .into_iter()
+        .map(|(id, name, email)| MetricReviewer { id, name, email })
+        .collect();
+
+    Ok(reviewers)
+}
+
+/// Most recent review request for a metric, if one was ever made
+pub async fn fetch_latest_review_request(metric_id: &Uuid) -> Result<Option<MetricReviewEvent>> {
+    let mut conn = get_pg_pool().get().await?;
+
+    match metric_review_events::table
+        .filter(metric_review_events::metric_file_id.eq(metric_id))
+        .filter(metric_review_events::action.eq(REVIEW_ACTION_REQUESTED))
+        .order(metric_review_events::created_at.desc())
+        .first::<MetricReviewEvent>(&mut conn)
+        .await
+    {
+        Ok(event) => Ok(Some(event)),
+        Err(diesel::NotFound) => Ok(None),
+        Err(e) => Err(anyhow!("Failed to load review request: {}", e)),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn record_review_event(
+    metric_file: &MetricFile,
+    actor_id: &Uuid,
+    action: &str,
+    from_status: Verification,
+    to_status: Verification,
+    comment: Option<String>,
+    reviewer_ids: Vec<Uuid>,
+    due_at: Option<chrono::DateTime<Utc>>,
+) -> Result<MetricReviewEvent> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let event = MetricReviewEvent {
+        id: Uuid::new_v4(),
+        metric_file_id: metric_file.id,
+        organization_id: metric_file.organization_id,
+        actor_id: *actor_id,
+        action: action.to_string(),
+        from_status,
+        to_status,
+        comment,
+        reviewer_ids,
+        due_at,
+        created_at: Utc::now(),
+    };
+
+    diesel::insert_into(metric_review_events::table)
+        .values(&event)
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to record review event: {}", e))?;
+
+    Ok(event)
+}
+
+/// Emails everyone in `recipients` except the actor. Failures are logged, not returned,
+/// so a mail outage never blocks a review transition.
+pub async fn notify_review_participants(
+    metric_file: &MetricFile,
+    actor: &AuthenticatedUser,
+    action: &str,
+    comment: Option<String>,
+    recipients: HashSet<Uuid>,
+) {
+    let recipient_ids: Vec<Uuid> = recipients.into_iter().filter(|id| *id != actor.id).collect();
+
+    let emails: HashSet<String> = match fetch_reviewers(&recipient_ids).await {
+        Ok(users) => users.into_iter().map(|user| user.email).collect(),
+        Err(e) => {
+            tracing::error!(error = %e, metric_id = %metric_file.id, "Failed to resolve review notification recipients");
+            return;
+        }
+    };
+
+    if emails.is_empty() {
+        return;
+    }
+
+    let notification = MetricReviewNotification {
+        metric_name: metric_file.name.clone(),
+        metric_id: metric_file.id,
+        actor_name: actor.name.clone().unwrap_or_else(|| actor.email.clone()),
+        action: action.to_string(),
+        comment,
+    };
+
+    if let Err(e) = send_email(emails, EmailType::MetricReview(notification)).await {
+        tracing::error!(error = %e, metric_id = %metric_file.id, "Failed to send review notification");
+    }
+}
+
+/// Records and announces that a metric lost its certification (or its pending
+/// review) because its SQL was edited. Called by `update_metric_handler` after
+/// the status has been reset.
+pub async fn record_metric_demotion(
+    metric_file: &MetricFile,
+    actor: &AuthenticatedUser,
+    from_status: Verification,
+) -> Result<()> {
+    let reviewer_ids = fetch_metric_reviewer_ids(metric_file).await?;
+
+    record_review_event(
+        metric_file,
+        &actor.id,
+        REVIEW_ACTION_DEMOTED,
+        from_status,
+        Verification::NotRequested,
+        Some("SQL changed".to_string()),
+        reviewer_ids.clone(),
+        None,
+    )
+    .await?;
+
+    let mut recipients: HashSet<Uuid> = reviewer_ids.into_iter().collect();
+    recipients.insert(metric_file.created_by);
+
+    notify_review_participants(
+        metric_file,
+        actor,
+        "edited the SQL of",
+        Some("Its certification was reset and it needs a new review.".to_string()),
+        recipients,
+    )
+    .await;
+
+    Ok(())
+}
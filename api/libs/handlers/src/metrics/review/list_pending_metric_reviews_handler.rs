@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use database::{
+    enums::Verification,
+    models::{MetricFile, MetricReviewEvent},
+    pool::get_pg_pool,
+    schema::{metric_files, metric_review_events},
+};
+use diesel::{ExpressionMethods, PgArrayExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use super::helpers::fetch_reviewers;
+use super::types::{MetricReviewer, PendingMetricReview, REVIEW_ACTION_REQUESTED};
+
+/// Handler to list the open review requests assigned to the current user,
+/// oldest deadline first
+pub async fn list_pending_metric_reviews_handler(
+    user: &AuthenticatedUser,
+) -> Result<Vec<PendingMetricReview>> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let requests = metric_review_events::table
+        .inner_join(metric_files::table)
+        .filter(metric_review_events::action.eq(REVIEW_ACTION_REQUESTED))
+        .filter(metric_review_events::reviewer_ids.contains(vec![user.id]))
+        .filter(
+            metric_files::verification.eq_any(vec![Verification::Requested, Verification::InReview]),
+        )
+        .filter(metric_files::deleted_at.is_null())
+        .order(metric_review_events::created_at.desc())
+        .load::<(MetricReviewEvent, MetricFile)>(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to load pending reviews: {}", e))?;
+
+    // Only the latest request per metric is still open
+    let mut latest: HashMap<Uuid, (MetricReviewEvent, MetricFile)> = HashMap::new();
+    for (event, metric_file) in requests {
+        latest.entry(metric_file.id).or_insert((event, metric_file));
+    }
+
+    let requester_ids: Vec<Uuid> = latest.values().map(|(event, _)| event.actor_id).collect();
+    let requesters: HashMap<Uuid, MetricReviewer> = fetch_reviewers(&requester_ids)
+        .await?
+        .into_iter()
+        .map(|user| (user.id, user))
+        .collect();
+
+    let now = Utc::now();
+    let mut pending: Vec<PendingMetricReview> = latest
+        .into_values()
+        .map(|(event, metric_file)| PendingMetricReview {
+            metric_id: metric_file.id,
+            metric_name: metric_file.name,
+            status: metric_file.verification,
+            requested_by: requesters
+                .get(&event.actor_id)
+                .cloned()
+                .unwrap_or(MetricReviewer {
+                    id: event.actor_id,
+                    name: None,
+                    email: String::new(),
+                }),
+            requested_at: event.created_at,
+            due_at: event.due_at,
+            overdue: event.due_at.is_some_and(|due_at| due_at < now),
+        })
+        .collect();
+
+    pending.sort_by_key(|review| (review.due_at.is_none(), review.due_at, review.requested_at));
+
+    Ok(pending)
+}
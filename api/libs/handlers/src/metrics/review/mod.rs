@@ -0,0 +1,14 @@
+pub mod add_metric_review_comment_handler;
+pub mod dataset_reviewers_handler;
+pub mod get_metric_review_handler;
+pub mod helpers;
+pub mod list_pending_metric_reviews_handler;
+pub mod transition_metric_review_handler;
+pub mod types;
+
+pub use add_metric_review_comment_handler::*;
+pub use dataset_reviewers_handler::*;
+pub use get_metric_review_handler::*;
+pub use list_pending_metric_reviews_handler::*;
+pub use transition_metric_review_handler::*;
+pub use types::*;
@@ -0,0 +1,151 @@
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use database::{
+    enums::{AssetPermissionRole, WebhookEventType},
+    helpers::metric_files::fetch_metric_file_with_permissions,
+    pool::get_pg_pool,
+    schema::metric_files,
+};
+use diesel::ExpressionMethods;
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use serde_json::json;
+use sharing::check_permission_access;
+use uuid::Uuid;
+
+use super::get_metric_review_handler::get_metric_review_handler;
+use super::helpers::{
+    fetch_latest_review_request, fetch_metric_reviewer_ids, notify_review_participants,
+    record_review_event, review_sla,
+};
+use super::types::{MetricReviewResponse, MetricReviewTransition, MetricReviewTransitionRequest};
+use crate::webhooks::emit_webhook_event;
+
+/// Handler to move a metric through the certification workflow
+///
+/// - `request`: an editor asks the metric's reviewers to certify it (starts the SLA clock)
+/// - `start`: a reviewer picks up the request
+/// - `approve`: a reviewer certifies the metric
+/// - `reject`: a reviewer sends it back with a comment
+///
+/// Every transition is recorded in `metric_review_events` and emailed to the
+/// other participants.
+pub async fn transition_metric_review_handler(
+    metric_id: &Uuid,
+    user: &AuthenticatedUser,
+    request: MetricReviewTransitionRequest,
+) -> Result<MetricReviewResponse> {
+    let metric_file_with_permission = fetch_metric_file_with_permissions(metric_id, &user.id)
+        .await
+        .map_err(|e| anyhow!("Failed to fetch metric file with permissions: {}", e))?
+        .ok_or_else(|| anyhow!("Metric file not found"))?;
+
+    let metric_file = metric_file_with_permission.metric_file;
+    let action = request.action;
+    let comment = request.comment.filter(|c| !c.trim().is_empty());
+
+    let reviewer_ids = fetch_metric_reviewer_ids(&metric_file).await?;
+
+    if action.requires_reviewer() {
+        if !reviewer_ids.contains(&user.id) {
+            return Err(anyhow!(
+                "You don't have permission to review this metric. Only its designated reviewers can {} a review.",
+                action.as_str()
+            ));
+        }
+    } else if !check_permission_access(
+        metric_file_with_permission.permission,
+        &[
+            AssetPermissionRole::CanEdit,
+            AssetPermissionRole::FullAccess,
+            AssetPermissionRole::Owner,
+        ],
+        metric_file.organization_id,
+        &user.organizations,
+    ) {
+        return Err(anyhow!(
+            "You don't have permission to request a review of this metric. Editor or higher role required."
+        ));
+    }
+
+    if action == MetricReviewTransition::Request && reviewer_ids.is_empty() {
+        return Err(anyhow!("No reviewers are configured for this metric's datasets"));
+    }
+    if action == MetricReviewTransition::Reject && comment.is_none() {
+        return Err(anyhow!("A comment is required when rejecting a review"));
+    }
+
+    let from_status = metric_file.verification;
+    let to_status = action.next_status(from_status)?;
+
+    let mut conn = get_pg_pool().get().await?;
+
+    // Guard against a concurrent transition having already moved the metric on
+    let updated = diesel::update(metric_files::table)
+        .filter(metric_files::id.eq(metric_id))
+        .filter(metric_files::deleted_at.is_null())
+        .filter(metric_files::verification.eq(from_status))
+        .set((
+            metric_files::verification.eq(to_status),
+            metric_files::updated_at.eq(Utc::now()),
+        ))
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to update metric status: {}", e))?;
+
+    if updated == 0 {
+        return Err(anyhow!("Metric status changed concurrently, please retry"));
+    }
+
+    let due_at = match action {
+        MetricReviewTransition::Request => Some(Utc::now() + review_sla()),
+        _ => None,
+    };
+    let latest_request = fetch_latest_review_request(metric_id).await?;
+
+    record_review_event(
+        &metric_file,
+        &user.id,
+        action.event_action(),
+        from_status,
+        to_status,
+        comment.clone(),
+        reviewer_ids.clone(),
+        due_at,
+    )
+    .await?;
+
+    // Reviewers hear about requests; the requester and the author hear about decisions
+    let recipients: HashSet<Uuid> = match action {
+        MetricReviewTransition::Request => reviewer_ids.into_iter().collect(),
+        _ => latest_request
+            .map(|event| event.actor_id)
+            .into_iter()
+            .chain(std::iter::once(metric_file.created_by))
+            .collect(),
+    };
+    let action_text = match action {
+        MetricReviewTransition::Request => "requested a review of",
+        MetricReviewTransition::Start => "started reviewing",
+        MetricReviewTransition::Approve => "verified",
+        MetricReviewTransition::Reject => "requested changes to",
+    };
+    notify_review_participants(&metric_file, user, action_text, comment, recipients).await;
+
+    if action == MetricReviewTransition::Approve {
+        emit_webhook_event(
+            metric_file.organization_id,
+            WebhookEventType::MetricVerified,
+            json!({
+                "id": metric_file.id,
+                "name": metric_file.name,
+                "version_number": metric_file.version_history.get_version_number(),
+                "actor_id": user.id,
+            }),
+        );
+    }
+
+    get_metric_review_handler(metric_id, user).await
+}
@@ -0,0 +1,221 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use database::enums::Verification;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A step in the metric certification workflow
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricReviewTransition {
+    /// Author asks the dataset reviewers to certify the metric
+    Request,
+    /// A reviewer picks the request up
+    Start,
+    /// A reviewer certifies the metric
+    Approve,
+    /// A reviewer sends the metric back to the author
+    Reject,
+}
+
+impl MetricReviewTransition {
+    /// Returns the status the metric moves to, or an error if the transition
+    /// isn't allowed from `current`.
+    pub fn next_status(&self, current: Verification) -> Result<Verification> {
+        let next = match (self, current) {
+            (Self::Request, Verification::NotRequested | Verification::Backlogged) => {
+                Verification::Requested
+            }
+            (Self::Start, Verification::Requested) => Verification::InReview,
+            (Self::Approve, Verification::Requested | Verification::InReview) => {
+                Verification::Verified
+            }
+            (Self::Reject, Verification::Requested | Verification::InReview) => {
+                Verification::Backlogged
+            }
+            _ => {
+                return Err(anyhow!(
+                    "Cannot {} a review for a metric with status {:?}",
+                    self.as_str(),
+                    current
+                ))
+            }
+        };
+        Ok(next)
+    }
+
+    /// Returns the transition that moves a metric from `current` to `target`,
+    /// or an error if the workflow has no such step.
+    pub fn to_reach(current: Verification, target: Verification) -> Result<Self> {
+        [Self::Request, Self::Start, Self::Approve, Self::Reject]
+            .into_iter()
+            .find(|action| {
+                action
+                    .next_status(current)
+                    .is_ok_and(|next| next == target)
+            })
+            .ok_or_else(|| {
+                anyhow!(
+                    "Cannot move a metric from {:?} to {:?} through the review workflow",
+                    current,
+                    target
+                )
+            })
+    }
+
+    /// Whether only designated reviewers may perform this transition
+    pub fn requires_reviewer(&self) -> bool {
+        !matches!(self, Self::Request)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Request => "request",
+            Self::Start => "start",
+            Self::Approve => "approve",
+            Self::Reject => "reject",
+        }
+    }
+
+    /// Value stored in `metric_review_events.action`
+    pub fn event_action(&self) -> &'static str {
+        match self {
+            Self::Request => REVIEW_ACTION_REQUESTED,
+            Self::Start => REVIEW_ACTION_STARTED,
+            Self::Approve => REVIEW_ACTION_APPROVED,
+            Self::Reject => REVIEW_ACTION_REJECTED,
+        }
+    }
+}
+
+pub const REVIEW_ACTION_REQUESTED: &str = "requested";
+pub const REVIEW_ACTION_STARTED: &str = "started";
+pub const REVIEW_ACTION_APPROVED: &str = "approved";
+pub const REVIEW_ACTION_REJECTED: &str = "rejected";
+pub const REVIEW_ACTION_DEMOTED: &str = "demoted";
+pub const REVIEW_ACTION_COMMENTED: &str = "commented";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MetricReviewTransitionRequest {
+    pub action: MetricReviewTransition,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MetricReviewCommentRequest {
+    pub comment: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MetricReviewer {
+    pub id: Uuid,
+    pub name: Option<String>,
+    pub email: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MetricReviewEventResponse {
+    pub id: Uuid,
+    pub action: String,
+    pub from_status: Verification,
+    pub to_status: Verification,
+    pub comment: Option<String>,
+    pub actor: MetricReviewer,
+    pub due_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Current certification state of a metric and its review history
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MetricReviewResponse {
+    pub metric_id: Uuid,
+    pub status: Verification,
+    pub reviewers: Vec<MetricReviewer>,
+    /// SLA deadline of the open review request, if any
+    pub due_at: Option<DateTime<Utc>>,
+    pub overdue: bool,
+    pub events: Vec<MetricReviewEventResponse>,
+}
+
+/// A metric waiting on the current user's review
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PendingMetricReview {
+    pub metric_id: Uuid,
+    pub metric_name: String,
+    pub status: Verification,
+    pub requested_by: MetricReviewer,
+    pub requested_at: DateTime<Utc>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub overdue: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SetDatasetReviewersRequest {
+    pub user_ids: Vec<Uuid>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_review_transitions() {
+        use MetricReviewTransition::*;
+
+        assert_eq!(
+            Request.next_status(Verification::NotRequested).unwrap(),
+            Verification::Requested
+        );
+        assert_eq!(
+            Request.next_status(Verification::Backlogged).unwrap(),
+            Verification::Requested
+        );
+        assert_eq!(
+            Start.next_status(Verification::Requested).unwrap(),
+            Verification::InReview
+        );
+        assert_eq!(
+            Approve.next_status(Verification::InReview).unwrap(),
+            Verification::Verified
+        );
+        assert_eq!(
+            Reject.next_status(Verification::Requested).unwrap(),
+            Verification::Backlogged
+        );
+
+        assert!(Request.next_status(Verification::Verified).is_err());
+        assert!(Start.next_status(Verification::InReview).is_err());
+        assert!(Approve.next_status(Verification::NotRequested).is_err());
+        assert!(Reject.next_status(Verification::Verified).is_err());
+    }
+
+    #[test]
+    fn test_review_transition_to_reach() {
+        use MetricReviewTransition::*;
+
+        assert_eq!(
+            MetricReviewTransition::to_reach(Verification::NotRequested, Verification::Requested)
+                .unwrap(),
+            Request
+        );
+        assert_eq!(
+            MetricReviewTransition::to_reach(Verification::InReview, Verification::Verified)
+                .unwrap(),
+            Approve
+        );
+        assert_eq!(
+            MetricReviewTransition::to_reach(Verification::Requested, Verification::Backlogged)
+                .unwrap(),
+            Reject
+        );
+
+        assert!(
+            MetricReviewTransition::to_reach(Verification::NotRequested, Verification::Verified)
+                .is_err()
+        );
+        assert!(
+            MetricReviewTransition::to_reach(Verification::Verified, Verification::NotRequested)
+                .is_err()
+        );
+    }
+}
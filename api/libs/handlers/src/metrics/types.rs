@@ -59,6 +59,19 @@ pub struct BusterMetric {
     pub public_enabled_by: Option<String>,
     pub publicly_accessible: bool,
     pub public_password: Option<String>,
+    /// Time-series annotations to render on the chart
+    pub annotations: Vec<BusterMetricAnnotation>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BusterMetricAnnotation {
+    pub id: Uuid,
+    pub x_value: String,
+    pub text: String,
+    pub created_by: Uuid,
+    pub created_by_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -1,17 +1,23 @@
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
 use database::{
-    enums::{AssetPermissionRole, Verification},
-    helpers::metric_files::fetch_metric_file_with_permissions,
+    enums::{AssetPermissionRole, AssetType, Verification, WebhookEventType},
+    helpers::{
+        metric_files::fetch_metric_file_with_permissions,
+        version_restores::record_version_restore,
+    },
     pool::get_pg_pool,
     schema::{datasets, metric_files},
-    types::{ColumnLabelFormat, DataMetadata, MetricYml, VersionContent, VersionHistory},
+    models::MetricFile,
+    types::{
+        normalize_sql, ColumnLabelFormat, DataMetadata, MetricYml, VersionContent, VersionHistory,
+    },
 };
 use diesel::{AsChangeset, ExpressionMethods, QueryDsl};
 use diesel_async::RunQueryDsl;
 use middleware::AuthenticatedUser;
 use query_engine::data_source_query_routes::query_engine::query_engine;
-use serde_json::Value;
+use serde_json::{json, Value};
 use sharing::check_permission_access;
 use uuid::Uuid;
 use indexmap;
@@ -51,7 +57,9 @@ fn merge_json_objects(base: Value, update: Value) -> Result<Value> {
 }
 
 use crate::metrics::get_metric_handler::get_metric_handler;
+use crate::metrics::review::helpers::record_metric_demotion;
 use crate::metrics::types::BusterMetric;
+use crate::webhooks::emit_webhook_event;
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, Default)]
 pub struct UpdateMetricRequest {
@@ -105,15 +113,16 @@ pub async fn update_metric_handler(
         .await
         .map_err(|e| anyhow!("Failed to fetch metric file with permissions: {}", e))?;
 
-    let (permission, organization_id) =
+    let (permission, previous_metric_file) =
         if let Some(file_with_permission) = metric_file_with_permissions {
             (
                 file_with_permission.permission,
-                file_with_permission.metric_file.organization_id,
+                file_with_permission.metric_file,
             )
         } else {
             return Err(anyhow!("Metric file not found"));
         };
+    let organization_id = previous_metric_file.organization_id;
 
     // Verify the user has at least Editor, FullAccess, or Owner permission
     if !check_permission_access(
@@ -131,6 +140,16 @@ pub async fn update_metric_handler(
         ));
     }
 
+    // Certification is only granted through the review workflow, so editors
+    // can't set a different status here
+    if let Some(verification) = request.verification {
+        if verification != previous_metric_file.verification {
+            return Err(anyhow!(
+                "Verification can only be changed through the metric review workflow"
+            ));
+        }
+    }
+
     // Now get the full metric with all its data needed for the update
     let metric = get_metric_handler(metric_id, user, None, None).await?;
 
@@ -318,6 +337,7 @@ pub async fn update_metric_handler(
         // Overwrite the current version instead of creating a new one
         current_version_history.update_latest_version(content.clone());
     }
+    let saved_version_number = current_version_history.get_version_number();
 
     // Convert content to JSON for storage
     let content_json = serde_json::to_value(content.clone())?;
@@ -334,13 +354,31 @@ pub async fn update_metric_handler(
         data_metadata: Option<DataMetadata>,
     }
 
+    // Editing the SQL of a certified (or under review) metric invalidates the
+    // certification
+    let previous_verification = previous_metric_file.verification;
+    let demoted = matches!(
+        previous_verification,
+        Verification::Verified | Verification::InReview | Verification::Requested
+    ) && normalize_sql(&previous_metric_file.content.sql) != normalize_sql(&content.sql);
+    let verification = if demoted {
+        tracing::info!(
+            metric_id = %metric_id,
+            ?previous_verification,
+            "SQL changed, resetting metric verification"
+        );
+        Some(Verification::NotRequested)
+    } else {
+        None
+    };
+
     // Create the changeset with all fields
     let changeset = MetricFileChangeset {
         name: content.name.clone(),
         content: content_json,
         updated_at: Utc::now(),
         version_history: current_version_history,
-        verification: request.verification, // Use verification from the request
+        verification,
         data_metadata,
     };
 
@@ -353,6 +391,44 @@ pub async fn update_metric_handler(
         .await
         .map_err(|e| anyhow!("Failed to update metric: {}", e))?;
 
+    // Keep an audit trail of restores
+    if let Some(restored_version) = request.restore_to_version {
+        record_version_restore(
+            metric_id,
+            AssetType::MetricFile,
+            &organization_id,
+            restored_version,
+            saved_version_number,
+            &user.id,
+        )
+        .await
+        .map_err(|e| anyhow!("Failed to record version restore: {}", e))?;
+    }
+
+    if demoted {
+        let updated_metric_file = MetricFile {
+            name: content.name.clone(),
+            content: content.clone(),
+            ..previous_metric_file
+        };
+        if let Err(e) =
+            record_metric_demotion(&updated_metric_file, user, previous_verification).await
+        {
+            tracing::error!(metric_id = %metric_id, error = %e, "Failed to record metric demotion");
+        }
+    }
+
+    emit_webhook_event(
+        organization_id,
+        WebhookEventType::MetricUpdated,
+        json!({
+            "id": metric_id,
+            "name": content.name,
+            "version_number": saved_version_number,
+            "actor_id": user.id,
+        }),
+    );
+
     // Return the updated metric - latest version
     get_metric_handler(metric_id, user, None, None).await
 }
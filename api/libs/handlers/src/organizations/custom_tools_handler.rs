@@ -0,0 +1,351 @@
+use std::collections::HashMap;
+
+use agents::tools::custom_tools::{
+    custom_tool_name, header_map, is_valid_custom_tool_name, McpSession, MAX_CUSTOM_TOOL_NAME_LEN,
+    MAX_CUSTOM_TOOL_TIMEOUT,
+};
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use serde_json::Value;
+use uuid::Uuid;
+
+use database::{
+    enums::{CustomToolKind, UserOrganizationRole},
+    helpers::custom_tools::fetch_organization_custom_tools,
+    models::CustomTool,
+    pool::get_pg_pool,
+    schema::custom_tools,
+};
+
+use crate::organizations::organization_models_handler::organization_role;
+use crate::organizations::types::{
+    CreateCustomToolRequest, CustomToolResponse, UpdateCustomToolRequest,
+};
+use middleware::AuthenticatedUser;
+
+const DEFAULT_TIMEOUT_MS: i32 = 30_000;
+
+impl From<CustomTool> for CustomToolResponse {
+    fn from(tool: CustomTool) -> Self {
+        let mut header_names: Vec<String> = tool
+            .headers
+            .as_object()
+            .map(|headers| headers.keys().cloned().collect())
+            .unwrap_or_default();
+        header_names.sort();
+
+        Self {
+            id: tool.id,
+            tool_name: custom_tool_name(&tool.name),
+            name: tool.name,
+            display_name: tool.display_name,
+            description: tool.description,
+            kind: tool.kind,
+            endpoint: tool.endpoint,
+            remote_name: tool.remote_name,
+            input_schema: tool.input_schema,
+            header_names,
+            allowed_roles: tool.allowed_roles,
+            timeout_ms: tool.timeout_ms,
+            enabled: tool.enabled,
+            created_at: tool.created_at,
+            updated_at: tool.updated_at,
+        }
+    }
+}
+
+fn ensure_workspace_admin(user: &AuthenticatedUser, organization_id: &Uuid) -> Result<()> {
+    if organization_role(user, organization_id)? != UserOrganizationRole::WorkspaceAdmin {
+        return Err(anyhow!("User is not a workspace admin"));
+    }
+    Ok(())
+}
+
+fn validate_endpoint(endpoint: &str) -> Result<()> {
+    let url = reqwest::Url::parse(endpoint).map_err(|_| anyhow!("Invalid endpoint URL"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(anyhow!("Invalid endpoint URL: must be http or https"));
+    }
+    Ok(())
+}
+
+fn validate_timeout(timeout_ms: i32) -> Result<()> {
+    if timeout_ms <= 0 || timeout_ms as u128 > MAX_CUSTOM_TOOL_TIMEOUT.as_millis() {
+        return Err(anyhow!(
+            "Invalid timeout: must be between 1 and {} ms",
+            MAX_CUSTOM_TOOL_TIMEOUT.as_millis()
+        ));
+    }
+    Ok(())
+}
+
+fn validate_input_schema(schema: &Value) -> Result<()> {
+    if schema.get("type").and_then(Value::as_str) != Some("object") {
+        return Err(anyhow!(
+            "Invalid input schema: must be a JSON schema of type object"
+        ));
+    }
+    Ok(())
+}
+
+fn headers_value(headers: HashMap<String, String>) -> Result<Value> {
+    let headers = serde_json::to_value(headers)?;
+    header_map(&headers).map_err(|e| anyhow!("Invalid headers: {}", e))?;
+    Ok(headers)
+}
+
+/// Fetches the input schema an MCP server advertises for `remote_name`
+async fn discover_mcp_schema(endpoint: &str, headers: &Value, remote_name: &str) -> Result<Value> {
+    let session = McpSession::connect(reqwest::Client::new(), endpoint, header_map(headers)?)
+        .await
+        .map_err(|e| anyhow!("Failed to reach MCP server: {}", e))?;
+
+    session
+        .list_tools()
+        .await
+        .map_err(|e| anyhow!("Failed to reach MCP server: {}", e))?
+        .into_iter()
+        .find(|tool| tool.name == remote_name)
+        .map(|tool| tool.input_schema)
+        .ok_or_else(|| anyhow!("MCP server has no tool named '{}'", remote_name))
+}
+
+async fn get_custom_tool(organization_id: &Uuid, tool_id: &Uuid) -> Result<CustomTool> {
+    let mut conn = get_pg_pool().get().await?;
+
+    custom_tools::table
+        .filter(custom_tools::id.eq(tool_id))
+        .filter(custom_tools::organization_id.eq(organization_id))
+        .filter(custom_tools::deleted_at.is_null())
+        .first::<CustomTool>(&mut conn)
+        .await
+        .map_err(|e| match e {
+            diesel::NotFound => anyhow!("Custom tool not found"),
+            e => anyhow!("Failed to load custom tool: {}", e),
+        })
+}
+
+fn map_write_error(e: diesel::result::Error) -> anyhow::Error {
+    match e {
+        diesel::result::Error::DatabaseError(
+            diesel::result::DatabaseErrorKind::UniqueViolation,
+            _,
+        ) => anyhow!("A custom tool with this name already exists"),
+        e => anyhow!("Failed to save custom tool: {}", e),
+    }
+}
+
+/// Lists the organization's custom tools
+pub async fn list_custom_tools_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+) -> Result<Vec<CustomToolResponse>> {
+    organization_role(user, &organization_id)?;
+
+    Ok(fetch_organization_custom_tools(&organization_id)
+        .await?
+        .into_iter()
+        .map(CustomToolResponse::from)
+        .collect())
+}
+
+/// Registers a custom tool for the organization
+///
+/// Restricted to workspace admins.
+pub async fn create_custom_tool_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+    request: CreateCustomToolRequest,
+) -> Result<CustomToolResponse> {
+    ensure_workspace_admin(user, &organization_id)?;
+
+    let name = request.name.trim().to_string();
+    if !is_valid_custom_tool_name(&name) {
+        return Err(anyhow!(
+            "Invalid tool name: use lowercase letters, digits and underscores, at most {} characters",
+            MAX_CUSTOM_TOOL_NAME_LEN
+        ));
+    }
+    if request.description.trim().is_empty() {
+        return Err(anyhow!("Invalid description: must not be empty"));
+    }
+    validate_endpoint(&request.endpoint)?;
+    let timeout_ms = request.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS);
+    validate_timeout(timeout_ms)?;
+    let headers = headers_value(request.headers)?;
+
+    let input_schema = match (request.input_schema, request.kind) {
+        (Some(schema), _) => schema,
+        (None, CustomToolKind::Mcp) => {
+            let remote_name = request.remote_name.as_deref().unwrap_or(&name);
+            discover_mcp_schema(&request.endpoint, &headers, remote_name).await?
+        }
+        (None, CustomToolKind::Http) => {
+            return Err(anyhow!("Invalid input schema: required for http tools"))
+        }
+    };
+    validate_input_schema(&input_schema)?;
+
+    let now = Utc::now();
+    let tool = CustomTool {
+        id: Uuid::new_v4(),
+        organization_id,
+        display_name: request
+            .display_name
+            .filter(|display_name| !display_name.trim().is_empty())
+            .unwrap_or_else(|| name.clone()),
+        name,
+        description: request.description,
+        kind: request.kind,
+        endpoint: request.endpoint,
+        remote_name: request.remote_name,
+        input_schema,
+        headers,
+        allowed_roles: request.allowed_roles,
+        timeout_ms,
+        enabled: request.enabled.unwrap_or(true),
+        created_by: user.id,
+        updated_by: user.id,
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+    };
+
+    let mut conn = get_pg_pool().get().await?;
+    diesel::insert_into(custom_tools::table)
+        .values(&tool)
+        .execute(&mut conn)
+        .await
+        .map_err(map_write_error)?;
+
+    Ok(tool.into())
+}
+
+/// Updates a custom tool
+///
+/// Restricted to workspace admins.
+pub async fn update_custom_tool_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+    tool_id: Uuid,
+    request: UpdateCustomToolRequest,
+) -> Result<CustomToolResponse> {
+    ensure_workspace_admin(user, &organization_id)?;
+
+    let mut tool = get_custom_tool(&organization_id, &tool_id).await?;
+
+    if let Some(display_name) = request.display_name {
+        tool.display_name = display_name;
+    }
+    if let Some(description) = request.description {
+        if description.trim().is_empty() {
+            return Err(anyhow!("Invalid description: must not be empty"));
+        }
+        tool.description = description;
+    }
+    if let Some(endpoint) = request.endpoint {
+        validate_endpoint(&endpoint)?;
+        tool.endpoint = endpoint;
+    }
+    if let Some(remote_name) = request.remote_name {
+        tool.remote_name = Some(remote_name);
+    }
+    if let Some(input_schema) = request.input_schema {
+        validate_input_schema(&input_schema)?;
+        tool.input_schema = input_schema;
+    }
+    if let Some(headers) = request.headers {
+        tool.headers = headers_value(headers)?;
+    }
+    if let Some(allowed_roles) = request.allowed_roles {
+        tool.allowed_roles = allowed_roles;
+    }
+    if let Some(timeout_ms) = request.timeout_ms {
+        validate_timeout(timeout_ms)?;
+        tool.timeout_ms = timeout_ms;
+    }
+    if let Some(enabled) = request.enabled {
+        tool.enabled = enabled;
+    }
+    tool.updated_by = user.id;
+    tool.updated_at = Utc::now();
+
+    let mut conn = get_pg_pool().get().await?;
+    diesel::update(custom_tools::table)
+        .filter(custom_tools::id.eq(tool.id))
+        .set((
+            custom_tools::display_name.eq(&tool.display_name),
+            custom_tools::description.eq(&tool.description),
+            custom_tools::endpoint.eq(&tool.endpoint),
+            custom_tools::remote_name.eq(&tool.remote_name),
+            custom_tools::input_schema.eq(&tool.input_schema),
+            custom_tools::headers.eq(&tool.headers),
+            custom_tools::allowed_roles.eq(&tool.allowed_roles),
+            custom_tools::timeout_ms.eq(tool.timeout_ms),
+            custom_tools::enabled.eq(tool.enabled),
+            custom_tools::updated_by.eq(tool.updated_by),
+            custom_tools::updated_at.eq(tool.updated_at),
+        ))
+        .execute(&mut conn)
+        .await
+        .map_err(map_write_error)?;
+
+    Ok(tool.into())
+}
+
+/// Soft deletes a custom tool so it is no longer offered to the agent
+///
+/// Restricted to workspace admins.
+pub async fn delete_custom_tool_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+    tool_id: Uuid,
+) -> Result<()> {
+    ensure_workspace_admin(user, &organization_id)?;
+
+    let tool = get_custom_tool(&organization_id, &tool_id).await?;
+
+    let mut conn = get_pg_pool().get().await?;
+    diesel::update(custom_tools::table)
+        .filter(custom_tools::id.eq(tool.id))
+        .set((
+            custom_tools::deleted_at.eq(Some(Utc::now())),
+            custom_tools::updated_by.eq(user.id),
+        ))
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to delete custom tool: {}", e))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validation() {
+        assert!(validate_endpoint("https://tools.example.com/fx").is_ok());
+        assert!(validate_endpoint("ftp://tools.example.com").is_err());
+        assert!(validate_endpoint("not a url").is_err());
+
+        assert!(validate_timeout(5_000).is_ok());
+        assert!(validate_timeout(0).is_err());
+        assert!(validate_timeout(600_000).is_err());
+
+        assert!(validate_input_schema(&json!({ "type": "object", "properties": {} })).is_ok());
+        assert!(validate_input_schema(&json!({ "type": "string" })).is_err());
+
+        assert!(headers_value(HashMap::from([(
+            "Authorization".to_string(),
+            "Bearer token".to_string()
+        )]))
+        .is_ok());
+        assert!(
+            headers_value(HashMap::from([("bad header".to_string(), "x".to_string())])).is_err()
+        );
+    }
+}
@@ -1,6 +1,14 @@
 pub mod types;
+pub mod custom_tools_handler;
 pub mod update_organization_handler;
 pub mod post_organization_handler;
+pub mod organization_models_handler;
+pub mod organization_usage_handler;
+pub mod terms_handler;
 
+pub use custom_tools_handler::*;
 pub use update_organization_handler::*;
-pub use post_organization_handler::*;
\ No newline at end of file
+pub use post_organization_handler::*;
+pub use organization_models_handler::*;
+pub use organization_usage_handler::*;
+pub use terms_handler::*;
\ No newline at end of file
@@ -0,0 +1,210 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use diesel::{upsert::excluded, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use litellm::{
+    providers::{resolve_model_route, ProviderKind},
+    LiteLLMClient,
+};
+use uuid::Uuid;
+
+use database::{
+    enums::{ModelPurpose, UserOrganizationRole},
+    helpers::model_configs::{fetch_organization_model_configs, get_organization_models},
+    models::OrganizationModelConfig,
+    pool::get_pg_pool,
+    schema::{data_sources, organization_model_configs},
+};
+use stored_values::reset_search_embeddings;
+
+use crate::organizations::types::{
+    OrganizationModelSetting, OrganizationModelsResponse, UpdateOrganizationModelsRequest,
+};
+use middleware::AuthenticatedUser;
+
+/// Returns the user's role in the organization, erroring if they aren't a member
+pub(crate) fn organization_role(
+    user: &AuthenticatedUser,
+    organization_id: &Uuid,
+) -> Result<UserOrganizationRole> {
+    user.organizations
+        .iter()
+        .find(|org| org.id == *organization_id)
+        .map(|org| org.role)
+        .ok_or_else(|| anyhow!("User is not a member of this organization"))
+}
+
+/// Lists the model used for every purpose, marking which ones are overridden
+pub async fn get_organization_models_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+) -> Result<OrganizationModelsResponse> {
+    organization_role(user, &organization_id)?;
+
+    let configs: HashMap<ModelPurpose, OrganizationModelConfig> =
+        fetch_organization_model_configs(&organization_id)
+            .await?
+            .into_iter()
+            .map(|config| (config.purpose, config))
+            .collect();
+
+    let models = ModelPurpose::ALL
+        .iter()
+        .map(|purpose| {
+            let config = configs.get(purpose);
+            OrganizationModelSetting {
+                purpose: *purpose,
+                model: config
+                    .map(|config| config.model.clone())
+                    .unwrap_or_else(|| purpose.default_model().to_string()),
+                default_model: purpose.default_model().to_string(),
+                is_default: config.is_none(),
+                updated_at: config.map(|config| config.updated_at),
+            }
+        })
+        .collect();
+
+    Ok(OrganizationModelsResponse {
+        organization_id,
+        models,
+    })
+}
+
+/// Lists the model identifiers available on the LLM gateway
+pub async fn list_available_models_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+) -> Result<Vec<String>> {
+    organization_role(user, &organization_id)?;
+
+    let mut models: Vec<String> = LiteLLMClient::new(None, None)
+        .list_models()
+        .await
+        .map_err(|e| anyhow!("Failed to list gateway models: {}", e))?
+        .into_iter()
+        .map(|model| model.id)
+        .collect();
+    models.sort();
+    models.dedup();
+
+    Ok(models)
+}
+
+/// Sets or resets the organization's model per purpose
+///
+/// Restricted to workspace admins. Models served by the LLM gateway must be
+/// exposed by it; models routed to a native provider (e.g. `ollama/llama3.1`)
+/// are accepted as-is.
+pub async fn update_organization_models_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+    request: UpdateOrganizationModelsRequest,
+) -> Result<OrganizationModelsResponse> {
+    if organization_role(user, &organization_id)? != UserOrganizationRole::WorkspaceAdmin {
+        return Err(anyhow!("User is not a workspace admin"));
+    }
+
+    let mut to_set = Vec::new();
+    let mut to_reset = Vec::new();
+    for (purpose, model) in request.models {
+        match model.map(|model| model.trim().to_string()) {
+            Some(model) if !model.is_empty() => to_set.push((purpose, model)),
+            _ => to_reset.push(purpose),
+        }
+    }
+
+    if to_set
+        .iter()
+        .any(|(_, model)| resolve_model_route(model).0 == ProviderKind::Gateway)
+    {
+        let available: HashSet<String> = list_available_models_handler(user, organization_id)
+            .await?
+            .into_iter()
+            .collect();
+        if let Some((_, model)) = to_set.iter().find(|(_, model)| {
+            resolve_model_route(model).0 == ProviderKind::Gateway && !available.contains(model)
+        }) {
+            return Err(anyhow!("Model '{}' is not available on the LLM gateway", model));
+        }
+    }
+
+    let previous_embedding_model = get_organization_models(&organization_id)
+        .await?
+        .model_for(ModelPurpose::Embeddings);
+
+    let mut conn = get_pg_pool().get().await?;
+    let now = Utc::now();
+
+    if !to_reset.is_empty() {
+        diesel::delete(organization_model_configs::table)
+            .filter(organization_model_configs::organization_id.eq(organization_id))
+            .filter(organization_model_configs::purpose.eq_any(&to_reset))
+            .execute(&mut conn)
+            .await
+            .map_err(|e| anyhow!("Failed to reset organization models: {}", e))?;
+    }
+
+    if !to_set.is_empty() {
+        let rows: Vec<OrganizationModelConfig> = to_set
+            .into_iter()
+            .map(|(purpose, model)| OrganizationModelConfig {
+                organization_id,
+                purpose,
+                model,
+                updated_by: user.id,
+                created_at: now,
+                updated_at: now,
+            })
+            .collect();
+
+        diesel::insert_into(organization_model_configs::table)
+            .values(&rows)
+            .on_conflict((
+                organization_model_configs::organization_id,
+                organization_model_configs::purpose,
+            ))
+            .do_update()
+            .set((
+                organization_model_configs::model.eq(excluded(organization_model_configs::model)),
+                organization_model_configs::updated_by
+                    .eq(excluded(organization_model_configs::updated_by)),
+                organization_model_configs::updated_at
+                    .eq(excluded(organization_model_configs::updated_at)),
+            ))
+            .execute(&mut conn)
+            .await
+            .map_err(|e| anyhow!("Failed to update organization models: {}", e))?;
+    }
+
+    let embedding_model = get_organization_models(&organization_id)
+        .await?
+        .model_for(ModelPurpose::Embeddings);
+    if embedding_model != previous_embedding_model {
+        let data_source_ids = data_sources::table
+            .filter(data_sources::organization_id.eq(organization_id))
+            .filter(data_sources::deleted_at.is_null())
+            .select(data_sources::id)
+            .load::<Uuid>(&mut conn)
+            .await
+            .map_err(|e| anyhow!("Failed to load data sources: {}", e))?;
+        tokio::spawn(reset_stored_values(data_source_ids, embedding_model));
+    }
+
+    get_organization_models_handler(user, organization_id).await
+}
+
+/// Drops the stored values embedded with the previous model and queues them
+/// to be embedded again, so value search never compares vectors across models
+async fn reset_stored_values(data_source_ids: Vec<Uuid>, embedding_model: String) {
+    for data_source_id in data_source_ids {
+        if let Err(e) = reset_search_embeddings(data_source_id, &embedding_model).await {
+            tracing::error!(
+                data_source_id = %data_source_id,
+                "Failed to reset stored values after the embedding model changed: {}",
+                e
+            );
+        }
+    }
+}
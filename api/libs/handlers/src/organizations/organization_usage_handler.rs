@@ -0,0 +1,189 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use diesel::{upsert::excluded, ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use database::{
+    enums::UserOrganizationRole,
+    helpers::llm_usage::{
+        get_llm_usage_rollup, get_organization_llm_budget, get_organization_llm_spend,
+        LlmUsageGrouping, LlmUsageScope,
+    },
+    models::OrganizationLlmBudget,
+    pool::get_pg_pool,
+    schema::{chats, organization_llm_budgets},
+};
+
+use crate::llm_usage::current_budget_period_start;
+use crate::organizations::organization_role;
+use crate::organizations::types::{
+    ChatUsageResponse, LlmBudgetStatus, LlmUsageTotals, OrganizationUsageQuery,
+    OrganizationUsageResponse, UpdateOrganizationBudgetRequest,
+};
+use middleware::AuthenticatedUser;
+
+fn ensure_workspace_admin(user: &AuthenticatedUser, organization_id: &Uuid) -> Result<()> {
+    if organization_role(user, organization_id)? != UserOrganizationRole::WorkspaceAdmin {
+        return Err(anyhow!("User is not a workspace admin"));
+    }
+    Ok(())
+}
+
+/// The organization's budget and how much of it this month has used
+async fn budget_status(organization_id: &Uuid) -> Result<Option<LlmBudgetStatus>> {
+    let Some(budget) = get_organization_llm_budget(organization_id).await? else {
+        return Ok(None);
+    };
+
+    let spent_this_month =
+        get_organization_llm_spend(organization_id, current_budget_period_start(Utc::now()))
+            .await?;
+
+    Ok(Some(LlmBudgetStatus {
+        monthly_budget: budget.monthly_budget,
+        spent_this_month,
+        remaining: (budget.monthly_budget - spent_this_month).max(0.0),
+        exceeded: spent_this_month >= budget.monthly_budget,
+        updated_at: budget.updated_at,
+    }))
+}
+
+/// Reports the organization's LLM usage per day, user and model
+///
+/// Restricted to workspace admins. Without a `start` the report covers the
+/// current month.
+pub async fn get_organization_usage_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+    query: OrganizationUsageQuery,
+) -> Result<OrganizationUsageResponse> {
+    ensure_workspace_admin(user, &organization_id)?;
+
+    let start = query
+        .start
+        .unwrap_or_else(|| current_budget_period_start(Utc::now()));
+    if let Some(end) = query.end {
+        if end <= start {
+            return Err(anyhow!("Invalid usage window: end must be after start"));
+        }
+    }
+
+    let scope = LlmUsageScope::Organization(organization_id);
+    let (daily, by_user, by_model, budget) = tokio::try_join!(
+        get_llm_usage_rollup(scope, LlmUsageGrouping::Day, Some(start), query.end),
+        get_llm_usage_rollup(scope, LlmUsageGrouping::User, Some(start), query.end),
+        get_llm_usage_rollup(scope, LlmUsageGrouping::Model, Some(start), query.end),
+        budget_status(&organization_id),
+    )?;
+
+    Ok(OrganizationUsageResponse {
+        organization_id,
+        start,
+        end: query.end,
+        totals: LlmUsageTotals::from_rollup(&daily),
+        daily,
+        by_user,
+        by_model,
+        budget,
+    })
+}
+
+/// Reports the LLM usage of one of the organization's chats, per message
+///
+/// Restricted to workspace admins.
+pub async fn get_chat_usage_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+    chat_id: Uuid,
+) -> Result<ChatUsageResponse> {
+    ensure_workspace_admin(user, &organization_id)?;
+
+    let mut conn = get_pg_pool().get().await?;
+    let chat_organization_id = chats::table
+        .filter(chats::id.eq(chat_id))
+        .select(chats::organization_id)
+        .first::<Uuid>(&mut conn)
+        .await
+        .optional()?;
+    if chat_organization_id != Some(organization_id) {
+        return Err(anyhow!("Chat not found"));
+    }
+
+    let by_message = get_llm_usage_rollup(
+        LlmUsageScope::Chat(chat_id),
+        LlmUsageGrouping::Message,
+        None,
+        None,
+    )
+    .await?;
+
+    Ok(ChatUsageResponse {
+        chat_id,
+        totals: LlmUsageTotals::from_rollup(&by_message),
+        by_message,
+    })
+}
+
+/// Gets the organization's monthly LLM budget and this month's spend
+pub async fn get_organization_budget_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+) -> Result<Option<LlmBudgetStatus>> {
+    ensure_workspace_admin(user, &organization_id)?;
+    budget_status(&organization_id).await
+}
+
+/// Sets or removes the organization's monthly LLM budget
+///
+/// Restricted to workspace admins. Once spend reaches the budget, new chats
+/// are refused until the next month.
+pub async fn update_organization_budget_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+    request: UpdateOrganizationBudgetRequest,
+) -> Result<Option<LlmBudgetStatus>> {
+    ensure_workspace_admin(user, &organization_id)?;
+
+    let mut conn = get_pg_pool().get().await?;
+
+    match request.monthly_budget {
+        Some(monthly_budget) => {
+            if !monthly_budget.is_finite() || monthly_budget < 0.0 {
+                return Err(anyhow!("Invalid budget: must be a non-negative amount"));
+            }
+
+            let now = Utc::now();
+            diesel::insert_into(organization_llm_budgets::table)
+                .values(&OrganizationLlmBudget {
+                    organization_id,
+                    monthly_budget,
+                    updated_by: user.id,
+                    created_at: now,
+                    updated_at: now,
+                })
+                .on_conflict(organization_llm_budgets::organization_id)
+                .do_update()
+                .set((
+                    organization_llm_budgets::monthly_budget
+                        .eq(excluded(organization_llm_budgets::monthly_budget)),
+                    organization_llm_budgets::updated_by
+                        .eq(excluded(organization_llm_budgets::updated_by)),
+                    organization_llm_budgets::updated_at
+                        .eq(excluded(organization_llm_budgets::updated_at)),
+                ))
+                .execute(&mut conn)
+                .await
+                .map_err(|e| anyhow!("Failed to update organization budget: {}", e))?;
+        }
+        None => {
+            diesel::delete(organization_llm_budgets::table)
+                .filter(organization_llm_budgets::organization_id.eq(organization_id))
+                .execute(&mut conn)
+                .await
+                .map_err(|e| anyhow!("Failed to remove organization budget: {}", e))?;
+        }
+    }
+
+    budget_status(&organization_id).await
+}
@@ -0,0 +1,321 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use database::{
+    enums::{TermKind, UserOrganizationRole},
+    helpers::terms::{fetch_organization_terms, fetch_term_dataset_ids, TermWithDatasets},
+    models::{Term, TermToDataset},
+    pool::get_pg_pool,
+    schema::{datasets, terms, terms_to_datasets},
+};
+
+use crate::organizations::organization_models_handler::organization_role;
+use crate::organizations::types::{CreateTermRequest, TermResponse, UpdateTermRequest};
+use middleware::AuthenticatedUser;
+
+impl From<TermWithDatasets> for TermResponse {
+    fn from(entry: TermWithDatasets) -> Self {
+        let TermWithDatasets { term, dataset_ids } = entry;
+        Self {
+            id: term.id,
+            name: term.name,
+            kind: term.kind,
+            definition: term.definition,
+            sql_snippet: term.sql_snippet,
+            dataset_ids,
+            created_at: term.created_at,
+            updated_at: term.updated_at,
+        }
+    }
+}
+
+fn ensure_knowledge_base_editor(user: &AuthenticatedUser, organization_id: &Uuid) -> Result<()> {
+    match organization_role(user, organization_id)? {
+        UserOrganizationRole::WorkspaceAdmin | UserOrganizationRole::DataAdmin => Ok(()),
+        _ => Err(anyhow!("User is not a workspace or data admin")),
+    }
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.trim().is_empty())
+}
+
+fn validate_term(term: &Term) -> Result<()> {
+    if term.name.trim().is_empty() {
+        return Err(anyhow!("Invalid name: must not be empty"));
+    }
+    match term.kind {
+        TermKind::Term if term.definition.is_none() && term.sql_snippet.is_none() => Err(anyhow!(
+            "Invalid term: a definition or SQL snippet is required"
+        )),
+        TermKind::Instruction if term.definition.is_none() => {
+            Err(anyhow!("Invalid instruction: a definition is required"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that every dataset belongs to the organization
+async fn validate_dataset_ids(organization_id: &Uuid, dataset_ids: &[Uuid]) -> Result<()> {
+    if dataset_ids.is_empty() {
+        return Ok(());
+    }
+
+    let mut conn = get_pg_pool().get().await?;
+    let found = datasets::table
+        .filter(datasets::id.eq_any(dataset_ids))
+        .filter(datasets::organization_id.eq(organization_id))
+        .filter(datasets::deleted_at.is_null())
+        .count()
+        .get_result::<i64>(&mut conn)
+        .await?;
+
+    let mut unique = dataset_ids.to_vec();
+    unique.sort();
+    unique.dedup();
+    if found as usize != unique.len() {
+        return Err(anyhow!("Invalid dataset_ids: every dataset must belong to the organization"));
+    }
+    Ok(())
+}
+
+/// Replaces the datasets a term is linked to
+async fn set_term_datasets(term_id: &Uuid, dataset_ids: &[Uuid]) -> Result<()> {
+    let mut conn = get_pg_pool().get().await?;
+
+    diesel::delete(terms_to_datasets::table)
+        .filter(terms_to_datasets::term_id.eq(term_id))
+        .execute(&mut conn)
+        .await?;
+
+    let now = Utc::now();
+    let links: Vec<TermToDataset> = dataset_ids
+        .iter()
+        .map(|dataset_id| TermToDataset {
+            term_id: *term_id,
+            dataset_id: *dataset_id,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        })
+        .collect();
+
+    diesel::insert_into(terms_to_datasets::table)
+        .values(&links)
+        .on_conflict_do_nothing()
+        .execute(&mut conn)
+        .await?;
+
+    Ok(())
+}
+
+async fn get_term(organization_id: &Uuid, term_id: &Uuid) -> Result<Term> {
+    let mut conn = get_pg_pool().get().await?;
+
+    terms::table
+        .filter(terms::id.eq(term_id))
+        .filter(terms::organization_id.eq(organization_id))
+        .filter(terms::deleted_at.is_null())
+        .first::<Term>(&mut conn)
+        .await
+        .map_err(|e| match e {
+            diesel::NotFound => anyhow!("Term not found"),
+            e => anyhow!("Failed to load term: {}", e),
+        })
+}
+
+/// Lists the organization's knowledge base
+pub async fn list_terms_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+) -> Result<Vec<TermResponse>> {
+    organization_role(user, &organization_id)?;
+
+    Ok(fetch_organization_terms(&organization_id)
+        .await?
+        .into_iter()
+        .map(TermResponse::from)
+        .collect())
+}
+
+/// Adds a glossary term or instruction to the organization's knowledge base
+///
+/// Restricted to workspace and data admins.
+pub async fn create_term_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+    request: CreateTermRequest,
+) -> Result<TermResponse> {
+    ensure_knowledge_base_editor(user, &organization_id)?;
+
+    let now = Utc::now();
+    let term = Term {
+        id: Uuid::new_v4(),
+        name: request.name.trim().to_string(),
+        definition: non_empty(request.definition),
+        sql_snippet: non_empty(request.sql_snippet),
+        organization_id,
+        created_by: user.id,
+        updated_by: user.id,
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+        kind: request.kind,
+    };
+    validate_term(&term)?;
+    validate_dataset_ids(&organization_id, &request.dataset_ids).await?;
+
+    let mut conn = get_pg_pool().get().await?;
+    diesel::insert_into(terms::table)
+        .values(&term)
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to save term: {}", e))?;
+    drop(conn);
+
+    set_term_datasets(&term.id, &request.dataset_ids).await?;
+
+    Ok(TermWithDatasets {
+        term,
+        dataset_ids: request.dataset_ids,
+    }
+    .into())
+}
+
+/// Updates a knowledge base entry
+///
+/// Restricted to workspace and data admins.
+pub async fn update_term_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+    term_id: Uuid,
+    request: UpdateTermRequest,
+) -> Result<TermResponse> {
+    ensure_knowledge_base_editor(user, &organization_id)?;
+
+    let mut term = get_term(&organization_id, &term_id).await?;
+
+    if let Some(name) = request.name {
+        term.name = name.trim().to_string();
+    }
+    if let Some(kind) = request.kind {
+        term.kind = kind;
+    }
+    if let Some(definition) = request.definition {
+        term.definition = non_empty(Some(definition));
+    }
+    if let Some(sql_snippet) = request.sql_snippet {
+        term.sql_snippet = non_empty(Some(sql_snippet));
+    }
+    validate_term(&term)?;
+    if let Some(dataset_ids) = &request.dataset_ids {
+        validate_dataset_ids(&organization_id, dataset_ids).await?;
+    }
+    term.updated_by = user.id;
+    term.updated_at = Utc::now();
+
+    let mut conn = get_pg_pool().get().await?;
+    diesel::update(terms::table)
+        .filter(terms::id.eq(term.id))
+        .set((
+            terms::name.eq(&term.name),
+            terms::kind.eq(term.kind),
+            terms::definition.eq(&term.definition),
+            terms::sql_snippet.eq(&term.sql_snippet),
+            terms::updated_by.eq(term.updated_by),
+            terms::updated_at.eq(term.updated_at),
+        ))
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to save term: {}", e))?;
+    drop(conn);
+
+    let dataset_ids = match request.dataset_ids {
+        Some(dataset_ids) => {
+            set_term_datasets(&term.id, &dataset_ids).await?;
+            dataset_ids
+        }
+        None => fetch_term_dataset_ids(&[term.id])
+            .await?
+            .remove(&term.id)
+            .unwrap_or_default(),
+    };
+
+    Ok(TermWithDatasets { term, dataset_ids }.into())
+}
+
+/// Soft deletes a knowledge base entry so the agent no longer sees it
+///
+/// Restricted to workspace and data admins.
+pub async fn delete_term_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+    term_id: Uuid,
+) -> Result<()> {
+    ensure_knowledge_base_editor(user, &organization_id)?;
+
+    let term = get_term(&organization_id, &term_id).await?;
+
+    let mut conn = get_pg_pool().get().await?;
+    diesel::update(terms::table)
+        .filter(terms::id.eq(term.id))
+        .set((
+            terms::deleted_at.eq(Some(Utc::now())),
+            terms::updated_by.eq(user.id),
+        ))
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to delete term: {}", e))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(kind: TermKind, definition: Option<&str>, sql_snippet: Option<&str>) -> Term {
+        Term {
+            id: Uuid::new_v4(),
+            name: "Active customer".to_string(),
+            definition: definition.map(str::to_string),
+            sql_snippet: sql_snippet.map(str::to_string),
+            organization_id: Uuid::new_v4(),
+            created_by: Uuid::new_v4(),
+            updated_by: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_validate_term() {
+        assert!(validate_term(&term(
+            TermKind::Term,
+            Some("Ordered in the last 90 days"),
+            None
+        ))
+        .is_ok());
+        assert!(validate_term(&term(
+            TermKind::Term,
+            None,
+            Some("last_order_at > now() - interval '90 days'")
+        ))
+        .is_ok());
+        assert!(validate_term(&term(TermKind::Term, None, None)).is_err());
+        assert!(validate_term(&term(TermKind::Instruction, None, Some("select 1"))).is_err());
+
+        let mut unnamed = term(
+            TermKind::Instruction,
+            Some("Fiscal years start in February"),
+            None,
+        );
+        unnamed.name = "  ".to_string();
+        assert!(validate_term(&unnamed).is_err());
+    }
+}
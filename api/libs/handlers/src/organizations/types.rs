@@ -1,4 +1,12 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use database::{
+    enums::{CustomToolKind, ModelPurpose, TermKind, UserOrganizationRole},
+    helpers::llm_usage::LlmUsageRollup,
+};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use uuid::Uuid;
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -10,4 +18,184 @@ pub struct UpdateOrganizationRequest {
 pub struct OrganizationResponse {
     pub id: Uuid,
     pub name: String,
-}
\ No newline at end of file
+}
+#[derive(Serialize, Deserialize, Clone)]
+pub struct OrganizationModelSetting {
+    pub purpose: ModelPurpose,
+    pub model: String,
+    pub default_model: String,
+    pub is_default: bool,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct OrganizationModelsResponse {
+    pub organization_id: Uuid,
+    pub models: Vec<OrganizationModelSetting>,
+}
+
+/// Model overrides keyed by purpose; `null` resets a purpose to its default
+#[derive(Serialize, Deserialize, Clone)]
+pub struct UpdateOrganizationModelsRequest {
+    pub models: HashMap<ModelPurpose, Option<String>>,
+}
+
+/// Time window for a usage report; defaults to the current month so far
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct OrganizationUsageQuery {
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq)]
+pub struct LlmUsageTotals {
+    pub calls: i64,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub total_tokens: i64,
+    pub estimated_cost: f64,
+}
+
+impl LlmUsageTotals {
+    pub fn from_rollup(rollup: &[LlmUsageRollup]) -> Self {
+        rollup.iter().fold(Self::default(), |totals, row| Self {
+            calls: totals.calls + row.calls,
+            prompt_tokens: totals.prompt_tokens + row.prompt_tokens,
+            completion_tokens: totals.completion_tokens + row.completion_tokens,
+            total_tokens: totals.total_tokens + row.total_tokens,
+            estimated_cost: totals.estimated_cost + row.estimated_cost,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LlmBudgetStatus {
+    pub monthly_budget: f64,
+    pub spent_this_month: f64,
+    pub remaining: f64,
+    pub exceeded: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct OrganizationUsageResponse {
+    pub organization_id: Uuid,
+    pub start: DateTime<Utc>,
+    pub end: Option<DateTime<Utc>>,
+    pub totals: LlmUsageTotals,
+    /// Keyed by UTC day (`YYYY-MM-DD`)
+    pub daily: Vec<LlmUsageRollup>,
+    /// Keyed by user id
+    pub by_user: Vec<LlmUsageRollup>,
+    pub by_model: Vec<LlmUsageRollup>,
+    pub budget: Option<LlmBudgetStatus>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ChatUsageResponse {
+    pub chat_id: Uuid,
+    pub totals: LlmUsageTotals,
+    /// Keyed by message id
+    pub by_message: Vec<LlmUsageRollup>,
+}
+
+/// Sets the monthly budget in USD; `null` removes it
+#[derive(Serialize, Deserialize, Clone)]
+pub struct UpdateOrganizationBudgetRequest {
+    pub monthly_budget: Option<f64>,
+}
+
+/// A custom tool as returned by the API; header values are never included
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CustomToolResponse {
+    pub id: Uuid,
+    pub name: String,
+    /// The name the agent calls the tool by
+    pub tool_name: String,
+    pub display_name: String,
+    pub description: String,
+    pub kind: CustomToolKind,
+    pub endpoint: String,
+    pub remote_name: Option<String>,
+    pub input_schema: Value,
+    pub header_names: Vec<String>,
+    pub allowed_roles: Vec<UserOrganizationRole>,
+    pub timeout_ms: i32,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Registers a tool. MCP tools without an `input_schema` take the one the
+/// server advertises.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CreateCustomToolRequest {
+    pub name: String,
+    pub display_name: Option<String>,
+    pub description: String,
+    pub kind: CustomToolKind,
+    pub endpoint: String,
+    pub remote_name: Option<String>,
+    pub input_schema: Option<Value>,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Roles allowed to use the tool; empty allows every role
+    #[serde(default)]
+    pub allowed_roles: Vec<UserOrganizationRole>,
+    pub timeout_ms: Option<i32>,
+    pub enabled: Option<bool>,
+}
+
+/// Fields to change; `headers` replaces the stored headers entirely
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct UpdateCustomToolRequest {
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub endpoint: Option<String>,
+    pub remote_name: Option<String>,
+    pub input_schema: Option<Value>,
+    pub headers: Option<HashMap<String, String>>,
+    pub allowed_roles: Option<Vec<UserOrganizationRole>>,
+    pub timeout_ms: Option<i32>,
+    pub enabled: Option<bool>,
+}
+
+/// A knowledge base entry
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TermResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub kind: TermKind,
+    pub definition: Option<String>,
+    pub sql_snippet: Option<String>,
+    /// Datasets the entry applies to
+    pub dataset_ids: Vec<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Adds a glossary term or an instruction to the knowledge base
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CreateTermRequest {
+    pub name: String,
+    #[serde(default = "default_term_kind")]
+    pub kind: TermKind,
+    pub definition: Option<String>,
+    pub sql_snippet: Option<String>,
+    #[serde(default)]
+    pub dataset_ids: Vec<Uuid>,
+}
+
+fn default_term_kind() -> TermKind {
+    TermKind::Term
+}
+
+/// Fields to change; `dataset_ids` replaces the linked datasets entirely
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct UpdateTermRequest {
+    pub name: Option<String>,
+    pub kind: Option<TermKind>,
+    pub definition: Option<String>,
+    pub sql_snippet: Option<String>,
+    pub dataset_ids: Option<Vec<Uuid>>,
+}
@@ -0,0 +1,121 @@
+use std::env;
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use dashmap::DashMap;
+use slack::{verify_request, EventEnvelope, SlackEvent, SLACK_SIGNING_SECRET_ENV};
+
+use super::helpers::fetch_integration_by_team;
+use super::mention_handler::answer_mention;
+use super::types::SlackEventResponse;
+
+/// How long an event id is remembered to drop Slack's redeliveries
+const SEEN_EVENT_TTL: Duration = Duration::from_secs(60 * 10);
+
+/// Event ids handled recently by this server, with when they arrived
+static SEEN_EVENTS: LazyLock<DashMap<String, Instant>> = LazyLock::new(DashMap::new);
+
+/// Handles a request to the Slack Events API endpoint
+///
+/// The request must carry a valid signature from the app's signing secret.
+/// Slack expects an answer within three seconds, so questions are answered
+/// in the background after the event is acknowledged.
+pub async fn slack_events_handler(
+    timestamp: Option<&str>,
+    signature: Option<&str>,
+    body: &[u8],
+) -> Result<SlackEventResponse> {
+    let signing_secret = env::var(SLACK_SIGNING_SECRET_ENV)
+        .map_err(|_| anyhow!("{} is not set", SLACK_SIGNING_SECRET_ENV))?;
+    let envelope = parse_event(&signing_secret, timestamp, signature, body)?;
+
+    match envelope {
+        EventEnvelope::UrlVerification { challenge } => {
+            Ok(SlackEventResponse::Challenge(challenge))
+        }
+        EventEnvelope::EventCallback {
+            team_id,
+            event_id,
+            event: SlackEvent::AppMention(mention),
+        } => {
+            // Ignore other bots, including our own replies
+            if mention.bot_id.is_some() || !first_delivery(&event_id) {
+                return Ok(SlackEventResponse::Accepted);
+            }
+
+            let Some(integration) = fetch_integration_by_team(&team_id).await? else {
+                tracing::warn!(team_id = %team_id, "Slack event from a workspace that isn't connected");
+                return Ok(SlackEventResponse::Accepted);
+            };
+
+            tokio::spawn(async move {
+                if let Err(e) = answer_mention(integration, mention).await {
+                    tracing::error!(event_id = %event_id, "Failed to answer Slack mention: {}", e);
+                }
+            });
+            Ok(SlackEventResponse::Accepted)
+        }
+        _ => Ok(SlackEventResponse::Accepted),
+    }
+}
+
+/// Verifies the request's signature and parses its body
+fn parse_event(
+    signing_secret: &str,
+    timestamp: Option<&str>,
+    signature: Option<&str>,
+    body: &[u8],
+) -> Result<EventEnvelope> {
+    let (Some(timestamp), Some(signature)) = (timestamp, signature) else {
+        return Err(anyhow!("Invalid Slack request: missing signature headers"));
+    };
+    verify_request(
+        signing_secret,
+        timestamp,
+        body,
+        signature,
+        Utc::now().timestamp(),
+    )?;
+
+    serde_json::from_slice(body).map_err(|e| anyhow!("Invalid Slack event: {}", e))
+}
+
+/// Whether this is the first time the event arrived here; Slack redelivers
+/// events it thinks weren't acknowledged
+fn first_delivery(event_id: &str) -> bool {
+    SEEN_EVENTS.retain(|_, seen_at| seen_at.elapsed() < SEEN_EVENT_TTL);
+    SEEN_EVENTS
+        .insert(event_id.to_string(), Instant::now())
+        .is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slack::sign_request;
+
+    #[test]
+    fn test_parse_event() {
+        let body = br#"{"type":"url_verification","token":"t","challenge":"abc123"}"#;
+        let timestamp = Utc::now().timestamp().to_string();
+        let signature = sign_request("secret", &timestamp, body);
+
+        let envelope = parse_event("secret", Some(&timestamp), Some(&signature), body).unwrap();
+        assert!(matches!(
+            envelope,
+            EventEnvelope::UrlVerification { challenge } if challenge == "abc123"
+        ));
+
+        assert!(parse_event("other", Some(&timestamp), Some(&signature), body).is_err());
+        assert!(parse_event("secret", Some(&timestamp), None, body).is_err());
+    }
+
+    #[test]
+    fn test_first_delivery() {
+        assert!(first_delivery("Ev-test-1"));
+        assert!(!first_delivery("Ev-test-1"));
+        assert!(first_delivery("Ev-test-2"));
+    }
+}
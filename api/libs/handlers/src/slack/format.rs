@@ -0,0 +1,253 @@
+use regex::Regex;
+use serde_json::Value;
+use slack::blocks::{context, divider, link_button, section};
+use std::sync::LazyLock;
+
+use crate::chats::export::{ChatResponse, ExportedMetric};
+
+/// Rows of each metric shown in Slack; the full results are a click away
+pub(crate) const SLACK_ROW_LIMIT: i64 = 10;
+
+/// Widest a table cell may be before it is cut
+const MAX_CELL_CHARS: usize = 24;
+
+/// Slack rejects messages with more blocks than this
+const MAX_BLOCKS: usize = 50;
+
+static MENTION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<@[A-Z0-9]+(\|[^>]*)?>").unwrap());
+static HEADING: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^#{1,6}\s+(.+)$").unwrap());
+static BOLD: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\*\*(.+?)\*\*").unwrap());
+static LINK: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\(([^)\s]+)\)").unwrap());
+
+/// The question in a mention, without user mentions and Slack's escaping
+pub(crate) fn question_text(text: &str) -> String {
+    MENTION
+        .replace_all(text, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+/// Converts the agent's Markdown to Slack's `mrkdwn`
+pub(crate) fn to_mrkdwn(markdown: &str) -> String {
+    let text = escape(markdown);
+    let text = HEADING.replace_all(&text, "*$1*");
+    let text = BOLD.replace_all(&text, "*$1*");
+    LINK.replace_all(&text, "<$2|$1>").into_owned()
+}
+
+/// Escapes the characters Slack treats as control sequences
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Status shown while the agent works, e.g. "Searching your data catalog…"
+pub(crate) fn status_text(step: Option<&str>) -> String {
+    format!("_{}…_", step.unwrap_or("Thinking").trim_end_matches('…'))
+}
+
+/// Renders result rows as a fixed-width table in a code block
+pub(crate) fn results_table(metric: &ExportedMetric) -> String {
+    let cell = |text: String| -> String {
+        let text = text.replace(['\n', '`'], " ");
+        if text.chars().count() > MAX_CELL_CHARS {
+            let mut cut: String = text.chars().take(MAX_CELL_CHARS - 1).collect();
+            cut.push('…');
+            cut
+        } else {
+            text
+        }
+    };
+
+    let header: Vec<String> = metric.columns.iter().cloned().map(cell).collect();
+    let rows: Vec<Vec<String>> = metric
+        .rows
+        .iter()
+        .map(|row| row.iter().map(|value| cell(cell_text(value))).collect())
+        .collect();
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .chain(std::iter::once(&header[i]))
+                .map(|text| text.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |cells: &[String]| -> String {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(text, width)| format!("{:<width$}", text, width = width))
+            .collect::<Vec<_>>()
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut table = String::from("```\n");
+    table.push_str(&line(&header));
+    table.push('\n');
+    table.push_str(
+        &widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("-+-"),
+    );
+    table.push('\n');
+    for row in &rows {
+        table.push_str(&line(row));
+        table.push('\n');
+    }
+    table.push_str("```");
+    table
+}
+
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn metric_blocks(metric: &ExportedMetric) -> Vec<Value> {
+    let mut blocks = vec![section(&format!(
+        "*<{}|{}>*",
+        metric.link,
+        escape(&metric.name)
+    ))];
+    if let Some(description) = &metric.description {
+        blocks.push(context(&escape(description)));
+    }
+
+    if let Some(error) = &metric.error {
+        blocks.push(context(&escape(error)));
+    } else if metric.columns.is_empty() {
+        blocks.push(context("No rows returned"));
+    } else {
+        blocks.push(section(&results_table(metric)));
+        if metric.truncated {
+            blocks.push(context(&format!(
+                "Showing the first {} rows. Open the metric for the full results.",
+                metric.rows.len()
+            )));
+        }
+    }
+    blocks
+}
+
+/// Blocks for the agent's answer: its text, a table per metric, dashboard
+/// links and a button to continue in Buster
+pub(crate) fn answer_blocks(responses: &[ChatResponse], chat_link: &str) -> Vec<Value> {
+    let mut blocks = Vec::new();
+    for response in responses {
+        match response {
+            ChatResponse::Text(text) => blocks.push(section(&to_mrkdwn(text))),
+            ChatResponse::Metric(metric) => {
+                blocks.push(divider());
+                blocks.extend(metric_blocks(metric));
+            }
+            ChatResponse::Dashboard { name, link } => blocks.push(section(&format!(
+                ":bar_chart: Dashboard: <{}|{}>",
+                link,
+                escape(name)
+            ))),
+        }
+    }
+
+    // Keep room for the button
+    blocks.truncate(MAX_BLOCKS - 2);
+    blocks.push(divider());
+    blocks.push(link_button("Open in Buster", chat_link));
+    blocks
+}
+
+/// Plain text of the answer, for notifications
+pub(crate) fn answer_fallback(responses: &[ChatResponse]) -> String {
+    responses
+        .iter()
+        .find_map(|response| match response {
+            ChatResponse::Text(text) => Some(text.clone()),
+            _ => None,
+        })
+        .unwrap_or_else(|| "Here's what I found".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn metric(rows: Vec<Vec<Value>>, truncated: bool) -> ExportedMetric {
+        ExportedMetric {
+            name: "Revenue by region".to_string(),
+            description: None,
+            sql: Some("select region, revenue from sales".to_string()),
+            link: "https://buster.example/app/metrics/1".to_string(),
+            columns: vec!["region".to_string(), "revenue".to_string()],
+            rows,
+            truncated,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_question_text() {
+        assert_eq!(
+            question_text("<@U0BOT> revenue &gt; 1000 by <@U123|ada>  "),
+            "revenue > 1000 by"
+        );
+    }
+
+    #[test]
+    fn test_to_mrkdwn() {
+        assert_eq!(
+            to_mrkdwn("## Summary\n**EMEA** grew, see [docs](https://x.io) & <b>"),
+            "*Summary*\n*EMEA* grew, see <https://x.io|docs> &amp; &lt;b&gt;"
+        );
+    }
+
+    #[test]
+    fn test_results_table() {
+        let table = results_table(&metric(
+            vec![
+                vec![json!("EMEA"), json!(1200.5)],
+                vec![json!("North America"), Value::Null],
+            ],
+            false,
+        ));
+        assert_eq!(
+            table,
+            "```\nregion        | revenue\n--------------+--------\nEMEA          | 1200.5\nNorth America |\n```"
+        );
+    }
+
+    #[test]
+    fn test_answer_blocks() {
+        let blocks = answer_blocks(
+            &[
+                ChatResponse::Text("**Up** 12%".to_string()),
+                ChatResponse::Metric(metric(vec![vec![json!("EMEA"), json!(1)]], true)),
+            ],
+            "https://buster.example/app/chats/1",
+        );
+
+        assert_eq!(blocks[0]["text"]["text"], "*Up* 12%");
+        assert!(blocks.iter().any(|block| block["elements"][0]["text"]
+            .as_str()
+            .is_some_and(|text| text.starts_with("Showing the first 1 rows"))));
+        let button = blocks.last().unwrap();
+        assert_eq!(
+            button["elements"][0]["url"],
+            "https://buster.example/app/chats/1"
+        );
+    }
+}
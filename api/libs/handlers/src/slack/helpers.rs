@@ -0,0 +1,196 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use database::{
+    models::{SlackIntegration, SlackThread, SlackUserLink},
+    pool::get_pg_pool,
+    schema::{slack_integrations, slack_threads, slack_user_links, users, users_to_organizations},
+    vault::read_secret,
+};
+use diesel::{upsert::excluded, ExpressionMethods, JoinOnDsl, QueryDsl};
+use diesel_async::RunQueryDsl;
+use middleware::{find_user_by_id, AuthenticatedUser};
+use slack::SlackClient;
+use uuid::Uuid;
+
+pub(crate) async fn fetch_integration_by_team(team_id: &str) -> Result<Option<SlackIntegration>> {
+    let mut conn = get_pg_pool().get().await?;
+
+    slack_integrations::table
+        .filter(slack_integrations::team_id.eq(team_id))
+        .filter(slack_integrations::deleted_at.is_null())
+        .first::<SlackIntegration>(&mut conn)
+        .await
+        .map(Some)
+        .or_else(|e| match e {
+            diesel::NotFound => Ok(None),
+            e => Err(anyhow!("Failed to load Slack integration: {}", e)),
+        })
+}
+
+pub(crate) async fn fetch_organization_integration(
+    organization_id: &Uuid,
+) -> Result<Option<SlackIntegration>> {
+    let mut conn = get_pg_pool().get().await?;
+
+    slack_integrations::table
+        .filter(slack_integrations::organization_id.eq(organization_id))
+        .filter(slack_integrations::deleted_at.is_null())
+        .first::<SlackIntegration>(&mut conn)
+        .await
+        .map(Some)
+        .or_else(|e| match e {
+            diesel::NotFound => Ok(None),
+            e => Err(anyhow!("Failed to load Slack integration: {}", e)),
+        })
+}
+
+/// A client authenticated with the workspace's bot token from the vault
+pub(crate) async fn integration_client(integration: &SlackIntegration) -> Result<SlackClient> {
+    let token = read_secret(&integration.id).await?;
+    Ok(SlackClient::new(&token))
+}
+
+/// The Buster user a Slack user asks as
+///
+/// Slack users are matched to members of the integration's organization by
+/// email the first time they ask, and the match is remembered. Returns None
+/// when nobody matches or the linked user has left the organization.
+pub(crate) async fn resolve_user(
+    integration: &SlackIntegration,
+    client: &SlackClient,
+    slack_user_id: &str,
+) -> Result<Option<AuthenticatedUser>> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let linked = slack_user_links::table
+        .filter(slack_user_links::slack_integration_id.eq(integration.id))
+        .filter(slack_user_links::slack_user_id.eq(slack_user_id))
+        .select(slack_user_links::user_id)
+        .first::<Uuid>(&mut conn)
+        .await
+        .map(Some)
+        .or_else(|e| match e {
+            diesel::NotFound => Ok(None),
+            e => Err(anyhow!("Failed to load Slack user link: {}", e)),
+        })?;
+
+    let user_id = match linked {
+        Some(user_id) => user_id,
+        None => {
+            let Some(email) = client.user_email(slack_user_id).await? else {
+                return Ok(None);
+            };
+
+            let member = users::table
+                .inner_join(
+                    users_to_organizations::table.on(users_to_organizations::user_id.eq(users::id)),
+                )
+                .filter(users::email.eq(&email))
+                .filter(users_to_organizations::organization_id.eq(integration.organization_id))
+                .filter(users_to_organizations::deleted_at.is_null())
+                .select(users::id)
+                .first::<Uuid>(&mut conn)
+                .await
+                .map(Some)
+                .or_else(|e| match e {
+                    diesel::NotFound => Ok(None),
+                    e => Err(anyhow!("Failed to look up Slack user: {}", e)),
+                })?;
+            let Some(user_id) = member else {
+                return Ok(None);
+            };
+
+            let now = Utc::now();
+            diesel::insert_into(slack_user_links::table)
+                .values(&SlackUserLink {
+                    slack_integration_id: integration.id,
+                    slack_user_id: slack_user_id.to_string(),
+                    user_id,
+                    created_at: now,
+                    updated_at: now,
+                })
+                .on_conflict((
+                    slack_user_links::slack_integration_id,
+                    slack_user_links::slack_user_id,
+                ))
+                .do_update()
+                .set((
+                    slack_user_links::user_id.eq(excluded(slack_user_links::user_id)),
+                    slack_user_links::updated_at.eq(excluded(slack_user_links::updated_at)),
+                ))
+                .execute(&mut conn)
+                .await?;
+            user_id
+        }
+    };
+    drop(conn);
+
+    Ok(find_user_by_id(&user_id).await?.filter(|user| {
+        user.organizations
+            .iter()
+            .any(|org| org.id == integration.organization_id)
+    }))
+}
+
+/// The chat the user started in a Slack thread, if any
+pub(crate) async fn fetch_thread_chat(
+    integration_id: &Uuid,
+    channel_id: &str,
+    thread_ts: &str,
+    user_id: &Uuid,
+) -> Result<Option<Uuid>> {
+    let mut conn = get_pg_pool().get().await?;
+
+    slack_threads::table
+        .filter(slack_threads::slack_integration_id.eq(integration_id))
+        .filter(slack_threads::channel_id.eq(channel_id))
+        .filter(slack_threads::thread_ts.eq(thread_ts))
+        .filter(slack_threads::user_id.eq(user_id))
+        .select(slack_threads::chat_id)
+        .first::<Uuid>(&mut conn)
+        .await
+        .map(Some)
+        .or_else(|e| match e {
+            diesel::NotFound => Ok(None),
+            e => Err(anyhow!("Failed to load Slack thread: {}", e)),
+        })
+}
+
+/// Remembers the chat so the user's next mention in the thread continues it
+pub(crate) async fn save_thread_chat(
+    integration_id: &Uuid,
+    channel_id: &str,
+    thread_ts: &str,
+    user_id: &Uuid,
+    chat_id: &Uuid,
+) -> Result<()> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let now = Utc::now();
+    diesel::insert_into(slack_threads::table)
+        .values(&SlackThread {
+            slack_integration_id: *integration_id,
+            channel_id: channel_id.to_string(),
+            thread_ts: thread_ts.to_string(),
+            user_id: *user_id,
+            chat_id: *chat_id,
+            created_at: now,
+            updated_at: now,
+        })
+        .on_conflict((
+            slack_threads::slack_integration_id,
+            slack_threads::channel_id,
+            slack_threads::thread_ts,
+            slack_threads::user_id,
+        ))
+        .do_update()
+        .set((
+            slack_threads::chat_id.eq(excluded(slack_threads::chat_id)),
+            slack_threads::updated_at.eq(excluded(slack_threads::updated_at)),
+        ))
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to save Slack thread: {}", e))?;
+
+    Ok(())
+}
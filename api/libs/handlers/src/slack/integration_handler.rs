@@ -0,0 +1,129 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use database::{
+    enums::UserOrganizationRole,
+    models::SlackIntegration,
+    pool::get_pg_pool,
+    schema::slack_integrations,
+    vault::{create_secret, delete_secret},
+};
+use diesel::ExpressionMethods;
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use slack::SlackClient;
+use uuid::Uuid;
+
+use super::helpers::{fetch_integration_by_team, fetch_organization_integration};
+use super::types::{ConnectSlackRequest, SlackIntegrationResponse};
+use crate::organizations::organization_models_handler::organization_role;
+
+fn ensure_workspace_admin(user: &AuthenticatedUser, organization_id: &Uuid) -> Result<()> {
+    if organization_role(user, organization_id)? != UserOrganizationRole::WorkspaceAdmin {
+        return Err(anyhow!("User is not a workspace admin"));
+    }
+    Ok(())
+}
+
+async fn remove_integration(integration: &SlackIntegration) -> Result<()> {
+    let mut conn = get_pg_pool().get().await?;
+    diesel::update(slack_integrations::table)
+        .filter(slack_integrations::id.eq(integration.id))
+        .set((
+            slack_integrations::deleted_at.eq(Some(Utc::now())),
+            slack_integrations::updated_at.eq(Utc::now()),
+        ))
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to remove Slack integration: {}", e))?;
+    drop(conn);
+
+    delete_secret(&integration.id).await
+}
+
+/// The Slack workspace connected to the organization
+pub async fn get_slack_integration_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+) -> Result<SlackIntegrationResponse> {
+    organization_role(user, &organization_id)?;
+
+    fetch_organization_integration(&organization_id)
+        .await?
+        .map(SlackIntegrationResponse::from)
+        .ok_or_else(|| anyhow!("Slack integration not found"))
+}
+
+/// Connects a Slack workspace to the organization, replacing any workspace
+/// connected before
+///
+/// The token is checked with Slack and stored in the vault. Restricted to
+/// workspace admins.
+pub async fn connect_slack_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+    request: ConnectSlackRequest,
+) -> Result<SlackIntegrationResponse> {
+    ensure_workspace_admin(user, &organization_id)?;
+
+    let bot_token = request.bot_token.trim();
+    if bot_token.is_empty() {
+        return Err(anyhow!("Invalid bot_token: must not be empty"));
+    }
+    let identity = SlackClient::new(bot_token)
+        .auth_test()
+        .await
+        .map_err(|e| anyhow!("Invalid bot_token: {}", e))?;
+
+    if let Some(existing) = fetch_integration_by_team(&identity.team_id).await? {
+        if existing.organization_id != organization_id {
+            return Err(anyhow!(
+                "Slack workspace is already connected to another organization"
+            ));
+        }
+    }
+    if let Some(previous) = fetch_organization_integration(&organization_id).await? {
+        remove_integration(&previous).await?;
+    }
+
+    let now = Utc::now();
+    let integration = SlackIntegration {
+        id: Uuid::new_v4(),
+        organization_id,
+        team_id: identity.team_id,
+        team_name: identity.team,
+        bot_user_id: identity.user_id,
+        created_by: user.id,
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+    };
+
+    let mut conn = get_pg_pool().get().await?;
+    diesel::insert_into(slack_integrations::table)
+        .values(&integration)
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to save Slack integration: {}", e))?;
+    drop(conn);
+
+    create_secret(bot_token, &integration.id.to_string(), None)
+        .await
+        .map_err(|e| anyhow!("Error storing Slack bot token in vault: {}", e))?;
+
+    Ok(integration.into())
+}
+
+/// Disconnects the organization's Slack workspace
+///
+/// Restricted to workspace admins.
+pub async fn disconnect_slack_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+) -> Result<()> {
+    ensure_workspace_admin(user, &organization_id)?;
+
+    let integration = fetch_organization_integration(&organization_id)
+        .await?
+        .ok_or_else(|| anyhow!("Slack integration not found"))?;
+    remove_integration(&integration).await
+}
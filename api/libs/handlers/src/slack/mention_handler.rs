@@ -0,0 +1,247 @@
+use std::env;
+use std::time::Duration;
+
+use anyhow::Result;
+use database::models::SlackIntegration;
+use slack::{MentionEvent, PostedMessage, SlackClient, SlackMessage};
+use tokio::sync::mpsc;
+
+use super::format::{answer_blocks, answer_fallback, question_text, status_text, SLACK_ROW_LIMIT};
+use super::helpers::{fetch_thread_chat, integration_client, resolve_user, save_thread_chat};
+use crate::chats::export_chat_handler::build_turn;
+use crate::chats::post_chat_handler;
+use crate::chats::post_chat_handler::{
+    BusterContainer, BusterReasoningMessage, ChatCreateNewChat, ThreadEvent,
+};
+
+/// Slack allows roughly one update per message per second; stay well below
+const STATUS_UPDATE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Generation events buffered while a status update is in flight
+const EVENT_BUFFER: usize = 1024;
+
+const EMPTY_QUESTION_REPLY: &str =
+    "Mention me with a question about your data, e.g. _What was revenue by region last month?_";
+
+const NO_ACCOUNT_REPLY: &str = "I couldn't find a Buster account with your Slack email in the organization connected to this workspace. Ask an admin to invite you, then try again.";
+
+const FAILED_REPLY: &str = "Sorry, something went wrong while answering. Please try again.";
+
+type GenerationEvents = mpsc::Receiver<Result<(BusterContainer, ThreadEvent)>>;
+
+/// Answers a question asked by mentioning the bot
+///
+/// The agent runs as the Buster user linked to the Slack user, so it only
+/// sees the datasets and metrics that user can. A status message in the
+/// thread follows the agent's reasoning and is replaced by the answer. A
+/// follow-up mention in the same thread continues the user's chat.
+pub(crate) async fn answer_mention(
+    integration: SlackIntegration,
+    mention: MentionEvent,
+) -> Result<()> {
+    let client = integration_client(&integration).await?;
+    let thread_ts = mention.thread().to_string();
+    let reply = |text: &str| SlackMessage {
+        channel: mention.channel.clone(),
+        thread_ts: Some(thread_ts.clone()),
+        text: text.to_string(),
+        blocks: vec![],
+    };
+
+    let question = question_text(&mention.text);
+    if question.is_empty() {
+        client.post_message(&reply(EMPTY_QUESTION_REPLY)).await?;
+        return Ok(());
+    }
+
+    let Some(user) = resolve_user(&integration, &client, &mention.user).await? else {
+        client.post_message(&reply(NO_ACCOUNT_REPLY)).await?;
+        return Ok(());
+    };
+
+    let status = client.post_message(&reply(&status_text(None))).await?;
+    let chat_id =
+        fetch_thread_chat(&integration.id, &mention.channel, &thread_ts, &user.id).await?;
+
+    let (tx, rx) = mpsc::channel(EVENT_BUFFER);
+    let progress = tokio::spawn(report_progress(
+        client.clone(),
+        status.clone(),
+        thread_ts.clone(),
+        rx,
+    ));
+
+    let result = post_chat_handler(
+        ChatCreateNewChat {
+            prompt: Some(question),
+            chat_id,
+            message_id: None,
+            asset_id: None,
+            asset_type: None,
+            metric_id: None,
+            dashboard_id: None,
+        },
+        user.clone(),
+        Some(tx),
+    )
+    .await;
+
+    // Stop status updates before the answer replaces the status message
+    progress.abort();
+    let _ = progress.await;
+
+    let chat = match result {
+        Ok(chat) => chat,
+        Err(e) => {
+            tracing::error!(
+                team_id = %integration.team_id,
+                user_id = %user.id,
+                "Failed to answer Slack mention: {}",
+                e
+            );
+            client.update_message(&status, &reply(FAILED_REPLY)).await?;
+            return Err(e);
+        }
+    };
+
+    save_thread_chat(
+        &integration.id,
+        &mention.channel,
+        &thread_ts,
+        &user.id,
+        &chat.id,
+    )
+    .await?;
+
+    let base_url = env::var("BUSTER_URL").unwrap_or_default();
+    let responses = match chat.message_ids.last().and_then(|id| chat.messages.get(id)) {
+        Some(message) => {
+            build_turn(message, &user, SLACK_ROW_LIMIT, &base_url)
+                .await
+                .responses
+        }
+        None => Vec::new(),
+    };
+    let chat_link = format!("{}/app/chats/{}", base_url, chat.id);
+
+    client
+        .update_message(
+            &status,
+            &SlackMessage {
+                channel: mention.channel.clone(),
+                thread_ts: Some(thread_ts),
+                text: answer_fallback(&responses),
+                blocks: answer_blocks(&responses, &chat_link),
+            },
+        )
+        .await
+}
+
+/// Shows the agent's latest reasoning step in the status message, at most
+/// once per `STATUS_UPDATE_INTERVAL`
+async fn report_progress(
+    client: SlackClient,
+    status: PostedMessage,
+    thread_ts: String,
+    mut events: GenerationEvents,
+) {
+    let mut ticker = tokio::time::interval(STATUS_UPDATE_INTERVAL);
+    let mut shown: Option<String> = None;
+    let mut pending: Option<String> = None;
+
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                let Some(event) = event else { break };
+                let Ok((BusterContainer::ReasoningMessage(container), _)) = event else {
+                    continue;
+                };
+                let title = match &container.reasoning {
+                    BusterReasoningMessage::Pill(pill) => &pill.title,
+                    BusterReasoningMessage::File(file) => &file.title,
+                    BusterReasoningMessage::Text(text) => &text.title,
+                };
+                if !title.trim().is_empty() && shown.as_ref() != Some(title) {
+                    pending = Some(title.clone());
+                }
+            }
+            _ = ticker.tick(), if pending.is_some() => {
+                let step = pending.take();
+                let message = SlackMessage {
+                    channel: status.channel.clone(),
+                    thread_ts: Some(thread_ts.clone()),
+                    text: status_text(step.as_deref()),
+                    blocks: vec![],
+                };
+                if let Err(e) = client.update_message(&status, &message).await {
+                    tracing::warn!("Failed to update Slack status message: {}", e);
+                }
+                shown = step;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chats::post_chat_handler::{BusterReasoningMessageContainer, BusterReasoningPill};
+    use mockito::Matcher;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn reasoning(title: &str) -> Result<(BusterContainer, ThreadEvent)> {
+        Ok((
+            BusterContainer::ReasoningMessage(BusterReasoningMessageContainer {
+                reasoning: BusterReasoningMessage::Pill(BusterReasoningPill {
+                    id: Uuid::new_v4().to_string(),
+                    thought_type: "pills".to_string(),
+                    title: title.to_string(),
+                    secondary_title: String::new(),
+                    pill_containers: None,
+                    status: "loading".to_string(),
+                }),
+                chat_id: Uuid::new_v4(),
+                message_id: Uuid::new_v4(),
+            }),
+            ThreadEvent::GeneratingReasoningMessage,
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_report_progress_updates_status_message() {
+        let mut server = mockito::Server::new_async().await;
+        let update = server
+            .mock("POST", "/chat.update")
+            .match_body(Matcher::PartialJson(json!({
+                "channel": "C1",
+                "ts": "2.0",
+                "text": "_Searching your data catalog…_"
+            })))
+            .with_body(r#"{"ok":true,"channel":"C1","ts":"2.0"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = SlackClient::with_base_url("xoxb-test", &server.url());
+        let status = PostedMessage {
+            channel: "C1".to_string(),
+            ts: "2.0".to_string(),
+        };
+        let (tx, rx) = mpsc::channel(EVENT_BUFFER);
+        let progress = tokio::spawn(report_progress(client, status, "1.0".to_string(), rx));
+
+        tx.send(reasoning("Searching your data catalog"))
+            .await
+            .unwrap();
+        // The same step again isn't worth another update
+        tx.send(reasoning("Searching your data catalog"))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        drop(tx);
+        progress.await.unwrap();
+
+        update.assert_async().await;
+    }
+}
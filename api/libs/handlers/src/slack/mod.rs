@@ -0,0 +1,10 @@
+mod events_handler;
+mod format;
+mod helpers;
+mod integration_handler;
+mod mention_handler;
+mod types;
+
+pub use events_handler::*;
+pub use integration_handler::*;
+pub use types::*;
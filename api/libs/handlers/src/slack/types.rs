@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use database::models::SlackIntegration;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Connects a Slack workspace using the bot token of the installed Buster app
+#[derive(Debug, Deserialize)]
+pub struct ConnectSlackRequest {
+    pub bot_token: String,
+}
+
+/// A connected Slack workspace; the bot token is never returned
+#[derive(Debug, Serialize)]
+pub struct SlackIntegrationResponse {
+    pub id: Uuid,
+    pub team_id: String,
+    pub team_name: String,
+    pub bot_user_id: String,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<SlackIntegration> for SlackIntegrationResponse {
+    fn from(integration: SlackIntegration) -> Self {
+        Self {
+            id: integration.id,
+            team_id: integration.team_id,
+            team_name: integration.team_name,
+            bot_user_id: integration.bot_user_id,
+            created_by: integration.created_by,
+            created_at: integration.created_at,
+        }
+    }
+}
+
+/// What to answer Slack with
+#[derive(Debug, PartialEq, Eq)]
+pub enum SlackEventResponse {
+    /// Echo the challenge of a `url_verification` request
+    Challenge(String),
+    /// Acknowledge the event; any work continues in the background
+    Accepted,
+}
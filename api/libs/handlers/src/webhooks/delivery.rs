@@ -0,0 +1,326 @@
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use database::{
+    enums::WebhookDeliveryStatus,
+    models::{Webhook, WebhookDelivery},
+    pool::get_pg_pool,
+    schema::{webhook_deliveries, webhooks},
+    vault::read_secret,
+};
+use diesel::{ExpressionMethods, JoinOnDsl, QueryDsl};
+use diesel_async::RunQueryDsl;
+use futures::future::join_all;
+use reqwest::header::{CONTENT_TYPE, USER_AGENT};
+
+use super::signature::{sign_payload, DELIVERY_HEADER, EVENT_HEADER, SIGNATURE_HEADER};
+
+/// How long an endpoint gets to answer
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Wait before each retry; a delivery fails for good after the last one
+const RETRY_DELAYS_MINUTES: [i64; 5] = [1, 5, 30, 120, 360];
+
+/// How long a delivery being attempted is hidden from the retry job, so
+/// another server doesn't send it at the same time
+const CLAIM_MINUTES: i64 = 1;
+
+/// Characters of the endpoint's response kept in the delivery log
+const MAX_RESPONSE_BODY_CHARS: usize = 2048;
+
+/// Due deliveries retried per run of the retry job
+const RETRY_BATCH_SIZE: i64 = 100;
+
+static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .expect("Failed to build webhook HTTP client")
+});
+
+/// What happened when a delivery was sent
+#[derive(Debug, Default)]
+pub(crate) struct AttemptOutcome {
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+}
+
+impl AttemptOutcome {
+    fn succeeded(&self) -> bool {
+        self.error.is_none()
+            && self
+                .response_status
+                .is_some_and(|s| (200..300).contains(&s))
+    }
+}
+
+/// The status and next retry of a delivery after its `attempts`th attempt
+pub(crate) fn next_state(
+    attempts: i32,
+    succeeded: bool,
+    now: DateTime<Utc>,
+) -> (WebhookDeliveryStatus, Option<DateTime<Utc>>) {
+    if succeeded {
+        return (WebhookDeliveryStatus::Delivered, None);
+    }
+    match RETRY_DELAYS_MINUTES.get((attempts - 1).max(0) as usize) {
+        Some(minutes) => (
+            WebhookDeliveryStatus::Pending,
+            Some(now + chrono::Duration::minutes(*minutes)),
+        ),
+        None => (WebhookDeliveryStatus::Failed, None),
+    }
+}
+
+/// When a delivery claimed now may be picked up by the retry job again
+pub(crate) fn claim_expiry(now: DateTime<Utc>) -> DateTime<Utc> {
+    now + chrono::Duration::minutes(CLAIM_MINUTES)
+}
+
+/// POSTs the signed payload to the webhook's URL
+pub(crate) async fn send_delivery(
+    client: &reqwest::Client,
+    url: &str,
+    secret: &str,
+    delivery: &WebhookDelivery,
+) -> AttemptOutcome {
+    let body = match serde_json::to_vec(&delivery.payload) {
+        Ok(body) => body,
+        Err(e) => {
+            return AttemptOutcome {
+                error: Some(format!("Failed to serialize payload: {}", e)),
+                ..Default::default()
+            }
+        }
+    };
+    let signature = sign_payload(secret, Utc::now().timestamp(), &body);
+
+    let response = client
+        .post(url)
+        .header(CONTENT_TYPE, "application/json")
+        .header(USER_AGENT, "Buster-Webhooks/1.0")
+        .header(SIGNATURE_HEADER, signature)
+        .header(EVENT_HEADER, delivery.event_type.to_str())
+        .header(DELIVERY_HEADER, delivery.id.to_string())
+        .body(body)
+        .send()
+        .await;
+
+    match response {
+        Ok(response) => {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            AttemptOutcome {
+                response_status: Some(status.as_u16() as i32),
+                response_body: Some(text.chars().take(MAX_RESPONSE_BODY_CHARS).collect()),
+                error: (!status.is_success()).then(|| format!("Endpoint answered {}", status)),
+            }
+        }
+        Err(e) => AttemptOutcome {
+            error: Some(format!("Request failed: {}", e)),
+            ..Default::default()
+        },
+    }
+}
+
+/// Sends a delivery the caller has claimed and records the outcome
+pub(crate) async fn attempt_delivery(webhook: &Webhook, delivery: &WebhookDelivery) -> Result<()> {
+    let outcome = match read_secret(&webhook.id).await {
+        Ok(secret) => send_delivery(&HTTP_CLIENT, &webhook.url, &secret, delivery).await,
+        Err(e) => AttemptOutcome {
+            error: Some(format!("Failed to read signing secret: {}", e)),
+            ..Default::default()
+        },
+    };
+
+    let attempts = delivery.attempts + 1;
+    let now = Utc::now();
+    let (status, next_attempt_at) = next_state(attempts, outcome.succeeded(), now);
+    if status == WebhookDeliveryStatus::Failed {
+        tracing::warn!(
+            webhook_id = %webhook.id,
+            delivery_id = %delivery.id,
+            "Webhook delivery failed after {} attempts: {:?}",
+            attempts,
+            outcome.error
+        );
+    }
+
+    let mut conn = get_pg_pool().get().await?;
+    diesel::update(webhook_deliveries::table)
+        .filter(webhook_deliveries::id.eq(delivery.id))
+        .set((
+            webhook_deliveries::status.eq(status),
+            webhook_deliveries::attempts.eq(attempts),
+            webhook_deliveries::response_status.eq(outcome.response_status),
+            webhook_deliveries::response_body.eq(outcome.response_body),
+            webhook_deliveries::error.eq(outcome.error),
+            webhook_deliveries::next_attempt_at.eq(next_attempt_at),
+            webhook_deliveries::delivered_at
+                .eq((status == WebhookDeliveryStatus::Delivered).then_some(now)),
+            webhook_deliveries::updated_at.eq(now),
+        ))
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to record webhook delivery attempt: {}", e))?;
+
+    Ok(())
+}
+
+/// Retries pending deliveries whose next attempt is due
+///
+/// Runs on a schedule. Deliveries of disabled webhooks wait until the
+/// webhook is enabled again.
+pub async fn retry_due_webhook_deliveries() -> Result<()> {
+    let mut conn = get_pg_pool().get().await?;
+    let now = Utc::now();
+
+    let due = webhook_deliveries::table
+        .inner_join(webhooks::table.on(webhooks::id.eq(webhook_deliveries::webhook_id)))
+        .filter(webhook_deliveries::status.eq(WebhookDeliveryStatus::Pending))
+        .filter(webhook_deliveries::next_attempt_at.le(now))
+        .filter(webhooks::enabled.eq(true))
+        .filter(webhooks::deleted_at.is_null())
+        .order(webhook_deliveries::next_attempt_at.asc())
+        .limit(RETRY_BATCH_SIZE)
+        .select((webhooks::all_columns, webhook_deliveries::all_columns))
+        .load::<(Webhook, WebhookDelivery)>(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to load due webhook deliveries: {}", e))?;
+
+    let mut claimed = Vec::with_capacity(due.len());
+    for (webhook, delivery) in due {
+        // Skip deliveries another server claimed since they were loaded
+        let updated = diesel::update(webhook_deliveries::table)
+            .filter(webhook_deliveries::id.eq(delivery.id))
+            .filter(webhook_deliveries::status.eq(WebhookDeliveryStatus::Pending))
+            .filter(webhook_deliveries::next_attempt_at.eq(delivery.next_attempt_at))
+            .set(webhook_deliveries::next_attempt_at.eq(claim_expiry(now)))
+            .execute(&mut conn)
+            .await?;
+        if updated == 1 {
+            claimed.push((webhook, delivery));
+        }
+    }
+    drop(conn);
+
+    if !claimed.is_empty() {
+        tracing::info!("Retrying {} webhook deliveries", claimed.len());
+    }
+    let results = join_all(
+        claimed
+            .iter()
+            .map(|(webhook, delivery)| attempt_delivery(webhook, delivery)),
+    )
+    .await;
+    for result in results {
+        if let Err(e) = result {
+            tracing::error!("Failed to retry webhook delivery: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use database::enums::WebhookEventType;
+    use mockito::Matcher;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_next_state() {
+        let now = Utc::now();
+        assert_eq!(
+            next_state(1, true, now),
+            (WebhookDeliveryStatus::Delivered, None)
+        );
+        assert_eq!(
+            next_state(1, false, now),
+            (
+                WebhookDeliveryStatus::Pending,
+                Some(now + chrono::Duration::minutes(1))
+            )
+        );
+        assert_eq!(
+            next_state(5, false, now),
+            (
+                WebhookDeliveryStatus::Pending,
+                Some(now + chrono::Duration::minutes(360))
+            )
+        );
+        assert_eq!(
+            next_state(6, false, now),
+            (WebhookDeliveryStatus::Failed, None)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_delivery() {
+        let delivery = WebhookDelivery {
+            id: Uuid::new_v4(),
+            webhook_id: Uuid::new_v4(),
+            event_id: Uuid::new_v4(),
+            event_type: WebhookEventType::MetricVerified,
+            payload: json!({"type": "metric.verified", "data": {"id": "m1"}}),
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            response_status: None,
+            response_body: None,
+            error: None,
+            next_attempt_at: None,
+            delivered_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let mut server = mockito::Server::new_async().await;
+        let accepted = server
+            .mock("POST", "/hook")
+            .match_header(EVENT_HEADER, "metric.verified")
+            .match_header(DELIVERY_HEADER, delivery.id.to_string().as_str())
+            .match_header(
+                SIGNATURE_HEADER,
+                Matcher::Regex(r"^t=\d+,v1=[0-9a-f]{64}$".into()),
+            )
+            .match_body(Matcher::Json(delivery.payload.clone()))
+            .with_body("ok")
+            .create_async()
+            .await;
+        let rejected = server
+            .mock("POST", "/down")
+            .with_status(503)
+            .with_body("unavailable")
+            .create_async()
+            .await;
+
+        let client = reqwest::Client::new();
+        let outcome = send_delivery(
+            &client,
+            &format!("{}/hook", server.url()),
+            "whsec_test",
+            &delivery,
+        )
+        .await;
+        assert!(outcome.succeeded());
+        assert_eq!(outcome.response_body.as_deref(), Some("ok"));
+
+        let outcome = send_delivery(
+            &client,
+            &format!("{}/down", server.url()),
+            "whsec_test",
+            &delivery,
+        )
+        .await;
+        assert!(!outcome.succeeded());
+        assert_eq!(outcome.response_status, Some(503));
+
+        accepted.assert_async().await;
+        rejected.assert_async().await;
+    }
+}
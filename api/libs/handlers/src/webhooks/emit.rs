@@ -0,0 +1,142 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use database::{
+    enums::{WebhookDeliveryStatus, WebhookEventType},
+    models::{Webhook, WebhookDelivery},
+    pool::get_pg_pool,
+    schema::{webhook_deliveries, webhooks},
+};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use futures::future::join_all;
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::delivery::{attempt_delivery, claim_expiry};
+use super::types::WebhookEvent;
+
+/// Notifies the organization's webhooks subscribed to the event
+///
+/// Returns immediately; deliveries are logged and sent in the background,
+/// and failed ones are retried by `retry_due_webhook_deliveries`. `data`
+/// describes the asset, e.g. `{"id": ..., "name": ..., "actor_id": ...}`.
+pub fn emit_webhook_event(organization_id: Uuid, event_type: WebhookEventType, data: Value) {
+    tokio::spawn(async move {
+        if let Err(e) = deliver_event(organization_id, event_type, data).await {
+            tracing::error!(
+                organization_id = %organization_id,
+                event_type = event_type.to_str(),
+                "Failed to deliver webhook event: {}",
+                e
+            );
+        }
+    });
+}
+
+/// Whether a webhook wants the event; no events means every event
+pub(crate) fn subscribes_to(webhook: &Webhook, event_type: WebhookEventType) -> bool {
+    webhook.events.is_empty() || webhook.events.contains(&event_type)
+}
+
+async fn deliver_event(
+    organization_id: Uuid,
+    event_type: WebhookEventType,
+    data: Value,
+) -> Result<()> {
+    let mut conn = get_pg_pool().get().await?;
+
+    let subscribed: Vec<Webhook> = webhooks::table
+        .filter(webhooks::organization_id.eq(organization_id))
+        .filter(webhooks::enabled.eq(true))
+        .filter(webhooks::deleted_at.is_null())
+        .load::<Webhook>(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to load webhooks: {}", e))?
+        .into_iter()
+        .filter(|webhook| subscribes_to(webhook, event_type))
+        .collect();
+    if subscribed.is_empty() {
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    let event_id = Uuid::new_v4();
+    let payload = serde_json::to_value(WebhookEvent {
+        id: event_id,
+        event_type,
+        organization_id,
+        created_at: now,
+        data,
+    })?;
+
+    // Logged as claimed so the retry job only picks them up if this task dies
+    let deliveries: Vec<WebhookDelivery> = subscribed
+        .iter()
+        .map(|webhook| WebhookDelivery {
+            id: Uuid::new_v4(),
+            webhook_id: webhook.id,
+            event_id,
+            event_type,
+            payload: payload.clone(),
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            response_status: None,
+            response_body: None,
+            error: None,
+            next_attempt_at: Some(claim_expiry(now)),
+            delivered_at: None,
+            created_at: now,
+            updated_at: now,
+        })
+        .collect();
+
+    diesel::insert_into(webhook_deliveries::table)
+        .values(&deliveries)
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to log webhook deliveries: {}", e))?;
+    drop(conn);
+
+    let results = join_all(
+        subscribed
+            .iter()
+            .zip(&deliveries)
+            .map(|(webhook, delivery)| attempt_delivery(webhook, delivery)),
+    )
+    .await;
+    for result in results {
+        if let Err(e) = result {
+            tracing::error!("Failed to attempt webhook delivery: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscribes_to() {
+        let now = Utc::now();
+        let mut webhook = Webhook {
+            id: Uuid::new_v4(),
+            organization_id: Uuid::new_v4(),
+            url: "https://catalog.example/hooks/buster".to_string(),
+            description: None,
+            events: vec![],
+            enabled: true,
+            created_by: Uuid::new_v4(),
+            updated_by: Uuid::new_v4(),
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        };
+        assert!(subscribes_to(&webhook, WebhookEventType::ChatCompleted));
+
+        webhook.events = vec![WebhookEventType::MetricVerified];
+        assert!(subscribes_to(&webhook, WebhookEventType::MetricVerified));
+        assert!(!subscribes_to(&webhook, WebhookEventType::MetricUpdated));
+    }
+}
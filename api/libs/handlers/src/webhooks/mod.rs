@@ -0,0 +1,11 @@
+mod delivery;
+mod emit;
+mod signature;
+mod types;
+mod webhooks_handler;
+
+pub use delivery::retry_due_webhook_deliveries;
+pub use emit::*;
+pub use signature::*;
+pub use types::*;
+pub use webhooks_handler::*;
@@ -0,0 +1,57 @@
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use sha2::Sha256;
+
+/// Header carrying `t=<unix time>,v1=<hex HMAC-SHA256 of "<t>.<body>">`
+pub const SIGNATURE_HEADER: &str = "X-Buster-Signature";
+
+/// Header carrying the event type, e.g. `metric.verified`
+pub const EVENT_HEADER: &str = "X-Buster-Event";
+
+/// Header carrying the delivery id; it stays the same across retries
+pub const DELIVERY_HEADER: &str = "X-Buster-Delivery";
+
+const SECRET_PREFIX: &str = "whsec_";
+
+/// A new random signing secret
+pub fn generate_secret() -> String {
+    let bytes: [u8; 32] = rand::thread_rng().gen();
+    format!("{}{}", SECRET_PREFIX, hex::encode(bytes))
+}
+
+/// The signature header value for a payload sent at `timestamp`
+///
+/// Receivers recompute the HMAC over the timestamp, a dot and the raw body
+/// with their copy of the secret, and should reject old timestamps.
+pub fn sign_payload(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(format!("{}.", timestamp).as_bytes());
+    mac.update(body);
+    format!(
+        "t={},v1={}",
+        timestamp,
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_payload() {
+        assert_eq!(
+            sign_payload("whsec_test", 1715331600, br#"{"type":"metric.created"}"#),
+            "t=1715331600,v1=2a3cf5e2cb041c9369bec569929cd0dc31dbf12c280e5c751afdfc4c89b81ba1"
+        );
+    }
+
+    #[test]
+    fn test_generate_secret() {
+        let secret = generate_secret();
+        assert!(secret.starts_with(SECRET_PREFIX));
+        assert_eq!(secret.len(), SECRET_PREFIX.len() + 64);
+        assert_ne!(secret, generate_secret());
+    }
+}
@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use database::{
+    enums::{WebhookDeliveryStatus, WebhookEventType},
+    models::{Webhook, WebhookDelivery},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// The JSON body POSTed to a webhook
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WebhookEvent {
+    pub id: Uuid,
+    #[serde(rename = "type")]
+    pub event_type: WebhookEventType,
+    pub organization_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub data: Value,
+}
+
+/// Registers an endpoint; an empty `events` list subscribes to every event
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub events: Vec<WebhookEventType>,
+    pub enabled: Option<bool>,
+}
+
+/// Changes the given fields; `rotate_secret` replaces the signing secret
+#[derive(Debug, Deserialize, Default)]
+pub struct UpdateWebhookRequest {
+    pub url: Option<String>,
+    pub description: Option<String>,
+    pub events: Option<Vec<WebhookEventType>>,
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub rotate_secret: bool,
+}
+
+/// A webhook; the signing secret is only returned when it is generated
+#[derive(Debug, Serialize)]
+pub struct WebhookResponse {
+    pub id: Uuid,
+    pub url: String,
+    pub description: Option<String>,
+    pub events: Vec<WebhookEventType>,
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Webhook> for WebhookResponse {
+    fn from(webhook: Webhook) -> Self {
+        Self {
+            id: webhook.id,
+            url: webhook.url,
+            description: webhook.description,
+            events: webhook.events,
+            enabled: webhook.enabled,
+            secret: None,
+            created_by: webhook.created_by,
+            created_at: webhook.created_at,
+            updated_at: webhook.updated_at,
+        }
+    }
+}
+
+/// Filters a webhook's delivery log, newest first
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ListWebhookDeliveriesQuery {
+    pub status: Option<WebhookDeliveryStatus>,
+    pub limit: Option<i64>,
+}
+
+/// An event sent to a webhook and the outcome of its latest attempt
+#[derive(Debug, Serialize)]
+pub struct WebhookDeliveryResponse {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub event_type: WebhookEventType,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub payload: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryResponse {
+    fn from(delivery: WebhookDelivery) -> Self {
+        Self {
+            id: delivery.id,
+            event_id: delivery.event_id,
+            event_type: delivery.event_type,
+            status: delivery.status,
+            attempts: delivery.attempts,
+            response_status: delivery.response_status,
+            response_body: delivery.response_body,
+            error: delivery.error,
+            next_attempt_at: delivery.next_attempt_at,
+            delivered_at: delivery.delivered_at,
+            payload: delivery.payload,
+            created_at: delivery.created_at,
+        }
+    }
+}
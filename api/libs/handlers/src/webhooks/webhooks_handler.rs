@@ -0,0 +1,299 @@
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use database::{
+    enums::{UserOrganizationRole, WebhookDeliveryStatus, WebhookEventType},
+    models::{Webhook, WebhookDelivery},
+    pool::get_pg_pool,
+    schema::{webhook_deliveries, webhooks},
+    vault::{create_secret, delete_secret},
+};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use middleware::AuthenticatedUser;
+use reqwest::Url;
+use uuid::Uuid;
+
+use super::signature::generate_secret;
+use super::types::{
+    CreateWebhookRequest, ListWebhookDeliveriesQuery, UpdateWebhookRequest,
+    WebhookDeliveryResponse, WebhookResponse,
+};
+use crate::organizations::organization_models_handler::organization_role;
+
+/// Deliveries listed when no limit is given
+const DEFAULT_DELIVERY_LIMIT: i64 = 50;
+
+/// Most deliveries listed at once
+const MAX_DELIVERY_LIMIT: i64 = 200;
+
+fn ensure_workspace_admin(user: &AuthenticatedUser, organization_id: &Uuid) -> Result<()> {
+    if organization_role(user, organization_id)? != UserOrganizationRole::WorkspaceAdmin {
+        return Err(anyhow!("User is not a workspace admin"));
+    }
+    Ok(())
+}
+
+fn validate_url(url: &str) -> Result<String> {
+    let url = url.trim();
+    let parsed = Url::parse(url).map_err(|e| anyhow!("Invalid url: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err(anyhow!("Invalid url: must be an http or https URL"));
+    }
+    Ok(url.to_string())
+}
+
+fn unique_events(mut events: Vec<WebhookEventType>) -> Vec<WebhookEventType> {
+    let mut seen = HashSet::new();
+    events.retain(|event| seen.insert(*event));
+    events
+}
+
+fn clean_description(description: Option<String>) -> Option<String> {
+    description
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty())
+}
+
+async fn fetch_webhook(organization_id: &Uuid, webhook_id: &Uuid) -> Result<Webhook> {
+    let mut conn = get_pg_pool().get().await?;
+
+    webhooks::table
+        .filter(webhooks::id.eq(webhook_id))
+        .filter(webhooks::organization_id.eq(organization_id))
+        .filter(webhooks::deleted_at.is_null())
+        .first::<Webhook>(&mut conn)
+        .await
+        .map_err(|e| match e {
+            diesel::NotFound => anyhow!("Webhook not found"),
+            e => anyhow!("Failed to load webhook: {}", e),
+        })
+}
+
+/// Handler to list the organization's webhooks
+///
+/// Restricted to workspace admins.
+pub async fn list_webhooks_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+) -> Result<Vec<WebhookResponse>> {
+    ensure_workspace_admin(user, &organization_id)?;
+
+    let mut conn = get_pg_pool().get().await?;
+
+    let webhooks = webhooks::table
+        .filter(webhooks::organization_id.eq(organization_id))
+        .filter(webhooks::deleted_at.is_null())
+        .order(webhooks::created_at.asc())
+        .load::<Webhook>(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to load webhooks: {}", e))?;
+
+    Ok(webhooks.into_iter().map(WebhookResponse::from).collect())
+}
+
+/// Handler to register a webhook
+///
+/// A signing secret is generated, stored in the vault and returned once in
+/// the response. Restricted to workspace admins.
+pub async fn create_webhook_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+    request: CreateWebhookRequest,
+) -> Result<WebhookResponse> {
+    ensure_workspace_admin(user, &organization_id)?;
+
+    let now = Utc::now();
+    let webhook = Webhook {
+        id: Uuid::new_v4(),
+        organization_id,
+        url: validate_url(&request.url)?,
+        description: clean_description(request.description),
+        events: unique_events(request.events),
+        enabled: request.enabled.unwrap_or(true),
+        created_by: user.id,
+        updated_by: user.id,
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+    };
+
+    let mut conn = get_pg_pool().get().await?;
+    diesel::insert_into(webhooks::table)
+        .values(&webhook)
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to create webhook: {}", e))?;
+    drop(conn);
+
+    let secret = generate_secret();
+    create_secret(&secret, &webhook.id.to_string(), None)
+        .await
+        .map_err(|e| anyhow!("Error storing webhook secret in vault: {}", e))?;
+
+    Ok(WebhookResponse {
+        secret: Some(secret),
+        ..webhook.into()
+    })
+}
+
+/// Handler to change a webhook or rotate its signing secret
+///
+/// A rotated secret is returned once in the response. Restricted to
+/// workspace admins.
+pub async fn update_webhook_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+    webhook_id: Uuid,
+    request: UpdateWebhookRequest,
+) -> Result<WebhookResponse> {
+    ensure_workspace_admin(user, &organization_id)?;
+
+    let mut webhook = fetch_webhook(&organization_id, &webhook_id).await?;
+    if let Some(url) = request.url {
+        webhook.url = validate_url(&url)?;
+    }
+    if request.description.is_some() {
+        webhook.description = clean_description(request.description);
+    }
+    if let Some(events) = request.events {
+        webhook.events = unique_events(events);
+    }
+    if let Some(enabled) = request.enabled {
+        webhook.enabled = enabled;
+    }
+    webhook.updated_by = user.id;
+    webhook.updated_at = Utc::now();
+
+    let mut conn = get_pg_pool().get().await?;
+    diesel::update(webhooks::table)
+        .filter(webhooks::id.eq(webhook.id))
+        .set((
+            webhooks::url.eq(&webhook.url),
+            webhooks::description.eq(&webhook.description),
+            webhooks::events.eq(&webhook.events),
+            webhooks::enabled.eq(webhook.enabled),
+            webhooks::updated_by.eq(webhook.updated_by),
+            webhooks::updated_at.eq(webhook.updated_at),
+        ))
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to update webhook: {}", e))?;
+    drop(conn);
+
+    let secret = if request.rotate_secret {
+        // Secrets are looked up by name, so replace rather than update it
+        let secret = generate_secret();
+        delete_secret(&webhook.id).await?;
+        create_secret(&secret, &webhook.id.to_string(), None)
+            .await
+            .map_err(|e| anyhow!("Error storing webhook secret in vault: {}", e))?;
+        Some(secret)
+    } else {
+        None
+    };
+
+    Ok(WebhookResponse {
+        secret,
+        ..webhook.into()
+    })
+}
+
+/// Handler to remove a webhook
+///
+/// Deliveries still waiting for a retry are marked failed. Restricted to
+/// workspace admins.
+pub async fn delete_webhook_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+    webhook_id: Uuid,
+) -> Result<()> {
+    ensure_workspace_admin(user, &organization_id)?;
+
+    let webhook = fetch_webhook(&organization_id, &webhook_id).await?;
+    let now = Utc::now();
+
+    let mut conn = get_pg_pool().get().await?;
+    diesel::update(webhooks::table)
+        .filter(webhooks::id.eq(webhook.id))
+        .set((
+            webhooks::deleted_at.eq(Some(now)),
+            webhooks::updated_by.eq(user.id),
+            webhooks::updated_at.eq(now),
+        ))
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to delete webhook: {}", e))?;
+
+    diesel::update(webhook_deliveries::table)
+        .filter(webhook_deliveries::webhook_id.eq(webhook.id))
+        .filter(webhook_deliveries::status.eq(WebhookDeliveryStatus::Pending))
+        .set((
+            webhook_deliveries::status.eq(WebhookDeliveryStatus::Failed),
+            webhook_deliveries::error.eq(Some("Webhook was deleted")),
+            webhook_deliveries::next_attempt_at.eq(None::<chrono::DateTime<Utc>>),
+            webhook_deliveries::updated_at.eq(now),
+        ))
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to cancel webhook deliveries: {}", e))?;
+    drop(conn);
+
+    delete_secret(&webhook.id).await
+}
+
+/// Handler to list a webhook's delivery log, newest first
+///
+/// Restricted to workspace admins.
+pub async fn list_webhook_deliveries_handler(
+    user: &AuthenticatedUser,
+    organization_id: Uuid,
+    webhook_id: Uuid,
+    query: ListWebhookDeliveriesQuery,
+) -> Result<Vec<WebhookDeliveryResponse>> {
+    ensure_workspace_admin(user, &organization_id)?;
+
+    let webhook = fetch_webhook(&organization_id, &webhook_id).await?;
+
+    let mut conn = get_pg_pool().get().await?;
+
+    let mut deliveries_query = webhook_deliveries::table
+        .filter(webhook_deliveries::webhook_id.eq(webhook.id))
+        .into_boxed();
+    if let Some(status) = query.status {
+        deliveries_query = deliveries_query.filter(webhook_deliveries::status.eq(status));
+    }
+
+    let deliveries = deliveries_query
+        .order(webhook_deliveries::created_at.desc())
+        .limit(
+            query
+                .limit
+                .unwrap_or(DEFAULT_DELIVERY_LIMIT)
+                .clamp(1, MAX_DELIVERY_LIMIT),
+        )
+        .load::<WebhookDelivery>(&mut conn)
+        .await
+        .map_err(|e| anyhow!("Failed to load webhook deliveries: {}", e))?;
+
+    Ok(deliveries
+        .into_iter()
+        .map(WebhookDeliveryResponse::from)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_url() {
+        assert_eq!(
+            validate_url(" https://catalog.example/hooks/buster ").unwrap(),
+            "https://catalog.example/hooks/buster"
+        );
+        assert!(validate_url("ftp://catalog.example/hooks").is_err());
+        assert!(validate_url("catalog.example/hooks").is_err());
+    }
+}
@@ -56,6 +56,7 @@ async fn create_test_metric(
             line_group_type: None,
         }),
         dataset_ids: Vec::new(),
+        drill_targets: Vec::new(),
     };
     
     let metric_file = MetricFile {
@@ -142,6 +142,7 @@ async fn test_update_metric_status() -> Result<()> {
         time_frame: "last 30 days".to_string(),
         chart_config: create_default_chart_config(),
         dataset_ids: vec![],
+        drill_targets: Vec::new(),
     };
     
     // Initial verification status
@@ -344,6 +345,7 @@ async fn test_update_metric_status_unauthorized() -> Result<()> {
         time_frame: "last 30 days".to_string(),
         chart_config: create_default_chart_config(),
         dataset_ids: vec![],
+        drill_targets: Vec::new(),
     };
     
     // Initial verification status
@@ -511,6 +513,7 @@ async fn test_update_metric_status_null_value() -> Result<()> {
         time_frame: "last 30 days".to_string(),
         chart_config: create_default_chart_config(),
         dataset_ids: vec![],
+        drill_targets: Vec::new(),
     };
     
     // Initial verification status - set to Verified for this test
@@ -1,11 +1,16 @@
-use anyhow::Result;
-use futures_util::StreamExt;
+use anyhow::{anyhow, Result};
 use reqwest::{header, Client};
 use std::env;
+use std::future::Future;
+use std::sync::Arc;
 use tokio::sync::mpsc;
 use once_cell::sync::Lazy;
 
+use super::replay::Cassette;
+use super::providers::{native_provider, resolve_model_route, OpenAiProvider, Provider, ProviderKind};
+use super::retry::{is_retryable, RetryPolicy};
 use super::types::*;
+use super::usage::{record_embedding_usage, record_usage, recorder_installed};
 
 // Debug flag controlled by environment variable
 static DEBUG_ENABLED: Lazy<bool> = Lazy::new(|| {
@@ -14,34 +19,61 @@ static DEBUG_ENABLED: Lazy<bool> = Lazy::new(|| {
         .unwrap_or(false)
 });
 
+// Candidate fallback models, e.g. `LLM_FALLBACK_MODELS=gemini-2.0-flash-001,ollama/llama3.1`
+static CONFIGURED_FALLBACK_MODELS: Lazy<Vec<String>> = Lazy::new(|| {
+    env::var("LLM_FALLBACK_MODELS")
+        .map(|models| {
+            models
+                .split(',')
+                .map(|model| model.trim().to_string())
+                .filter(|model| !model.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+});
+
+pub(crate) fn debug_enabled() -> bool {
+    *DEBUG_ENABLED
+}
+
+// Helper function for conditional debug logging
+pub(crate) fn debug_log(msg: &str) {
+    if *DEBUG_ENABLED {
+        println!("DEBUG: {}", msg);
+    }
+}
+
 #[derive(Clone)]
 pub struct LiteLLMClient {
     client: Client,
     pub(crate) base_url: String,
+    gateway: OpenAiProvider,
+    retry_policy: RetryPolicy,
+    /// Models tried in order once the requested model keeps failing
+    fallback_models: Vec<String>,
+    /// Records or replays calls, from `LLM_REPLAY_MODE`
+    cassette: Option<Cassette>,
 }
 
 impl LiteLLMClient {
-    // Helper function for conditional debug logging
-    fn debug_log(msg: &str) {
-        if *DEBUG_ENABLED {
-            println!("DEBUG: {}", msg);
-        }
-    }
-    
+    /// Creates a client for the LLM gateway
+    ///
+    /// The API key is optional so that deployments which only talk to a local
+    /// model (e.g. `ollama/...`) don't need a gateway at all.
     pub fn new(api_key: Option<String>, base_url: Option<String>) -> Self {
-        let api_key = api_key.or_else(|| env::var("LLM_API_KEY").ok()).expect(
-            "LLM_API_KEY must be provided either through parameter or environment variable",
-        );
+        let api_key = api_key.or_else(|| env::var("LLM_API_KEY").ok());
 
         let base_url = base_url
             .or_else(|| env::var("LLM_BASE_URL").ok())
             .unwrap_or_else(|| "http://localhost:8000".to_string());
 
         let mut headers = header::HeaderMap::new();
-        headers.insert(
-            "Authorization",
-            header::HeaderValue::from_str(&format!("Bearer {}", api_key)).unwrap(),
-        );
+        if let Some(api_key) = &api_key {
+            headers.insert(
+                "Authorization",
+                header::HeaderValue::from_str(&format!("Bearer {}", api_key)).unwrap(),
+            );
+        }
         headers.insert(
             "Content-Type",
             header::HeaderValue::from_static("application/json"),
@@ -56,241 +88,287 @@ impl LiteLLMClient {
             .build()
             .expect("Failed to create HTTP client");
 
+        let gateway = OpenAiProvider::new(api_key, base_url.clone());
+
         Self {
             client,
             base_url,
+            gateway,
+            retry_policy: RetryPolicy::from_env(),
+            fallback_models: Vec::new(),
+            cassette: Cassette::from_env(),
         }
     }
 
-    pub async fn chat_completion(
-        &self,
-        request: ChatCompletionRequest,
-    ) -> Result<ChatCompletionResponse> {
-        let url = format!("{}/chat/completions", self.base_url);
+    /// Replaces the retry policy read from the environment
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
 
-        Self::debug_log(&format!("Sending chat completion request to URL: {}", url));
-        if *DEBUG_ENABLED {
-            Self::debug_log(&format!(
-                "Request payload: {}",
-                serde_json::to_string_pretty(&request).unwrap()
-            ));
-        }
+    /// Fails over to these models, in order
+    pub fn with_fallback_models(mut self, fallback_models: Vec<String>) -> Self {
+        self.fallback_models = fallback_models;
+        self
+    }
 
-        let response = self
-            .client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
+    /// Fails over to the `LLM_FALLBACK_MODELS` found in `approved`
+    ///
+    /// The fallback list is shared by every organization, so a client only
+    /// fails over once it knows which models the caller's organization allows.
+    pub fn with_approved_fallbacks(self, approved: &[String]) -> Self {
+        let fallback_models = approved_fallbacks(&CONFIGURED_FALLBACK_MODELS, approved);
+        self.with_fallback_models(fallback_models)
+    }
 
-        // Get the raw response text
-        let response_text = response.text().await?;
-        if *DEBUG_ENABLED {
-            Self::debug_log(&format!("Raw response payload: {}", response_text));
-        }
+    /// Records or replays calls through `cassette` instead of following
+    /// `LLM_REPLAY_MODE`
+    pub fn with_cassette(mut self, cassette: Option<Cassette>) -> Self {
+        self.cassette = cassette;
+        self
+    }
+
+    pub fn fallback_models(&self) -> &[String] {
+        &self.fallback_models
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Routes a request to the provider serving its model, rewriting the model
+    /// name to the one that provider expects
+    fn route(
+        &self,
+        mut request: ChatCompletionRequest,
+    ) -> Result<(Arc<dyn Provider>, ChatCompletionRequest)> {
+        let (kind, model) = resolve_model_route(&request.model);
+        debug_log(&format!("Routing model {} to {:?} as {}", request.model, kind, model));
+        request.model = model;
+
+        let provider: Arc<dyn Provider> = match kind {
+            ProviderKind::Gateway => Arc::new(self.gateway.clone()),
+            kind => native_provider(kind)?,
+        };
+
+        Ok((provider, request))
+    }
 
-        // Parse the response text into the expected type
-        let response: ChatCompletionResponse = serde_json::from_str(&response_text)?;
+    /// Sends a request to its model and then to each fallback model in turn
+    ///
+    /// Every model gets the full retry policy. Only retryable failures (rate
+    /// limits, outages, dropped connections) move on to the next model; any
+    /// other error is returned as-is.
+    async fn with_failover<T, F, Fut>(
+        &self,
+        request: ChatCompletionRequest,
+        label: &str,
+        call: F,
+    ) -> Result<T>
+    where
+        F: Fn(Arc<dyn Provider>, ChatCompletionRequest, String) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut models = vec![request.model.clone()];
+        for model in &self.fallback_models {
+            if !models.contains(model) {
+                models.push(model.clone());
+            }
+        }
 
-        // Log tool calls if present and debug is enabled
-        if *DEBUG_ENABLED {
-            if let Some(AgentMessage::Assistant {
-                tool_calls: Some(tool_calls),
-                ..
-            }) = response.choices.first().map(|c| &c.message)
+        let mut last_error = None;
+        for model in models {
+            let routed = self.route(ChatCompletionRequest {
+                model: model.clone(),
+                ..request.clone()
+            });
+            let (provider, routed_request) = match routed {
+                Ok(routed) => routed,
+                Err(e) => {
+                    tracing::warn!("Skipping model {}: {}", model, e);
+                    last_error = Some(e);
+                    continue;
+                }
+            };
+
+            match self
+                .retry_policy
+                .run(label, || {
+                    call(provider.clone(), routed_request.clone(), model.clone())
+                })
+                .await
             {
-                Self::debug_log("Tool calls in response:");
-                for tool_call in tool_calls {
-                    Self::debug_log(&format!("Tool Call ID: {}", tool_call.id));
-                    Self::debug_log(&format!("Tool Name: {}", tool_call.function.name));
-                    Self::debug_log(&format!("Tool Arguments: {}", tool_call.function.arguments));
+                Ok(value) => return Ok(value),
+                Err(e) if is_retryable(&e) => {
+                    tracing::warn!("Model {} is unavailable, trying the next fallback: {}", model, e);
+                    last_error = Some(e);
                 }
+                Err(e) => return Err(e),
             }
-
-            Self::debug_log(&format!(
-                "Received chat completion response: {}",
-                serde_json::to_string_pretty(&response).unwrap()
-            ));
         }
 
-        Ok(response)
+        Err(last_error.unwrap_or_else(|| anyhow!("No model available for the request")))
     }
 
-    pub async fn stream_chat_completion(
+    pub async fn chat_completion(
         &self,
         request: ChatCompletionRequest,
-    ) -> Result<mpsc::Receiver<Result<ChatCompletionChunk>>> {
-        let url = format!("{}/chat/completions", self.base_url);
-
-        Self::debug_log(&format!(
-            "Starting stream chat completion request to URL: {}",
-            url
-        ));
-        if *DEBUG_ENABLED {
-            Self::debug_log(&format!(
-                "Stream request payload: {}",
-                serde_json::to_string_pretty(&request).unwrap()
-            ));
+    ) -> Result<ChatCompletionResponse> {
+        if let Some(cassette) = &self.cassette {
+            if let Some(response) = cassette.load_chat_completion(&request)? {
+                return Ok(response);
+            }
         }
 
-        let mut stream = self
-            .client
-            .post(&url)
-            .json(&ChatCompletionRequest {
-                stream: Some(true),
-                ..request
+        let recorded_request = self.cassette.as_ref().map(|_| request.clone());
+        let response = self
+            .with_failover(request, "Chat completion", |provider, request, model| async move {
+                let response = provider.chat_completion(request.clone()).await?;
+                record_usage(&model, &request, &response.usage);
+                Ok(response)
             })
-            .send()
-            .await?
-            .bytes_stream();
+            .await?;
 
-        let (tx, rx) = mpsc::channel(100);
-        let debug_enabled = *DEBUG_ENABLED; // Capture for the async block
+        if let (Some(cassette), Some(request)) = (&self.cassette, recorded_request) {
+            cassette.record_chat_completion(&request, &response)?;
+        }
+        Ok(response)
+    }
 
-        tokio::spawn(async move {
-            let mut buffer = String::new();
-            if debug_enabled {
-                Self::debug_log("Stream processing started");
+    /// Opens a completion stream, retrying and failing over until one opens
+    ///
+    /// Failures after the stream opened are reported through the receiver;
+    /// callers can check them with [`crate::is_retryable`] and resend.
+    pub async fn stream_chat_completion(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<mpsc::Receiver<Result<ChatCompletionChunk>>> {
+        if let Some(cassette) = &self.cassette {
+            if let Some(rx) = cassette.load_stream(&request)? {
+                return Ok(rx);
             }
+        }
 
-            while let Some(chunk_result) = stream.next().await {
-                match chunk_result {
-                    Ok(chunk) => {
-                        let chunk_str = String::from_utf8_lossy(&chunk);
-                        if debug_enabled {
-                            Self::debug_log(&format!("Raw response payload: {}", chunk_str));
-                        }
-                        buffer.push_str(&chunk_str);
-
-                        while let Some(pos) = buffer.find("\n\n") {
-                            let line = buffer[..pos].trim().to_string();
-                            buffer = buffer[pos + 2..].to_string();
-
-                            if let Some(data) = line.strip_prefix("data: ") {
-                                if debug_enabled {
-                                    Self::debug_log(&format!("Processing stream data: {}", data));
-                                }
-                                if data == "[DONE]" {
-                                    if debug_enabled {
-                                        Self::debug_log("Stream completed with [DONE] signal");
-                                    }
-                                    break;
-                                }
-
-                                if let Ok(response) =
-                                    serde_json::from_str::<ChatCompletionChunk>(data)
-                                {
-                                    // Log tool calls if present and debug is enabled
-                                    if debug_enabled {
-                                        if let Some(tool_calls) = &response.choices[0].delta.tool_calls
-                                        {
-                                            Self::debug_log("Tool calls in stream chunk:");
-                                            for tool_call in tool_calls {
-                                                if let (Some(id), Some(function)) =
-                                                    (tool_call.id.clone(), tool_call.function.clone())
-                                                {
-                                                    Self::debug_log(&format!("Tool Call ID: {}", id));
-                                                    if let Some(name) = function.name {
-                                                        Self::debug_log(&format!("Tool Name: {}", name));
-                                                    }
-                                                    if let Some(arguments) = function.arguments {
-                                                        Self::debug_log(&format!(
-                                                            "Tool Arguments: {}",
-                                                            arguments
-                                                        ));
-                                                    }
-                                                }
-                                            }
-                                        }
-                                        Self::debug_log(&format!("Parsed stream chunk: {:?}", response));
-                                    }
-                                    
-                                    // Use try_send instead of send to avoid blocking
-                                    if tx.try_send(Ok(response)).is_err() {
-                                        // If the channel is full, log it but continue processing
-                                        if debug_enabled {
-                                            Self::debug_log("Warning: Channel full, receiver not keeping up");
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        if debug_enabled {
-                            Self::debug_log(&format!("Error in stream processing: {:?}", e));
-                        }
-                        // Use try_send to avoid blocking
-                        let _ = tx.try_send(Err(anyhow::Error::from(e)));
-                    }
-                }
-            }
-            if debug_enabled {
-                Self::debug_log("Stream processing completed");
-            }
-        });
+        let recorded_request = self.cassette.as_ref().map(|_| request.clone());
+        let rx = self
+            .with_failover(request, "Chat completion stream", |provider, request, model| async move {
+                let rx = provider.stream_chat_completion(request.clone()).await?;
+                Ok(forward_usage(rx, model, request))
+            })
+            .await?;
 
-        if debug_enabled {
-            Self::debug_log("Returning stream receiver");
+        match (&self.cassette, recorded_request) {
+            (Some(cassette), Some(request)) => cassette.record_stream(&request, rx),
+            _ => Ok(rx),
         }
-        Ok(rx)
     }
 
+    /// Generates embeddings, retrying transient failures
+    ///
+    /// The model is routed like completions, so `ollama/nomic-embed-text`
+    /// embeds locally. Embeddings never fail over to another model, as vectors
+    /// from different models aren't comparable.
     pub async fn generate_embeddings(
         &self,
         request: EmbeddingRequest,
     ) -> Result<EmbeddingResponse> {
-        let url = format!("{}/embeddings", self.base_url);
-
-        Self::debug_log(&format!("Sending embedding request to URL: {}", url));
-        if *DEBUG_ENABLED {
-            Self::debug_log(&format!(
-                "Embedding request payload: {}",
-                serde_json::to_string_pretty(&request).unwrap_or_else(|e| format!("Serialization Error: {}", e))
-            ));
+        if let Some(cassette) = &self.cassette {
+            if let Some(response) = cassette.load_embeddings(&request)? {
+                return Ok(response);
+            }
         }
 
+        let (kind, model) = resolve_model_route(&request.model);
+        debug_log(&format!("Routing embedding model {} to {:?} as {}", request.model, kind, model));
+        let provider: Arc<dyn Provider> = match kind {
+            ProviderKind::Gateway => Arc::new(self.gateway.clone()),
+            kind => native_provider(kind)?,
+        };
+        let routed_request = EmbeddingRequest {
+            model,
+            ..request.clone()
+        };
+
         let response = self
-            .client
-            .post(&url)
-            .json(&request)
-            .send()
+            .retry_policy
+            .run("Embedding request", || provider.embeddings(routed_request.clone()))
             .await?;
+        record_embedding_usage(
+            &request.model,
+            request.user.as_deref(),
+            response.usage.prompt_tokens,
+        );
+
+        if let Some(cassette) = &self.cassette {
+            cassette.record_embeddings(&request, &response)?;
+        }
+        Ok(response)
+    }
+
+    /// Lists the models the gateway exposes
+    pub async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        let url = format!("{}/models", self.base_url);
+
+        debug_log(&format!("Listing models from URL: {}", url));
+
+        let response = self.client.get(&url).send().await?;
 
-        // Check for non-success status codes first
         let status = response.status();
         let response_text = response.text().await?;
         if !status.is_success() {
-            Self::debug_log(&format!(
-                "Error response from embedding endpoint (Status: {}): {}",
-                status,
-                response_text
-            ));
             return Err(anyhow::anyhow!(
-                "Embedding request failed with status {}: {}",
+                "Model list request failed with status {}: {}",
                 status,
                 response_text
             ));
         }
-        
-        if *DEBUG_ENABLED {
-            Self::debug_log(&format!("Raw embedding response payload: {}", response_text));
-        }
 
-        // Parse the response text into the expected type
-        let response: EmbeddingResponse = serde_json::from_str(&response_text)
-            .map_err(|e| anyhow::anyhow!("Failed to deserialize embedding response: {}. Response text: {}", e, response_text))?;
+        let models: ModelList = serde_json::from_str(&response_text).map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to deserialize model list: {}. Response text: {}",
+                e,
+                response_text
+            )
+        })?;
 
+        Ok(models.data)
+    }
+}
 
-        if *DEBUG_ENABLED {
-             Self::debug_log(&format!(
-                "Received embedding response: {}",
-                serde_json::to_string_pretty(&response).unwrap_or_else(|e| format!("Serialization Error: {}", e))
-            ));
-        }
+/// Passes stream chunks through, recording the usage carried by the final one
+fn approved_fallbacks(configured: &[String], approved: &[String]) -> Vec<String> {
+    configured
+        .iter()
+        .filter(|model| approved.contains(model))
+        .cloned()
+        .collect()
+}
 
-        Ok(response)
+fn forward_usage(
+    mut rx: mpsc::Receiver<Result<ChatCompletionChunk>>,
+    model: String,
+    request: ChatCompletionRequest,
+) -> mpsc::Receiver<Result<ChatCompletionChunk>> {
+    if !recorder_installed() {
+        return rx;
     }
+
+    let (tx, forwarded_rx) = mpsc::channel(100);
+    tokio::spawn(async move {
+        while let Some(chunk) = rx.recv().await {
+            if let Ok(ChatCompletionChunk {
+                usage: Some(usage), ..
+            }) = &chunk
+            {
+                record_usage(&model, &request, usage);
+            }
+            if tx.send(chunk).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    forwarded_rx
 }
 
 impl Default for LiteLLMClient {
@@ -390,6 +468,36 @@ mod tests {
         mock.assert();
     }
 
+    #[tokio::test]
+    async fn test_list_models() {
+        let mut server = mockito::Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/models")
+            .match_header("authorization", "Bearer test-key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                "object": "list",
+                "data": [
+                    {"id": "o4-mini", "object": "model", "owned_by": "openai"},
+                    {"id": "text-embedding-3-small", "object": "model"}
+                ]
+            }"#,
+            )
+            .create();
+
+        let client = LiteLLMClient::new(Some("test-key".to_string()), Some(server.url()));
+
+        let models = client.list_models().await.unwrap();
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[0].id, "o4-mini");
+        assert_eq!(models[1].owned_by, None);
+
+        mock.assert();
+    }
+
     #[tokio::test]
     async fn test_chat_completion_error() {
         let mut server = mockito::Server::new_async().await;
@@ -415,6 +523,79 @@ mod tests {
         mock.assert();
     }
 
+    #[test]
+    fn test_approved_fallbacks() {
+        let configured = vec![
+            "gemini-2.0-flash-001".to_string(),
+            "ollama/llama3.1".to_string(),
+            "gpt-4o-mini".to_string(),
+        ];
+        let approved = vec![
+            "gpt-4o-mini".to_string(),
+            "o4-mini".to_string(),
+            "gemini-2.0-flash-001".to_string(),
+        ];
+
+        assert_eq!(
+            approved_fallbacks(&configured, &approved),
+            vec!["gemini-2.0-flash-001".to_string(), "gpt-4o-mini".to_string()]
+        );
+        assert!(approved_fallbacks(&configured, &[]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_retries_then_falls_back() {
+        let mut server = mockito::Server::new_async().await;
+
+        let primary = server
+            .mock("POST", "/chat/completions")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"model": "gpt-4"}"#.to_string(),
+            ))
+            .with_status(503)
+            .with_header("retry-after", "0")
+            .with_body(r#"{"error": "Service unavailable"}"#)
+            .expect(3)
+            .create();
+
+        let fallback = server
+            .mock("POST", "/chat/completions")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"model": "gpt-4o-mini"}"#.to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                "id": "fallback-id",
+                "object": "chat.completion",
+                "created": 1234567890,
+                "model": "gpt-4o-mini",
+                "choices": [{
+                    "index": 0,
+                    "message": { "role": "assistant", "content": "Hello from the fallback" },
+                    "finish_reason": "stop"
+                }],
+                "usage": { "prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15 }
+            }"#,
+            )
+            .create();
+
+        let client = LiteLLMClient::new(Some("test-key".to_string()), Some(server.url()))
+            .with_retry_policy(RetryPolicy {
+                max_retries: 2,
+                base_delay: Duration::ZERO,
+                max_delay: Duration::ZERO,
+            })
+            .with_fallback_models(vec!["gpt-4o-mini".to_string()]);
+
+        let response = client.chat_completion(create_test_request()).await.unwrap();
+        assert_eq!(response.id, "fallback-id");
+
+        primary.assert();
+        fallback.assert();
+    }
+
     #[tokio::test]
     async fn test_stream_chat_completion() {
         let mut server = mockito::Server::new_async().await;
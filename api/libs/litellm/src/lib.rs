@@ -1,5 +1,11 @@
 mod client;
+pub mod providers;
+pub mod replay;
+mod retry;
 mod types;
+pub mod usage;
 
 pub use client::*;
-pub use types::{AgentMessage, ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse, Metadata, MessageProgress, Tool, ToolCall, ToolChoice, ResponseFormat, EmbeddingRequest, EmbeddingResponse, EmbeddingData, EmbeddingUsage, DeltaToolCall, FunctionCall}; 
\ No newline at end of file
+pub use replay::{Cassette, ReplayMode};
+pub use retry::{is_retryable, retry_after, ProviderError, RetryPolicy};
+pub use types::{AgentMessage, ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse, Metadata, MessageProgress, Tool, ToolCall, ToolChoice, ResponseFormat, EmbeddingRequest, EmbeddingResponse, EmbeddingData, EmbeddingUsage, embedding_dimensions, DEFAULT_EMBEDDING_DIMENSIONS, ModelList, ModelInfo, DeltaToolCall, FunctionCall, StreamOptions, Usage}; 
\ No newline at end of file
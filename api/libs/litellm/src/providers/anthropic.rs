@@ -0,0 +1,646 @@
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use reqwest::{header, Client};
+use serde_json::{json, Map, Value};
+use tokio::sync::mpsc;
+
+use super::{drain_sse_data, Provider};
+use crate::client::{debug_enabled, debug_log};
+use crate::retry::ProviderError;
+use crate::types::*;
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const DEFAULT_MAX_TOKENS: u32 = 4096;
+
+/// Provider for the Anthropic Messages API
+#[derive(Clone)]
+pub struct AnthropicProvider {
+    client: Client,
+    base_url: String,
+}
+
+impl AnthropicProvider {
+    pub fn new(api_key: String, base_url: String) -> Self {
+        let mut headers = header::HeaderMap::new();
+        headers.insert(
+            "x-api-key",
+            header::HeaderValue::from_str(&api_key).expect("Invalid Anthropic API key"),
+        );
+        headers.insert(
+            "anthropic-version",
+            header::HeaderValue::from_static(ANTHROPIC_VERSION),
+        );
+        headers.insert(
+            "Content-Type",
+            header::HeaderValue::from_static("application/json"),
+        );
+
+        let client = Client::builder()
+            .default_headers(headers)
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    async fn send(&self, body: &Value) -> Result<reqwest::Response> {
+        let url = format!("{}/v1/messages", self.base_url);
+
+        debug_log(&format!("Sending Anthropic messages request to URL: {}", url));
+        if debug_enabled() {
+            debug_log(&format!(
+                "Request payload: {}",
+                serde_json::to_string_pretty(body).unwrap()
+            ));
+        }
+
+        let response = self.client.post(&url).json(body).send().await?;
+
+        if !response.status().is_success() {
+            return Err(ProviderError::from_response(response).await.into());
+        }
+
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl Provider for AnthropicProvider {
+    async fn chat_completion(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<ChatCompletionResponse> {
+        let body = to_messages_request(&request, false);
+        let response_text = self.send(&body).await?.text().await?;
+        if debug_enabled() {
+            debug_log(&format!("Raw response payload: {}", response_text));
+        }
+
+        let response: Value = serde_json::from_str(&response_text)?;
+        from_messages_response(&response)
+    }
+
+    async fn stream_chat_completion(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<mpsc::Receiver<Result<ChatCompletionChunk>>> {
+        let body = to_messages_request(&request, true);
+        let mut stream = self.send(&body).await?.bytes_stream();
+
+        let (tx, rx) = mpsc::channel(100);
+        let debug_enabled = debug_enabled();
+
+        tokio::spawn(async move {
+            let mut buffer = String::new();
+            let mut state = AnthropicStreamState::default();
+
+            while let Some(chunk_result) = stream.next().await {
+                match chunk_result {
+                    Ok(chunk) => {
+                        buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+                        for data in drain_sse_data(&mut buffer) {
+                            if debug_enabled {
+                                debug_log(&format!("Processing stream data: {}", data));
+                            }
+
+                            match state.handle(&data) {
+                                Ok(Some(chunk)) => {
+                                    if tx.send(Ok(chunk)).await.is_err() {
+                                        return;
+                                    }
+                                }
+                                Ok(None) => {}
+                                Err(e) => {
+                                    let _ = tx.send(Err(e)).await;
+                                    return;
+                                }
+                            }
+
+                            if state.finished {
+                                return;
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(anyhow::Error::from(e))).await;
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+}
+
+/// Translates an OpenAI-style request into a Messages API request body
+fn to_messages_request(request: &ChatCompletionRequest, stream: bool) -> Value {
+    let mut system = Vec::new();
+    let mut messages: Vec<Value> = Vec::new();
+
+    // The Messages API requires alternating roles, so consecutive blocks from
+    // the same role (e.g. several tool results) share a message
+    let mut push_block = |role: &str, block: Value| {
+        if let Some(last) = messages.last_mut() {
+            if last["role"] == role {
+                if let Some(content) = last["content"].as_array_mut() {
+                    content.push(block);
+                    return;
+                }
+            }
+        }
+        messages.push(json!({ "role": role, "content": [block] }));
+    };
+
+    for message in &request.messages {
+        match message {
+            AgentMessage::Developer { content, .. } => system.push(content.clone()),
+            AgentMessage::User { content, .. } => {
+                if !content.is_empty() {
+                    push_block("user", json!({ "type": "text", "text": content }));
+                }
+            }
+            AgentMessage::Assistant {
+                content,
+                tool_calls,
+                ..
+            } => {
+                if let Some(content) = content.as_ref().filter(|c| !c.is_empty()) {
+                    push_block("assistant", json!({ "type": "text", "text": content }));
+                }
+                for tool_call in tool_calls.iter().flatten() {
+                    let input = serde_json::from_str::<Value>(&tool_call.function.arguments)
+                        .ok()
+                        .filter(Value::is_object)
+                        .unwrap_or_else(|| json!({}));
+                    push_block(
+                        "assistant",
+                        json!({
+                            "type": "tool_use",
+                            "id": tool_call.id,
+                            "name": tool_call.function.name,
+                            "input": input,
+                        }),
+                    );
+                }
+            }
+            AgentMessage::Tool {
+                content,
+                tool_call_id,
+                ..
+            } => push_block(
+                "user",
+                json!({
+                    "type": "tool_result",
+                    "tool_use_id": tool_call_id,
+                    "content": content,
+                }),
+            ),
+            AgentMessage::Done => {}
+        }
+    }
+
+    let mut body = Map::new();
+    body.insert("model".to_string(), json!(request.model));
+    body.insert("messages".to_string(), json!(messages));
+    body.insert(
+        "max_tokens".to_string(),
+        json!(request.max_completion_tokens.unwrap_or(DEFAULT_MAX_TOKENS)),
+    );
+    if !system.is_empty() {
+        body.insert("system".to_string(), json!(system.join("\n\n")));
+    }
+    if let Some(temperature) = request.temperature {
+        body.insert("temperature".to_string(), json!(temperature));
+    }
+    if let Some(top_p) = request.top_p {
+        body.insert("top_p".to_string(), json!(top_p));
+    }
+    if let Some(stop) = &request.stop {
+        body.insert("stop_sequences".to_string(), json!(stop));
+    }
+    if stream {
+        body.insert("stream".to_string(), json!(true));
+    }
+
+    if let Some(tools) = request.tools.as_ref().filter(|tools| !tools.is_empty()) {
+        let tools: Vec<Value> = tools
+            .iter()
+            .map(|tool| {
+                json!({
+                    "name": tool.function["name"],
+                    "description": tool.function.get("description").cloned().unwrap_or(json!("")),
+                    "input_schema": tool
+                        .function
+                        .get("parameters")
+                        .cloned()
+                        .unwrap_or_else(|| json!({ "type": "object", "properties": {} })),
+                })
+            })
+            .collect();
+        body.insert("tools".to_string(), json!(tools));
+
+        let mut tool_choice = match &request.tool_choice {
+            Some(ToolChoice::None) => json!({ "type": "none" }),
+            Some(ToolChoice::Required) => json!({ "type": "any" }),
+            Some(ToolChoice::Function { function, .. }) => {
+                json!({ "type": "tool", "name": function.name })
+            }
+            Some(ToolChoice::Auto) | None => json!({ "type": "auto" }),
+        };
+        if request.parallel_tool_calls == Some(false) && tool_choice["type"] != "none" {
+            tool_choice["disable_parallel_tool_use"] = json!(true);
+        }
+        body.insert("tool_choice".to_string(), tool_choice);
+    }
+
+    Value::Object(body)
+}
+
+fn finish_reason(stop_reason: &str) -> String {
+    match stop_reason {
+        "tool_use" => "tool_calls",
+        "max_tokens" => "length",
+        _ => "stop",
+    }
+    .to_string()
+}
+
+fn unix_timestamp() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+fn usage_from(usage: &Value) -> Usage {
+    let prompt_tokens = usage["input_tokens"].as_i64().unwrap_or(0) as i32;
+    let completion_tokens = usage["output_tokens"].as_i64().unwrap_or(0) as i32;
+    Usage {
+        prompt_tokens,
+        completion_tokens,
+        total_tokens: prompt_tokens + completion_tokens,
+        completion_tokens_details: None,
+    }
+}
+
+/// Translates a Messages API response into an OpenAI-style response
+fn from_messages_response(response: &Value) -> Result<ChatCompletionResponse> {
+    let blocks = response["content"]
+        .as_array()
+        .ok_or_else(|| anyhow!("Anthropic response has no content: {}", response))?;
+
+    let mut text = String::new();
+    let mut tool_calls = Vec::new();
+    for block in blocks {
+        match block["type"].as_str() {
+            Some("text") => text.push_str(block["text"].as_str().unwrap_or_default()),
+            Some("tool_use") => tool_calls.push(ToolCall {
+                id: block["id"].as_str().unwrap_or_default().to_string(),
+                call_type: "function".to_string(),
+                function: FunctionCall {
+                    name: block["name"].as_str().unwrap_or_default().to_string(),
+                    arguments: block["input"].to_string(),
+                },
+                code_interpreter: None,
+                retrieval: None,
+            }),
+            _ => {}
+        }
+    }
+
+    let message = AgentMessage::assistant(
+        None,
+        (!text.is_empty()).then_some(text),
+        (!tool_calls.is_empty()).then_some(tool_calls),
+        MessageProgress::Complete,
+        None,
+        None,
+    );
+
+    Ok(ChatCompletionResponse {
+        id: response["id"].as_str().unwrap_or_default().to_string(),
+        object: "chat.completion".to_string(),
+        created: unix_timestamp(),
+        model: response["model"].as_str().unwrap_or_default().to_string(),
+        system_fingerprint: None,
+        choices: vec![Choice {
+            index: 0,
+            message,
+            delta: None,
+            logprobs: None,
+            finish_reason: response["stop_reason"].as_str().map(finish_reason),
+        }],
+        service_tier: None,
+        usage: usage_from(&response["usage"]),
+    })
+}
+
+/// Turns Messages API stream events into OpenAI-style chunks
+///
+/// Anthropic only names a tool call in its `content_block_start` event, so the
+/// call id is remembered per block and repeated on every argument delta.
+#[derive(Default)]
+struct AnthropicStreamState {
+    id: String,
+    model: String,
+    created: i64,
+    tool_ids: HashMap<u64, String>,
+    /// Prompt tokens, reported up front in `message_start`
+    usage: Value,
+    finished: bool,
+}
+
+impl AnthropicStreamState {
+    fn handle(&mut self, data: &str) -> Result<Option<ChatCompletionChunk>> {
+        let event: Value = serde_json::from_str(data)?;
+
+        match event["type"].as_str().unwrap_or_default() {
+            "message_start" => {
+                self.id = event["message"]["id"].as_str().unwrap_or_default().to_string();
+                self.model = event["message"]["model"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string();
+                self.created = unix_timestamp();
+                self.usage = event["message"]["usage"].clone();
+                Ok(Some(self.chunk(
+                    Delta {
+                        role: Some("assistant".to_string()),
+                        content: None,
+                        function_call: None,
+                        tool_calls: None,
+                    },
+                    None,
+                )))
+            }
+            "content_block_start" if event["content_block"]["type"] == "tool_use" => {
+                let index = event["index"].as_u64().unwrap_or_default();
+                let id = event["content_block"]["id"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string();
+                self.tool_ids.insert(index, id.clone());
+                Ok(Some(self.tool_chunk(
+                    id,
+                    Some("function".to_string()),
+                    event["content_block"]["name"].as_str().map(str::to_string),
+                    String::new(),
+                )))
+            }
+            "content_block_delta" => {
+                let delta = &event["delta"];
+                match delta["type"].as_str() {
+                    Some("text_delta") => Ok(Some(self.chunk(
+                        Delta {
+                            role: None,
+                            content: delta["text"].as_str().map(str::to_string),
+                            function_call: None,
+                            tool_calls: None,
+                        },
+                        None,
+                    ))),
+                    Some("input_json_delta") => {
+                        let index = event["index"].as_u64().unwrap_or_default();
+                        let id = self.tool_ids.get(&index).cloned().unwrap_or_default();
+                        Ok(Some(self.tool_chunk(
+                            id,
+                            None,
+                            None,
+                            delta["partial_json"].as_str().unwrap_or_default().to_string(),
+                        )))
+                    }
+                    _ => Ok(None),
+                }
+            }
+            "message_delta" => Ok(event["delta"]["stop_reason"].as_str().map(|reason| {
+                let mut chunk = self.chunk(
+                    Delta {
+                        role: None,
+                        content: None,
+                        function_call: None,
+                        tool_calls: None,
+                    },
+                    Some(finish_reason(reason)),
+                );
+                chunk.usage = Some(usage_from(&json!({
+                    "input_tokens": self.usage["input_tokens"],
+                    "output_tokens": event["usage"]["output_tokens"],
+                })));
+                chunk
+            })),
+            "message_stop" => {
+                self.finished = true;
+                Ok(None)
+            }
+            // Errors after the stream opened carry no HTTP status, so map the
+            // error type onto the status Anthropic would have returned
+            "error" => {
+                let status = match event["error"]["type"].as_str() {
+                    Some("overloaded_error") => 529,
+                    Some("rate_limit_error") => 429,
+                    Some("api_error") => 500,
+                    _ => 400,
+                };
+                Err(ProviderError {
+                    status,
+                    retry_after: None,
+                    body: event["error"]["message"].as_str().unwrap_or(data).to_string(),
+                }
+                .into())
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn chunk(&self, delta: Delta, finish_reason: Option<String>) -> ChatCompletionChunk {
+        ChatCompletionChunk {
+            id: self.id.clone(),
+            object: "chat.completion.chunk".to_string(),
+            created: self.created,
+            model: self.model.clone(),
+            system_fingerprint: None,
+            choices: vec![StreamChoice {
+                index: 0,
+                delta,
+                logprobs: None,
+                finish_reason,
+            }],
+            usage: None,
+        }
+    }
+
+    fn tool_chunk(
+        &self,
+        id: String,
+        call_type: Option<String>,
+        name: Option<String>,
+        arguments: String,
+    ) -> ChatCompletionChunk {
+        self.chunk(
+            Delta {
+                role: None,
+                content: None,
+                function_call: None,
+                tool_calls: Some(vec![DeltaToolCall {
+                    id: Some(id),
+                    call_type,
+                    function: Some(DeltaFunctionCall {
+                        name,
+                        arguments: Some(arguments),
+                    }),
+                    code_interpreter: None,
+                    retrieval: None,
+                }]),
+            },
+            None,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_messages_request() {
+        let request = ChatCompletionRequest {
+            model: "claude-3-7-sonnet-latest".to_string(),
+            messages: vec![
+                AgentMessage::developer("You are a data analyst."),
+                AgentMessage::user("How many orders?"),
+                AgentMessage::assistant(
+                    None,
+                    Some("Let me check.".to_string()),
+                    Some(vec![ToolCall {
+                        id: "toolu_1".to_string(),
+                        call_type: "function".to_string(),
+                        function: FunctionCall {
+                            name: "run_sql".to_string(),
+                            arguments: "{\"sql\":\"select count(*) from orders\"}".to_string(),
+                        },
+                        code_interpreter: None,
+                        retrieval: None,
+                    }]),
+                    MessageProgress::Complete,
+                    None,
+                    None,
+                ),
+                AgentMessage::tool(None, "42", "toolu_1", None, MessageProgress::Complete),
+                AgentMessage::user("Thanks"),
+            ],
+            tools: Some(vec![Tool {
+                tool_type: "function".to_string(),
+                function: json!({
+                    "name": "run_sql",
+                    "description": "Runs SQL",
+                    "parameters": { "type": "object", "properties": { "sql": { "type": "string" } } },
+                }),
+            }]),
+            tool_choice: Some(ToolChoice::Required),
+            parallel_tool_calls: Some(false),
+            ..Default::default()
+        };
+
+        let body = to_messages_request(&request, true);
+
+        assert_eq!(body["system"], "You are a data analyst.");
+        assert_eq!(body["max_tokens"], DEFAULT_MAX_TOKENS);
+        assert_eq!(body["stream"], true);
+        assert_eq!(body["tool_choice"]["type"], "any");
+        assert_eq!(body["tool_choice"]["disable_parallel_tool_use"], true);
+        assert_eq!(body["tools"][0]["input_schema"]["properties"]["sql"]["type"], "string");
+
+        let messages = body["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[1]["role"], "assistant");
+        assert_eq!(messages[1]["content"][1]["type"], "tool_use");
+        assert_eq!(messages[1]["content"][1]["input"]["sql"], "select count(*) from orders");
+        // The tool result and the following user turn share one user message
+        assert_eq!(messages[2]["role"], "user");
+        assert_eq!(messages[2]["content"][0]["type"], "tool_result");
+        assert_eq!(messages[2]["content"][0]["tool_use_id"], "toolu_1");
+        assert_eq!(messages[2]["content"][1]["text"], "Thanks");
+    }
+
+    #[test]
+    fn test_from_messages_response() {
+        let response = json!({
+            "id": "msg_1",
+            "type": "message",
+            "role": "assistant",
+            "model": "claude-3-7-sonnet-latest",
+            "content": [
+                { "type": "text", "text": "Running it." },
+                { "type": "tool_use", "id": "toolu_1", "name": "run_sql", "input": { "sql": "select 1" } }
+            ],
+            "stop_reason": "tool_use",
+            "usage": { "input_tokens": 10, "output_tokens": 5 }
+        });
+
+        let response = from_messages_response(&response).unwrap();
+
+        assert_eq!(response.choices[0].finish_reason.as_deref(), Some("tool_calls"));
+        assert_eq!(response.usage.total_tokens, 15);
+        match &response.choices[0].message {
+            AgentMessage::Assistant {
+                content,
+                tool_calls: Some(tool_calls),
+                ..
+            } => {
+                assert_eq!(content.as_deref(), Some("Running it."));
+                assert_eq!(tool_calls[0].id, "toolu_1");
+                assert_eq!(tool_calls[0].function.arguments, "{\"sql\":\"select 1\"}");
+            }
+            other => panic!("Unexpected message: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_stream_events_map_to_chunks() {
+        let mut state = AnthropicStreamState::default();
+        let events = [
+            r#"{"type":"message_start","message":{"id":"msg_1","model":"claude-3-7-sonnet-latest","usage":{"input_tokens":10}}}"#,
+            r#"{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}"#,
+            r#"{"type":"content_block_start","index":1,"content_block":{"type":"tool_use","id":"toolu_1","name":"run_sql","input":{}}}"#,
+            r#"{"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"{\"sql\":"}}"#,
+            r#"{"type":"ping"}"#,
+            r#"{"type":"message_delta","delta":{"stop_reason":"tool_use"},"usage":{"output_tokens":5}}"#,
+            r#"{"type":"message_stop"}"#,
+        ];
+
+        let chunks: Vec<ChatCompletionChunk> = events
+            .iter()
+            .filter_map(|event| state.handle(event).unwrap())
+            .collect();
+
+        assert_eq!(chunks.len(), 5);
+        assert!(chunks.iter().all(|chunk| chunk.id == "msg_1"));
+        assert_eq!(chunks[1].choices[0].delta.content.as_deref(), Some("Hi"));
+
+        let start = &chunks[2].choices[0].delta.tool_calls.as_ref().unwrap()[0];
+        assert_eq!(start.id.as_deref(), Some("toolu_1"));
+        assert_eq!(start.function.as_ref().unwrap().name.as_deref(), Some("run_sql"));
+
+        let args = &chunks[3].choices[0].delta.tool_calls.as_ref().unwrap()[0];
+        assert_eq!(args.id.as_deref(), Some("toolu_1"));
+        assert_eq!(args.function.as_ref().unwrap().arguments.as_deref(), Some("{\"sql\":"));
+
+        assert_eq!(chunks[4].choices[0].finish_reason.as_deref(), Some("tool_calls"));
+        assert_eq!(chunks[4].usage.as_ref().unwrap().total_tokens, 15);
+        assert!(state.finished);
+        let error = state
+            .handle(r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#)
+            .unwrap_err();
+        assert!(crate::is_retryable(&error));
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use tokio::sync::mpsc;
+
+use crate::types::{
+    ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse, EmbeddingRequest,
+    EmbeddingResponse,
+};
+
+mod anthropic;
+mod ollama;
+mod openai;
+
+pub use anthropic::AnthropicProvider;
+pub use ollama::OllamaProvider;
+pub use openai::OpenAiProvider;
+
+/// A chat completion backend
+///
+/// Every provider accepts and returns the OpenAI-shaped types used throughout
+/// the crate, translating to and from its native wire format.
+#[async_trait]
+pub trait Provider: Send + Sync {
+    async fn chat_completion(&self, request: ChatCompletionRequest)
+        -> Result<ChatCompletionResponse>;
+
+    async fn stream_chat_completion(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<mpsc::Receiver<Result<ChatCompletionChunk>>>;
+
+    /// Embeds `request.input`; providers without an embeddings API refuse
+    async fn embeddings(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+        Err(anyhow!(
+            "Model {} is served by a provider without embeddings",
+            request.model
+        ))
+    }
+}
+
+/// Which backend serves a model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderKind {
+    /// The OpenAI-compatible gateway at `LLM_BASE_URL`
+    Gateway,
+    OpenAi,
+    Anthropic,
+    Ollama,
+}
+
+impl ProviderKind {
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "gateway" => Some(ProviderKind::Gateway),
+            "openai" => Some(ProviderKind::OpenAi),
+            "anthropic" => Some(ProviderKind::Anthropic),
+            "ollama" => Some(ProviderKind::Ollama),
+            _ => None,
+        }
+    }
+}
+
+// Per-model provider overrides, e.g. `LLM_MODEL_PROVIDERS=o4-mini=openai,claude-3-7-sonnet-latest=anthropic`
+static MODEL_PROVIDERS: Lazy<HashMap<String, ProviderKind>> = Lazy::new(|| {
+    env::var("LLM_MODEL_PROVIDERS")
+        .map(|value| parse_model_providers(&value))
+        .unwrap_or_default()
+});
+
+fn parse_model_providers(value: &str) -> HashMap<String, ProviderKind> {
+    value
+        .split(',')
+        .filter_map(|entry| {
+            let (model, provider) = entry.split_once('=')?;
+            Some((
+                model.trim().to_string(),
+                ProviderKind::from_prefix(provider.trim())?,
+            ))
+        })
+        .collect()
+}
+
+/// Picks the provider for a model and the model name to send it
+///
+/// A `provider/` prefix (`anthropic/claude-3-7-sonnet-latest`,
+/// `ollama/llama3.1`) selects a native backend and is stripped. Unprefixed
+/// models use `LLM_MODEL_PROVIDERS`, then fall back to the gateway.
+pub fn resolve_model_route(model: &str) -> (ProviderKind, String) {
+    resolve_with(model, &MODEL_PROVIDERS)
+}
+
+fn resolve_with(model: &str, overrides: &HashMap<String, ProviderKind>) -> (ProviderKind, String) {
+    if let Some((prefix, name)) = model.split_once('/') {
+        if let Some(kind) = ProviderKind::from_prefix(prefix) {
+            return (kind, name.to_string());
+        }
+    }
+
+    let kind = overrides
+        .get(model)
+        .copied()
+        .unwrap_or(ProviderKind::Gateway);
+    (kind, model.to_string())
+}
+
+static OPENAI: Lazy<Arc<dyn Provider>> = Lazy::new(|| {
+    Arc::new(OpenAiProvider::new(
+        env::var("OPENAI_API_KEY").ok(),
+        env::var("OPENAI_BASE_URL").unwrap_or_else(|_| "https://api.openai.com/v1".to_string()),
+    ))
+});
+
+static ANTHROPIC: Lazy<Option<Arc<dyn Provider>>> = Lazy::new(|| {
+    let api_key = env::var("ANTHROPIC_API_KEY").ok()?;
+    Some(Arc::new(AnthropicProvider::new(
+        api_key,
+        env::var("ANTHROPIC_BASE_URL").unwrap_or_else(|_| "https://api.anthropic.com".to_string()),
+    )))
+});
+
+static OLLAMA: Lazy<Arc<dyn Provider>> = Lazy::new(|| {
+    Arc::new(OllamaProvider::new(
+        env::var("OLLAMA_BASE_URL").unwrap_or_else(|_| "http://localhost:11434".to_string()),
+    ))
+});
+
+/// The shared native provider for `kind`, configured from the environment
+pub(crate) fn native_provider(kind: ProviderKind) -> Result<Arc<dyn Provider>> {
+    match kind {
+        ProviderKind::OpenAi => Ok(OPENAI.clone()),
+        ProviderKind::Anthropic => ANTHROPIC
+            .clone()
+            .ok_or_else(|| anyhow!("ANTHROPIC_API_KEY must be set to use anthropic/ models")),
+        ProviderKind::Ollama => Ok(OLLAMA.clone()),
+        ProviderKind::Gateway => Err(anyhow!("The gateway is not a native provider")),
+    }
+}
+
+/// Splits a buffered server-sent event stream into complete `data:` payloads,
+/// leaving any partial event in the buffer
+pub(crate) fn drain_sse_data(buffer: &mut String) -> Vec<String> {
+    let mut payloads = Vec::new();
+
+    while let Some(pos) = buffer.find("\n\n") {
+        let event: String = buffer.drain(..pos + 2).collect();
+        for line in event.lines() {
+            if let Some(data) = line.strip_prefix("data:") {
+                payloads.push(data.trim().to_string());
+            }
+        }
+    }
+
+    payloads
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_model_route() {
+        let overrides = parse_model_providers("o4-mini=openai, llama3.1 = ollama,bad=nope");
+
+        assert_eq!(
+            resolve_with("anthropic/claude-3-7-sonnet-latest", &overrides),
+            (ProviderKind::Anthropic, "claude-3-7-sonnet-latest".to_string())
+        );
+        assert_eq!(
+            resolve_with("ollama/qwen2.5:14b", &overrides),
+            (ProviderKind::Ollama, "qwen2.5:14b".to_string())
+        );
+        assert_eq!(
+            resolve_with("o4-mini", &overrides),
+            (ProviderKind::OpenAi, "o4-mini".to_string())
+        );
+        assert_eq!(
+            resolve_with("llama3.1", &overrides),
+            (ProviderKind::Ollama, "llama3.1".to_string())
+        );
+        // Unknown prefixes are part of the model name, as gateway aliases may contain slashes
+        assert_eq!(
+            resolve_with("vertex_ai/gemini-2.0-flash-001", &overrides),
+            (ProviderKind::Gateway, "vertex_ai/gemini-2.0-flash-001".to_string())
+        );
+        assert!(!overrides.contains_key("bad"));
+    }
+
+    #[test]
+    fn test_drain_sse_data() {
+        let mut buffer = "event: ping\ndata: {\"a\":1}\n\ndata: [DONE]\n\ndata: {\"par".to_string();
+
+        let payloads = drain_sse_data(&mut buffer);
+
+        assert_eq!(payloads, vec!["{\"a\":1}".to_string(), "[DONE]".to_string()]);
+        assert_eq!(buffer, "data: {\"par");
+    }
+}
@@ -0,0 +1,628 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use reqwest::{header, Client};
+use serde_json::{json, Map, Value};
+use tokio::sync::mpsc;
+
+use super::Provider;
+use crate::client::{debug_enabled, debug_log};
+use crate::retry::ProviderError;
+use crate::types::*;
+
+/// Provider for a local Ollama server's `/api/chat` endpoint
+///
+/// Needs no credentials, so it works for air-gapped deployments.
+#[derive(Clone)]
+pub struct OllamaProvider {
+    client: Client,
+    base_url: String,
+}
+
+impl OllamaProvider {
+    pub fn new(base_url: String) -> Self {
+        let mut headers = header::HeaderMap::new();
+        headers.insert(
+            "Content-Type",
+            header::HeaderValue::from_static("application/json"),
+        );
+
+        let client = Client::builder()
+            .default_headers(headers)
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    async fn send(&self, path: &str, body: &Value) -> Result<reqwest::Response> {
+        let url = format!("{}{}", self.base_url, path);
+
+        debug_log(&format!("Sending Ollama request to URL: {}", url));
+        if debug_enabled() {
+            debug_log(&format!(
+                "Request payload: {}",
+                serde_json::to_string_pretty(body).unwrap()
+            ));
+        }
+
+        let response = self.client.post(&url).json(body).send().await?;
+
+        if !response.status().is_success() {
+            return Err(ProviderError::from_response(response).await.into());
+        }
+
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl Provider for OllamaProvider {
+    async fn chat_completion(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<ChatCompletionResponse> {
+        let body = to_chat_request(&request, false);
+        let response_text = self.send("/api/chat", &body).await?.text().await?;
+        if debug_enabled() {
+            debug_log(&format!("Raw response payload: {}", response_text));
+        }
+
+        let response: Value = serde_json::from_str(&response_text)?;
+        from_chat_response(&response)
+    }
+
+    async fn stream_chat_completion(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<mpsc::Receiver<Result<ChatCompletionChunk>>> {
+        let body = to_chat_request(&request, true);
+        let mut stream = self.send("/api/chat", &body).await?.bytes_stream();
+
+        let (tx, rx) = mpsc::channel(100);
+        let debug_enabled = debug_enabled();
+
+        tokio::spawn(async move {
+            let mut buffer = String::new();
+            let mut state = OllamaStreamState::new();
+
+            while let Some(chunk_result) = stream.next().await {
+                match chunk_result {
+                    Ok(chunk) => {
+                        buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+                        // The stream is newline-delimited JSON, one object per line
+                        while let Some(pos) = buffer.find('\n') {
+                            let line: String = buffer.drain(..=pos).collect();
+                            let line = line.trim();
+                            if line.is_empty() {
+                                continue;
+                            }
+                            if debug_enabled {
+                                debug_log(&format!("Processing stream data: {}", line));
+                            }
+
+                            match state.handle(line) {
+                                Ok(chunks) => {
+                                    for chunk in chunks {
+                                        if tx.send(Ok(chunk)).await.is_err() {
+                                            return;
+                                        }
+                                    }
+                                }
+                                Err(e) => {
+                                    let _ = tx.send(Err(e)).await;
+                                    return;
+                                }
+                            }
+
+                            if state.finished {
+                                return;
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(anyhow::Error::from(e))).await;
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+
+    async fn embeddings(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+        let body = json!({
+            "model": request.model,
+            "input": request.input,
+        });
+        let response_text = self.send("/api/embed", &body).await?.text().await?;
+        if debug_enabled() {
+            debug_log(&format!("Raw embedding response payload: {}", response_text));
+        }
+
+        let response: Value = serde_json::from_str(&response_text)?;
+        from_embed_response(&request.model, &response)
+    }
+}
+
+/// Translates an OpenAI-style request into an Ollama chat request body
+fn to_chat_request(request: &ChatCompletionRequest, stream: bool) -> Value {
+    let messages: Vec<Value> = request
+        .messages
+        .iter()
+        .filter_map(|message| match message {
+            AgentMessage::Developer { content, .. } => {
+                Some(json!({ "role": "system", "content": content }))
+            }
+            AgentMessage::User { content, .. } => {
+                Some(json!({ "role": "user", "content": content }))
+            }
+            AgentMessage::Assistant {
+                content,
+                tool_calls,
+                ..
+            } => {
+                let mut message = json!({
+                    "role": "assistant",
+                    "content": content.clone().unwrap_or_default(),
+                });
+                if let Some(tool_calls) = tool_calls.as_ref().filter(|calls| !calls.is_empty()) {
+                    // Ollama takes tool arguments as an object rather than a JSON string
+                    message["tool_calls"] = tool_calls
+                        .iter()
+                        .map(|tool_call| {
+                            json!({
+                                "function": {
+                                    "name": tool_call.function.name,
+                                    "arguments": serde_json::from_str::<Value>(&tool_call.function.arguments)
+                                        .unwrap_or_else(|_| json!({})),
+                                }
+                            })
+                        })
+                        .collect();
+                }
+                Some(message)
+            }
+            AgentMessage::Tool { content, name, .. } => {
+                let mut message = json!({ "role": "tool", "content": content });
+                if let Some(name) = name {
+                    message["tool_name"] = json!(name);
+                }
+                Some(message)
+            }
+            AgentMessage::Done => None,
+        })
+        .collect();
+
+    let mut body = Map::new();
+    body.insert("model".to_string(), json!(request.model));
+    body.insert("messages".to_string(), json!(messages));
+    body.insert("stream".to_string(), json!(stream));
+
+    if let Some(tools) = request.tools.as_ref().filter(|tools| !tools.is_empty()) {
+        body.insert("tools".to_string(), json!(tools));
+    }
+
+    if let Some(format) = &request.response_format {
+        match (&format.json_schema, format.type_.as_str()) {
+            (Some(schema), "json_schema") => {
+                body.insert(
+                    "format".to_string(),
+                    schema.get("schema").cloned().unwrap_or_else(|| schema.clone()),
+                );
+            }
+            (_, "json_object") | (_, "json_schema") => {
+                body.insert("format".to_string(), json!("json"));
+            }
+            _ => {}
+        }
+    }
+
+    let mut options = Map::new();
+    if let Some(temperature) = request.temperature {
+        options.insert("temperature".to_string(), json!(temperature));
+    }
+    if let Some(top_p) = request.top_p {
+        options.insert("top_p".to_string(), json!(top_p));
+    }
+    if let Some(stop) = &request.stop {
+        options.insert("stop".to_string(), json!(stop));
+    }
+    if let Some(seed) = request.seed {
+        options.insert("seed".to_string(), json!(seed));
+    }
+    if let Some(max_tokens) = request.max_completion_tokens {
+        options.insert("num_predict".to_string(), json!(max_tokens));
+    }
+    if !options.is_empty() {
+        body.insert("options".to_string(), Value::Object(options));
+    }
+
+    Value::Object(body)
+}
+
+fn unix_timestamp() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+// Ollama doesn't id its tool calls, so ids are minted per response
+fn tool_call_id(response_id: &str, index: usize) -> String {
+    format!("call_{}_{}", response_id, index)
+}
+
+fn response_id() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+    format!("ollama-{:x}", nanos)
+}
+
+fn tool_calls_from(message: &Value, response_id: &str, offset: usize) -> Vec<ToolCall> {
+    message["tool_calls"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .map(|(index, tool_call)| ToolCall {
+            id: tool_call_id(response_id, offset + index),
+            call_type: "function".to_string(),
+            function: FunctionCall {
+                name: tool_call["function"]["name"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                arguments: match &tool_call["function"]["arguments"] {
+                    Value::String(arguments) => arguments.clone(),
+                    arguments => arguments.to_string(),
+                },
+            },
+            code_interpreter: None,
+            retrieval: None,
+        })
+        .collect()
+}
+
+fn finish_reason(response: &Value, has_tool_calls: bool) -> String {
+    if has_tool_calls {
+        "tool_calls".to_string()
+    } else if response["done_reason"] == "length" {
+        "length".to_string()
+    } else {
+        "stop".to_string()
+    }
+}
+
+fn usage_from(response: &Value) -> Usage {
+    let prompt_tokens = response["prompt_eval_count"].as_i64().unwrap_or(0) as i32;
+    let completion_tokens = response["eval_count"].as_i64().unwrap_or(0) as i32;
+    Usage {
+        prompt_tokens,
+        completion_tokens,
+        total_tokens: prompt_tokens + completion_tokens,
+        completion_tokens_details: None,
+    }
+}
+
+/// Translates an Ollama chat response into an OpenAI-style response
+fn from_chat_response(response: &Value) -> Result<ChatCompletionResponse> {
+    if let Some(error) = response["error"].as_str() {
+        return Err(anyhow!("Ollama error: {}", error));
+    }
+
+    let id = response_id();
+    let message = &response["message"];
+    let tool_calls = tool_calls_from(message, &id, 0);
+    let content = message["content"].as_str().unwrap_or_default().to_string();
+    let finish_reason = finish_reason(response, !tool_calls.is_empty());
+
+    Ok(ChatCompletionResponse {
+        id,
+        object: "chat.completion".to_string(),
+        created: unix_timestamp(),
+        model: response["model"].as_str().unwrap_or_default().to_string(),
+        system_fingerprint: None,
+        choices: vec![Choice {
+            index: 0,
+            message: AgentMessage::assistant(
+                None,
+                (!content.is_empty()).then_some(content),
+                (!tool_calls.is_empty()).then_some(tool_calls),
+                MessageProgress::Complete,
+                None,
+                None,
+            ),
+            delta: None,
+            logprobs: None,
+            finish_reason: Some(finish_reason),
+        }],
+        service_tier: None,
+        usage: usage_from(response),
+    })
+}
+
+/// Turns Ollama stream lines into OpenAI-style chunks
+///
+/// Ollama sends each tool call whole, so every call becomes a single delta
+/// carrying its id, name and complete arguments.
+/// Converts an `/api/embed` response to the OpenAI shape
+fn from_embed_response(model: &str, response: &Value) -> Result<EmbeddingResponse> {
+    let embeddings: Vec<Vec<f32>> = serde_json::from_value(
+        response
+            .get("embeddings")
+            .cloned()
+            .ok_or_else(|| anyhow!("Ollama embedding response has no embeddings"))?,
+    )?;
+    let prompt_tokens = response
+        .get("prompt_eval_count")
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as u32;
+
+    Ok(EmbeddingResponse {
+        object: "list".to_string(),
+        data: embeddings
+            .into_iter()
+            .enumerate()
+            .map(|(index, embedding)| EmbeddingData {
+                object: "embedding".to_string(),
+                index,
+                embedding,
+            })
+            .collect(),
+        model: model.to_string(),
+        usage: EmbeddingUsage {
+            prompt_tokens,
+            total_tokens: prompt_tokens,
+        },
+    })
+}
+
+struct OllamaStreamState {
+    id: String,
+    created: i64,
+    started: bool,
+    tool_call_count: usize,
+    finished: bool,
+}
+
+impl OllamaStreamState {
+    fn new() -> Self {
+        Self {
+            id: response_id(),
+            created: unix_timestamp(),
+            started: false,
+            tool_call_count: 0,
+            finished: false,
+        }
+    }
+
+    fn handle(&mut self, line: &str) -> Result<Vec<ChatCompletionChunk>> {
+        let event: Value = serde_json::from_str(line)?;
+        if let Some(error) = event["error"].as_str() {
+            return Err(anyhow!("Ollama stream error: {}", error));
+        }
+
+        let model = event["model"].as_str().unwrap_or_default().to_string();
+        let message = &event["message"];
+        let mut chunks = Vec::new();
+
+        let content = message["content"].as_str().filter(|content| !content.is_empty());
+        if content.is_some() || !self.started {
+            chunks.push(self.chunk(
+                &model,
+                Delta {
+                    role: (!self.started).then(|| "assistant".to_string()),
+                    content: content.map(str::to_string),
+                    function_call: None,
+                    tool_calls: None,
+                },
+                None,
+            ));
+            self.started = true;
+        }
+
+        let tool_calls = tool_calls_from(message, &self.id, self.tool_call_count);
+        self.tool_call_count += tool_calls.len();
+        for tool_call in tool_calls {
+            chunks.push(self.chunk(
+                &model,
+                Delta {
+                    role: None,
+                    content: None,
+                    function_call: None,
+                    tool_calls: Some(vec![DeltaToolCall {
+                        id: Some(tool_call.id),
+                        call_type: Some(tool_call.call_type),
+                        function: Some(DeltaFunctionCall {
+                            name: Some(tool_call.function.name),
+                            arguments: Some(tool_call.function.arguments),
+                        }),
+                        code_interpreter: None,
+                        retrieval: None,
+                    }]),
+                },
+                None,
+            ));
+        }
+
+        if event["done"].as_bool().unwrap_or(false) {
+            self.finished = true;
+            let mut chunk = self.chunk(
+                &model,
+                Delta {
+                    role: None,
+                    content: None,
+                    function_call: None,
+                    tool_calls: None,
+                },
+                Some(finish_reason(&event, self.tool_call_count > 0)),
+            );
+            chunk.usage = Some(usage_from(&event));
+            chunks.push(chunk);
+        }
+
+        Ok(chunks)
+    }
+
+    fn chunk(&self, model: &str, delta: Delta, finish_reason: Option<String>) -> ChatCompletionChunk {
+        ChatCompletionChunk {
+            id: self.id.clone(),
+            object: "chat.completion.chunk".to_string(),
+            created: self.created,
+            model: model.to_string(),
+            system_fingerprint: None,
+            choices: vec![StreamChoice {
+                index: 0,
+                delta,
+                logprobs: None,
+                finish_reason,
+            }],
+            usage: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_chat_request() {
+        let request = ChatCompletionRequest {
+            model: "llama3.1".to_string(),
+            messages: vec![
+                AgentMessage::developer("Be brief."),
+                AgentMessage::assistant(
+                    None,
+                    None,
+                    Some(vec![ToolCall {
+                        id: "call_1".to_string(),
+                        call_type: "function".to_string(),
+                        function: FunctionCall {
+                            name: "run_sql".to_string(),
+                            arguments: "{\"sql\":\"select 1\"}".to_string(),
+                        },
+                        code_interpreter: None,
+                        retrieval: None,
+                    }]),
+                    MessageProgress::Complete,
+                    None,
+                    None,
+                ),
+                AgentMessage::tool(
+                    None,
+                    "1",
+                    "call_1",
+                    Some("run_sql".to_string()),
+                    MessageProgress::Complete,
+                ),
+            ],
+            temperature: Some(0.0),
+            response_format: Some(ResponseFormat {
+                type_: "json_object".to_string(),
+                json_schema: None,
+            }),
+            ..Default::default()
+        };
+
+        let body = to_chat_request(&request, false);
+
+        assert_eq!(body["stream"], false);
+        assert_eq!(body["format"], "json");
+        assert_eq!(body["options"]["temperature"], 0.0);
+        assert_eq!(body["messages"][0]["role"], "system");
+        assert_eq!(body["messages"][1]["tool_calls"][0]["function"]["arguments"]["sql"], "select 1");
+        assert_eq!(body["messages"][2]["role"], "tool");
+        assert_eq!(body["messages"][2]["tool_name"], "run_sql");
+        assert!(body.get("tools").is_none());
+    }
+
+    #[test]
+    fn test_from_chat_response() {
+        let response = json!({
+            "model": "llama3.1",
+            "message": {
+                "role": "assistant",
+                "content": "",
+                "tool_calls": [{ "function": { "name": "run_sql", "arguments": { "sql": "select 1" } } }]
+            },
+            "done": true,
+            "prompt_eval_count": 12,
+            "eval_count": 3
+        });
+
+        let response = from_chat_response(&response).unwrap();
+
+        assert_eq!(response.choices[0].finish_reason.as_deref(), Some("tool_calls"));
+        assert_eq!(response.usage.total_tokens, 15);
+        match &response.choices[0].message {
+            AgentMessage::Assistant {
+                content: None,
+                tool_calls: Some(tool_calls),
+                ..
+            } => {
+                assert_eq!(tool_calls[0].function.name, "run_sql");
+                assert_eq!(tool_calls[0].function.arguments, "{\"sql\":\"select 1\"}");
+                assert!(tool_calls[0].id.starts_with("call_"));
+            }
+            other => panic!("Unexpected message: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_from_embed_response() {
+        let response = json!({
+            "model": "nomic-embed-text",
+            "embeddings": [[0.1, 0.2], [0.3, 0.4]],
+            "prompt_eval_count": 6
+        });
+
+        let response = from_embed_response("nomic-embed-text", &response).unwrap();
+        assert_eq!(response.data.len(), 2);
+        assert_eq!(response.data[1].index, 1);
+        assert_eq!(response.data[1].embedding, vec![0.3, 0.4]);
+        assert_eq!(response.usage.prompt_tokens, 6);
+
+        assert!(from_embed_response("nomic-embed-text", &json!({"error": "boom"})).is_err());
+    }
+
+    #[test]
+    fn test_stream_lines_map_to_chunks() {
+        let mut state = OllamaStreamState::new();
+
+        let first = state
+            .handle(r#"{"model":"llama3.1","message":{"role":"assistant","content":"Hel"},"done":false}"#)
+            .unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].choices[0].delta.role.as_deref(), Some("assistant"));
+        assert_eq!(first[0].choices[0].delta.content.as_deref(), Some("Hel"));
+
+        let tools = state
+            .handle(r#"{"model":"llama3.1","message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"run_sql","arguments":{"sql":"select 1"}}}]},"done":false}"#)
+            .unwrap();
+        assert_eq!(tools.len(), 1);
+        let tool_call = &tools[0].choices[0].delta.tool_calls.as_ref().unwrap()[0];
+        assert!(tool_call.id.is_some());
+        assert_eq!(
+            tool_call.function.as_ref().unwrap().arguments.as_deref(),
+            Some("{\"sql\":\"select 1\"}")
+        );
+
+        let done = state
+            .handle(r#"{"model":"llama3.1","message":{"role":"assistant","content":""},"done":true,"done_reason":"stop"}"#)
+            .unwrap();
+        assert_eq!(done.len(), 1);
+        assert_eq!(done[0].choices[0].finish_reason.as_deref(), Some("tool_calls"));
+        assert!(state.finished);
+    }
+}
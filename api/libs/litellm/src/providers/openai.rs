@@ -0,0 +1,269 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use reqwest::{header, Client};
+use tokio::sync::mpsc;
+
+use super::Provider;
+use crate::client::{debug_enabled, debug_log};
+use crate::retry::ProviderError;
+use crate::types::*;
+
+/// Provider for OpenAI-compatible `/chat/completions` endpoints
+///
+/// Used both for the LLM gateway (a LiteLLM proxy) and for talking to OpenAI
+/// directly.
+#[derive(Clone)]
+pub struct OpenAiProvider {
+    client: Client,
+    base_url: String,
+}
+
+impl OpenAiProvider {
+    pub fn new(api_key: Option<String>, base_url: String) -> Self {
+        let mut headers = header::HeaderMap::new();
+        if let Some(api_key) = api_key {
+            headers.insert(
+                "Authorization",
+                header::HeaderValue::from_str(&format!("Bearer {}", api_key)).unwrap(),
+            );
+        }
+        headers.insert(
+            "Content-Type",
+            header::HeaderValue::from_static("application/json"),
+        );
+        headers.insert(
+            "Accept",
+            header::HeaderValue::from_static("application/json"),
+        );
+
+        let client = Client::builder()
+            .default_headers(headers)
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self { client, base_url }
+    }
+}
+
+#[async_trait]
+impl Provider for OpenAiProvider {
+    async fn embeddings(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+        let url = format!("{}/embeddings", self.base_url);
+
+        debug_log(&format!("Sending embedding request to URL: {}", url));
+        if debug_enabled() {
+            debug_log(&format!(
+                "Embedding request payload: {}",
+                serde_json::to_string_pretty(&request).unwrap()
+            ));
+        }
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(ProviderError::from_response(response).await.into());
+        }
+
+        let response_text = response.text().await?;
+        if debug_enabled() {
+            debug_log(&format!("Raw embedding response payload: {}", response_text));
+        }
+
+        serde_json::from_str(&response_text).map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to deserialize embedding response: {}. Response text: {}",
+                e,
+                response_text
+            )
+        })
+    }
+
+    async fn chat_completion(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<ChatCompletionResponse> {
+        let url = format!("{}/chat/completions", self.base_url);
+
+        debug_log(&format!("Sending chat completion request to URL: {}", url));
+        if debug_enabled() {
+            debug_log(&format!(
+                "Request payload: {}",
+                serde_json::to_string_pretty(&request).unwrap()
+            ));
+        }
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(ProviderError::from_response(response).await.into());
+        }
+
+        // Get the raw response text
+        let response_text = response.text().await?;
+        if debug_enabled() {
+            debug_log(&format!("Raw response payload: {}", response_text));
+        }
+
+        // Parse the response text into the expected type
+        let response: ChatCompletionResponse = serde_json::from_str(&response_text)?;
+
+        // Log tool calls if present and debug is enabled
+        if debug_enabled() {
+            if let Some(AgentMessage::Assistant {
+                tool_calls: Some(tool_calls),
+                ..
+            }) = response.choices.first().map(|c| &c.message)
+            {
+                debug_log("Tool calls in response:");
+                for tool_call in tool_calls {
+                    debug_log(&format!("Tool Call ID: {}", tool_call.id));
+                    debug_log(&format!("Tool Name: {}", tool_call.function.name));
+                    debug_log(&format!("Tool Arguments: {}", tool_call.function.arguments));
+                }
+            }
+
+            debug_log(&format!(
+                "Received chat completion response: {}",
+                serde_json::to_string_pretty(&response).unwrap()
+            ));
+        }
+
+        Ok(response)
+    }
+
+    async fn stream_chat_completion(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<mpsc::Receiver<Result<ChatCompletionChunk>>> {
+        let url = format!("{}/chat/completions", self.base_url);
+
+        debug_log(&format!(
+            "Starting stream chat completion request to URL: {}",
+            url
+        ));
+        if debug_enabled() {
+            debug_log(&format!(
+                "Stream request payload: {}",
+                serde_json::to_string_pretty(&request).unwrap()
+            ));
+        }
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&ChatCompletionRequest {
+                stream: Some(true),
+                ..request
+            })
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(ProviderError::from_response(response).await.into());
+        }
+
+        let mut stream = response.bytes_stream();
+
+        let (tx, rx) = mpsc::channel(100);
+        let debug_enabled = debug_enabled(); // Capture for the async block
+
+        tokio::spawn(async move {
+            let mut buffer = String::new();
+            if debug_enabled {
+                debug_log("Stream processing started");
+            }
+
+            while let Some(chunk_result) = stream.next().await {
+                match chunk_result {
+                    Ok(chunk) => {
+                        let chunk_str = String::from_utf8_lossy(&chunk);
+                        if debug_enabled {
+                            debug_log(&format!("Raw response payload: {}", chunk_str));
+                        }
+                        buffer.push_str(&chunk_str);
+
+                        while let Some(pos) = buffer.find("\n\n") {
+                            let line = buffer[..pos].trim().to_string();
+                            buffer = buffer[pos + 2..].to_string();
+
+                            if let Some(data) = line.strip_prefix("data: ") {
+                                if debug_enabled {
+                                    debug_log(&format!("Processing stream data: {}", data));
+                                }
+                                if data == "[DONE]" {
+                                    if debug_enabled {
+                                        debug_log("Stream completed with [DONE] signal");
+                                    }
+                                    break;
+                                }
+
+                                if let Ok(response) =
+                                    serde_json::from_str::<ChatCompletionChunk>(data)
+                                {
+                                    // Log tool calls if present and debug is enabled
+                                    if debug_enabled {
+                                        if let Some(tool_calls) = &response.choices[0].delta.tool_calls
+                                        {
+                                            debug_log("Tool calls in stream chunk:");
+                                            for tool_call in tool_calls {
+                                                if let (Some(id), Some(function)) =
+                                                    (tool_call.id.clone(), tool_call.function.clone())
+                                                {
+                                                    debug_log(&format!("Tool Call ID: {}", id));
+                                                    if let Some(name) = function.name {
+                                                        debug_log(&format!("Tool Name: {}", name));
+                                                    }
+                                                    if let Some(arguments) = function.arguments {
+                                                        debug_log(&format!(
+                                                            "Tool Arguments: {}",
+                                                            arguments
+                                                        ));
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        debug_log(&format!("Parsed stream chunk: {:?}", response));
+                                    }
+                                    
+                                    // Use try_send instead of send to avoid blocking
+                                    if tx.try_send(Ok(response)).is_err() {
+                                        // If the channel is full, log it but continue processing
+                                        if debug_enabled {
+                                            debug_log("Warning: Channel full, receiver not keeping up");
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        if debug_enabled {
+                            debug_log(&format!("Error in stream processing: {:?}", e));
+                        }
+                        // Use try_send to avoid blocking
+                        let _ = tx.try_send(Err(anyhow::Error::from(e)));
+                    }
+                }
+            }
+            if debug_enabled {
+                debug_log("Stream processing completed");
+            }
+        });
+
+        if debug_enabled {
+            debug_log("Returning stream receiver");
+        }
+        Ok(rx)
+    }
+}
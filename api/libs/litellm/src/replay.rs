@@ -0,0 +1,432 @@
+//! Record/replay of LLM calls for tests that run without network access.
+//!
+//! With `LLM_REPLAY_MODE=record`, every chat completion, stream and
+//! embedding request made through [`crate::LiteLLMClient`] is sent as usual
+//! and its response saved under `LLM_FIXTURES_DIR`. With `replay`, responses
+//! are read back from those files and no request leaves the process; `auto`
+//! replays what's there and records the rest.
+//!
+//! Fixtures are keyed by a hash of the request. Values that change on every
+//! run (UUIDs, timestamps and dates, the caller's ids in `metadata`) are
+//! masked before hashing so a recording keeps matching the same logical
+//! request. Identical requests replay the same response.
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tokio::sync::mpsc;
+
+use crate::types::{
+    ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse, EmbeddingRequest,
+    EmbeddingResponse,
+};
+
+/// Directory fixtures are read from and written to when `LLM_FIXTURES_DIR`
+/// isn't set
+pub const DEFAULT_FIXTURES_DIR: &str = "tests/fixtures/llm";
+
+static VOLATILE_VALUES: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}",
+        r"|\d{4}-\d{2}-\d{2}(?:[T ]\d{2}:\d{2}(?::\d{2}(?:\.\d+)?)?(?:Z|[+-]\d{2}:?\d{2})?)?",
+    ))
+    .expect("Invalid volatile value pattern")
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayMode {
+    /// Send requests and save their responses
+    Record,
+    /// Answer from saved responses only, failing on a miss
+    Replay,
+    /// Answer from saved responses, sending and saving on a miss
+    Auto,
+}
+
+impl ReplayMode {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "record" => Some(ReplayMode::Record),
+            "replay" => Some(ReplayMode::Replay),
+            "auto" => Some(ReplayMode::Auto),
+            _ => None,
+        }
+    }
+}
+
+/// A saved response, along with the request that produced it for readability
+#[derive(Debug, Serialize, Deserialize)]
+struct Fixture {
+    request: Value,
+    #[serde(flatten)]
+    recording: Recording,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Recording {
+    ChatCompletion { response: ChatCompletionResponse },
+    Stream { chunks: Vec<ChatCompletionChunk> },
+    Embeddings { response: EmbeddingResponse },
+}
+
+impl Recording {
+    fn kind(&self) -> &'static str {
+        match self {
+            Recording::ChatCompletion { .. } => "chat_completion",
+            Recording::Stream { .. } => "stream",
+            Recording::Embeddings { .. } => "embeddings",
+        }
+    }
+}
+
+/// Where and how LLM calls are recorded or replayed
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    dir: PathBuf,
+    mode: ReplayMode,
+}
+
+impl Cassette {
+    pub fn new(dir: impl Into<PathBuf>, mode: ReplayMode) -> Self {
+        Self {
+            dir: dir.into(),
+            mode,
+        }
+    }
+
+    /// The cassette configured by `LLM_REPLAY_MODE` and `LLM_FIXTURES_DIR`,
+    /// or `None` when calls should go to the provider as usual
+    pub fn from_env() -> Option<Self> {
+        let mode = env::var("LLM_REPLAY_MODE").ok()?;
+        let mode = match ReplayMode::parse(&mode) {
+            Some(mode) => mode,
+            None => {
+                tracing::warn!("Ignoring unknown LLM_REPLAY_MODE '{}'", mode);
+                return None;
+            }
+        };
+        let dir = env::var("LLM_FIXTURES_DIR").unwrap_or_else(|_| DEFAULT_FIXTURES_DIR.to_string());
+
+        Some(Self::new(dir, mode))
+    }
+
+    pub fn mode(&self) -> ReplayMode {
+        self.mode
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Whether a saved response should be used instead of calling the provider
+    fn replays(&self) -> bool {
+        matches!(self.mode, ReplayMode::Replay | ReplayMode::Auto)
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    fn load(&self, key: &str) -> Result<Option<Recording>> {
+        let path = self.path(key);
+        if !path.exists() {
+            if self.mode == ReplayMode::Replay {
+                return Err(anyhow!(
+                    "No recorded LLM response {} in {}; run with LLM_REPLAY_MODE=record to capture it",
+                    key,
+                    self.dir.display()
+                ));
+            }
+            return Ok(None);
+        }
+
+        let json = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read LLM fixture {}", path.display()))?;
+        let fixture: Fixture = serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse LLM fixture {}", path.display()))?;
+        Ok(Some(fixture.recording))
+    }
+
+    fn save(&self, key: &str, request: Value, recording: Recording) -> Result<()> {
+        std::fs::create_dir_all(&self.dir).with_context(|| {
+            format!("Failed to create fixture directory {}", self.dir.display())
+        })?;
+
+        let path = self.path(key);
+        // Written aside and renamed so concurrent tests never read half a file
+        let partial = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        let fixture = Fixture { request, recording };
+        std::fs::write(&partial, serde_json::to_string_pretty(&fixture)?)?;
+        std::fs::rename(&partial, &path)?;
+        Ok(())
+    }
+
+    fn save_or_warn(&self, key: &str, request: Value, recording: Recording) {
+        if let Err(e) = self.save(key, request, recording) {
+            tracing::warn!("Failed to record LLM fixture {}: {}", key, e);
+        }
+    }
+
+    pub(crate) fn load_chat_completion(
+        &self,
+        request: &ChatCompletionRequest,
+    ) -> Result<Option<ChatCompletionResponse>> {
+        if !self.replays() {
+            return Ok(None);
+        }
+        match self.load(&request_key("chat_completion", request)?)? {
+            Some(Recording::ChatCompletion { response }) => Ok(Some(response)),
+            Some(other) => Err(wrong_kind("chat_completion", &other)),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) fn record_chat_completion(
+        &self,
+        request: &ChatCompletionRequest,
+        response: &ChatCompletionResponse,
+    ) -> Result<()> {
+        let key = request_key("chat_completion", request)?;
+        self.save_or_warn(
+            &key,
+            serde_json::to_value(request)?,
+            Recording::ChatCompletion {
+                response: response.clone(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Replays a recorded stream through a channel, as a provider would
+    pub(crate) fn load_stream(
+        &self,
+        request: &ChatCompletionRequest,
+    ) -> Result<Option<mpsc::Receiver<Result<ChatCompletionChunk>>>> {
+        if !self.replays() {
+            return Ok(None);
+        }
+        let chunks = match self.load(&request_key("stream", request)?)? {
+            Some(Recording::Stream { chunks }) => chunks,
+            Some(other) => return Err(wrong_kind("stream", &other)),
+            None => return Ok(None),
+        };
+
+        let (tx, rx) = mpsc::channel(chunks.len().max(1));
+        tokio::spawn(async move {
+            for chunk in chunks {
+                if tx.send(Ok(chunk)).await.is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Some(rx))
+    }
+
+    /// Passes a live stream through, saving it once it ends cleanly
+    ///
+    /// Streams that fail partway aren't saved, so a retry records the
+    /// complete response.
+    pub(crate) fn record_stream(
+        &self,
+        request: &ChatCompletionRequest,
+        mut rx: mpsc::Receiver<Result<ChatCompletionChunk>>,
+    ) -> Result<mpsc::Receiver<Result<ChatCompletionChunk>>> {
+        let key = request_key("stream", request)?;
+        let request = serde_json::to_value(request)?;
+        let cassette = self.clone();
+
+        let (tx, forwarded_rx) = mpsc::channel(100);
+        tokio::spawn(async move {
+            let mut chunks = Vec::new();
+            let mut failed = false;
+            while let Some(chunk) = rx.recv().await {
+                match &chunk {
+                    Ok(chunk) => chunks.push(chunk.clone()),
+                    Err(_) => failed = true,
+                }
+                if tx.send(chunk).await.is_err() {
+                    failed = true;
+                    break;
+                }
+            }
+            if !failed {
+                cassette.save_or_warn(&key, request, Recording::Stream { chunks });
+            }
+        });
+
+        Ok(forwarded_rx)
+    }
+
+    pub(crate) fn load_embeddings(
+        &self,
+        request: &EmbeddingRequest,
+    ) -> Result<Option<EmbeddingResponse>> {
+        if !self.replays() {
+            return Ok(None);
+        }
+        match self.load(&request_key("embeddings", request)?)? {
+            Some(Recording::Embeddings { response }) => Ok(Some(response)),
+            Some(other) => Err(wrong_kind("embeddings", &other)),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) fn record_embeddings(
+        &self,
+        request: &EmbeddingRequest,
+        response: &EmbeddingResponse,
+    ) -> Result<()> {
+        let key = request_key("embeddings", request)?;
+        self.save_or_warn(
+            &key,
+            serde_json::to_value(request)?,
+            Recording::Embeddings {
+                response: response.clone(),
+            },
+        );
+        Ok(())
+    }
+}
+
+fn wrong_kind(expected: &str, recording: &Recording) -> anyhow::Error {
+    anyhow!(
+        "Recorded LLM fixture is a {} but a {} was requested",
+        recording.kind(),
+        expected
+    )
+}
+
+/// The fixture key of a request: `<kind>-<hash>`
+///
+/// Caller identifiers are dropped and volatile values masked before hashing,
+/// so the key only changes when the substance of the request does.
+pub fn request_key(kind: &str, request: &impl Serialize) -> Result<String> {
+    let mut value = serde_json::to_value(request)?;
+    if let Some(object) = value.as_object_mut() {
+        object.remove("user");
+        if let Some(metadata) = object.get_mut("metadata").and_then(Value::as_object_mut) {
+            metadata.retain(|key, _| key == "generation_name");
+        }
+    }
+
+    let canonical = serde_json::to_string(&value)?;
+    let masked = VOLATILE_VALUES.replace_all(&canonical, "<volatile>");
+
+    let mut hasher = Sha256::new();
+    hasher.update(kind.as_bytes());
+    hasher.update(masked.as_bytes());
+    let hash = hasher.finalize();
+    let hex: String = hash
+        .iter()
+        .take(8)
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    Ok(format!("{}-{}", kind, hex))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AgentMessage, Metadata};
+
+    fn request(content: &str, session_id: &str) -> ChatCompletionRequest {
+        ChatCompletionRequest {
+            model: "gpt-4o".to_string(),
+            messages: vec![AgentMessage::user(content)],
+            metadata: Some(Metadata {
+                generation_name: "agent".to_string(),
+                user_id: "user".to_string(),
+                session_id: session_id.to_string(),
+                trace_id: session_id.to_string(),
+                message_id: None,
+                organization_id: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_request_key_ignores_volatile_values() {
+        let key = request_key(
+            "chat_completion",
+            &request(
+                "Metric 0b9e4e0c-5f7b-4a8e-9d64-1f0c2b7a3e11 as of 2025-05-01T10:00:00Z",
+                "a",
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(
+            key,
+            request_key(
+                "chat_completion",
+                &request(
+                    "Metric 7d1f5c2a-0e3b-4c9d-8a61-2b4e6f8a0c33 as of 2025-06-12T08:30:15Z",
+                    "b",
+                ),
+            )
+            .unwrap()
+        );
+        assert_ne!(
+            key,
+            request_key("stream", &request("Metric as of", "a")).unwrap()
+        );
+        assert_ne!(
+            key,
+            request_key("chat_completion", &request("Another question", "a")).unwrap()
+        );
+        assert!(key.starts_with("chat_completion-"));
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay_stream() {
+        let dir = std::env::temp_dir().join(format!("litellm-replay-{}", std::process::id()));
+        let request = request("Hello", "a");
+
+        let chunk: ChatCompletionChunk = serde_json::from_value(serde_json::json!({
+            "id": "chunk-1",
+            "object": "chat.completion.chunk",
+            "created": 1,
+            "model": "gpt-4o",
+            "choices": [{"index": 0, "delta": {"content": "Hi"}, "finish_reason": null}]
+        }))
+        .unwrap();
+
+        let recorder = Cassette::new(&dir, ReplayMode::Record);
+        let (tx, live) = mpsc::channel(4);
+        tx.send(Ok(chunk.clone())).await.unwrap();
+        drop(tx);
+        let mut forwarded = recorder.record_stream(&request, live).unwrap();
+        assert_eq!(forwarded.recv().await.unwrap().unwrap().id, "chunk-1");
+        assert!(forwarded.recv().await.is_none());
+
+        // The fixture is written after the stream ends
+        let key = request_key("stream", &request).unwrap();
+        for _ in 0..50 {
+            if recorder.path(&key).exists() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let player = Cassette::new(&dir, ReplayMode::Replay);
+        let mut replayed = player.load_stream(&request).unwrap().unwrap();
+        assert_eq!(replayed.recv().await.unwrap().unwrap().id, "chunk-1");
+        assert!(replayed.recv().await.is_none());
+
+        assert!(player
+            .load_stream(&super::tests::request("Unrecorded", "a"))
+            .is_err());
+        assert!(player.load_chat_completion(&request).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
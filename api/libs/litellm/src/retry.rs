@@ -0,0 +1,276 @@
+use std::env;
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+
+use anyhow::Result;
+use rand::Rng;
+use reqwest::header::HeaderMap;
+
+use crate::client::debug_log;
+
+// Longest `Retry-After` we are willing to wait before giving up on a provider
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// How failed LLM calls are retried
+///
+/// Retries use exponential backoff with full jitter, unless the provider asks
+/// for a specific delay through `Retry-After`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt; `0` disables retrying
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(20),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Reads `LLM_MAX_RETRIES`, `LLM_RETRY_BASE_DELAY_MS` and
+    /// `LLM_RETRY_MAX_DELAY_MS`, keeping the defaults for unset values
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let read = |name: &str| env::var(name).ok().and_then(|value| value.parse::<u64>().ok());
+
+        Self {
+            max_retries: read("LLM_MAX_RETRIES")
+                .map(|value| value as u32)
+                .unwrap_or(default.max_retries),
+            base_delay: read("LLM_RETRY_BASE_DELAY_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(default.base_delay),
+            max_delay: read("LLM_RETRY_MAX_DELAY_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(default.max_delay),
+        }
+    }
+
+    /// A policy that never retries
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// The jittered backoff before retry number `attempt` (starting at 1)
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(16);
+        let ceiling = self
+            .base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+        let millis = ceiling.as_millis() as u64;
+        if millis == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_millis(rand::thread_rng().gen_range(0..=millis))
+    }
+
+    /// The delay before retrying after `error`, honoring `Retry-After`
+    pub fn delay_for(&self, attempt: u32, error: &anyhow::Error) -> Duration {
+        retry_after(error)
+            .map(|delay| delay.min(MAX_RETRY_AFTER))
+            .unwrap_or_else(|| self.backoff(attempt))
+    }
+
+    /// Runs `operation` until it succeeds, fails with a non-retryable error, or
+    /// runs out of retries
+    pub async fn run<T, F, Fut>(&self, label: &str, mut operation: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match operation().await {
+                Ok(value) => return Ok(value),
+                Err(e) if attempt < self.max_retries && is_retryable(&e) => {
+                    attempt += 1;
+                    let delay = self.delay_for(attempt, &e);
+                    tracing::warn!(
+                        "{} failed (attempt {} of {}), retrying in {:?}: {}",
+                        label,
+                        attempt,
+                        self.max_retries + 1,
+                        delay,
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// A non-success HTTP response from an LLM provider
+#[derive(Debug, Clone)]
+pub struct ProviderError {
+    pub status: u16,
+    pub retry_after: Option<Duration>,
+    pub body: String,
+}
+
+impl ProviderError {
+    /// Consumes an error response, keeping its status, body and `Retry-After`
+    pub async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status().as_u16();
+        let retry_after = parse_retry_after(response.headers());
+        let body = response.text().await.unwrap_or_default();
+        debug_log(&format!("Provider error response (Status: {}): {}", status, body));
+
+        Self {
+            status,
+            retry_after,
+            body,
+        }
+    }
+
+    /// Rate limits, timeouts, overload and server errors are worth retrying
+    pub fn is_retryable(&self) -> bool {
+        matches!(self.status, 408 | 409 | 429) || self.status >= 500
+    }
+}
+
+impl fmt::Display for ProviderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "LLM request failed with status {}: {}",
+            self.status, self.body
+        )
+    }
+}
+
+impl std::error::Error for ProviderError {}
+
+/// Reads `retry-after-ms` or `retry-after` (in seconds) from response headers
+fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse::<f64>().ok();
+
+    header("retry-after-ms")
+        .map(|millis| Duration::from_secs_f64(millis.max(0.0) / 1000.0))
+        .or_else(|| header("retry-after").map(|secs| Duration::from_secs_f64(secs.max(0.0))))
+}
+
+/// Whether a failed LLM call may succeed if sent again
+///
+/// Covers retryable provider statuses and transport failures such as dropped
+/// connections and timeouts, including ones surfacing mid-stream.
+pub fn is_retryable(error: &anyhow::Error) -> bool {
+    if let Some(error) = error.downcast_ref::<ProviderError>() {
+        return error.is_retryable();
+    }
+    if let Some(error) = error.downcast_ref::<reqwest::Error>() {
+        return error.is_timeout() || error.is_connect() || error.is_request() || error.is_body();
+    }
+    false
+}
+
+/// The delay a provider asked for before retrying, if any
+pub fn retry_after(error: &anyhow::Error) -> Option<Duration> {
+    error
+        .downcast_ref::<ProviderError>()
+        .and_then(|error| error.retry_after)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_parse_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+
+        headers.insert("retry-after", HeaderValue::from_static("2"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(2)));
+
+        headers.insert("retry-after-ms", HeaderValue::from_static("250"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_millis(250)));
+
+        // HTTP dates aren't supported and fall back to backoff
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "retry-after",
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(parse_retry_after(&headers), None);
+    }
+
+    #[test]
+    fn test_backoff_is_bounded() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(300),
+        };
+
+        for attempt in 1..=10 {
+            let ceiling = Duration::from_millis(100 * (1 << (attempt - 1).min(16))).min(policy.max_delay);
+            assert!(policy.backoff(attempt) <= ceiling);
+        }
+    }
+
+    #[test]
+    fn test_retryable_errors() {
+        let error = |status| {
+            anyhow::Error::new(ProviderError {
+                status,
+                retry_after: Some(Duration::from_secs(1)),
+                body: String::new(),
+            })
+        };
+
+        assert!(is_retryable(&error(429)));
+        assert!(is_retryable(&error(503)));
+        assert!(is_retryable(&error(529)));
+        assert!(!is_retryable(&error(400)));
+        assert!(!is_retryable(&anyhow::anyhow!("bad json")));
+        assert_eq!(
+            RetryPolicy::default().delay_for(1, &error(429)),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_retries_until_success() {
+        let policy = RetryPolicy {
+            max_retries: 2,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        };
+        let mut calls = 0;
+
+        let result = policy
+            .run("test", || {
+                calls += 1;
+                let attempt = calls;
+                async move {
+                    if attempt < 3 {
+                        Err(anyhow::Error::new(ProviderError {
+                            status: 500,
+                            retry_after: None,
+                            body: String::new(),
+                        }))
+                    } else {
+                        Ok(attempt)
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 3);
+    }
+}
@@ -1,3 +1,4 @@
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -39,6 +40,8 @@ pub struct ChatCompletionRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<StreamOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub top_p: Option<f32>,
@@ -60,6 +63,18 @@ pub struct Metadata {
     pub user_id: String,
     pub session_id: String,
     pub trace_id: String,
+    /// The chat message this call is made for, used to attribute token usage
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    /// The organization billed for this call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StreamOptions {
+    /// Asks for a final chunk carrying the token usage of the whole stream
+    pub include_usage: bool,
 }
 
 impl Default for ChatCompletionRequest {
@@ -80,6 +95,7 @@ impl Default for ChatCompletionRequest {
             seed: None,
             stop: None,
             stream: None,
+            stream_options: None,
             temperature: None,
             top_p: None,
             tools: None,
@@ -433,6 +449,9 @@ pub struct ChatCompletionChunk {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub system_fingerprint: Option<String>,
     pub choices: Vec<StreamChoice>,
+    /// Only present on the final chunk, and only when usage was requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<Usage>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -473,6 +492,41 @@ pub struct EmbeddingRequest {
     // pub extra_params: Option<HashMap<String, Value>>,
 }
 
+/// Vector size of known embedding models, by name without a provider prefix
+const EMBEDDING_MODEL_DIMENSIONS: &[(&str, u32)] = &[
+    ("text-embedding-3-small", 1536),
+    ("text-embedding-3-large", 3072),
+    ("text-embedding-ada-002", 1536),
+    ("text-embedding-004", 768),
+    ("text-embedding-005", 768),
+    ("nomic-embed-text", 768),
+    ("mxbai-embed-large", 1024),
+    ("all-minilm", 384),
+    ("bge-m3", 1024),
+];
+
+/// Vector size of models missing from the table, unless `EMBEDDING_DIMENSIONS` is set
+pub const DEFAULT_EMBEDDING_DIMENSIONS: u32 = 1536;
+
+static CONFIGURED_EMBEDDING_DIMENSIONS: Lazy<Option<u32>> = Lazy::new(|| {
+    std::env::var("EMBEDDING_DIMENSIONS")
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+});
+
+/// The vector size `model` produces, e.g. for `EmbeddingRequest::dimensions`
+/// and the vector columns its embeddings are stored in
+pub fn embedding_dimensions(model: &str) -> u32 {
+    let name = model.rsplit('/').next().unwrap_or(model);
+    let name = name.split(':').next().unwrap_or(name);
+    EMBEDDING_MODEL_DIMENSIONS
+        .iter()
+        .find(|(known, _)| *known == name)
+        .map(|(_, dimensions)| *dimensions)
+        .or(*CONFIGURED_EMBEDDING_DIMENSIONS)
+        .unwrap_or(DEFAULT_EMBEDDING_DIMENSIONS)
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct EmbeddingResponse {
     pub object: String, // e.g., "list"
@@ -496,11 +550,36 @@ pub struct EmbeddingUsage {
 
 // --- Embedding Types End ---
 
+// --- Model List Types ---
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModelList {
+    pub object: String, // e.g., "list"
+    pub data: Vec<ModelInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModelInfo {
+    pub id: String,
+    pub object: String, // e.g., "model"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owned_by: Option<String>,
+}
+
+// --- Model List Types End ---
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn test_embedding_dimensions() {
+        assert_eq!(embedding_dimensions("text-embedding-3-small"), 1536);
+        assert_eq!(embedding_dimensions("openai/text-embedding-3-large"), 3072);
+        assert_eq!(embedding_dimensions("ollama/nomic-embed-text:latest"), 768);
+    }
+
     #[tokio::test]
     async fn test_chat_completion_request_serialization() {
         // Create the request body matching the curl example
@@ -834,6 +913,7 @@ mod tests {
                 logprobs: None,
                 finish_reason: None,
             }],
+            usage: None,
         };
 
         // Test content chunk
@@ -854,6 +934,7 @@ mod tests {
                 logprobs: None,
                 finish_reason: None,
             }],
+            usage: None,
         };
 
         // Test final chunk
@@ -874,6 +955,7 @@ mod tests {
                 logprobs: None,
                 finish_reason: Some("stop".to_string()),
             }],
+            usage: None,
         };
 
         // Test serialization/deserialization of all chunks
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use once_cell::sync::OnceCell;
+
+use crate::types::{ChatCompletionRequest, Usage};
+
+/// Token usage of a single LLM call
+#[derive(Debug, Clone)]
+pub struct UsageEvent {
+    /// The model as requested, including any provider prefix
+    pub model: String,
+    /// What the call was for, from `Metadata::generation_name`
+    pub generation_name: Option<String>,
+    pub user_id: Option<String>,
+    /// The organization billed, from `Metadata::organization_id`
+    pub organization_id: Option<String>,
+    /// The chat the call belongs to, from `Metadata::session_id`
+    pub session_id: Option<String>,
+    pub message_id: Option<String>,
+    pub prompt_tokens: i32,
+    pub completion_tokens: i32,
+    pub total_tokens: i32,
+}
+
+/// Receives the usage of every LLM call made through [`crate::LiteLLMClient`]
+///
+/// Called inline after each call, so implementations should hand the event
+/// off rather than doing slow work.
+pub trait UsageRecorder: Send + Sync {
+    fn record(&self, event: UsageEvent);
+}
+
+static RECORDER: OnceCell<Arc<dyn UsageRecorder>> = OnceCell::new();
+
+/// Installs the process-wide usage recorder
+///
+/// Returns false if one was already installed.
+pub fn set_usage_recorder(recorder: Arc<dyn UsageRecorder>) -> bool {
+    RECORDER.set(recorder).is_ok()
+}
+
+pub(crate) fn recorder_installed() -> bool {
+    RECORDER.get().is_some()
+}
+
+pub(crate) fn record_usage(model: &str, request: &ChatCompletionRequest, usage: &Usage) {
+    let Some(recorder) = RECORDER.get() else {
+        return;
+    };
+
+    let metadata = request.metadata.as_ref();
+    recorder.record(UsageEvent {
+        model: model.to_string(),
+        generation_name: metadata.map(|m| m.generation_name.clone()),
+        user_id: metadata.map(|m| m.user_id.clone()),
+        organization_id: metadata.and_then(|m| m.organization_id.clone()),
+        session_id: metadata.map(|m| m.session_id.clone()),
+        message_id: metadata.and_then(|m| m.message_id.clone()),
+        prompt_tokens: usage.prompt_tokens,
+        completion_tokens: usage.completion_tokens,
+        total_tokens: usage.total_tokens,
+    });
+}
+
+pub(crate) fn record_embedding_usage(model: &str, user: Option<&str>, prompt_tokens: u32) {
+    let Some(recorder) = RECORDER.get() else {
+        return;
+    };
+
+    recorder.record(UsageEvent {
+        model: model.to_string(),
+        generation_name: Some("embeddings".to_string()),
+        user_id: user.map(str::to_string),
+        organization_id: None,
+        session_id: None,
+        message_id: None,
+        prompt_tokens: prompt_tokens as i32,
+        completion_tokens: 0,
+        total_tokens: prompt_tokens as i32,
+    });
+}
@@ -176,7 +176,9 @@ async fn authorize_current_user(token: &str) -> Result<Option<AuthenticatedUser>
     Ok(user)
 }
 
-async fn find_user_by_id(id: &Uuid) -> Result<Option<AuthenticatedUser>> {
+/// Loads a user with their organization and team memberships, as the auth
+/// middleware does for a request
+pub async fn find_user_by_id(id: &Uuid) -> Result<Option<AuthenticatedUser>> {
     let pg_pool = get_pg_pool();
     let id = *id; // Clone the UUID for move into tasks
 
@@ -9,7 +9,7 @@ pub mod types;
 pub mod error;
 
 // Re-export commonly used types
-pub use auth::auth;
+pub use auth::{auth, find_user_by_id};
 pub use cors::cors;
 pub use error::{
     sentry_layer, 
@@ -0,0 +1,62 @@
+//! Builders for the Block Kit blocks the bot posts
+
+use serde_json::{json, Value};
+
+/// Slack rejects section text longer than this
+pub const MAX_SECTION_TEXT: usize = 3000;
+
+/// A section of `mrkdwn` text, cut to fit Slack's limit
+pub fn section(text: &str) -> Value {
+    json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": truncate(text, MAX_SECTION_TEXT) }
+    })
+}
+
+/// Small grey `mrkdwn` text
+pub fn context(text: &str) -> Value {
+    json!({
+        "type": "context",
+        "elements": [{ "type": "mrkdwn", "text": truncate(text, MAX_SECTION_TEXT) }]
+    })
+}
+
+pub fn divider() -> Value {
+    json!({ "type": "divider" })
+}
+
+/// A button opening a URL
+pub fn link_button(text: &str, url: &str) -> Value {
+    json!({
+        "type": "actions",
+        "elements": [{
+            "type": "button",
+            "text": { "type": "plain_text", "text": text },
+            "url": url
+        }]
+    })
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_section_truncates() {
+        let block = section(&"a".repeat(MAX_SECTION_TEXT + 10));
+        let text = block["text"]["text"].as_str().unwrap();
+        assert_eq!(text.chars().count(), MAX_SECTION_TEXT);
+        assert!(text.ends_with('…'));
+
+        assert_eq!(section("*hi*")["text"]["text"], "*hi*");
+    }
+}
@@ -0,0 +1,242 @@
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::env;
+
+use crate::types::{AuthIdentity, PostedMessage, SlackMessage};
+use crate::{API_BASE, SLACK_API_URL_ENV};
+
+/// Client for a workspace's Slack Web API, authenticated with its bot token
+#[derive(Clone)]
+pub struct SlackClient {
+    token: String,
+    base_url: String,
+    client: Client,
+}
+
+#[derive(Deserialize)]
+struct UserInfo {
+    user: SlackUser,
+}
+
+#[derive(Deserialize)]
+struct SlackUser {
+    #[serde(default)]
+    deleted: bool,
+    #[serde(default)]
+    is_bot: bool,
+    #[serde(default)]
+    profile: SlackUserProfile,
+}
+
+#[derive(Deserialize, Default)]
+struct SlackUserProfile {
+    email: Option<String>,
+}
+
+impl SlackClient {
+    /// Create a client for a bot token
+    ///
+    /// Calls go to `SLACK_API_URL` when it is set, and to Slack otherwise.
+    pub fn new(token: &str) -> Self {
+        let base_url = env::var(SLACK_API_URL_ENV).unwrap_or_else(|_| API_BASE.to_string());
+        Self::with_base_url(token, &base_url)
+    }
+
+    /// Create a client calling the Web API at `base_url`
+    pub fn with_base_url(token: &str, base_url: &str) -> Self {
+        Self {
+            token: token.to_string(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client: Client::new(),
+        }
+    }
+
+    /// The workspace and bot user the token belongs to
+    pub async fn auth_test(&self) -> Result<AuthIdentity> {
+        self.post("auth.test", &json!({})).await
+    }
+
+    /// Post a message, returning where it landed
+    pub async fn post_message(&self, message: &SlackMessage) -> Result<PostedMessage> {
+        self.post("chat.postMessage", message).await
+    }
+
+    /// Replace the text and blocks of a message the bot posted
+    pub async fn update_message(
+        &self,
+        posted: &PostedMessage,
+        message: &SlackMessage,
+    ) -> Result<()> {
+        let mut body = serde_json::to_value(message)?;
+        body["channel"] = json!(posted.channel);
+        body["ts"] = json!(posted.ts);
+        self.post::<Value>("chat.update", &body).await?;
+        Ok(())
+    }
+
+    /// Email of an active, human Slack user
+    ///
+    /// Requires the `users:read.email` scope. Returns None for bots,
+    /// deactivated users and users without a visible email.
+    pub async fn user_email(&self, user_id: &str) -> Result<Option<String>> {
+        let response = self
+            .client
+            .get(format!("{}/users.info", self.base_url))
+            .bearer_auth(&self.token)
+            .query(&[("user", user_id)])
+            .send()
+            .await
+            .map_err(|e| anyhow!("Error calling Slack users.info: {}", e))?;
+        let info: UserInfo = Self::parse("users.info", response).await?;
+
+        if info.user.deleted || info.user.is_bot {
+            return Ok(None);
+        }
+        Ok(info.user.profile.email)
+    }
+
+    async fn post<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        body: &impl serde::Serialize,
+    ) -> Result<T> {
+        let response = self
+            .client
+            .post(format!("{}/{}", self.base_url, method))
+            .bearer_auth(&self.token)
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| anyhow!("Error calling Slack {}: {}", method, e))?;
+        Self::parse(method, response).await
+    }
+
+    /// Slack answers most failures with HTTP 200 and `"ok": false`
+    async fn parse<T: DeserializeOwned>(method: &str, response: reqwest::Response) -> Result<T> {
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow!("Slack {} failed: HTTP {}", method, status));
+        }
+
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Invalid response from Slack {}: {}", method, e))?;
+        if body.get("ok").and_then(Value::as_bool) != Some(true) {
+            let error = body
+                .get("error")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown_error");
+            return Err(anyhow!("Slack {} failed: {}", method, error));
+        }
+
+        serde_json::from_value(body)
+            .map_err(|e| anyhow!("Invalid response from Slack {}: {}", method, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    #[tokio::test]
+    async fn test_post_and_update_message() {
+        let mut server = mockito::Server::new_async().await;
+        let post = server
+            .mock("POST", "/chat.postMessage")
+            .match_header("authorization", "Bearer xoxb-test")
+            .match_body(Matcher::PartialJson(json!({
+                "channel": "C1",
+                "thread_ts": "1.0",
+                "text": "Thinking…"
+            })))
+            .with_body(r#"{"ok":true,"channel":"C1","ts":"2.0","message":{}}"#)
+            .create_async()
+            .await;
+        let update = server
+            .mock("POST", "/chat.update")
+            .match_body(Matcher::PartialJson(json!({
+                "channel": "C1",
+                "ts": "2.0",
+                "text": "Done"
+            })))
+            .with_body(r#"{"ok":true,"channel":"C1","ts":"2.0"}"#)
+            .create_async()
+            .await;
+
+        let client = SlackClient::with_base_url("xoxb-test", &server.url());
+        let posted = client
+            .post_message(&SlackMessage {
+                channel: "C1".to_string(),
+                thread_ts: Some("1.0".to_string()),
+                text: "Thinking…".to_string(),
+                blocks: vec![],
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            posted,
+            PostedMessage {
+                channel: "C1".to_string(),
+                ts: "2.0".to_string()
+            }
+        );
+
+        client
+            .update_message(
+                &posted,
+                &SlackMessage {
+                    channel: "C1".to_string(),
+                    text: "Done".to_string(),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        post.assert_async().await;
+        update.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_user_email() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/users.info")
+            .match_query(Matcher::UrlEncoded("user".into(), "U1".into()))
+            .with_body(r#"{"ok":true,"user":{"id":"U1","profile":{"email":"ada@example.com"}}}"#)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/users.info")
+            .match_query(Matcher::UrlEncoded("user".into(), "U2".into()))
+            .with_body(r#"{"ok":true,"user":{"id":"U2","deleted":true,"profile":{"email":"gone@example.com"}}}"#)
+            .create_async()
+            .await;
+
+        let client = SlackClient::with_base_url("xoxb-test", &server.url());
+        assert_eq!(
+            client.user_email("U1").await.unwrap().as_deref(),
+            Some("ada@example.com")
+        );
+        assert_eq!(client.user_email("U2").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_api_errors() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/auth.test")
+            .with_body(r#"{"ok":false,"error":"invalid_auth"}"#)
+            .create_async()
+            .await;
+
+        let client = SlackClient::with_base_url("xoxb-bad", &server.url());
+        let error = client.auth_test().await.unwrap_err();
+        assert_eq!(error.to_string(), "Slack auth.test failed: invalid_auth");
+    }
+}
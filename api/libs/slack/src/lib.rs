@@ -0,0 +1,29 @@
+//! Slack Client Library
+//!
+//! A small client for the parts of the Slack Web API the Buster bot uses,
+//! request signature verification for the Events API, and the event and
+//! Block Kit types exchanged with Slack.
+
+pub mod blocks;
+mod client;
+mod signature;
+mod types;
+
+// Re-export public API
+pub use client::SlackClient;
+pub use signature::{
+    sign_request, verify_request, MAX_REQUEST_AGE_SECONDS, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+pub use types::{
+    AuthIdentity, EventEnvelope, MentionEvent, PostedMessage, SlackEvent, SlackMessage,
+};
+
+// Constants
+pub const API_BASE: &str = "https://slack.com/api";
+
+/// Environment variable overriding the Web API base URL, e.g. to point the
+/// bot at a local mock of Slack
+pub const SLACK_API_URL_ENV: &str = "SLACK_API_URL";
+
+/// Environment variable holding the Slack app's signing secret
+pub const SLACK_SIGNING_SECRET_ENV: &str = "SLACK_SIGNING_SECRET";
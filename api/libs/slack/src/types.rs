@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Body of a request to the Events API endpoint
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EventEnvelope {
+    /// Sent once when the endpoint is configured; echo the challenge back
+    UrlVerification { challenge: String },
+    EventCallback {
+        team_id: String,
+        event_id: String,
+        event: SlackEvent,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SlackEvent {
+    AppMention(MentionEvent),
+    #[serde(other)]
+    Other,
+}
+
+/// A message mentioning the bot
+#[derive(Debug, Clone, Deserialize)]
+pub struct MentionEvent {
+    pub user: String,
+    pub text: String,
+    pub channel: String,
+    pub ts: String,
+    /// Set when the mention is a reply in a thread
+    #[serde(default)]
+    pub thread_ts: Option<String>,
+    /// Set when another bot posted the mention
+    #[serde(default)]
+    pub bot_id: Option<String>,
+}
+
+impl MentionEvent {
+    /// The thread replies to this mention belong in
+    pub fn thread(&self) -> &str {
+        self.thread_ts.as_deref().unwrap_or(&self.ts)
+    }
+}
+
+/// A message to post or update
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SlackMessage {
+    pub channel: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_ts: Option<String>,
+    /// Fallback for notifications and clients that can't render blocks
+    pub text: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub blocks: Vec<Value>,
+}
+
+/// Where a posted message lives, for updating it later
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PostedMessage {
+    pub channel: String,
+    pub ts: String,
+}
+
+/// The workspace and bot user a token belongs to
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct AuthIdentity {
+    pub team_id: String,
+    pub team: String,
+    pub user_id: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_deserialize_envelope() {
+        let envelope: EventEnvelope = serde_json::from_value(json!({
+            "type": "event_callback",
+            "team_id": "T1",
+            "event_id": "Ev1",
+            "event": {
+                "type": "app_mention",
+                "user": "U1",
+                "text": "<@UBOT> revenue by month",
+                "channel": "C1",
+                "ts": "1700000000.000100",
+                "event_ts": "1700000000.000100"
+            }
+        }))
+        .unwrap();
+        match envelope {
+            EventEnvelope::EventCallback {
+                event: SlackEvent::AppMention(mention),
+                ..
+            } => assert_eq!(mention.thread(), "1700000000.000100"),
+            other => panic!("unexpected envelope: {:?}", other),
+        }
+
+        let envelope: EventEnvelope =
+            serde_json::from_value(json!({ "type": "app_rate_limited", "team_id": "T1" })).unwrap();
+        assert!(matches!(envelope, EventEnvelope::Other));
+
+        let envelope: EventEnvelope = serde_json::from_value(json!({
+            "type": "event_callback",
+            "team_id": "T1",
+            "event_id": "Ev2",
+            "event": { "type": "reaction_added", "user": "U1" }
+        }))
+        .unwrap();
+        assert!(matches!(
+            envelope,
+            EventEnvelope::EventCallback {
+                event: SlackEvent::Other,
+                ..
+            }
+        ));
+    }
+}
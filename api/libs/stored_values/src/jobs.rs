@@ -6,10 +6,12 @@ use diesel_async::RunQueryDsl;
 use query_engine::data_source_query_routes::query_engine::query_engine;
 use tracing::{error, info, warn};
 use uuid::Uuid;
-use litellm::{EmbeddingRequest, LiteLLMClient};
+use litellm::{embedding_dimensions, EmbeddingRequest, LiteLLMClient};
 use sqlx::QueryBuilder;
 
 use database::{
+    enums::ModelPurpose,
+    helpers::model_configs::get_data_source_models,
     models::StoredValuesSyncJob,
     pool::{get_pg_pool, get_sqlx_pool},
     schema::stored_values_sync_jobs,
@@ -18,6 +20,8 @@ use database::{
 // query_engine imports
 use query_engine::data_types::DataType;
 
+use crate::schema::reset_search_embeddings;
+
 const SYNC_CHUNK_LIMIT: i64 = 1000;
 
 /// Sets up a new sync job record for a specific column in the `stored_values_sync_jobs` table.
@@ -147,18 +151,38 @@ pub async fn sync_distinct_values_chunk(
         "Starting full sync of distinct values and embeddings for job"
     );
 
+    // Organizations with their own embedding model go through the gateway;
+    // everyone else embeds directly with OpenAI
+    let models = match get_data_source_models(&data_source_id).await {
+        Ok(models) => models,
+        Err(e) => {
+            error!(%job_id, "Failed to load organization models: {}. Aborting sync.", e);
+            update_job_status(job_id, "error", Some(e.to_string())).await?;
+            return Err(e);
+        }
+    };
+    let embedding_model = models.model_for(ModelPurpose::Embeddings);
+    let dimensions = embedding_dimensions(&embedding_model);
+    // Values embedded with another model can't be compared with new embeddings
+    if let Err(e) = reset_search_embeddings(data_source_id, &embedding_model).await {
+        error!(%job_id, "Failed to reset stored values for the embedding model: {}. Aborting sync.", e);
+        update_job_status(job_id, "error", Some(e.to_string())).await?;
+        return Err(e);
+    }
+    let litellm_client = match models.configured(ModelPurpose::Embeddings) {
+        Some(_) => LiteLLMClient::new(None, None),
+        None => LiteLLMClient::new(
+            std::env::var("OPENAI_API_KEY").ok(),
+            Some("https://api.openai.com/v1/".to_string()),
+        ),
+    };
+
     // Set status to in_progress immediately
     if let Err(e) = update_job_status(job_id, "in_progress", None).await {
         error!(%job_id, "Failed to set job status to in_progress: {}. Aborting sync.", e);
         return Err(e); // Propagate the error
     }
 
-    // Instantiate the LiteLLM Client
-    let litellm_client = LiteLLMClient::new(
-        std::env::var("OPENAI_API_KEY").ok(),
-        Some("https://api.openai.com/v1/".to_string()),
-    );
-
     // Wrap the core sync logic in a closure or block to handle errors centrally
     let sync_result: Result<usize, anyhow::Error> = async {
         let app_db_pool = get_sqlx_pool();
@@ -256,9 +280,9 @@ pub async fn sync_distinct_values_chunk(
             // 4. Embed Chunk
             info!(%job_id, count = chunk_values_to_process.len(), "Generating embeddings for chunk...");
             let embedding_request = EmbeddingRequest {
-                model: "text-embedding-3-small".to_string(),
+                model: embedding_model.clone(),
                 input: chunk_values_to_process.clone(), // Clone values needed for embedding request
-                dimensions: Some(1536),
+                dimensions: Some(dimensions),
                 encoding_format: Some("float".to_string()),
                 user: None,
             };
@@ -410,6 +434,27 @@ pub async fn sync_distinct_values_chunk(
     }
 }
 
+/// Marks every sync job of a data source as pending so its values are synced again.
+pub(crate) async fn queue_sync_jobs(data_source_id: Uuid) -> Result<()> {
+    let pool = get_pg_pool();
+    let mut conn = pool
+        .get()
+        .await
+        .context("Failed to get DB connection for queueing sync jobs")?;
+
+    diesel::update(stored_values_sync_jobs::table)
+        .filter(stored_values_sync_jobs::data_source_id.eq(data_source_id))
+        .set((
+            stored_values_sync_jobs::status.eq("pending"),
+            stored_values_sync_jobs::error_message.eq(None::<String>),
+        ))
+        .execute(&mut conn)
+        .await
+        .with_context(|| format!("Failed to queue sync jobs for data_source_id: {}", data_source_id))?;
+
+    Ok(())
+}
+
 /// Updates the status, last_synced_at, and optional error message for a sync job.
 async fn update_job_status(
     job_id: Uuid,
@@ -7,7 +7,7 @@ pub mod jobs;
 pub mod search;
 
 // Re-export key functions
-pub use schema::create_search_schema;
+pub use schema::{create_search_schema, reset_search_embeddings};
 pub use jobs::setup_sync_job;
 pub use search::{search_values_by_embedding, StoredValueResult};
 
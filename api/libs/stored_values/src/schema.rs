@@ -1,18 +1,32 @@
 use anyhow::{Context, Result};
+use litellm::embedding_dimensions;
 use sqlx::Executor;
 use tracing::info;
 use uuid::Uuid;
 
-use database::pool::get_sqlx_pool;
+use database::{
+    enums::ModelPurpose,
+    helpers::model_configs::get_data_source_models,
+    pool::get_sqlx_pool,
+};
+
+use crate::jobs::queue_sync_jobs;
 
 /// Creates a dedicated schema and table for storing searchable column values and embeddings.
 ///
 /// The schema name is derived from the data_source_id by replacing hyphens with underscores.
 /// It also creates an HNSW index on the embedding column for efficient vector search.
+/// The embedding column is sized for the organization's embedding model, whose name is
+/// kept as the column's comment.
 pub async fn create_search_schema(data_source_id: Uuid) -> Result<()> {
     let schema_name = format!("ds_{}", data_source_id.to_string().replace('-', "_"));
     info!(%data_source_id, %schema_name, "Creating search schema and table");
 
+    let embedding_model = get_data_source_models(&data_source_id)
+        .await
+        .context("Failed to load the data source's embedding model")?
+        .model_for(ModelPurpose::Embeddings);
+
     let pool = get_sqlx_pool();
     let mut conn = pool
         .acquire()
@@ -36,16 +50,20 @@ pub async fn create_search_schema(data_source_id: Uuid) -> Result<()> {
             column_name text NOT NULL,
             table_name text NOT NULL,
             schema_name text NOT NULL,
-            embedding public.halfvec(1536), -- Assuming halfvec is in public or installed extension schema
+            embedding public.halfvec({}), -- Assuming halfvec is in public or installed extension schema
             synced_at timestamp with time zone DEFAULT now()
         );
         "#,
-        schema_name
+        schema_name,
+        embedding_dimensions(&embedding_model)
     );
     conn.execute(create_table_query.as_str())
         .await
         .with_context(|| format!("Failed to create table searchable_column_values in schema {}", schema_name))?;
-    info!(%schema_name, "Table searchable_column_values created successfully");
+    conn.execute(comment_embedding_model_query(&schema_name, &embedding_model).as_str())
+        .await
+        .with_context(|| format!("Failed to record the embedding model in schema {}", schema_name))?;
+    info!(%schema_name, %embedding_model, "Table searchable_column_values created successfully");
 
     // 3. Create the HNSW index on the embedding column
     let create_index_query = format!(
@@ -153,6 +171,88 @@ pub async fn create_search_schema(data_source_id: Uuid) -> Result<()> {
     Ok(())
 }
 
+/// Re-embeds a data source's stored values if they weren't embedded with `embedding_model`
+///
+/// The values are removed, the embedding column is resized for the new model and
+/// every sync job of the data source is queued again. Returns whether anything was
+/// reset. Tables created before the model was recorded hold embeddings from the
+/// default model.
+pub async fn reset_search_embeddings(data_source_id: Uuid, embedding_model: &str) -> Result<bool> {
+    let schema_name = format!("ds_{}", data_source_id.to_string().replace('-', "_"));
+    let pool = get_sqlx_pool();
+
+    let stored_model: Option<Option<String>> = sqlx::query_scalar(
+        r#"
+        SELECT col_description(c.oid, a.attnum)
+        FROM pg_class c
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        JOIN pg_attribute a ON a.attrelid = c.oid
+        WHERE n.nspname = $1
+          AND c.relname = 'searchable_column_values'
+          AND a.attname = 'embedding'
+        "#,
+    )
+    .bind(&schema_name)
+    .fetch_optional(pool)
+    .await
+    .with_context(|| format!("Failed to read the embedding model of schema {}", schema_name))?;
+
+    // No search table yet, nothing to reset
+    let Some(stored_model) = stored_model else {
+        return Ok(false);
+    };
+    let stored_model =
+        stored_model.unwrap_or_else(|| ModelPurpose::Embeddings.default_model().to_string());
+    if stored_model == embedding_model {
+        return Ok(false);
+    }
+
+    info!(
+        %data_source_id,
+        %stored_model,
+        %embedding_model,
+        "Embedding model changed, resetting stored values"
+    );
+
+    let mut tx = pool
+        .begin()
+        .await
+        .context("Failed to start a transaction to reset stored values")?;
+    tx.execute(format!(r#"TRUNCATE "{}"."searchable_column_values""#, schema_name).as_str())
+        .await
+        .with_context(|| format!("Failed to clear stored values in schema {}", schema_name))?;
+    tx.execute(
+        format!(
+            r#"ALTER TABLE "{}"."searchable_column_values" ALTER COLUMN embedding TYPE public.halfvec({})"#,
+            schema_name,
+            embedding_dimensions(embedding_model)
+        )
+        .as_str(),
+    )
+    .await
+    .with_context(|| format!("Failed to resize the embedding column in schema {}", schema_name))?;
+    tx.execute(comment_embedding_model_query(&schema_name, embedding_model).as_str())
+        .await
+        .with_context(|| format!("Failed to record the embedding model in schema {}", schema_name))?;
+    tx.commit()
+        .await
+        .context("Failed to commit the stored values reset")?;
+
+    queue_sync_jobs(data_source_id)
+        .await
+        .context("Failed to queue sync jobs after resetting stored values")?;
+
+    Ok(true)
+}
+
+fn comment_embedding_model_query(schema_name: &str, embedding_model: &str) -> String {
+    format!(
+        r#"COMMENT ON COLUMN "{}"."searchable_column_values".embedding IS '{}'"#,
+        schema_name,
+        embedding_model.replace('\'', "''")
+    )
+}
+
 // Potential future functions:
 // pub async fn store_column_value(...) -> Result<()> { ... }
 // pub async fn search_similar_values(...) -> Result<Vec<SearchResult>> { ... } 
\ No newline at end of file
@@ -1,5 +1,7 @@
 use anyhow::{Context, Result};
-use database::pool::get_sqlx_pool;
+use database::{
+    enums::ModelPurpose, helpers::model_configs::get_data_source_models, pool::get_sqlx_pool,
+};
 use futures::future;
 use serde_yaml;
 use sqlx::FromRow;
@@ -438,6 +440,9 @@ pub async fn search_values_in_datasets(
 
     // Step 2: Generate embeddings for each search term
     let litellm_client = litellm::LiteLLMClient::new(None, None);
+    let embedding_model = get_data_source_models(&data_source_id)
+        .await?
+        .model_for(ModelPurpose::Embeddings);
 
     // Process each search term
     for term in search_terms {
@@ -449,9 +454,9 @@ pub async fn search_values_in_datasets(
 
         // Generate embedding for the search term
         let embedding_request = litellm::EmbeddingRequest {
-            model: "text-embedding-3-small".to_string(),
+            model: embedding_model.clone(),
             input: vec![term.clone()],
-            dimensions: Some(1536),
+            dimensions: Some(litellm::embedding_dimensions(&embedding_model)),
             encoding_format: Some("float".to_string()),
             user: None,
         };
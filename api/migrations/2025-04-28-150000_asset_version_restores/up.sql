@@ -0,0 +1,20 @@
+-- Your SQL goes here
+
+-- Audit trail of version restores on metric and dashboard files
+CREATE TABLE asset_version_restores (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    asset_id UUID NOT NULL,
+    asset_type asset_type_enum NOT NULL,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    restored_version_number INTEGER NOT NULL,
+    new_version_number INTEGER NOT NULL,
+    restored_by UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_asset_version_restores_asset ON asset_version_restores(asset_id, asset_type);
+CREATE INDEX idx_asset_version_restores_organization_id ON asset_version_restores(organization_id);
+
+COMMENT ON TABLE asset_version_restores IS 'Records every restore of a previous metric/dashboard version.';
+COMMENT ON COLUMN asset_version_restores.restored_version_number IS 'The historical version whose content was restored.';
+COMMENT ON COLUMN asset_version_restores.new_version_number IS 'The version created by the restore.';
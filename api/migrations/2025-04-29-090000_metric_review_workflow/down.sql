@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS metric_review_events;
+DROP TABLE IF EXISTS dataset_reviewers;
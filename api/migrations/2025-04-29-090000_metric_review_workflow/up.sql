@@ -0,0 +1,34 @@
+-- Your SQL goes here
+
+-- Designated data-team reviewers for metrics built on a dataset
+CREATE TABLE dataset_reviewers (
+    dataset_id UUID NOT NULL REFERENCES datasets(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_by UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (dataset_id, user_id)
+);
+
+CREATE INDEX idx_dataset_reviewers_user_id ON dataset_reviewers(user_id);
+
+-- Every certification transition and review comment on a metric
+CREATE TABLE metric_review_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    metric_file_id UUID NOT NULL REFERENCES metric_files(id) ON DELETE CASCADE,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    actor_id UUID NOT NULL REFERENCES users(id),
+    action TEXT NOT NULL,
+    from_status verification_enum NOT NULL,
+    to_status verification_enum NOT NULL,
+    comment TEXT,
+    reviewer_ids UUID[] NOT NULL DEFAULT '{}',
+    due_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_metric_review_events_metric_file_id ON metric_review_events(metric_file_id, created_at);
+CREATE INDEX idx_metric_review_events_reviewer_ids ON metric_review_events USING GIN (reviewer_ids);
+
+COMMENT ON COLUMN metric_review_events.action IS 'requested, started, approved, rejected, demoted or commented.';
+COMMENT ON COLUMN metric_review_events.reviewer_ids IS 'Reviewers assigned when the review was requested.';
+COMMENT ON COLUMN metric_review_events.due_at IS 'Review SLA deadline, set on review requests.';
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS metric_annotations;
+DROP TABLE IF EXISTS asset_comments;
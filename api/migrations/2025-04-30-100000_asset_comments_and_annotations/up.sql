@@ -0,0 +1,37 @@
+-- Your SQL goes here
+
+-- Threaded discussion on metrics and dashboards
+CREATE TABLE asset_comments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    asset_id UUID NOT NULL,
+    asset_type asset_type_enum NOT NULL,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    parent_id UUID REFERENCES asset_comments(id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    mentions UUID[] NOT NULL DEFAULT '{}',
+    created_by UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    deleted_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX idx_asset_comments_asset ON asset_comments(asset_id, asset_type) WHERE deleted_at IS NULL;
+CREATE INDEX idx_asset_comments_parent_id ON asset_comments(parent_id);
+
+-- Time-series annotations rendered on metric charts
+CREATE TABLE metric_annotations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    metric_file_id UUID NOT NULL REFERENCES metric_files(id) ON DELETE CASCADE,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    x_value TEXT NOT NULL,
+    text TEXT NOT NULL,
+    created_by UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    deleted_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX idx_metric_annotations_metric_file_id ON metric_annotations(metric_file_id) WHERE deleted_at IS NULL;
+
+COMMENT ON COLUMN asset_comments.mentions IS 'Users @mentioned in the comment.';
+COMMENT ON COLUMN metric_annotations.x_value IS 'X-axis value the annotation is pinned to (date, number or category, as text).';
@@ -0,0 +1,15 @@
+-- Your SQL goes here
+
+-- Per-organization LLM model overrides, one row per purpose (planning, review, embeddings, ...)
+CREATE TABLE organization_model_configs (
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    purpose TEXT NOT NULL,
+    model TEXT NOT NULL,
+    updated_by UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (organization_id, purpose)
+);
+
+COMMENT ON TABLE organization_model_configs IS 'LLM model chosen by an organization for each agent mode or tool purpose. Purposes without a row use the built-in default.';
+COMMENT ON COLUMN organization_model_configs.model IS 'Model identifier as exposed by the LLM gateway.';
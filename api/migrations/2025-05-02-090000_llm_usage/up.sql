@@ -0,0 +1,35 @@
+-- Your SQL goes here
+
+-- One row per LLM call, with the tokens it used and its estimated cost
+CREATE TABLE llm_usage_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID REFERENCES organizations(id) ON DELETE CASCADE,
+    user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    chat_id UUID,
+    message_id UUID,
+    model TEXT NOT NULL,
+    generation_name TEXT,
+    prompt_tokens INTEGER NOT NULL,
+    completion_tokens INTEGER NOT NULL,
+    total_tokens INTEGER NOT NULL,
+    estimated_cost DOUBLE PRECISION NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX llm_usage_events_organization_created_at_idx ON llm_usage_events (organization_id, created_at);
+CREATE INDEX llm_usage_events_user_created_at_idx ON llm_usage_events (user_id, created_at);
+CREATE INDEX llm_usage_events_chat_id_idx ON llm_usage_events (chat_id);
+
+COMMENT ON COLUMN llm_usage_events.estimated_cost IS 'Estimated cost in USD from the price table in effect when the call was made.';
+COMMENT ON COLUMN llm_usage_events.generation_name IS 'What the call was for, e.g. agent, conversation_title, embeddings.';
+
+-- Monthly LLM spend limit per organization; new chats are refused once it is reached
+CREATE TABLE organization_llm_budgets (
+    organization_id UUID PRIMARY KEY REFERENCES organizations(id) ON DELETE CASCADE,
+    monthly_budget DOUBLE PRECISION NOT NULL CHECK (monthly_budget >= 0),
+    updated_by UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+COMMENT ON COLUMN organization_llm_budgets.monthly_budget IS 'Budget in USD per calendar month (UTC).';
@@ -0,0 +1,14 @@
+-- Your SQL goes here
+create extension if not exists vector;
+
+create table dataset_embeddings (
+    dataset_id uuid primary key references datasets(id) on delete cascade,
+    organization_id uuid not null references organizations(id),
+    model text not null,
+    embedding halfvec(1536) not null,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now()
+);
+
+create index dataset_embeddings_organization_id_idx on dataset_embeddings(organization_id);
+create index dataset_embeddings_embedding_idx on dataset_embeddings using hnsw (embedding halfvec_cosine_ops);
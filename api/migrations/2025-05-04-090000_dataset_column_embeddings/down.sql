@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+drop table if exists dataset_column_embeddings;
+
+alter table dataset_embeddings drop column if exists content_hash;
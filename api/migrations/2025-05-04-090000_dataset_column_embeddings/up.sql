@@ -0,0 +1,18 @@
+-- Your SQL goes here
+alter table dataset_embeddings add column content_hash text not null default '';
+
+create table dataset_column_embeddings (
+    id uuid primary key default gen_random_uuid(),
+    dataset_id uuid not null references datasets(id) on delete cascade,
+    column_name text not null,
+    organization_id uuid not null references organizations(id),
+    model text not null,
+    content_hash text not null,
+    embedding halfvec(1536) not null,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    constraint dataset_column_embeddings_dataset_id_column_name_key unique (dataset_id, column_name)
+);
+
+create index dataset_column_embeddings_organization_id_idx on dataset_column_embeddings(organization_id);
+create index dataset_column_embeddings_embedding_idx on dataset_column_embeddings using hnsw (embedding halfvec_cosine_ops);
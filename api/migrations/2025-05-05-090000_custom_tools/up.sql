@@ -0,0 +1,28 @@
+-- Your SQL goes here
+create table custom_tools (
+    id uuid primary key default gen_random_uuid(),
+    organization_id uuid not null references organizations(id) on delete cascade,
+    name text not null,
+    display_name text not null,
+    description text not null,
+    kind text not null,
+    endpoint text not null,
+    remote_name text,
+    input_schema jsonb not null default '{"type": "object", "properties": {}}'::jsonb,
+    headers jsonb not null default '{}'::jsonb,
+    allowed_roles user_organization_role_enum[] not null default '{}',
+    timeout_ms integer not null default 30000,
+    enabled boolean not null default true,
+    created_by uuid not null references users(id),
+    updated_by uuid not null references users(id),
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    deleted_at timestamptz
+);
+
+create unique index custom_tools_organization_id_name_key on custom_tools(organization_id, name) where deleted_at is null;
+
+comment on table custom_tools is 'External tools an organization exposes to the analysis agent, called over HTTP or through a Model Context Protocol server.';
+comment on column custom_tools.kind is 'http: POST the arguments as JSON to the endpoint. mcp: call remote_name on the MCP server at the endpoint.';
+comment on column custom_tools.headers is 'Headers sent with every call, e.g. credentials. Never returned by the API.';
+comment on column custom_tools.allowed_roles is 'Organization roles whose chats may use the tool. Empty means every role.';
@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+drop index if exists messages_chat_id_parent_message_id_idx;
+
+alter table chats
+    drop column if exists active_message_id;
+
+alter table messages
+    drop column if exists parent_message_id;
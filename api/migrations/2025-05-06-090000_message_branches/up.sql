@@ -0,0 +1,31 @@
+-- Your SQL goes here
+alter table messages
+    add column parent_message_id uuid references messages(id);
+
+alter table chats
+    add column active_message_id uuid;
+
+-- Existing chats are a single branch in creation order
+update messages
+set parent_message_id = ordered.parent_message_id
+from (
+    select id,
+        lag(id) over (partition by chat_id order by created_at) as parent_message_id
+    from messages
+    where deleted_at is null
+) ordered
+where messages.id = ordered.id
+    and ordered.parent_message_id is not null;
+
+update chats
+set active_message_id = latest.id
+from (
+    select distinct on (chat_id) chat_id, id
+    from messages
+    where deleted_at is null
+    order by chat_id, created_at desc
+) latest
+where chats.id = latest.chat_id;
+
+create index messages_chat_id_parent_message_id_idx
+    on messages (chat_id, parent_message_id);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+drop index if exists terms_organization_id_idx;
+
+alter table terms drop column if exists kind;
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+alter table terms add column kind text not null default 'term';
+
+create index terms_organization_id_idx on terms(organization_id) where deleted_at is null;
+
+comment on column terms.kind is 'term: a glossary definition, retrieved for the agent when relevant to the question. instruction: a free-form rule the agent always follows.';
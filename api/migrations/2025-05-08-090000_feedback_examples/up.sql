@@ -0,0 +1,44 @@
+-- Your SQL goes here
+create table asset_feedback (
+    id uuid primary key default gen_random_uuid(),
+    organization_id uuid not null references organizations(id) on delete cascade,
+    message_id uuid references messages(id) on delete cascade,
+    metric_file_id uuid references metric_files(id) on delete cascade,
+    rating text not null,
+    category text,
+    comment text,
+    correction_sql text,
+    question text,
+    status text not null default 'pending',
+    reviewed_by uuid references users(id),
+    reviewed_at timestamptz,
+    created_by uuid not null references users(id),
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    deleted_at timestamptz,
+    constraint asset_feedback_target_check check ((message_id is null) <> (metric_file_id is null))
+);
+
+create unique index asset_feedback_message_id_created_by_key on asset_feedback(message_id, created_by) where deleted_at is null and message_id is not null;
+create unique index asset_feedback_metric_file_id_created_by_key on asset_feedback(metric_file_id, created_by) where deleted_at is null and metric_file_id is not null;
+create index asset_feedback_organization_id_status_idx on asset_feedback(organization_id, status) where deleted_at is null;
+
+comment on table asset_feedback is 'Structured feedback on a chat message or a metric, reviewed by admins.';
+comment on column asset_feedback.question is 'The question that produced the answer, captured when the feedback is given.';
+comment on column asset_feedback.status is 'pending: waiting in the review queue. promoted: turned into a verified example. dismissed: reviewed and not used.';
+
+create table verified_examples (
+    id uuid primary key default gen_random_uuid(),
+    organization_id uuid not null references organizations(id) on delete cascade,
+    question text not null,
+    sql text not null,
+    feedback_id uuid references asset_feedback(id) on delete set null,
+    created_by uuid not null references users(id),
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    deleted_at timestamptz
+);
+
+create index verified_examples_organization_id_idx on verified_examples(organization_id) where deleted_at is null;
+
+comment on table verified_examples is 'Question and SQL pairs confirmed by admins, retrieved as few-shot examples for similar questions.';
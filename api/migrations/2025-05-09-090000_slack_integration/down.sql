@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+drop table if exists slack_threads;
+drop table if exists slack_user_links;
+drop table if exists slack_integrations;
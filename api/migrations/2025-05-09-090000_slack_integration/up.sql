@@ -0,0 +1,39 @@
+-- Your SQL goes here
+create table slack_integrations (
+    id uuid primary key default gen_random_uuid(),
+    organization_id uuid not null references organizations(id) on delete cascade,
+    team_id text not null,
+    team_name text not null,
+    bot_user_id text not null,
+    created_by uuid not null references users(id),
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    deleted_at timestamptz
+);
+
+create unique index slack_integrations_organization_id_key on slack_integrations(organization_id) where deleted_at is null;
+create unique index slack_integrations_team_id_key on slack_integrations(team_id) where deleted_at is null;
+
+create table slack_user_links (
+    slack_integration_id uuid not null references slack_integrations(id) on delete cascade,
+    slack_user_id text not null,
+    user_id uuid not null references users(id) on delete cascade,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    primary key (slack_integration_id, slack_user_id)
+);
+
+create table slack_threads (
+    slack_integration_id uuid not null references slack_integrations(id) on delete cascade,
+    channel_id text not null,
+    thread_ts text not null,
+    user_id uuid not null references users(id) on delete cascade,
+    chat_id uuid not null references chats(id) on delete cascade,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    primary key (slack_integration_id, channel_id, thread_ts, user_id)
+);
+
+comment on table slack_integrations is 'Slack workspaces connected to an organization. The bot token is kept in the vault under the integration id.';
+comment on table slack_user_links is 'Slack users mapped to the Buster users the bot acts as, matched by email on their first question.';
+comment on table slack_threads is 'The chat each user continues when they mention the bot again in a Slack thread.';
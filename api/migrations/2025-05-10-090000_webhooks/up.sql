@@ -0,0 +1,39 @@
+-- Your SQL goes here
+create table webhooks (
+    id uuid primary key default gen_random_uuid(),
+    organization_id uuid not null references organizations(id) on delete cascade,
+    url text not null,
+    description text,
+    events text[] not null default '{}',
+    enabled boolean not null default true,
+    created_by uuid not null references users(id),
+    updated_by uuid not null references users(id),
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    deleted_at timestamptz
+);
+
+create index webhooks_organization_id_idx on webhooks(organization_id) where deleted_at is null;
+
+create table webhook_deliveries (
+    id uuid primary key default gen_random_uuid(),
+    webhook_id uuid not null references webhooks(id) on delete cascade,
+    event_id uuid not null,
+    event_type text not null,
+    payload jsonb not null,
+    status text not null,
+    attempts integer not null default 0,
+    response_status integer,
+    response_body text,
+    error text,
+    next_attempt_at timestamptz,
+    delivered_at timestamptz,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now()
+);
+
+create index webhook_deliveries_webhook_id_idx on webhook_deliveries(webhook_id, created_at desc);
+create index webhook_deliveries_due_idx on webhook_deliveries(next_attempt_at) where status = 'pending';
+
+comment on table webhooks is 'Endpoints an organization notifies of asset lifecycle events. An empty events list subscribes to every event. The signing secret is kept in the vault under the webhook id.';
+comment on table webhook_deliveries is 'Each event sent to a webhook, with the outcome of its latest attempt. Pending deliveries are retried with backoff until next_attempt_at is cleared.';
@@ -0,0 +1,14 @@
+-- This file should undo anything in `up.sql`
+delete from dataset_embeddings where vector_dims(embedding) <> 1536;
+delete from dataset_embeddings e
+using dataset_embeddings newer
+where e.dataset_id = newer.dataset_id
+  and e.model <> newer.model
+  and e.updated_at < newer.updated_at;
+
+alter table dataset_embeddings alter column embedding type halfvec(1536);
+
+alter table dataset_embeddings drop constraint dataset_embeddings_pkey;
+alter table dataset_embeddings add primary key (dataset_id);
+
+create index dataset_embeddings_embedding_idx on dataset_embeddings using hnsw (embedding halfvec_cosine_ops);
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+-- Embeddings are kept per model, and the column takes whatever dimensions the
+-- model produces. An HNSW index needs fixed dimensions, so it's dropped;
+-- searches only scan the embeddings of a user's candidate datasets.
+drop index if exists dataset_embeddings_embedding_idx;
+
+alter table dataset_embeddings drop constraint dataset_embeddings_pkey;
+alter table dataset_embeddings add primary key (dataset_id, model);
+
+alter table dataset_embeddings alter column embedding type halfvec;
@@ -0,0 +1,15 @@
+-- This file should undo anything in `up.sql`
+delete from dataset_column_embeddings where vector_dims(embedding) <> 1536;
+delete from dataset_column_embeddings c
+using dataset_column_embeddings newer
+where c.dataset_id = newer.dataset_id
+  and c.column_name = newer.column_name
+  and c.model <> newer.model
+  and c.updated_at < newer.updated_at;
+
+alter table dataset_column_embeddings alter column embedding type halfvec(1536);
+
+alter table dataset_column_embeddings drop constraint dataset_column_embeddings_dataset_id_column_name_model_key;
+alter table dataset_column_embeddings add constraint dataset_column_embeddings_dataset_id_column_name_key unique (dataset_id, column_name);
+
+create index dataset_column_embeddings_embedding_idx on dataset_column_embeddings using hnsw (embedding halfvec_cosine_ops);
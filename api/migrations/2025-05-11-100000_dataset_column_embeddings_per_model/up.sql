@@ -0,0 +1,9 @@
+-- Your SQL goes here
+-- Like dataset embeddings, column embeddings are kept per model in whatever
+-- dimensions the model produces
+drop index if exists dataset_column_embeddings_embedding_idx;
+
+alter table dataset_column_embeddings drop constraint dataset_column_embeddings_dataset_id_column_name_key;
+alter table dataset_column_embeddings add constraint dataset_column_embeddings_dataset_id_column_name_model_key unique (dataset_id, column_name, model);
+
+alter table dataset_column_embeddings alter column embedding type halfvec;
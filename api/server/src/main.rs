@@ -11,6 +11,7 @@ use database::{self, pool::init_pools};
 use diesel::{Connection, PgConnection};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 use dotenv::dotenv;
+use handlers::webhooks::retry_due_webhook_deliveries;
 use rustls::crypto::ring;
 use stored_values::jobs::trigger_stale_sync_jobs;
 use tokio::sync::broadcast;
@@ -55,6 +56,8 @@ async fn main() -> Result<(), anyhow::Error> {
         return Ok(());
     }
 
+    handlers::llm_usage::install_llm_usage_recorder();
+
     tracing::info!("Running database migrations");
 
     if let Err(e) = run_migrations().await {
@@ -80,6 +83,17 @@ async fn main() -> Result<(), anyhow::Error> {
     })?;
 
     scheduler.add(job).await?;
+
+    // Retry failed webhook deliveries every minute
+    let webhook_retry_job = Job::new_async("0 * * * * *", |uuid, _l| {
+        Box::pin(async move {
+            if let Err(e) = retry_due_webhook_deliveries().await {
+                error!(job_uuid = %uuid, "Webhook delivery retry job failed: {}", e);
+            }
+        })
+    })?;
+    scheduler.add(webhook_retry_job).await?;
+
     scheduler.start().await?;
     info!("Stored values sync job scheduler started.");
     // --- End Stored Values Sync Job Scheduler ---
@@ -0,0 +1,46 @@
+use anyhow::Result;
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::Extension;
+use handlers::chats::cancel_chat_handler::{cancel_chat_handler, CancelChatResponse};
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use crate::routes::rest::ApiResponse;
+
+/// Stop a message that is still generating
+///
+/// POST /api/v1/chats/:id/messages/:message_id/cancel
+///
+/// The partial reasoning and response are saved with a "stopped" status once
+/// the running generation winds down.
+pub async fn cancel_chat_route(
+    Path((chat_id, message_id)): Path<(Uuid, Uuid)>,
+    Extension(user): Extension<AuthenticatedUser>,
+) -> Result<ApiResponse<CancelChatResponse>, (StatusCode, String)> {
+    match cancel_chat_handler(&chat_id, &message_id, &user).await {
+        Ok(response) => Ok(ApiResponse::JsonData(response)),
+        Err(e) => {
+            let error_message = e.to_string();
+            tracing::error!(
+                "Error stopping message {} in chat {}: {}",
+                message_id,
+                chat_id,
+                error_message
+            );
+
+            if error_message.contains("not found") {
+                Err((StatusCode::NOT_FOUND, error_message))
+            } else if error_message.contains("permission") {
+                Err((StatusCode::FORBIDDEN, error_message))
+            } else if error_message.contains("already finished") {
+                Err((StatusCode::CONFLICT, error_message))
+            } else {
+                Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to stop message".to_string(),
+                ))
+            }
+        }
+    }
+}
@@ -137,6 +137,7 @@ mod tests {
             most_recent_file_id: None,
             most_recent_file_type: None,
             most_recent_version_number: None,
+            active_message_id: None,
         };
 
         let mut conn = get_pg_pool().get().await.unwrap();
@@ -184,6 +185,7 @@ mod tests {
                 deleted_at: None,
                 created_by: user.id,
                 feedback: None,
+                parent_message_id: i.checked_sub(1).map(|prev| message_ids[prev]),
             };
 
             insert_into(messages::table)
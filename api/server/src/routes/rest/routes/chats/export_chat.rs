@@ -0,0 +1,55 @@
+use axum::{
+    extract::{Path, Query},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension,
+};
+use handlers::chats::{export_chat_handler, ExportChatRequest};
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+/// Downloads a chat as a Markdown or HTML document
+///
+/// GET /chats/:id/export?format=markdown|html&row_limit=100
+pub async fn export_chat_route(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    Query(request): Query<ExportChatRequest>,
+) -> Result<Response, (StatusCode, &'static str)> {
+    tracing::info!(
+        "Processing export for chat with ID: {}, user_id: {}, format: {:?}",
+        id,
+        user.id,
+        request.format
+    );
+
+    let export = match export_chat_handler(&id, &user, request).await {
+        Ok(export) => export,
+        Err(e) => {
+            tracing::error!("Error exporting chat: {}", e);
+            let error_message = e.to_string();
+
+            if error_message.contains("don't have permission") {
+                return Err((StatusCode::FORBIDDEN, "Permission denied"));
+            }
+            if error_message.contains("not found") {
+                return Err((StatusCode::NOT_FOUND, "Chat not found"));
+            }
+
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, "Failed to export chat"));
+        }
+    };
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, export.content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", export.file_name),
+            ),
+        ],
+        export.content,
+    )
+        .into_response())
+}
@@ -3,24 +3,32 @@ use axum::{
     Router,
 };
 
+mod cancel_chat;
 mod delete_chats;
 mod duplicate_chat;
+mod export_chat;
 mod get_chat;
 mod get_chat_raw_llm_messages;
 mod list_chats;
 mod post_chat;
 mod restore_chat;
 mod sharing;
+mod stream_chat;
+mod switch_chat_branch;
 mod update_chat;
 mod update_chats;
 
+pub use cancel_chat::cancel_chat_route;
 pub use delete_chats::delete_chats_route;
 pub use duplicate_chat::duplicate_chat_route;
+pub use export_chat::export_chat_route;
 pub use get_chat::get_chat_route;
 pub use get_chat_raw_llm_messages::get_chat_raw_llm_messages;
 pub use list_chats::list_chats_route;
 pub use post_chat::post_chat_route;
 pub use restore_chat::restore_chat_route;
+pub use stream_chat::{resume_chat_stream_route, stream_chat_route};
+pub use switch_chat_branch::switch_chat_branch_route;
 pub use update_chat::update_chat_route;
 pub use update_chats::update_chats_route;
 
@@ -31,9 +39,17 @@ pub fn router() -> Router {
         .route("/", put(update_chats_route))
         .route("/", delete(delete_chats_route))
         .route("/duplicate", post(duplicate_chat_route))
+        .route("/stream", post(stream_chat_route))
         .route("/:id", get(get_chat_route))
         .route("/:id", put(update_chat_route))
         .route("/:id/restore", put(restore_chat_route))
+        .route("/:id/branch", put(switch_chat_branch_route))
+        .route("/:id/export", get(export_chat_route))
+        .route("/:id/messages/:message_id/cancel", post(cancel_chat_route))
+        .route(
+            "/:id/messages/:message_id/stream",
+            get(resume_chat_stream_route),
+        )
         .route("/:id/raw_llm_messages", get(get_chat_raw_llm_messages))
         .nest("/:id/sharing", sharing::router())
 }
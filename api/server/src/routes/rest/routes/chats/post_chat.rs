@@ -80,6 +80,9 @@ pub async fn post_chat_route(
         Ok(response) => Ok(ApiResponse::JsonData(response)),
         Err(e) => {
             tracing::error!("Error processing chat: {}", e);
+            if e.to_string().contains("budget exceeded") {
+                return Err((StatusCode::PAYMENT_REQUIRED, "Monthly LLM budget exceeded"));
+            }
             Err((StatusCode::INTERNAL_SERVER_ERROR, "Failed to process chat"))
         }
     }
@@ -0,0 +1,293 @@
+use std::{convert::Infallible, time::Duration};
+
+use axum::extract::Path;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::{Extension, Json};
+use futures::stream::{self, Stream};
+use handlers::chats::chat_stream::{
+    read_chat_stream_after, resume_chat_stream_handler, ChatStreamEntry, ChatStreamRecord,
+    ChatStreamRecorder,
+};
+use handlers::chats::get_chat_handler;
+use handlers::chats::post_chat_handler::{
+    post_chat_handler, BusterContainer, ChatCreateNewChat, ThreadEvent,
+};
+use middleware::AuthenticatedUser;
+use serde::Serialize;
+use serde_json::json;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::post_chat::ChatCreateNewChatRequest;
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const EVENT_BUFFER: usize = 1000;
+
+fn event_name(event: ThreadEvent) -> &'static str {
+    match event {
+        ThreadEvent::GeneratingResponseMessage => "generating_response_message",
+        ThreadEvent::GeneratingReasoningMessage => "generating_reasoning_message",
+        ThreadEvent::GeneratingTitle => "generating_title",
+        ThreadEvent::InitializeChat => "initialize_chat",
+        ThreadEvent::Completed => "completed",
+    }
+}
+
+fn data_event<T: Serialize>(name: &str, data: &T, sequence_id: Option<String>) -> Event {
+    let event = match Event::default().event(name).json_data(data) {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::error!("Failed to serialize chat stream event: {}", e);
+            return error_event("Failed to serialize event", sequence_id);
+        }
+    };
+
+    match sequence_id {
+        Some(sequence_id) => event.id(sequence_id),
+        None => event,
+    }
+}
+
+fn error_event(message: &str, sequence_id: Option<String>) -> Event {
+    data_event("error", &json!({ "message": message }), sequence_id)
+}
+
+fn sse_response(rx: mpsc::Receiver<Event>) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let events = stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|event| (Ok(event), rx))
+    });
+
+    Sse::new(events).keep_alive(
+        KeepAlive::new()
+            .interval(HEARTBEAT_INTERVAL)
+            .text("heartbeat"),
+    )
+}
+
+/// Send a chat message and stream the response as Server-Sent Events
+///
+/// POST /api/v1/chats/stream
+///
+/// Takes the same body as `POST /chats`. Each event is named after its
+/// stage (`initialize_chat`, `generating_reasoning_message`,
+/// `generating_response_message`, `generating_title`, `completed` or
+/// `error`), carries the same JSON as the websocket stream and has an id
+/// that can be passed as `Last-Event-ID` to the resume route. The
+/// generation keeps running if the client disconnects.
+pub async fn stream_chat_route(
+    Extension(user): Extension<AuthenticatedUser>,
+    Json(request): Json<ChatCreateNewChatRequest>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, String)> {
+    let handler_request: ChatCreateNewChat = request.into();
+
+    if handler_request.asset_id.is_some() && handler_request.asset_type.is_none() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "asset_type must be provided when asset_id is specified".to_string(),
+        ));
+    }
+
+    let (event_tx, event_rx) = mpsc::channel(EVENT_BUFFER);
+
+    tokio::spawn(async move {
+        let (tx, mut rx) =
+            mpsc::channel::<anyhow::Result<(BusterContainer, ThreadEvent)>>(EVENT_BUFFER);
+
+        // Events are recorded even after the client leaves so it can resume
+        let forward_tx = event_tx.clone();
+        let forwarder = tokio::spawn(async move {
+            let mut recorder = ChatStreamRecorder::new();
+            let mut ended = false;
+
+            while let Some(result) = rx.recv().await {
+                let event = match result {
+                    Ok((container, event)) => {
+                        let sequence_id = recorder.record(&container, event).await;
+                        ended = event == ThreadEvent::Completed;
+                        data_event(event_name(event), &container, sequence_id)
+                    }
+                    Err(e) => {
+                        let message = format!("Error processing chat: {}", e);
+                        let sequence_id = recorder.record_failure(&message).await;
+                        ended = true;
+                        error_event(&message, sequence_id)
+                    }
+                };
+                let _ = forward_tx.send(event).await;
+            }
+
+            (recorder, ended)
+        });
+
+        let result = post_chat_handler(handler_request, user, Some(tx)).await;
+
+        let (recorder, ended) = match forwarder.await {
+            Ok(forwarded) => forwarded,
+            Err(e) => {
+                tracing::error!("Chat stream forwarder failed: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = result {
+            tracing::error!("Error processing chat: {}", e);
+            if !ended {
+                let message = format!("Error processing chat: {}", e);
+                let sequence_id = recorder.record_failure(&message).await;
+                let _ = event_tx.send(error_event(&message, sequence_id)).await;
+            }
+        }
+    });
+
+    Ok(sse_response(event_rx))
+}
+
+/// Resume the Server-Sent Events stream of a chat message
+///
+/// GET /api/v1/chats/:id/messages/:message_id/stream
+///
+/// Replays the events after the one named by the `Last-Event-ID` header, or
+/// all of them without it, then follows the generation if it is still
+/// running. The stream ends with a `completed` event carrying the stored
+/// chat, or an `error` event if the generation failed.
+pub async fn resume_chat_stream_route(
+    Path((chat_id, message_id)): Path<(Uuid, Uuid)>,
+    Extension(user): Extension<AuthenticatedUser>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, String)> {
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    let resume =
+        match resume_chat_stream_handler(&chat_id, &message_id, last_event_id.as_deref(), &user)
+            .await
+        {
+            Ok(resume) => resume,
+            Err(e) => {
+                let error_message = e.to_string();
+                tracing::error!(
+                    "Error resuming stream of message {} in chat {}: {}",
+                    message_id,
+                    chat_id,
+                    error_message
+                );
+
+                return if error_message.contains("not found") {
+                    Err((StatusCode::NOT_FOUND, error_message))
+                } else if error_message.contains("permission") {
+                    Err((StatusCode::FORBIDDEN, error_message))
+                } else if error_message.contains("Invalid sequence id") {
+                    Err((StatusCode::BAD_REQUEST, error_message))
+                } else {
+                    Err((
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "Failed to resume chat stream".to_string(),
+                    ))
+                };
+            }
+        };
+
+    let (event_tx, event_rx) = mpsc::channel(EVENT_BUFFER);
+
+    tokio::spawn(async move {
+        let mut last_sequence_id = last_event_id;
+        let mut entries = resume.entries;
+        let mut completed = resume.completed;
+        let mut chat = resume.chat;
+
+        loop {
+            for entry in entries {
+                let ChatStreamEntry {
+                    sequence_id,
+                    record,
+                } = entry;
+                last_sequence_id = Some(sequence_id.clone());
+
+                let event = match record {
+                    // The stored chat sent at the end is the final state
+                    ChatStreamRecord::Event {
+                        event: ThreadEvent::Completed,
+                        ..
+                    } => {
+                        completed = true;
+                        continue;
+                    }
+                    ChatStreamRecord::Event { event, container } => {
+                        data_event(event_name(event), &container, Some(sequence_id))
+                    }
+                    ChatStreamRecord::Failed { error } => {
+                        completed = true;
+                        error_event(&error, Some(sequence_id))
+                    }
+                };
+                if event_tx.send(event).await.is_err() {
+                    return;
+                }
+            }
+
+            if completed || event_tx.is_closed() {
+                break;
+            }
+
+            tokio::time::sleep(FOLLOW_POLL_INTERVAL).await;
+            entries = match read_chat_stream_after(&message_id, last_sequence_id.as_deref()).await {
+                Ok(Some(entries)) => entries,
+                // The stream expired, so the stored chat is all that's left
+                Ok(None) => {
+                    completed = true;
+                    vec![]
+                }
+                Err(e) => {
+                    tracing::error!("Error following stream of message {}: {}", message_id, e);
+                    let _ = event_tx
+                        .send(error_event("Failed to follow chat stream", None))
+                        .await;
+                    return;
+                }
+            };
+        }
+
+        if chat.is_none() {
+            chat = match get_chat_handler(&chat_id, &user, false).await {
+                Ok(chat) => Some(chat),
+                Err(e) => {
+                    tracing::error!("Error loading chat {} after its stream: {}", chat_id, e);
+                    None
+                }
+            };
+        }
+        if let Some(chat) = chat {
+            let _ = event_tx
+                .send(data_event(
+                    event_name(ThreadEvent::Completed),
+                    &chat,
+                    last_sequence_id,
+                ))
+                .await;
+        }
+    });
+
+    Ok(sse_response(event_rx))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_names_match_recorded_events() {
+        for event in [
+            ThreadEvent::GeneratingResponseMessage,
+            ThreadEvent::GeneratingReasoningMessage,
+            ThreadEvent::GeneratingTitle,
+            ThreadEvent::InitializeChat,
+            ThreadEvent::Completed,
+        ] {
+            assert_eq!(serde_json::to_value(event).unwrap(), event_name(event));
+        }
+    }
+}
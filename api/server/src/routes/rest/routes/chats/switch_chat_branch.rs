@@ -0,0 +1,49 @@
+use anyhow::Result;
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::Extension;
+use axum::Json;
+use handlers::chats::switch_chat_branch_handler::{
+    switch_chat_branch_handler, SwitchChatBranchRequest,
+};
+use handlers::chats::types::ChatWithMessages;
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use crate::routes::rest::ApiResponse;
+
+/// Switch the branch of a chat that is shown and continued
+///
+/// PUT /api/v1/chats/:id/branch
+///
+/// Takes the id of any message on the branch, usually one of the
+/// `sibling_message_ids` of an edited prompt, and returns the chat with that
+/// branch active.
+pub async fn switch_chat_branch_route(
+    Path(chat_id): Path<Uuid>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Json(request): Json<SwitchChatBranchRequest>,
+) -> Result<ApiResponse<ChatWithMessages>, (StatusCode, String)> {
+    match switch_chat_branch_handler(&chat_id, request, &user).await {
+        Ok(chat) => Ok(ApiResponse::JsonData(chat)),
+        Err(e) => {
+            let error_message = e.to_string();
+            tracing::error!(
+                "Error switching branch of chat {}: {}",
+                chat_id,
+                error_message
+            );
+
+            if error_message.contains("not found") {
+                Err((StatusCode::NOT_FOUND, error_message))
+            } else if error_message.contains("permission") {
+                Err((StatusCode::FORBIDDEN, error_message))
+            } else {
+                Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to switch chat branch".to_string(),
+                ))
+            }
+        }
+    }
+}
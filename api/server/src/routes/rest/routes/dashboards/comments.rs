@@ -0,0 +1,90 @@
+use axum::{extract::Path, http::StatusCode, Extension, Json};
+use database::enums::AssetType;
+use handlers::comments::{
+    create_comment_handler, delete_comment_handler, list_comments_handler,
+    update_comment_handler, AssetCommentResponse, CreateCommentRequest, UpdateCommentRequest,
+};
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use crate::routes::rest::ApiResponse;
+
+fn map_comment_error(error: anyhow::Error, fallback: &'static str) -> (StatusCode, &'static str) {
+    let error_message = error.to_string();
+
+    if error_message.contains("public_password required") {
+        return (StatusCode::IM_A_TEAPOT, "Password required for public access");
+    }
+    if error_message.contains("don't have permission") {
+        return (StatusCode::FORBIDDEN, "Permission denied");
+    }
+    if error_message.contains("Comment not found") || error_message.contains("Parent comment not found") {
+        return (StatusCode::NOT_FOUND, "Comment not found");
+    }
+    if error_message.contains("not found") {
+        return (StatusCode::NOT_FOUND, "Dashboard not found");
+    }
+    if error_message.contains("cannot be empty") {
+        return (StatusCode::BAD_REQUEST, "Comment cannot be empty");
+    }
+
+    (StatusCode::INTERNAL_SERVER_ERROR, fallback)
+}
+
+/// GET /dashboards/:id/comments
+pub async fn list_dashboard_comments_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+) -> Result<ApiResponse<Vec<AssetCommentResponse>>, (StatusCode, &'static str)> {
+    match list_comments_handler(&id, AssetType::DashboardFile, &user).await {
+        Ok(comments) => Ok(ApiResponse::JsonData(comments)),
+        Err(e) => {
+            tracing::error!("Error listing dashboard comments: {}", e);
+            Err(map_comment_error(e, "Failed to list comments"))
+        }
+    }
+}
+
+/// POST /dashboards/:id/comments
+pub async fn create_dashboard_comment_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<CreateCommentRequest>,
+) -> Result<ApiResponse<AssetCommentResponse>, (StatusCode, &'static str)> {
+    match create_comment_handler(&id, AssetType::DashboardFile, &user, request).await {
+        Ok(comment) => Ok(ApiResponse::JsonData(comment)),
+        Err(e) => {
+            tracing::error!("Error creating dashboard comment: {}", e);
+            Err(map_comment_error(e, "Failed to create comment"))
+        }
+    }
+}
+
+/// PUT /dashboards/:id/comments/:comment_id
+pub async fn update_dashboard_comment_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<UpdateCommentRequest>,
+) -> Result<ApiResponse<AssetCommentResponse>, (StatusCode, &'static str)> {
+    match update_comment_handler(&id, AssetType::DashboardFile, &comment_id, &user, request).await {
+        Ok(comment) => Ok(ApiResponse::JsonData(comment)),
+        Err(e) => {
+            tracing::error!("Error updating dashboard comment: {}", e);
+            Err(map_comment_error(e, "Failed to update comment"))
+        }
+    }
+}
+
+/// DELETE /dashboards/:id/comments/:comment_id
+pub async fn delete_dashboard_comment_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<ApiResponse<()>, (StatusCode, &'static str)> {
+    match delete_comment_handler(&id, AssetType::DashboardFile, &comment_id, &user).await {
+        Ok(()) => Ok(ApiResponse::NoContent),
+        Err(e) => {
+            tracing::error!("Error deleting dashboard comment: {}", e);
+            Err(map_comment_error(e, "Failed to delete comment"))
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use axum::{
+    extract::Path,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use handlers::dashboards::{export_dashboard_pdf_handler, ExportDashboardPdfRequest};
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+/// Exports a dashboard (optionally a specific version) as a PDF document
+///
+/// POST /dashboards/:id/export/pdf
+pub async fn export_dashboard_pdf_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<ExportDashboardPdfRequest>,
+) -> Result<Response, (StatusCode, &'static str)> {
+    tracing::info!(
+        "Processing PDF export for dashboard with ID: {}, user_id: {}, version_number: {:?}",
+        id,
+        user.id,
+        request.version_number
+    );
+
+    let export = match export_dashboard_pdf_handler(&id, &user, request).await {
+        Ok(export) => export,
+        Err(e) => {
+            tracing::error!("Error exporting dashboard PDF: {}", e);
+            let error_message = e.to_string();
+
+            if error_message.contains("public_password required") {
+                return Err((StatusCode::IM_A_TEAPOT, "Password required for public access"));
+            }
+            if error_message.contains("don't have permission") {
+                return Err((StatusCode::FORBIDDEN, "Permission denied"));
+            }
+            if error_message.contains("Version") && error_message.contains("not found") {
+                return Err((StatusCode::NOT_FOUND, "Version not found"));
+            }
+            if error_message.contains("not found") {
+                return Err((StatusCode::NOT_FOUND, "Dashboard not found"));
+            }
+
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, "Failed to export dashboard"));
+        }
+    };
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", export.file_name),
+            ),
+        ],
+        export.content,
+    )
+        .into_response())
+}
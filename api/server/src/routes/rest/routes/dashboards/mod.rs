@@ -5,12 +5,15 @@ use axum::{
 };
 
 // Modules for dashboard endpoints
+mod comments;
 mod create_dashboard;
 mod delete_dashboard;
+mod export_dashboard_pdf;
 mod get_dashboard;
 mod list_dashboards;
 mod sharing;
 mod update_dashboard;
+mod versions;
 
 pub fn router() -> Router {
     Router::new()
@@ -22,6 +25,32 @@ pub fn router() -> Router {
             delete(delete_dashboard::delete_dashboards_rest_handler),
         )
         .route("/", get(list_dashboards::list_dashboard_rest_handler))
+        .route(
+            "/:id/export/pdf",
+            post(export_dashboard_pdf::export_dashboard_pdf_rest_handler),
+        )
+        .route(
+            "/:id/versions/diff",
+            get(versions::get_dashboard_version_diff_rest_handler),
+        )
+        .route(
+            "/:id/versions/restores",
+            get(versions::list_dashboard_version_restores_rest_handler),
+        )
+        .route(
+            "/:id/versions/:version_number/restore",
+            post(versions::restore_dashboard_version_rest_handler),
+        )
+        .route(
+            "/:id/comments",
+            get(comments::list_dashboard_comments_rest_handler)
+                .post(comments::create_dashboard_comment_rest_handler),
+        )
+        .route(
+            "/:id/comments/:comment_id",
+            put(comments::update_dashboard_comment_rest_handler)
+                .delete(comments::delete_dashboard_comment_rest_handler),
+        )
         .route(
             "/:id/sharing",
             get(sharing::list_dashboard_sharing_rest_handler),
@@ -0,0 +1,98 @@
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    Extension,
+};
+use database::{models::AssetVersionRestore, types::VersionDiff};
+use handlers::dashboards::{
+    get_dashboard_version_diff_handler, list_dashboard_version_restores_handler,
+    restore_dashboard_version_handler, BusterDashboardResponse,
+};
+use middleware::AuthenticatedUser;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::routes::rest::ApiResponse;
+
+#[derive(Debug, Deserialize)]
+pub struct VersionDiffQueryParams {
+    pub from: i32,
+    pub to: i32,
+}
+
+fn map_version_error(error: anyhow::Error, fallback: &'static str) -> (StatusCode, &'static str) {
+    let error_message = error.to_string();
+
+    if error_message.contains("public_password required") {
+        return (StatusCode::IM_A_TEAPOT, "Password required for public access");
+    }
+    if error_message.contains("don't have permission") {
+        return (StatusCode::FORBIDDEN, "Permission denied");
+    }
+    if error_message.contains("Version") && error_message.contains("not found") {
+        return (StatusCode::NOT_FOUND, "Version not found");
+    }
+    if error_message.contains("not found") {
+        return (StatusCode::NOT_FOUND, "Dashboard not found");
+    }
+
+    (StatusCode::INTERNAL_SERVER_ERROR, fallback)
+}
+
+/// GET /dashboards/:id/versions/diff?from=1&to=2
+pub async fn get_dashboard_version_diff_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<VersionDiffQueryParams>,
+) -> Result<ApiResponse<VersionDiff>, (StatusCode, &'static str)> {
+    tracing::info!(
+        "Processing version diff for dashboard with ID: {}, user_id: {}, from: {}, to: {}",
+        id,
+        user.id,
+        params.from,
+        params.to
+    );
+
+    match get_dashboard_version_diff_handler(&id, &user, params.from, params.to).await {
+        Ok(diff) => Ok(ApiResponse::JsonData(diff)),
+        Err(e) => {
+            tracing::error!("Error diffing dashboard versions: {}", e);
+            Err(map_version_error(e, "Failed to diff dashboard versions"))
+        }
+    }
+}
+
+/// POST /dashboards/:id/versions/:version_number/restore
+pub async fn restore_dashboard_version_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((id, version_number)): Path<(Uuid, i32)>,
+) -> Result<ApiResponse<BusterDashboardResponse>, (StatusCode, &'static str)> {
+    tracing::info!(
+        "Processing restore for dashboard with ID: {}, user_id: {}, version_number: {}",
+        id,
+        user.id,
+        version_number
+    );
+
+    match restore_dashboard_version_handler(&id, &user, version_number).await {
+        Ok(dashboard) => Ok(ApiResponse::JsonData(dashboard)),
+        Err(e) => {
+            tracing::error!("Error restoring dashboard version: {}", e);
+            Err(map_version_error(e, "Failed to restore dashboard version"))
+        }
+    }
+}
+
+/// GET /dashboards/:id/versions/restores
+pub async fn list_dashboard_version_restores_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+) -> Result<ApiResponse<Vec<AssetVersionRestore>>, (StatusCode, &'static str)> {
+    match list_dashboard_version_restores_handler(&id, &user).await {
+        Ok(restores) => Ok(ApiResponse::JsonData(restores)),
+        Err(e) => {
+            tracing::error!("Error listing dashboard version restores: {}", e);
+            Err(map_version_error(e, "Failed to list dashboard version restores"))
+        }
+    }
+}
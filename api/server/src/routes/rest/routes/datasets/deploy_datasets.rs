@@ -8,17 +8,24 @@ use diesel_async::RunQueryDsl;
 use middleware::AuthenticatedUser;
 use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 use stored_values::jobs::{setup_sync_job, sync_distinct_values_chunk};
+use dataset_retrieval::embeddings::{
+    column_embedding_text, dataset_embedding_text, sync_dataset_embeddings,
+    ColumnEmbeddingInput, DatasetEmbeddingInput,
+};
 use tracing::{error, info, warn};
 
 // Import from handlers library
 use handlers::utils::user::user_info::get_user_organization_id;
+use handlers::webhooks::emit_webhook_event;
 
 use crate::{
     database::{
-        enums::DatasetType,
+        enums::{DatasetType, ModelPurpose, WebhookEventType},
+        helpers::model_configs::get_organization_models,
         models::{DataSource, Dataset, DatasetColumn},
         pool::get_pg_pool,
         schema::{data_sources, dataset_columns, datasets},
@@ -530,6 +537,42 @@ async fn deploy_datasets_handler(
                 }
             };
 
+            // Embed the deployed datasets and their columns for catalog search
+            let embedding_inputs: Vec<DatasetEmbeddingInput> = datasets_to_upsert
+                .iter()
+                .filter_map(|dataset| {
+                    final_dataset_ids
+                        .get(&dataset.database_name)
+                        .map(|id| DatasetEmbeddingInput {
+                            dataset_id: *id,
+                            organization_id,
+                            text: dataset_embedding_text(
+                                &dataset.name,
+                                dataset.when_to_use.as_deref(),
+                                dataset.yml_file.as_deref(),
+                            ),
+                            columns: columns_to_upsert_map
+                                .get(&dataset.database_name)
+                                .map(|columns| {
+                                    columns
+                                        .iter()
+                                        .map(|column| ColumnEmbeddingInput {
+                                            name: column.name.clone(),
+                                            text: column_embedding_text(
+                                                &dataset.name,
+                                                &column.name,
+                                                column.description.as_deref(),
+                                                Some(&column.type_),
+                                            ),
+                                        })
+                                        .collect()
+                                })
+                                .unwrap_or_default(),
+                        })
+                })
+                .collect();
+            spawn_dataset_embeddings(organization_id, embedding_inputs);
+
             // Bulk upsert columns for each dataset that was successfully upserted
             for (dataset_name, columns_for_dataset) in columns_to_upsert_map {
                 let dataset_id = match final_dataset_ids.get(&dataset_name) {
@@ -764,9 +807,58 @@ async fn deploy_datasets_handler(
         }
     } // End of loop through data_source_groups
 
+    let deployed: Vec<_> = results
+        .iter()
+        .filter(|result| result.success)
+        .map(|result| {
+            json!({
+                "name": result.model_name,
+                "schema": result.schema,
+                "data_source_name": result.data_source_name,
+            })
+        })
+        .collect();
+    if !deployed.is_empty() {
+        emit_webhook_event(
+            organization_id,
+            WebhookEventType::DatasetDeployed,
+            json!({ "datasets": deployed, "actor_id": user_id }),
+        );
+    }
+
     Ok(results)
 }
 
+/// Embeds deployed datasets and their columns in the background so deploys
+/// don't wait on the embedding model; only changed content is re-embedded, and
+/// failures only degrade catalog search to lexical ranking
+fn spawn_dataset_embeddings(organization_id: Uuid, inputs: Vec<DatasetEmbeddingInput>) {
+    if inputs.is_empty() {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let model = match get_organization_models(&organization_id).await {
+            Ok(models) => models.model_for(ModelPurpose::Embeddings),
+            Err(e) => {
+                error!(%organization_id, "Failed to load embedding model for datasets: {}", e);
+                return;
+            }
+        };
+
+        match sync_dataset_embeddings(&inputs, &model).await {
+            Ok(stats) => info!(
+                %organization_id,
+                embedded = stats.embedded,
+                unchanged = stats.unchanged,
+                removed = stats.removed,
+                "Synced embeddings of deployed datasets"
+            ),
+            Err(e) => error!(%organization_id, "Failed to embed deployed datasets: {}", e),
+        }
+    });
+}
+
 // --- Local Struct Definitions --- (No import needed for these within this file)
 #[derive(Debug, Serialize, Clone)] // Make Cloneable if needed by results.push(validation)
 pub struct ValidationResult {
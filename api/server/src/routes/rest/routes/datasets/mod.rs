@@ -6,6 +6,7 @@ mod get_dataset;
 mod get_dataset_data_sample;
 mod list_datasets;
 mod post_dataset;
+mod reviewers;
 
 use axum::{
     routing::{get, post, delete},
@@ -24,5 +25,9 @@ pub fn router() -> Router {
             "/:dataset_id/data/sample",
             get(get_dataset_data_sample::get_dataset_data_sample),
         )
+        .route(
+            "/:dataset_id/reviewers",
+            get(reviewers::list_dataset_reviewers).put(reviewers::put_dataset_reviewers),
+        )
         .nest("/:dataset_id", assets::router())
 }
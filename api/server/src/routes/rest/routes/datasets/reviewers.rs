@@ -0,0 +1,54 @@
+use axum::{extract::Path, http::StatusCode, Extension, Json};
+use handlers::metrics::{
+    list_dataset_reviewers_handler, set_dataset_reviewers_handler, MetricReviewer,
+    SetDatasetReviewersRequest,
+};
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use crate::routes::rest::ApiResponse;
+
+fn map_reviewers_error(error: anyhow::Error, fallback: &'static str) -> (StatusCode, String) {
+    let error_message = error.to_string();
+
+    if error_message.contains("don't have permission") {
+        return (StatusCode::FORBIDDEN, error_message);
+    }
+    if error_message.contains("not found") {
+        return (StatusCode::NOT_FOUND, "Dataset not found".to_string());
+    }
+    if error_message.contains("must be members") {
+        return (StatusCode::BAD_REQUEST, error_message);
+    }
+
+    (StatusCode::INTERNAL_SERVER_ERROR, fallback.to_string())
+}
+
+/// GET /datasets/:dataset_id/reviewers
+pub async fn list_dataset_reviewers(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(dataset_id): Path<Uuid>,
+) -> Result<ApiResponse<Vec<MetricReviewer>>, (StatusCode, String)> {
+    match list_dataset_reviewers_handler(&dataset_id, &user).await {
+        Ok(reviewers) => Ok(ApiResponse::JsonData(reviewers)),
+        Err(e) => {
+            tracing::error!("Error listing dataset reviewers: {}", e);
+            Err(map_reviewers_error(e, "Failed to list dataset reviewers"))
+        }
+    }
+}
+
+/// PUT /datasets/:dataset_id/reviewers
+pub async fn put_dataset_reviewers(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(dataset_id): Path<Uuid>,
+    Json(request): Json<SetDatasetReviewersRequest>,
+) -> Result<ApiResponse<Vec<MetricReviewer>>, (StatusCode, String)> {
+    match set_dataset_reviewers_handler(&dataset_id, &user, request).await {
+        Ok(reviewers) => Ok(ApiResponse::JsonData(reviewers)),
+        Err(e) => {
+            tracing::error!("Error updating dataset reviewers: {}", e);
+            Err(map_reviewers_error(e, "Failed to update dataset reviewers"))
+        }
+    }
+}
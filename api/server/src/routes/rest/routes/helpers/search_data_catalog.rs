@@ -1,9 +1,9 @@
 use axum::{routing::post, Extension, Json, Router};
-use cohere_rust::{
-    api::rerank::{ReRankModel, ReRankRequest},
-    Cohere,
+use dataset_retrieval::{HybridRetriever, RetrievalDocument, Retriever};
+use database::{
+    enums::ModelPurpose, helpers::model_configs::get_user_models, pool::get_pg_pool,
+    schema::datasets,
 };
-use database::{pool::get_pg_pool, schema::datasets};
 use diesel::prelude::*;
 use diesel_async::RunQueryDsl;
 use futures::stream::{self, StreamExt};
@@ -11,6 +11,7 @@ use litellm::{AgentMessage, ChatCompletionRequest, LiteLLMClient, Metadata, Resp
 use middleware::types::AuthenticatedUser;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
@@ -146,10 +147,16 @@ async fn handle_search_data_catalog(
         return ApiResponse::JsonData(SearchDataCatalogResponse { results: vec![] });
     }
 
-    // Extract YML content for reranking
-    let documents: Vec<String> = datasets
+    // Extract YML content for retrieval
+    let documents: Vec<RetrievalDocument> = datasets
         .iter()
-        .filter_map(|dataset| dataset.yml_content.clone())
+        .filter_map(|dataset| {
+            dataset.yml_content.as_ref().map(|yml| RetrievalDocument {
+                id: dataset.id,
+                name: dataset.name.clone(),
+                content: yml.clone(),
+            })
+        })
         .collect();
 
     if documents.is_empty() {
@@ -160,10 +167,20 @@ async fn handle_search_data_catalog(
     // Store user_request for passing to process_query
     let user_request = request.user_request.clone();
 
-    // Process all queries concurrently using Cohere reranking
+    let embedding_model = match get_user_models(&user.id).await {
+        Ok(models) => models.model_for(ModelPurpose::Embeddings),
+        Err(e) => {
+            error!("Failed to load organization models: {}", e);
+            return ApiResponse::JsonData(SearchDataCatalogResponse { results: vec![] });
+        }
+    };
+    let retriever = Arc::new(HybridRetriever::from_env(&embedding_model));
+
+    // Process all queries concurrently
     let ranked_datasets_futures = stream::iter(request.queries)
         .map(|query| {
             process_query(
+                retriever.clone(),
                 query,
                 datasets.clone(),
                 documents.clone(),
@@ -209,14 +226,16 @@ async fn get_datasets_for_organization(org_id: Uuid) -> Result<Vec<Dataset>, any
 }
 
 async fn process_query(
+    retriever: Arc<HybridRetriever>,
     query: String,
     all_datasets: Vec<Dataset>,
-    documents: Vec<String>,
+    documents: Vec<RetrievalDocument>,
     user: &AuthenticatedUser,
     user_request: Option<String>,
 ) -> Result<Vec<DatasetResult>, anyhow::Error> {
-    // Step 1: Rerank datasets using Cohere
-    let ranked_datasets = rerank_datasets(&query, &all_datasets, &documents).await?;
+    // Step 1: Rank datasets with the configured retriever
+    let ranked_datasets =
+        rerank_datasets(retriever.as_ref(), &query, &all_datasets, &documents).await?;
 
     if ranked_datasets.is_empty() {
         info!(
@@ -234,50 +253,28 @@ async fn process_query(
 }
 
 async fn rerank_datasets(
+    retriever: &dyn Retriever,
     query: &str,
     all_datasets: &[Dataset],
-    documents: &[String],
+    documents: &[RetrievalDocument],
 ) -> Result<Vec<RankedDataset>, anyhow::Error> {
-    // Initialize Cohere client
-    let co = Cohere::default();
-
-    // Create rerank request
-    let request = ReRankRequest {
-        query,
-        documents,
-        model: ReRankModel::EnglishV3,
-        top_n: Some(30),
-        ..Default::default()
-    };
-
-    // Get reranked results
-    let rerank_results = co.rerank(&request).await?;
-
-    // Map results back to datasets
-    let mut ranked_datasets = Vec::new();
-    for result in rerank_results {
-        if let Some(dataset) = all_datasets.get(result.index as usize) {
-            ranked_datasets.push(RankedDataset {
-                dataset: dataset.clone(),
-                relevance_score: result.relevance_score,
-            });
-        } else {
-            error!("Invalid dataset index from Cohere: {}", result.index);
-        }
-    }
+    let retrieved = retriever.retrieve(query, documents, 30).await?;
 
-    // Sort by relevance score (highest first)
-    ranked_datasets.sort_by(|a, b| {
-        b.relevance_score
-            .partial_cmp(&a.relevance_score)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    // Map results back to datasets, most relevant first
+    let datasets_by_id: HashMap<Uuid, &Dataset> =
+        all_datasets.iter().map(|dataset| (dataset.id, dataset)).collect();
 
-    // Only keep results with meaningful relevance scores
-    // This threshold is arbitrary and may need tuning
-    let relevant_datasets = ranked_datasets.into_iter().collect::<Vec<_>>();
+    let ranked_datasets = retrieved
+        .into_iter()
+        .filter_map(|scored| {
+            datasets_by_id.get(&scored.id).map(|dataset| RankedDataset {
+                dataset: (*dataset).clone(),
+                relevance_score: scored.score,
+            })
+        })
+        .collect();
 
-    Ok(relevant_datasets)
+    Ok(ranked_datasets)
 }
 
 async fn filter_datasets_with_llm(
@@ -317,10 +314,11 @@ async fn filter_datasets_with_llm(
 
     // Initialize LiteLLM client
     let llm_client = LiteLLMClient::new(None, None);
+    let models = get_user_models(&user.id).await?;
 
     // Create the request
     let request = ChatCompletionRequest {
-        model: "gemini-2.0-flash-001".to_string(), // Using a small model for cost efficiency
+        model: models.model_for(ModelPurpose::CatalogFiltering),
         messages: vec![AgentMessage::User {
             id: None,
             content: prompt,
@@ -336,6 +334,8 @@ async fn filter_datasets_with_llm(
             user_id: user.id.to_string(),
             session_id: Uuid::new_v4().to_string(),
             trace_id: Uuid::new_v4().to_string(),
+            message_id: None,
+            organization_id: models.organization_id().map(|id| id.to_string()),
         }),
         // reasoning_effort: Some(String::from("low")),
         max_completion_tokens: Some(8096),
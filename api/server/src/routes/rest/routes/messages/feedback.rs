@@ -0,0 +1,40 @@
+use axum::{
+    extract::{Json, Path},
+    http::StatusCode,
+    Extension,
+};
+use handlers::feedback::{
+    submit_message_feedback_handler, FeedbackResponse, SubmitFeedbackRequest,
+};
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use crate::routes::rest::ApiResponse;
+
+/// Rate a message, optionally with a category, comment and corrected SQL
+///
+/// Admins review the feedback and may promote it to a verified example.
+pub async fn submit_message_feedback_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(message_id): Path<Uuid>,
+    Json(request): Json<SubmitFeedbackRequest>,
+) -> Result<ApiResponse<FeedbackResponse>, (StatusCode, &'static str)> {
+    match submit_message_feedback_handler(&message_id, &user, request).await {
+        Ok(feedback) => Ok(ApiResponse::JsonData(feedback)),
+        Err(e) => {
+            tracing::error!("Error submitting message feedback: {}", e);
+
+            let error_message = e.to_string();
+            if error_message.contains("don't have permission") {
+                Err((StatusCode::FORBIDDEN, "Permission denied"))
+            } else if error_message.contains("not found") {
+                Err((StatusCode::NOT_FOUND, "Message not found"))
+            } else {
+                Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to submit feedback",
+                ))
+            }
+        }
+    }
+}
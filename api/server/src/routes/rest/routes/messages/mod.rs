@@ -1,12 +1,14 @@
 use axum::{
-    routing::{delete, put},
+    routing::{delete, post, put},
     Router,
 };
 
 mod delete_message;
+mod feedback;
 mod update_message;
 
 use delete_message::delete_message_rest_handler;
+use feedback::submit_message_feedback_rest_handler;
 use update_message::update_message_rest_handler;
 
 /// Create a router for message-related endpoints
@@ -16,4 +18,6 @@ pub fn router() -> Router {
         .route("/:id", delete(delete_message_rest_handler))
         // Update a message
         .route("/:id", put(update_message_rest_handler))
+        // Rate a message for the review queue
+        .route("/:id/feedback", post(submit_message_feedback_rest_handler))
 }
\ No newline at end of file
@@ -0,0 +1,90 @@
+use axum::{extract::Path, http::StatusCode, Extension, Json};
+use handlers::metrics::{
+    create_metric_annotation_handler, delete_metric_annotation_handler,
+    list_metric_annotations_handler, update_metric_annotation_handler, BusterMetricAnnotation,
+    CreateMetricAnnotationRequest, UpdateMetricAnnotationRequest,
+};
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use crate::routes::rest::ApiResponse;
+
+fn map_annotation_error(error: anyhow::Error, fallback: &'static str) -> (StatusCode, &'static str) {
+    let error_message = error.to_string();
+
+    if error_message.contains("public_password required") {
+        return (StatusCode::IM_A_TEAPOT, "Password required for public access");
+    }
+    if error_message.contains("don't have permission") {
+        return (StatusCode::FORBIDDEN, "Permission denied");
+    }
+    if error_message.contains("Annotation not found") {
+        return (StatusCode::NOT_FOUND, "Annotation not found");
+    }
+    if error_message.contains("not found") {
+        return (StatusCode::NOT_FOUND, "Metric not found");
+    }
+    if error_message.contains("x_value") || error_message.contains("cannot be empty") {
+        return (StatusCode::BAD_REQUEST, "Invalid annotation");
+    }
+
+    (StatusCode::INTERNAL_SERVER_ERROR, fallback)
+}
+
+/// GET /metrics/:id/annotations
+pub async fn list_metric_annotations_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+) -> Result<ApiResponse<Vec<BusterMetricAnnotation>>, (StatusCode, &'static str)> {
+    match list_metric_annotations_handler(&id, &user).await {
+        Ok(annotations) => Ok(ApiResponse::JsonData(annotations)),
+        Err(e) => {
+            tracing::error!("Error listing metric annotations: {}", e);
+            Err(map_annotation_error(e, "Failed to list annotations"))
+        }
+    }
+}
+
+/// POST /metrics/:id/annotations
+pub async fn create_metric_annotation_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<CreateMetricAnnotationRequest>,
+) -> Result<ApiResponse<BusterMetricAnnotation>, (StatusCode, &'static str)> {
+    match create_metric_annotation_handler(&id, &user, request).await {
+        Ok(annotation) => Ok(ApiResponse::JsonData(annotation)),
+        Err(e) => {
+            tracing::error!("Error creating metric annotation: {}", e);
+            Err(map_annotation_error(e, "Failed to create annotation"))
+        }
+    }
+}
+
+/// PUT /metrics/:id/annotations/:annotation_id
+pub async fn update_metric_annotation_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((id, annotation_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<UpdateMetricAnnotationRequest>,
+) -> Result<ApiResponse<BusterMetricAnnotation>, (StatusCode, &'static str)> {
+    match update_metric_annotation_handler(&id, &annotation_id, &user, request).await {
+        Ok(annotation) => Ok(ApiResponse::JsonData(annotation)),
+        Err(e) => {
+            tracing::error!("Error updating metric annotation: {}", e);
+            Err(map_annotation_error(e, "Failed to update annotation"))
+        }
+    }
+}
+
+/// DELETE /metrics/:id/annotations/:annotation_id
+pub async fn delete_metric_annotation_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((id, annotation_id)): Path<(Uuid, Uuid)>,
+) -> Result<ApiResponse<()>, (StatusCode, &'static str)> {
+    match delete_metric_annotation_handler(&id, &annotation_id, &user).await {
+        Ok(()) => Ok(ApiResponse::NoContent),
+        Err(e) => {
+            tracing::error!("Error deleting metric annotation: {}", e);
+            Err(map_annotation_error(e, "Failed to delete annotation"))
+        }
+    }
+}
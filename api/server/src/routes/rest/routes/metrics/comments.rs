@@ -0,0 +1,90 @@
+use axum::{extract::Path, http::StatusCode, Extension, Json};
+use database::enums::AssetType;
+use handlers::comments::{
+    create_comment_handler, delete_comment_handler, list_comments_handler,
+    update_comment_handler, AssetCommentResponse, CreateCommentRequest, UpdateCommentRequest,
+};
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use crate::routes::rest::ApiResponse;
+
+fn map_comment_error(error: anyhow::Error, fallback: &'static str) -> (StatusCode, &'static str) {
+    let error_message = error.to_string();
+
+    if error_message.contains("public_password required") {
+        return (StatusCode::IM_A_TEAPOT, "Password required for public access");
+    }
+    if error_message.contains("don't have permission") {
+        return (StatusCode::FORBIDDEN, "Permission denied");
+    }
+    if error_message.contains("Comment not found") || error_message.contains("Parent comment not found") {
+        return (StatusCode::NOT_FOUND, "Comment not found");
+    }
+    if error_message.contains("not found") {
+        return (StatusCode::NOT_FOUND, "Metric not found");
+    }
+    if error_message.contains("cannot be empty") {
+        return (StatusCode::BAD_REQUEST, "Comment cannot be empty");
+    }
+
+    (StatusCode::INTERNAL_SERVER_ERROR, fallback)
+}
+
+/// GET /metrics/:id/comments
+pub async fn list_metric_comments_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+) -> Result<ApiResponse<Vec<AssetCommentResponse>>, (StatusCode, &'static str)> {
+    match list_comments_handler(&id, AssetType::MetricFile, &user).await {
+        Ok(comments) => Ok(ApiResponse::JsonData(comments)),
+        Err(e) => {
+            tracing::error!("Error listing metric comments: {}", e);
+            Err(map_comment_error(e, "Failed to list comments"))
+        }
+    }
+}
+
+/// POST /metrics/:id/comments
+pub async fn create_metric_comment_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<CreateCommentRequest>,
+) -> Result<ApiResponse<AssetCommentResponse>, (StatusCode, &'static str)> {
+    match create_comment_handler(&id, AssetType::MetricFile, &user, request).await {
+        Ok(comment) => Ok(ApiResponse::JsonData(comment)),
+        Err(e) => {
+            tracing::error!("Error creating metric comment: {}", e);
+            Err(map_comment_error(e, "Failed to create comment"))
+        }
+    }
+}
+
+/// PUT /metrics/:id/comments/:comment_id
+pub async fn update_metric_comment_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<UpdateCommentRequest>,
+) -> Result<ApiResponse<AssetCommentResponse>, (StatusCode, &'static str)> {
+    match update_comment_handler(&id, AssetType::MetricFile, &comment_id, &user, request).await {
+        Ok(comment) => Ok(ApiResponse::JsonData(comment)),
+        Err(e) => {
+            tracing::error!("Error updating metric comment: {}", e);
+            Err(map_comment_error(e, "Failed to update comment"))
+        }
+    }
+}
+
+/// DELETE /metrics/:id/comments/:comment_id
+pub async fn delete_metric_comment_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<ApiResponse<()>, (StatusCode, &'static str)> {
+    match delete_comment_handler(&id, AssetType::MetricFile, &comment_id, &user).await {
+        Ok(()) => Ok(ApiResponse::NoContent),
+        Err(e) => {
+            tracing::error!("Error deleting metric comment: {}", e);
+            Err(map_comment_error(e, "Failed to delete comment"))
+        }
+    }
+}
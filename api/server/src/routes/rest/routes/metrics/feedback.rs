@@ -0,0 +1,36 @@
+use axum::{extract::Path, http::StatusCode, Extension, Json};
+use handlers::feedback::{submit_metric_feedback_handler, FeedbackResponse, SubmitFeedbackRequest};
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use crate::routes::rest::ApiResponse;
+
+/// POST /metrics/:id/feedback
+pub async fn submit_metric_feedback_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<SubmitFeedbackRequest>,
+) -> Result<ApiResponse<FeedbackResponse>, (StatusCode, &'static str)> {
+    match submit_metric_feedback_handler(&id, &user, request).await {
+        Ok(feedback) => Ok(ApiResponse::JsonData(feedback)),
+        Err(e) => {
+            tracing::error!("Error submitting metric feedback: {}", e);
+            let error_message = e.to_string();
+            if error_message.contains("public_password required") {
+                Err((
+                    StatusCode::IM_A_TEAPOT,
+                    "Password required for public access",
+                ))
+            } else if error_message.contains("don't have permission") {
+                Err((StatusCode::FORBIDDEN, "Permission denied"))
+            } else if error_message.contains("not found") {
+                Err((StatusCode::NOT_FOUND, "Metric not found"))
+            } else {
+                Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to submit feedback",
+                ))
+            }
+        }
+    }
+}
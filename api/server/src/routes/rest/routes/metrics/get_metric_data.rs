@@ -1,8 +1,9 @@
 use crate::routes::rest::ApiResponse;
 use axum::extract::{Path, Query};
 use axum::http::StatusCode;
-use axum::Extension;
+use axum::{Extension, Json};
 use handlers::metrics::get_metric_data_handler::{GetMetricDataRequest, MetricDataResponse};
+use handlers::metrics::DrillContext;
 use middleware::AuthenticatedUser;
 use serde::Deserialize;
 use uuid::Uuid;
@@ -29,8 +30,47 @@ pub async fn get_metric_data_rest_handler(
         version_number: params.version_number,
         limit: params.limit,
         password: params.password,
+        drill: None,
     };
 
+    metric_data_response(request, user).await
+}
+
+#[derive(Deserialize)]
+pub struct DrillMetricDataBody {
+    pub version_number: Option<i32>,
+    pub limit: Option<i64>,
+    pub password: Option<String>,
+    pub drill: Option<DrillContext>,
+}
+
+/// Fetches metric data for a clicked data point: either a drill target of the
+/// metric or, without a target, the metric cross-filtered on the clicked values
+pub async fn drill_metric_data_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(metric_id): Path<Uuid>,
+    Json(body): Json<DrillMetricDataBody>,
+) -> Result<ApiResponse<MetricDataResponse>, (StatusCode, String)> {
+    tracing::info!(
+        "Processing POST request for metric data with ID: {}",
+        metric_id
+    );
+
+    let request = GetMetricDataRequest {
+        metric_id,
+        version_number: body.version_number,
+        limit: body.limit,
+        password: body.password,
+        drill: body.drill,
+    };
+
+    metric_data_response(request, user).await
+}
+
+async fn metric_data_response(
+    request: GetMetricDataRequest,
+    user: AuthenticatedUser,
+) -> Result<ApiResponse<MetricDataResponse>, (StatusCode, String)> {
     match handlers::metrics::get_metric_data_handler(request, user).await {
         Ok(response) => Ok(ApiResponse::JsonData(response)),
         Err(e) => {
@@ -38,7 +78,9 @@ pub async fn get_metric_data_rest_handler(
             tracing::error!("Error getting metric data: {}", error_message);
             
             // Check for specific password-related errors
-            if error_message.contains("Incorrect password") || error_message.contains("public_password required") {
+            if error_message.contains("Invalid drill") {
+                Err((StatusCode::BAD_REQUEST, error_message))
+            } else if error_message.contains("Incorrect password") || error_message.contains("public_password required") {
                 Err((StatusCode::IM_A_TEAPOT, error_message))
             } else if error_message.contains("don't have permission") || error_message.contains("not found") || error_message.contains("expired") {
                 // Handle permission, not found, or expired errors with 403 Forbidden
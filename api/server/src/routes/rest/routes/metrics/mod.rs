@@ -4,13 +4,18 @@ use axum::{
 };
 
 // Import modules
+mod annotations;
 mod bulk_update_metrics;
+mod comments;
 mod delete_metric;
+mod feedback;
 mod get_metric;
 mod get_metric_data;
 mod list_metrics;
+mod review;
 mod sharing;
 mod update_metric;
+mod versions;
 
 pub fn router() -> Router {
     Router::new()
@@ -22,7 +27,54 @@ pub fn router() -> Router {
         .route("/", delete(delete_metric::delete_metrics_rest_handler))
         .route(
             "/:id/data",
-            get(get_metric_data::get_metric_data_rest_handler),
+            get(get_metric_data::get_metric_data_rest_handler)
+                .post(get_metric_data::drill_metric_data_rest_handler),
+        )
+        .route(
+            "/:id/versions/diff",
+            get(versions::get_metric_version_diff_rest_handler),
+        )
+        .route(
+            "/:id/versions/restores",
+            get(versions::list_metric_version_restores_rest_handler),
+        )
+        .route(
+            "/:id/versions/:version_number/restore",
+            post(versions::restore_metric_version_rest_handler),
+        )
+        .route("/:id/review", get(review::get_metric_review_rest_handler))
+        .route("/:id/review", post(review::transition_metric_review_rest_handler))
+        .route(
+            "/:id/review/comments",
+            post(review::add_metric_review_comment_rest_handler),
+        )
+        .route(
+            "/reviews/pending",
+            get(review::list_pending_metric_reviews_rest_handler),
+        )
+        .route(
+            "/:id/comments",
+            get(comments::list_metric_comments_rest_handler)
+                .post(comments::create_metric_comment_rest_handler),
+        )
+        .route(
+            "/:id/comments/:comment_id",
+            put(comments::update_metric_comment_rest_handler)
+                .delete(comments::delete_metric_comment_rest_handler),
+        )
+        .route(
+            "/:id/annotations",
+            get(annotations::list_metric_annotations_rest_handler)
+                .post(annotations::create_metric_annotation_rest_handler),
+        )
+        .route(
+            "/:id/annotations/:annotation_id",
+            put(annotations::update_metric_annotation_rest_handler)
+                .delete(annotations::delete_metric_annotation_rest_handler),
+        )
+        .route(
+            "/:id/feedback",
+            post(feedback::submit_metric_feedback_rest_handler),
         )
         .nest("/:id/sharing", sharing::router())
 }
@@ -0,0 +1,100 @@
+use axum::{extract::Path, http::StatusCode, Extension, Json};
+use handlers::metrics::{
+    add_metric_review_comment_handler, get_metric_review_handler,
+    list_pending_metric_reviews_handler, transition_metric_review_handler,
+    MetricReviewCommentRequest, MetricReviewResponse, MetricReviewTransitionRequest,
+    PendingMetricReview,
+};
+use middleware::AuthenticatedUser;
+use uuid::Uuid;
+
+use crate::routes::rest::ApiResponse;
+
+fn map_review_error(error: anyhow::Error, fallback: &'static str) -> (StatusCode, &'static str) {
+    let error_message = error.to_string();
+
+    if error_message.contains("don't have permission") {
+        return (StatusCode::FORBIDDEN, "Permission denied");
+    }
+    if error_message.contains("not found") {
+        return (StatusCode::NOT_FOUND, "Metric not found");
+    }
+    if error_message.contains("Cannot") {
+        return (StatusCode::CONFLICT, "Transition not allowed for the metric's current status");
+    }
+    if error_message.contains("changed concurrently") {
+        return (StatusCode::CONFLICT, "Metric status changed, please retry");
+    }
+    if error_message.contains("No reviewers") {
+        return (StatusCode::BAD_REQUEST, "No reviewers are configured for this metric's datasets");
+    }
+    if error_message.contains("comment is required") || error_message.contains("cannot be empty") {
+        return (StatusCode::BAD_REQUEST, "A comment is required");
+    }
+
+    (StatusCode::INTERNAL_SERVER_ERROR, fallback)
+}
+
+/// GET /metrics/:id/review
+pub async fn get_metric_review_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+) -> Result<ApiResponse<MetricReviewResponse>, (StatusCode, &'static str)> {
+    match get_metric_review_handler(&id, &user).await {
+        Ok(review) => Ok(ApiResponse::JsonData(review)),
+        Err(e) => {
+            tracing::error!("Error getting metric review: {}", e);
+            Err(map_review_error(e, "Failed to get metric review"))
+        }
+    }
+}
+
+/// POST /metrics/:id/review
+pub async fn transition_metric_review_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<MetricReviewTransitionRequest>,
+) -> Result<ApiResponse<MetricReviewResponse>, (StatusCode, &'static str)> {
+    tracing::info!(
+        "Processing review transition for metric with ID: {}, user_id: {}, action: {:?}",
+        id,
+        user.id,
+        request.action
+    );
+
+    match transition_metric_review_handler(&id, &user, request).await {
+        Ok(review) => Ok(ApiResponse::JsonData(review)),
+        Err(e) => {
+            tracing::error!("Error transitioning metric review: {}", e);
+            Err(map_review_error(e, "Failed to update metric review"))
+        }
+    }
+}
+
+/// POST /metrics/:id/review/comments
+pub async fn add_metric_review_comment_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<MetricReviewCommentRequest>,
+) -> Result<ApiResponse<MetricReviewResponse>, (StatusCode, &'static str)> {
+    match add_metric_review_comment_handler(&id, &user, request).await {
+        Ok(review) => Ok(ApiResponse::JsonData(review)),
+        Err(e) => {
+            tracing::error!("Error adding metric review comment: {}", e);
+            Err(map_review_error(e, "Failed to add review comment"))
+        }
+    }
+}
+
+/// GET /metrics/reviews/pending
+pub async fn list_pending_metric_reviews_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+) -> Result<ApiResponse<Vec<PendingMetricReview>>, (StatusCode, &'static str)> {
+    match list_pending_metric_reviews_handler(&user).await {
+        Ok(reviews) => Ok(ApiResponse::JsonData(reviews)),
+        Err(e) => {
+            tracing::error!("Error listing pending metric reviews: {}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, "Failed to list pending reviews"))
+        }
+    }
+}
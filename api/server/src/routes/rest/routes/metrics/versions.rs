@@ -0,0 +1,98 @@
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    Extension,
+};
+use database::{models::AssetVersionRestore, types::VersionDiff};
+use handlers::metrics::{
+    get_metric_version_diff_handler, list_metric_version_restores_handler,
+    restore_metric_version_handler, BusterMetric,
+};
+use middleware::AuthenticatedUser;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::routes::rest::ApiResponse;
+
+#[derive(Debug, Deserialize)]
+pub struct VersionDiffQueryParams {
+    pub from: i32,
+    pub to: i32,
+}
+
+fn map_version_error(error: anyhow::Error, fallback: &'static str) -> (StatusCode, &'static str) {
+    let error_message = error.to_string();
+
+    if error_message.contains("public_password required") {
+        return (StatusCode::IM_A_TEAPOT, "Password required for public access");
+    }
+    if error_message.contains("don't have permission") {
+        return (StatusCode::FORBIDDEN, "Permission denied");
+    }
+    if error_message.contains("Version") && error_message.contains("not found") {
+        return (StatusCode::NOT_FOUND, "Version not found");
+    }
+    if error_message.contains("not found") {
+        return (StatusCode::NOT_FOUND, "Metric not found");
+    }
+
+    (StatusCode::INTERNAL_SERVER_ERROR, fallback)
+}
+
+/// GET /metrics/:id/versions/diff?from=1&to=2
+pub async fn get_metric_version_diff_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<VersionDiffQueryParams>,
+) -> Result<ApiResponse<VersionDiff>, (StatusCode, &'static str)> {
+    tracing::info!(
+        "Processing version diff for metric with ID: {}, user_id: {}, from: {}, to: {}",
+        id,
+        user.id,
+        params.from,
+        params.to
+    );
+
+    match get_metric_version_diff_handler(&id, &user, params.from, params.to).await {
+        Ok(diff) => Ok(ApiResponse::JsonData(diff)),
+        Err(e) => {
+            tracing::error!("Error diffing metric versions: {}", e);
+            Err(map_version_error(e, "Failed to diff metric versions"))
+        }
+    }
+}
+
+/// POST /metrics/:id/versions/:version_number/restore
+pub async fn restore_metric_version_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((id, version_number)): Path<(Uuid, i32)>,
+) -> Result<ApiResponse<BusterMetric>, (StatusCode, &'static str)> {
+    tracing::info!(
+        "Processing restore for metric with ID: {}, user_id: {}, version_number: {}",
+        id,
+        user.id,
+        version_number
+    );
+
+    match restore_metric_version_handler(&id, &user, version_number).await {
+        Ok(metric) => Ok(ApiResponse::JsonData(metric)),
+        Err(e) => {
+            tracing::error!("Error restoring metric version: {}", e);
+            Err(map_version_error(e, "Failed to restore metric version"))
+        }
+    }
+}
+
+/// GET /metrics/:id/versions/restores
+pub async fn list_metric_version_restores_rest_handler(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+) -> Result<ApiResponse<Vec<AssetVersionRestore>>, (StatusCode, &'static str)> {
+    match list_metric_version_restores_handler(&id, &user).await {
+        Ok(restores) => Ok(ApiResponse::JsonData(restores)),
+        Err(e) => {
+            tracing::error!("Error listing metric version restores: {}", e);
+            Err(map_version_error(e, "Failed to list metric version restores"))
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use anyhow::Result;
+use axum::{extract::Path, http::StatusCode, Extension, Json};
+use uuid::Uuid;
+
+use handlers::organizations::{
+    create_custom_tool_handler, delete_custom_tool_handler, list_custom_tools_handler,
+    types::{CreateCustomToolRequest, CustomToolResponse, UpdateCustomToolRequest},
+    update_custom_tool_handler,
+};
+
+use crate::routes::rest::ApiResponse;
+use middleware::AuthenticatedUser;
+
+pub async fn list_custom_tools(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(organization_id): Path<Uuid>,
+) -> Result<ApiResponse<Vec<CustomToolResponse>>, (StatusCode, String)> {
+    match list_custom_tools_handler(&user, organization_id).await {
+        Ok(tools) => Ok(ApiResponse::JsonData(tools)),
+        Err(e) => {
+            tracing::error!("Error listing custom tools: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn create_custom_tool(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(organization_id): Path<Uuid>,
+    Json(payload): Json<CreateCustomToolRequest>,
+) -> Result<ApiResponse<CustomToolResponse>, (StatusCode, String)> {
+    match create_custom_tool_handler(&user, organization_id, payload).await {
+        Ok(tool) => Ok(ApiResponse::JsonData(tool)),
+        Err(e) => {
+            tracing::error!("Error creating custom tool: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn update_custom_tool(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((organization_id, tool_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateCustomToolRequest>,
+) -> Result<ApiResponse<CustomToolResponse>, (StatusCode, String)> {
+    match update_custom_tool_handler(&user, organization_id, tool_id, payload).await {
+        Ok(tool) => Ok(ApiResponse::JsonData(tool)),
+        Err(e) => {
+            tracing::error!("Error updating custom tool: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn delete_custom_tool(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((organization_id, tool_id)): Path<(Uuid, Uuid)>,
+) -> Result<ApiResponse<()>, (StatusCode, String)> {
+    match delete_custom_tool_handler(&user, organization_id, tool_id).await {
+        Ok(()) => Ok(ApiResponse::NoContent),
+        Err(e) => {
+            tracing::error!("Error deleting custom tool: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+fn map_error(e: anyhow::Error) -> (StatusCode, String) {
+    let message = e.to_string();
+    if message.contains("not a member") || message.contains("not a workspace admin") {
+        (StatusCode::FORBIDDEN, message)
+    } else if message.contains("not found") {
+        (StatusCode::NOT_FOUND, message)
+    } else if message.contains("already exists") {
+        (StatusCode::CONFLICT, message)
+    } else if message.starts_with("Invalid") || message.contains("has no tool named") {
+        (StatusCode::BAD_REQUEST, message)
+    } else if message.contains("Failed to reach MCP server") {
+        (StatusCode::BAD_GATEWAY, message)
+    } else {
+        (StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
@@ -0,0 +1,98 @@
+use anyhow::Result;
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    Extension, Json,
+};
+use uuid::Uuid;
+
+use handlers::feedback::{
+    create_verified_example_handler, delete_verified_example_handler, list_feedback_handler,
+    list_verified_examples_handler, review_feedback_handler, CreateVerifiedExampleRequest,
+    FeedbackResponse, ListFeedbackQuery, ReviewFeedbackRequest, ReviewFeedbackResponse,
+    VerifiedExampleResponse,
+};
+
+use crate::routes::rest::ApiResponse;
+use middleware::AuthenticatedUser;
+
+pub async fn list_feedback(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(organization_id): Path<Uuid>,
+    Query(query): Query<ListFeedbackQuery>,
+) -> Result<ApiResponse<Vec<FeedbackResponse>>, (StatusCode, String)> {
+    match list_feedback_handler(&user, organization_id, query).await {
+        Ok(feedback) => Ok(ApiResponse::JsonData(feedback)),
+        Err(e) => {
+            tracing::error!("Error listing feedback: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn review_feedback(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((organization_id, feedback_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<ReviewFeedbackRequest>,
+) -> Result<ApiResponse<ReviewFeedbackResponse>, (StatusCode, String)> {
+    match review_feedback_handler(&user, organization_id, feedback_id, payload).await {
+        Ok(review) => Ok(ApiResponse::JsonData(review)),
+        Err(e) => {
+            tracing::error!("Error reviewing feedback: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn list_verified_examples(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(organization_id): Path<Uuid>,
+) -> Result<ApiResponse<Vec<VerifiedExampleResponse>>, (StatusCode, String)> {
+    match list_verified_examples_handler(&user, organization_id).await {
+        Ok(examples) => Ok(ApiResponse::JsonData(examples)),
+        Err(e) => {
+            tracing::error!("Error listing verified examples: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn create_verified_example(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(organization_id): Path<Uuid>,
+    Json(payload): Json<CreateVerifiedExampleRequest>,
+) -> Result<ApiResponse<VerifiedExampleResponse>, (StatusCode, String)> {
+    match create_verified_example_handler(&user, organization_id, payload).await {
+        Ok(example) => Ok(ApiResponse::JsonData(example)),
+        Err(e) => {
+            tracing::error!("Error creating verified example: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn delete_verified_example(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((organization_id, example_id)): Path<(Uuid, Uuid)>,
+) -> Result<ApiResponse<()>, (StatusCode, String)> {
+    match delete_verified_example_handler(&user, organization_id, example_id).await {
+        Ok(()) => Ok(ApiResponse::NoContent),
+        Err(e) => {
+            tracing::error!("Error deleting verified example: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+fn map_error(e: anyhow::Error) -> (StatusCode, String) {
+    let message = e.to_string();
+    if message.contains("not a member") || message.contains("not a workspace or data admin") {
+        (StatusCode::FORBIDDEN, message)
+    } else if message.contains("not found") {
+        (StatusCode::NOT_FOUND, message)
+    } else if message.starts_with("Invalid") {
+        (StatusCode::BAD_REQUEST, message)
+    } else {
+        (StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
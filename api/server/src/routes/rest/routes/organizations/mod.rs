@@ -1,15 +1,77 @@
 use axum::{
-    routing::{get, post, put},
+    routing::{delete, get, post, put},
     Router,
 };
 
+mod custom_tools;
+mod feedback;
+mod models;
 pub mod post_organization;
+mod slack;
+mod terms;
 mod update_organization;
+mod usage;
 mod users;
+mod webhooks;
 
 pub fn router() -> Router {
     Router::new()
         .route("/:id/users", get(users::list_organization_users))
+        .route(
+            "/:id/models",
+            get(models::get_organization_models).put(models::update_organization_models),
+        )
+        .route("/:id/models/available", get(models::list_available_models))
+        .route(
+            "/:id/custom_tools",
+            get(custom_tools::list_custom_tools).post(custom_tools::create_custom_tool),
+        )
+        .route(
+            "/:id/custom_tools/:tool_id",
+            put(custom_tools::update_custom_tool).delete(custom_tools::delete_custom_tool),
+        )
+        .route(
+            "/:id/terms",
+            get(terms::list_terms).post(terms::create_term),
+        )
+        .route(
+            "/:id/terms/:term_id",
+            put(terms::update_term).delete(terms::delete_term),
+        )
+        .route("/:id/feedback", get(feedback::list_feedback))
+        .route("/:id/feedback/:feedback_id", put(feedback::review_feedback))
+        .route(
+            "/:id/examples",
+            get(feedback::list_verified_examples).post(feedback::create_verified_example),
+        )
+        .route(
+            "/:id/examples/:example_id",
+            delete(feedback::delete_verified_example),
+        )
+        .route(
+            "/:id/slack",
+            get(slack::get_slack_integration)
+                .put(slack::connect_slack)
+                .delete(slack::disconnect_slack),
+        )
+        .route(
+            "/:id/webhooks",
+            get(webhooks::list_webhooks).post(webhooks::create_webhook),
+        )
+        .route(
+            "/:id/webhooks/:webhook_id",
+            put(webhooks::update_webhook).delete(webhooks::delete_webhook),
+        )
+        .route(
+            "/:id/webhooks/:webhook_id/deliveries",
+            get(webhooks::list_webhook_deliveries),
+        )
+        .route("/:id/usage", get(usage::get_organization_usage))
+        .route("/:id/usage/chats/:chat_id", get(usage::get_chat_usage))
+        .route(
+            "/:id/usage/budget",
+            get(usage::get_organization_budget).put(usage::update_organization_budget),
+        )
         .route("/:id", put(update_organization::update_organization))
         .route("/", post(post_organization::post_organization))
 }
@@ -0,0 +1,69 @@
+use anyhow::Result;
+use axum::{extract::Path, http::StatusCode, Extension, Json};
+use uuid::Uuid;
+
+use handlers::organizations::{
+    get_organization_models_handler, list_available_models_handler,
+    types::{OrganizationModelsResponse, UpdateOrganizationModelsRequest},
+    update_organization_models_handler,
+};
+
+use crate::routes::rest::ApiResponse;
+use middleware::AuthenticatedUser;
+
+pub async fn get_organization_models(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(organization_id): Path<Uuid>,
+) -> Result<ApiResponse<OrganizationModelsResponse>, (StatusCode, String)> {
+    match get_organization_models_handler(&user, organization_id).await {
+        Ok(models) => Ok(ApiResponse::JsonData(models)),
+        Err(e) => {
+            tracing::error!("Error getting organization models: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn list_available_models(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(organization_id): Path<Uuid>,
+) -> Result<ApiResponse<Vec<String>>, (StatusCode, String)> {
+    match list_available_models_handler(&user, organization_id).await {
+        Ok(models) => Ok(ApiResponse::JsonData(models)),
+        Err(e) => {
+            tracing::error!("Error listing available models: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn update_organization_models(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(organization_id): Path<Uuid>,
+    Json(payload): Json<UpdateOrganizationModelsRequest>,
+) -> Result<ApiResponse<OrganizationModelsResponse>, (StatusCode, String)> {
+    if payload.models.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "No models to update".to_string()));
+    }
+
+    match update_organization_models_handler(&user, organization_id, payload).await {
+        Ok(models) => Ok(ApiResponse::JsonData(models)),
+        Err(e) => {
+            tracing::error!("Error updating organization models: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+fn map_error(e: anyhow::Error) -> (StatusCode, String) {
+    let message = e.to_string();
+    if message.contains("not a member") || message.contains("not a workspace admin") {
+        (StatusCode::FORBIDDEN, message)
+    } else if message.contains("not available on the LLM gateway") {
+        (StatusCode::BAD_REQUEST, message)
+    } else if message.contains("Failed to list gateway models") {
+        (StatusCode::BAD_GATEWAY, message)
+    } else {
+        (StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
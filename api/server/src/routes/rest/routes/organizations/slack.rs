@@ -0,0 +1,66 @@
+use anyhow::Result;
+use axum::{extract::Path, http::StatusCode, Extension, Json};
+use uuid::Uuid;
+
+use handlers::slack::{
+    connect_slack_handler, disconnect_slack_handler, get_slack_integration_handler,
+    ConnectSlackRequest, SlackIntegrationResponse,
+};
+
+use crate::routes::rest::ApiResponse;
+use middleware::AuthenticatedUser;
+
+pub async fn get_slack_integration(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(organization_id): Path<Uuid>,
+) -> Result<ApiResponse<SlackIntegrationResponse>, (StatusCode, String)> {
+    match get_slack_integration_handler(&user, organization_id).await {
+        Ok(integration) => Ok(ApiResponse::JsonData(integration)),
+        Err(e) => {
+            tracing::error!("Error getting Slack integration: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn connect_slack(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(organization_id): Path<Uuid>,
+    Json(payload): Json<ConnectSlackRequest>,
+) -> Result<ApiResponse<SlackIntegrationResponse>, (StatusCode, String)> {
+    match connect_slack_handler(&user, organization_id, payload).await {
+        Ok(integration) => Ok(ApiResponse::JsonData(integration)),
+        Err(e) => {
+            tracing::error!("Error connecting Slack: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn disconnect_slack(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(organization_id): Path<Uuid>,
+) -> Result<ApiResponse<()>, (StatusCode, String)> {
+    match disconnect_slack_handler(&user, organization_id).await {
+        Ok(()) => Ok(ApiResponse::NoContent),
+        Err(e) => {
+            tracing::error!("Error disconnecting Slack: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+fn map_error(e: anyhow::Error) -> (StatusCode, String) {
+    let message = e.to_string();
+    if message.contains("not a member") || message.contains("not a workspace admin") {
+        (StatusCode::FORBIDDEN, message)
+    } else if message.contains("not found") {
+        (StatusCode::NOT_FOUND, message)
+    } else if message.contains("already connected") {
+        (StatusCode::CONFLICT, message)
+    } else if message.starts_with("Invalid") {
+        (StatusCode::BAD_REQUEST, message)
+    } else {
+        (StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
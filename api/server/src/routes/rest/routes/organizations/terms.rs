@@ -0,0 +1,79 @@
+use anyhow::Result;
+use axum::{extract::Path, http::StatusCode, Extension, Json};
+use uuid::Uuid;
+
+use handlers::organizations::{
+    create_term_handler, delete_term_handler, list_terms_handler,
+    types::{CreateTermRequest, TermResponse, UpdateTermRequest},
+    update_term_handler,
+};
+
+use crate::routes::rest::ApiResponse;
+use middleware::AuthenticatedUser;
+
+pub async fn list_terms(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(organization_id): Path<Uuid>,
+) -> Result<ApiResponse<Vec<TermResponse>>, (StatusCode, String)> {
+    match list_terms_handler(&user, organization_id).await {
+        Ok(terms) => Ok(ApiResponse::JsonData(terms)),
+        Err(e) => {
+            tracing::error!("Error listing terms: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn create_term(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(organization_id): Path<Uuid>,
+    Json(payload): Json<CreateTermRequest>,
+) -> Result<ApiResponse<TermResponse>, (StatusCode, String)> {
+    match create_term_handler(&user, organization_id, payload).await {
+        Ok(term) => Ok(ApiResponse::JsonData(term)),
+        Err(e) => {
+            tracing::error!("Error creating term: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn update_term(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((organization_id, term_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateTermRequest>,
+) -> Result<ApiResponse<TermResponse>, (StatusCode, String)> {
+    match update_term_handler(&user, organization_id, term_id, payload).await {
+        Ok(term) => Ok(ApiResponse::JsonData(term)),
+        Err(e) => {
+            tracing::error!("Error updating term: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn delete_term(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((organization_id, term_id)): Path<(Uuid, Uuid)>,
+) -> Result<ApiResponse<()>, (StatusCode, String)> {
+    match delete_term_handler(&user, organization_id, term_id).await {
+        Ok(()) => Ok(ApiResponse::NoContent),
+        Err(e) => {
+            tracing::error!("Error deleting term: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+fn map_error(e: anyhow::Error) -> (StatusCode, String) {
+    let message = e.to_string();
+    if message.contains("not a member") || message.contains("not a workspace or data admin") {
+        (StatusCode::FORBIDDEN, message)
+    } else if message.contains("not found") {
+        (StatusCode::NOT_FOUND, message)
+    } else if message.starts_with("Invalid") {
+        (StatusCode::BAD_REQUEST, message)
+    } else {
+        (StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
@@ -0,0 +1,86 @@
+use anyhow::Result;
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    Extension, Json,
+};
+use uuid::Uuid;
+
+use handlers::organizations::{
+    get_chat_usage_handler, get_organization_budget_handler, get_organization_usage_handler,
+    types::{
+        ChatUsageResponse, LlmBudgetStatus, OrganizationUsageQuery, OrganizationUsageResponse,
+        UpdateOrganizationBudgetRequest,
+    },
+    update_organization_budget_handler,
+};
+
+use crate::routes::rest::ApiResponse;
+use middleware::AuthenticatedUser;
+
+pub async fn get_organization_usage(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(organization_id): Path<Uuid>,
+    Query(query): Query<OrganizationUsageQuery>,
+) -> Result<ApiResponse<OrganizationUsageResponse>, (StatusCode, String)> {
+    match get_organization_usage_handler(&user, organization_id, query).await {
+        Ok(usage) => Ok(ApiResponse::JsonData(usage)),
+        Err(e) => {
+            tracing::error!("Error getting organization usage: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn get_chat_usage(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((organization_id, chat_id)): Path<(Uuid, Uuid)>,
+) -> Result<ApiResponse<ChatUsageResponse>, (StatusCode, String)> {
+    match get_chat_usage_handler(&user, organization_id, chat_id).await {
+        Ok(usage) => Ok(ApiResponse::JsonData(usage)),
+        Err(e) => {
+            tracing::error!("Error getting chat usage: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn get_organization_budget(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(organization_id): Path<Uuid>,
+) -> Result<ApiResponse<Option<LlmBudgetStatus>>, (StatusCode, String)> {
+    match get_organization_budget_handler(&user, organization_id).await {
+        Ok(budget) => Ok(ApiResponse::JsonData(budget)),
+        Err(e) => {
+            tracing::error!("Error getting organization budget: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn update_organization_budget(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(organization_id): Path<Uuid>,
+    Json(payload): Json<UpdateOrganizationBudgetRequest>,
+) -> Result<ApiResponse<Option<LlmBudgetStatus>>, (StatusCode, String)> {
+    match update_organization_budget_handler(&user, organization_id, payload).await {
+        Ok(budget) => Ok(ApiResponse::JsonData(budget)),
+        Err(e) => {
+            tracing::error!("Error updating organization budget: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+fn map_error(e: anyhow::Error) -> (StatusCode, String) {
+    let message = e.to_string();
+    if message.contains("not a member") || message.contains("not a workspace admin") {
+        (StatusCode::FORBIDDEN, message)
+    } else if message.contains("Chat not found") {
+        (StatusCode::NOT_FOUND, message)
+    } else if message.contains("Invalid") {
+        (StatusCode::BAD_REQUEST, message)
+    } else {
+        (StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
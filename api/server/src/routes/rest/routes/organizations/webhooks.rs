@@ -0,0 +1,97 @@
+use anyhow::Result;
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    Extension, Json,
+};
+use uuid::Uuid;
+
+use handlers::webhooks::{
+    create_webhook_handler, delete_webhook_handler, list_webhook_deliveries_handler,
+    list_webhooks_handler, update_webhook_handler, CreateWebhookRequest,
+    ListWebhookDeliveriesQuery, UpdateWebhookRequest, WebhookDeliveryResponse, WebhookResponse,
+};
+
+use crate::routes::rest::ApiResponse;
+use middleware::AuthenticatedUser;
+
+pub async fn list_webhooks(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(organization_id): Path<Uuid>,
+) -> Result<ApiResponse<Vec<WebhookResponse>>, (StatusCode, String)> {
+    match list_webhooks_handler(&user, organization_id).await {
+        Ok(webhooks) => Ok(ApiResponse::JsonData(webhooks)),
+        Err(e) => {
+            tracing::error!("Error listing webhooks: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn create_webhook(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(organization_id): Path<Uuid>,
+    Json(payload): Json<CreateWebhookRequest>,
+) -> Result<ApiResponse<WebhookResponse>, (StatusCode, String)> {
+    match create_webhook_handler(&user, organization_id, payload).await {
+        Ok(webhook) => Ok(ApiResponse::JsonData(webhook)),
+        Err(e) => {
+            tracing::error!("Error creating webhook: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn update_webhook(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((organization_id, webhook_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateWebhookRequest>,
+) -> Result<ApiResponse<WebhookResponse>, (StatusCode, String)> {
+    match update_webhook_handler(&user, organization_id, webhook_id, payload).await {
+        Ok(webhook) => Ok(ApiResponse::JsonData(webhook)),
+        Err(e) => {
+            tracing::error!("Error updating webhook: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn delete_webhook(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((organization_id, webhook_id)): Path<(Uuid, Uuid)>,
+) -> Result<ApiResponse<()>, (StatusCode, String)> {
+    match delete_webhook_handler(&user, organization_id, webhook_id).await {
+        Ok(()) => Ok(ApiResponse::NoContent),
+        Err(e) => {
+            tracing::error!("Error deleting webhook: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+pub async fn list_webhook_deliveries(
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((organization_id, webhook_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<ListWebhookDeliveriesQuery>,
+) -> Result<ApiResponse<Vec<WebhookDeliveryResponse>>, (StatusCode, String)> {
+    match list_webhook_deliveries_handler(&user, organization_id, webhook_id, query).await {
+        Ok(deliveries) => Ok(ApiResponse::JsonData(deliveries)),
+        Err(e) => {
+            tracing::error!("Error listing webhook deliveries: {:?}", e);
+            Err(map_error(e))
+        }
+    }
+}
+
+fn map_error(e: anyhow::Error) -> (StatusCode, String) {
+    let message = e.to_string();
+    if message.contains("not a member") || message.contains("not a workspace admin") {
+        (StatusCode::FORBIDDEN, message)
+    } else if message.contains("not found") {
+        (StatusCode::NOT_FOUND, message)
+    } else if message.starts_with("Invalid") {
+        (StatusCode::BAD_REQUEST, message)
+    } else {
+        (StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
@@ -0,0 +1,36 @@
+use axum::{
+    body::Bytes,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use handlers::slack::{slack_events_handler, SlackEventResponse};
+use slack::{SIGNATURE_HEADER, TIMESTAMP_HEADER};
+
+/// Receives events from the Slack Events API
+///
+/// POST /slack/events
+pub async fn slack_events(
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, (StatusCode, String)> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+
+    match slack_events_handler(header(TIMESTAMP_HEADER), header(SIGNATURE_HEADER), &body).await {
+        Ok(SlackEventResponse::Challenge(challenge)) => Ok(challenge.into_response()),
+        Ok(SlackEventResponse::Accepted) => Ok(StatusCode::OK.into_response()),
+        Err(e) => {
+            let message = e.to_string();
+            tracing::error!("Error handling Slack event: {}", message);
+            if message.starts_with("Invalid Slack request") {
+                Err((StatusCode::UNAUTHORIZED, message))
+            } else if message.starts_with("Invalid") {
+                Err((StatusCode::BAD_REQUEST, message))
+            } else {
+                Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to handle Slack event".to_string(),
+                ))
+            }
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use axum::{routing::post, Router};
+
+mod events;
+
+/// Routes Slack calls directly; they authenticate with Slack's request
+/// signature instead of a Buster token
+pub fn router() -> Router {
+    Router::new().route("/events", post(events::slack_events))
+}
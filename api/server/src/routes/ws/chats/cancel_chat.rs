@@ -0,0 +1,61 @@
+use anyhow::Result;
+use handlers::chats::cancel_chat_handler::cancel_chat_handler;
+use middleware::AuthenticatedUser;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::routes::ws::{
+    chats::chats_router::{ChatEvent as WSThreadEvent, ChatsRoute},
+    ws::{WsErrorCode, WsEvent, WsResponseMessage, WsSendMethod},
+    ws_router::WsRoutes,
+    ws_utils::{send_error_message, send_ws_message},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct CancelChatRequest {
+    pub chat_id: Uuid,
+    pub message_id: Uuid,
+}
+
+/// Stops a message that is still generating
+///
+/// The generation's own stream ends with its usual `Complete` event once the
+/// partial state is saved; this only acknowledges the request.
+pub async fn cancel_chat(user: &AuthenticatedUser, request: CancelChatRequest) -> Result<()> {
+    match cancel_chat_handler(&request.chat_id, &request.message_id, user).await {
+        Ok(response) => {
+            let response = WsResponseMessage::new(
+                WsRoutes::Chats(ChatsRoute::Cancel),
+                WsEvent::Threads(WSThreadEvent::StoppingChat),
+                response,
+                None,
+                user,
+                WsSendMethod::All,
+            );
+
+            send_ws_message(&user.id.to_string(), &response).await
+        }
+        Err(e) => {
+            let message = e.to_string();
+            let code = if message.contains("not found") {
+                WsErrorCode::NotFound
+            } else if message.contains("permission") {
+                WsErrorCode::Unauthorized
+            } else if message.contains("already finished") {
+                WsErrorCode::BadRequest
+            } else {
+                WsErrorCode::InternalServerError
+            };
+
+            send_error_message(
+                &user.id.to_string(),
+                WsRoutes::Chats(ChatsRoute::Cancel),
+                WsEvent::Threads(WSThreadEvent::StoppingChat),
+                code,
+                message,
+                user,
+            )
+            .await
+        }
+    }
+}
@@ -8,12 +8,18 @@ use middleware::AuthenticatedUser;
 
 use crate::routes::ws::ws::SubscriptionRwLock;
 
+use super::cancel_chat::cancel_chat;
 use super::post_chat::post_thread;
+use super::resume_chat::resume_chat;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub enum ChatsRoute {
     #[serde(rename = "/chats/post")]
     Post,
+    #[serde(rename = "/chats/cancel")]
+    Cancel,
+    #[serde(rename = "/chats/resume")]
+    Resume,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -54,6 +60,8 @@ pub enum ChatEvent {
     GeneratingReasoningMessage,
     Complete,
     GetChat,
+    StoppingChat,
+    ResumeChat,
 }
 
 pub async fn chats_router(
@@ -69,6 +77,16 @@ pub async fn chats_router(
 
             post_thread(user, req).await?;
         }
+        ChatsRoute::Cancel => {
+            let req = serde_json::from_value(data)?;
+
+            cancel_chat(user, req).await?;
+        }
+        ChatsRoute::Resume => {
+            let req = serde_json::from_value(data)?;
+
+            resume_chat(user, req).await?;
+        }
     };
 
     Ok(())
@@ -78,6 +96,8 @@ impl ChatsRoute {
     pub fn from_str(path: &str) -> Result<Self> {
         match path {
             "/chats/post" => Ok(Self::Post),
+            "/chats/cancel" => Ok(Self::Cancel),
+            "/chats/resume" => Ok(Self::Resume),
             _ => Err(anyhow!("Invalid path")),
         }
     }
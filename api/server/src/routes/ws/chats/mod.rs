@@ -1,2 +1,4 @@
+mod cancel_chat;
 mod post_chat;
+mod resume_chat;
 pub mod chats_router;
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use handlers::chats::post_chat_handler::ChatCreateNewChat;
-use handlers::chats::post_chat_handler::{self, ThreadEvent};
+use handlers::chats::chat_stream::ChatStreamRecorder;
+use handlers::chats::post_chat_handler::{self, BusterContainer, ThreadEvent};
 use middleware::AuthenticatedUser;
 use tokio::sync::mpsc;
 
@@ -36,41 +37,31 @@ pub async fn post_thread(
     }
 
     // Create channel for streaming results
-    let (tx, mut rx) = mpsc::channel(1000);
+    let (tx, mut rx) = mpsc::channel::<Result<(BusterContainer, ThreadEvent)>>(1000);
 
     let user_id = user.id.to_string();
     let user_clone = user.clone();
 
     // Spawn task to process streaming results
     tokio::spawn(async move {
+        let mut recorder = ChatStreamRecorder::new();
+        let mut ended = false;
+
         while let Some(result) = rx.recv().await {
             match result {
                 Ok((container, event)) => {
-                    let event = match event {
-                        ThreadEvent::GeneratingResponseMessage => {
-                            WsEvent::Threads(WSThreadEvent::GeneratingResponseMessage)
-                        }
-                        ThreadEvent::GeneratingReasoningMessage => {
-                            WsEvent::Threads(WSThreadEvent::GeneratingReasoningMessage)
-                        }
-                        ThreadEvent::GeneratingTitle => {
-                            WsEvent::Threads(WSThreadEvent::GeneratingTitle)
-                        }
-                        ThreadEvent::InitializeChat => {
-                            WsEvent::Threads(WSThreadEvent::InitializeChat)
-                        }
-                        ThreadEvent::Completed => {
-                            WsEvent::Threads(WSThreadEvent::Complete)
-                        }
-                    };
+                    // Recorded first so the client only sees ids it can resume from
+                    let sequence_id = recorder.record(&container, event).await;
+                    ended = event == ThreadEvent::Completed;
 
                     let response = WsResponseMessage::new_no_user(
                         WsRoutes::Chats(ChatsRoute::Post),
-                        event,
+                        ws_thread_event(event),
                         &container,
                         None,
                         WsSendMethod::All,
-                    );
+                    )
+                    .with_sequence_id(sequence_id);
 
                     if let Err(e) = send_ws_message(&user_id, &response).await {
                         tracing::error!("Failed to send websocket message: {}", e);
@@ -79,7 +70,9 @@ pub async fn post_thread(
                 }
                 Err(err) => {
                     tracing::error!("Error in message stream: {:?}", err);
-                    
+                    recorder.record_failure(&err.to_string()).await;
+                    ended = true;
+
                     // Send error message to client
                     if let Err(e) = send_error_message(
                         &user_id,
@@ -97,6 +90,14 @@ pub async fn post_thread(
             }
         }
 
+        // The handler failed before its stream finished; mark the stream as
+        // ended so resuming clients stop waiting for more events
+        if !ended {
+            recorder
+                .record_failure("Chat generation ended before completing")
+                .await;
+        }
+
         Ok::<(), anyhow::Error>(())
     });
 
@@ -110,14 +111,35 @@ pub async fn post_thread(
             Ok(())
         }
         Err(e) => {
+            let (code, message) = if e.to_string().contains("budget exceeded") {
+                (WsErrorCode::PaymentRequired, "Monthly LLM budget exceeded".to_string())
+            } else {
+                (WsErrorCode::InternalServerError, format!("Error creating thread: {}", e))
+            };
+
             send_error_message(
                 &user.id.to_string(),
                 WsRoutes::Chats(ChatsRoute::Post),
                 WsEvent::Threads(WSThreadEvent::PostThread),
-                WsErrorCode::InternalServerError,
-                format!("Error creating thread: {}", e),
+                code,
+                message,
                 user,
             ).await
         }
     }
-}
\ No newline at end of file
+}
+
+/// Maps a chat generation event to the event sent over the websocket
+pub(super) fn ws_thread_event(event: ThreadEvent) -> WsEvent {
+    match event {
+        ThreadEvent::GeneratingResponseMessage => {
+            WsEvent::Threads(WSThreadEvent::GeneratingResponseMessage)
+        }
+        ThreadEvent::GeneratingReasoningMessage => {
+            WsEvent::Threads(WSThreadEvent::GeneratingReasoningMessage)
+        }
+        ThreadEvent::GeneratingTitle => WsEvent::Threads(WSThreadEvent::GeneratingTitle),
+        ThreadEvent::InitializeChat => WsEvent::Threads(WSThreadEvent::InitializeChat),
+        ThreadEvent::Completed => WsEvent::Threads(WSThreadEvent::Complete),
+    }
+}
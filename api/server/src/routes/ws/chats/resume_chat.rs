@@ -0,0 +1,129 @@
+use anyhow::Result;
+use handlers::chats::{
+    chat_stream::{resume_chat_stream_handler, ChatStreamRecord},
+    post_chat_handler::ThreadEvent,
+};
+use middleware::AuthenticatedUser;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::routes::ws::{
+    chats::chats_router::{ChatEvent as WSThreadEvent, ChatsRoute},
+    ws::{WsError, WsErrorCode, WsEvent, WsResponseMessage, WsSendMethod},
+    ws_router::WsRoutes,
+    ws_utils::{send_error_message, send_ws_message},
+};
+
+use super::post_chat::ws_thread_event;
+
+#[derive(Debug, Deserialize)]
+pub struct ResumeChatRequest {
+    pub chat_id: Uuid,
+    pub message_id: Uuid,
+    /// The last sequence id the client received; omit to replay the whole
+    /// stream
+    pub last_sequence_id: Option<String>,
+}
+
+/// Replays the stream events of a chat message the client missed while
+/// disconnected
+///
+/// Missed events are resent in order with their sequence ids. Events of a
+/// generation still running keep arriving as usual, so a client may see an
+/// event both replayed and live and should ignore sequence ids it already
+/// has. Once the generation has ended a final `Complete` event carries the
+/// stored chat.
+pub async fn resume_chat(user: &AuthenticatedUser, request: ResumeChatRequest) -> Result<()> {
+    let resume = match resume_chat_stream_handler(
+        &request.chat_id,
+        &request.message_id,
+        request.last_sequence_id.as_deref(),
+        user,
+    )
+    .await
+    {
+        Ok(resume) => resume,
+        Err(e) => {
+            let message = e.to_string();
+            let code = if message.contains("not found") {
+                WsErrorCode::NotFound
+            } else if message.contains("permission") {
+                WsErrorCode::Unauthorized
+            } else if message.contains("Invalid sequence id") {
+                WsErrorCode::BadRequest
+            } else {
+                WsErrorCode::InternalServerError
+            };
+
+            return send_error_message(
+                &user.id.to_string(),
+                WsRoutes::Chats(ChatsRoute::Resume),
+                WsEvent::Threads(WSThreadEvent::ResumeChat),
+                code,
+                message,
+                user,
+            )
+            .await;
+        }
+    };
+
+    let mut last_sequence_id = request.last_sequence_id;
+    for entry in resume.entries {
+        let response = match entry.record {
+            // The stored chat sent below is the final state
+            ChatStreamRecord::Event {
+                event: ThreadEvent::Completed,
+                ..
+            } => {
+                last_sequence_id = Some(entry.sequence_id);
+                continue;
+            }
+            ChatStreamRecord::Event { event, container } => WsResponseMessage::new(
+                WsRoutes::Chats(ChatsRoute::Post),
+                ws_thread_event(event),
+                container,
+                None,
+                user,
+                WsSendMethod::SenderOnly,
+            ),
+            ChatStreamRecord::Failed { error } => {
+                let mut response = WsResponseMessage::new(
+                    WsRoutes::Chats(ChatsRoute::Post),
+                    WsEvent::Threads(WSThreadEvent::PostThread),
+                    serde_json::Value::Null,
+                    None,
+                    user,
+                    WsSendMethod::SenderOnly,
+                );
+                response.error = Some(WsError {
+                    code: WsErrorCode::InternalServerError,
+                    message: format!("Error processing thread: {}", error),
+                });
+                response
+            }
+        };
+
+        last_sequence_id = Some(entry.sequence_id.clone());
+        send_ws_message(
+            &user.id.to_string(),
+            &response.with_sequence_id(Some(entry.sequence_id)),
+        )
+        .await?;
+    }
+
+    if let Some(chat) = resume.chat {
+        let response = WsResponseMessage::new(
+            WsRoutes::Chats(ChatsRoute::Post),
+            WsEvent::Threads(WSThreadEvent::Complete),
+            chat,
+            None,
+            user,
+            WsSendMethod::SenderOnly,
+        )
+        .with_sequence_id(last_sequence_id);
+
+        send_ws_message(&user.id.to_string(), &response).await?;
+    }
+
+    Ok(())
+}
@@ -61,6 +61,7 @@ pub enum WsErrorCode {
     NotFound,
     Unauthorized,
     BadRequest,
+    PaymentRequired,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -83,6 +84,10 @@ pub struct WsResponseMessage {
     pub sent_by: Option<WsSentBy>,
     pub error: Option<WsError>,
     pub send_method: WsSendMethod,
+    /// Position of a chat stream event, used to resume the stream after
+    /// reconnecting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence_id: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -117,6 +122,7 @@ impl WsResponseMessage {
                         name: user.name.as_ref().unwrap_or(&user.email).to_string(),
                     }),
                     send_method,
+                    sequence_id: None,
                 };
             }
         };
@@ -130,6 +136,7 @@ impl WsResponseMessage {
                 name: user.name.as_ref().unwrap_or(&user.email).to_string(),
             }),
             send_method,
+            sequence_id: None,
         }
     }
 
@@ -153,6 +160,7 @@ impl WsResponseMessage {
                     }),
                     sent_by: None,
                     send_method,
+                    sequence_id: None,
                 };
             }
         };
@@ -163,8 +171,14 @@ impl WsResponseMessage {
             error,
             sent_by: None,
             send_method,
+            sequence_id: None,
         }
     }
+
+    pub fn with_sequence_id(mut self, sequence_id: Option<String>) -> Self {
+        self.sequence_id = sequence_id;
+        self
+    }
 }
 
 #[derive(Serialize)]
@@ -11,6 +11,7 @@ use rustyline::DefaultEditor;
 use std::env;
 use std::io::{self, Stdout};
 use std::path::Path;
+use std::sync::Arc;
 use std::time::{Instant, SystemTime};
 use thiserror::Error;
 use tokio::sync::broadcast;
@@ -19,6 +20,7 @@ use std::process::Command;
 
 // --- Agent Imports ---
 use agents::{AgentError, AgentExt, AgentThread, BusterCliAgent};
+use agents::tools::cli_tools::{ChannelConfirmer, CliSandbox};
 
 // Ratatui / Crossterm related imports
 use crossterm::{
@@ -127,7 +129,13 @@ pub async fn run_chat(args: ChatArgs) -> Result<()> {
     // --- Agent Initialization ---
     let user_id = Uuid::new_v4();
     let session_id = Uuid::new_v4();
-    let cli_agent = BusterCliAgent::new(user_id, session_id, api_key, base_url, Some(cwd.clone())).await
+    // CLI tools stay inside the working directory; destructive commands are
+    // sent to the UI for confirmation
+    let (confirmer, mut confirmation_rx) = ChannelConfirmer::new();
+    let sandbox = CliSandbox::new(&cwd)
+        .map_err(|e| ChatError::InitializationError(e.to_string()))?
+        .with_confirmer(Arc::new(confirmer));
+    let cli_agent = BusterCliAgent::new(user_id, session_id, api_key, base_url, Some(cwd.clone()), Arc::new(sandbox)).await
         .map_err(|e| ChatError::InitializationError(format!("Failed to create agent: {}", e)))?;
 
     // --- Terminal Setup ---
@@ -197,6 +205,13 @@ pub async fn run_chat(args: ChatArgs) -> Result<()> {
             }
         }
 
+        // Show the next command waiting for confirmation
+        if app_state.pending_confirmation.is_none() {
+            if let Ok(request) = confirmation_rx.try_recv() {
+                app_state.pending_confirmation = Some(request);
+            }
+        }
+
         // If agent finished processing during this tick, update the canonical agent thread state
         if agent_finished_processing_this_tick {
              // This await might briefly block the UI update if `get_current_thread` is slow,
@@ -229,6 +244,15 @@ pub async fn run_chat(args: ChatArgs) -> Result<()> {
                             app_state.should_quit = true;
                             continue; // Skip further processing
                         }
+                        // --- Command Confirmation (takes over the keyboard until answered) ---
+                        if app_state.pending_confirmation.is_some() {
+                            match key.code {
+                                KeyCode::Char('y') | KeyCode::Char('Y') => app_state.answer_confirmation(true),
+                                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app_state.answer_confirmation(false),
+                                _ => {}
+                            }
+                            continue;
+                        }
                         if key.code == KeyCode::Esc {
                             // Escape only cancels completion mode if active
                             if app_state.is_completing {
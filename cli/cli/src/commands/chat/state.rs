@@ -1,4 +1,5 @@
 use agents::{AgentError, AgentThread};
+use agents::tools::cli_tools::ConfirmationRequest;
 use crate::commands::chat::completion; // Add import for completion logic
 use litellm::{AgentMessage, MessageProgress, ToolCall};
 use uuid::Uuid;
@@ -52,6 +53,8 @@ pub struct AppState {
     pub current_error: Option<String>,
     pub agent_thread: AgentThread,
     pub is_agent_processing: bool,
+    // Destructive command waiting for the user to approve with y/n
+    pub pending_confirmation: Option<ConfirmationRequest>,
 
     // --- Autocompletion State ---
     pub is_completing: bool,
@@ -76,6 +79,7 @@ impl AppState {
             current_error: None,
             agent_thread: AgentThread::new(Some(session_id), user_id, vec![]),
             is_agent_processing: false,
+            pending_confirmation: None,
 
             // --- Autocompletion State ---
             is_completing: false,
@@ -100,6 +104,22 @@ impl AppState {
         }
     }
 
+    /// Answers the pending command confirmation and logs the decision
+    pub fn answer_confirmation(&mut self, approved: bool) {
+        if let Some(request) = self.pending_confirmation.take() {
+            let message = if approved {
+                format!("Approved: {}", request.command)
+            } else {
+                format!("Denied: {}", request.command)
+            };
+            self.display_log.push(DisplayLogEntry::Info {
+                timestamp: SystemTime::now(),
+                message,
+            });
+            request.respond(approved);
+        }
+    }
+
     pub fn process_agent_message(&mut self, msg_result: Result<AgentMessage, AgentError>) {
         match msg_result {
             Ok(msg) => {
@@ -20,6 +20,7 @@ pub fn ui(frame: &mut Frame, app: &mut AppState, cwd: &str) {
     let status_height = if app.active_tool_calls.is_empty()
         && !app.is_agent_processing
         && app.current_error.is_none()
+        && app.pending_confirmation.is_none()
     {
         0
     } else {
@@ -27,7 +28,8 @@ pub fn ui(frame: &mut Frame, app: &mut AppState, cwd: &str) {
         let tool_call_lines = app.active_tool_calls.len() as u16;
         let thinking_line = if app.is_agent_processing && app.active_tool_calls.is_empty() { 1 } else { 0 };
         let error_line = if app.current_error.is_some() { 1 } else { 0 };
-        tool_call_lines + thinking_line + error_line + 1 // +1 border
+        let confirmation_lines = if app.pending_confirmation.is_some() { 2 } else { 0 };
+        tool_call_lines + thinking_line + error_line + confirmation_lines + 1 // +1 border
     };
 
     let main_chunks = Layout::default()
@@ -325,6 +327,17 @@ fn render_status(frame: &mut Frame, app: &AppState, area: Rect) {
         ))));
     }
 
+    if let Some(request) = &app.pending_confirmation {
+        status_items.push(ListItem::new(Line::from(vec![
+            Span::styled("Run command? ", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            Span::styled(request.command.clone(), Style::default().fg(Color::White)),
+        ])));
+        status_items.push(ListItem::new(Line::from(vec![
+            Span::styled(format!("{} ", request.reason), Style::default().fg(Color::DarkGray)),
+            Span::styled("[y/n]", Style::default().fg(Color::Yellow)),
+        ])));
+    }
+
     if let Some(err) = &app.current_error {
         status_items.push(ListItem::new(Line::from(Span::styled(
             err,
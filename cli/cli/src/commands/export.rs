@@ -0,0 +1,78 @@
+use anyhow::{anyhow, Result};
+use colored::*;
+use indexmap::IndexMap;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+use crate::utils::{
+    buster::{BusterClient, ExportDashboardPdfRequest},
+    file::buster_credentials::get_and_validate_buster_credentials,
+};
+
+/// Downloads a PDF rendering of a dashboard and writes it to `output`
+/// (defaults to `<dashboard_id>.pdf` in the current directory).
+pub async fn export_dashboard(
+    dashboard_id: Uuid,
+    output: Option<&str>,
+    version_number: Option<i32>,
+    variables: Vec<(String, String)>,
+) -> Result<()> {
+    let creds = get_and_validate_buster_credentials().await?;
+    let client = BusterClient::new(creds.url, creds.api_key)?;
+
+    println!("Exporting dashboard {}...", dashboard_id.to_string().cyan());
+
+    let pdf = client
+        .export_dashboard_pdf(
+            dashboard_id,
+            ExportDashboardPdfRequest {
+                version_number,
+                variables: variables.into_iter().collect::<IndexMap<_, _>>(),
+            },
+        )
+        .await?;
+
+    let path = output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(format!("{}.pdf", dashboard_id)));
+    tokio::fs::write(&path, &pdf)
+        .await
+        .map_err(|e| anyhow!("Failed to write {}: {}", path.display(), e))?;
+
+    println!(
+        "{} Saved {} ({} KB)",
+        "✓".green(),
+        path.display(),
+        pdf.len().div_ceil(1024)
+    );
+    Ok(())
+}
+
+/// Parses `key=value` pairs passed via `--var`.
+pub fn parse_variable(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!("invalid variable '{}', expected key=value", arg)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_variable() {
+        assert_eq!(
+            parse_variable("region=EMEA"),
+            Ok(("region".to_string(), "EMEA".to_string()))
+        );
+        assert_eq!(
+            parse_variable("period = 2024-Q3"),
+            Ok(("period".to_string(), "2024-Q3".to_string()))
+        );
+        assert!(parse_variable("=oops").is_err());
+        assert!(parse_variable("missing").is_err());
+    }
+}
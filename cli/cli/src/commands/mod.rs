@@ -1,5 +1,6 @@
 pub mod auth;
 pub mod deploy;
+pub mod export;
 pub mod generate;
 pub mod init;
 pub mod update;
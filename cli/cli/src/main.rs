@@ -7,6 +7,7 @@ use clap::{Parser, Subcommand};
 use colored::*;
 use commands::{auth::AuthArgs, deploy, init, auth::check_authentication};
 use utils::updater::check_for_updates;
+use uuid::Uuid;
 
 pub const APP_NAME: &str = "buster";
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -83,6 +84,20 @@ pub enum Commands {
         #[arg(long, env = "OPENAI_API_KEY")]
         api_key: Option<String>,
     },
+    /// Export a dashboard as a PDF document
+    ExportDashboard {
+        /// The ID of the dashboard to export
+        dashboard_id: Uuid,
+        /// Path of the PDF file to write (defaults to <dashboard_id>.pdf)
+        #[arg(long, short)]
+        output: Option<String>,
+        /// Export a specific version instead of the latest one
+        #[arg(long)]
+        version: Option<i32>,
+        /// Variable value to print in the document header, as key=value (repeatable)
+        #[arg(long = "var", value_parser = commands::export::parse_variable)]
+        variables: Vec<(String, String)>,
+    },
 }
 
 #[derive(Parser)]
@@ -179,6 +194,16 @@ async fn main() {
                 api_key,
             }).await
         }.await,
+        Commands::ExportDashboard {
+            dashboard_id,
+            output,
+            version,
+            variables,
+        } => async move {
+            check_authentication().await?;
+            commands::export::export_dashboard(dashboard_id, output.as_deref(), version, variables)
+                .await
+        }.await,
     };
 
     if let Err(e) = result {
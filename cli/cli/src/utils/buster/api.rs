@@ -4,10 +4,12 @@ use reqwest::{
     Client,
 };
 use std::error::Error as StdError;
+use std::time::Duration;
+use uuid::Uuid;
 
 use super::{
-    DeployDatasetsRequest, DeployDatasetsResponse, GenerateApiRequest, GenerateApiResponse,
-    PostDataSourcesRequest, ValidateApiKeyRequest, ValidateApiKeyResponse,
+    DeployDatasetsRequest, DeployDatasetsResponse, ExportDashboardPdfRequest, GenerateApiRequest,
+    GenerateApiResponse, PostDataSourcesRequest, ValidateApiKeyRequest, ValidateApiKeyResponse,
 };
 
 pub struct BusterClient {
@@ -191,4 +193,45 @@ impl BusterClient {
             )),
         }
     }
+
+    pub async fn export_dashboard_pdf(
+        &self,
+        dashboard_id: Uuid,
+        req_body: ExportDashboardPdfRequest,
+    ) -> Result<Vec<u8>> {
+        let headers = self.build_headers()?;
+
+        // Rendering runs every tile's query, so allow more time than the client default.
+        match self
+            .client
+            .post(format!(
+                "{}/api/v1/dashboards/{}/export/pdf",
+                self.base_url, dashboard_id
+            ))
+            .headers(headers)
+            .timeout(Duration::from_secs(300))
+            .json(&req_body)
+            .send()
+            .await
+        {
+            Ok(res) => {
+                if !res.status().is_success() {
+                    let status = res.status();
+                    let body = res.text().await?;
+                    return Err(anyhow::anyhow!(
+                        "POST /api/v1/dashboards/{}/export/pdf failed with status {}: {}",
+                        dashboard_id,
+                        status,
+                        body
+                    ));
+                }
+                Ok(res.bytes().await?.to_vec())
+            }
+            Err(e) => Err(anyhow::anyhow!(
+                "POST /api/v1/dashboards/{}/export/pdf request failed: {}",
+                dashboard_id,
+                e
+            )),
+        }
+    }
 }
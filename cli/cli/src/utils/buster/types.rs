@@ -1,3 +1,4 @@
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use std::collections::HashMap;
@@ -116,3 +117,9 @@ pub struct GenerateApiError {
     pub error_type: Option<String>,
     pub context: Option<String>,
 }
+
+#[derive(Debug, Serialize)]
+pub struct ExportDashboardPdfRequest {
+    pub version_number: Option<i32>,
+    pub variables: IndexMap<String, String>,
+}